@@ -210,6 +210,58 @@ pub struct McpServer {
     pub tags: Vec<String>,
 }
 
+/// Claude Code hook 条目（对应 settings.json 中 `hooks.<event>[]` 的一组 matcher）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HookEntry {
+    pub id: String,
+    pub name: String,
+    /// Claude 事件名，如 `PreToolUse`；写入 Gemini 时按映射改名
+    pub event: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    /// 原样写入的 hook 列表（`{"type": "command", "command": ...}`）
+    pub hooks: Vec<serde_json::Value>,
+    pub apps: McpApps,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 权限规则集（对应 settings.json 中 `permissions.allow/deny/ask`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PermissionRuleSet {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ask: Vec<String>,
+    pub apps: McpApps,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Claude 状态栏命令（对应 settings.json 中的 `statusLine`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatusLineConfig {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<u32>,
+}
+
+/// hooks / 权限规则 / 状态栏的统一存储（主库 settings 表中的一个 JSON 值）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentSettingsResources {
+    #[serde(default)]
+    pub hooks: Vec<HookEntry>,
+    #[serde(default)]
+    pub permissions: Vec<PermissionRuleSet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_line: Option<StatusLineConfig>,
+}
+
 /// MCP 配置：单客户端维度（v3.6.x 及以前，保留用于向后兼容）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct McpConfig {
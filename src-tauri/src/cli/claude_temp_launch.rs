@@ -66,7 +66,8 @@ where
     Resolve: FnOnce() -> Result<PathBuf, AppError>,
 {
     let executable = resolve_claude_binary()?;
-    let normalized_settings = normalize_launch_settings(provider_id, settings)?;
+    let settings = crate::secret_ref::resolve_settings(settings)?;
+    let normalized_settings = normalize_launch_settings(provider_id, &settings)?;
     let settings_path = write_temp_settings_file(temp_dir, provider_id, &normalized_settings)?;
 
     Ok(PreparedClaudeLaunch {
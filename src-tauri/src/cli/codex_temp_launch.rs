@@ -46,7 +46,8 @@ where
             format!("Failed to resolve current cc-switch executable path: {err}"),
         )
     })?;
-    let codex_home = write_temp_codex_home(temp_dir, provider)?;
+    let resolved = crate::secret_ref::resolve_provider(provider)?;
+    let codex_home = write_temp_codex_home(temp_dir, &resolved)?;
     Ok(PreparedCodexLaunch {
         executable,
         cc_switch_executable,
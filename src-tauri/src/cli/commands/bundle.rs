@@ -0,0 +1,386 @@
+//! `cc-switch bundle`: share providers, MCP servers, prompts and skills as a
+//! portable JSON/YAML file.
+//!
+//! See [`crate::services::bundle`] for the format and how credentials are
+//! protected.
+
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use zeroize::Zeroizing;
+
+use super::vault::read_passphrase;
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::services::bundle::{
+    self, BundleFileFormat, BundleSelection, ConflictPolicy, ImportOutcome, SecretMode,
+};
+use crate::store::AppState;
+
+const BUNDLE_AFTER_LONG_HELP: &str = "\
+Providers and prompts are selected as [app:]id; ids without an app use the
+global --app flag (default: claude). Skills are referenced by their install
+key (owner/repo:directory) or directory and reinstalled from their repository.
+
+Credentials never leave the machine in plain text:
+  --secrets placeholder  replace them with env:CC_SWITCH_... references (default)
+  --secrets strip        leave them empty
+  --secrets encrypt      encrypt them with a shared passphrase
+                         (prompted, or read from CC_SWITCH_BUNDLE_PASSPHRASE)
+
+Examples:
+  cc-switch bundle export team.yaml --provider relay --provider codex:relay --mcp github
+  cc-switch bundle export team.yaml --all --secrets encrypt
+  cc-switch bundle inspect team.yaml
+  cc-switch bundle import team.yaml --on-conflict rename
+
+Credential references (env:, file:, cmd:) read environment variables, files or
+command output when they are resolved, and the result is sent to the bundle's
+base URL. Import therefore empties every reference except the env:CC_SWITCH_...
+placeholders export writes, unless --trust-references is given.";
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = BUNDLE_AFTER_LONG_HELP)]
+pub enum BundleCommand {
+    /// Write selected providers, MCP servers, prompts and skills to a bundle
+    Export {
+        /// Output file; `.yaml`/`.yml` writes YAML, anything else JSON
+        file: PathBuf,
+        /// Export every provider, MCP server, prompt and repository skill
+        #[arg(long)]
+        all: bool,
+        /// Provider to include, as [app:]id (repeatable)
+        #[arg(long = "provider", value_name = "[APP:]ID")]
+        providers: Vec<String>,
+        /// MCP server to include (repeatable)
+        #[arg(long = "mcp", value_name = "ID")]
+        mcp_servers: Vec<String>,
+        /// Prompt to include, as [app:]id (repeatable)
+        #[arg(long = "prompt", value_name = "[APP:]ID")]
+        prompts: Vec<String>,
+        /// Skill to include by install key or directory (repeatable)
+        #[arg(long = "skill", value_name = "KEY")]
+        skills: Vec<String>,
+        /// How credentials are written
+        #[arg(long, value_enum, default_value_t = SecretMode::Placeholder)]
+        secrets: SecretMode,
+        /// Short note shown by `bundle inspect`
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Import a bundle into the local setup
+    Import {
+        /// Bundle file
+        file: PathBuf,
+        /// What to do with items whose id already exists
+        #[arg(long, value_enum, default_value_t = ConflictPolicy::Skip)]
+        on_conflict: ConflictPolicy,
+        /// Keep `file:`/`cmd:` credential references; they read files or run
+        /// commands on this machine, so only use this for bundles you trust
+        #[arg(long)]
+        trust_references: bool,
+        /// Output the import report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show what a bundle contains and what already exists locally
+    Inspect {
+        /// Bundle file
+        file: PathBuf,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn execute(cmd: BundleCommand, app: Option<AppType>) -> Result<(), AppError> {
+    let default_app = app.unwrap_or(AppType::Claude);
+    match cmd {
+        BundleCommand::Export {
+            file,
+            all,
+            providers,
+            mcp_servers,
+            prompts,
+            skills,
+            secrets,
+            description,
+        } => {
+            let selection = BundleSelection {
+                all,
+                providers: providers
+                    .iter()
+                    .map(|selector| bundle::parse_app_selector(selector, &default_app))
+                    .collect::<Result<_, _>>()?,
+                mcp_servers,
+                prompts: prompts
+                    .iter()
+                    .map(|selector| bundle::parse_app_selector(selector, &default_app))
+                    .collect::<Result<_, _>>()?,
+                skills,
+            };
+            export(&file, &selection, secrets, description)
+        }
+        BundleCommand::Import {
+            file,
+            on_conflict,
+            trust_references,
+            json,
+        } => import(&file, on_conflict, trust_references, json),
+        BundleCommand::Inspect { file, json } => inspect(&file, json),
+    }
+}
+
+fn bundle_passphrase(confirm: bool) -> Result<Zeroizing<String>, AppError> {
+    match std::env::var(bundle::PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(Zeroizing::new(passphrase)),
+        _ => read_passphrase("Bundle passphrase:", confirm),
+    }
+}
+
+fn export(
+    file: &Path,
+    selection: &BundleSelection,
+    secrets: SecretMode,
+    description: Option<String>,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let passphrase = match secrets {
+        SecretMode::Encrypt => Some(bundle_passphrase(true)?),
+        _ => None,
+    };
+    let exported = bundle::export(
+        &state,
+        selection,
+        secrets,
+        passphrase.as_deref().map(String::as_str),
+        description,
+    )?;
+    let text = bundle::render(&exported, BundleFileFormat::from_path(file))?;
+    crate::config::write_text_file(file, &text)?;
+
+    println!(
+        "{}",
+        success(&format!(
+            "✓ Exported {} provider(s), {} MCP server(s), {} prompt(s) and {} skill(s) to {}",
+            exported.providers.len(),
+            exported.mcp_servers.len(),
+            exported.prompts.len(),
+            exported.skills.len(),
+            file.display()
+        ))
+    );
+    match secrets {
+        SecretMode::Placeholder => {
+            let vars = bundle::required_env_vars(&exported);
+            if !vars.is_empty() {
+                println!(
+                    "{}",
+                    info(&format!(
+                        "Importers set these environment variables: {}",
+                        vars.join(", ")
+                    ))
+                );
+            }
+        }
+        SecretMode::Strip => println!(
+            "{}",
+            info("Credentials were left empty; importers fill them in with `provider edit`.")
+        ),
+        SecretMode::Encrypt => println!(
+            "{}",
+            info("Share the passphrase separately from the bundle.")
+        ),
+    }
+    Ok(())
+}
+
+fn read_bundle(file: &Path) -> Result<bundle::Bundle, AppError> {
+    let text = std::fs::read_to_string(file).map_err(|e| AppError::io(file, e))?;
+    bundle::parse(&text)
+}
+
+fn import(
+    file: &Path,
+    policy: ConflictPolicy,
+    trust_references: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let parsed = read_bundle(file)?;
+    let key = match parsed.secrets {
+        SecretMode::Encrypt => {
+            let passphrase = bundle_passphrase(false)?;
+            Some(bundle::unlock(&parsed, &passphrase)?)
+        }
+        _ => None,
+    };
+
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::Message(format!("Failed to create async runtime: {e}")))?;
+    let report = runtime.block_on(bundle::import(
+        &state,
+        parsed,
+        policy,
+        key.as_ref(),
+        trust_references,
+    ))?;
+
+    if json {
+        println!(
+            "{}",
+            to_json(&report).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Kind", "App", "ID", "Result"]);
+    for item in &report.items {
+        let result = match &item.outcome {
+            ImportOutcome::Added => success("added"),
+            ImportOutcome::Renamed(id) => success(&format!("added as {id}")),
+            ImportOutcome::Overwritten => warning("overwritten"),
+            ImportOutcome::Skipped => "skipped (exists)".to_string(),
+            ImportOutcome::Failed(message) => error(&format!("failed: {message}")),
+        };
+        table.add_row(vec![
+            item.kind.as_str().to_string(),
+            item.app.clone().unwrap_or_else(|| "-".to_string()),
+            item.id.clone(),
+            result,
+        ]);
+    }
+    println!("{table}");
+
+    if !report.env_vars.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "Credentials are read from these environment variables: {}",
+                report.env_vars.join(", ")
+            ))
+        );
+    }
+    if !report.dropped_references.is_empty() {
+        println!(
+            "{}",
+            warning(&format!(
+                "Emptied credential references of {} (they read variables, files or commands on this machine); re-import with --trust-references to keep them.",
+                report.dropped_references.join(", ")
+            ))
+        );
+    }
+    if !report.missing_credentials.is_empty() {
+        println!(
+            "{}",
+            warning(&format!(
+                "Set the credentials of {} with `provider edit` / `mcp edit`.",
+                report.missing_credentials.join(", ")
+            ))
+        );
+    }
+    let failed = report
+        .items
+        .iter()
+        .filter(|item| matches!(item.outcome, ImportOutcome::Failed(_)))
+        .count();
+    if failed > 0 {
+        return Err(AppError::Message(format!(
+            "{failed} item(s) could not be imported"
+        )));
+    }
+    Ok(())
+}
+
+fn inspect(file: &Path, json: bool) -> Result<(), AppError> {
+    let parsed = read_bundle(file)?;
+    let state = AppState::try_new()?;
+    let entries = bundle::entries(&state, &parsed)?;
+    let env_vars = bundle::required_env_vars(&parsed);
+    let references = bundle::references(&parsed);
+
+    if json {
+        let value = serde_json::json!({
+            "version": parsed.version,
+            "createdAt": parsed.created_at,
+            "description": parsed.description,
+            "secrets": parsed.secrets,
+            "keyId": parsed.encryption.as_ref().map(|meta| meta.key_id.clone()),
+            "items": entries,
+            "envVars": env_vars,
+            "references": references,
+        });
+        println!(
+            "{}",
+            to_json(&value).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    println!("{}", highlight(&format!("Bundle {}", file.display())));
+    println!("Created:     {}", parsed.created_at);
+    if let Some(description) = &parsed.description {
+        println!("Description: {description}");
+    }
+    match &parsed.encryption {
+        Some(meta) => println!(
+            "Secrets:     {} (key {})",
+            parsed.secrets.as_str(),
+            meta.key_id
+        ),
+        None => println!("Secrets:     {}", parsed.secrets.as_str()),
+    }
+
+    if entries.is_empty() {
+        println!("{}", info("The bundle is empty."));
+        return Ok(());
+    }
+    let mut table = create_table();
+    table.set_header(vec!["Kind", "App", "ID", "Name", "Secrets", "Local"]);
+    for entry in &entries {
+        table.add_row(vec![
+            entry.kind.as_str().to_string(),
+            entry.app.clone().unwrap_or_else(|| "-".to_string()),
+            entry.id.clone(),
+            entry.name.clone(),
+            entry.secret_fields.to_string(),
+            if entry.exists {
+                warning("exists")
+            } else {
+                "new".to_string()
+            },
+        ]);
+    }
+    println!("{table}");
+    if !env_vars.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "Expects environment variables: {}",
+                env_vars.join(", ")
+            ))
+        );
+    }
+    if !references.is_empty() {
+        println!("Credential references:");
+        for reference in &references {
+            let line = format!(
+                "  {} {}: {}",
+                reference.item, reference.field, reference.reference
+            );
+            if reference.kept {
+                println!("{line}");
+            } else {
+                println!(
+                    "{}",
+                    warning(&format!(
+                        "{line} (emptied on import unless --trust-references)"
+                    ))
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -3,9 +3,14 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::app_config::AppType;
+use crate::cli::commands::config_codex_profiles;
 use crate::cli::commands::config_common;
+use crate::cli::commands::config_health;
+use crate::cli::commands::config_model_watch;
 use crate::cli::commands::config_openclaw;
+use crate::cli::commands::config_otel;
 use crate::cli::commands::config_s3;
+use crate::cli::commands::config_status_line;
 use crate::cli::commands::config_webdav;
 use crate::cli::i18n::texts;
 use crate::cli::ui::{error, highlight, info, success, to_json};
@@ -65,6 +70,26 @@ pub enum ConfigCommand {
     /// Manage S3-compatible sync settings and operations
     #[command(name = "s3", subcommand)]
     S3(config_s3::S3Command),
+
+    /// Manage OpenTelemetry (OTLP/HTTP) export of proxy spans and usage metrics
+    #[command(name = "otel", subcommand)]
+    Otel(config_otel::OtelCommand),
+
+    /// Manage background provider health checks run by the daemon
+    #[command(name = "health-monitor", subcommand)]
+    HealthMonitor(config_health::HealthMonitorCommand),
+
+    /// Manage background refetching of provider model lists by the daemon
+    #[command(name = "model-watch", subcommand)]
+    ModelWatch(config_model_watch::ModelWatchCommand),
+
+    /// Manage Codex providers written as `codex --profile` profiles in config.toml
+    #[command(name = "codex-profiles", subcommand)]
+    CodexProfiles(config_codex_profiles::CodexProfilesCommand),
+
+    /// Manage the Claude Code status line command in settings.json
+    #[command(name = "status-line", subcommand)]
+    StatusLine(config_status_line::StatusLineCommand),
 }
 
 pub fn execute(cmd: ConfigCommand, app: Option<AppType>) -> Result<(), AppError> {
@@ -83,6 +108,11 @@ pub fn execute(cmd: ConfigCommand, app: Option<AppType>) -> Result<(), AppError>
         ConfigCommand::OpenClaw(cmd) => config_openclaw::execute(cmd),
         ConfigCommand::WebDav(cmd) => config_webdav::execute(cmd),
         ConfigCommand::S3(cmd) => config_s3::execute(cmd),
+        ConfigCommand::Otel(cmd) => config_otel::execute(cmd),
+        ConfigCommand::HealthMonitor(cmd) => config_health::execute(cmd),
+        ConfigCommand::ModelWatch(cmd) => config_model_watch::execute(cmd),
+        ConfigCommand::CodexProfiles(cmd) => config_codex_profiles::execute(cmd),
+        ConfigCommand::StatusLine(cmd) => config_status_line::execute(cmd),
     }
 }
 
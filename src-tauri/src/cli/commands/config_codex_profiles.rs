@@ -0,0 +1,115 @@
+use clap::Subcommand;
+
+use crate::cli::ui::{highlight, info, success};
+use crate::error::AppError;
+use crate::services::ProviderService;
+use crate::settings::{get_codex_profiles_settings, set_codex_profiles_settings};
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CodexProfilesCommand {
+    /// Show profile settings and the profiles currently in config.toml
+    Show,
+
+    /// Write every Codex provider as a profile in config.toml
+    Enable {
+        /// Provider id that gets no profile (repeatable; replaces the current list)
+        #[arg(long = "exclude", value_name = "ID")]
+        exclude: Vec<String>,
+    },
+
+    /// Remove the profiles written by cc-switch from config.toml
+    Disable,
+
+    /// Rewrite the profiles from the current providers
+    Sync,
+}
+
+pub fn execute(command: CodexProfilesCommand) -> Result<(), AppError> {
+    match command {
+        CodexProfilesCommand::Show => show(),
+        CodexProfilesCommand::Enable { exclude } => {
+            let mut settings = get_codex_profiles_settings();
+            settings.enabled = true;
+            if !exclude.is_empty() {
+                settings.exclude = exclude;
+            }
+            set_codex_profiles_settings(settings)?;
+            sync()
+        }
+        CodexProfilesCommand::Disable => {
+            let mut settings = get_codex_profiles_settings();
+            settings.enabled = false;
+            set_codex_profiles_settings(settings)?;
+            ProviderService::sync_codex_profiles(&AppState::try_new()?)?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ Codex profiles disabled and removed from config.toml.",
+                    "✓ 已关闭 Codex profile，并从 config.toml 中移除。"
+                ))
+            );
+            Ok(())
+        }
+        CodexProfilesCommand::Sync => sync(),
+    }
+}
+
+fn sync() -> Result<(), AppError> {
+    if !get_codex_profiles_settings().enabled {
+        println!(
+            "{}",
+            info(crate::t!(
+                "Codex profiles are disabled; run `cc-switch config codex-profiles enable` first.",
+                "Codex profile 未开启，请先运行 `cc-switch config codex-profiles enable`。"
+            ))
+        );
+        return Ok(());
+    }
+    let names = ProviderService::sync_codex_profiles(&AppState::try_new()?)?;
+    println!(
+        "{}",
+        success(&crate::t!(
+            format!(
+                "✓ {} Codex profile(s) in config.toml. Use `codex --profile <name>`.",
+                names.len()
+            ),
+            format!(
+                "✓ config.toml 中共有 {} 个 Codex profile，可用 `codex --profile <名称>` 选择。",
+                names.len()
+            )
+        ))
+    );
+    if !names.is_empty() {
+        println!("{}", info(&names.join(", ")));
+    }
+    Ok(())
+}
+
+fn show() -> Result<(), AppError> {
+    let settings = get_codex_profiles_settings();
+    let names = ProviderService::list_codex_profiles()?;
+    println!(
+        "{}",
+        highlight(crate::t!("Codex Profiles", "Codex Profile"))
+    );
+    println!("{}", "═".repeat(60));
+    println!("Enabled:  {}", if settings.enabled { "yes" } else { "no" });
+    println!(
+        "Excluded: {}",
+        if settings.exclude.is_empty() {
+            "-".to_string()
+        } else {
+            settings.exclude.join(", ")
+        }
+    );
+    println!(
+        "Profiles: {}",
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join(", ")
+        }
+    );
+    Ok(())
+}
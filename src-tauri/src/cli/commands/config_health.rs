@@ -0,0 +1,132 @@
+use clap::Subcommand;
+
+use crate::cli::ui::{highlight, success};
+use crate::error::AppError;
+use crate::settings::{
+    get_health_monitor_settings, set_health_monitor_settings, HealthMonitorSettings,
+};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum HealthMonitorCommand {
+    /// Show background health monitor settings
+    Show,
+
+    /// Update background health monitor settings
+    Set {
+        /// Seconds between two checks of the same provider (30-86400)
+        #[arg(long)]
+        interval: Option<u64>,
+
+        /// Check every provider of the failover-capable apps, not only queued ones
+        #[arg(long)]
+        all_providers: Option<bool>,
+
+        /// Consecutive failed checks that open the circuit breaker (0 = never)
+        #[arg(long)]
+        failure_threshold: Option<u32>,
+
+        /// Days of check history to keep (1-365)
+        #[arg(long)]
+        retain_days: Option<u32>,
+
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+
+        #[arg(long, conflicts_with = "enable")]
+        disable: bool,
+    },
+
+    /// Restore default health monitor settings
+    Reset,
+}
+
+pub fn execute(command: HealthMonitorCommand) -> Result<(), AppError> {
+    match command {
+        HealthMonitorCommand::Show => show(),
+        HealthMonitorCommand::Set {
+            interval,
+            all_providers,
+            failure_threshold,
+            retain_days,
+            enable,
+            disable,
+        } => {
+            let mut settings = get_health_monitor_settings();
+            if let Some(value) = interval {
+                settings.interval_secs = value;
+            }
+            if let Some(value) = all_providers {
+                settings.all_providers = value;
+            }
+            if let Some(value) = failure_threshold {
+                settings.failure_threshold = value;
+            }
+            if let Some(value) = retain_days {
+                settings.retain_days = value;
+            }
+            if enable {
+                settings.enabled = true;
+            }
+            if disable {
+                settings.enabled = false;
+            }
+            set_health_monitor_settings(settings)?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ Health monitor settings saved. The daemon picks them up on its next check.",
+                    "✓ 健康监测设置已保存，守护进程会在下一轮检查时生效。"
+                ))
+            );
+            Ok(())
+        }
+        HealthMonitorCommand::Reset => {
+            set_health_monitor_settings(HealthMonitorSettings::default())?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ Health monitor settings reset to defaults.",
+                    "✓ 健康监测设置已恢复默认。"
+                ))
+            );
+            Ok(())
+        }
+    }
+}
+
+fn show() -> Result<(), AppError> {
+    let settings = get_health_monitor_settings();
+    println!(
+        "{}",
+        highlight(crate::t!("Provider Health Monitor", "供应商健康监测"))
+    );
+    println!("{}", "═".repeat(60));
+    println!("Enabled:           {}", yes_no(settings.enabled));
+    println!("Interval:          {}s", settings.interval_secs);
+    println!(
+        "Providers:         {}",
+        if settings.all_providers {
+            "all"
+        } else {
+            "failover queue"
+        }
+    );
+    println!(
+        "Failure Threshold: {}",
+        if settings.failure_threshold == 0 {
+            "off".to_string()
+        } else {
+            settings.failure_threshold.to_string()
+        }
+    );
+    println!("Retain Days:       {}", settings.retain_days);
+    Ok(())
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
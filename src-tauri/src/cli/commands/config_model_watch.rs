@@ -0,0 +1,93 @@
+use clap::Subcommand;
+
+use crate::cli::ui::{highlight, success};
+use crate::error::AppError;
+use crate::settings::{get_model_watch_settings, set_model_watch_settings, ModelWatchSettings};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ModelWatchCommand {
+    /// Show model watch settings
+    Show,
+
+    /// Update model watch settings
+    Set {
+        /// Seconds between two fetches of a provider's model list (900-604800)
+        #[arg(long)]
+        interval: Option<u64>,
+
+        /// Days of model change history to keep (1-365)
+        #[arg(long)]
+        retain_days: Option<u32>,
+
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+
+        #[arg(long, conflicts_with = "enable")]
+        disable: bool,
+    },
+
+    /// Restore default model watch settings
+    Reset,
+}
+
+pub fn execute(command: ModelWatchCommand) -> Result<(), AppError> {
+    match command {
+        ModelWatchCommand::Show => show(),
+        ModelWatchCommand::Set {
+            interval,
+            retain_days,
+            enable,
+            disable,
+        } => {
+            let mut settings = get_model_watch_settings();
+            if let Some(value) = interval {
+                settings.interval_secs = value;
+            }
+            if let Some(value) = retain_days {
+                settings.retain_days = value;
+            }
+            if enable {
+                settings.enabled = true;
+            }
+            if disable {
+                settings.enabled = false;
+            }
+            set_model_watch_settings(settings)?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ Model watch settings saved. The daemon picks them up on its next check.",
+                    "✓ 模型目录监测设置已保存，守护进程会在下一轮检查时生效。"
+                ))
+            );
+            Ok(())
+        }
+        ModelWatchCommand::Reset => {
+            set_model_watch_settings(ModelWatchSettings::default())?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ Model watch settings reset to defaults.",
+                    "✓ 模型目录监测设置已恢复默认。"
+                ))
+            );
+            Ok(())
+        }
+    }
+}
+
+fn show() -> Result<(), AppError> {
+    let settings = get_model_watch_settings();
+    println!(
+        "{}",
+        highlight(crate::t!("Provider Model Watch", "供应商模型目录监测"))
+    );
+    println!("{}", "═".repeat(60));
+    println!(
+        "Enabled:     {}",
+        if settings.enabled { "yes" } else { "no" }
+    );
+    println!("Interval:    {}s", settings.interval_secs);
+    println!("Retain Days: {}", settings.retain_days);
+    Ok(())
+}
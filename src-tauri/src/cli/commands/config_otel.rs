@@ -0,0 +1,220 @@
+use clap::Subcommand;
+
+use crate::cli::ui::{highlight, info, success};
+use crate::error::AppError;
+use crate::settings::{get_otel_export_settings, set_otel_export_settings, OtelExportSettings};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum OtelCommand {
+    /// Show current OpenTelemetry export settings
+    Show,
+
+    /// Create or update OpenTelemetry export settings
+    Set {
+        /// OTLP/HTTP collector base URL (e.g. http://localhost:4318)
+        #[arg(long)]
+        endpoint: Option<String>,
+
+        /// Extra request header as KEY=VALUE (repeatable)
+        #[arg(long = "header", value_parser = parse_header_arg)]
+        headers: Vec<(String, String)>,
+
+        /// Remove a previously configured header (repeatable)
+        #[arg(long)]
+        remove_header: Vec<String>,
+
+        #[arg(long)]
+        service_name: Option<String>,
+
+        /// Export one span per proxied request
+        #[arg(long)]
+        traces: Option<bool>,
+
+        /// Seconds between usage metric pushes (0 disables metrics)
+        #[arg(long)]
+        metrics_interval: Option<u64>,
+
+        /// Spans per export request
+        #[arg(long)]
+        batch_size: Option<usize>,
+
+        /// Spans buffered before new ones are dropped
+        #[arg(long)]
+        queue_capacity: Option<usize>,
+
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+
+        #[arg(long, conflicts_with = "enable")]
+        disable: bool,
+    },
+
+    /// Clear stored OpenTelemetry export settings
+    Clear,
+
+    /// Send a test span to the configured collector
+    Test,
+}
+
+fn parse_header_arg(raw: &str) -> Result<(String, String), String> {
+    let (name, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("invalid header '{raw}', expected KEY=VALUE"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("invalid header '{raw}', expected KEY=VALUE"));
+    }
+    Ok((name.to_string(), value.trim().to_string()))
+}
+
+pub fn execute(command: OtelCommand) -> Result<(), AppError> {
+    match command {
+        OtelCommand::Show => show(),
+        OtelCommand::Set {
+            endpoint,
+            headers,
+            remove_header,
+            service_name,
+            traces,
+            metrics_interval,
+            batch_size,
+            queue_capacity,
+            enable,
+            disable,
+        } => {
+            let mut settings = get_otel_export_settings();
+            if let Some(value) = endpoint {
+                settings.endpoint = value;
+            }
+            for name in remove_header {
+                settings.headers.remove(&name);
+            }
+            settings.headers.extend(headers);
+            if let Some(value) = service_name {
+                settings.service_name = value;
+            }
+            if let Some(value) = traces {
+                settings.traces = value;
+            }
+            if let Some(value) = metrics_interval {
+                settings.metrics_interval_secs = value;
+            }
+            if let Some(value) = batch_size {
+                settings.batch_size = value;
+            }
+            if let Some(value) = queue_capacity {
+                settings.queue_capacity = value;
+            }
+            if enable {
+                settings.enabled = true;
+            }
+            if disable {
+                settings.enabled = false;
+            }
+            if settings.enabled && settings.endpoint.trim().is_empty() {
+                return Err(AppError::InvalidInput(
+                    "set --endpoint before enabling OpenTelemetry export".to_string(),
+                ));
+            }
+            set_otel_export_settings(settings)?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ OpenTelemetry settings saved. Restart the proxy to pick up queue size changes.",
+                    "✓ OpenTelemetry 设置已保存。队列容量变更需重启代理后生效。"
+                ))
+            );
+            Ok(())
+        }
+        OtelCommand::Clear => {
+            set_otel_export_settings(OtelExportSettings::default())?;
+            println!(
+                "{}",
+                success(crate::t!(
+                    "✓ OpenTelemetry settings cleared.",
+                    "✓ OpenTelemetry 设置已清空。"
+                ))
+            );
+            Ok(())
+        }
+        OtelCommand::Test => test(),
+    }
+}
+
+fn show() -> Result<(), AppError> {
+    let settings = get_otel_export_settings();
+    println!(
+        "{}",
+        highlight(crate::t!("OpenTelemetry Export", "OpenTelemetry 导出"))
+    );
+    println!("{}", "═".repeat(60));
+    println!("Enabled:          {}", yes_no(settings.enabled));
+    println!(
+        "Endpoint:         {}",
+        if settings.endpoint.is_empty() {
+            "N/A"
+        } else {
+            settings.endpoint.as_str()
+        }
+    );
+    println!("Service Name:     {}", settings.service_name);
+    println!("Traces:           {}", yes_no(settings.traces));
+    println!(
+        "Metrics Interval: {}",
+        if settings.metrics_interval_secs == 0 {
+            "off".to_string()
+        } else {
+            format!("{}s", settings.metrics_interval_secs)
+        }
+    );
+    println!("Batch Size:       {}", settings.batch_size);
+    println!("Queue Capacity:   {}", settings.queue_capacity);
+    if settings.headers.is_empty() {
+        println!("Headers:          N/A");
+    } else {
+        for (index, name) in settings.headers.keys().enumerate() {
+            let label = if index == 0 { "Headers:" } else { "" };
+            println!("{label:<18}{name}: ********");
+        }
+    }
+    Ok(())
+}
+
+fn test() -> Result<(), AppError> {
+    let settings = get_otel_export_settings();
+    if settings.endpoint.is_empty() {
+        println!(
+            "{}",
+            info(crate::t!(
+                "OpenTelemetry export is not configured.",
+                "OpenTelemetry 导出尚未配置。"
+            ))
+        );
+        return Ok(());
+    }
+    crate::services::global_proxy::initialize_http_client_from_disk_best_effort();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| AppError::Message(format!("failed to create async runtime: {e}")))?;
+    runtime
+        .block_on(crate::services::otel_export::send_test_span(&settings))
+        .map_err(|err| AppError::Message(format!("OTLP export failed: {err}")))?;
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {} {}",
+            crate::t!("Test span accepted by", "测试 span 已被接收："),
+            settings.signal_url("v1/traces")
+        ))
+    );
+    Ok(())
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
@@ -0,0 +1,69 @@
+use clap::Subcommand;
+
+use crate::app_config::StatusLineConfig;
+use crate::cli::ui::{highlight, info, success};
+use crate::error::AppError;
+use crate::services::AgentSettingsService;
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum StatusLineCommand {
+    /// Show the status line command cc-switch writes to Claude settings.json
+    Show,
+
+    /// Set the Claude Code status line command
+    Set {
+        /// Command whose output becomes the status line
+        command: String,
+        /// Horizontal padding in characters
+        #[arg(long)]
+        padding: Option<u32>,
+    },
+
+    /// Stop managing the status line and remove it from settings.json
+    Clear,
+}
+
+pub fn execute(command: StatusLineCommand) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    match command {
+        StatusLineCommand::Show => {
+            let resources = AgentSettingsService::get(&state)?;
+            println!("{}", highlight("Claude Status Line"));
+            println!("{}", "═".repeat(60));
+            match resources.status_line {
+                Some(config) => {
+                    println!("Command: {}", config.command);
+                    println!(
+                        "Padding: {}",
+                        config
+                            .padding
+                            .map(|padding| padding.to_string())
+                            .unwrap_or_else(|| "-".to_string())
+                    );
+                }
+                None => println!(
+                    "{}",
+                    info("Not managed; set one with `cc-switch config status-line set`.")
+                ),
+            }
+            Ok(())
+        }
+        StatusLineCommand::Set { command, padding } => {
+            AgentSettingsService::set_status_line(
+                &state,
+                Some(StatusLineConfig { command, padding }),
+            )?;
+            println!(
+                "{}",
+                success("✓ Status line saved and written to Claude settings.json")
+            );
+            Ok(())
+        }
+        StatusLineCommand::Clear => {
+            AgentSettingsService::set_status_line(&state, None)?;
+            println!("{}", success("✓ Status line removed"));
+            Ok(())
+        }
+    }
+}
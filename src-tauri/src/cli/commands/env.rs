@@ -1,9 +1,13 @@
 use crate::app_config::AppType;
+use crate::cli::commands::start::resolve_provider_selector;
+use crate::cli::shell_env::{provider_env_vars, render_vars, route_through_proxy, ExportFormat};
 use crate::cli::ui::{create_table, error, highlight, info, success};
 use crate::database::Database;
 use crate::error::AppError;
 use crate::services::env_checker;
 use crate::services::local_env_check::{check_local_environment, ToolCheckStatus};
+use crate::services::ProviderService;
+use crate::store::AppState;
 use clap::Subcommand;
 use serde_json::Value;
 use std::path::Path;
@@ -16,6 +20,20 @@ pub enum EnvCommand {
     List,
     /// Check whether supported app CLIs are installed locally
     Tools,
+    /// Print a provider's credentials as environment variables
+    Export {
+        /// Provider ID or exact provider name
+        provider: String,
+        /// Output format
+        #[arg(long = "shell", value_enum, default_value_t = ExportFormat::Bash)]
+        format: ExportFormat,
+        /// Model override for this export
+        #[arg(long)]
+        model: Option<String>,
+        /// Export the provider's own endpoint even while proxy takeover is on
+        #[arg(long)]
+        no_proxy: bool,
+    },
 }
 
 pub fn execute(cmd: EnvCommand, app: Option<AppType>) -> Result<(), AppError> {
@@ -25,7 +43,85 @@ pub fn execute(cmd: EnvCommand, app: Option<AppType>) -> Result<(), AppError> {
         EnvCommand::Check => check_conflicts(app_type),
         EnvCommand::List => list_env_vars(app_type),
         EnvCommand::Tools => check_local_tools(),
+        EnvCommand::Export {
+            provider,
+            format,
+            model,
+            no_proxy,
+        } => {
+            let vars = provider_export_vars(&app_type, &provider, model.as_deref(), !no_proxy)?;
+            print!("{}", render_vars(format, &vars));
+            Ok(())
+        }
+    }
+}
+
+/// Variables that point a process at the provider matched by `selector`.
+///
+/// While proxy takeover is on for `app` and the selector names the current
+/// provider, the variables target the local proxy route instead, so usage
+/// and failover keep working. Takeover always forwards to the current
+/// provider, so any other provider is exported with its own endpoint.
+pub(crate) fn provider_export_vars(
+    app: &AppType,
+    selector: &str,
+    model: Option<&str>,
+    allow_proxy: bool,
+) -> Result<Vec<(String, String)>, AppError> {
+    if matches!(app, AppType::OpenCode | AppType::Hermes | AppType::OpenClaw) {
+        return Err(AppError::localized(
+            "cli.env.export_unsupported_app",
+            format!(
+                "{} 的供应商保存在自身配置中，没有可导出的环境变量",
+                app.as_str()
+            ),
+            format!(
+                "{} keeps its providers in its own config; there are no environment variables to export",
+                app.as_str()
+            ),
+        ));
+    }
+
+    let state = AppState::try_new()?;
+    let providers = ProviderService::list(&state, app.clone())?;
+    let provider = resolve_provider_selector(&providers, selector.trim(), app.as_str())?;
+    let mut vars = provider_env_vars(app, &crate::secret_ref::resolve_provider(&provider)?, model);
+
+    if allow_proxy {
+        let current = ProviderService::current(&state, app.clone())?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| AppError::Message(format!("failed to create async runtime: {e}")))?;
+        let route = runtime
+            .block_on(state.proxy_service.takeover_route_for_app(app))
+            .map_err(AppError::Message)?;
+        match route {
+            Some((origin, codex_base_url)) if current == provider.id => {
+                route_through_proxy(app, &mut vars, &origin, &codex_base_url);
+            }
+            Some(_) => eprintln!(
+                "{}",
+                info(&crate::t!(
+                    format!(
+                        "Proxy takeover forwards to the current provider '{current}'; exporting '{}' with its own endpoint.",
+                        provider.id
+                    ),
+                    format!(
+                        "代理接管只转发到当前供应商 '{current}'，'{}' 将使用其自身端点导出。",
+                        provider.id
+                    )
+                ))
+            ),
+            None => {}
+        }
     }
+
+    vars.push((
+        format!("CC_SWITCH_{}_PROVIDER", app.as_str().to_ascii_uppercase()),
+        provider.id,
+    ));
+    Ok(vars)
 }
 
 fn check_conflicts(app_type: AppType) -> Result<(), AppError> {
@@ -423,4 +519,66 @@ mod tests {
     fn truncate_value_handles_multibyte_text() {
         assert_eq!(super::truncate_value("中文中文中文", 5), "中文...");
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn export_routes_only_the_current_provider_through_takeover() {
+        use crate::app_config::AppType;
+        use crate::provider::Provider;
+        use crate::store::AppState;
+        use crate::test_support::TestEnvGuard;
+        use serde_json::json;
+
+        let temp_home = tempfile::TempDir::new().expect("create temp home");
+        let _env = TestEnvGuard::isolated(temp_home.path());
+        let state = AppState::try_new().expect("create state");
+        for id in ["p1", "p2"] {
+            let provider = Provider::with_id(
+                id.to_string(),
+                id.to_uppercase(),
+                json!({ "env": {
+                    "ANTHROPIC_BASE_URL": format!("https://{id}.example"),
+                    "ANTHROPIC_AUTH_TOKEN": format!("sk-{id}")
+                }}),
+                None,
+            );
+            state.db.save_provider("claude", &provider).expect("save");
+        }
+        state
+            .db
+            .set_current_provider("claude", "p1")
+            .expect("set current");
+
+        let direct = super::provider_export_vars(&AppType::Claude, "p1", None, true).unwrap();
+        assert!(direct.contains(&("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-p1".to_string())));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let mut config = state
+                .db
+                .get_proxy_config_for_app_or_default("claude")
+                .await
+                .unwrap();
+            config.enabled = true;
+            state.db.update_proxy_config_for_app(config).await.unwrap();
+        });
+
+        let proxied = super::provider_export_vars(&AppType::Claude, "P1", None, true).unwrap();
+        assert!(proxied.contains(&(
+            "ANTHROPIC_AUTH_TOKEN".to_string(),
+            "PROXY_MANAGED".to_string()
+        )));
+        assert!(proxied
+            .iter()
+            .any(|(key, value)| key == "ANTHROPIC_BASE_URL" && value.starts_with("http://")));
+        assert!(proxied.contains(&("CC_SWITCH_CLAUDE_PROVIDER".to_string(), "p1".to_string())));
+
+        let other = super::provider_export_vars(&AppType::Claude, "p2", None, true).unwrap();
+        assert!(other.contains(&(
+            "ANTHROPIC_BASE_URL".to_string(),
+            "https://p2.example".to_string()
+        )));
+        let opted_out = super::provider_export_vars(&AppType::Claude, "p1", None, false).unwrap();
+        assert!(opted_out.contains(&("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-p1".to_string())));
+    }
 }
@@ -0,0 +1,335 @@
+//! `cc-switch hook <shell>`: print a shell hook that exports the provider
+//! pinned by the nearest `.cc-switch.toml` whenever the directory changes.
+//!
+//! Install with `eval "$(cc-switch hook zsh)"` (bash/zsh) or
+//! `cc-switch hook fish | source`. On every directory change the hook runs
+//! the hidden `cc-switch internal hook-env <shell>`, which prints the
+//! export/unset statements for the new directory. The names it exported are
+//! remembered in `CC_SWITCH_HOOK_VARS` so leaving a pinned tree unsets them;
+//! values the shell had before entering are kept in `CC_SWITCH_HOOK_SAVED`
+//! and exported again on the way out, like direnv does. Moving around inside
+//! the same pinned tree re-uses the exports until `.cc-switch.toml` changes,
+//! so `cmd:` references are not re-run on every `cd`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use clap::Args;
+
+use crate::app_config::AppType;
+use crate::cli::commands::start::resolve_provider_selector;
+use crate::cli::project_pin::{self, ProjectPin};
+use crate::cli::shell_env::{
+    is_valid_env_name, provider_env_vars, render_export, render_unset, HookShell,
+};
+use crate::error::AppError;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const HOOK_VARS_KEY: &str = "CC_SWITCH_HOOK_VARS";
+const PROJECT_FILE_KEY: &str = "CC_SWITCH_PROJECT_FILE";
+const SAVED_VARS_KEY: &str = "CC_SWITCH_HOOK_SAVED";
+const PIN_STAMP_KEY: &str = "CC_SWITCH_HOOK_PIN";
+
+const HOOK_AFTER_LONG_HELP: &str = "\
+Setup:
+  bash:  echo 'eval \"$(cc-switch hook bash)\"' >> ~/.bashrc
+  zsh:   echo 'eval \"$(cc-switch hook zsh)\"' >> ~/.zshrc
+  fish:  echo 'cc-switch hook fish | source' >> ~/.config/fish/config.fish
+
+Example .cc-switch.toml:
+  [claude]
+  provider = \"acme-relay\"
+  model = \"claude-sonnet-4-5\"
+
+  [codex]
+  provider = \"acme-openai\"";
+
+#[derive(Args, Debug, Clone)]
+#[command(after_long_help = HOOK_AFTER_LONG_HELP)]
+pub struct HookCommand {
+    /// Shell to generate the hook for
+    #[arg(value_enum)]
+    pub shell: HookShell,
+}
+
+pub fn execute(cmd: HookCommand) -> Result<(), AppError> {
+    let exe = std::env::current_exe()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "cc-switch".to_string());
+    print!("{}", hook_script(cmd.shell, &exe));
+    Ok(())
+}
+
+fn hook_script(shell: HookShell, exe: &str) -> String {
+    let exe = format!("'{}'", exe.replace('\'', "'\\''"));
+    match shell {
+        HookShell::Bash => format!(
+            r#"_cc_switch_hook() {{
+  local previous_exit_status=$?
+  if [ "${{_CC_SWITCH_HOOK_PWD:-}}" != "$PWD" ]; then
+    _CC_SWITCH_HOOK_PWD="$PWD"
+    eval "$({exe} internal hook-env bash)"
+  fi
+  return $previous_exit_status
+}}
+if [[ ";${{PROMPT_COMMAND[*]:-}};" != *";_cc_switch_hook;"* ]]; then
+  PROMPT_COMMAND="_cc_switch_hook${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+fi
+"#
+        ),
+        HookShell::Zsh => format!(
+            r#"_cc_switch_hook() {{
+  eval "$({exe} internal hook-env zsh)"
+}}
+typeset -ag chpwd_functions
+if (( ! ${{chpwd_functions[(I)_cc_switch_hook]}} )); then
+  chpwd_functions=(_cc_switch_hook $chpwd_functions)
+fi
+_cc_switch_hook
+"#
+        ),
+        HookShell::Fish => format!(
+            r#"function __cc_switch_hook --on-variable PWD
+    {exe} internal hook-env fish | source
+end
+__cc_switch_hook
+"#
+        ),
+    }
+}
+
+/// Statements moving the shell from what it exported last time to what the
+/// current directory pins. Problems are reported on stderr and never abort
+/// the prompt.
+pub(crate) fn hook_env(shell: HookShell) -> String {
+    let previous: BTreeSet<String> = std::env::var(HOOK_VARS_KEY)
+        .unwrap_or_default()
+        .split(':')
+        .filter(|name| is_valid_env_name(name))
+        .map(str::to_string)
+        .collect();
+    let saved: BTreeMap<String, String> = std::env::var(SAVED_VARS_KEY)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+
+    let vars = match project_pin::discover() {
+        Ok(Some(pin)) => {
+            let stamp = pin_stamp(&pin);
+            if std::env::var(PIN_STAMP_KEY).ok().as_deref() == Some(stamp.as_str()) {
+                return String::new();
+            }
+            let mut vars = pinned_env_vars(&pin);
+            vars.push((PIN_STAMP_KEY.to_string(), stamp));
+            vars
+        }
+        Ok(None) => Vec::new(),
+        Err(err) => {
+            eprintln!("cc-switch: {err}");
+            Vec::new()
+        }
+    };
+    render_transition(shell, &previous, &saved, &vars, |name| {
+        std::env::var(name).ok()
+    })
+}
+
+/// Identifies the pin file and its last edit, so the exports are only
+/// recomputed when entering another tree or after `.cc-switch.toml` changed.
+fn pin_stamp(pin: &ProjectPin) -> String {
+    let modified = std::fs::metadata(&pin.path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos());
+    format!("{}@{modified}", pin.path.display())
+}
+
+fn pinned_env_vars(pin: &ProjectPin) -> Vec<(String, String)> {
+    let state = match AppState::try_new() {
+        Ok(state) => state,
+        Err(err) => {
+            eprintln!("cc-switch: {err}");
+            return Vec::new();
+        }
+    };
+
+    let mut vars = Vec::new();
+    for app in pin.pinned_apps() {
+        let Some(app_pin) = pin.for_app(&app) else {
+            continue;
+        };
+        let selector = app_pin.provider.as_deref().unwrap_or_default().trim();
+        let provider = ProviderService::list(&state, app.clone())
+            .and_then(|providers| {
+                resolve_provider_selector(&providers, selector, app_display_name(&app))
+            })
+            .and_then(|provider| crate::secret_ref::resolve_provider(&provider));
+        match provider {
+            Ok(provider) => {
+                vars.extend(provider_env_vars(&app, &provider, app_pin.model.as_deref()));
+                vars.push((
+                    format!("CC_SWITCH_{}_PROVIDER", app.as_str().to_ascii_uppercase()),
+                    provider.id,
+                ));
+            }
+            Err(err) => eprintln!("cc-switch: {}: {err}", pin.path.display()),
+        }
+    }
+    vars.push((PROJECT_FILE_KEY.to_string(), pin.path.display().to_string()));
+    vars
+}
+
+/// `outer` looks up the shell's current value of a variable; it is saved
+/// before a pinned export replaces it and restored when the pin goes away.
+fn render_transition(
+    shell: HookShell,
+    previous: &BTreeSet<String>,
+    saved: &BTreeMap<String, String>,
+    vars: &[(String, String)],
+    outer: impl Fn(&str) -> Option<String>,
+) -> String {
+    let current: BTreeSet<&str> = vars.iter().map(|(name, _)| name.as_str()).collect();
+    let mut still_saved = saved.clone();
+    let mut lines = Vec::new();
+    for name in previous {
+        if !current.contains(name.as_str()) {
+            match still_saved.remove(name) {
+                Some(value) => lines.push(render_export(shell, name, &value)),
+                None => lines.push(render_unset(shell, name)),
+            }
+        }
+    }
+    for (name, _) in vars {
+        let bookkeeping = name == PIN_STAMP_KEY || name == PROJECT_FILE_KEY;
+        if !bookkeeping && !previous.contains(name) && !still_saved.contains_key(name) {
+            if let Some(value) = outer(name) {
+                still_saved.insert(name.clone(), value);
+            }
+        }
+    }
+    for (name, value) in vars {
+        lines.push(render_export(shell, name, value));
+    }
+    if vars.is_empty() {
+        if !previous.is_empty() {
+            lines.push(render_unset(shell, HOOK_VARS_KEY));
+        }
+    } else {
+        let names = current.into_iter().collect::<Vec<_>>().join(":");
+        lines.push(render_export(shell, HOOK_VARS_KEY, &names));
+    }
+    if !still_saved.is_empty() {
+        let encoded = serde_json::to_string(&still_saved).unwrap_or_default();
+        lines.push(render_export(shell, SAVED_VARS_KEY, &encoded));
+    } else if !saved.is_empty() {
+        lines.push(render_unset(shell, SAVED_VARS_KEY));
+    }
+
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+fn app_display_name(app: &AppType) -> &'static str {
+    match app {
+        AppType::Claude => "Claude",
+        AppType::Codex => "Codex",
+        AppType::Gemini => "Gemini",
+        AppType::OpenCode => "OpenCode",
+        AppType::Hermes => "Hermes",
+        AppType::OpenClaw => "OpenClaw",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_a_pinned_tree_unsets_previous_exports() {
+        let previous = BTreeSet::from([
+            "ANTHROPIC_AUTH_TOKEN".to_string(),
+            PROJECT_FILE_KEY.to_string(),
+        ]);
+
+        assert_eq!(
+            render_transition(HookShell::Bash, &previous, &BTreeMap::new(), &[], |_| None),
+            "unset ANTHROPIC_AUTH_TOKEN;\nunset CC_SWITCH_PROJECT_FILE;\nunset CC_SWITCH_HOOK_VARS;\n"
+        );
+        assert_eq!(
+            render_transition(
+                HookShell::Bash,
+                &BTreeSet::new(),
+                &BTreeMap::new(),
+                &[],
+                |_| None
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn switching_trees_drops_stale_names_and_records_new_ones() {
+        let previous = BTreeSet::from(["OPENAI_API_KEY".to_string()]);
+        let vars = vec![
+            ("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-a".to_string()),
+            (
+                PROJECT_FILE_KEY.to_string(),
+                "/repo/.cc-switch.toml".to_string(),
+            ),
+        ];
+
+        let script = render_transition(HookShell::Fish, &previous, &BTreeMap::new(), &vars, |_| {
+            None
+        });
+        assert_eq!(
+            script,
+            "set -e OPENAI_API_KEY;\n\
+             set -gx ANTHROPIC_AUTH_TOKEN 'sk-a';\n\
+             set -gx CC_SWITCH_PROJECT_FILE '/repo/.cc-switch.toml';\n\
+             set -gx CC_SWITCH_HOOK_VARS 'ANTHROPIC_AUTH_TOKEN:CC_SWITCH_PROJECT_FILE';\n"
+        );
+    }
+
+    #[test]
+    fn values_exported_before_entering_are_restored_on_leaving() {
+        let vars = vec![("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-pinned".to_string())];
+        let entering = render_transition(
+            HookShell::Bash,
+            &BTreeSet::new(),
+            &BTreeMap::new(),
+            &vars,
+            |name| (name == "ANTHROPIC_AUTH_TOKEN").then(|| "sk-mine".to_string()),
+        );
+        assert!(entering.contains("export ANTHROPIC_AUTH_TOKEN='sk-pinned';"));
+        assert!(entering
+            .contains("export CC_SWITCH_HOOK_SAVED='{\"ANTHROPIC_AUTH_TOKEN\":\"sk-mine\"}';"));
+
+        let previous = BTreeSet::from(["ANTHROPIC_AUTH_TOKEN".to_string()]);
+        let saved = BTreeMap::from([("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-mine".to_string())]);
+        // Staying pinned keeps the saved value even though the shell now
+        // holds the pinned one.
+        let staying = render_transition(HookShell::Bash, &previous, &saved, &vars, |_| {
+            Some("sk-pinned".to_string())
+        });
+        assert!(staying.contains("sk-mine"));
+
+        assert_eq!(
+            render_transition(HookShell::Bash, &previous, &saved, &[], |_| None),
+            "export ANTHROPIC_AUTH_TOKEN='sk-mine';\n\
+             unset CC_SWITCH_HOOK_VARS;\n\
+             unset CC_SWITCH_HOOK_SAVED;\n"
+        );
+    }
+
+    #[test]
+    fn hook_scripts_call_back_into_hook_env() {
+        for shell in [HookShell::Bash, HookShell::Zsh, HookShell::Fish] {
+            let script = hook_script(shell, "/opt/cc switch/cc-switch");
+            assert!(script.contains("'/opt/cc switch/cc-switch' internal hook-env"));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! `cc-switch hooks`: Claude Code hooks kept in cc-switch and synced into
+//! `settings.json` of every enabled app (Claude, and Gemini under its own
+//! event names). Hand-written hooks in the live files are left alone.
+
+use clap::Subcommand;
+use serde_json::{json, Value};
+
+use crate::app_config::{AppType, HookEntry, McpApps};
+use crate::cli::commands::app_targets::{app_target_names, app_targets_or_default};
+use crate::cli::ui::{create_table, info, success};
+use crate::error::AppError;
+use crate::services::agent_settings::{generate_id, AgentSettingsImport};
+use crate::services::AgentSettingsService;
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum HooksCommand {
+    /// List hooks and the apps they are synced to
+    List,
+
+    /// Add a hook (or replace the one with the same --id)
+    Add {
+        /// Display name
+        name: String,
+        /// Claude event: PreToolUse, PostToolUse, Notification, UserPromptSubmit, Stop,
+        /// SubagentStop, PreCompact, SessionStart or SessionEnd
+        #[arg(long)]
+        event: String,
+        /// Tool matcher, e.g. `Bash` or `Edit|Write`
+        #[arg(long)]
+        matcher: Option<String>,
+        /// Shell command to run (repeatable)
+        #[arg(long = "command", value_name = "CMD", required = true)]
+        commands: Vec<String>,
+        /// Timeout in seconds for each command
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+        /// Target apps (claude, gemini). Defaults to the global --app.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+        /// Hook id (defaults to one derived from the name)
+        #[arg(long)]
+        id: Option<String>,
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+    },
+
+    /// Remove a hook from cc-switch and from every live settings file
+    Remove {
+        /// Hook id
+        id: String,
+    },
+
+    /// Enable a hook for specific app(s)
+    Enable {
+        /// Hook id
+        id: String,
+        /// Target apps. Accepts repeated values or comma-separated backend ids.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+    },
+
+    /// Disable a hook for specific app(s)
+    Disable {
+        /// Hook id
+        id: String,
+        /// Target apps. Accepts repeated values or comma-separated backend ids.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+    },
+
+    /// Import hooks, permission rules and the status line from live settings
+    Import,
+
+    /// Rewrite hooks, permission rules and the status line into live settings
+    Sync,
+}
+
+pub fn execute(cmd: HooksCommand, app: Option<AppType>) -> Result<(), AppError> {
+    let app_type = app.unwrap_or(AppType::Claude);
+    match cmd {
+        HooksCommand::List => list_hooks(),
+        HooksCommand::Add {
+            name,
+            event,
+            matcher,
+            commands,
+            timeout,
+            apps,
+            id,
+            description,
+        } => {
+            let state = AppState::try_new()?;
+            let targets = app_targets_or_default(&apps, app_type, "Hooks")?;
+            let mut enabled = McpApps::default();
+            for target in &targets {
+                enabled.set_enabled_for(target, true);
+            }
+            let id = match id {
+                Some(id) => id,
+                None => {
+                    let resources = AgentSettingsService::get(&state)?;
+                    let taken: Vec<&str> = resources
+                        .hooks
+                        .iter()
+                        .map(|hook| hook.id.as_str())
+                        .collect();
+                    generate_id(&taken, &name, "hook")
+                }
+            };
+            let hooks = commands
+                .into_iter()
+                .map(|command| {
+                    let mut hook = json!({ "type": "command", "command": command });
+                    if let Some(timeout) = timeout {
+                        hook["timeout"] = json!(timeout);
+                    }
+                    hook
+                })
+                .collect();
+            AgentSettingsService::upsert_hook(
+                &state,
+                HookEntry {
+                    id: id.clone(),
+                    name,
+                    event,
+                    matcher,
+                    hooks,
+                    apps: enabled,
+                    description,
+                },
+            )?;
+            println!(
+                "{}",
+                success(&format!(
+                    "✓ Saved hook '{id}' for {}",
+                    app_target_names(&targets)
+                ))
+            );
+            Ok(())
+        }
+        HooksCommand::Remove { id } => {
+            if !AgentSettingsService::delete_hook(&AppState::try_new()?, &id)? {
+                return Err(AppError::Message(format!("Hook '{id}' not found")));
+            }
+            println!("{}", success(&format!("✓ Removed hook '{id}'")));
+            Ok(())
+        }
+        HooksCommand::Enable { id, apps } => set_enabled(app_type, &id, &apps, true),
+        HooksCommand::Disable { id, apps } => set_enabled(app_type, &id, &apps, false),
+        HooksCommand::Import => import(),
+        HooksCommand::Sync => sync(),
+    }
+}
+
+fn set_enabled(
+    app_type: AppType,
+    id: &str,
+    raw_apps: &[String],
+    enabled: bool,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let apps = app_targets_or_default(raw_apps, app_type, "Hooks")?;
+    for app in &apps {
+        if !AgentSettingsService::toggle_hook_app(&state, id, app, enabled)? {
+            return Err(AppError::Message(format!("Hook '{id}' not found")));
+        }
+    }
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {} hook '{id}' for {}",
+            if enabled { "Enabled" } else { "Disabled" },
+            app_target_names(&apps)
+        ))
+    );
+    Ok(())
+}
+
+fn list_hooks() -> Result<(), AppError> {
+    let resources = AgentSettingsService::get(&AppState::try_new()?)?;
+    if resources.hooks.is_empty() {
+        println!("{}", info("No hooks found."));
+        println!("Use 'cc-switch hooks add' or 'cc-switch hooks import' to add hooks.");
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec![
+        "ID", "Name", "Event", "Matcher", "Command", "Claude", "Gemini",
+    ]);
+    for hook in &resources.hooks {
+        let commands = hook
+            .hooks
+            .iter()
+            .map(|hook| {
+                hook.get("command")
+                    .and_then(Value::as_str)
+                    .unwrap_or("-")
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        table.add_row(vec![
+            hook.id.clone(),
+            hook.name.clone(),
+            hook.event.clone(),
+            hook.matcher.clone().unwrap_or_else(|| "*".to_string()),
+            commands,
+            if hook.apps.claude { "✓" } else { " " }.to_string(),
+            if hook.apps.gemini { "✓" } else { " " }.to_string(),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+pub(crate) fn import() -> Result<(), AppError> {
+    let summary = AgentSettingsService::import_from_live(&AppState::try_new()?)?;
+    print_import_summary(&summary);
+    Ok(())
+}
+
+pub(crate) fn sync() -> Result<(), AppError> {
+    AgentSettingsService::sync(&AppState::try_new()?)?;
+    println!(
+        "{}",
+        success("✓ Hooks, permission rules and status line synced to live settings")
+    );
+    Ok(())
+}
+
+fn print_import_summary(summary: &AgentSettingsImport) {
+    if *summary == AgentSettingsImport::default() {
+        println!(
+            "{}",
+            info("Nothing new to import from Claude or Gemini settings.")
+        );
+        return;
+    }
+    println!(
+        "{}",
+        success(&format!(
+            "✓ Imported {} hook(s), {} permission rule(s){}",
+            summary.hooks,
+            summary.permission_rules,
+            if summary.status_line {
+                " and the status line"
+            } else {
+                ""
+            }
+        ))
+    );
+}
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use clap::Subcommand;
 
+use crate::cli::shell_env::HookShell;
 use crate::error::AppError;
 use crate::services::ProviderService;
 use crate::store::AppState;
@@ -13,6 +14,11 @@ pub enum InternalCommand {
         provider_id: String,
         codex_home: PathBuf,
     },
+    /// Print the export/unset statements `cc-switch hook` evaluates on cd.
+    HookEnv {
+        #[arg(value_enum)]
+        shell: HookShell,
+    },
 }
 
 pub fn execute(cmd: InternalCommand) -> Result<(), AppError> {
@@ -24,5 +30,9 @@ pub fn execute(cmd: InternalCommand) -> Result<(), AppError> {
             let state = AppState::try_new()?;
             ProviderService::capture_codex_temp_launch_snapshot(&state, &provider_id, &codex_home)
         }
+        InternalCommand::HookEnv { shell } => {
+            print!("{}", super::hook::hook_env(shell));
+            Ok(())
+        }
     }
 }
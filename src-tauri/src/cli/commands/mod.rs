@@ -1,10 +1,16 @@
 pub(crate) mod app_targets;
 pub mod auth;
+pub mod bundle;
 pub mod completions;
 pub mod config;
+pub mod config_codex_profiles;
 mod config_common;
+pub mod config_health;
+pub mod config_model_watch;
 pub(crate) mod config_openclaw;
+pub mod config_otel;
 pub mod config_s3;
+pub mod config_status_line;
 pub mod config_webdav;
 #[cfg(unix)]
 pub mod daemon;
@@ -12,16 +18,31 @@ pub mod deeplink;
 pub mod env;
 pub mod failover;
 pub mod hermes;
+pub mod hook;
+pub mod hooks;
 pub mod internal;
 pub mod mcp;
+pub mod permissions;
+pub mod profile;
 pub mod prompts;
 pub mod provider;
+pub(crate) mod provider_history;
+pub(crate) mod provider_import;
 pub mod provider_input;
-mod provider_inspect;
+pub(crate) mod provider_inspect;
+pub mod provider_keys;
+pub(crate) mod provider_lint;
+pub mod provider_models;
+pub mod provider_presets;
+pub mod provider_rules;
 pub mod provider_usage_query;
 pub mod proxy;
+pub mod run;
 pub mod sessions;
 pub mod settings;
 pub mod skills;
 pub mod start;
+pub mod universal;
 pub mod update;
+pub mod usage;
+pub mod vault;
@@ -0,0 +1,190 @@
+//! `cc-switch permissions`: named sets of Claude Code permission rules
+//! (`permissions.allow/deny/ask` in settings.json) that can be turned on and
+//! off without touching rules written by hand.
+
+use clap::Subcommand;
+
+use crate::app_config::{AppType, McpApps, PermissionRuleSet};
+use crate::cli::commands::app_targets::{app_target_names, app_targets_or_default};
+use crate::cli::commands::hooks;
+use crate::cli::ui::{create_table, info, success};
+use crate::error::AppError;
+use crate::services::agent_settings::generate_id;
+use crate::services::AgentSettingsService;
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum PermissionsCommand {
+    /// List permission rule sets
+    List,
+
+    /// Add a rule set (or replace the one with the same --id)
+    Add {
+        /// Display name
+        name: String,
+        /// Rule to allow without asking, e.g. `Bash(npm run test:*)` (repeatable)
+        #[arg(long, value_name = "RULE")]
+        allow: Vec<String>,
+        /// Rule to always refuse, e.g. `Read(./.env)` (repeatable)
+        #[arg(long, value_name = "RULE")]
+        deny: Vec<String>,
+        /// Rule to always confirm (repeatable)
+        #[arg(long, value_name = "RULE")]
+        ask: Vec<String>,
+        /// Target apps. Only claude has permission rules.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+        /// Rule set id (defaults to one derived from the name)
+        #[arg(long)]
+        id: Option<String>,
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+    },
+
+    /// Remove a rule set from cc-switch and from live settings
+    Remove {
+        /// Rule set id
+        id: String,
+    },
+
+    /// Enable a rule set for specific app(s)
+    Enable {
+        /// Rule set id
+        id: String,
+        /// Target apps. Accepts repeated values or comma-separated backend ids.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+    },
+
+    /// Disable a rule set for specific app(s)
+    Disable {
+        /// Rule set id
+        id: String,
+        /// Target apps. Accepts repeated values or comma-separated backend ids.
+        #[arg(long, value_name = "APP[,APP]", value_delimiter = ',', num_args = 1)]
+        apps: Vec<String>,
+    },
+
+    /// Import hooks, permission rules and the status line from live settings
+    Import,
+
+    /// Rewrite hooks, permission rules and the status line into live settings
+    Sync,
+}
+
+pub fn execute(cmd: PermissionsCommand, app: Option<AppType>) -> Result<(), AppError> {
+    let app_type = app.unwrap_or(AppType::Claude);
+    match cmd {
+        PermissionsCommand::List => list_sets(),
+        PermissionsCommand::Add {
+            name,
+            allow,
+            deny,
+            ask,
+            apps,
+            id,
+            description,
+        } => {
+            let state = AppState::try_new()?;
+            let targets = app_targets_or_default(&apps, app_type, "Permissions")?;
+            let mut enabled = McpApps::default();
+            for target in &targets {
+                enabled.set_enabled_for(target, true);
+            }
+            let id = match id {
+                Some(id) => id,
+                None => {
+                    let resources = AgentSettingsService::get(&state)?;
+                    let taken: Vec<&str> = resources
+                        .permissions
+                        .iter()
+                        .map(|set| set.id.as_str())
+                        .collect();
+                    generate_id(&taken, &name, "rules")
+                }
+            };
+            AgentSettingsService::upsert_permission_set(
+                &state,
+                PermissionRuleSet {
+                    id: id.clone(),
+                    name,
+                    allow,
+                    deny,
+                    ask,
+                    apps: enabled,
+                    description,
+                },
+            )?;
+            println!(
+                "{}",
+                success(&format!(
+                    "✓ Saved rule set '{id}' for {}",
+                    app_target_names(&targets)
+                ))
+            );
+            Ok(())
+        }
+        PermissionsCommand::Remove { id } => {
+            if !AgentSettingsService::delete_permission_set(&AppState::try_new()?, &id)? {
+                return Err(AppError::Message(format!("Rule set '{id}' not found")));
+            }
+            println!("{}", success(&format!("✓ Removed rule set '{id}'")));
+            Ok(())
+        }
+        PermissionsCommand::Enable { id, apps } => set_enabled(app_type, &id, &apps, true),
+        PermissionsCommand::Disable { id, apps } => set_enabled(app_type, &id, &apps, false),
+        PermissionsCommand::Import => hooks::import(),
+        PermissionsCommand::Sync => hooks::sync(),
+    }
+}
+
+fn set_enabled(
+    app_type: AppType,
+    id: &str,
+    raw_apps: &[String],
+    enabled: bool,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let apps = app_targets_or_default(raw_apps, app_type, "Permissions")?;
+    for app in &apps {
+        if !AgentSettingsService::toggle_permission_app(&state, id, app, enabled)? {
+            return Err(AppError::Message(format!("Rule set '{id}' not found")));
+        }
+    }
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {} rule set '{id}' for {}",
+            if enabled { "Enabled" } else { "Disabled" },
+            app_target_names(&apps)
+        ))
+    );
+    Ok(())
+}
+
+fn list_sets() -> Result<(), AppError> {
+    let resources = AgentSettingsService::get(&AppState::try_new()?)?;
+    if resources.permissions.is_empty() {
+        println!("{}", info("No permission rule sets found."));
+        println!(
+            "Use 'cc-switch permissions add' or 'cc-switch permissions import' to add rule sets."
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["ID", "Name", "Allow", "Deny", "Ask", "Claude"]);
+    for set in &resources.permissions {
+        table.add_row(vec![
+            set.id.clone(),
+            set.name.clone(),
+            set.allow.join("\n"),
+            set.deny.join("\n"),
+            set.ask.join("\n"),
+            if set.apps.claude { "✓" } else { " " }.to_string(),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
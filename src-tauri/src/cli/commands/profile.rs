@@ -0,0 +1,258 @@
+//! `cc-switch profile`: save the current multi-app setup under a name and
+//! switch between saved setups in one step.
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use crate::cli::ui::{create_table, highlight, info, success, to_json};
+use crate::error::AppError;
+use crate::profile::Profile;
+use crate::services::{ProfileChange, ProfileService};
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProfileCommand {
+    /// Save current providers, MCP servers, prompts and skills as a profile
+    /// (overwrites a profile with the same name)
+    Save {
+        /// Profile name, e.g. "work"
+        name: String,
+    },
+    /// List saved profiles
+    List {
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Switch every app to a saved profile, rolling back on failure
+    Apply {
+        /// Profile id or name
+        name: String,
+    },
+    /// Show what `apply` would change (or compare two profiles)
+    Diff {
+        /// Profile id or name
+        name: String,
+        /// Compare against this profile instead of the current setup
+        other: Option<String>,
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete a saved profile
+    Delete {
+        /// Profile id or name
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+pub fn execute(cmd: ProfileCommand) -> Result<(), AppError> {
+    match cmd {
+        ProfileCommand::Save { name } => save(&name),
+        ProfileCommand::List { json } => list(json),
+        ProfileCommand::Apply { name } => apply(&name),
+        ProfileCommand::Diff { name, other, json } => diff(&name, other.as_deref(), json),
+        ProfileCommand::Delete { name, yes } => delete(&name, yes),
+    }
+}
+
+fn get_state() -> Result<AppState, AppError> {
+    AppState::try_new()
+}
+
+fn save(name: &str) -> Result<(), AppError> {
+    let state = get_state()?;
+    let (profile, existed) = ProfileService::save(&state, name)?;
+    let verb = if existed { "Updated" } else { "Saved" };
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {verb} profile '{}' ({})",
+            profile.name, profile.id
+        ))
+    );
+    println!("{}", info(&summary(&profile)));
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileOutput<'a> {
+    #[serde(flatten)]
+    profile: &'a Profile,
+    current_for: Vec<String>,
+}
+
+fn list(json: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let profiles = ProfileService::list(&state)?;
+    let current = ProfileService::current_ids(&state)?;
+    let current_for = |profile: &Profile| {
+        current
+            .iter()
+            .filter(|(_, id)| **id == profile.id)
+            .map(|(scope, _)| scope.clone())
+            .collect::<Vec<_>>()
+    };
+
+    if json {
+        let outputs = profiles
+            .iter()
+            .map(|profile| ProfileOutput {
+                profile,
+                current_for: current_for(profile),
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            to_json(&outputs).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if profiles.is_empty() {
+        println!("{}", info("No profiles saved."));
+        println!(
+            "{}",
+            info("Save the current setup with `cc-switch profile save <NAME>`.")
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["", "ID", "Name", "Providers", "Contents"]);
+    for profile in &profiles {
+        let scopes = current_for(profile);
+        let providers = profile
+            .payload
+            .providers
+            .iter()
+            .map(|(app, id)| format!("{app}={id}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        table.add_row(vec![
+            if scopes.is_empty() { "" } else { "✓" }.to_string(),
+            profile.id.clone(),
+            profile.name.clone(),
+            if providers.is_empty() {
+                "-".to_string()
+            } else {
+                providers
+            },
+            summary(profile),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn apply(name: &str) -> Result<(), AppError> {
+    let state = get_state()?;
+    let profile = ProfileService::get(&state, name)?;
+    let changes = ProfileService::apply(&state, &profile.id)?;
+    if changes.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "Already on profile '{}'; nothing changed.",
+                profile.name
+            ))
+        );
+        return Ok(());
+    }
+
+    print_changes(&changes);
+    println!(
+        "{}",
+        success(&format!(
+            "✓ Switched to profile '{}' ({} change{})",
+            profile.name,
+            changes.len(),
+            if changes.len() == 1 { "" } else { "s" }
+        ))
+    );
+    Ok(())
+}
+
+fn diff(name: &str, other: Option<&str>, json: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let profile = ProfileService::get(&state, name)?;
+    let changes = match other {
+        Some(other) => {
+            let other = ProfileService::get(&state, other)?;
+            ProfileService::diff(&profile.payload, &other.payload)
+        }
+        None => ProfileService::diff_with_current(&state, &profile)?,
+    };
+
+    if json {
+        println!(
+            "{}",
+            to_json(&changes).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        println!("{}", info("No differences."));
+        return Ok(());
+    }
+    print_changes(&changes);
+    Ok(())
+}
+
+fn delete(name: &str, yes: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let profile = ProfileService::get(&state, name)?;
+    if !yes {
+        let confirm = inquire::Confirm::new(&format!("Delete profile '{}'?", profile.name))
+            .with_default(false)
+            .prompt()
+            .map_err(|e| AppError::Message(format!("Prompt failed: {e}")))?;
+        if !confirm {
+            println!("{}", info("Cancelled."));
+            return Ok(());
+        }
+    }
+
+    ProfileService::delete(&state, &profile.id)?;
+    println!(
+        "{}",
+        success(&format!("✓ Deleted profile '{}'", profile.name))
+    );
+    Ok(())
+}
+
+fn print_changes(changes: &[ProfileChange]) {
+    let mut table = create_table();
+    table.set_header(vec!["Section", "Item", "Before", "After"]);
+    for change in changes {
+        table.add_row(vec![
+            change.section.as_str().to_string(),
+            change.key.clone(),
+            change.before.clone().unwrap_or_else(|| "-".to_string()),
+            highlight(change.after.as_deref().unwrap_or("-")),
+        ]);
+    }
+    println!("{table}");
+}
+
+fn summary(profile: &Profile) -> String {
+    let payload = &profile.payload;
+    let count = |len: Option<usize>| len.map_or("-".to_string(), |len| len.to_string());
+    format!(
+        "{} providers, {} MCP servers, {} prompts, {} skills",
+        payload.providers.len(),
+        count(payload.mcp_servers.as_ref().map(|servers| servers.len())),
+        count(
+            payload
+                .prompts
+                .as_ref()
+                .map(|prompts| prompts.values().filter(|id| id.is_some()).count())
+        ),
+        count(payload.skills.as_ref().map(|skills| skills.len())),
+    )
+}
@@ -1,7 +1,10 @@
 use clap::{Subcommand, ValueEnum};
-use std::{collections::HashSet, path::PathBuf};
+use std::{collections::HashSet, path::PathBuf, time::Duration};
 
-use super::{provider_inspect, provider_usage_query};
+use super::{
+    provider_history, provider_import, provider_inspect, provider_keys, provider_lint,
+    provider_models, provider_presets, provider_rules, provider_usage_query,
+};
 use crate::app_config::AppType;
 use crate::claude_model_config::{ClaudeModelRole, CLAUDE_DEFAULT_MODEL_ENV_KEY};
 use crate::cli::commands::provider_input::{
@@ -17,9 +20,12 @@ use crate::cli::commands::provider_input::{
 use crate::cli::i18n::texts;
 use crate::cli::ui::{highlight, info, success, warning};
 use crate::error::AppError;
+use crate::preset_catalog::load_catalog;
 use crate::provider::{AuthBinding, AuthBindingSource, ClaudeApiKeyField, Provider, ProviderMeta};
-use crate::provider_preset_models::GEMINI_DEFAULT_MODEL;
-use crate::services::{AuthService, ManagedAuthAccount, ProviderService};
+use crate::provider_preset_models::gemini_default_model;
+use crate::proxy::capability_probe::{Capability, ProbeOptions};
+use crate::services::switch_history::SwitchSource;
+use crate::services::{AuthService, ForeignSource, ManagedAuthAccount, ProviderService};
 use crate::store::AppState;
 use indexmap::IndexMap;
 use inquire::{Confirm, Select, Text};
@@ -675,9 +681,14 @@ pub enum ProviderCommand {
     List,
     /// Show current provider
     Current,
+    /// Show a provider's configuration and secret reference status
+    Show {
+        /// Provider ID
+        id: String,
+    },
     /// Switch to a provider
     Switch {
-        /// Provider ID to switch to
+        /// Provider ID to switch to, or `-` for the previous one
         id: String,
     },
     /// Add a new provider (non-interactive; use the TUI for interactive add)
@@ -685,6 +696,9 @@ pub enum ProviderCommand {
         /// Provider template to apply before creation
         #[arg(long, value_enum)]
         template: Option<ProviderAddTemplate>,
+        /// Preset from the preset catalog (see `provider presets list`)
+        #[arg(long, conflicts_with = "template")]
+        preset: Option<String>,
         /// Provider display name (required)
         #[arg(long)]
         name: Option<String>,
@@ -772,6 +786,27 @@ pub enum ProviderCommand {
     },
     /// Import providers from the current live app config
     ImportLive,
+    /// Import providers from claude-code-router, LiteLLM, shell rc files or Codex profiles
+    ImportFrom {
+        /// Tool to import from
+        #[arg(value_enum)]
+        source: ForeignSource,
+        /// Config file to read instead of the default location
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Import every candidate whose base URL is not configured yet
+        #[arg(long, conflicts_with = "select")]
+        all: bool,
+        /// Candidates to import by their number in the preview, e.g. 1,3
+        #[arg(long, value_delimiter = ',')]
+        select: Vec<usize>,
+        /// Show the candidates without importing
+        #[arg(long)]
+        dry_run: bool,
+        /// Output the candidates as JSON without importing
+        #[arg(long)]
+        json: bool,
+    },
     /// Remove a provider from additive live app config without deleting it
     RemoveFromConfig {
         /// Provider ID to remove from live config
@@ -817,10 +852,108 @@ pub enum ProviderCommand {
         /// Output raw quota result as JSON
         #[arg(long)]
         json: bool,
+        /// Show recorded balance history and burn-rate estimates instead of querying
+        #[arg(long)]
+        history: bool,
+        /// History window in days (with --history)
+        #[arg(long, default_value_t = 30, requires = "history")]
+        days: u32,
+    },
+    /// Show uptime and latency from background and manual health checks
+    Health {
+        /// Only show this provider
+        id: Option<String>,
+        /// History window, e.g. 30m, 24h or 7d
+        #[arg(long, default_value = "24h", value_parser = provider_inspect::parse_history_window)]
+        since: i64,
+        /// Output summaries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Probe which features a Claude provider's upstream model accepts
+    Probe {
+        /// Provider ID to probe
+        id: String,
+        /// Model to request (default: claude-sonnet-4-5; the provider's model mapping applies)
+        #[arg(long)]
+        model: Option<String>,
+        /// Only run these probes (repeatable)
+        #[arg(long, value_enum)]
+        only: Vec<ProbeCapabilityArg>,
+        /// Also probe a prompt of about this many tokens
+        #[arg(long, value_name = "TOKENS")]
+        long_context: Option<u32>,
+        /// Per-request timeout in seconds
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+        /// Show the stored capability matrix instead of probing
+        #[arg(long, conflicts_with_all = ["model", "only", "long_context"])]
+        show: bool,
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List recorded revisions of a provider, or recent switches with --switches
+    History {
+        /// Provider ID
+        #[arg(required_unless_present = "switches", conflicts_with = "switches")]
+        id: Option<String>,
+        /// List recent provider switches of every app instead
+        #[arg(long)]
+        switches: bool,
+        /// Number of switches to show
+        #[arg(long, default_value_t = 20, requires = "switches")]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show what changed between a revision and the current provider (secrets masked)
+    Diff {
+        /// Provider ID
+        id: String,
+        /// Revision to compare with (default: the one before the latest)
+        #[arg(long)]
+        rev: Option<i64>,
+    },
+    /// Restore a provider to a recorded revision and rewrite live configs
+    Revert {
+        /// Provider ID
+        id: String,
+        /// Revision to restore
+        #[arg(long)]
+        rev: i64,
+    },
+    /// Check provider configs for common mistakes and fix the safe ones
+    Lint {
+        /// Provider ID to check
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+        /// Check every provider of the app
+        #[arg(long)]
+        all: bool,
+        /// Apply safe fixes and save the provider
+        #[arg(long)]
+        fix: bool,
+        /// Output findings as JSON
+        #[arg(long, conflicts_with = "fix")]
+        json: bool,
     },
     /// Configure provider Usage Query
     #[command(subcommand)]
     UsageQuery(provider_usage_query::ProviderUsageQueryCommand),
+    /// Manage extra API keys the proxy rotates through
+    #[command(subcommand)]
+    Keys(provider_keys::ProviderKeysCommand),
+    /// Browse and refresh the provider preset catalog
+    #[command(subcommand)]
+    Presets(provider_presets::ProviderPresetsCommand),
+    /// Track upstream model lists and remap retired models
+    #[command(subcommand)]
+    Models(provider_models::ProviderModelsCommand),
+    /// Switch providers automatically on a schedule, budget or quota
+    #[command(subcommand)]
+    Rules(provider_rules::ProviderRulesCommand),
     /// Export a Claude provider to a standalone settings file
     Export {
         /// Provider ID to export
@@ -838,9 +971,11 @@ pub fn execute(cmd: ProviderCommand, app: Option<AppType>) -> Result<(), AppErro
     match cmd {
         ProviderCommand::List => provider_inspect::list_providers(app_type),
         ProviderCommand::Current => provider_inspect::show_current(app_type),
+        ProviderCommand::Show { id } => provider_inspect::show_provider(app_type, &id),
         ProviderCommand::Switch { id } => switch_provider(app_type, &id),
         ProviderCommand::Add {
             template,
+            preset,
             name,
             id,
             base_url,
@@ -867,6 +1002,7 @@ pub fn execute(cmd: ProviderCommand, app: Option<AppType>) -> Result<(), AppErro
             app_type,
             AddProviderArgs {
                 template,
+                preset,
                 name,
                 id,
                 base_url,
@@ -895,6 +1031,14 @@ pub fn execute(cmd: ProviderCommand, app: Option<AppType>) -> Result<(), AppErro
         ProviderCommand::Delete { id } => delete_provider(app_type, &id),
         ProviderCommand::Duplicate { id, edit } => duplicate_provider(app_type, &id, edit),
         ProviderCommand::ImportLive => import_live_config(app_type),
+        ProviderCommand::ImportFrom {
+            source,
+            file,
+            all,
+            select,
+            dry_run,
+            json,
+        } => provider_import::import_from(source, file.as_deref(), all, &select, dry_run, json),
         ProviderCommand::RemoveFromConfig { id } => remove_from_config(app_type, &id),
         ProviderCommand::SetDefault { id, model } => {
             set_default_provider(app_type, &id, model.as_deref())
@@ -920,14 +1064,100 @@ pub fn execute(cmd: ProviderCommand, app: Option<AppType>) -> Result<(), AppErro
                 )
             }
         }
-        ProviderCommand::Quota { id, json } => {
-            provider_inspect::quota_provider(app_type, &id, json)
+        ProviderCommand::Quota {
+            id,
+            json,
+            history,
+            days,
+        } => {
+            if history {
+                provider_inspect::quota_history(app_type, &id, days, json)
+            } else {
+                provider_inspect::quota_provider(app_type, &id, json)
+            }
+        }
+        ProviderCommand::Health { id, since, json } => {
+            provider_inspect::provider_health(app_type, id.as_deref(), since, json)
+        }
+        ProviderCommand::Probe {
+            id,
+            model,
+            only,
+            long_context,
+            timeout,
+            show,
+            json,
+        } => {
+            if show {
+                provider_inspect::show_capability_matrix(app_type, &id, json)
+            } else {
+                provider_inspect::probe_provider(
+                    app_type,
+                    &id,
+                    ProbeOptions {
+                        model,
+                        only: only.into_iter().map(Capability::from).collect(),
+                        long_context_tokens: long_context,
+                        timeout: Duration::from_secs(timeout.max(1)),
+                    },
+                    json,
+                )
+            }
         }
+        ProviderCommand::History {
+            id,
+            switches: _,
+            limit,
+            json,
+        } => match id {
+            Some(id) => provider_history::show_history(app_type, &id, json),
+            None => provider_history::show_switches(limit, json),
+        },
+        ProviderCommand::Diff { id, rev } => provider_history::show_diff(app_type, &id, rev),
+        ProviderCommand::Revert { id, rev } => provider_history::revert(app_type, &id, rev),
+        ProviderCommand::Lint {
+            id,
+            all: _,
+            fix,
+            json,
+        } => provider_lint::lint_providers(app_type, id.as_deref(), fix, json),
         ProviderCommand::UsageQuery(cmd) => provider_usage_query::execute(cmd, app_type),
+        ProviderCommand::Keys(cmd) => provider_keys::execute(cmd, app_type),
+        ProviderCommand::Rules(cmd) => provider_rules::execute(cmd, app_type),
+        ProviderCommand::Models(cmd) => provider_models::execute(cmd, app_type),
+        ProviderCommand::Presets(cmd) => provider_presets::execute(cmd, app_type),
         ProviderCommand::Export { id, output } => export_provider(app_type, &id, output),
     }
 }
 
+/// Capability probe selector (`provider probe --only`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProbeCapabilityArg {
+    Streaming,
+    Tools,
+    ParallelTools,
+    Thinking,
+    ImageInput,
+    PromptCaching,
+    JsonOutput,
+    LongContext,
+}
+
+impl From<ProbeCapabilityArg> for Capability {
+    fn from(value: ProbeCapabilityArg) -> Self {
+        match value {
+            ProbeCapabilityArg::Streaming => Capability::Streaming,
+            ProbeCapabilityArg::Tools => Capability::Tools,
+            ProbeCapabilityArg::ParallelTools => Capability::ParallelTools,
+            ProbeCapabilityArg::Thinking => Capability::Thinking,
+            ProbeCapabilityArg::ImageInput => Capability::ImageInput,
+            ProbeCapabilityArg::PromptCaching => Capability::PromptCaching,
+            ProbeCapabilityArg::JsonOutput => Capability::JsonOutput,
+            ProbeCapabilityArg::LongContext => Capability::LongContext,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum ModelFetchAuthArg {
     Bearer,
@@ -935,7 +1165,7 @@ pub enum ModelFetchAuthArg {
     GoogleApiKey,
 }
 
-impl From<ModelFetchAuthArg> for provider_inspect::ProviderModelFetchStrategy {
+impl From<ModelFetchAuthArg> for crate::services::provider::models::ModelFetchStrategy {
     fn from(value: ModelFetchAuthArg) -> Self {
         match value {
             ModelFetchAuthArg::Bearer => Self::Bearer,
@@ -992,13 +1222,22 @@ fn switch_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
     let app_str = app_type.as_str().to_string();
     let skip_live_sync = !crate::sync_policy::should_sync_live(&app_type);
 
+    // `-` 回到上一个供应商（类似 `cd -`）
+    let previous;
+    let id = if id == "-" {
+        previous = provider_history::previous_provider(&state, &app_type)?;
+        previous.as_str()
+    } else {
+        id
+    };
+
     // 检查 provider 是否存在（支持按 id 或名称解析）
     let providers = ProviderService::list(&state, app_type.clone())?;
     let (resolved_id, provider) = resolve_provider_for_switch(&providers, id)?;
     let id = resolved_id.as_str();
 
     // 执行切换（upstream parity：干净写入，无冲突提示）
-    ProviderService::switch(&state, app_type.clone(), id)?;
+    ProviderService::switch_with_source(&state, app_type.clone(), id, SwitchSource::Cli)?;
     if let Err(err) =
         crate::claude_plugin::sync_claude_plugin_on_provider_switch(&app_type, &provider)
     {
@@ -1066,6 +1305,7 @@ fn delete_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
 #[derive(Default)]
 struct AddProviderArgs {
     template: Option<ProviderAddTemplate>,
+    preset: Option<String>,
     name: Option<String>,
     id: Option<String>,
     base_url: Option<String>,
@@ -1380,7 +1620,7 @@ fn build_add_settings_config(
                     .unwrap_or_default();
                 let model = non_empty(args.model.clone())
                     .or_else(|| gemini_current_env(current, &["GEMINI_MODEL"]))
-                    .unwrap_or_else(|| GEMINI_DEFAULT_MODEL.to_string());
+                    .unwrap_or_else(|| gemini_default_model().to_string());
                 Ok(build_gemini_api_key_settings_config(
                     current, &api_key, &base_url, &model,
                 ))
@@ -1522,8 +1762,27 @@ fn add_provider(app_type: AppType, args: AddProviderArgs) -> Result<(), AppError
     let raw_config = load_raw_settings_config(&args)?;
     let mut settings_prompt_result: Option<SettingsConfigPromptResult> = None;
 
-    // 2. 构造供应商（自定义或基于模板）
-    let mut provider = if template.is_custom() {
+    // 2. 构造供应商（自定义、预设目录或基于模板）
+    let mut provider = if let Some(preset_id) = args.preset.as_deref() {
+        // 预设目录中的供应商与赞助商模板一样，允许用字段参数覆盖骨架配置
+        let mut provider = load_catalog().build_provider(&app_type, preset_id)?;
+        provider.name = name.clone();
+        provider.id = resolve_add_provider_id(&app_type, args.id.as_deref(), &name, &existing_ids)?;
+        provider.created_at = Some(current_timestamp());
+        if let Some(url) = non_empty(args.website_url.clone()) {
+            provider.website_url = Some(url);
+        }
+        let current = provider.settings_config.clone();
+        provider.settings_config = build_add_settings_config(
+            &app_type,
+            &args,
+            raw_config.as_ref(),
+            Some(&current),
+            &name,
+            &mut settings_prompt_result,
+        )?;
+        provider
+    } else if template.is_custom() {
         let id = resolve_add_provider_id(&app_type, args.id.as_deref(), &name, &existing_ids)?;
         let settings_config = build_add_settings_config(
             &app_type,
@@ -2148,7 +2407,7 @@ mod tests {
         .expect("build Gemini settings");
         assert_eq!(
             gemini["env"]["GEMINI_MODEL"],
-            crate::provider_preset_models::GEMINI_DEFAULT_MODEL
+            crate::provider_preset_models::gemini_default_model()
         );
     }
 
@@ -0,0 +1,235 @@
+//! `cc-switch provider history|diff|revert`: per-provider edit history.
+//!
+//! Every add, update and delete stores a snapshot in the local sidecar store;
+//! see [`crate::services::config_history`]. Diffs mask credentials.
+//! `provider history --switches` and `use -` read the switch log kept by
+//! [`crate::services::switch_history`].
+
+use serde::Serialize;
+
+use super::provider_inspect::format_check_time;
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, highlight, info, success, to_json};
+use crate::error::AppError;
+use crate::local_store::config_history::ConfigRevision;
+use crate::local_store::switch_history::ProviderSwitchRecord;
+use crate::services::config_history::{self, FieldChange, HistoryKind};
+use crate::services::switch_history;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RevisionEntry {
+    rev: i64,
+    action: String,
+    recorded_at: i64,
+    name: Option<String>,
+}
+
+fn revisions(app_type: &AppType, id: &str) -> Result<Vec<ConfigRevision>, AppError> {
+    config_history::list_revisions(HistoryKind::Provider, Some(app_type), id)
+}
+
+pub(crate) fn show_history(app_type: AppType, id: &str, json: bool) -> Result<(), AppError> {
+    let entries = revisions(&app_type, id)?
+        .into_iter()
+        .rev()
+        .map(|revision| RevisionEntry {
+            rev: revision.rev,
+            action: revision.action,
+            recorded_at: revision.recorded_at,
+            name: revision
+                .snapshot
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(str::to_string),
+        })
+        .collect::<Vec<_>>();
+
+    if json {
+        println!(
+            "{}",
+            to_json(&entries).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            info(&format!("No edit history recorded for provider '{id}'."))
+        );
+        return Ok(());
+    }
+
+    println!("{}", highlight(&format!("Edit history of {id}")));
+    let mut table = create_table();
+    table.set_header(vec!["Rev", "Action", "Recorded", "Name"]);
+    for entry in entries {
+        table.add_row(vec![
+            entry.rev.to_string(),
+            entry.action,
+            format_check_time(entry.recorded_at),
+            entry.name.unwrap_or_default(),
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "{}",
+        info(&format!(
+            "Compare with `cc-switch provider diff {id} --rev <N>`, restore with `cc-switch provider revert {id} --rev <N>`."
+        ))
+    );
+    Ok(())
+}
+
+fn print_changes(changes: &[FieldChange]) {
+    for change in changes {
+        match (&change.before, &change.after) {
+            (Some(before), Some(after)) => {
+                println!("~ {}: {before} → {after}", change.path)
+            }
+            (Some(before), None) => println!("- {}: {before}", change.path),
+            (None, Some(after)) => println!("+ {}: {after}", change.path),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Compare revision `rev` (default: the one before the latest) with the
+/// provider as it is stored now.
+pub(crate) fn show_diff(app_type: AppType, id: &str, rev: Option<i64>) -> Result<(), AppError> {
+    let history = revisions(&app_type, id)?;
+    let revision = match rev {
+        Some(rev) => history.iter().find(|revision| revision.rev == rev),
+        None => history.iter().rev().nth(1),
+    }
+    .ok_or_else(|| match rev {
+        Some(rev) => AppError::InvalidInput(format!("No revision {rev} recorded for '{id}'")),
+        None => AppError::InvalidInput(format!(
+            "Provider '{id}' has fewer than two recorded revisions"
+        )),
+    })?;
+
+    let state = AppState::try_new()?;
+    let current = state
+        .db
+        .get_provider_by_id(id, app_type.as_str())?
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|source| AppError::JsonSerialize { source })?;
+
+    let changes = config_history::diff_snapshots(Some(&revision.snapshot), current.as_ref());
+    println!(
+        "{}",
+        highlight(&format!(
+            "{id}: rev {} ({}) → {}",
+            revision.rev,
+            format_check_time(revision.recorded_at),
+            if current.is_some() {
+                "current"
+            } else {
+                "deleted"
+            }
+        ))
+    );
+    if changes.is_empty() {
+        println!("{}", info("No differences."));
+    } else {
+        print_changes(&changes);
+    }
+    Ok(())
+}
+
+pub(crate) fn revert(app_type: AppType, id: &str, rev: i64) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let provider = ProviderService::revert(&state, app_type, id, rev)?;
+    println!(
+        "{}",
+        success(&format!(
+            "✓ Provider '{}' reverted to revision {rev}.",
+            provider.name
+        ))
+    );
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwitchEntry {
+    app: String,
+    from: Option<String>,
+    to: String,
+    source: String,
+    switched_at: i64,
+}
+
+impl From<ProviderSwitchRecord> for SwitchEntry {
+    fn from(record: ProviderSwitchRecord) -> Self {
+        Self {
+            app: record.app_type,
+            from: record.from_id,
+            to: record.to_id,
+            source: record.source,
+            switched_at: record.switched_at,
+        }
+    }
+}
+
+/// Recent switches of every app, newest first.
+pub(crate) fn show_switches(limit: usize, json: bool) -> Result<(), AppError> {
+    let entries = switch_history::recent(None, limit)?
+        .into_iter()
+        .map(SwitchEntry::from)
+        .collect::<Vec<_>>();
+
+    if json {
+        println!(
+            "{}",
+            to_json(&entries).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("{}", info("No provider switches recorded yet."));
+        return Ok(());
+    }
+
+    println!("{}", highlight("Recent provider switches"));
+    let mut table = create_table();
+    table.set_header(vec!["When", "App", "From", "To", "Source"]);
+    for entry in entries {
+        table.add_row(vec![
+            format_check_time(entry.switched_at),
+            entry.app,
+            entry.from.unwrap_or_else(|| "-".to_string()),
+            entry.to,
+            entry.source,
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "{}",
+        info("Return to the previous provider of an app with `cc-switch --app <app> use -`.")
+    );
+    Ok(())
+}
+
+/// Target of `use -`: the provider the app was on before its latest switch.
+pub(crate) fn previous_provider(state: &AppState, app_type: &AppType) -> Result<String, AppError> {
+    if app_type.is_additive_mode() {
+        return Err(AppError::InvalidInput(format!(
+            "`use -` is not available for {}, which has no single current provider",
+            app_type.as_str()
+        )));
+    }
+    let current = crate::settings::get_effective_current_provider(&state.db, app_type)?;
+    switch_history::previous_provider(app_type, current.as_deref())?.ok_or_else(|| {
+        AppError::InvalidInput(format!(
+            "No previous provider recorded for {}",
+            app_type.as_str()
+        ))
+    })
+}
@@ -0,0 +1,184 @@
+//! `cc-switch provider import-from`: bring providers over from
+//! claude-code-router, LiteLLM, shell rc files or hand-written Codex profiles.
+//!
+//! Parsing and mapping live in [`crate::services::ProviderService::scan_foreign`];
+//! this module previews the candidates and asks which ones to import.
+
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::cli::ui::{create_table, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::services::{ForeignCandidate, ForeignSource, ProviderService};
+use crate::store::AppState;
+
+pub(crate) fn import_from(
+    source: ForeignSource,
+    file: Option<&Path>,
+    all: bool,
+    select: &[usize],
+    dry_run: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let candidates = ProviderService::scan_foreign(&state, source, file)?;
+
+    if json {
+        println!(
+            "{}",
+            to_json(&candidates).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if candidates.is_empty() {
+        let message = match (source, file) {
+            (ForeignSource::Shell, _) | (_, Some(_)) => {
+                format!("No providers found in {}.", source.label())
+            }
+            (_, None) => format!(
+                "No {} config found at {}; pass --file.",
+                source.label(),
+                source
+                    .default_paths()
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        println!("{}", info(&message));
+        return Ok(());
+    }
+
+    print_candidates(source, &candidates);
+    if dry_run {
+        return Ok(());
+    }
+
+    let chosen = if all {
+        candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, candidate)| candidate.duplicate_of.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    } else if !select.is_empty() {
+        select
+            .iter()
+            .map(|number| {
+                number
+                    .checked_sub(1)
+                    .filter(|index| *index < candidates.len())
+                    .ok_or_else(|| {
+                        AppError::InvalidInput(format!(
+                            "No candidate #{number}; choose between 1 and {}",
+                            candidates.len()
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else if std::io::stdin().is_terminal() {
+        prompt_selection(&candidates)?
+    } else {
+        return Err(AppError::InvalidInput(
+            "Pass --all or --select to import without a terminal".to_string(),
+        ));
+    };
+
+    if chosen.is_empty() {
+        println!("{}", info("Nothing selected."));
+        return Ok(());
+    }
+
+    let selected: Vec<ForeignCandidate> = chosen
+        .iter()
+        .map(|index| candidates[*index].clone())
+        .collect();
+    let missing_keys: Vec<String> = selected
+        .iter()
+        .filter(|candidate| !candidate.has_key)
+        .map(|candidate| candidate.provider.name.clone())
+        .collect();
+    let imported = ProviderService::import_foreign(&state, selected)?;
+    for provider in &imported {
+        println!(
+            "{}",
+            success(&format!(
+                "✓ Imported {} provider '{}' as {}",
+                provider.app.as_str(),
+                provider.name,
+                provider.id
+            ))
+        );
+    }
+    if !missing_keys.is_empty() {
+        println!(
+            "{}",
+            warning(&format!(
+                "Set the API key of {} with `cc-switch provider edit`.",
+                missing_keys.join(", ")
+            ))
+        );
+    }
+    Ok(())
+}
+
+fn print_candidates(source: ForeignSource, candidates: &[ForeignCandidate]) {
+    println!(
+        "{}",
+        highlight(&format!("Providers found in {}", source.label()))
+    );
+    let mut table = create_table();
+    table.set_header(vec![
+        "#", "App", "Name", "Base URL", "Format", "Model", "Key", "Local",
+    ]);
+    for (index, candidate) in candidates.iter().enumerate() {
+        table.add_row(vec![
+            (index + 1).to_string(),
+            candidate.app.as_str().to_string(),
+            candidate.provider.name.clone(),
+            candidate.base_url.clone(),
+            candidate.api_format.clone(),
+            candidate.model.clone().unwrap_or_else(|| "-".to_string()),
+            if candidate.has_key { "yes" } else { "no" }.to_string(),
+            match &candidate.duplicate_of {
+                Some(id) => warning(&format!("same URL as {id}")),
+                None => "new".to_string(),
+            },
+        ]);
+    }
+    println!("{table}");
+    for (index, candidate) in candidates.iter().enumerate() {
+        for note in &candidate.notes {
+            println!("{}", info(&format!("#{}: {note}", index + 1)));
+        }
+    }
+}
+
+fn prompt_selection(candidates: &[ForeignCandidate]) -> Result<Vec<usize>, AppError> {
+    let options: Vec<String> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| {
+            format!(
+                "#{} {} · {} ({})",
+                index + 1,
+                candidate.provider.name,
+                candidate.base_url,
+                candidate.app.as_str()
+            )
+        })
+        .collect();
+    let defaults: Vec<usize> = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| candidate.duplicate_of.is_none())
+        .map(|(index, _)| index)
+        .collect();
+    let chosen = inquire::MultiSelect::new("Providers to import:", options)
+        .with_default(&defaults)
+        .raw_prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {e}")))?;
+    Ok(chosen.into_iter().map(|option| option.index).collect())
+}
@@ -9,17 +9,9 @@ use crate::claude_model_config::{
 use crate::cli::i18n::texts;
 use crate::cli::ui::info;
 use crate::error::AppError;
-use crate::provider::{
-    AuthBinding, AuthBindingSource, ClaudeApiKeyField, CodexChatReasoningConfig, Provider,
-    ProviderMeta,
-};
-use crate::provider_preset_models::{
-    codex_oauth_claude_env, sponsor_hermes_models, sponsor_model_family, sponsor_openclaw_models,
-    sponsor_opencode_settings, SponsorModelFamily, CODEX_DEFAULT_MODEL, GEMINI_DEFAULT_MODEL,
-};
-use crate::provider_preset_sponsors::{
-    sponsor_provider_preset, sponsor_provider_presets_for_app, SponsorProviderPreset,
-};
+use crate::preset_catalog::shared_catalog;
+use crate::provider::{ClaudeApiKeyField, Provider, ProviderMeta};
+use crate::provider_preset_models::{codex_default_model, gemini_default_model};
 use crate::services::ProviderService;
 use clap::ValueEnum;
 use colored::Colorize;
@@ -72,6 +64,12 @@ impl ProviderAddTemplate {
         matches!(self, Self::Custom)
     }
 
+    /// The preset catalog ([`crate::preset_catalog`]) entry behind the
+    /// template; the custom template has none.
+    pub fn catalog_preset_id(self) -> Option<&'static str> {
+        (!self.is_custom()).then(|| self.cli_name())
+    }
+
     pub fn supports_field_overrides(self) -> bool {
         matches!(
             self,
@@ -89,17 +87,6 @@ impl ProviderAddTemplate {
     }
 }
 
-const DEEPSEEK_CODEX_CONFIG: &str = r#"model_provider = "custom"
-model = "deepseek-v4-flash"
-model_reasoning_effort = "high"
-disable_response_storage = true
-
-[model_providers.custom]
-name = "deepseek"
-base_url = "https://api.deepseek.com"
-wire_api = "responses"
-requires_openai_auth = true"#;
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ProviderAddTemplateChoice {
     pub template: ProviderAddTemplate,
@@ -183,83 +170,27 @@ pub fn set_provider_common_config_meta(provider: &mut Provider, enabled: bool) {
         .apply_common_config = Some(enabled);
 }
 
+/// Custom first, then every catalog preset of the app that backs a template,
+/// in catalog order.
 pub fn provider_add_template_choices(app_type: &AppType) -> Vec<ProviderAddTemplateChoice> {
-    let mut choices = match app_type {
-        AppType::Claude => vec![
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::Custom,
-                label: "Custom",
-            },
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::ClaudeOfficial,
-                label: "Claude Official",
-            },
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::CodexOauth,
-                label: "Codex",
-            },
-        ],
-        AppType::Codex => vec![
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::Custom,
-                label: "Custom",
-            },
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::OpenaiOfficial,
-                label: "OpenAI Official",
-            },
-        ],
-        AppType::Gemini => vec![
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::Custom,
-                label: "Custom",
-            },
-            ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::GoogleOauth,
-                label: "Google OAuth",
-            },
-        ],
-        AppType::OpenCode | AppType::Hermes | AppType::OpenClaw => {
-            vec![ProviderAddTemplateChoice {
-                template: ProviderAddTemplate::Custom,
-                label: "Custom",
-            }]
-        }
-    };
-
+    let mut choices = vec![ProviderAddTemplateChoice {
+        template: ProviderAddTemplate::Custom,
+        label: "Custom",
+    }];
     choices.extend(
-        sponsor_provider_presets_for_app(app_type)
-            .iter()
-            .map(|preset| ProviderAddTemplateChoice {
-                template: sponsor_template_from_id(preset.id),
-                label: preset.chip_label,
+        shared_catalog()
+            .presets_for_app(app_type)
+            .filter_map(|loaded| {
+                let template = ProviderAddTemplate::from_str(&loaded.preset.id, false).ok()?;
+                (!template.is_custom()).then(|| ProviderAddTemplateChoice {
+                    template,
+                    label: loaded.preset.label(),
+                })
             }),
     );
-    if matches!(app_type, AppType::Codex) {
-        choices.push(ProviderAddTemplateChoice {
-            template: ProviderAddTemplate::Deepseek,
-            label: "DeepSeek",
-        });
-    }
-
     choices
 }
 
-fn sponsor_template_from_id(id: &str) -> ProviderAddTemplate {
-    match id {
-        "claudeapi" => ProviderAddTemplate::Claudeapi,
-        "packycode" => ProviderAddTemplate::Packycode,
-        "runapi" => ProviderAddTemplate::Runapi,
-        "aicodemirror" => ProviderAddTemplate::Aicodemirror,
-        "cubence" => ProviderAddTemplate::Cubence,
-        "openmodel" => ProviderAddTemplate::Openmodel,
-        "dds" => ProviderAddTemplate::Dds,
-        "qiniu" => ProviderAddTemplate::Qiniu,
-        "fenno" => ProviderAddTemplate::Fenno,
-        _ => unreachable!("unknown shared sponsor preset: {id}"),
-    }
-}
-
 pub fn provider_add_template_supported(app_type: &AppType, template: ProviderAddTemplate) -> bool {
     provider_add_template_choices(app_type)
         .iter()
@@ -322,354 +253,12 @@ pub fn build_provider_template_seed(
         ));
     }
 
-    let name = template_default_name(template)?;
-    let id = generate_provider_id(name, existing_ids);
-    let website_url = template_default_website_url(template).map(str::to_string);
-    let category = template_default_category(template).map(str::to_string);
-    let meta = template_default_meta(app_type, template);
-    let settings_config = build_provider_template_settings_config(app_type, template)?;
-    let icon = template_default_icon(template).map(str::to_string);
-    let icon_color = template_default_icon_color(template).map(str::to_string);
-
-    Ok(Provider {
-        id,
-        name: name.to_string(),
-        settings_config,
-        website_url,
-        category,
-        created_at: None,
-        sort_index: None,
-        notes: None,
-        icon,
-        icon_color,
-        meta,
-        in_failover_queue: false,
-    })
-}
-
-fn template_default_name(template: ProviderAddTemplate) -> Result<&'static str, AppError> {
-    Ok(match template {
-        ProviderAddTemplate::ClaudeOfficial => "Claude Official",
-        ProviderAddTemplate::CodexOauth => "Codex",
-        ProviderAddTemplate::OpenaiOfficial => "OpenAI Official",
-        ProviderAddTemplate::GoogleOauth => "Google OAuth",
-        ProviderAddTemplate::Deepseek => "DeepSeek",
-        ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => sponsor_preset(template)
-            .map(|preset| preset.provider_name)
-            .ok_or_else(|| unsupported_template_error(template))?,
-        ProviderAddTemplate::Custom => return Err(unsupported_template_error(template)),
-    })
-}
-
-fn template_default_website_url(template: ProviderAddTemplate) -> Option<&'static str> {
-    match template {
-        ProviderAddTemplate::ClaudeOfficial => Some("https://www.anthropic.com/claude-code"),
-        ProviderAddTemplate::CodexOauth => Some("https://openai.com/chatgpt/pricing"),
-        ProviderAddTemplate::OpenaiOfficial => Some("https://chatgpt.com/codex"),
-        ProviderAddTemplate::GoogleOauth => Some("https://ai.google.dev"),
-        ProviderAddTemplate::Deepseek => Some("https://platform.deepseek.com"),
-        ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => sponsor_preset(template).map(|preset| preset.website_url),
-        ProviderAddTemplate::Custom => None,
-    }
-}
-
-fn template_default_category(template: ProviderAddTemplate) -> Option<&'static str> {
-    match template {
-        ProviderAddTemplate::ClaudeOfficial
-        | ProviderAddTemplate::OpenaiOfficial
-        | ProviderAddTemplate::GoogleOauth => Some("official"),
-        ProviderAddTemplate::Deepseek => Some("cn_official"),
-        ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => Some("aggregator"),
-        ProviderAddTemplate::Custom
-        | ProviderAddTemplate::CodexOauth
-        | ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Dds => None,
-    }
-}
-
-fn template_default_meta(
-    app_type: &AppType,
-    template: ProviderAddTemplate,
-) -> Option<ProviderMeta> {
-    match template {
-        ProviderAddTemplate::CodexOauth => Some(ProviderMeta {
-            provider_type: Some("codex_oauth".to_string()),
-            api_format: Some("openai_responses".to_string()),
-            codex_fast_mode: Some(false),
-            auth_binding: Some(AuthBinding {
-                source: AuthBindingSource::ManagedAccount,
-                auth_provider: Some("codex_oauth".to_string()),
-                account_id: None,
-            }),
-            ..Default::default()
-        }),
-        ProviderAddTemplate::OpenaiOfficial => Some(ProviderMeta {
-            codex_official: Some(true),
-            ..Default::default()
-        }),
-        ProviderAddTemplate::Deepseek => Some(ProviderMeta {
-            api_format: Some("openai_chat".to_string()),
-            codex_chat_reasoning: Some(CodexChatReasoningConfig {
-                supports_thinking: Some(true),
-                supports_effort: Some(true),
-                thinking_param: Some("thinking".to_string()),
-                effort_param: Some("reasoning_effort".to_string()),
-                effort_value_mode: Some("deepseek".to_string()),
-                output_format: Some("reasoning_content".to_string()),
-            }),
-            ..Default::default()
-        }),
-        ProviderAddTemplate::GoogleOauth => Some(ProviderMeta {
-            partner_promotion_key: Some("google-official".to_string()),
-            ..Default::default()
-        }),
-        ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => sponsor_preset(template).map(|preset| {
-            let mut meta = ProviderMeta {
-                is_partner: Some(true),
-                partner_promotion_key: Some(preset.partner_promotion_key.to_string()),
-                ..Default::default()
-            };
-            if matches!(app_type, AppType::Codex) {
-                meta.api_format = Some("openai_responses".to_string());
-            }
-            meta
-        }),
-        ProviderAddTemplate::Custom | ProviderAddTemplate::ClaudeOfficial => None,
-    }
-}
-
-fn template_default_icon(template: ProviderAddTemplate) -> Option<&'static str> {
-    match template {
-        ProviderAddTemplate::Deepseek => Some("deepseek"),
-        ProviderAddTemplate::Runapi => Some("runapi"),
-        ProviderAddTemplate::Qiniu => Some("qiniu"),
-        ProviderAddTemplate::Fenno => Some("fenno"),
-        ProviderAddTemplate::Custom
-        | ProviderAddTemplate::ClaudeOfficial
-        | ProviderAddTemplate::CodexOauth
-        | ProviderAddTemplate::OpenaiOfficial
-        | ProviderAddTemplate::GoogleOauth
-        | ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds => None,
-    }
-}
-
-fn template_default_icon_color(template: ProviderAddTemplate) -> Option<&'static str> {
-    match template {
-        ProviderAddTemplate::Deepseek => Some("#1E88E5"),
-        ProviderAddTemplate::Custom
-        | ProviderAddTemplate::ClaudeOfficial
-        | ProviderAddTemplate::CodexOauth
-        | ProviderAddTemplate::OpenaiOfficial
-        | ProviderAddTemplate::GoogleOauth
-        | ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => None,
-    }
-}
-
-fn build_provider_template_settings_config(
-    app_type: &AppType,
-    template: ProviderAddTemplate,
-) -> Result<Value, AppError> {
-    match template {
-        ProviderAddTemplate::ClaudeOfficial => Ok(json!({ "env": {} })),
-        ProviderAddTemplate::CodexOauth => Ok(json!({
-            "env": codex_oauth_claude_env(),
-            "attribution": {
-                "commit": "",
-                "pr": ""
-            }
-        })),
-        ProviderAddTemplate::OpenaiOfficial => build_codex_official_settings_config(None),
-        ProviderAddTemplate::Deepseek => Ok(build_codex_deepseek_settings_config()),
-        ProviderAddTemplate::GoogleOauth => Ok(json!({ "env": {} })),
-        ProviderAddTemplate::Claudeapi
-        | ProviderAddTemplate::Packycode
-        | ProviderAddTemplate::Runapi
-        | ProviderAddTemplate::Aicodemirror
-        | ProviderAddTemplate::Cubence
-        | ProviderAddTemplate::Openmodel
-        | ProviderAddTemplate::Dds
-        | ProviderAddTemplate::Qiniu
-        | ProviderAddTemplate::Fenno => build_sponsor_template_settings_config(
-            app_type,
-            sponsor_preset(template).ok_or_else(|| unsupported_template_error(template))?,
-        ),
-        ProviderAddTemplate::Custom => Err(unsupported_template_error(template)),
-    }
-}
-
-fn build_codex_deepseek_settings_config() -> Value {
-    json!({
-        "config": DEEPSEEK_CODEX_CONFIG,
-        "modelCatalog": {
-            "models": [
-                {
-                    "model": "deepseek-v4-flash",
-                    "displayName": "DeepSeek V4 Flash",
-                    "contextWindow": 1000000,
-                },
-                {
-                    "model": "deepseek-v4-pro",
-                    "displayName": "DeepSeek V4 Pro",
-                    "contextWindow": 1000000,
-                },
-            ],
-        },
-    })
-}
-
-fn build_sponsor_template_settings_config(
-    app_type: &AppType,
-    preset: SponsorProviderPreset,
-) -> Result<Value, AppError> {
-    match app_type {
-        AppType::Claude => Ok(json!({
-            "env": {
-                "ANTHROPIC_BASE_URL": preset.claude_base_url,
-            }
-        })),
-        AppType::Codex => Ok(build_codex_settings_config(
-            None,
-            preset.codex_base_url,
-            CODEX_DEFAULT_MODEL,
-            "responses",
-            preset.provider_name,
-        )),
-        AppType::Gemini => Ok(json!({
-            "env": {
-                "GOOGLE_GEMINI_BASE_URL": preset.gemini_base_url,
-                "GEMINI_MODEL": GEMINI_DEFAULT_MODEL,
-            }
-        })),
-        AppType::OpenCode => {
-            let family = sponsor_model_family(preset.id);
-            if let Some(family) = family {
-                Ok(sponsor_opencode_settings(
-                    preset.provider_name,
-                    preset.opencode_base_url,
-                    family,
-                ))
-            } else {
-                build_opencode_settings_config(
-                    None,
-                    "@ai-sdk/openai-compatible",
-                    "",
-                    preset.opencode_base_url,
-                    "",
-                    "",
-                    "",
-                    "",
-                    None,
-                )
-            }
-        }
-        AppType::Hermes => {
-            let family = sponsor_model_family(preset.id);
-            if let Some(family) = family {
-                build_hermes_settings_config(
-                    Some(&json!({ "name": preset.partner_promotion_key })),
-                    match family {
-                        SponsorModelFamily::Claude | SponsorModelFamily::RunApiClaude => {
-                            "anthropic_messages"
-                        }
-                        SponsorModelFamily::Gpt => crate::hermes_config::HERMES_DEFAULT_API_MODE,
-                    },
-                    preset.hermes_base_url,
-                    "",
-                    Value::Array(sponsor_hermes_models(family)),
-                    "",
-                )
-            } else {
-                build_hermes_settings_config(
-                    None,
-                    crate::hermes_config::HERMES_DEFAULT_API_MODE,
-                    preset.hermes_base_url,
-                    "",
-                    json!([]),
-                    "",
-                )
-            }
-        }
-        AppType::OpenClaw => {
-            let family = sponsor_model_family(preset.id);
-            if let Some(family) = family {
-                build_openclaw_settings_config(
-                    None,
-                    match family {
-                        SponsorModelFamily::Claude | SponsorModelFamily::RunApiClaude => {
-                            "anthropic-messages"
-                        }
-                        SponsorModelFamily::Gpt => {
-                            crate::openclaw_config::OPENCLAW_DEFAULT_API_PROTOCOL
-                        }
-                    },
-                    "",
-                    preset.openclaw_base_url,
-                    false,
-                    Value::Array(sponsor_openclaw_models(family)),
-                )
-            } else {
-                build_openclaw_settings_config(
-                    None,
-                    crate::openclaw_config::OPENCLAW_DEFAULT_API_PROTOCOL,
-                    "",
-                    preset.openclaw_base_url,
-                    false,
-                    json!([{
-                        "id": "placeholder-model"
-                    }]),
-                )
-                .map(|mut value| {
-                    if let Some(obj) = value.as_object_mut() {
-                        obj.remove("models");
-                    }
-                    value
-                })
-            }
-        }
-    }
+    let preset_id = template
+        .catalog_preset_id()
+        .ok_or_else(|| unsupported_template_error(template))?;
+    let mut provider = shared_catalog().build_provider(app_type, preset_id)?;
+    provider.id = generate_provider_id(&provider.name, existing_ids);
+    Ok(provider)
 }
 
 /// Apply script-friendly field overrides to an additive-app preset while
@@ -759,10 +348,6 @@ fn models_with_primary_override(current: &Value, model: Option<&str>) -> Value {
     Value::Array(models)
 }
 
-fn sponsor_preset(template: ProviderAddTemplate) -> Option<SponsorProviderPreset> {
-    sponsor_provider_preset(template.cli_name())
-}
-
 fn unsupported_template_error(template: ProviderAddTemplate) -> AppError {
     AppError::InvalidInput(format!(
         "Unsupported provider template '{}'",
@@ -773,6 +358,7 @@ fn unsupported_template_error(template: ProviderAddTemplate) -> AppError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provider::AuthBindingSource;
 
     #[test]
     fn codex_official_settings_config_uses_upstream_seed_shape() {
@@ -881,11 +467,11 @@ env_key = "OLD_API_KEY"
 
     #[test]
     fn build_codex_settings_config_defaults_model_to_upstream_value() {
-        let cfg = build_codex_settings_config(
-            Some("sk-test"),
+        let cfg = build_codex_settings_config_from_prompt(
+            None,
+            "sk-test",
             "https://api.example.com/v1",
             "",
-            "responses",
             "custom",
         );
 
@@ -2468,45 +2054,6 @@ requires_openai_auth = true
     }
 }
 
-fn build_codex_settings_config(
-    api_key: Option<&str>,
-    base_url: &str,
-    model: &str,
-    wire_api: &str,
-    provider_name: &str,
-) -> Value {
-    let model = if model.trim().is_empty() {
-        CODEX_DEFAULT_MODEL
-    } else {
-        model.trim()
-    };
-    let base_url = base_url.trim();
-
-    let config_toml = crate::codex_config::build_codex_third_party_config_toml(
-        provider_name,
-        base_url,
-        model,
-        wire_api,
-    )
-    .trim()
-    .to_string();
-
-    match api_key {
-        Some(key) if !key.trim().is_empty() => {
-            json!({
-                "auth": { "OPENAI_API_KEY": key.trim() },
-                "config": config_toml
-            })
-        }
-        None => json!({
-            "config": config_toml
-        }),
-        Some(_) => json!({
-            "config": config_toml
-        }),
-    }
-}
-
 /// Parse a Codex provider's stored `settings_config` and extract the effective
 /// `base_url` and `model` from its `config` TOML, supporting both the flat
 /// (`base_url = ...`) and full upstream (`model_providers.<key>.base_url`) shapes.
@@ -2540,7 +2087,7 @@ pub(crate) fn build_codex_settings_config_from_prompt(
     provider_name: &str,
 ) -> Value {
     let model = if model.trim().is_empty() {
-        CODEX_DEFAULT_MODEL
+        codex_default_model()
     } else {
         model.trim()
     };
@@ -4113,7 +3660,7 @@ fn prompt_codex_config(current: Option<&Value>, provider_name: &str) -> Result<V
             .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
     } else {
         Text::new(&format!("{}:", texts::model_label()))
-            .with_placeholder(CODEX_DEFAULT_MODEL)
+            .with_placeholder(codex_default_model())
             .with_help_message("Model name")
             .prompt()
             .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
@@ -4216,7 +3763,7 @@ fn prompt_gemini_config(current: Option<&Value>) -> Result<Value, AppError> {
                 .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
         } else {
             Text::new(&format!("{}:", texts::model_label()))
-                .with_initial_value(GEMINI_DEFAULT_MODEL)
+                .with_initial_value(gemini_default_model())
                 .with_help_message(texts::model_default_help())
                 .prompt()
                 .map_err(|e| AppError::Message(texts::input_failed_error(&e.to_string())))?
@@ -4,41 +4,25 @@ use serde_json::Value;
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
 use crate::cli::provider_quota::{
-    display_usage_plan_name, provider_display_name, query_quota, quota_target_for_provider,
-    usage_value_summary, ProviderUsageQuota, QuotaTarget,
+    days_until_empty_label, display_usage_plan_name, provider_display_name, query_quota,
+    quota_sparkline, quota_target_for_provider, usage_number, usage_value_summary,
+    ProviderUsageQuota, QuotaTarget,
 };
 use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
 use crate::error::AppError;
+use crate::local_store::LocalStore;
 use crate::provider::{Provider, UsageData, UsageResult};
-use crate::services::{
-    CodexOAuthService, CredentialStatus, ProviderService, SpeedtestService, StreamCheckService,
+use crate::proxy::capability_probe::{self, ProbeOptions, ProbeStatus};
+use crate::services::health_monitor::{self, ProviderHealthSummary};
+use crate::services::provider::models::{
+    self, ModelFetchSource, ModelFetchStrategy, ModelFetchTarget,
 };
+use crate::services::quota_history::{
+    quota_trends, record_quota_result_best_effort, QuotaSample, QuotaSource, QuotaTrend,
+};
+use crate::services::{CredentialStatus, ProviderService, SpeedtestService, StreamCheckService};
 use crate::store::AppState;
 
-const AUTH_PROVIDER_CODEX_OAUTH: &str = "codex_oauth";
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ProviderModelFetchStrategy {
-    Bearer,
-    Anthropic,
-    GoogleApiKey,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum ModelFetchSource {
-    Http(ModelFetchTarget),
-    CodexOAuth { account_id: Option<String> },
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ModelFetchTarget {
-    base_url: String,
-    is_full_url: bool,
-    auth_value: Option<String>,
-    custom_user_agent: Option<String>,
-    strategy: ProviderModelFetchStrategy,
-}
-
 #[derive(Default)]
 struct ClaudeConfig {
     api_key: Option<String>,
@@ -80,6 +64,9 @@ pub(crate) fn list_providers(app_type: AppType) -> Result<(), AppError> {
         return Ok(());
     }
 
+    let missing_models =
+        crate::services::model_watch::missing_models_by_provider(&app_type, providers.values());
+
     let mut table = create_table();
     table.set_header(vec!["", "ID", "Name", "API URL"]);
 
@@ -94,14 +81,28 @@ pub(crate) fn list_providers(app_type: AppType) -> Result<(), AppError> {
     for (id, provider) in provider_list {
         let current_marker = if id == current_id { "✓" } else { " " };
         let api_url = extract_api_url(&provider, &app_type).unwrap_or_else(|| "N/A".to_string());
+        let name = if missing_models.contains_key(&id) {
+            format!("{} ⚠", provider.name)
+        } else {
+            provider.name
+        };
 
-        table.add_row(vec![current_marker.to_string(), id, provider.name, api_url]);
+        table.add_row(vec![current_marker.to_string(), id, name, api_url]);
     }
 
     println!("{}", table);
     println!("\n{} Application: {}", info("ℹ"), app_str);
     println!("{} Current: {}", info("→"), highlight(&current_id));
 
+    let mut flagged: Vec<_> = missing_models.iter().collect();
+    flagged.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if !flagged.is_empty() {
+        println!();
+    }
+    for (provider_id, entries) in flagged {
+        super::provider_models::print_missing_models(provider_id, entries);
+    }
+
     Ok(())
 }
 
@@ -114,7 +115,21 @@ pub(crate) fn show_current(app_type: AppType) -> Result<(), AppError> {
         .get(&current_id)
         .ok_or_else(|| AppError::Message(format!("Current provider '{}' not found", current_id)))?;
 
-    println!("{}", highlight("Current Provider"));
+    print_provider_details("Current Provider", app_type, provider);
+    Ok(())
+}
+
+pub(crate) fn show_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
+    let state = get_state()?;
+    let provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+
+    print_provider_details("Provider", app_type, &provider);
+    Ok(())
+}
+
+fn print_provider_details(title: &str, app_type: AppType, provider: &Provider) {
+    let current_id = &provider.id;
+    println!("{}", highlight(title));
     println!("{}", "═".repeat(60));
 
     println!("\n{}", highlight(texts::basic_info_section_header()));
@@ -183,9 +198,37 @@ pub(crate) fn show_current(app_type: AppType) -> Result<(), AppError> {
         );
     }
 
+    print_secret_references(&provider.settings_config);
+    super::provider_keys::print_key_pool(&app_type, provider);
+
     println!("\n{}", "─".repeat(60));
+}
 
-    Ok(())
+/// Resolves every secret reference so a broken one is reported here rather
+/// than at the next switch or proxied request.
+fn print_secret_references(settings: &Value) {
+    let references = crate::secret_ref::references(settings);
+    if references.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n{}",
+        highlight(crate::t!("Secret References", "密钥引用"))
+    );
+    for (path, reference) in references {
+        let status = match crate::secret_ref::resolve_value(&reference) {
+            Ok(_) => success(crate::t!("✓ resolved", "✓ 可解析")),
+            Err(err) => error(&format!("✗ {err}")),
+        };
+        let shown = if crate::vault::is_sealed(&reference) {
+            crate::t!("vault (encrypted)", "加密库（密文）").to_string()
+        } else {
+            reference
+        };
+        println!("  {}: {shown}", path.join("."));
+        println!("    {status}");
+    }
 }
 
 pub(crate) fn speedtest_provider(app_type: AppType, id: &str) -> Result<(), AppError> {
@@ -288,7 +331,8 @@ pub(crate) fn fetch_models_provider(app_type: AppType, id: &str) -> Result<(), A
     let provider = providers
         .get(id)
         .ok_or_else(|| AppError::Message(format!("Provider '{}' not found", id)))?;
-    let source = model_fetch_source(provider, &app_type)?;
+    let provider = &crate::key_pool::resolve_for_live(&app_type, provider)?;
+    let source = models::model_fetch_source(provider, &app_type)?;
 
     println!(
         "{}",
@@ -307,7 +351,7 @@ pub(crate) fn fetch_models_once(
     app_type: AppType,
     base_url: Option<&str>,
     api_key: Option<&str>,
-    strategy: Option<ProviderModelFetchStrategy>,
+    strategy: Option<ModelFetchStrategy>,
 ) -> Result<(), AppError> {
     let target = one_off_model_fetch_target(&app_type, base_url, api_key, strategy)?;
     let source = ModelFetchSource::Http(target);
@@ -344,25 +388,7 @@ fn fetch_models_from_source(source: &ModelFetchSource) -> Result<Vec<String>, Ap
     let runtime = tokio::runtime::Runtime::new()
         .map_err(|e| AppError::Message(format!("Failed to create async runtime: {}", e)))?;
 
-    match &source {
-        ModelFetchSource::Http(target) => runtime.block_on(async {
-            crate::cli::tui::fetch_provider_models_for_tui(
-                &target.base_url,
-                target.is_full_url,
-                target.auth_value.as_deref(),
-                target.custom_user_agent.as_deref(),
-                to_tui_strategy(target.strategy),
-            )
-            .await
-            .map_err(AppError::Message)
-        }),
-        ModelFetchSource::CodexOAuth { account_id } => runtime.block_on(async {
-            CodexOAuthService::get_models(account_id.as_deref())
-                .await
-                .map(|models| models.into_iter().map(|model| model.id).collect())
-                .map_err(AppError::Message)
-        }),
-    }
+    runtime.block_on(models::fetch_models_from_source(source))
 }
 
 fn print_fetched_models(models: &[String]) {
@@ -398,7 +424,9 @@ pub(crate) fn quota_provider(app_type: AppType, id: &str, json: bool) -> Result<
     let output = if let Some(target) = target {
         let runtime = tokio::runtime::Runtime::new()
             .map_err(|e| AppError::Message(format!("Failed to create async runtime: {}", e)))?;
-        match runtime.block_on(query_quota(&target)) {
+        let result = runtime.block_on(query_quota(&target));
+        record_quota_result_best_effort(&target, QuotaSource::Cli, queried_at, &result);
+        match result {
             Ok(result) => quota_output_from_result(
                 app_type,
                 id.to_string(),
@@ -447,6 +475,427 @@ pub(crate) fn quota_provider(app_type: AppType, id: &str, json: bool) -> Result<
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderQuotaHistoryOutput {
+    app: AppType,
+    provider_id: String,
+    provider_name: String,
+    days: u32,
+    trends: Vec<QuotaTrend>,
+    samples: Vec<QuotaSample>,
+}
+
+pub(crate) fn quota_history(
+    app_type: AppType,
+    id: &str,
+    days: u32,
+    json: bool,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let providers = ProviderService::list(&state, app_type.clone())?;
+    let provider = providers
+        .get(id)
+        .ok_or_else(|| AppError::Message(format!("Provider '{}' not found", id)))?;
+    let provider_name = provider_display_name(&app_type, id, provider);
+    let since = chrono::Utc::now().timestamp_millis() - i64::from(days) * 24 * 60 * 60 * 1000;
+    let samples = crate::local_store::LocalStore::open()?.quota_samples_since(
+        app_type.as_str(),
+        Some(id),
+        since,
+    )?;
+    let trends = quota_trends(&samples);
+
+    if json {
+        let output = ProviderQuotaHistoryOutput {
+            app: app_type,
+            provider_id: id.to_string(),
+            provider_name,
+            days,
+            trends,
+            samples,
+        };
+        println!(
+            "{}",
+            to_json(&output).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    println!("{}", highlight(&format!("{provider_name} ({id})")));
+    if samples.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "No quota history in the last {days} day(s). The daemon records results for providers with an auto-query interval; `provider quota {id}` records one now."
+            ))
+        );
+        return Ok(());
+    }
+
+    if trends.is_empty() {
+        println!("{}", warning("No successful quota results in this range."));
+    } else {
+        let mut table = create_table();
+        table.set_header(vec![
+            "Tier",
+            "Remaining",
+            "Trend",
+            "Burn / day",
+            "Days until empty",
+        ]);
+        for trend in &trends {
+            let unit = trend.unit.as_deref().unwrap_or("");
+            table.add_row(vec![
+                if trend.tier.is_empty() {
+                    "-".to_string()
+                } else {
+                    trend.tier.clone()
+                },
+                format!("{} {unit}", usage_number(trend.latest_remaining))
+                    .trim()
+                    .to_string(),
+                quota_sparkline(&trend.points, 24),
+                trend
+                    .burn_per_day
+                    .map(|burn| format!("{} {unit}", usage_number(burn)).trim().to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                days_until_empty_label(trend.days_until_empty),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    let failures = samples.iter().filter(|sample| !sample.success).count();
+    let queries = samples
+        .iter()
+        .map(|sample| sample.queried_at)
+        .collect::<std::collections::BTreeSet<_>>()
+        .len();
+    println!(
+        "{}",
+        info(&format!(
+            "{queries} quota quer{} in the last {days} day(s), {failures} failed.",
+            if queries == 1 { "y" } else { "ies" }
+        ))
+    );
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderHealthOutput {
+    app: AppType,
+    since: i64,
+    monitor: crate::settings::HealthMonitorSettings,
+    providers: Vec<ProviderHealthSummary>,
+}
+
+/// Parse a history window such as `90m`, `24h` or `7d` into seconds.
+pub(crate) fn parse_history_window(raw: &str) -> Result<i64, String> {
+    let raw = raw.trim();
+    let (value, unit) = raw.split_at(raw.len().saturating_sub(1));
+    let multiplier = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "expected a window like 30m, 24h or 7d, got '{raw}'"
+            ))
+        }
+    };
+    match value.parse::<i64>() {
+        Ok(value) if value > 0 => Ok(value * multiplier),
+        _ => Err(format!(
+            "expected a window like 30m, 24h or 7d, got '{raw}'"
+        )),
+    }
+}
+
+pub(crate) fn provider_health(
+    app_type: AppType,
+    id: Option<&str>,
+    since_secs: i64,
+    json: bool,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    if let Some(id) = id {
+        let providers = ProviderService::list(&state, app_type.clone())?;
+        if !providers.contains_key(id) {
+            return Err(AppError::Message(format!("Provider '{}' not found", id)));
+        }
+    }
+    let since = chrono::Utc::now().timestamp() - since_secs;
+    let store = LocalStore::open().ok();
+    let entries = health_monitor::load_checks(
+        &state.db,
+        store.as_ref(),
+        Some(app_type.as_str()),
+        id,
+        since,
+    )?;
+    let summaries = health_monitor::summarize(&entries);
+    let monitor = crate::settings::get_health_monitor_settings();
+
+    if json {
+        let output = ProviderHealthOutput {
+            app: app_type,
+            since,
+            monitor,
+            providers: summaries,
+        };
+        println!(
+            "{}",
+            to_json(&output).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    let scope = if monitor.all_providers {
+        "all providers"
+    } else {
+        "failover queue"
+    };
+    if monitor.enabled {
+        println!(
+            "{}",
+            info(&format!(
+                "Background monitor: every {}s ({scope}); runs while the daemon is up.",
+                monitor.interval_secs
+            ))
+        );
+    } else {
+        println!("{}", info("Background monitor: disabled."));
+    }
+
+    if summaries.is_empty() {
+        println!(
+            "{}",
+            info("No health checks in this window. Run `provider stream-check <id>` or start the daemon with the monitor enabled.")
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec![
+        "Provider",
+        "Checks",
+        "Uptime",
+        "Avg",
+        "p95",
+        "Latency",
+        "Last check",
+    ]);
+    for summary in &summaries {
+        let last = format!(
+            "{} {}",
+            summary.last_status,
+            format_check_time(summary.last_tested_at)
+        );
+        table.add_row(vec![
+            format!("{} ({})", summary.provider_name, summary.provider_id),
+            summary.probes.to_string(),
+            format!("{:.1}%", summary.uptime_pct),
+            latency_label(summary.avg_latency_ms),
+            latency_label(summary.p95_latency_ms),
+            quota_sparkline(&summary.latencies, 24),
+            if summary.last_message.is_empty() || summary.last_status == "operational" {
+                last
+            } else {
+                format!("{last}: {}", summary.last_message)
+            },
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CapabilityMatrixRow {
+    provider_id: String,
+    model: String,
+    capability: String,
+    status: String,
+    detail: Option<String>,
+    latency_ms: Option<u64>,
+    probed_at: i64,
+}
+
+fn find_probe_target(app_type: &AppType, id: &str) -> Result<(AppState, Provider), AppError> {
+    if *app_type != AppType::Claude {
+        return Err(AppError::Message(
+            "Capability probes support Claude providers only".to_string(),
+        ));
+    }
+    let state = get_state()?;
+    let provider = ProviderService::list(&state, app_type.clone())?
+        .get(id)
+        .cloned()
+        .ok_or_else(|| AppError::Message(format!("Provider '{}' not found", id)))?;
+    Ok((state, provider))
+}
+
+pub(crate) fn probe_provider(
+    app_type: AppType,
+    id: &str,
+    options: ProbeOptions,
+    json: bool,
+) -> Result<(), AppError> {
+    let (state, provider) = find_probe_target(&app_type, id)?;
+    let request_model = options
+        .model
+        .clone()
+        .unwrap_or_else(|| capability_probe::DEFAULT_PROBE_MODEL.to_string());
+    let upstream_model = capability_probe::upstream_model(&provider, &request_model);
+
+    if !json {
+        println!(
+            "{}",
+            info(&format!(
+                "Probing '{}' with model {}...",
+                provider.name,
+                if upstream_model == request_model {
+                    upstream_model.clone()
+                } else {
+                    format!("{request_model} → {upstream_model}")
+                }
+            ))
+        );
+    }
+
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::Message(format!("Failed to create async runtime: {}", e)))?;
+    let report = runtime.block_on(capability_probe::run_probe_suite(
+        state.db.clone(),
+        &provider,
+        &options,
+    ))?;
+    capability_probe::save_report(&app_type, &report)?;
+
+    if json {
+        println!(
+            "{}",
+            to_json(&report).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Capability", "Result", "Latency", "Detail"]);
+    for outcome in &report.outcomes {
+        table.add_row(vec![
+            outcome.capability.label().to_string(),
+            probe_status_label(outcome.status.as_str()),
+            latency_label(outcome.latency_ms),
+            outcome.detail.clone().unwrap_or_default(),
+        ]);
+    }
+    println!("{table}");
+
+    let failed = report
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.status == ProbeStatus::Failed)
+        .count();
+    if failed > 0 {
+        println!(
+            "{}",
+            warning(&format!(
+                "{failed} probe(s) were inconclusive; the proxy keeps its defaults for those."
+            ))
+        );
+    } else {
+        println!(
+            "{}",
+            success("✓ Capability matrix saved; the proxy applies it within a minute.")
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn show_capability_matrix(
+    app_type: AppType,
+    id: &str,
+    json: bool,
+) -> Result<(), AppError> {
+    find_probe_target(&app_type, id)?;
+    let records = LocalStore::open()?.list_capability_records(app_type.as_str(), Some(id))?;
+
+    if json {
+        let rows: Vec<_> = records
+            .into_iter()
+            .map(|record| CapabilityMatrixRow {
+                provider_id: record.provider_id,
+                model: record.model,
+                capability: record.capability,
+                status: record.status,
+                detail: record.detail,
+                latency_ms: record.latency_ms,
+                probed_at: record.probed_at,
+            })
+            .collect();
+        println!(
+            "{}",
+            to_json(&rows).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "No capability probes recorded for '{id}'. Run `provider probe {id}`."
+            ))
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["Model", "Capability", "Result", "Latency", "Probed"]);
+    for record in &records {
+        let capability = capability_probe::Capability::from_str(&record.capability)
+            .map(|capability| capability.label().to_string())
+            .unwrap_or_else(|| record.capability.clone());
+        table.add_row(vec![
+            record.model.clone(),
+            capability,
+            probe_status_label(&record.status),
+            latency_label(record.latency_ms),
+            format_check_time(record.probed_at),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn probe_status_label(status: &str) -> String {
+    match status {
+        "supported" => success("✓ supported"),
+        "unsupported" => error("✗ unsupported"),
+        "failed" => warning("? failed"),
+        other => other.to_string(),
+    }
+}
+
+fn latency_label(latency_ms: Option<u64>) -> String {
+    latency_ms
+        .map(|ms| format!("{ms}ms"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+pub(crate) fn format_check_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
 fn quota_output_from_result(
     app: AppType,
     provider_id: String,
@@ -766,134 +1215,11 @@ fn quota_percent_text(utilization: f64) -> String {
     format!("{:.0}%", utilization.clamp(0.0, 100.0))
 }
 
-fn model_fetch_target(
-    provider: &Provider,
-    app_type: &AppType,
-) -> Result<ModelFetchTarget, AppError> {
-    let base_url = StreamCheckService::extract_base_url(provider, app_type)?;
-    let base_url = base_url.trim().trim_end_matches('/').to_string();
-    if base_url.is_empty() {
-        return Err(AppError::Message(format!(
-            "No API URL configured for provider '{}'",
-            provider.id
-        )));
-    }
-    let custom_user_agent = provider
-        .meta
-        .as_ref()
-        .and_then(|meta| meta.custom_user_agent.clone());
-    let is_full_url = matches!(app_type, AppType::Claude | AppType::Codex)
-        && !provider.is_codex_oauth()
-        && provider
-            .meta
-            .as_ref()
-            .and_then(|meta| meta.is_full_url)
-            .unwrap_or(false);
-
-    match app_type {
-        AppType::Claude => {
-            let auth_value = StreamCheckService::extract_claude_key(provider).ok_or_else(|| {
-                AppError::Message(format!("Missing API key for provider '{}'", provider.id))
-            })?;
-            let strategy = if claude_uses_bearer_auth(provider, &base_url) {
-                ProviderModelFetchStrategy::Bearer
-            } else {
-                ProviderModelFetchStrategy::Anthropic
-            };
-
-            Ok(ModelFetchTarget {
-                base_url,
-                is_full_url,
-                auth_value: Some(auth_value),
-                custom_user_agent,
-                strategy,
-            })
-        }
-        AppType::Codex => {
-            Ok(ModelFetchTarget {
-                base_url,
-                is_full_url,
-                auth_value: Some(StreamCheckService::extract_codex_key(provider).ok_or_else(
-                    || AppError::Message(format!("Missing API key for provider '{}'", provider.id)),
-                )?),
-                custom_user_agent,
-                strategy: ProviderModelFetchStrategy::Bearer,
-            })
-        }
-        AppType::Gemini => {
-            let (auth_value, strategy) = extract_gemini_model_fetch_auth(provider)?;
-            Ok(ModelFetchTarget {
-                base_url,
-                is_full_url,
-                auth_value: Some(auth_value),
-                custom_user_agent,
-                strategy,
-            })
-        }
-        AppType::OpenCode => Ok(ModelFetchTarget {
-            base_url,
-            is_full_url,
-            auth_value: Some(
-                provider
-                    .settings_config
-                    .get("options")
-                    .and_then(|options| options.get("apiKey"))
-                    .and_then(|value| value.as_str())
-                    .map(str::trim)
-                    .filter(|value| !value.is_empty())
-                    .map(str::to_string)
-                    .ok_or_else(|| {
-                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
-                    })?,
-            ),
-            custom_user_agent,
-            strategy: ProviderModelFetchStrategy::Bearer,
-        }),
-        AppType::Hermes => Ok(ModelFetchTarget {
-            base_url,
-            is_full_url,
-            auth_value: Some(
-                provider
-                    .settings_config
-                    .get("apiKey")
-                    .or_else(|| provider.settings_config.get("api_key"))
-                    .and_then(|value| value.as_str())
-                    .map(str::trim)
-                    .filter(|value| !value.is_empty())
-                    .map(str::to_string)
-                    .ok_or_else(|| {
-                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
-                    })?,
-            ),
-            custom_user_agent,
-            strategy: ProviderModelFetchStrategy::Bearer,
-        }),
-        AppType::OpenClaw => Ok(ModelFetchTarget {
-            base_url,
-            is_full_url,
-            auth_value: Some(
-                provider
-                    .settings_config
-                    .get("apiKey")
-                    .and_then(|value| value.as_str())
-                    .map(str::trim)
-                    .filter(|value| !value.is_empty())
-                    .map(str::to_string)
-                    .ok_or_else(|| {
-                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
-                    })?,
-            ),
-            custom_user_agent,
-            strategy: ProviderModelFetchStrategy::Bearer,
-        }),
-    }
-}
-
 fn one_off_model_fetch_target(
     app_type: &AppType,
     base_url: Option<&str>,
     api_key: Option<&str>,
-    strategy: Option<ProviderModelFetchStrategy>,
+    strategy: Option<ModelFetchStrategy>,
 ) -> Result<ModelFetchTarget, AppError> {
     let base_url = base_url
         .map(str::trim)
@@ -916,108 +1242,12 @@ fn one_off_model_fetch_target(
     })
 }
 
-fn default_one_off_model_fetch_strategy(app_type: &AppType) -> ProviderModelFetchStrategy {
+fn default_one_off_model_fetch_strategy(app_type: &AppType) -> ModelFetchStrategy {
     match app_type {
-        AppType::Claude => ProviderModelFetchStrategy::Anthropic,
-        AppType::Gemini => ProviderModelFetchStrategy::GoogleApiKey,
+        AppType::Claude => ModelFetchStrategy::Anthropic,
+        AppType::Gemini => ModelFetchStrategy::GoogleApiKey,
         AppType::Codex | AppType::OpenCode | AppType::Hermes | AppType::OpenClaw => {
-            ProviderModelFetchStrategy::Bearer
-        }
-    }
-}
-
-fn model_fetch_source(
-    provider: &Provider,
-    app_type: &AppType,
-) -> Result<ModelFetchSource, AppError> {
-    if matches!(app_type, AppType::Claude) && provider.is_codex_oauth() {
-        return Ok(ModelFetchSource::CodexOAuth {
-            account_id: codex_oauth_account_id(provider),
-        });
-    }
-
-    model_fetch_target(provider, app_type).map(ModelFetchSource::Http)
-}
-
-fn codex_oauth_account_id(provider: &Provider) -> Option<String> {
-    provider
-        .meta
-        .as_ref()
-        .and_then(|meta| meta.managed_account_id_for(AUTH_PROVIDER_CODEX_OAUTH))
-}
-
-fn claude_uses_bearer_auth(provider: &Provider, base_url: &str) -> bool {
-    if base_url.contains("openrouter.ai") {
-        return true;
-    }
-
-    provider
-        .settings_config
-        .get("auth_mode")
-        .and_then(|value| value.as_str())
-        .or_else(|| {
-            provider
-                .settings_config
-                .get("env")
-                .and_then(|env| env.get("AUTH_MODE"))
-                .and_then(|value| value.as_str())
-        })
-        .is_some_and(|value| value == "bearer_only")
-}
-
-fn extract_gemini_model_fetch_auth(
-    provider: &Provider,
-) -> Result<(String, ProviderModelFetchStrategy), AppError> {
-    let env_map = crate::gemini_config::json_to_env(&provider.settings_config)?;
-
-    if let Some(token) = env_map
-        .get("GOOGLE_ACCESS_TOKEN")
-        .or_else(|| env_map.get("GEMINI_ACCESS_TOKEN"))
-        .map(String::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
-        return Ok((token.to_string(), ProviderModelFetchStrategy::Bearer));
-    }
-
-    let key = env_map
-        .get("GEMINI_API_KEY")
-        .or_else(|| env_map.get("GOOGLE_API_KEY"))
-        .or_else(|| env_map.get("API_KEY"))
-        .map(String::as_str)
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .ok_or_else(|| {
-            AppError::Message(format!("Missing API key for provider '{}'", provider.id))
-        })?;
-
-    if key.starts_with("ya29.") {
-        return Ok((key.to_string(), ProviderModelFetchStrategy::Bearer));
-    }
-
-    if let Some(access_token) = parse_access_token_blob(key) {
-        return Ok((access_token, ProviderModelFetchStrategy::Bearer));
-    }
-
-    Ok((key.to_string(), ProviderModelFetchStrategy::GoogleApiKey))
-}
-
-fn parse_access_token_blob(raw: &str) -> Option<String> {
-    let value: Value = serde_json::from_str(raw.trim()).ok()?;
-    value
-        .get("access_token")
-        .and_then(|value| value.as_str())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string)
-}
-
-fn to_tui_strategy(strategy: ProviderModelFetchStrategy) -> crate::cli::tui::ModelFetchStrategy {
-    match strategy {
-        ProviderModelFetchStrategy::Bearer => crate::cli::tui::ModelFetchStrategy::Bearer,
-        ProviderModelFetchStrategy::Anthropic => crate::cli::tui::ModelFetchStrategy::Anthropic,
-        ProviderModelFetchStrategy::GoogleApiKey => {
-            crate::cli::tui::ModelFetchStrategy::GoogleApiKey
+            ModelFetchStrategy::Bearer
         }
     }
 }
@@ -1308,169 +1538,6 @@ base_url = "https://current.example.com/v1"
         )));
     }
 
-    #[test]
-    fn model_fetch_target_for_claude_uses_base_url_and_api_key() {
-        let provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "env": {
-                    "ANTHROPIC_BASE_URL": "https://claude.example.com",
-                    "ANTHROPIC_API_KEY": "sk-claude"
-                }
-            }),
-            None,
-        );
-
-        let target = model_fetch_target(&provider, &AppType::Claude)
-            .expect("claude provider should resolve fetch target");
-
-        assert_eq!(target.base_url, "https://claude.example.com");
-        assert_eq!(target.auth_value.as_deref(), Some("sk-claude"));
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::Anthropic);
-    }
-
-    #[test]
-    fn saved_provider_model_fetch_target_keeps_custom_user_agent() {
-        let mut provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "env": {
-                    "ANTHROPIC_BASE_URL": "https://claude.example.com",
-                    "ANTHROPIC_API_KEY": "sk-claude"
-                }
-            }),
-            None,
-        );
-        provider.meta = Some(ProviderMeta {
-            custom_user_agent: Some("cc-switch-model-fetch/test".to_string()),
-            is_full_url: Some(true),
-            ..Default::default()
-        });
-
-        let target = model_fetch_target(&provider, &AppType::Claude)
-            .expect("saved provider should resolve fetch target");
-
-        assert_eq!(
-            target.custom_user_agent.as_deref(),
-            Some("cc-switch-model-fetch/test")
-        );
-        assert!(target.is_full_url);
-    }
-
-    #[test]
-    fn model_fetch_target_for_claude_supports_openrouter_bearer_mode() {
-        let provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "env": {
-                    "ANTHROPIC_BASE_URL": "https://openrouter.ai/api/v1",
-                    "OPENROUTER_API_KEY": "sk-openrouter"
-                }
-            }),
-            None,
-        );
-
-        let target = model_fetch_target(&provider, &AppType::Claude)
-            .expect("openrouter provider should resolve fetch target");
-
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::Bearer);
-        assert_eq!(target.auth_value.as_deref(), Some("sk-openrouter"));
-    }
-
-    #[test]
-    fn model_fetch_source_for_claude_codex_oauth_uses_managed_auth_without_config_key() {
-        let mut provider = Provider::with_id(
-            "codex".to_string(),
-            "Codex OAuth".to_string(),
-            json!({ "env": {} }),
-            None,
-        );
-        provider.meta = Some(ProviderMeta {
-            provider_type: Some("codex_oauth".to_string()),
-            ..Default::default()
-        });
-
-        let source = model_fetch_source(&provider, &AppType::Claude)
-            .expect("codex oauth provider should use managed auth");
-
-        assert_eq!(source, ModelFetchSource::CodexOAuth { account_id: None });
-    }
-
-    #[test]
-    fn model_fetch_source_for_claude_codex_oauth_keeps_bound_account_id() {
-        let mut provider = Provider::with_id(
-            "codex".to_string(),
-            "Codex OAuth".to_string(),
-            json!({ "env": {} }),
-            None,
-        );
-        provider.meta = Some(ProviderMeta {
-            provider_type: Some("codex_oauth".to_string()),
-            auth_binding: Some(AuthBinding {
-                source: AuthBindingSource::ManagedAccount,
-                auth_provider: Some("codex_oauth".to_string()),
-                account_id: Some("acc-123".to_string()),
-            }),
-            ..Default::default()
-        });
-
-        let source = model_fetch_source(&provider, &AppType::Claude)
-            .expect("codex oauth provider should use managed auth");
-
-        assert_eq!(
-            source,
-            ModelFetchSource::CodexOAuth {
-                account_id: Some("acc-123".to_string())
-            }
-        );
-    }
-
-    #[test]
-    fn model_fetch_target_for_codex_supports_env_openai_key() {
-        let provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "env": {
-                    "OPENAI_API_KEY": "sk-codex-env"
-                },
-                "config": "model_provider = \"demo\"\n\n[model_providers.demo]\nbase_url = \"https://codex.example.com/v1\"\n"
-            }),
-            None,
-        );
-
-        let target = model_fetch_target(&provider, &AppType::Codex)
-            .expect("codex provider should resolve fetch target");
-
-        assert_eq!(target.base_url, "https://codex.example.com/v1");
-        assert_eq!(target.auth_value.as_deref(), Some("sk-codex-env"));
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::Bearer);
-    }
-
-    #[test]
-    fn model_fetch_target_for_gemini_supports_access_token() {
-        let provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "env": {
-                    "GOOGLE_GEMINI_BASE_URL": "https://generativelanguage.googleapis.com",
-                    "GOOGLE_ACCESS_TOKEN": "ya29.token"
-                }
-            }),
-            None,
-        );
-
-        let target = model_fetch_target(&provider, &AppType::Gemini)
-            .expect("gemini provider should resolve oauth fetch target");
-
-        assert_eq!(target.auth_value.as_deref(), Some("ya29.token"));
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::Bearer);
-    }
-
     #[test]
     fn one_off_model_fetch_target_defaults_strategy_by_app_and_trims_input() {
         let target = one_off_model_fetch_target(
@@ -1484,7 +1551,7 @@ base_url = "https://current.example.com/v1"
         assert_eq!(target.base_url, "https://gemini.example.com");
         assert_eq!(target.auth_value.as_deref(), Some("sk-gemini"));
         assert_eq!(target.custom_user_agent, None);
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::GoogleApiKey);
+        assert_eq!(target.strategy, ModelFetchStrategy::GoogleApiKey);
     }
 
     #[test]
@@ -1493,35 +1560,12 @@ base_url = "https://current.example.com/v1"
             &AppType::Claude,
             Some("https://openrouter.ai/api/v1"),
             None,
-            Some(ProviderModelFetchStrategy::Bearer),
+            Some(ModelFetchStrategy::Bearer),
         )
         .expect("one-off target should be built");
 
         assert_eq!(target.base_url, "https://openrouter.ai/api/v1");
         assert_eq!(target.auth_value, None);
-        assert_eq!(target.strategy, ProviderModelFetchStrategy::Bearer);
-    }
-
-    #[test]
-    fn model_fetch_target_rejects_empty_base_url() {
-        let provider = Provider::with_id(
-            "demo".to_string(),
-            "Demo".to_string(),
-            json!({
-                "options": {
-                    "baseURL": "",
-                    "apiKey": "sk-opencode"
-                }
-            }),
-            None,
-        );
-
-        let err = model_fetch_target(&provider, &AppType::OpenCode)
-            .expect_err("empty base url should be rejected");
-
-        assert!(
-            err.to_string().contains("options.baseURL"),
-            "unexpected error: {err}"
-        );
+        assert_eq!(target.strategy, ModelFetchStrategy::Bearer);
     }
 }
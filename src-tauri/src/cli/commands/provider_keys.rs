@@ -0,0 +1,405 @@
+//! `cc-switch provider keys`: extra API keys for one provider.
+//!
+//! The proxy rotates through the pool and parks keys that upstream rejects or
+//! reports out of quota; see [`crate::key_pool`].
+
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::key_pool::{self, KeyStatus, PRIMARY_LABEL};
+use crate::local_store::LocalStore;
+use crate::provider::{ApiKeyPool, KeyRotationStrategy, PooledApiKey, Provider, ProviderMeta};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const KEYS_AFTER_LONG_HELP: &str = "\
+The key in the provider's settings is the pool's `primary` key; the keys added
+here are used alongside it. Keys may be secret references (env:, file:, cmd:).
+
+Examples:
+  cc-switch provider keys add relay sk-second --label second
+  cc-switch provider keys strategy relay fill-first --cooldown 1800
+  cc-switch provider keys list relay
+  cc-switch provider keys enable relay second";
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = KEYS_AFTER_LONG_HELP)]
+pub enum ProviderKeysCommand {
+    /// Show the key pool and the state of each key
+    List {
+        /// Provider ID
+        id: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a key to the pool
+    Add {
+        /// Provider ID
+        id: String,
+        /// API key or secret reference
+        key: String,
+        /// Label used in logs and status output (default: key-N)
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// Remove a key from the pool
+    Remove {
+        /// Provider ID
+        id: String,
+        /// Label of the key to remove
+        label: String,
+    },
+    /// Choose how the proxy rotates through the pool
+    Strategy {
+        /// Provider ID
+        id: String,
+        #[arg(value_enum)]
+        strategy: KeyStrategyArg,
+        /// How long a rejected or exhausted key stays disabled, in seconds
+        #[arg(long, value_name = "SECS")]
+        cooldown: Option<u64>,
+    },
+    /// Re-enable a disabled key (every key when no label is given)
+    Enable {
+        /// Provider ID
+        id: String,
+        /// Label of the key to re-enable
+        label: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum KeyStrategyArg {
+    /// Use the next usable key for every request
+    RoundRobin,
+    /// Stay on the first usable key until it is disabled
+    FillFirst,
+}
+
+impl From<KeyStrategyArg> for KeyRotationStrategy {
+    fn from(value: KeyStrategyArg) -> Self {
+        match value {
+            KeyStrategyArg::RoundRobin => Self::RoundRobin,
+            KeyStrategyArg::FillFirst => Self::FillFirst,
+        }
+    }
+}
+
+pub fn execute(cmd: ProviderKeysCommand, app_type: AppType) -> Result<(), AppError> {
+    match cmd {
+        ProviderKeysCommand::List { id, json } => list(app_type, &id, json),
+        ProviderKeysCommand::Add { id, key, label } => add(app_type, &id, key, label),
+        ProviderKeysCommand::Remove { id, label } => remove(app_type, &id, &label),
+        ProviderKeysCommand::Strategy {
+            id,
+            strategy,
+            cooldown,
+        } => set_strategy(app_type, &id, strategy.into(), cooldown),
+        ProviderKeysCommand::Enable { id, label } => enable(app_type, &id, label.as_deref()),
+    }
+}
+
+#[derive(Serialize)]
+struct KeyStatusJson {
+    label: String,
+    key: String,
+    usable: bool,
+    disabled_until: Option<i64>,
+    disabled_reason: Option<String>,
+    last_status: Option<u16>,
+    success_count: u64,
+    failure_count: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    total_cost_usd: f64,
+    last_used_at: Option<i64>,
+}
+
+fn list(app_type: AppType, id: &str, json: bool) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    let statuses = key_pool::statuses(&LocalStore::open()?, &app_type, &provider)?;
+
+    if json {
+        let now = chrono::Utc::now().timestamp();
+        let rows: Vec<_> = statuses
+            .iter()
+            .map(|status| {
+                let key_state = status.state.clone().unwrap_or_default();
+                KeyStatusJson {
+                    label: status.key.label.clone(),
+                    key: describe_key(&status.key.stored),
+                    usable: status.is_usable_at(now),
+                    disabled_until: key_state.disabled_until.filter(|until| *until > now),
+                    disabled_reason: key_state.disabled_reason,
+                    last_status: key_state.last_status,
+                    success_count: key_state.success_count,
+                    failure_count: key_state.failure_count,
+                    input_tokens: key_state.input_tokens,
+                    output_tokens: key_state.output_tokens,
+                    total_cost_usd: key_state.total_cost_usd,
+                    last_used_at: key_state.last_used_at,
+                }
+            })
+            .collect();
+        println!(
+            "{}",
+            to_json(&rows).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!(
+            "{}",
+            info(crate::t!(
+                "No key pool. Add keys with `cc-switch provider keys add`.",
+                "未配置 Key 池，使用 `cc-switch provider keys add` 添加。"
+            ))
+        );
+        return Ok(());
+    }
+    print_pool_table(&provider, &statuses);
+    Ok(())
+}
+
+/// The key pool section of `provider show`.
+pub(crate) fn print_key_pool(app_type: &AppType, provider: &Provider) {
+    if key_pool::pool(provider).is_none() {
+        return;
+    }
+    let statuses =
+        match LocalStore::open().and_then(|store| key_pool::statuses(&store, app_type, provider)) {
+            Ok(statuses) if !statuses.is_empty() => statuses,
+            Ok(_) => return,
+            Err(err) => {
+                println!("\n{}", error(&format!("✗ {err}")));
+                return;
+            }
+        };
+    println!("\n{}", highlight(crate::t!("API Key Pool", "API Key 池")));
+    print_pool_table(provider, &statuses);
+}
+
+fn print_pool_table(provider: &Provider, statuses: &[KeyStatus]) {
+    if let Some(pool) = key_pool::pool(provider) {
+        println!(
+            "  {}: {}, {}: {}s",
+            crate::t!("Strategy", "策略"),
+            pool.strategy.as_str(),
+            crate::t!("cooldown", "停用时长"),
+            key_pool::cooldown(pool).as_secs()
+        );
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut table = create_table();
+    table.set_header(vec![
+        crate::t!("Label", "标签"),
+        "Key",
+        crate::t!("Status", "状态"),
+        crate::t!("OK", "成功"),
+        crate::t!("Failed", "失败"),
+        "Tokens",
+        crate::t!("Cost", "费用"),
+        crate::t!("Last used", "最近使用"),
+    ]);
+    for status in statuses {
+        let key_state = status.state.clone().unwrap_or_default();
+        let state_label = match key_state.disabled_until.filter(|until| *until > now) {
+            Some(until) => warning(&format!(
+                "{} ({}) → {}",
+                crate::t!("disabled", "已停用"),
+                key_state.disabled_reason.as_deref().unwrap_or("-"),
+                format_time(until)
+            )),
+            None => success(crate::t!("active", "可用")),
+        };
+        table.add_row(vec![
+            status.key.label.clone(),
+            describe_key(&status.key.stored),
+            state_label,
+            key_state.success_count.to_string(),
+            key_state.failure_count.to_string(),
+            (key_state.input_tokens + key_state.output_tokens).to_string(),
+            format!("${:.4}", key_state.total_cost_usd),
+            key_state
+                .last_used_at
+                .map(format_time)
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    println!("{table}");
+}
+
+fn add(app_type: AppType, id: &str, key: String, label: Option<String>) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let mut provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    if provider.configured_api_key(&app_type).is_none() {
+        return Err(AppError::localized(
+            "provider.keys.no_primary",
+            "该供应商没有 API Key，无法使用 Key 池",
+            "This provider has no API key to pool with",
+        ));
+    }
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err(AppError::InvalidInput(
+            "API key must not be empty".to_string(),
+        ));
+    }
+
+    let pool = pool_mut(&mut provider);
+    let label = match label.map(|label| label.trim().to_string()) {
+        Some(label) => {
+            validate_label(pool, &label)?;
+            label
+        }
+        None => (2..)
+            .map(|n| format!("key-{n}"))
+            .find(|label| validate_label(pool, label).is_ok())
+            .expect("an unused label"),
+    };
+    pool.keys.push(PooledApiKey {
+        label: label.clone(),
+        key,
+    });
+    ProviderService::update(&state, app_type, provider)?;
+
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {} '{label}'",
+            crate::t!("Added key", "已添加 Key")
+        ))
+    );
+    Ok(())
+}
+
+fn remove(app_type: AppType, id: &str, label: &str) -> Result<(), AppError> {
+    if label == PRIMARY_LABEL {
+        return Err(AppError::localized(
+            "provider.keys.remove_primary",
+            "primary 是供应商配置中的 Key，请通过编辑供应商修改",
+            "The primary key lives in the provider settings; edit the provider to change it",
+        ));
+    }
+    let state = AppState::try_new()?;
+    let mut provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    let pool = pool_mut(&mut provider);
+    let before = pool.keys.len();
+    pool.keys.retain(|key| key.label != label);
+    if pool.keys.len() == before {
+        return Err(key_not_found(label));
+    }
+    ProviderService::update(&state, app_type.clone(), provider)?;
+    LocalStore::open()?.delete_api_key_state(app_type.as_str(), id, label)?;
+
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {} '{label}'",
+            crate::t!("Removed key", "已移除 Key")
+        ))
+    );
+    Ok(())
+}
+
+fn set_strategy(
+    app_type: AppType,
+    id: &str,
+    strategy: KeyRotationStrategy,
+    cooldown: Option<u64>,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let mut provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    let pool = pool_mut(&mut provider);
+    pool.strategy = strategy;
+    if cooldown.is_some() {
+        pool.cooldown_secs = cooldown;
+    }
+    ProviderService::update(&state, app_type, provider)?;
+
+    println!(
+        "{}",
+        success(&format!(
+            "✓ {}: {}",
+            crate::t!("Key rotation", "Key 轮换策略"),
+            strategy.as_str()
+        ))
+    );
+    Ok(())
+}
+
+fn enable(app_type: AppType, id: &str, label: Option<&str>) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    if let Some(label) = label {
+        if !key_pool::pool_keys(&app_type, &provider)
+            .iter()
+            .any(|key| key.label == label)
+        {
+            return Err(key_not_found(label));
+        }
+    }
+    LocalStore::open()?.reset_api_key_state(app_type.as_str(), id, label)?;
+
+    println!(
+        "{}",
+        success(crate::t!("✓ Key re-enabled", "✓ Key 已重新启用"))
+    );
+    Ok(())
+}
+
+fn pool_mut(provider: &mut Provider) -> &mut ApiKeyPool {
+    provider
+        .meta
+        .get_or_insert_with(ProviderMeta::default)
+        .api_key_pool
+        .get_or_insert_with(ApiKeyPool::default)
+}
+
+fn validate_label(pool: &ApiKeyPool, label: &str) -> Result<(), AppError> {
+    if label.is_empty() || label == PRIMARY_LABEL || pool.keys.iter().any(|key| key.label == label)
+    {
+        return Err(AppError::localized(
+            "provider.keys.label_taken",
+            format!("Key 标签不可用: '{label}'"),
+            format!("Key label is empty or already in use: '{label}'"),
+        ));
+    }
+    Ok(())
+}
+
+fn key_not_found(label: &str) -> AppError {
+    AppError::localized(
+        "provider.keys.not_found",
+        format!("Key 池中没有 '{label}'"),
+        format!("No key labelled '{label}' in the pool"),
+    )
+}
+
+/// How a stored key is shown: references as written, plain keys masked.
+fn describe_key(stored: &str) -> String {
+    if crate::vault::is_sealed(stored) {
+        crate::t!("vault (encrypted)", "加密库（密文）").to_string()
+    } else if crate::secret_ref::is_reference(stored) {
+        stored.to_string()
+    } else {
+        super::universal::mask_api_key(stored)
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -0,0 +1,140 @@
+//! `cc-switch provider lint`: configuration checks and safe auto-fixes.
+//!
+//! The rules live in [`crate::services::ProviderService::lint_provider`];
+//! this module prints the findings and, with `--fix`, applies the safe ones.
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::{LintFinding, LintSeverity, ProviderService};
+use crate::store::AppState;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderLintReport {
+    provider_id: String,
+    findings: Vec<LintFinding>,
+}
+
+pub(crate) fn lint_providers(
+    app_type: AppType,
+    id: Option<&str>,
+    fix: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let providers: Vec<Provider> = match id {
+        Some(id) => vec![ProviderService::get_provider(&state, app_type.clone(), id)?],
+        None => ProviderService::list(&state, app_type.clone())?
+            .into_values()
+            .collect(),
+    };
+
+    let mut reports = Vec::new();
+    let mut fixed = 0;
+    for mut provider in providers {
+        let mut findings = ProviderService::lint_provider(&app_type, &provider);
+        if fix && findings.iter().any(LintFinding::has_safe_fix) {
+            let mut applied = Vec::new();
+            for fix in findings.iter().filter_map(|finding| finding.fix.as_ref()) {
+                if fix.is_safe() {
+                    ProviderService::apply_lint_fix(&app_type, &mut provider, fix)?;
+                    applied.push(fix.describe());
+                }
+            }
+            ProviderService::update(&state, app_type.clone(), provider.clone())?;
+            for description in &applied {
+                println!("{}", success(&format!("✓ {}: {description}", provider.id)));
+            }
+            fixed += applied.len();
+            findings = ProviderService::lint_provider(&app_type, &provider);
+        }
+        reports.push(ProviderLintReport {
+            provider_id: provider.id,
+            findings,
+        });
+    }
+    reports.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+
+    if json {
+        println!(
+            "{}",
+            to_json(&reports).map_err(|source| AppError::JsonSerialize { source })?
+        );
+    } else {
+        print_reports(&reports, fixed);
+    }
+
+    let failing = reports
+        .iter()
+        .filter(|report| {
+            report
+                .findings
+                .iter()
+                .any(|finding| finding.severity == LintSeverity::Error)
+        })
+        .count();
+    if failing > 0 {
+        return Err(AppError::Message(format!(
+            "{failing} provider(s) have configuration errors"
+        )));
+    }
+    Ok(())
+}
+
+fn print_reports(reports: &[ProviderLintReport], fixed: usize) {
+    let total: usize = reports.iter().map(|report| report.findings.len()).sum();
+    if total == 0 {
+        println!(
+            "{}",
+            success(&format!(
+                "✓ No problems found in {} provider(s).",
+                reports.len()
+            ))
+        );
+        return;
+    }
+
+    for report in reports.iter().filter(|report| !report.findings.is_empty()) {
+        println!("{}", highlight(&report.provider_id));
+        let mut table = create_table();
+        table.set_header(vec!["Severity", "Rule", "Problem", "Fix"]);
+        for finding in &report.findings {
+            let fix = match (&finding.fix, &finding.suggestion) {
+                (Some(fix), _) if fix.is_safe() => format!("{} (--fix)", fix.describe()),
+                (Some(fix), _) => fix.describe(),
+                (None, Some(suggestion)) => suggestion.clone(),
+                (None, None) => String::new(),
+            };
+            table.add_row(vec![
+                finding.severity.as_str().to_string(),
+                finding.rule.to_string(),
+                finding.message.clone(),
+                fix,
+            ]);
+        }
+        println!("{table}");
+    }
+
+    let safe = reports
+        .iter()
+        .flat_map(|report| &report.findings)
+        .filter(|finding| finding.has_safe_fix())
+        .count();
+    let summary = format!("{total} finding(s), {safe} fixable with --fix.");
+    if reports
+        .iter()
+        .flat_map(|report| &report.findings)
+        .any(|finding| finding.severity == LintSeverity::Error)
+    {
+        println!("{}", error(&summary));
+    } else {
+        println!("{}", warning(&summary));
+    }
+    if fixed > 0 {
+        println!("{}", info(&format!("Applied {fixed} fix(es).")));
+    }
+}
@@ -0,0 +1,239 @@
+//! `cc-switch provider models`: model list snapshots and retired-model remaps.
+//!
+//! The daemon refetches provider model lists in the background (see
+//! `config model-watch`); these commands refresh them on demand, show what
+//! changed and point role mappings at a replacement; see
+//! [`crate::services::model_watch`].
+
+use clap::Subcommand;
+
+use super::provider_inspect::{format_check_time, parse_history_window};
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::local_store::model_catalog::ModelChange;
+use crate::local_store::LocalStore;
+use crate::provider::Provider;
+use crate::services::model_watch::{self, MissingModel};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const MODELS_AFTER_LONG_HELP: &str = "\
+Snapshots of each provider's model list are kept in the local sidecar store.
+The first fetch is the baseline; later fetches record added and removed models.
+Role mappings (ANTHROPIC_DEFAULT_*_MODEL, the Codex `model`, OpenCode `models`)
+that point at a model the provider no longer lists are flagged in
+`provider list` and the TUI.
+
+Examples:
+  cc-switch provider models refresh
+  cc-switch provider models changes relay --since 30d
+  cc-switch provider models remap relay --from glm-4.6 --to glm-4.7
+  cc-switch config model-watch set --interval 21600";
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = MODELS_AFTER_LONG_HELP)]
+pub enum ProviderModelsCommand {
+    /// Fetch model lists now and report what changed
+    Refresh {
+        /// Only this provider (default: every provider of the app)
+        id: Option<String>,
+    },
+    /// Show models added or removed upstream
+    Changes {
+        /// Only this provider
+        id: Option<String>,
+        /// History window, e.g. 24h or 30d
+        #[arg(long, default_value = "30d", value_parser = parse_history_window)]
+        since: i64,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Point every role that uses a model at another one
+    Remap {
+        /// Provider ID
+        id: String,
+        /// Model to replace
+        #[arg(long)]
+        from: String,
+        /// Replacement model (default: the closest model the provider lists)
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
+
+pub fn execute(cmd: ProviderModelsCommand, app_type: AppType) -> Result<(), AppError> {
+    match cmd {
+        ProviderModelsCommand::Refresh { id } => refresh(app_type, id.as_deref()),
+        ProviderModelsCommand::Changes { id, since, json } => {
+            changes(app_type, id.as_deref(), since, json)
+        }
+        ProviderModelsCommand::Remap { id, from, to } => remap(app_type, &id, &from, to),
+    }
+}
+
+fn providers_in_scope(
+    state: &AppState,
+    app_type: &AppType,
+    id: Option<&str>,
+) -> Result<Vec<Provider>, AppError> {
+    let providers = ProviderService::list(state, app_type.clone())?;
+    match id {
+        Some(id) => providers
+            .get(id)
+            .cloned()
+            .map(|provider| vec![provider])
+            .ok_or_else(|| AppError::Message(format!("Provider '{}' not found", id))),
+        None => Ok(providers.into_values().collect()),
+    }
+}
+
+/// Remap hint for each flagged role, shared with `provider list`.
+pub(crate) fn print_missing_models(provider_id: &str, missing: &[MissingModel]) {
+    for entry in missing {
+        println!(
+            "{}",
+            warning(&format!(
+                "{provider_id}: {} = {} is no longer listed by the provider",
+                entry.field, entry.model
+            ))
+        );
+        let to = entry.suggestion.as_deref().unwrap_or("<model>");
+        println!(
+            "  cc-switch provider models remap {provider_id} --from {} --to {to}",
+            entry.model
+        );
+    }
+}
+
+fn refresh(app_type: AppType, id: Option<&str>) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let providers = providers_in_scope(&state, &app_type, id)?;
+    let store = LocalStore::open()?;
+    crate::services::global_proxy::initialize_http_client_from_disk_best_effort();
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::Message(format!("Failed to create async runtime: {}", e)))?;
+
+    let mut failures = 0;
+    for provider in &providers {
+        match runtime.block_on(model_watch::refresh_provider(&store, &app_type, provider)) {
+            Ok(diff) if diff.baseline => println!(
+                "{}",
+                info(&format!("{}: baseline snapshot recorded.", provider.id))
+            ),
+            Ok(diff) if diff.is_empty() => {
+                println!("{}", info(&format!("{}: no changes.", provider.id)))
+            }
+            Ok(diff) => {
+                for model in &diff.added {
+                    println!("{}", success(&format!("{}: + {model}", provider.id)));
+                }
+                for model in &diff.removed {
+                    println!("{}", warning(&format!("{}: - {model}", provider.id)));
+                }
+            }
+            Err(error) => {
+                failures += 1;
+                println!("{}", warning(&format!("{}: {error}", provider.id)));
+            }
+        }
+    }
+
+    let missing = model_watch::missing_models_by_provider(&app_type, &providers);
+    let mut flagged: Vec<_> = missing.iter().collect();
+    flagged.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (provider_id, entries) in flagged {
+        print_missing_models(provider_id, entries);
+    }
+
+    if id.is_some() && failures > 0 {
+        return Err(AppError::Message("Model list fetch failed".to_string()));
+    }
+    Ok(())
+}
+
+fn changes(
+    app_type: AppType,
+    id: Option<&str>,
+    since_secs: i64,
+    json: bool,
+) -> Result<(), AppError> {
+    let since = chrono::Utc::now().timestamp() - since_secs;
+    let changes: Vec<ModelChange> = if LocalStore::path()?.exists() {
+        LocalStore::open()?.list_model_changes(app_type.as_str(), id, since)?
+    } else {
+        Vec::new()
+    };
+
+    if json {
+        println!(
+            "{}",
+            to_json(&changes).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        println!(
+            "{}",
+            info("No model changes in this window. Run `provider models refresh` or keep the daemon running.")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        highlight(&format!("Model changes for {}", app_type.as_str()))
+    );
+    let mut table = create_table();
+    table.set_header(vec!["Detected", "Provider", "Change", "Model"]);
+    for change in changes.iter().rev() {
+        table.add_row(vec![
+            format_check_time(change.detected_at),
+            change.provider_id.clone(),
+            if change.change == "added" {
+                "+ added"
+            } else {
+                "- removed"
+            }
+            .to_string(),
+            change.model.clone(),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+fn remap(app_type: AppType, id: &str, from: &str, to: Option<String>) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let mut provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    let to = match to {
+        Some(to) => to,
+        None => model_watch::missing_models_by_provider(&app_type, [&provider])
+            .remove(id)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|entry| entry.model == from)
+            .and_then(|entry| entry.suggestion)
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!(
+                    "No replacement known for '{from}'; pass --to <model>"
+                ))
+            })?,
+    };
+
+    let changed = model_watch::remap_model(&app_type, &mut provider.settings_config, from, &to)?;
+    if changed.is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Provider '{id}' does not use model '{from}'"
+        )));
+    }
+    ProviderService::update(&state, app_type, provider)?;
+
+    println!(
+        "{}",
+        success(&format!("✓ {id}: {from} → {to} ({})", changed.join(", ")))
+    );
+    Ok(())
+}
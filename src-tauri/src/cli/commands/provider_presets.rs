@@ -0,0 +1,305 @@
+//! `cc-switch provider presets`: the data-driven preset catalog.
+//!
+//! Presets come from the built-in catalog, a signed remote catalog and files
+//! in `~/.cc-switch/presets.d/`; see [`crate::preset_catalog`].
+
+use clap::Subcommand;
+use serde::Serialize;
+
+use super::update::verify_minisign_signature;
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, highlight, info, success, to_json, warning};
+use crate::config::write_json_file;
+use crate::error::AppError;
+use crate::preset_catalog::{
+    load_catalog, parse_catalog, presets_dir, remote_catalog_path, CatalogFormat, LoadedCatalog,
+    PresetCatalog,
+};
+use crate::settings::{
+    get_preset_catalog_settings, set_preset_catalog_settings, PresetCatalogSettings,
+};
+
+const PRESETS_AFTER_LONG_HELP: &str = "\
+Catalogs are JSON or TOML files with `version = 1`, optional model role defaults
+and a list of presets with per-app settings skeletons. Drop them into
+~/.cc-switch/presets.d/; later files override presets with the same id.
+
+Examples:
+  cc-switch provider presets list
+  cc-switch provider add --preset team-relay --name Relay --api-key sk-...
+  cc-switch provider presets source --url https://example.com/presets.json --public-key RWQ...
+  cc-switch provider presets refresh";
+
+const REFRESH_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = PRESETS_AFTER_LONG_HELP)]
+pub enum ProviderPresetsCommand {
+    /// List catalog presets available for the app
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the provider a preset creates
+    Show {
+        /// Preset ID
+        id: String,
+    },
+    /// Download the configured remote catalog and verify its signature
+    Refresh,
+    /// Show or configure the remote catalog source
+    Source {
+        /// Catalog URL; the signature is read from <url>.minisig
+        #[arg(long, requires = "public_key")]
+        url: Option<String>,
+        /// Minisign public key the catalog is signed with
+        #[arg(long, requires = "url")]
+        public_key: Option<String>,
+        /// Remove the remote source and the downloaded catalog
+        #[arg(long, conflicts_with_all = ["url", "public_key"])]
+        clear: bool,
+    },
+}
+
+pub fn execute(cmd: ProviderPresetsCommand, app_type: AppType) -> Result<(), AppError> {
+    match cmd {
+        ProviderPresetsCommand::List { json } => list(app_type, json),
+        ProviderPresetsCommand::Show { id } => show(app_type, &id),
+        ProviderPresetsCommand::Refresh => refresh(),
+        ProviderPresetsCommand::Source {
+            url,
+            public_key,
+            clear,
+        } => source(url, public_key, clear),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetListEntry {
+    id: String,
+    name: String,
+    website_url: Option<String>,
+    source: String,
+}
+
+fn print_catalog_warnings(catalog: &LoadedCatalog) {
+    for message in &catalog.warnings {
+        println!("{}", warning(&format!("Skipped catalog {message}")));
+    }
+}
+
+fn list(app_type: AppType, json: bool) -> Result<(), AppError> {
+    let catalog = load_catalog();
+    let entries = catalog
+        .presets_for_app(&app_type)
+        .map(|loaded| PresetListEntry {
+            id: loaded.preset.id.clone(),
+            name: loaded.preset.name.clone(),
+            website_url: loaded.preset.website_url.clone(),
+            source: loaded.source.label(),
+        })
+        .collect::<Vec<_>>();
+
+    if json {
+        println!(
+            "{}",
+            to_json(&entries).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    print_catalog_warnings(&catalog);
+    if entries.is_empty() {
+        println!(
+            "{}",
+            info(&format!(
+                "No catalog presets for {}. Add catalogs to {}.",
+                app_type.as_str(),
+                presets_dir().display()
+            ))
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["ID", "Name", "Source", "Website"]);
+    for entry in entries {
+        table.add_row(vec![
+            entry.id,
+            entry.name,
+            entry.source,
+            entry.website_url.unwrap_or_default(),
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "{}",
+        info("Create one with `cc-switch provider add --preset <id> --name <name> ...`.")
+    );
+    Ok(())
+}
+
+fn show(app_type: AppType, id: &str) -> Result<(), AppError> {
+    let catalog = load_catalog();
+    print_catalog_warnings(&catalog);
+    let provider = catalog.build_provider(&app_type, id)?;
+    let source = catalog
+        .find(&app_type, id)
+        .map(|loaded| loaded.source.label())
+        .unwrap_or_default();
+
+    println!(
+        "{}",
+        highlight(&format!("Preset: {} ({source})", provider.name))
+    );
+    println!(
+        "{}",
+        to_json(&provider).map_err(|source| AppError::JsonSerialize { source })?
+    );
+    Ok(())
+}
+
+/// Parse a downloaded catalog after checking its minisign signature.
+fn verified_catalog(
+    url: &str,
+    payload: &str,
+    signature: &str,
+    public_key: &str,
+) -> Result<PresetCatalog, AppError> {
+    verify_minisign_signature(payload.as_bytes(), signature, public_key)?;
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let format =
+        CatalogFormat::from_path(std::path::Path::new(path)).unwrap_or(CatalogFormat::Json);
+    parse_catalog(payload, format)
+}
+
+async fn download_text(client: &reqwest::Client, url: &str) -> Result<String, AppError> {
+    client
+        .get(url)
+        .timeout(std::time::Duration::from_secs(REFRESH_TIMEOUT_SECS))
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| AppError::Message(format!("Failed to download {url}: {e}")))?
+        .text()
+        .await
+        .map_err(|e| AppError::Message(format!("Failed to read {url}: {e}")))
+}
+
+fn refresh() -> Result<(), AppError> {
+    let settings = get_preset_catalog_settings();
+    let (Some(url), Some(public_key)) = (settings.url, settings.public_key) else {
+        return Err(AppError::InvalidInput(
+            "No remote preset catalog configured. Run `cc-switch provider presets source --url <url> --public-key <key>` first."
+                .to_string(),
+        ));
+    };
+
+    println!(
+        "{}",
+        info(&format!("Downloading preset catalog from {url}..."))
+    );
+    crate::services::global_proxy::initialize_http_client_from_disk_best_effort();
+    let client = crate::proxy::http_client::get();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| AppError::Message(format!("Failed to create runtime: {e}")))?;
+    let (payload, signature) = runtime.block_on(async {
+        let payload = download_text(&client, &url).await?;
+        let signature = download_text(&client, &format!("{url}.minisig")).await?;
+        Ok::<_, AppError>((payload, signature))
+    })?;
+
+    let catalog = verified_catalog(&url, &payload, &signature, &public_key)?;
+    write_json_file(&remote_catalog_path(), &catalog)?;
+    println!(
+        "{}",
+        success(&format!(
+            "✓ Verified and saved {} preset(s){}.",
+            catalog.presets.len(),
+            catalog
+                .name
+                .as_deref()
+                .map(|name| format!(" from catalog '{name}'"))
+                .unwrap_or_default()
+        ))
+    );
+    Ok(())
+}
+
+fn source(url: Option<String>, public_key: Option<String>, clear: bool) -> Result<(), AppError> {
+    if clear {
+        set_preset_catalog_settings(None)?;
+        let path = remote_catalog_path();
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+        }
+        println!("{}", success("✓ Remote preset catalog removed."));
+        return Ok(());
+    }
+
+    if let (Some(url), Some(public_key)) = (url, public_key) {
+        url::Url::parse(&url)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid catalog URL: {e}")))?;
+        set_preset_catalog_settings(Some(PresetCatalogSettings {
+            url: Some(url),
+            public_key: Some(public_key.trim().to_string()),
+        }))?;
+        println!(
+            "{}",
+            success("✓ Remote preset catalog configured. Run `cc-switch provider presets refresh` to download it.")
+        );
+        return Ok(());
+    }
+
+    let settings = get_preset_catalog_settings();
+    println!("{}", highlight("Preset catalog sources"));
+    println!("  Built-in:   embedded");
+    println!(
+        "  Remote:     {}",
+        settings.url.as_deref().unwrap_or("(not configured)")
+    );
+    if remote_catalog_path().exists() {
+        println!("  Downloaded: {}", remote_catalog_path().display());
+    }
+    println!("  Local:      {}", presets_dir().display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minisign::KeyPair;
+    use std::io::Cursor;
+
+    #[test]
+    fn remote_catalog_must_match_its_signature() {
+        let KeyPair { pk, sk } = KeyPair::generate_unencrypted_keypair().expect("keypair");
+        let public_key = pk.to_box().expect("public key box").to_string();
+        let payload = r#"{"version":1,"presets":[{"id":"relay","name":"Relay","apps":{"claude":{"settings":{"env":{}}}}}]}"#;
+        let signature = minisign::sign(None, &sk, Cursor::new(payload), None, None)
+            .expect("sign catalog")
+            .to_string();
+
+        let catalog = verified_catalog(
+            "https://example.com/presets.json?v=2",
+            payload,
+            &signature,
+            &public_key,
+        )
+        .expect("verified catalog");
+        assert_eq!(catalog.presets[0].id, "relay");
+
+        let tampered = payload.replace("Relay", "Evil");
+        assert!(verified_catalog(
+            "https://example.com/presets.json",
+            &tampered,
+            &signature,
+            &public_key
+        )
+        .is_err());
+    }
+}
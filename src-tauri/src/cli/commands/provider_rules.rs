@@ -0,0 +1,366 @@
+//! `cc-switch provider rules`: automatic provider switching.
+//!
+//! Rules live in `settings.json` and are evaluated by the daemon; see
+//! [`crate::services::switch_rules`]. `preview` runs the same evaluation
+//! without switching.
+
+use clap::{ArgGroup, Subcommand};
+
+use super::provider_inspect::format_check_time;
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
+use crate::error::AppError;
+use crate::services::switch_rules;
+use crate::services::ProviderService;
+use crate::settings::{
+    get_switch_rules_settings, set_switch_rules_settings, SwitchCondition, SwitchRule,
+};
+use crate::store::AppState;
+
+const RULES_AFTER_LONG_HELP: &str = "\
+Rules are evaluated by the daemon once a minute, in list order. A rule fires
+when its condition becomes true; at most one rule switches an app per minute.
+Rules only run while the daemon is up (`cc-switch daemon start`); nothing
+switches while it is stopped.
+The app comes from the global --app flag (default: claude).
+
+Examples:
+  cc-switch provider rules add night --to relay --schedule 22:00-08:00
+  cc-switch provider rules add day --to official --schedule 08:00-22:00 --days mon,tue,wed,thu,fri
+  cc-switch provider rules add over-budget --from relay --to backup --budget-exceeded
+  cc-switch provider rules add plan-limit --from official --to relay --quota-above 90
+  cc-switch provider rules preview
+  cc-switch provider rules log";
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = RULES_AFTER_LONG_HELP)]
+pub enum ProviderRulesCommand {
+    /// List switching rules of every app
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a rule with one of --schedule, --budget-exceeded or --quota-above
+    #[command(group(ArgGroup::new("condition").required(true).multiple(false)))]
+    Add {
+        /// Rule name
+        name: String,
+        /// Provider to switch to
+        #[arg(long)]
+        to: String,
+        /// Only fire while this provider is the current one
+        #[arg(long)]
+        from: Option<String>,
+        /// Local time window, wrapping past midnight when the end is earlier
+        #[arg(long, value_name = "HH:MM-HH:MM", group = "condition")]
+        schedule: Option<String>,
+        /// Weekdays the schedule applies to, e.g. mon,tue,wed
+        #[arg(long, value_delimiter = ',', requires = "schedule")]
+        days: Vec<String>,
+        /// Fire when the current provider's daily or monthly limit is exceeded
+        #[arg(long, group = "condition")]
+        budget_exceeded: bool,
+        /// Fire when the app's subscription quota window reaches this percentage
+        #[arg(
+            long,
+            value_name = "PERCENT",
+            group = "condition",
+            value_parser = clap::value_parser!(u8).range(1..=100)
+        )]
+        quota_above: Option<u8>,
+        /// Quota window checked by --quota-above
+        #[arg(long, default_value = "five_hour", requires = "quota_above")]
+        quota_tier: String,
+    },
+    /// Remove a rule
+    Remove {
+        /// Rule name
+        name: String,
+    },
+    /// Enable a rule, or the whole rules engine when no name is given
+    Enable {
+        /// Rule name
+        name: Option<String>,
+    },
+    /// Disable a rule, or the whole rules engine when no name is given
+    Disable {
+        /// Rule name
+        name: Option<String>,
+    },
+    /// Show what the rules would do right now, without switching
+    Preview {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show recently fired rules
+    Log {
+        /// Number of entries to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn execute(cmd: ProviderRulesCommand, app_type: AppType) -> Result<(), AppError> {
+    match cmd {
+        ProviderRulesCommand::List { json } => list(json),
+        ProviderRulesCommand::Add {
+            name,
+            to,
+            from,
+            schedule,
+            days,
+            budget_exceeded: _,
+            quota_above,
+            quota_tier,
+        } => {
+            let condition = match (schedule, quota_above) {
+                (Some(window), _) => {
+                    let (start, end) = window.split_once('-').ok_or_else(|| {
+                        AppError::InvalidInput(format!(
+                            "Invalid schedule '{window}', expected HH:MM-HH:MM"
+                        ))
+                    })?;
+                    SwitchCondition::Schedule {
+                        start: start.trim().to_string(),
+                        end: end.trim().to_string(),
+                        days: days
+                            .iter()
+                            .map(|day| day.trim().to_ascii_lowercase())
+                            .collect(),
+                    }
+                }
+                (None, Some(percent)) => SwitchCondition::QuotaAbove {
+                    tier: quota_tier,
+                    percent,
+                },
+                (None, None) => SwitchCondition::BudgetExceeded,
+            };
+            add(SwitchRule {
+                name,
+                app: app_type,
+                to,
+                from,
+                enabled: true,
+                condition,
+            })
+        }
+        ProviderRulesCommand::Remove { name } => remove(&name),
+        ProviderRulesCommand::Enable { name } => set_enabled(name.as_deref(), true),
+        ProviderRulesCommand::Disable { name } => set_enabled(name.as_deref(), false),
+        ProviderRulesCommand::Preview { json } => preview(json),
+        ProviderRulesCommand::Log { limit, json } => show_log(limit, json),
+    }
+}
+
+fn list(json: bool) -> Result<(), AppError> {
+    let settings = get_switch_rules_settings();
+    if json {
+        println!(
+            "{}",
+            to_json(&settings).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if settings.rules.is_empty() {
+        println!(
+            "{}",
+            info("No switching rules. Add one with `cc-switch provider rules add`.")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        highlight(&format!(
+            "Switching rules (engine {})",
+            if settings.enabled { "on" } else { "off" }
+        ))
+    );
+    let mut table = create_table();
+    table.set_header(vec!["Name", "App", "Condition", "From", "To", "Enabled"]);
+    for rule in &settings.rules {
+        table.add_row(vec![
+            rule.name.clone(),
+            rule.app.as_str().to_string(),
+            switch_rules::describe_condition(&rule.condition),
+            rule.from.clone().unwrap_or_else(|| "any".to_string()),
+            rule.to.clone(),
+            if rule.enabled { "yes" } else { "no" }.to_string(),
+        ]);
+    }
+    println!("{table}");
+    if !settings.enabled {
+        println!(
+            "{}",
+            warning(
+                "The rules engine is disabled; turn it on with `cc-switch provider rules enable`."
+            )
+        );
+    }
+    Ok(())
+}
+
+fn add(rule: SwitchRule) -> Result<(), AppError> {
+    switch_rules::validate_rule(&rule)?;
+    let state = AppState::try_new()?;
+    for id in std::iter::once(&rule.to).chain(rule.from.as_ref()) {
+        ProviderService::get_provider(&state, rule.app.clone(), id)?;
+    }
+
+    let mut settings = get_switch_rules_settings();
+    if settings
+        .rules
+        .iter()
+        .any(|existing| existing.name == rule.name)
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Rule '{}' already exists",
+            rule.name
+        )));
+    }
+    let summary = format!(
+        "✓ Rule '{}' added: {} → {} on {}.",
+        rule.name,
+        rule.app.as_str(),
+        rule.to,
+        switch_rules::describe_condition(&rule.condition)
+    );
+    settings.rules.push(rule);
+    set_switch_rules_settings(settings)?;
+    println!("{}", success(&summary));
+    println!(
+        "{}",
+        info("Rules are evaluated by the daemon (`cc-switch daemon start`).")
+    );
+    Ok(())
+}
+
+fn remove(name: &str) -> Result<(), AppError> {
+    let mut settings = get_switch_rules_settings();
+    let before = settings.rules.len();
+    settings.rules.retain(|rule| rule.name != name);
+    if settings.rules.len() == before {
+        return Err(AppError::InvalidInput(format!("No rule named '{name}'")));
+    }
+    set_switch_rules_settings(settings)?;
+    println!("{}", success(&format!("✓ Rule '{name}' removed.")));
+    Ok(())
+}
+
+fn set_enabled(name: Option<&str>, enabled: bool) -> Result<(), AppError> {
+    let mut settings = get_switch_rules_settings();
+    let state = if enabled { "enabled" } else { "disabled" };
+    let message = match name {
+        Some(name) => {
+            let rule = settings
+                .rules
+                .iter_mut()
+                .find(|rule| rule.name == name)
+                .ok_or_else(|| AppError::InvalidInput(format!("No rule named '{name}'")))?;
+            rule.enabled = enabled;
+            format!("✓ Rule '{name}' {state}.")
+        }
+        None => {
+            settings.enabled = enabled;
+            format!("✓ Rules engine {state}.")
+        }
+    };
+    set_switch_rules_settings(settings)?;
+    println!("{}", success(&message));
+    Ok(())
+}
+
+fn preview(json: bool) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::Message(format!("Failed to create async runtime: {e}")))?;
+    let evaluations = runtime.block_on(switch_rules::preview_rules(&state.db));
+
+    if json {
+        println!(
+            "{}",
+            to_json(&evaluations).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if evaluations.is_empty() {
+        println!("{}", info("No switching rules configured."));
+        return Ok(());
+    }
+
+    println!("{}", highlight("Switching rules right now (dry run)"));
+    let mut table = create_table();
+    table.set_header(vec!["Rule", "App", "Condition", "Current", "Action"]);
+    for evaluation in &evaluations {
+        let condition = match (&evaluation.reason, evaluation.enabled) {
+            (_, false) => "disabled".to_string(),
+            (Some(reason), true) => format!("met: {reason}"),
+            (None, true) => "not met".to_string(),
+        };
+        let action = if evaluation.fires {
+            format!("switch to {}", evaluation.to)
+        } else {
+            "-".to_string()
+        };
+        table.add_row(vec![
+            evaluation.rule.clone(),
+            evaluation.app.clone(),
+            condition,
+            evaluation
+                .current
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+            action,
+        ]);
+    }
+    println!("{table}");
+    if !get_switch_rules_settings().enabled {
+        println!("{}", warning("The rules engine is disabled."));
+    }
+    Ok(())
+}
+
+fn show_log(limit: usize, json: bool) -> Result<(), AppError> {
+    let firings = switch_rules::recent_firings(limit)?;
+    if json {
+        println!(
+            "{}",
+            to_json(&firings).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if firings.is_empty() {
+        println!("{}", info("No rules have fired yet."));
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["When", "Rule", "App", "Switch", "Reason", "Result"]);
+    for firing in firings {
+        table.add_row(vec![
+            format_check_time(firing.fired_at),
+            firing.rule,
+            firing.app_type,
+            format!(
+                "{} → {}",
+                firing.from_id.as_deref().unwrap_or("-"),
+                firing.to_id
+            ),
+            firing.reason,
+            match firing.error {
+                Some(message) => error(&message),
+                None => success("ok"),
+            },
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
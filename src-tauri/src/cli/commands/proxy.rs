@@ -274,6 +274,20 @@ fn serve_proxy(
                 state.db.clone(),
                 "foreground-proxy",
             );
+            let usage_alert_task = crate::services::usage_alerts::spawn_usage_alert_monitor(
+                state.db.clone(),
+                "foreground-proxy",
+            );
+            let otel_span_task =
+                crate::services::otel_export::spawn_otel_span_exporter("foreground-proxy");
+            // A daemon-managed worker leaves metrics to the daemon so they are
+            // not pushed twice.
+            let otel_metrics_task = (!announced_to_daemon).then(|| {
+                crate::services::otel_export::spawn_otel_metrics_exporter(
+                    state.db.clone(),
+                    "foreground-proxy",
+                )
+            });
 
             println!("{}", highlight(crate::t!("Local Proxy Running", "本地代理已启动")));
             println!(
@@ -322,6 +336,11 @@ fn serve_proxy(
                 .map_err(|e| AppError::Message(format!("failed to listen for Ctrl-C: {e}")))?;
             session_sync_task.abort();
             usage_maintenance_task.abort();
+            usage_alert_task.abort();
+            otel_span_task.abort();
+            if let Some(task) = otel_metrics_task {
+                task.abort();
+            }
             #[cfg(unix)]
             outbound_proxy_reload_task.abort();
 
@@ -0,0 +1,76 @@
+//! `cc-switch run <provider> -- <command>`: run any command with a provider's
+//! credentials in its environment, without touching the live config.
+//!
+//! The variables are the ones `cc-switch env export` prints for the same
+//! provider, including the local proxy route while takeover is on.
+
+use std::ffi::OsString;
+use std::process::Command;
+
+use clap::Args;
+
+use crate::app_config::AppType;
+use crate::cli::commands::env::provider_export_vars;
+use crate::error::AppError;
+
+const RUN_AFTER_LONG_HELP: &str = "\
+Examples:
+  cc-switch run acme-relay -- claude -p 'hello'
+  cc-switch --app codex run acme-openai -- python agent.py
+  cc-switch run acme-relay --model claude-sonnet-4-5 -- ./scripts/eval.sh";
+
+#[derive(Args, Debug, Clone)]
+#[command(after_long_help = RUN_AFTER_LONG_HELP)]
+pub struct RunCommand {
+    /// Provider ID or exact provider name
+    pub provider: String,
+    /// Model override for this run
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Use the provider's own endpoint even while proxy takeover is on
+    #[arg(long)]
+    pub no_proxy: bool,
+    /// Command and arguments to run (after `--`)
+    #[arg(last = true, required = true, value_name = "COMMAND")]
+    pub command: Vec<OsString>,
+}
+
+pub fn execute(cmd: RunCommand, app: Option<AppType>) -> Result<(), AppError> {
+    let app_type = app.unwrap_or(AppType::Claude);
+    let vars = provider_export_vars(
+        &app_type,
+        &cmd.provider,
+        cmd.model.as_deref(),
+        !cmd.no_proxy,
+    )?;
+    let Some((program, args)) = cmd.command.split_first() else {
+        return Err(AppError::InvalidInput("missing command to run".to_string()));
+    };
+
+    let mut command = Command::new(program);
+    command.args(args).envs(vars);
+    exec_command(command, program)
+}
+
+#[cfg(unix)]
+fn exec_command(mut command: Command, program: &OsString) -> Result<(), AppError> {
+    use std::os::unix::process::CommandExt;
+
+    let exec_err = command.exec();
+    Err(exec_failed(program, exec_err))
+}
+
+#[cfg(not(unix))]
+fn exec_command(mut command: Command, program: &OsString) -> Result<(), AppError> {
+    let status = command.status().map_err(|err| exec_failed(program, err))?;
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+fn exec_failed(program: &OsString, err: std::io::Error) -> AppError {
+    let program = program.to_string_lossy();
+    AppError::localized(
+        "cli.run.exec_failed",
+        format!("运行 {program} 失败: {err}"),
+        format!("Failed to run {program}: {err}"),
+    )
+}
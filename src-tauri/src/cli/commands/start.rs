@@ -13,6 +13,12 @@ use crate::cli::codex_temp_launch::{
     prepare_launch as prepare_codex_launch, preview_launch_with as preview_codex_launch_with,
     resolve_codex_binary, PreparedCodexLaunch,
 };
+use crate::cli::overlay_temp_launch::{
+    self, ensure_temp_launch_supported as ensure_overlay_temp_launch_supported,
+    exec_prepared_overlay, prepare_launch_with as prepare_overlay_launch_with,
+    preview_launch_with as preview_overlay_launch_with, PreparedOverlayLaunch,
+};
+use crate::cli::project_pin::{self, ProjectPin, PIN_FILE_NAME};
 use crate::cli::ui::{highlight, info};
 use crate::error::AppError;
 use crate::provider::Provider;
@@ -22,23 +28,47 @@ use crate::store::AppState;
 const CLAUDE_RESERVED_NATIVE_ARGS: &[&str] = &["--settings"];
 const CLAUDE_START_AFTER_LONG_HELP: &str = "\
 Examples:
+  cc-switch start claude            # provider pinned by the nearest .cc-switch.toml
   cc-switch start claude demo
   cc-switch start claude demo --dry-run
   cc-switch start claude demo -- --dangerously-skip-permissions";
 
 const CODEX_START_AFTER_LONG_HELP: &str = "\
 Examples:
+  cc-switch start codex             # provider pinned by the nearest .cc-switch.toml
   cc-switch start codex demo
   cc-switch start codex demo --dry-run
   cc-switch start codex demo -- --model gpt-5.4";
 
+const GEMINI_START_AFTER_LONG_HELP: &str = "\
+Examples:
+  cc-switch start gemini            # provider pinned by the nearest .cc-switch.toml
+  cc-switch start gemini demo
+  cc-switch start gemini demo --dry-run
+  cc-switch start gemini demo -- --yolo";
+
+const OPENCODE_START_AFTER_LONG_HELP: &str = "\
+Examples:
+  cc-switch start opencode          # provider pinned by the nearest .cc-switch.toml
+  cc-switch start opencode demo
+  cc-switch start opencode demo --dry-run
+  cc-switch start opencode demo -- run \"explain this repo\"";
+
+const HERMES_START_AFTER_LONG_HELP: &str = "\
+Examples:
+  cc-switch start hermes            # provider pinned by the nearest .cc-switch.toml
+  cc-switch start hermes demo
+  cc-switch start hermes demo --dry-run
+  cc-switch start hermes demo -- chat";
+
 #[derive(Subcommand)]
 pub enum StartCommand {
     /// Start Claude with a provider selector without switching the global current provider
     #[command(after_long_help = CLAUDE_START_AFTER_LONG_HELP)]
     Claude {
-        /// Provider selector: exact ID first, then exact Name
-        selector: String,
+        /// Provider selector: exact ID first, then exact Name (default: the
+        /// provider pinned by the nearest .cc-switch.toml)
+        selector: Option<String>,
         /// Preview the resolved launch without starting Claude
         #[arg(long)]
         dry_run: bool,
@@ -49,8 +79,9 @@ pub enum StartCommand {
     /// Start Codex with a provider selector without switching the global current provider
     #[command(after_long_help = CODEX_START_AFTER_LONG_HELP)]
     Codex {
-        /// Provider selector: exact ID first, then exact Name
-        selector: String,
+        /// Provider selector: exact ID first, then exact Name (default: the
+        /// provider pinned by the nearest .cc-switch.toml)
+        selector: Option<String>,
         /// Preview the resolved launch without starting Codex
         #[arg(long)]
         dry_run: bool,
@@ -58,6 +89,45 @@ pub enum StartCommand {
         #[arg(last = true, value_name = "NATIVE_ARGS")]
         native_args: Vec<OsString>,
     },
+    /// Start Gemini with a provider selector without switching the global current provider
+    #[command(after_long_help = GEMINI_START_AFTER_LONG_HELP)]
+    Gemini {
+        /// Provider selector: exact ID first, then exact Name (default: the
+        /// provider pinned by the nearest .cc-switch.toml)
+        selector: Option<String>,
+        /// Preview the resolved launch without starting Gemini
+        #[arg(long)]
+        dry_run: bool,
+        /// Native Gemini CLI arguments to pass through after `--`
+        #[arg(last = true, value_name = "NATIVE_ARGS")]
+        native_args: Vec<OsString>,
+    },
+    /// Start OpenCode with a provider selector without switching the global current provider
+    #[command(name = "opencode", after_long_help = OPENCODE_START_AFTER_LONG_HELP)]
+    OpenCode {
+        /// Provider selector: exact ID first, then exact Name (default: the
+        /// provider pinned by the nearest .cc-switch.toml)
+        selector: Option<String>,
+        /// Preview the resolved launch without starting OpenCode
+        #[arg(long)]
+        dry_run: bool,
+        /// Native OpenCode CLI arguments to pass through after `--`
+        #[arg(last = true, value_name = "NATIVE_ARGS")]
+        native_args: Vec<OsString>,
+    },
+    /// Start Hermes with a provider selector without switching the global current provider
+    #[command(after_long_help = HERMES_START_AFTER_LONG_HELP)]
+    Hermes {
+        /// Provider selector: exact ID first, then exact Name (default: the
+        /// provider pinned by the nearest .cc-switch.toml)
+        selector: Option<String>,
+        /// Preview the resolved launch without starting Hermes
+        #[arg(long)]
+        dry_run: bool,
+        /// Native Hermes CLI arguments to pass through after `--`
+        #[arg(last = true, value_name = "NATIVE_ARGS")]
+        native_args: Vec<OsString>,
+    },
 }
 
 pub fn execute(cmd: StartCommand) -> Result<(), AppError> {
@@ -66,12 +136,32 @@ pub fn execute(cmd: StartCommand) -> Result<(), AppError> {
             selector,
             dry_run,
             native_args,
-        } => start_claude(&selector, dry_run, &native_args),
+        } => start_claude(selector.as_deref(), dry_run, &native_args),
         StartCommand::Codex {
             selector,
             dry_run,
             native_args,
-        } => start_codex(&selector, dry_run, &native_args),
+        } => start_codex(selector.as_deref(), dry_run, &native_args),
+        StartCommand::Gemini {
+            selector,
+            dry_run,
+            native_args,
+        } => start_overlay(AppType::Gemini, selector.as_deref(), dry_run, &native_args),
+        StartCommand::OpenCode {
+            selector,
+            dry_run,
+            native_args,
+        } => start_overlay(
+            AppType::OpenCode,
+            selector.as_deref(),
+            dry_run,
+            &native_args,
+        ),
+        StartCommand::Hermes {
+            selector,
+            dry_run,
+            native_args,
+        } => start_overlay(AppType::Hermes, selector.as_deref(), dry_run, &native_args),
     }
 }
 
@@ -79,11 +169,23 @@ fn get_state() -> Result<AppState, AppError> {
     AppState::try_new()
 }
 
-fn start_claude(selector: &str, dry_run: bool, native_args: &[OsString]) -> Result<(), AppError> {
+fn start_claude(
+    selector: Option<&str>,
+    dry_run: bool,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
     reject_reserved_native_args(native_args, "Claude", CLAUDE_RESERVED_NATIVE_ARGS)?;
     let state = get_state()?;
     let providers = ProviderService::list(&state, AppType::Claude)?;
-    let provider = resolve_provider_selector(&providers, selector, "Claude")?;
+    let pin = project_pin::discover()?;
+    let (provider, model) = resolve_start_provider(
+        &providers,
+        selector,
+        pin.as_ref(),
+        &AppType::Claude,
+        "Claude",
+    )?;
+    let native_args = &with_model_arg(native_args, model.as_deref(), &["--model"]);
 
     ensure_temp_launch_supported()?;
     if dry_run {
@@ -104,15 +206,23 @@ fn start_claude(selector: &str, dry_run: bool, native_args: &[OsString]) -> Resu
     handoff_claude_and_cleanup(&prepared, native_args)
 }
 
-fn start_codex(selector: &str, dry_run: bool, native_args: &[OsString]) -> Result<(), AppError> {
+fn start_codex(
+    selector: Option<&str>,
+    dry_run: bool,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
+    let pin = project_pin::discover()?;
     start_with(
         selector,
+        pin.as_ref(),
+        &AppType::Codex,
         "Codex",
         || {
             let state = get_state()?;
             ProviderService::list(&state, AppType::Codex)
         },
-        |provider| {
+        |provider, model| {
+            let native_args = &with_model_arg(native_args, model, &["--model", "-m"]);
             ensure_codex_temp_launch_supported()?;
             if dry_run {
                 let prepared = preview_codex_launch_with(
@@ -128,6 +238,52 @@ fn start_codex(selector: &str, dry_run: bool, native_args: &[OsString]) -> Resul
     )
 }
 
+/// Gemini, OpenCode and Hermes launch through a per-process config overlay;
+/// a pinned model goes into the overlay, so an explicit native model flag
+/// still wins.
+fn start_overlay(
+    app: AppType,
+    selector: Option<&str>,
+    dry_run: bool,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
+    let app_name = overlay_temp_launch::app_name(&app);
+    let state = get_state()?;
+    let pin = project_pin::discover()?;
+    start_with(
+        selector,
+        pin.as_ref(),
+        &app,
+        app_name,
+        || ProviderService::list(&state, app.clone()),
+        |provider, model| {
+            ensure_overlay_temp_launch_supported(&app)?;
+            let settings = overlay_temp_launch::launch_settings(&state, &app, provider)?;
+            let resolve = || overlay_temp_launch::resolve_binary(&app);
+            if dry_run {
+                let prepared = preview_overlay_launch_with(
+                    &app,
+                    provider,
+                    &settings,
+                    model,
+                    &std::env::temp_dir(),
+                    resolve,
+                )?;
+                return print_overlay_dry_run(provider, &prepared, native_args);
+            }
+            let prepared = prepare_overlay_launch_with(
+                &app,
+                provider,
+                &settings,
+                model,
+                &std::env::temp_dir(),
+                resolve,
+            )?;
+            handoff_overlay_and_cleanup(&prepared, native_args)
+        },
+    )
+}
+
 fn reject_reserved_native_args(
     native_args: &[OsString],
     app_name: &str,
@@ -163,18 +319,85 @@ fn is_reserved_native_arg(arg: &OsString, reserved_arg: &str) -> bool {
 }
 
 fn start_with<Load, Launch>(
-    selector: &str,
+    selector: Option<&str>,
+    pin: Option<&ProjectPin>,
+    app: &AppType,
     app_name: &str,
     load_providers: Load,
     launch_provider: Launch,
 ) -> Result<(), AppError>
 where
     Load: FnOnce() -> Result<IndexMap<String, Provider>, AppError>,
-    Launch: FnOnce(&Provider) -> Result<(), AppError>,
+    Launch: FnOnce(&Provider, Option<&str>) -> Result<(), AppError>,
 {
     let providers = load_providers()?;
-    let provider = resolve_provider_selector(&providers, selector, app_name)?;
-    launch_provider(&provider)
+    let (provider, model) = resolve_start_provider(&providers, selector, pin, app, app_name)?;
+    launch_provider(&provider, model.as_deref())
+}
+
+/// An explicit selector wins; otherwise use the provider (and model) pinned
+/// for `app` by the nearest `.cc-switch.toml`.
+fn resolve_start_provider(
+    providers: &IndexMap<String, Provider>,
+    selector: Option<&str>,
+    pin: Option<&ProjectPin>,
+    app: &AppType,
+    app_name: &str,
+) -> Result<(Provider, Option<String>), AppError> {
+    if let Some(selector) = selector {
+        return resolve_provider_selector(providers, selector, app_name)
+            .map(|provider| (provider, None));
+    }
+
+    let Some((path, app_pin)) = pin.and_then(|pin| Some((&pin.path, pin.for_app(app)?))) else {
+        let app_slug = app.as_str();
+        return Err(AppError::localized(
+            "cli.start.selector_missing",
+            format!(
+                "未指定供应商，且当前目录及其上级目录中没有为 {app_name} 固定供应商的 {PIN_FILE_NAME}。用法: cc-switch start {app_slug} <供应商>"
+            ),
+            format!(
+                "No provider given and no {PIN_FILE_NAME} in this directory or its parents pins one for {app_name}. Usage: cc-switch start {app_slug} <PROVIDER>"
+            ),
+        ));
+    };
+    let selector = app_pin.provider.as_deref().unwrap_or_default().trim();
+    let provider = resolve_provider_selector(providers, selector, app_name).map_err(|err| {
+        AppError::localized(
+            "cli.start.pinned_provider_invalid",
+            format!("{} 中固定的供应商无效: {err}", path.display()),
+            format!("Provider pinned in {} is invalid: {err}", path.display()),
+        )
+    })?;
+    let model = app_pin
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .map(str::to_string);
+    Ok((provider, model))
+}
+
+/// Prepend `--model <model>` unless the user already passed a model flag.
+fn with_model_arg(
+    native_args: &[OsString],
+    model: Option<&str>,
+    model_flags: &[&str],
+) -> Vec<OsString> {
+    let Some(model) = model else {
+        return native_args.to_vec();
+    };
+    if native_args.iter().any(|arg| {
+        model_flags
+            .iter()
+            .any(|flag| is_reserved_native_arg(arg, flag))
+    }) {
+        return native_args.to_vec();
+    }
+    [OsString::from("--model"), OsString::from(model)]
+        .into_iter()
+        .chain(native_args.iter().cloned())
+        .collect()
 }
 
 fn prepare_claude_launch_with<Resolve>(
@@ -239,6 +462,20 @@ fn handoff_codex_and_cleanup(
     )
 }
 
+fn handoff_overlay_and_cleanup(
+    prepared: &PreparedOverlayLaunch,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
+    finish_launch(
+        exec_prepared_overlay(prepared, native_args),
+        prepared.cleanup_overlay_dir(),
+        overlay_temp_launch::app_name(&prepared.app),
+        "临时覆盖目录",
+        "temporary overlay directory",
+        "temp_launch.cleanup_failed",
+    )
+}
+
 fn print_claude_dry_run(
     provider: &Provider,
     prepared: &PreparedClaudeLaunch,
@@ -308,6 +545,59 @@ fn print_codex_dry_run(
     print_dry_run_note()
 }
 
+fn print_overlay_dry_run(
+    provider: &Provider,
+    prepared: &PreparedOverlayLaunch,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
+    print_dry_run_header(overlay_temp_launch::app_name(&prepared.app), provider);
+    println!(
+        "{} {}",
+        info(crate::t!("Executable:", "可执行文件：")),
+        prepared.executable.display()
+    );
+    println!(
+        "{} {}",
+        info(crate::t!("Overlay directory preview:", "覆盖目录预览：")),
+        prepared.overlay_dir.display()
+    );
+    for file in &prepared.files {
+        println!(
+            "{} {}",
+            info(crate::t!("Generated file:", "生成文件：")),
+            file.display()
+        );
+    }
+    for (name, value) in &prepared.env {
+        println!(
+            "{} {name}={}",
+            info(crate::t!("Environment:", "环境变量：")),
+            quote_display(&display_env_value(name, value))
+        );
+    }
+    println!(
+        "{} {}",
+        info(crate::t!("Launch command:", "启动命令：")),
+        format_command_preview(&prepared.executable, &[], native_args)
+    );
+    print_native_args(native_args);
+    print_dry_run_note()
+}
+
+/// Keys and tokens are masked in previews; everything else is shown as is.
+fn display_env_value(name: &str, value: &str) -> String {
+    let upper = name.to_ascii_uppercase();
+    if ["KEY", "TOKEN", "SECRET", "PASSWORD"]
+        .iter()
+        .any(|marker| upper.contains(marker))
+        && !value.is_empty()
+    {
+        super::universal::mask_api_key(value)
+    } else {
+        value.to_string()
+    }
+}
+
 fn print_dry_run_header(app_name: &str, provider: &Provider) {
     let title = if crate::cli::i18n::is_chinese() {
         format!("{app_name} 启动预览")
@@ -406,7 +696,7 @@ fn finish_launch(
     }
 }
 
-fn resolve_provider_selector(
+pub(crate) fn resolve_provider_selector(
     providers: &IndexMap<String, Provider>,
     selector: &str,
     app_name: &str,
@@ -561,10 +851,12 @@ mod tests {
         let launched = std::cell::RefCell::new(None::<String>);
 
         start_with(
-            "Claude Demo",
+            Some("Claude Demo"),
+            None,
+            &AppType::Claude,
             "Claude",
             || Ok(providers),
-            |provider| {
+            |provider, _model| {
                 launched.replace(Some(provider.id.clone()));
                 Ok(())
             },
@@ -580,10 +872,12 @@ mod tests {
         let launched = std::cell::RefCell::new(None::<String>);
 
         start_with(
-            "Codex Demo",
+            Some("Codex Demo"),
+            None,
+            &AppType::Codex,
             "Codex",
             || Ok(providers),
-            |provider| {
+            |provider, _model| {
                 launched.replace(Some(provider.id.clone()));
                 Ok(())
             },
@@ -593,6 +887,66 @@ mod tests {
         assert_eq!(launched.into_inner().as_deref(), Some("demo"));
     }
 
+    #[test]
+    fn start_without_selector_uses_pinned_provider_and_model() {
+        let providers = IndexMap::from([
+            ("demo".to_string(), provider("demo", "Claude Demo")),
+            ("work".to_string(), provider("work", "Work Relay")),
+        ]);
+        let pin = ProjectPin::parse(
+            std::path::PathBuf::from("/repo/.cc-switch.toml"),
+            "[claude]\nprovider = \"Work Relay\"\nmodel = \"claude-opus-4-1\"\n",
+        )
+        .expect("parse pin");
+        let launched = std::cell::RefCell::new(None::<(String, Option<String>)>);
+
+        start_with(
+            None,
+            Some(&pin),
+            &AppType::Claude,
+            "Claude",
+            || Ok(providers),
+            |provider, model| {
+                launched.replace(Some((provider.id.clone(), model.map(str::to_string))));
+                Ok(())
+            },
+        )
+        .expect("pinned provider should launch");
+
+        assert_eq!(
+            launched.into_inner(),
+            Some(("work".to_string(), Some("claude-opus-4-1".to_string())))
+        );
+    }
+
+    #[test]
+    fn start_without_selector_or_pin_explains_usage() {
+        let providers = IndexMap::from([("demo".to_string(), provider("demo", "Codex Demo"))]);
+
+        let err = resolve_start_provider(&providers, None, None, &AppType::Codex, "Codex")
+            .expect_err("no selector and no pin");
+
+        assert!(err.to_string().contains(".cc-switch.toml"));
+        assert!(err.to_string().contains("cc-switch start codex"));
+    }
+
+    #[test]
+    fn pinned_model_yields_to_explicit_model_flag() {
+        let args = [OsString::from("--model=claude-haiku"), OsString::from("-p")];
+        assert_eq!(
+            with_model_arg(&args, Some("claude-opus-4-1"), &["--model"]),
+            args.to_vec()
+        );
+        assert_eq!(
+            with_model_arg(&[OsString::from("-p")], Some("gpt-5.5"), &["--model", "-m"]),
+            vec![
+                OsString::from("--model"),
+                OsString::from("gpt-5.5"),
+                OsString::from("-p")
+            ]
+        );
+    }
+
     #[test]
     fn prepare_claude_launch_with_writes_effective_snapshot_from_state() {
         let temp_dir = TempDir::new().expect("create temp dir");
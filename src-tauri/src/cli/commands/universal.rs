@@ -0,0 +1,446 @@
+//! `cc-switch universal`: manage universal providers (one relay account shared
+//! by every app) and the per-app providers derived from them.
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, highlight, info, success, to_json};
+use crate::error::AppError;
+use crate::provider::{
+    ClaudeModelConfig, CodexModelConfig, UniversalModelConfig, UniversalProvider,
+    UniversalProviderApps,
+};
+use crate::services::{ProviderService, UniversalProviderService, UniversalSyncReport};
+use crate::store::AppState;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum UniversalCommand {
+    /// Add a universal provider and generate its per-app providers
+    Add {
+        /// Display name
+        #[arg(long)]
+        name: String,
+        /// Universal provider id (derived from the name when omitted)
+        #[arg(long)]
+        id: Option<String>,
+        /// Base URL shared by every protocol unless overridden
+        #[arg(long)]
+        base_url: String,
+        /// API key used by every derived provider
+        #[arg(long)]
+        api_key: String,
+        #[command(flatten)]
+        fields: UniversalFieldArgs,
+    },
+    /// List universal providers and their derived provider ids
+    List {
+        /// Print JSON (API keys masked)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Edit a universal provider and re-sync its derived providers
+    Edit {
+        /// Universal provider id
+        id: String,
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+        /// New base URL
+        #[arg(long)]
+        base_url: Option<String>,
+        /// New API key
+        #[arg(long)]
+        api_key: Option<String>,
+        #[command(flatten)]
+        fields: UniversalFieldArgs,
+    },
+    /// Delete a universal provider and all of its derived providers
+    Delete {
+        /// Universal provider id
+        id: String,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Switch apps to the providers derived from a universal provider
+    /// (only the app given by the global `--app`, otherwise every enabled app)
+    Apply {
+        /// Universal provider id
+        id: String,
+    },
+}
+
+/// Optional fields shared by `add` and `edit`. An empty string clears a value.
+#[derive(Args, Debug, Clone, Default)]
+pub struct UniversalFieldArgs {
+    /// Apps to generate providers for (comma separated; default: all)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub apps: Option<Vec<AppType>>,
+    /// Anthropic Messages endpoint (defaults to the base URL)
+    #[arg(long)]
+    pub anthropic_url: Option<String>,
+    /// OpenAI-compatible endpoint (defaults to the base URL plus /v1)
+    #[arg(long)]
+    pub openai_url: Option<String>,
+    /// Gemini endpoint (defaults to the base URL)
+    #[arg(long)]
+    pub gemini_url: Option<String>,
+    /// Claude main model (ANTHROPIC_MODEL)
+    #[arg(long)]
+    pub claude_model: Option<String>,
+    /// Claude Haiku role model
+    #[arg(long)]
+    pub claude_haiku_model: Option<String>,
+    /// Claude Sonnet role model
+    #[arg(long)]
+    pub claude_sonnet_model: Option<String>,
+    /// Claude Opus role model
+    #[arg(long)]
+    pub claude_opus_model: Option<String>,
+    /// Codex model
+    #[arg(long)]
+    pub codex_model: Option<String>,
+    /// Codex reasoning effort (e.g. low, medium, high)
+    #[arg(long)]
+    pub codex_reasoning_effort: Option<String>,
+    /// Gemini model
+    #[arg(long)]
+    pub gemini_model: Option<String>,
+    /// OpenCode model (defaults to the Codex model)
+    #[arg(long)]
+    pub opencode_model: Option<String>,
+    /// Hermes model (defaults to the Codex model)
+    #[arg(long)]
+    pub hermes_model: Option<String>,
+    /// OpenClaw model (defaults to the Codex model)
+    #[arg(long)]
+    pub openclaw_model: Option<String>,
+    /// Website URL
+    #[arg(long)]
+    pub website_url: Option<String>,
+    /// Notes
+    #[arg(long)]
+    pub notes: Option<String>,
+}
+
+impl UniversalFieldArgs {
+    pub(crate) fn apply_to(self, provider: &mut UniversalProvider) {
+        if let Some(apps) = self.apps {
+            let mut enabled = UniversalProviderApps::default();
+            for app in &apps {
+                enabled.set_enabled_for(app, true);
+            }
+            provider.apps = enabled;
+        }
+
+        set_optional(&mut provider.endpoints.anthropic, self.anthropic_url);
+        set_optional(&mut provider.endpoints.openai, self.openai_url);
+        set_optional(&mut provider.endpoints.gemini, self.gemini_url);
+
+        let claude = provider
+            .models
+            .claude
+            .get_or_insert_with(ClaudeModelConfig::default);
+        set_optional(&mut claude.model, self.claude_model);
+        set_optional(&mut claude.haiku_model, self.claude_haiku_model);
+        set_optional(&mut claude.sonnet_model, self.claude_sonnet_model);
+        set_optional(&mut claude.opus_model, self.claude_opus_model);
+        if *claude == ClaudeModelConfig::default() {
+            provider.models.claude = None;
+        }
+
+        let codex = provider
+            .models
+            .codex
+            .get_or_insert_with(CodexModelConfig::default);
+        set_optional(&mut codex.model, self.codex_model);
+        set_optional(&mut codex.reasoning_effort, self.codex_reasoning_effort);
+        if *codex == CodexModelConfig::default() {
+            provider.models.codex = None;
+        }
+
+        set_model(&mut provider.models.gemini, self.gemini_model);
+        set_model(&mut provider.models.opencode, self.opencode_model);
+        set_model(&mut provider.models.hermes, self.hermes_model);
+        set_model(&mut provider.models.openclaw, self.openclaw_model);
+
+        set_optional(&mut provider.website_url, self.website_url);
+        set_optional(&mut provider.notes, self.notes);
+    }
+}
+
+fn set_optional(target: &mut Option<String>, value: Option<String>) {
+    if let Some(value) = value {
+        let value = value.trim();
+        *target = (!value.is_empty()).then(|| value.to_string());
+    }
+}
+
+fn set_model(target: &mut Option<UniversalModelConfig>, value: Option<String>) {
+    if value.is_none() {
+        return;
+    }
+    let mut config = target.take().unwrap_or_default();
+    set_optional(&mut config.model, value);
+    *target = config.model.is_some().then_some(config);
+}
+
+pub fn execute(cmd: UniversalCommand, app: Option<AppType>) -> Result<(), AppError> {
+    match cmd {
+        UniversalCommand::Add {
+            name,
+            id,
+            base_url,
+            api_key,
+            fields,
+        } => add(name, id, base_url, api_key, fields),
+        UniversalCommand::List { json } => list(json),
+        UniversalCommand::Edit {
+            id,
+            name,
+            base_url,
+            api_key,
+            fields,
+        } => edit(&id, name, base_url, api_key, fields),
+        UniversalCommand::Delete { id, yes } => delete(&id, yes),
+        UniversalCommand::Apply { id } => apply(&id, app.as_slice()),
+    }
+}
+
+fn get_state() -> Result<AppState, AppError> {
+    AppState::try_new()
+}
+
+fn add(
+    name: String,
+    id: Option<String>,
+    base_url: String,
+    api_key: String,
+    fields: UniversalFieldArgs,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let id = match id {
+        Some(id) => id.trim().to_string(),
+        None => UniversalProviderService::generate_id(&state, &name)?,
+    };
+    if state.db.get_universal_provider(&id)?.is_some() {
+        return Err(AppError::Message(format!(
+            "Universal provider '{id}' already exists; use `cc-switch universal edit {id}`"
+        )));
+    }
+
+    let mut provider = UniversalProvider::new(
+        id.clone(),
+        name.trim().to_string(),
+        base_url.trim().to_string(),
+        api_key.trim().to_string(),
+    );
+    fields.apply_to(&mut provider);
+
+    let report = UniversalProviderService::upsert(&state, provider.clone())?;
+    println!("{}", success(&format!("✓ Added universal provider '{id}'")));
+    print_report(&provider, &report);
+    Ok(())
+}
+
+fn edit(
+    id: &str,
+    name: Option<String>,
+    base_url: Option<String>,
+    api_key: Option<String>,
+    fields: UniversalFieldArgs,
+) -> Result<(), AppError> {
+    let state = get_state()?;
+    let mut provider = UniversalProviderService::get(&state, id)?;
+    if let Some(name) = name {
+        provider.name = name.trim().to_string();
+    }
+    if let Some(base_url) = base_url {
+        provider.base_url = base_url.trim().to_string();
+    }
+    if let Some(api_key) = api_key {
+        provider.api_key = api_key.trim().to_string();
+    }
+    fields.apply_to(&mut provider);
+
+    let report = UniversalProviderService::upsert(&state, provider.clone())?;
+    println!(
+        "{}",
+        success(&format!("✓ Updated universal provider '{id}'"))
+    );
+    print_report(&provider, &report);
+    Ok(())
+}
+
+fn delete(id: &str, yes: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let provider = UniversalProviderService::get(&state, id)?;
+    if !yes {
+        let confirm = inquire::Confirm::new(&format!(
+            "Delete universal provider '{id}' and its derived providers?"
+        ))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::Message(format!("Prompt failed: {e}")))?;
+        if !confirm {
+            println!("{}", info("Cancelled."));
+            return Ok(());
+        }
+    }
+
+    let report = UniversalProviderService::delete(&state, id)?;
+    println!(
+        "{}",
+        success(&format!("✓ Deleted universal provider '{id}'"))
+    );
+    print_report(&provider, &report);
+    Ok(())
+}
+
+fn apply(id: &str, apps: &[AppType]) -> Result<(), AppError> {
+    let state = get_state()?;
+    let provider = UniversalProviderService::get(&state, id)?;
+    let switched = UniversalProviderService::apply(&state, id, apps)?;
+    if switched.is_empty() {
+        println!(
+            "{}",
+            info(&format!("Universal provider '{id}' has no enabled apps."))
+        );
+        return Ok(());
+    }
+    for app in switched {
+        println!(
+            "{}",
+            success(&format!(
+                "✓ {}: {}",
+                app.as_str(),
+                provider.derived_provider_id(&app)
+            ))
+        );
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UniversalProviderOutput {
+    #[serde(flatten)]
+    provider: UniversalProvider,
+    derived: Vec<DerivedProviderOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DerivedProviderOutput {
+    app: AppType,
+    provider_id: String,
+    current: bool,
+}
+
+fn list(json: bool) -> Result<(), AppError> {
+    let state = get_state()?;
+    let providers = UniversalProviderService::list(&state)?;
+
+    let mut outputs = Vec::with_capacity(providers.len());
+    for mut provider in providers {
+        let mut derived = Vec::new();
+        for app in provider.apps.enabled_apps() {
+            let provider_id = provider.derived_provider_id(&app);
+            let current = !app.is_additive_mode()
+                && ProviderService::current(&state, app.clone())? == provider_id;
+            derived.push(DerivedProviderOutput {
+                app,
+                provider_id,
+                current,
+            });
+        }
+        provider.api_key = mask_api_key(&provider.api_key);
+        outputs.push(UniversalProviderOutput { provider, derived });
+    }
+
+    if json {
+        println!(
+            "{}",
+            to_json(&outputs).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+
+    if outputs.is_empty() {
+        println!("{}", info("No universal providers found."));
+        println!(
+            "{}",
+            info("Add one with `cc-switch universal add --name <NAME> --base-url <URL> --api-key <KEY>`.")
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_header(vec!["ID", "Name", "Base URL", "API Key", "Apps"]);
+    for output in &outputs {
+        let apps = output
+            .derived
+            .iter()
+            .map(|derived| {
+                if derived.current {
+                    format!("{}✓", derived.app.as_str())
+                } else {
+                    derived.app.as_str().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        table.add_row(vec![
+            output.provider.id.clone(),
+            output.provider.name.clone(),
+            output.provider.base_url.clone(),
+            output.provider.api_key.clone(),
+            if apps.is_empty() {
+                "-".to_string()
+            } else {
+                apps
+            },
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "\n{} {}",
+        info("ℹ"),
+        crate::t!(
+            "Derived providers are named universal-<app>-<id>; ✓ marks the current provider.",
+            "派生供应商命名为 universal-<app>-<id>；✓ 表示当前供应商。"
+        )
+    );
+    Ok(())
+}
+
+fn print_report(provider: &UniversalProvider, report: &UniversalSyncReport) {
+    for (label, apps) in [
+        ("created", &report.created),
+        ("updated", &report.updated),
+        ("removed", &report.removed),
+    ] {
+        for app in apps {
+            println!(
+                "  {} {} ({})",
+                highlight(label),
+                provider.derived_provider_id(app),
+                app.as_str()
+            );
+        }
+    }
+}
+
+pub(crate) fn mask_api_key(key: &str) -> String {
+    if crate::vault::is_sealed(key) {
+        return format!("{}…", crate::vault::SEALED_PREFIX);
+    }
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}…{tail}")
+}
@@ -562,20 +562,21 @@ fn select_release_asset_from_candidates<'a>(
 fn parse_public_key(public_key_text: &str) -> Result<PublicKey, AppError> {
     PublicKey::decode(public_key_text.trim())
         .or_else(|_| PublicKey::from_base64(public_key_text.trim()))
-        .map_err(|e| AppError::Message(format!("Invalid updater public key: {e}")))
+        .map_err(|e| AppError::Message(format!("Invalid minisign public key: {e}")))
 }
 
-fn verify_minisign_signature(
+/// Verify a minisign signature; also used for signed preset catalogs.
+pub(crate) fn verify_minisign_signature(
     payload: &[u8],
     signature_text: &str,
     public_key_text: &str,
 ) -> Result<(), AppError> {
     let public_key = parse_public_key(public_key_text)?;
     let signature = Signature::decode(signature_text.trim())
-        .map_err(|e| AppError::Message(format!("Invalid updater signature: {e}")))?;
+        .map_err(|e| AppError::Message(format!("Invalid minisign signature: {e}")))?;
     public_key
         .verify(payload, &signature, false)
-        .map_err(|e| AppError::Message(format!("Updater signature verification failed: {e}")))
+        .map_err(|e| AppError::Message(format!("Signature verification failed: {e}")))
 }
 
 fn verify_downloaded_asset_signature(
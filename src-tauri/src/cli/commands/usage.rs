@@ -0,0 +1,673 @@
+use clap::{Subcommand, ValueEnum};
+
+use crate::app_config::AppType;
+use crate::cli::ui::{create_table, error, highlight, info, success, to_json, warning};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::LocalStore;
+use crate::services::cache_analytics::{
+    CacheAnalyticsFilter, CacheAnalyticsReport, CacheGroupBy, CacheStats,
+};
+use crate::services::project_usage::{
+    self, ProjectBreakdownRow, ProjectUsageRange, ProjectUsageRow,
+};
+use crate::services::usage_alerts::{self, AlertHistoryRow};
+use crate::settings::{AlertWebhook, AlertWebhookFormat, UsageAlertSettings};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum UsageCommand {
+    /// Break down usage and cost by project directory
+    Projects {
+        /// Preset date range
+        #[arg(long, value_enum, default_value_t = UsageRangeArg::Days30)]
+        range: UsageRangeArg,
+        /// Start date (YYYY-MM-DD, inclusive); overrides --range
+        #[arg(long, value_parser = parse_date_arg)]
+        from: Option<String>,
+        /// End date (YYYY-MM-DD, inclusive)
+        #[arg(long, value_parser = parse_date_arg)]
+        to: Option<String>,
+        /// Show the provider/model breakdown for one project (path or `.`)
+        #[arg(long)]
+        project: Option<String>,
+        /// Maximum number of projects to list
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Prompt-cache hit ratio, savings and write overhead
+    Cache {
+        /// Preset date range
+        #[arg(long, value_enum, default_value_t = UsageRangeArg::Days30)]
+        range: UsageRangeArg,
+        /// Start date (YYYY-MM-DD, inclusive); overrides --range
+        #[arg(long, value_parser = parse_date_arg)]
+        from: Option<String>,
+        /// End date (YYYY-MM-DD, inclusive)
+        #[arg(long, value_parser = parse_date_arg)]
+        to: Option<String>,
+        /// Group rows by provider, model or day
+        #[arg(long, value_enum, default_value_t = CacheGroupArg::Provider)]
+        by: CacheGroupArg,
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Budget and anomaly alerts (evaluated by the daemon / foreground proxy)
+    #[command(subcommand)]
+    Alerts(AlertsCommand),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AlertsCommand {
+    /// Show alert rules and delivery channels
+    Show {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Turn alert evaluation on
+    Enable,
+    /// Turn alert evaluation off
+    Disable,
+    /// Update alert rules (pass 0 to clear a budget or disable a rule)
+    Set {
+        /// Daily budget in USD
+        #[arg(long)]
+        daily_budget: Option<f64>,
+        /// Monthly budget in USD
+        #[arg(long)]
+        monthly_budget: Option<f64>,
+        /// Budget percentages that trigger alerts (e.g. 50,80,100)
+        #[arg(long, value_delimiter = ',')]
+        thresholds: Option<Vec<u8>>,
+        /// Alert when the last hour costs this many times the trailing 24h average
+        #[arg(long)]
+        spike_multiplier: Option<f64>,
+        /// Ignore hourly spikes below this amount in USD
+        #[arg(long)]
+        spike_min_cost: Option<f64>,
+        /// Alert when a provider's 15-minute error ratio reaches this value (0-1)
+        #[arg(long)]
+        error_rate: Option<f64>,
+        /// Minimum requests in the window before the error-rate rule applies
+        #[arg(long)]
+        error_min_requests: Option<u32>,
+        /// Raise desktop notifications via notify-send (Linux)
+        #[arg(long)]
+        desktop_notify: Option<bool>,
+    },
+    /// Manage alert webhooks
+    #[command(subcommand)]
+    Webhook(AlertWebhookCommand),
+    /// Send a test alert to every configured channel
+    Test,
+    /// List alerts that have fired
+    History {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AlertWebhookCommand {
+    /// Add a webhook
+    Add {
+        url: String,
+        /// Payload dialect
+        #[arg(long, value_enum, default_value_t = WebhookFormatArg::Generic)]
+        format: WebhookFormatArg,
+    },
+    /// Remove a webhook by URL
+    Remove { url: String },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormatArg {
+    Generic,
+    Slack,
+    Feishu,
+    Dingtalk,
+}
+
+impl From<WebhookFormatArg> for AlertWebhookFormat {
+    fn from(value: WebhookFormatArg) -> Self {
+        match value {
+            WebhookFormatArg::Generic => Self::Generic,
+            WebhookFormatArg::Slack => Self::Slack,
+            WebhookFormatArg::Feishu => Self::Feishu,
+            WebhookFormatArg::Dingtalk => Self::Dingtalk,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheGroupArg {
+    Provider,
+    Model,
+    Day,
+}
+
+impl From<CacheGroupArg> for CacheGroupBy {
+    fn from(value: CacheGroupArg) -> Self {
+        match value {
+            CacheGroupArg::Provider => Self::Provider,
+            CacheGroupArg::Model => Self::Model,
+            CacheGroupArg::Day => Self::Day,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageRangeArg {
+    Today,
+    #[value(name = "7d")]
+    Days7,
+    #[value(name = "30d")]
+    Days30,
+    Month,
+    All,
+}
+
+impl UsageRangeArg {
+    fn preset(self) -> ProjectUsageRange {
+        match self {
+            Self::Today => ProjectUsageRange::Today,
+            Self::Days7 => ProjectUsageRange::Days(7),
+            Self::Days30 => ProjectUsageRange::Days(30),
+            Self::Month => ProjectUsageRange::ThisMonth,
+            Self::All => ProjectUsageRange::All,
+        }
+    }
+}
+
+fn parse_date_arg(raw: &str) -> Result<String, String> {
+    chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .map_err(|_| format!("invalid date '{raw}', expected YYYY-MM-DD"))
+}
+
+pub fn execute(cmd: UsageCommand, app: Option<AppType>) -> Result<(), AppError> {
+    match cmd {
+        UsageCommand::Projects {
+            range,
+            from,
+            to,
+            project,
+            limit,
+            json,
+        } => show_projects(app, range, from, to, project.as_deref(), limit, json),
+        UsageCommand::Cache {
+            range,
+            from,
+            to,
+            by,
+            json,
+        } => show_cache(app, range, from, to, by, json),
+        UsageCommand::Alerts(cmd) => execute_alerts(cmd),
+    }
+}
+
+fn open_attributed_store() -> Result<LocalStore, AppError> {
+    let db = Database::init()?;
+    let store = LocalStore::open()?;
+    project_usage::refresh_project_attribution(&db, &store)?;
+    Ok(store)
+}
+
+fn show_projects(
+    app: Option<AppType>,
+    range: UsageRangeArg,
+    from: Option<String>,
+    to: Option<String>,
+    project: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<(), AppError> {
+    let start = from.or_else(|| range.preset().start_date());
+    let filter = project_usage::project_usage_filter(app.as_ref().map(AppType::as_str), start, to);
+    let store = open_attributed_store()?;
+
+    if let Some(raw) = project {
+        let key = project_usage::resolve_project_key(raw).ok_or_else(|| {
+            AppError::InvalidInput(format!("cannot resolve project path '{raw}'"))
+        })?;
+        let rows = store.project_usage_breakdown(&key, &filter)?;
+        let display = store
+            .project_display_path(&key)?
+            .unwrap_or_else(|| raw.to_string());
+        if json {
+            println!(
+                "{}",
+                to_json(&serde_json::json!({
+                    "project": display,
+                    "projectKey": key,
+                    "rows": rows,
+                }))
+                .map_err(|source| AppError::JsonSerialize { source })?
+            );
+            return Ok(());
+        }
+        print_breakdown(&display, &rows);
+        return Ok(());
+    }
+
+    let rows = store.project_usage_rows(&filter, Some(limit))?;
+    if json {
+        println!(
+            "{}",
+            to_json(&rows).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+    print_projects(&rows);
+    Ok(())
+}
+
+fn project_label(row: &ProjectUsageRow) -> String {
+    if row.is_unknown() {
+        return "(unknown project)".to_string();
+    }
+    row.display_path
+        .clone()
+        .unwrap_or_else(|| row.project_key.clone())
+}
+
+fn format_cost(cost: f64) -> String {
+    format!("${cost:.4}")
+}
+
+fn print_projects(rows: &[ProjectUsageRow]) {
+    if rows.is_empty() {
+        println!("{}", info("No attributed usage in this range."));
+        return;
+    }
+    let mut table = create_table();
+    table.set_header(vec!["Project", "Requests", "Success", "Tokens", "Cost"]);
+    for row in rows {
+        table.add_row(vec![
+            project_label(row),
+            row.request_count.to_string(),
+            row.success_count.to_string(),
+            row.total_tokens().to_string(),
+            format_cost(row.total_cost_usd),
+        ]);
+    }
+    println!("{table}");
+    if rows.iter().any(ProjectUsageRow::is_unknown) {
+        println!(
+            "{}",
+            warning("Requests without a resolvable session are grouped under (unknown project).")
+        );
+    }
+}
+
+fn print_breakdown(display: &str, rows: &[ProjectBreakdownRow]) {
+    println!("{}", highlight(display));
+    if rows.is_empty() {
+        println!(
+            "{}",
+            info("No attributed usage for this project in this range.")
+        );
+        return;
+    }
+    let mut table = create_table();
+    table.set_header(vec![
+        "App", "Provider", "Model", "Requests", "Tokens", "Cost",
+    ]);
+    for row in rows {
+        table.add_row(vec![
+            row.app_type.clone(),
+            row.provider_id.clone(),
+            row.model.clone(),
+            row.request_count.to_string(),
+            row.total_tokens.to_string(),
+            format_cost(row.total_cost_usd),
+        ]);
+    }
+    println!("{table}");
+}
+
+fn show_cache(
+    app: Option<AppType>,
+    range: UsageRangeArg,
+    from: Option<String>,
+    to: Option<String>,
+    by: CacheGroupArg,
+    json: bool,
+) -> Result<(), AppError> {
+    let filter = CacheAnalyticsFilter {
+        app_type: app.as_ref().map(|app| app.as_str().to_string()),
+        start: from.or_else(|| range.preset().start_date()),
+        end: to,
+    };
+    let db = Database::init()?;
+    let report = db.get_cache_analytics(&filter, by.into())?;
+    if json {
+        println!(
+            "{}",
+            to_json(&report).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+    print_cache_report(&report);
+    Ok(())
+}
+
+fn format_ratio(ratio: f64) -> String {
+    format!("{:.1}%", ratio * 100.0)
+}
+
+fn cache_stats_cells(stats: &CacheStats) -> Vec<String> {
+    vec![
+        stats.request_count.to_string(),
+        format_ratio(stats.hit_ratio),
+        stats.cache_read_tokens.to_string(),
+        stats.cache_creation_tokens.to_string(),
+        format_cost(stats.saved_usd),
+        format_cost(stats.write_overhead_usd),
+        format_cost(stats.net_saved_usd),
+    ]
+}
+
+fn print_cache_report(report: &CacheAnalyticsReport) {
+    if report.rows.is_empty() {
+        println!("{}", info("No usage in this range."));
+        return;
+    }
+    let first = match report.group_by {
+        CacheGroupBy::Provider => "Provider",
+        CacheGroupBy::Model => "Model",
+        CacheGroupBy::Day => "Date",
+    };
+    let mut header = vec![first];
+    if report.group_by == CacheGroupBy::Provider {
+        header.push("App");
+    }
+    header.extend([
+        "Requests",
+        "Hit ratio",
+        "Cache read",
+        "Cache write",
+        "Saved",
+        "Write cost",
+        "Net",
+    ]);
+    let mut table = create_table();
+    table.set_header(header);
+    for row in &report.rows {
+        let mut cells = vec![row.label.clone()];
+        if let Some(app) = &row.app_type {
+            cells.push(app.clone());
+        }
+        cells.extend(cache_stats_cells(&row.stats));
+        table.add_row(cells);
+    }
+    println!("{table}");
+
+    let totals = &report.totals;
+    println!(
+        "{}",
+        highlight(&format!(
+            "Total: {} hit ratio · saved {} · write overhead {} · net {}",
+            format_ratio(totals.hit_ratio),
+            format_cost(totals.saved_usd),
+            format_cost(totals.write_overhead_usd),
+            format_cost(totals.net_saved_usd),
+        ))
+    );
+    if totals.unpriced_requests > 0 {
+        println!(
+            "{}",
+            info(&format!(
+                "{} requests use models without pricing and are excluded from dollar figures.",
+                totals.unpriced_requests
+            ))
+        );
+    }
+    for warning_row in &report.warnings {
+        println!("{}", warning(&warning_row.message));
+    }
+}
+
+fn execute_alerts(cmd: AlertsCommand) -> Result<(), AppError> {
+    match cmd {
+        AlertsCommand::Show { json } => show_alerts(json),
+        AlertsCommand::Enable => update_alerts(|alerts| alerts.enabled = true, "Alerts enabled."),
+        AlertsCommand::Disable => {
+            update_alerts(|alerts| alerts.enabled = false, "Alerts disabled.")
+        }
+        AlertsCommand::Set {
+            daily_budget,
+            monthly_budget,
+            thresholds,
+            spike_multiplier,
+            spike_min_cost,
+            error_rate,
+            error_min_requests,
+            desktop_notify,
+        } => update_alerts(
+            |alerts| {
+                if let Some(value) = daily_budget {
+                    alerts.daily_budget_usd = non_zero(value);
+                }
+                if let Some(value) = monthly_budget {
+                    alerts.monthly_budget_usd = non_zero(value);
+                }
+                if let Some(values) = thresholds {
+                    alerts.budget_thresholds = values;
+                }
+                if let Some(value) = spike_multiplier {
+                    alerts.spike_multiplier = non_zero(value);
+                }
+                if let Some(value) = spike_min_cost {
+                    alerts.spike_min_cost_usd = value.max(0.0);
+                }
+                if let Some(value) = error_rate {
+                    alerts.error_rate_threshold = non_zero(value);
+                }
+                if let Some(value) = error_min_requests {
+                    alerts.error_rate_min_requests = value;
+                }
+                if let Some(value) = desktop_notify {
+                    alerts.desktop_notify = value;
+                }
+            },
+            "Alert rules updated.",
+        ),
+        AlertsCommand::Webhook(AlertWebhookCommand::Add { url, format }) => {
+            let url = url.trim().to_string();
+            update_alerts(
+                move |alerts| {
+                    alerts.webhooks.retain(|webhook| webhook.url != url);
+                    alerts.webhooks.push(AlertWebhook {
+                        url,
+                        format: format.into(),
+                    });
+                },
+                "Webhook added.",
+            )
+        }
+        AlertsCommand::Webhook(AlertWebhookCommand::Remove { url }) => {
+            let mut alerts = crate::settings::get_usage_alert_settings();
+            let before = alerts.webhooks.len();
+            alerts.webhooks.retain(|webhook| webhook.url != url.trim());
+            if alerts.webhooks.len() == before {
+                return Err(AppError::InvalidInput(format!(
+                    "no webhook with URL '{url}'"
+                )));
+            }
+            crate::settings::set_usage_alert_settings(alerts)?;
+            println!("{}", success("Webhook removed."));
+            Ok(())
+        }
+        AlertsCommand::Test => send_test_alert(),
+        AlertsCommand::History { limit, json } => show_alert_history(limit, json),
+    }
+}
+
+fn non_zero(value: f64) -> Option<f64> {
+    (value != 0.0).then_some(value)
+}
+
+fn update_alerts(
+    mutate: impl FnOnce(&mut UsageAlertSettings),
+    message: &str,
+) -> Result<(), AppError> {
+    let mut alerts = crate::settings::get_usage_alert_settings();
+    mutate(&mut alerts);
+    crate::settings::set_usage_alert_settings(alerts)?;
+    println!("{}", success(message));
+    Ok(())
+}
+
+fn show_alerts(json: bool) -> Result<(), AppError> {
+    let alerts = crate::settings::get_usage_alert_settings();
+    if json {
+        println!(
+            "{}",
+            to_json(&alerts).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+    let budget = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("${v:.2}"));
+    let mut table = create_table();
+    table.set_header(vec!["Rule", "Value"]);
+    table.add_row(vec![
+        "Enabled".to_string(),
+        if alerts.enabled { "yes" } else { "no" }.to_string(),
+    ]);
+    table.add_row(vec![
+        "Daily budget".to_string(),
+        budget(alerts.daily_budget_usd),
+    ]);
+    table.add_row(vec![
+        "Monthly budget".to_string(),
+        budget(alerts.monthly_budget_usd),
+    ]);
+    table.add_row(vec![
+        "Budget thresholds".to_string(),
+        alerts
+            .budget_thresholds
+            .iter()
+            .map(|value| format!("{value}%"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    ]);
+    table.add_row(vec![
+        "Spend spike".to_string(),
+        alerts.spike_multiplier.map_or("off".to_string(), |value| {
+            format!(
+                "{value}x trailing average (min ${:.2}/h)",
+                alerts.spike_min_cost_usd
+            )
+        }),
+    ]);
+    table.add_row(vec![
+        "Error rate".to_string(),
+        alerts
+            .error_rate_threshold
+            .map_or("off".to_string(), |value| {
+                format!(
+                    ">= {:.0}% over 15 min (min {} requests)",
+                    value * 100.0,
+                    alerts.error_rate_min_requests
+                )
+            }),
+    ]);
+    table.add_row(vec![
+        "Desktop notification".to_string(),
+        if alerts.desktop_notify { "on" } else { "off" }.to_string(),
+    ]);
+    for webhook in &alerts.webhooks {
+        table.add_row(vec![
+            format!("Webhook ({})", webhook.format.as_str()),
+            webhook.url.clone(),
+        ]);
+    }
+    println!("{table}");
+    if !alerts.enabled {
+        println!(
+            "{}",
+            info("Alerts are disabled. Run `cc-switch usage alerts enable` to turn them on.")
+        );
+    } else if alerts.daily_budget_usd.is_none() && alerts.monthly_budget_usd.is_none() {
+        println!(
+            "{}",
+            info("No budget set; only spike and error-rate rules are active.")
+        );
+    }
+    Ok(())
+}
+
+fn send_test_alert() -> Result<(), AppError> {
+    let alerts = crate::settings::get_usage_alert_settings();
+    if alerts.webhooks.is_empty() && !alerts.desktop_notify {
+        return Err(AppError::InvalidInput(
+            "no alert channel configured; add a webhook or enable desktop notifications"
+                .to_string(),
+        ));
+    }
+    crate::services::global_proxy::initialize_http_client_from_disk_best_effort();
+    let alert = usage_alerts::test_alert();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|error| AppError::Message(format!("failed to create async runtime: {error}")))?;
+    match runtime.block_on(usage_alerts::deliver_alert(&alerts, &alert)) {
+        Ok(()) => {
+            println!("{}", success("Test alert delivered."));
+            Ok(())
+        }
+        Err(message) => {
+            println!("{}", error(&format!("Delivery failed: {message}")));
+            Err(AppError::Message(message))
+        }
+    }
+}
+
+fn show_alert_history(limit: usize, json: bool) -> Result<(), AppError> {
+    let rows = LocalStore::open()?.usage_alert_history(limit)?;
+    if json {
+        println!(
+            "{}",
+            to_json(&rows).map_err(|source| AppError::JsonSerialize { source })?
+        );
+        return Ok(());
+    }
+    print_alert_history(&rows);
+    Ok(())
+}
+
+fn print_alert_history(rows: &[AlertHistoryRow]) {
+    if rows.is_empty() {
+        println!("{}", info("No alerts have fired yet."));
+        return;
+    }
+    let mut table = create_table();
+    table.set_header(vec!["Time", "Severity", "Alert", "Delivery"]);
+    for row in rows {
+        let time = chrono::DateTime::from_timestamp(row.created_at, 0)
+            .map(|at| {
+                at.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let delivery = match (&row.delivery_error, row.delivered) {
+            (Some(error), _) => format!("failed: {error}"),
+            (None, true) => "sent".to_string(),
+            (None, false) => "pending".to_string(),
+        };
+        table.add_row(vec![
+            time,
+            row.severity.clone(),
+            format!("{}\n{}", row.title, row.message),
+            delivery,
+        ]);
+    }
+    println!("{table}");
+}
@@ -0,0 +1,236 @@
+//! `cc-switch vault`: encrypt stored credentials with a master passphrase.
+//!
+//! Sealed values stay encrypted in the database, exports and sync artifacts;
+//! they are decrypted only while the vault is unlocked, when a live config is
+//! written, the proxy authenticates upstream or a tool is launched.
+
+use std::io::{BufRead, IsTerminal};
+use std::time::Duration;
+
+use clap::Subcommand;
+use zeroize::Zeroizing;
+
+use crate::cli::ui::{highlight, info, success, warning};
+use crate::error::AppError;
+use crate::services::vault::{VaultMigration, VaultService};
+use crate::store::AppState;
+use crate::vault;
+
+const VAULT_AFTER_LONG_HELP: &str = "\
+Examples:
+  cc-switch vault init                 # create the vault and encrypt stored keys
+  cc-switch vault unlock --timeout 480 # unlock for this session (8 hours)
+  cc-switch vault lock
+  CC_SWITCH_VAULT_PASSPHRASE=... cc-switch use acme-relay   # non-interactive";
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(after_long_help = VAULT_AFTER_LONG_HELP)]
+pub enum VaultCommand {
+    /// Create the vault and encrypt every stored credential
+    Init,
+    /// Unlock the vault for this session (the daemon keeps the key)
+    Unlock {
+        /// Lock again after this many minutes
+        #[arg(long, value_name = "MINUTES")]
+        timeout: Option<u64>,
+    },
+    /// Forget the session key
+    Lock,
+    /// Change the master passphrase
+    Rekey,
+    /// Show whether the vault exists, is unlocked and what is still plain text
+    Status,
+    /// Encrypt credentials that were stored in plain text since `init`
+    Migrate,
+}
+
+pub fn execute(cmd: VaultCommand) -> Result<(), AppError> {
+    match cmd {
+        VaultCommand::Init => init(),
+        VaultCommand::Unlock { timeout } => unlock(timeout),
+        VaultCommand::Lock => lock(),
+        VaultCommand::Rekey => rekey(),
+        VaultCommand::Status => status(),
+        VaultCommand::Migrate => migrate(),
+    }
+}
+
+fn init() -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let passphrase = match vault::passphrase_from_env() {
+        Some(passphrase) => passphrase,
+        None => read_passphrase("New vault passphrase:", true)?,
+    };
+    let (key, migration) = VaultService::init(&state, &passphrase)?;
+    println!("{}", success("✓ Vault created"));
+    print_migration(&migration);
+    VaultService::start_session(key, None)?;
+    println!(
+        "{}",
+        info("Unlocked for this session; run `cc-switch vault lock` to lock it.")
+    );
+    Ok(())
+}
+
+fn unlock(timeout: Option<u64>) -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let passphrase = current_passphrase()?;
+    let key = VaultService::unlock(&state, &passphrase)?;
+    let ttl = timeout.map(|minutes| Duration::from_secs(minutes.saturating_mul(60)));
+    VaultService::start_session(key, ttl)?;
+    match timeout {
+        Some(minutes) => println!(
+            "{}",
+            success(&format!("✓ Vault unlocked for {minutes} minute(s)"))
+        ),
+        None => println!(
+            "{}",
+            success("✓ Vault unlocked until `cc-switch vault lock`")
+        ),
+    }
+    Ok(())
+}
+
+fn lock() -> Result<(), AppError> {
+    VaultService::end_session()?;
+    println!("{}", success("✓ Vault locked"));
+    if vault::passphrase_from_env().is_some() {
+        println!(
+            "{}",
+            warning(&format!(
+                "{} is set; processes that see it can still decrypt.",
+                vault::PASSPHRASE_ENV
+            ))
+        );
+    }
+    Ok(())
+}
+
+fn rekey() -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let old = current_passphrase()?;
+    let new = read_passphrase("New vault passphrase:", true)?;
+    VaultService::rekey(&state, &old, &new)?;
+    println!("{}", success("✓ Vault passphrase changed"));
+    Ok(())
+}
+
+fn status() -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let status = VaultService::status(&state)?;
+    let Some(meta) = status.meta else {
+        println!(
+            "{}",
+            info("No vault. Run `cc-switch vault init` to create one.")
+        );
+        if status.plain_entries > 0 {
+            println!(
+                "{}",
+                warning(&format!(
+                    "{} stored entries hold plain-text credentials.",
+                    status.plain_entries
+                ))
+            );
+        }
+        return Ok(());
+    };
+
+    let state_label = if status.unlocked {
+        success("unlocked")
+    } else {
+        warning("locked")
+    };
+    println!("Vault:      {} ({})", highlight(&meta.key_id), state_label);
+    println!(
+        "KDF:        {} (m={} KiB, t={}, p={})",
+        meta.kdf,
+        meta.kdf_params.memory_kib,
+        meta.kdf_params.iterations,
+        meta.kdf_params.parallelism
+    );
+    println!("Created:    {}", meta.created_at);
+    if let Some(rekeyed_at) = meta.rekeyed_at {
+        println!("Rekeyed:    {rekeyed_at}");
+    }
+    if status.plain_entries > 0 {
+        println!(
+            "{}",
+            warning(&format!(
+                "{} stored entries still hold plain-text credentials; run `cc-switch vault migrate`.",
+                status.plain_entries
+            ))
+        );
+    }
+    Ok(())
+}
+
+fn migrate() -> Result<(), AppError> {
+    let state = AppState::try_new()?;
+    let key = vault::current_key()?;
+    let migration = VaultService::migrate(&state, &key)?;
+    print_migration(&migration);
+    Ok(())
+}
+
+fn print_migration(migration: &VaultMigration) {
+    if migration.history_revisions > 0 {
+        println!(
+            "{}",
+            info(&format!(
+                "Encrypted credentials in {} edit history revision(s).",
+                migration.history_revisions
+            ))
+        );
+    }
+    if migration.total_fields() == 0 {
+        println!("{}", info("No plain-text credentials to encrypt."));
+        return;
+    }
+    println!(
+        "{}",
+        info(&format!(
+            "Encrypted {} credential(s): {} in {} provider(s), {} universal provider key(s), {} in {} MCP server(s), {} sync password(s).",
+            migration.total_fields(),
+            migration.provider_fields,
+            migration.providers,
+            migration.universal_providers,
+            migration.mcp_fields,
+            migration.mcp_servers,
+            migration.sync_passwords
+        ))
+    );
+}
+
+fn current_passphrase() -> Result<Zeroizing<String>, AppError> {
+    match vault::passphrase_from_env() {
+        Some(passphrase) => Ok(passphrase),
+        None => read_passphrase("Vault passphrase:", false),
+    }
+}
+
+/// Prompts on a terminal; otherwise reads one line from stdin so scripts can
+/// pipe the passphrase in.
+pub(super) fn read_passphrase(prompt: &str, confirm: bool) -> Result<Zeroizing<String>, AppError> {
+    if !std::io::stdin().is_terminal() {
+        let mut line = Zeroizing::new(String::new());
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|err| AppError::Message(format!("read passphrase: {err}")))?;
+        return Ok(Zeroizing::new(
+            line.trim_end_matches(['\r', '\n']).to_string(),
+        ));
+    }
+
+    let mut input = inquire::Password::new(prompt)
+        .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .with_custom_confirmation_message("Repeat passphrase:")
+        .with_custom_confirmation_error_message("The passphrases don't match.");
+    if !confirm {
+        input = input.without_confirmation();
+    }
+    input
+        .prompt()
+        .map(Zeroizing::new)
+        .map_err(|err| AppError::Message(format!("read passphrase: {err}")))
+}
@@ -621,14 +621,14 @@ pub mod texts {
     pub fn tui_help_line_providers(app_type: &crate::app_config::AppType) -> &'static str {
         if matches!(app_type, crate::app_config::AppType::Hermes) {
             if is_chinese() {
-                "供应商：Space 添加/移除，Enter/e 编辑，a 新增，c 复制，d 删除，t 测试，r 刷新，x 启用"
+                "供应商：Space 添加/移除，Enter/e 编辑，a 新增，c 复制，d 删除，t 测试，r 刷新，x 启用，u 统一供应商"
             } else {
-                "Providers: Space add/remove, Enter/e edit, a add, c copy, d delete, t test, r refresh, x enable"
+                "Providers: Space add/remove, Enter/e edit, a add, c copy, d delete, t test, r refresh, x enable, u universal providers"
             }
         } else if is_chinese() {
-            "供应商：Space 切换，Enter/e 编辑，a 新增，c 复制，d 删除，t 测试，r 刷新，o 临时启动(Claude/Codex)，f 管理故障转移(Claude/Codex/Gemini)，x 设为默认(OpenClaw)"
+            "供应商：Space 切换，Enter/e 编辑，a 新增，c 复制，d 删除，t 测试，r 刷新，o 临时启动(OpenClaw 除外)，f 管理故障转移(Claude/Codex/Gemini)，x 设为默认(OpenClaw)，u 统一供应商"
         } else {
-            "Providers: Space switch, Enter/e edit, a add, c copy, d delete, t test, r refresh, o launch temp (Claude/Codex), f manage failover (Claude/Codex/Gemini), x set default (OpenClaw)"
+            "Providers: Space switch, Enter/e edit, a add, c copy, d delete, t test, r refresh, o launch temp (all but OpenClaw), f manage failover (Claude/Codex/Gemini), x set default (OpenClaw), u universal providers"
         }
     }
 
@@ -772,6 +772,126 @@ pub mod texts {
         }
     }
 
+    pub fn tui_provider_lint_title() -> &'static str {
+        if is_chinese() {
+            "配置检查"
+        } else {
+            "Configuration Check"
+        }
+    }
+
+    pub fn tui_provider_lint_save_anyway_prompt() -> &'static str {
+        if is_chinese() {
+            "仍要保存吗？可用 `cc-switch provider lint --fix` 自动修复安全项。"
+        } else {
+            "Save anyway? `cc-switch provider lint --fix` can apply the safe fixes."
+        }
+    }
+
+    pub fn tui_switch_history_title() -> &'static str {
+        if is_chinese() {
+            "最近切换（Enter 切回切换前的供应商）"
+        } else {
+            "Recent Switches (Enter to switch back)"
+        }
+    }
+
+    pub fn tui_key_switch_history() -> &'static str {
+        if is_chinese() {
+            "最近切换"
+        } else {
+            "recent"
+        }
+    }
+
+    pub fn tui_key_switch_back() -> &'static str {
+        if is_chinese() {
+            "切回"
+        } else {
+            "switch back"
+        }
+    }
+
+    pub fn tui_toast_no_switch_history() -> &'static str {
+        if is_chinese() {
+            "还没有切换记录。"
+        } else {
+            "No provider switches recorded yet."
+        }
+    }
+
+    pub fn tui_toast_switch_history_no_previous() -> &'static str {
+        if is_chinese() {
+            "这次切换之前没有供应商。"
+        } else {
+            "There was no provider before this switch."
+        }
+    }
+
+    pub fn tui_key_import_foreign() -> &'static str {
+        if is_chinese() {
+            "从其他工具导入"
+        } else {
+            "import from tools"
+        }
+    }
+
+    pub fn tui_provider_import_title() -> &'static str {
+        if is_chinese() {
+            "从其他工具导入供应商"
+        } else {
+            "Import Providers From Other Tools"
+        }
+    }
+
+    pub fn tui_provider_import_description() -> &'static str {
+        if is_chinese() {
+            "来自 claude-code-router、LiteLLM 与 shell rc 文件。引用环境变量的 Key 以 env: 引用保存。"
+        } else {
+            "Found in claude-code-router, LiteLLM and shell rc files. Keys read from environment variables are kept as env: references."
+        }
+    }
+
+    pub fn tui_provider_import_same_url(id: &str) -> String {
+        if is_chinese() {
+            format!("同 {id}")
+        } else {
+            format!("same as {id}")
+        }
+    }
+
+    pub fn tui_provider_import_new() -> &'static str {
+        if is_chinese() {
+            "新"
+        } else {
+            "new"
+        }
+    }
+
+    pub fn tui_toast_no_foreign_providers() -> &'static str {
+        if is_chinese() {
+            "未在 claude-code-router、LiteLLM 或 shell rc 文件中发现当前应用的供应商。"
+        } else {
+            "No providers for this app found in claude-code-router, LiteLLM or shell rc files."
+        }
+    }
+
+    pub fn tui_toast_no_foreign_selected() -> &'static str {
+        if is_chinese() {
+            "请至少选择一个要导入的供应商。"
+        } else {
+            "Select at least one provider to import."
+        }
+    }
+
+    pub fn tui_toast_foreign_imported(count: usize) -> String {
+        if is_chinese() {
+            format!("已导入 {count} 个供应商。")
+        } else {
+            format!("Imported {count} provider(s).")
+        }
+    }
+
     pub fn tui_speedtest_title() -> &'static str {
         if is_chinese() {
             "测速"
@@ -1700,6 +1820,30 @@ pub mod texts {
         }
     }
 
+    pub fn tui_header_source() -> &'static str {
+        if is_chinese() {
+            "来源"
+        } else {
+            "Source"
+        }
+    }
+
+    pub fn tui_header_api_format() -> &'static str {
+        if is_chinese() {
+            "格式"
+        } else {
+            "Format"
+        }
+    }
+
+    pub fn tui_header_local() -> &'static str {
+        if is_chinese() {
+            "本地"
+        } else {
+            "Local"
+        }
+    }
+
     pub fn tui_header_field() -> &'static str {
         if is_chinese() {
             "字段"
@@ -4003,6 +4147,14 @@ pub mod texts {
         }
     }
 
+    pub fn tui_key_universal() -> &'static str {
+        if is_chinese() {
+            "统一供应商"
+        } else {
+            "universal"
+        }
+    }
+
     pub fn tui_key_launch_temp() -> &'static str {
         if is_chinese() {
             "临时启动"
@@ -4555,6 +4707,14 @@ pub mod texts {
         }
     }
 
+    pub fn tui_key_save_anyway() -> &'static str {
+        if is_chinese() {
+            "仍然保存"
+        } else {
+            "save anyway"
+        }
+    }
+
     pub fn tui_key_exit_without_save() -> &'static str {
         if is_chinese() {
             "不保存退出"
@@ -7,11 +7,14 @@ mod codex_temp_launch;
 pub mod commands;
 pub mod editor;
 pub(crate) mod failover_policy;
+mod overlay_temp_launch;
 pub use crate::i18n;
 pub mod interactive;
 pub(crate) mod openclaw_form_normalization;
+pub(crate) mod project_pin;
 pub(crate) mod provider_quota;
 pub(crate) mod proxy_settings;
+pub(crate) mod shell_env;
 pub mod terminal;
 pub mod tui;
 pub mod ui;
@@ -50,14 +53,34 @@ pub enum Commands {
 
     /// Switch to a provider (shortcut for `provider switch <id>`)
     Use {
-        /// Provider ID to switch to
+        /// Provider ID to switch to, or `-` for the previous one
         id: String,
     },
 
+    /// Manage universal providers shared by every app (add, list, edit, delete, apply)
+    #[command(subcommand)]
+    Universal(commands::universal::UniversalCommand),
+
+    /// Save and switch whole multi-app setups (save, list, apply, diff, delete)
+    #[command(subcommand)]
+    Profile(commands::profile::ProfileCommand),
+
+    /// Share providers, MCP servers, prompts and skills as a portable file (export, import, inspect)
+    #[command(subcommand)]
+    Bundle(commands::bundle::BundleCommand),
+
     /// Manage MCP servers (list, add, edit, delete, sync)
     #[command(subcommand)]
     Mcp(commands::mcp::McpCommand),
 
+    /// Manage Claude Code hooks synced to Claude and Gemini (list, add, enable, import, sync)
+    #[command(subcommand)]
+    Hooks(commands::hooks::HooksCommand),
+
+    /// Manage Claude Code permission rule sets (list, add, enable, import, sync)
+    #[command(subcommand)]
+    Permissions(commands::permissions::PermissionsCommand),
+
     /// Manage prompts (list, current, live, import, activate, create, rename, edit)
     #[command(subcommand)]
     Prompts(commands::prompts::PromptsCommand),
@@ -82,6 +105,10 @@ pub enum Commands {
     #[command(subcommand)]
     Failover(commands::failover::FailoverCommand),
 
+    /// Show usage breakdowns (per-project cost attribution)
+    #[command(subcommand)]
+    Usage(commands::usage::UsageCommand),
+
     /// Manage saved assistant sessions
     #[command(subcommand)]
     Sessions(commands::sessions::SessionsCommand),
@@ -100,10 +127,20 @@ pub enum Commands {
     #[command(subcommand)]
     Daemon(commands::daemon::DaemonCommand),
 
+    /// Print a shell hook that exports the provider pinned by .cc-switch.toml on cd
+    Hook(commands::hook::HookCommand),
+
     /// Manage environment variables and local CLI tool checks
     #[command(subcommand)]
     Env(commands::env::EnvCommand),
 
+    /// Run any command with a provider's credentials in its environment
+    Run(commands::run::RunCommand),
+
+    /// Encrypt stored credentials with a master passphrase (init, unlock, lock, rekey)
+    #[command(subcommand)]
+    Vault(commands::vault::VaultCommand),
+
     /// Import a resource (provider/mcp/prompt/skill) from a ccswitch:// deep link URL
     Deeplink(commands::deeplink::DeeplinkCommand),
 
@@ -622,6 +659,129 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_usage_projects_subcommand() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "--app",
+            "codex",
+            "usage",
+            "projects",
+            "--range",
+            "7d",
+            "--project",
+            ".",
+            "--json",
+        ]);
+
+        assert_eq!(cli.app, Some(super::AppType::Codex));
+        match cli.command {
+            Some(Commands::Usage(super::commands::usage::UsageCommand::Projects {
+                range,
+                from,
+                project,
+                limit,
+                json,
+                ..
+            })) => {
+                assert_eq!(range, super::commands::usage::UsageRangeArg::Days7);
+                assert_eq!(from, None);
+                assert_eq!(project.as_deref(), Some("."));
+                assert_eq!(limit, 20);
+                assert!(json);
+            }
+            _ => panic!("expected usage projects command"),
+        }
+    }
+
+    #[test]
+    fn usage_projects_rejects_malformed_dates() {
+        assert!(
+            Cli::try_parse_from(["cc-switch", "usage", "projects", "--from", "2026/10/01"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_usage_cache_subcommand() {
+        use super::commands::usage::{CacheGroupArg, UsageCommand, UsageRangeArg};
+
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "usage",
+            "cache",
+            "--by",
+            "day",
+            "--range",
+            "all",
+        ]);
+        match cli.command {
+            Some(Commands::Usage(UsageCommand::Cache {
+                range, by, json, ..
+            })) => {
+                assert_eq!(range, UsageRangeArg::All);
+                assert_eq!(by, CacheGroupArg::Day);
+                assert!(!json);
+            }
+            _ => panic!("expected usage cache command"),
+        }
+    }
+
+    #[test]
+    fn parses_usage_alerts_set_and_webhook_add() {
+        use super::commands::usage::{
+            AlertWebhookCommand, AlertsCommand, UsageCommand, WebhookFormatArg,
+        };
+
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "usage",
+            "alerts",
+            "set",
+            "--daily-budget",
+            "20",
+            "--thresholds",
+            "50,90",
+            "--error-rate",
+            "0",
+        ]);
+        match cli.command {
+            Some(Commands::Usage(UsageCommand::Alerts(AlertsCommand::Set {
+                daily_budget,
+                thresholds,
+                error_rate,
+                monthly_budget,
+                ..
+            }))) => {
+                assert_eq!(daily_budget, Some(20.0));
+                assert_eq!(thresholds, Some(vec![50, 90]));
+                assert_eq!(error_rate, Some(0.0));
+                assert_eq!(monthly_budget, None);
+            }
+            _ => panic!("expected usage alerts set command"),
+        }
+
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "usage",
+            "alerts",
+            "webhook",
+            "add",
+            "https://hooks.slack.com/services/x",
+            "--format",
+            "slack",
+        ]);
+        match cli.command {
+            Some(Commands::Usage(UsageCommand::Alerts(AlertsCommand::Webhook(
+                AlertWebhookCommand::Add { url, format },
+            )))) => {
+                assert_eq!(url, "https://hooks.slack.com/services/x");
+                assert_eq!(format, WebhookFormatArg::Slack);
+            }
+            _ => panic!("expected usage alerts webhook add command"),
+        }
+    }
+
     #[test]
     fn parses_sessions_show_with_provider() {
         let cli = Cli::parse_from([
@@ -765,7 +925,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(!dry_run);
                 assert!(native_args.is_empty());
             }
@@ -784,7 +944,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(dry_run);
                 assert!(native_args.is_empty());
             }
@@ -810,7 +970,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(!dry_run);
                 assert_eq!(
                     native_args,
@@ -850,7 +1010,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(!dry_run);
                 assert!(native_args.is_empty());
             }
@@ -878,7 +1038,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(dry_run);
                 assert_eq!(
                     native_args,
@@ -910,7 +1070,7 @@ mod tests {
                 dry_run,
                 native_args,
             })) => {
-                assert_eq!(selector, "demo");
+                assert_eq!(selector.as_deref(), Some("demo"));
                 assert!(!dry_run);
                 assert_eq!(
                     native_args,
@@ -962,6 +1122,56 @@ mod tests {
         assert!(help.contains("cc-switch start codex demo -- --model gpt-5.4"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn parses_start_opencode_dry_run_with_native_args() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "start",
+            "opencode",
+            "relay",
+            "--dry-run",
+            "--",
+            "run",
+            "hello",
+        ]);
+
+        match cli.command {
+            Some(Commands::Start(super::commands::start::StartCommand::OpenCode {
+                selector,
+                dry_run,
+                native_args,
+            })) => {
+                assert_eq!(selector.as_deref(), Some("relay"));
+                assert!(dry_run);
+                assert_eq!(
+                    native_args,
+                    vec![OsString::from("run"), OsString::from("hello")]
+                );
+            }
+            _ => panic!("expected start opencode command"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_start_gemini_and_hermes_without_selector() {
+        for (app, expect_gemini) in [("gemini", true), ("hermes", false)] {
+            let cli = Cli::parse_from(["cc-switch", "start", app]);
+            match cli.command {
+                Some(Commands::Start(super::commands::start::StartCommand::Gemini {
+                    selector,
+                    ..
+                })) if expect_gemini => assert!(selector.is_none()),
+                Some(Commands::Start(super::commands::start::StartCommand::Hermes {
+                    selector,
+                    ..
+                })) if !expect_gemini => assert!(selector.is_none()),
+                _ => panic!("expected start {app} command"),
+            }
+        }
+    }
+
     #[test]
     fn parses_prompts_live_subcommand() {
         let cli = Cli::parse_from(["cc-switch", "prompts", "live"]);
@@ -996,6 +1206,377 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_provider_keys_add_subcommand() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "keys",
+            "add",
+            "relay",
+            "sk-second",
+            "--label",
+            "second",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Keys(
+                super::commands::provider_keys::ProviderKeysCommand::Add { id, key, label },
+            ))) => {
+                assert_eq!(id, "relay");
+                assert_eq!(key, "sk-second");
+                assert_eq!(label.as_deref(), Some("second"));
+            }
+            _ => panic!("expected provider keys add command"),
+        }
+    }
+
+    #[test]
+    fn parses_provider_health_window() {
+        let cli = Cli::parse_from(["cc-switch", "provider", "health", "relay", "--since", "7d"]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Health {
+                id,
+                since,
+                json,
+            })) => {
+                assert_eq!(id.as_deref(), Some("relay"));
+                assert_eq!(since, 7 * 24 * 60 * 60);
+                assert!(!json);
+            }
+            _ => panic!("expected provider health command"),
+        }
+        assert!(Cli::try_parse_from(["cc-switch", "provider", "health", "--since", "7w"]).is_err());
+    }
+
+    #[test]
+    fn parses_provider_probe_selection() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "probe",
+            "relay",
+            "--only",
+            "image-input",
+            "--only",
+            "thinking",
+            "--long-context",
+            "120000",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Probe {
+                id,
+                only,
+                long_context,
+                timeout,
+                show,
+                ..
+            })) => {
+                assert_eq!(id, "relay");
+                assert_eq!(
+                    only,
+                    vec![
+                        super::commands::provider::ProbeCapabilityArg::ImageInput,
+                        super::commands::provider::ProbeCapabilityArg::Thinking,
+                    ]
+                );
+                assert_eq!(long_context, Some(120_000));
+                assert_eq!(timeout, 60);
+                assert!(!show);
+            }
+            _ => panic!("expected provider probe command"),
+        }
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "provider",
+            "probe",
+            "relay",
+            "--show",
+            "--only",
+            "tools"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_provider_add_preset_option() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "add",
+            "--preset",
+            "team-relay",
+            "--name",
+            "Relay",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Add {
+                preset,
+                template,
+                ..
+            })) => {
+                assert_eq!(preset.as_deref(), Some("team-relay"));
+                assert!(template.is_none());
+            }
+            _ => panic!("expected provider add command"),
+        }
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "provider",
+            "add",
+            "--preset",
+            "team-relay",
+            "--template",
+            "deepseek",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_provider_models_remap() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "models",
+            "remap",
+            "relay",
+            "--from",
+            "glm-4.6",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Models(
+                super::commands::provider_models::ProviderModelsCommand::Remap { id, from, to },
+            ))) => {
+                assert_eq!(id, "relay");
+                assert_eq!(from, "glm-4.6");
+                assert!(to.is_none());
+            }
+            _ => panic!("expected provider models remap command"),
+        }
+        assert!(
+            Cli::try_parse_from(["cc-switch", "provider", "models", "remap", "relay"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_provider_diff_and_revert() {
+        let cli = Cli::parse_from(["cc-switch", "provider", "diff", "relay"]);
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Diff {
+                id,
+                rev,
+            })) => {
+                assert_eq!(id, "relay");
+                assert!(rev.is_none());
+            }
+            _ => panic!("expected provider diff command"),
+        }
+
+        let cli = Cli::parse_from(["cc-switch", "provider", "revert", "relay", "--rev", "3"]);
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Revert {
+                id,
+                rev,
+            })) => {
+                assert_eq!(id, "relay");
+                assert_eq!(rev, 3);
+            }
+            _ => panic!("expected provider revert command"),
+        }
+        assert!(Cli::try_parse_from(["cc-switch", "provider", "revert", "relay"]).is_err());
+    }
+
+    #[test]
+    fn parses_use_previous_and_switch_history() {
+        let cli = Cli::parse_from(["cc-switch", "use", "-"]);
+        match cli.command {
+            Some(Commands::Use { id }) => assert_eq!(id, "-"),
+            _ => panic!("expected use shortcut command"),
+        }
+
+        let cli = Cli::parse_from(["cc-switch", "provider", "history", "--switches"]);
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::History {
+                id,
+                switches,
+                limit,
+                json,
+            })) => {
+                assert!(id.is_none());
+                assert!(switches && !json);
+                assert_eq!(limit, 20);
+            }
+            _ => panic!("expected provider history command"),
+        }
+        assert!(Cli::try_parse_from(["cc-switch", "provider", "history"]).is_err());
+        assert!(
+            Cli::try_parse_from(["cc-switch", "provider", "history", "relay", "--switches"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_bundle_export_selection() {
+        use super::commands::bundle::BundleCommand;
+        use crate::services::bundle::SecretMode;
+
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "bundle",
+            "export",
+            "team.yaml",
+            "--provider",
+            "relay",
+            "--provider",
+            "codex:relay",
+            "--mcp",
+            "github",
+            "--secrets",
+            "encrypt",
+        ]);
+
+        match cli.command {
+            Some(Commands::Bundle(BundleCommand::Export {
+                file,
+                all,
+                providers,
+                mcp_servers,
+                secrets,
+                ..
+            })) => {
+                assert_eq!(file, std::path::PathBuf::from("team.yaml"));
+                assert!(!all);
+                assert_eq!(
+                    providers,
+                    vec!["relay".to_string(), "codex:relay".to_string()]
+                );
+                assert_eq!(mcp_servers, vec!["github".to_string()]);
+                assert_eq!(secrets, SecretMode::Encrypt);
+            }
+            _ => panic!("expected bundle export command"),
+        }
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "bundle",
+            "import",
+            "team.yaml",
+            "--on-conflict",
+            "rename"
+        ])
+        .is_ok());
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "bundle",
+            "import",
+            "team.yaml",
+            "--on-conflict",
+            "merge"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_provider_rules_add() {
+        use super::commands::provider_rules::ProviderRulesCommand;
+
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "rules",
+            "add",
+            "night",
+            "--to",
+            "relay",
+            "--schedule",
+            "22:00-08:00",
+            "--days",
+            "mon,fri",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Rules(
+                ProviderRulesCommand::Add {
+                    name,
+                    to,
+                    schedule,
+                    days,
+                    quota_above,
+                    ..
+                },
+            ))) => {
+                assert_eq!(name, "night");
+                assert_eq!(to, "relay");
+                assert_eq!(schedule.as_deref(), Some("22:00-08:00"));
+                assert_eq!(days, vec!["mon".to_string(), "fri".to_string()]);
+                assert!(quota_above.is_none());
+            }
+            _ => panic!("expected provider rules add command"),
+        }
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "provider",
+            "rules",
+            "add",
+            "x",
+            "--to",
+            "relay"
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "provider",
+            "rules",
+            "add",
+            "x",
+            "--to",
+            "relay",
+            "--budget-exceeded",
+            "--quota-above",
+            "90",
+        ])
+        .is_err());
+        assert!(Cli::try_parse_from([
+            "cc-switch",
+            "provider",
+            "rules",
+            "add",
+            "x",
+            "--to",
+            "relay",
+            "--quota-above",
+            "101",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parses_provider_lint_selection() {
+        let cli = Cli::parse_from(["cc-switch", "provider", "lint", "--all", "--fix"]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Lint {
+                id,
+                all,
+                fix,
+                json,
+            })) => {
+                assert!(id.is_none());
+                assert!(all && fix && !json);
+            }
+            _ => panic!("expected provider lint command"),
+        }
+        assert!(Cli::try_parse_from(["cc-switch", "provider", "lint"]).is_err());
+        assert!(Cli::try_parse_from(["cc-switch", "provider", "lint", "relay", "--all"]).is_err());
+        assert!(
+            Cli::try_parse_from(["cc-switch", "provider", "lint", "relay", "--fix", "--json"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn parses_provider_add_template_option() {
         let cli = Cli::parse_from(["cc-switch", "provider", "add", "--template", "codex-oauth"]);
@@ -1168,9 +1749,12 @@ mod tests {
             Some(Commands::Provider(super::commands::provider::ProviderCommand::Quota {
                 id,
                 json,
+                history,
+                ..
             })) => {
                 assert_eq!(id, "demo");
                 assert!(!json);
+                assert!(!history);
             }
             _ => panic!("expected provider quota command"),
         }
@@ -1184,14 +1768,49 @@ mod tests {
             Some(Commands::Provider(super::commands::provider::ProviderCommand::Quota {
                 id,
                 json,
+                history,
+                ..
             })) => {
                 assert_eq!(id, "demo");
                 assert!(json);
+                assert!(!history);
             }
             _ => panic!("expected provider quota json command"),
         }
     }
 
+    #[test]
+    fn parses_provider_quota_history_subcommand() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "quota",
+            "demo",
+            "--history",
+            "--days",
+            "7",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::Quota {
+                id,
+                history,
+                days,
+                ..
+            })) => {
+                assert_eq!(id, "demo");
+                assert!(history);
+                assert_eq!(days, 7);
+            }
+            _ => panic!("expected provider quota history command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["cc-switch", "provider", "quota", "demo", "--days", "7"]).is_err(),
+            "--days only applies to --history"
+        );
+    }
+
     #[test]
     fn parses_provider_usage_query_show_json_subcommand() {
         let cli = Cli::parse_from([
@@ -1312,6 +1931,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_provider_import_from_selection() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "provider",
+            "import-from",
+            "ccr",
+            "--file",
+            "router.json",
+            "--select",
+            "1,3",
+        ]);
+
+        match cli.command {
+            Some(Commands::Provider(super::commands::provider::ProviderCommand::ImportFrom {
+                source,
+                file,
+                all,
+                select,
+                ..
+            })) => {
+                assert_eq!(source, crate::services::ForeignSource::Ccr);
+                assert_eq!(file, Some(std::path::PathBuf::from("router.json")));
+                assert!(!all);
+                assert_eq!(select, vec![1, 3]);
+            }
+            _ => panic!("expected provider import-from command"),
+        }
+    }
+
+    #[test]
+    fn parses_config_codex_profiles_enable_with_excludes() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "config",
+            "codex-profiles",
+            "enable",
+            "--exclude",
+            "official",
+            "--exclude",
+            "staging",
+        ]);
+
+        match cli.command {
+            Some(Commands::Config(super::commands::config::ConfigCommand::CodexProfiles(
+                super::commands::config_codex_profiles::CodexProfilesCommand::Enable { exclude },
+            ))) => {
+                assert_eq!(exclude, vec!["official".to_string(), "staging".to_string()]);
+            }
+            _ => panic!("expected config codex-profiles enable command"),
+        }
+    }
+
+    #[test]
+    fn parses_hooks_add_with_repeated_commands_and_apps() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "hooks",
+            "add",
+            "Lint",
+            "--event",
+            "PostToolUse",
+            "--matcher",
+            "Edit|Write",
+            "--command",
+            "cargo fmt",
+            "--command",
+            "cargo clippy",
+            "--apps",
+            "claude,gemini",
+        ]);
+
+        match cli.command {
+            Some(Commands::Hooks(super::commands::hooks::HooksCommand::Add {
+                name,
+                event,
+                matcher,
+                commands,
+                apps,
+                ..
+            })) => {
+                assert_eq!(name, "Lint");
+                assert_eq!(event, "PostToolUse");
+                assert_eq!(matcher.as_deref(), Some("Edit|Write"));
+                assert_eq!(commands, vec!["cargo fmt", "cargo clippy"]);
+                assert_eq!(apps, vec!["claude", "gemini"]);
+            }
+            _ => panic!("expected hooks add command"),
+        }
+    }
+
     #[test]
     fn parses_provider_remove_from_config_subcommand() {
         let cli = Cli::parse_from(["cc-switch", "provider", "remove-from-config", "demo"]);
@@ -1536,6 +2246,105 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_config_otel_set_subcommand() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "config",
+            "otel",
+            "set",
+            "--endpoint",
+            "http://localhost:4318",
+            "--header",
+            "authorization=Bearer abc",
+            "--header",
+            "x-team=infra",
+            "--metrics-interval",
+            "0",
+            "--enable",
+        ]);
+
+        match cli.command {
+            Some(Commands::Config(super::commands::config::ConfigCommand::Otel(
+                super::commands::config_otel::OtelCommand::Set {
+                    endpoint,
+                    headers,
+                    metrics_interval,
+                    enable,
+                    ..
+                },
+            ))) => {
+                assert_eq!(endpoint.as_deref(), Some("http://localhost:4318"));
+                assert_eq!(
+                    headers,
+                    vec![
+                        ("authorization".to_string(), "Bearer abc".to_string()),
+                        ("x-team".to_string(), "infra".to_string()),
+                    ]
+                );
+                assert_eq!(metrics_interval, Some(0));
+                assert!(enable);
+            }
+            _ => panic!("expected config otel set command"),
+        }
+        assert!(
+            Cli::try_parse_from(["cc-switch", "config", "otel", "set", "--header", "nokey"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_universal_add_subcommand() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "universal",
+            "add",
+            "--name",
+            "Relay",
+            "--base-url",
+            "https://relay.example.com",
+            "--api-key",
+            "sk-relay",
+            "--apps",
+            "claude,codex",
+            "--codex-model",
+            "gpt-relay",
+        ]);
+
+        match cli.command {
+            Some(Commands::Universal(super::commands::universal::UniversalCommand::Add {
+                name,
+                id,
+                fields,
+                ..
+            })) => {
+                assert_eq!(name, "Relay");
+                assert_eq!(id, None);
+                assert_eq!(fields.apps, Some(vec![AppType::Claude, AppType::Codex]));
+                assert_eq!(fields.codex_model.as_deref(), Some("gpt-relay"));
+            }
+            _ => panic!("expected universal add command"),
+        }
+    }
+
+    #[test]
+    fn parses_profile_diff_subcommand() {
+        let cli = Cli::parse_from(["cc-switch", "profile", "diff", "work", "personal", "--json"]);
+
+        match cli.command {
+            Some(Commands::Profile(super::commands::profile::ProfileCommand::Diff {
+                name,
+                other,
+                json,
+            })) => {
+                assert_eq!(name, "work");
+                assert_eq!(other.as_deref(), Some("personal"));
+                assert!(json);
+            }
+            _ => panic!("expected profile diff command"),
+        }
+    }
+
     #[test]
     fn parses_config_openclaw_env_put_subcommand() {
         let cli = Cli::parse_from([
@@ -1794,6 +2603,62 @@ mod tests {
         assert!(help.contains("applicable"));
     }
 
+    #[test]
+    fn parses_env_export_with_format() {
+        let cli = Cli::parse_from(["cc-switch", "env", "export", "acme", "--shell", "dotenv"]);
+
+        match cli.command {
+            Some(Commands::Env(super::commands::env::EnvCommand::Export {
+                provider,
+                format,
+                model,
+                no_proxy,
+            })) => {
+                assert_eq!(provider, "acme");
+                assert_eq!(format, crate::cli::shell_env::ExportFormat::Dotenv);
+                assert!(model.is_none());
+                assert!(!no_proxy);
+            }
+            _ => panic!("expected env export command"),
+        }
+    }
+
+    #[test]
+    fn parses_run_with_trailing_command() {
+        let cli = Cli::parse_from([
+            "cc-switch",
+            "--app",
+            "codex",
+            "run",
+            "acme",
+            "--",
+            "python",
+            "-V",
+        ]);
+
+        assert_eq!(cli.app, Some(AppType::Codex));
+        match cli.command {
+            Some(Commands::Run(cmd)) => {
+                assert_eq!(cmd.provider, "acme");
+                assert_eq!(cmd.command, vec!["python", "-V"]);
+            }
+            _ => panic!("expected run command"),
+        }
+        assert!(Cli::try_parse_from(["cc-switch", "run", "acme"]).is_err());
+    }
+
+    #[test]
+    fn parses_vault_unlock_with_timeout() {
+        let cli = Cli::parse_from(["cc-switch", "vault", "unlock", "--timeout", "480"]);
+
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Vault(
+                super::commands::vault::VaultCommand::Unlock { timeout: Some(480) }
+            ))
+        ));
+    }
+
     #[test]
     fn parses_env_tools_subcommand() {
         let cli = Cli::parse_from(["cc-switch", "env", "tools"]);
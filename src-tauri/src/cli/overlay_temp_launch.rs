@@ -0,0 +1,803 @@
+//! Temporary launches for Gemini, OpenCode and Hermes.
+//!
+//! Claude takes `--settings` and Codex takes `CODEX_HOME`; these apps are
+//! pointed at a provider through a private overlay directory plus variables
+//! exported only to the launched process:
+//!
+//! - Gemini: the provider's `env` block is exported (Gemini CLI only fills
+//!   unset variables from `~/.gemini/.env`) and
+//!   `GEMINI_CLI_SYSTEM_SETTINGS_PATH` points at a settings overlay that
+//!   carries the auth type.
+//! - OpenCode: `OPENCODE_CONFIG` points at a generated config that defines
+//!   the provider and selects its model; OpenCode merges it over the global
+//!   config.
+//! - Hermes: `HERMES_HOME` points at a private home whose `config.yaml`
+//!   activates the provider. Every other entry of the real home is symlinked
+//!   so sessions, memories and `.env` keep working.
+//!
+//! The overlay directory is removed when the app exits.
+
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::cli::shell_env::provider_env_vars;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const GEMINI_SETTINGS_FILE: &str = "settings.json";
+const OPENCODE_CONFIG_FILE: &str = "opencode.json";
+const HERMES_CONFIG_FILE: &str = "config.yaml";
+
+/// Variables a previous switch may have left in `~/.gemini/.env`. They are
+/// exported empty when the provider does not set them, so the file cannot
+/// leak another provider's endpoint or key into the launch.
+const GEMINI_MANAGED_ENV_KEYS: &[&str] =
+    &["GEMINI_API_KEY", "GOOGLE_GEMINI_BASE_URL", "GEMINI_MODEL"];
+
+#[derive(Debug, Clone)]
+pub(crate) struct PreparedOverlayLaunch {
+    pub(crate) app: AppType,
+    pub(crate) executable: PathBuf,
+    pub(crate) overlay_dir: PathBuf,
+    /// Exported to the launched process only.
+    pub(crate) env: Vec<(String, String)>,
+    /// Files generated inside `overlay_dir`.
+    pub(crate) files: Vec<PathBuf>,
+}
+
+impl PreparedOverlayLaunch {
+    pub(crate) fn cleanup_overlay_dir(&self) -> Result<(), AppError> {
+        cleanup_overlay_dir(&self.overlay_dir)
+    }
+}
+
+struct OverlayPlan {
+    env: Vec<(String, String)>,
+    files: Vec<(&'static str, Vec<u8>)>,
+    links: Vec<(OsString, PathBuf)>,
+}
+
+pub(crate) fn supports_app(app: &AppType) -> bool {
+    matches!(app, AppType::Gemini | AppType::OpenCode | AppType::Hermes)
+}
+
+pub(crate) fn app_name(app: &AppType) -> &'static str {
+    match app {
+        AppType::Claude => "Claude",
+        AppType::Codex => "Codex",
+        AppType::Gemini => "Gemini",
+        AppType::OpenCode => "OpenCode",
+        AppType::Hermes => "Hermes",
+        AppType::OpenClaw => "OpenClaw",
+    }
+}
+
+fn binary_name(app: &AppType) -> &'static str {
+    match app {
+        AppType::Gemini => "gemini",
+        AppType::OpenCode => "opencode",
+        AppType::Hermes => "hermes",
+        AppType::Claude => "claude",
+        AppType::Codex => "codex",
+        AppType::OpenClaw => "openclaw",
+    }
+}
+
+/// Settings the overlay is generated from. Gemini uses the effective
+/// snapshot (common config applied) exactly like a real switch; OpenCode and
+/// Hermes keep their provider fragment as stored.
+pub(crate) fn launch_settings(
+    state: &AppState,
+    app: &AppType,
+    provider: &Provider,
+) -> Result<Value, AppError> {
+    match app {
+        AppType::Gemini => {
+            ProviderService::build_effective_live_snapshot_from_state(state, app.clone(), provider)
+        }
+        _ => Ok(provider.settings_config.clone()),
+    }
+}
+
+pub(crate) fn resolve_binary(app: &AppType) -> Result<PathBuf, AppError> {
+    let binary = binary_name(app);
+    let name = app_name(app);
+    which::which(binary).map_err(|_| {
+        AppError::localized(
+            "temp_launch.missing_binary",
+            format!("未找到 {binary} 命令，请先安装 {name} CLI。"),
+            format!("Could not find `{binary}` in PATH. Install {name} CLI first."),
+        )
+    })
+}
+
+#[cfg(unix)]
+pub(crate) fn ensure_temp_launch_supported(_app: &AppType) -> Result<(), AppError> {
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn ensure_temp_launch_supported(app: &AppType) -> Result<(), AppError> {
+    let name = app_name(app);
+    Err(AppError::localized(
+        "temp_launch.unsupported_platform",
+        format!("当前平台暂不支持在当前终端临时启动 {name}。"),
+        format!(
+            "Temporary {name} launch in the current terminal is not supported on this platform."
+        ),
+    ))
+}
+
+pub(crate) fn prepare_launch_with<Resolve>(
+    app: &AppType,
+    provider: &Provider,
+    settings: &Value,
+    model: Option<&str>,
+    temp_dir: &Path,
+    resolve_binary: Resolve,
+) -> Result<PreparedOverlayLaunch, AppError>
+where
+    Resolve: FnOnce() -> Result<PathBuf, AppError>,
+{
+    let executable = resolve_binary()?;
+    let overlay_dir = temp_overlay_dir_path(temp_dir, app, &provider.id);
+    let settings = crate::secret_ref::resolve_settings(settings)?;
+    let plan = plan_launch(
+        app,
+        provider,
+        &settings,
+        model,
+        &overlay_dir,
+        &crate::hermes_config::get_hermes_dir(),
+    )?;
+    let files = write_overlay_dir(&overlay_dir, &plan)?;
+    Ok(PreparedOverlayLaunch {
+        app: app.clone(),
+        executable,
+        overlay_dir,
+        env: plan.env,
+        files,
+    })
+}
+
+pub(crate) fn preview_launch_with<Resolve>(
+    app: &AppType,
+    provider: &Provider,
+    settings: &Value,
+    model: Option<&str>,
+    temp_dir: &Path,
+    resolve_binary: Resolve,
+) -> Result<PreparedOverlayLaunch, AppError>
+where
+    Resolve: FnOnce() -> Result<PathBuf, AppError>,
+{
+    let executable = resolve_binary()?;
+    let overlay_dir = temp_overlay_dir_path(temp_dir, app, &provider.id);
+    let plan = plan_launch(
+        app,
+        provider,
+        settings,
+        model,
+        &overlay_dir,
+        &crate::hermes_config::get_hermes_dir(),
+    )?;
+    let files = plan
+        .files
+        .iter()
+        .map(|(name, _)| overlay_dir.join(name))
+        .collect();
+    Ok(PreparedOverlayLaunch {
+        app: app.clone(),
+        executable,
+        overlay_dir,
+        env: plan.env,
+        files,
+    })
+}
+
+fn plan_launch(
+    app: &AppType,
+    provider: &Provider,
+    settings: &Value,
+    model: Option<&str>,
+    overlay_dir: &Path,
+    hermes_dir: &Path,
+) -> Result<OverlayPlan, AppError> {
+    match app {
+        AppType::Gemini => plan_gemini(provider, settings, model, overlay_dir),
+        AppType::OpenCode => plan_opencode(&provider.id, settings, model, overlay_dir),
+        AppType::Hermes => plan_hermes(&provider.id, settings, model, overlay_dir, hermes_dir),
+        AppType::Claude | AppType::Codex | AppType::OpenClaw => {
+            let name = app_name(app);
+            Err(AppError::localized(
+                "temp_launch.unsupported_app",
+                format!("{name} 不支持覆盖层临时启动。"),
+                format!("{name} does not support overlay temporary launches."),
+            ))
+        }
+    }
+}
+
+fn plan_gemini(
+    provider: &Provider,
+    settings: &Value,
+    model: Option<&str>,
+    overlay_dir: &Path,
+) -> Result<OverlayPlan, AppError> {
+    if settings.get("env").and_then(Value::as_object).is_none() {
+        return Err(AppError::localized(
+            "gemini.temp_launch_missing_env",
+            format!("供应商 {} 缺少有效的 env 配置。", provider.id),
+            format!("Provider {} is missing a valid env object.", provider.id),
+        ));
+    }
+
+    let mut effective = provider.clone();
+    effective.settings_config = settings.clone();
+    let mut env = provider_env_vars(&AppType::Gemini, &effective, model);
+    for key in GEMINI_MANAGED_ENV_KEYS {
+        if !env.iter().any(|(name, _)| name == key) {
+            env.push(((*key).to_string(), String::new()));
+        }
+    }
+
+    let mut overlay = match settings.get("config") {
+        Some(Value::Object(config)) => Value::Object(config.clone()),
+        Some(Value::Null) | None => json!({}),
+        Some(_) => {
+            return Err(AppError::localized(
+                "gemini.validation.invalid_config",
+                "Gemini 配置格式错误: config 必须是对象或 null",
+                "Gemini config invalid: config must be an object or null",
+            ))
+        }
+    };
+    set_gemini_selected_type(
+        &mut overlay,
+        ProviderService::gemini_selected_auth_type(provider),
+    );
+    let settings_path = overlay_dir.join(GEMINI_SETTINGS_FILE);
+    env.push((
+        "GEMINI_CLI_SYSTEM_SETTINGS_PATH".to_string(),
+        settings_path.display().to_string(),
+    ));
+
+    Ok(OverlayPlan {
+        env,
+        files: vec![(GEMINI_SETTINGS_FILE, to_pretty_json(&overlay)?)],
+        links: Vec::new(),
+    })
+}
+
+fn set_gemini_selected_type(settings: &mut Value, selected_type: &str) {
+    let root = ensure_object(settings);
+    let security = ensure_object(root.entry("security").or_insert_with(|| json!({})));
+    let auth = ensure_object(security.entry("auth").or_insert_with(|| json!({})));
+    auth.insert(
+        "selectedType".to_string(),
+        Value::String(selected_type.to_string()),
+    );
+}
+
+fn ensure_object(value: &mut Value) -> &mut serde_json::Map<String, Value> {
+    if !value.is_object() {
+        *value = json!({});
+    }
+    value
+        .as_object_mut()
+        .expect("value was just made an object")
+}
+
+fn plan_opencode(
+    provider_id: &str,
+    settings: &Value,
+    model: Option<&str>,
+    overlay_dir: &Path,
+) -> Result<OverlayPlan, AppError> {
+    // Older entries stored a whole opencode.json; unwrap the provider fragment
+    // the same way a switch does.
+    let fragment = match settings.as_object() {
+        Some(obj) if obj.contains_key("$schema") || obj.contains_key("provider") => obj
+            .get("provider")
+            .and_then(|providers| providers.get(provider_id))
+            .cloned()
+            .unwrap_or_else(|| settings.clone()),
+        Some(_) => settings.clone(),
+        None => {
+            return Err(AppError::localized(
+                "opencode.temp_launch_settings_not_object",
+                format!("供应商 {provider_id} 的 OpenCode 配置必须是 JSON 对象。"),
+                format!("Provider {provider_id} OpenCode configuration must be a JSON object."),
+            ))
+        }
+    };
+
+    let model = model
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            fragment
+                .get("models")
+                .and_then(Value::as_object)
+                .and_then(|models| models.keys().next().cloned())
+        });
+    let mut config = json!({
+        "$schema": "https://opencode.ai/config.json",
+        "provider": { provider_id: fragment },
+    });
+    if let Some(model) = model {
+        config["model"] = Value::String(format!("{provider_id}/{model}"));
+    }
+
+    Ok(OverlayPlan {
+        env: vec![(
+            "OPENCODE_CONFIG".to_string(),
+            overlay_dir.join(OPENCODE_CONFIG_FILE).display().to_string(),
+        )],
+        files: vec![(OPENCODE_CONFIG_FILE, to_pretty_json(&config)?)],
+        links: Vec::new(),
+    })
+}
+
+fn plan_hermes(
+    provider_id: &str,
+    settings: &Value,
+    model: Option<&str>,
+    overlay_dir: &Path,
+    hermes_dir: &Path,
+) -> Result<OverlayPlan, AppError> {
+    if !settings.is_object() {
+        return Err(AppError::localized(
+            "provider.hermes.settings.not_object",
+            "Hermes 配置必须是 JSON 对象",
+            "Hermes configuration must be a JSON object",
+        ));
+    }
+
+    let config_path = hermes_dir.join(HERMES_CONFIG_FILE);
+    let base = match fs::read_to_string(&config_path) {
+        Ok(content) if !content.trim().is_empty() => serde_yaml::from_str(&content)
+            .map_err(|e| AppError::Config(format!("Failed to parse Hermes config as YAML: {e}")))?,
+        Ok(_) => serde_yaml::Value::Null,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => serde_yaml::Value::Null,
+        Err(err) => return Err(AppError::io(&config_path, err)),
+    };
+    let overlay = crate::hermes_config::build_overlay_config(base, provider_id, settings, model)?;
+    let overlay_text = serde_yaml::to_string(&overlay)
+        .map_err(|e| AppError::Config(format!("Failed to serialize Hermes config: {e}")))?;
+
+    let mut links = Vec::new();
+    match fs::read_dir(hermes_dir) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry.map_err(|err| AppError::io(hermes_dir, err))?;
+                let name = entry.file_name();
+                if name != HERMES_CONFIG_FILE {
+                    links.push((name, entry.path()));
+                }
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(AppError::io(hermes_dir, err)),
+    }
+    links.sort();
+
+    Ok(OverlayPlan {
+        env: vec![("HERMES_HOME".to_string(), overlay_dir.display().to_string())],
+        files: vec![(HERMES_CONFIG_FILE, overlay_text.into_bytes())],
+        links,
+    })
+}
+
+fn to_pretty_json(value: &Value) -> Result<Vec<u8>, AppError> {
+    serde_json::to_vec_pretty(value).map_err(|source| AppError::JsonSerialize { source })
+}
+
+#[cfg(unix)]
+pub(crate) fn build_handoff_command(
+    prepared: &PreparedOverlayLaunch,
+    native_args: &[OsString],
+) -> std::process::Command {
+    let name = app_name(&prepared.app);
+    let mut command = std::process::Command::new("/bin/sh");
+    command.arg("-c").arg(format!(
+        "overlay_dir=\"$1\"; app_bin=\"$2\"; shift 2; exit_status=0; cleanup() {{ rm -rf -- \"$overlay_dir\"; cleanup_status=$?; if [ \"$cleanup_status\" -ne 0 ]; then printf '%s\\n' \"cc-switch: failed to remove temporary {name} overlay: $overlay_dir\" >&2; if [ \"$exit_status\" -eq 0 ]; then exit_status=$cleanup_status; fi; fi; }}; on_signal() {{ exit_status=\"$1\"; trap - INT TERM HUP; cleanup; exit \"$exit_status\"; }}; trap 'on_signal 130' INT; trap 'on_signal 143' TERM; trap 'on_signal 129' HUP; \"$app_bin\" \"$@\"; exit_status=$?; cleanup; exit \"$exit_status\""
+    ));
+    command.arg(format!("cc-switch-{}-handoff", prepared.app.as_str()));
+    command.arg(&prepared.overlay_dir);
+    command.arg(&prepared.executable);
+    command.args(native_args);
+    command.envs(prepared.env.iter().map(|(key, value)| (key, value)));
+    command
+}
+
+#[cfg(unix)]
+pub(crate) fn exec_prepared_overlay(
+    prepared: &PreparedOverlayLaunch,
+    native_args: &[OsString],
+) -> Result<(), AppError> {
+    use std::os::unix::process::CommandExt;
+
+    let name = app_name(&prepared.app);
+    let exec_err = build_handoff_command(prepared, native_args).exec();
+    Err(AppError::localized(
+        "temp_launch.exec_failed",
+        format!("启动 {name} 失败: {exec_err}"),
+        format!("Failed to launch {name}: {exec_err}"),
+    ))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn exec_prepared_overlay(
+    prepared: &PreparedOverlayLaunch,
+    _native_args: &[OsString],
+) -> Result<(), AppError> {
+    ensure_temp_launch_supported(&prepared.app)
+}
+
+fn write_overlay_dir(overlay_dir: &Path, plan: &OverlayPlan) -> Result<Vec<PathBuf>, AppError> {
+    write_overlay_dir_with(overlay_dir, plan, finalize_overlay_dir)
+}
+
+fn write_overlay_dir_with<Finalize>(
+    overlay_dir: &Path,
+    plan: &OverlayPlan,
+    finalize: Finalize,
+) -> Result<Vec<PathBuf>, AppError>
+where
+    Finalize: FnOnce(&Path) -> Result<(), AppError>,
+{
+    let write_result = (|| {
+        fs::create_dir_all(overlay_dir).map_err(|err| AppError::io(overlay_dir, err))?;
+        finalize(overlay_dir)?;
+
+        let mut written = Vec::with_capacity(plan.files.len());
+        for (name, content) in &plan.files {
+            let path = overlay_dir.join(name);
+            write_secret_file(&path, content)?;
+            written.push(path);
+        }
+        for (name, target) in &plan.links {
+            link_into_overlay(target, &overlay_dir.join(name))?;
+        }
+        Ok(written)
+    })();
+
+    match write_result {
+        Ok(written) => Ok(written),
+        Err(err) => match cleanup_overlay_dir(overlay_dir) {
+            Ok(()) => Err(err),
+            Err(cleanup_err) => Err(AppError::localized(
+                "temp_launch.overlay_cleanup_failed",
+                format!("写入临时覆盖目录失败: {err}；同时清理失败: {cleanup_err}"),
+                format!(
+                    "Failed to write the temporary overlay directory: {err}; also failed to clean it up: {cleanup_err}"
+                ),
+            )),
+        },
+    }
+}
+
+#[cfg(unix)]
+fn link_into_overlay(target: &Path, link: &Path) -> Result<(), AppError> {
+    std::os::unix::fs::symlink(target, link).map_err(|err| AppError::io(link, err))
+}
+
+#[cfg(not(unix))]
+fn link_into_overlay(_target: &Path, _link: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+fn temp_overlay_dir_path(temp_dir: &Path, app: &AppType, provider_id: &str) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let dir_name = format!(
+        "cc-switch-{}-{}-{}-{timestamp}",
+        app.as_str(),
+        sanitize_filename_fragment(provider_id),
+        std::process::id()
+    );
+    temp_dir.join(dir_name)
+}
+
+#[cfg(unix)]
+fn finalize_overlay_dir(path: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o700))
+        .map_err(|err| AppError::io(path, err))
+}
+
+#[cfg(not(unix))]
+fn finalize_overlay_dir(_path: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+fn write_secret_file(path: &Path, content: &[u8]) -> Result<(), AppError> {
+    let mut file = create_secret_temp_file(path)?;
+    file.write_all(content)
+        .and_then(|()| file.flush())
+        .map_err(|err| AppError::io(path, err))
+}
+
+#[cfg(unix)]
+fn create_secret_temp_file(path: &Path) -> Result<File, AppError> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|err| AppError::io(path, err))
+}
+
+#[cfg(not(unix))]
+fn create_secret_temp_file(path: &Path) -> Result<File, AppError> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|err| AppError::io(path, err))
+}
+
+/// `remove_dir_all` never follows the Hermes symlinks, so the real home is
+/// left untouched.
+fn cleanup_overlay_dir(path: &Path) -> Result<(), AppError> {
+    match fs::remove_dir_all(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(AppError::io(path, err)),
+    }
+}
+
+fn sanitize_filename_fragment(value: &str) -> String {
+    let sanitized: String = value
+        .chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => ch,
+            _ => '-',
+        })
+        .collect();
+    if sanitized.is_empty() {
+        "provider".to_string()
+    } else {
+        sanitized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn gemini_provider() -> Provider {
+        Provider::with_id(
+            "relay".to_string(),
+            "Gemini Relay".to_string(),
+            json!({
+                "env": {
+                    "GEMINI_API_KEY": "sk-relay",
+                    "GOOGLE_GEMINI_BASE_URL": "https://relay.example"
+                },
+                "config": { "ui": { "theme": "Dracula" } }
+            }),
+            None,
+        )
+    }
+
+    fn env_value<'a>(plan: &'a OverlayPlan, key: &str) -> Option<&'a str> {
+        plan.env
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn gemini_overlay_exports_env_and_points_at_settings_overlay() {
+        let provider = gemini_provider();
+        let overlay_dir = PathBuf::from("/tmp/overlay");
+
+        let plan = plan_gemini(
+            &provider,
+            &provider.settings_config,
+            Some("gemini-2.5-pro"),
+            &overlay_dir,
+        )
+        .expect("plan gemini");
+
+        assert_eq!(env_value(&plan, "GEMINI_API_KEY"), Some("sk-relay"));
+        assert_eq!(env_value(&plan, "GEMINI_MODEL"), Some("gemini-2.5-pro"));
+        assert_eq!(
+            env_value(&plan, "GEMINI_CLI_SYSTEM_SETTINGS_PATH"),
+            Some("/tmp/overlay/settings.json")
+        );
+        let settings: Value = serde_json::from_slice(&plan.files[0].1).expect("settings json");
+        assert_eq!(settings["ui"]["theme"], "Dracula");
+        assert_eq!(
+            settings["security"]["auth"]["selectedType"],
+            "gemini-api-key"
+        );
+    }
+
+    #[test]
+    fn gemini_overlay_masks_managed_keys_the_provider_does_not_set() {
+        let provider = Provider::with_id(
+            "google".to_string(),
+            "Google".to_string(),
+            json!({ "env": {} }),
+            None,
+        );
+
+        let plan = plan_gemini(
+            &provider,
+            &provider.settings_config,
+            None,
+            Path::new("/tmp/o"),
+        )
+        .expect("plan gemini");
+
+        assert_eq!(env_value(&plan, "GOOGLE_GEMINI_BASE_URL"), Some(""));
+        assert_eq!(env_value(&plan, "GEMINI_API_KEY"), Some(""));
+        let settings: Value = serde_json::from_slice(&plan.files[0].1).expect("settings json");
+        assert_eq!(
+            settings["security"]["auth"]["selectedType"],
+            "oauth-personal"
+        );
+    }
+
+    #[test]
+    fn opencode_overlay_selects_provider_model() {
+        let settings = json!({
+            "npm": "@ai-sdk/openai-compatible",
+            "options": { "baseURL": "https://relay.example/v1", "apiKey": "sk" },
+            "models": { "qwen3-coder": { "name": "Qwen3 Coder" } }
+        });
+
+        let plan =
+            plan_opencode("relay", &settings, None, Path::new("/tmp/o")).expect("plan opencode");
+        let config: Value = serde_json::from_slice(&plan.files[0].1).expect("config json");
+        assert_eq!(config["model"], "relay/qwen3-coder");
+        assert_eq!(config["provider"]["relay"], settings);
+        assert_eq!(
+            env_value(&plan, "OPENCODE_CONFIG"),
+            Some("/tmp/o/opencode.json")
+        );
+
+        let plan = plan_opencode("relay", &settings, Some("glm-4.6"), Path::new("/tmp/o"))
+            .expect("plan opencode with model");
+        let config: Value = serde_json::from_slice(&plan.files[0].1).expect("config json");
+        assert_eq!(config["model"], "relay/glm-4.6");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hermes_overlay_links_real_home_and_leaves_it_untouched() {
+        let hermes_home = TempDir::new().expect("hermes home");
+        let original = "model:\n  default: old\n  provider: other\nagent:\n  max_turns: 50\n";
+        fs::write(hermes_home.path().join("config.yaml"), original).expect("seed config");
+        fs::write(hermes_home.path().join(".env"), "OPENROUTER_API_KEY=k\n").expect("seed env");
+        fs::create_dir(hermes_home.path().join("sessions")).expect("seed sessions");
+        let temp = TempDir::new().expect("temp");
+        let overlay_dir = temp.path().join("overlay");
+
+        let plan = plan_hermes(
+            "acme",
+            &json!({ "base_url": "https://acme", "models": [{ "id": "acme-large" }] }),
+            None,
+            &overlay_dir,
+            hermes_home.path(),
+        )
+        .expect("plan hermes");
+        write_overlay_dir(&overlay_dir, &plan).expect("write overlay");
+
+        let overlay: serde_yaml::Value = serde_yaml::from_str(
+            &fs::read_to_string(overlay_dir.join("config.yaml")).expect("read overlay"),
+        )
+        .expect("parse overlay");
+        assert_eq!(overlay["model"]["provider"].as_str(), Some("acme"));
+        assert_eq!(overlay["model"]["default"].as_str(), Some("acme-large"));
+        assert_eq!(
+            fs::read_to_string(overlay_dir.join(".env")).expect("linked env"),
+            "OPENROUTER_API_KEY=k\n"
+        );
+        assert!(overlay_dir.join("sessions").is_dir());
+        assert_eq!(
+            env_value(&plan, "HERMES_HOME"),
+            Some(overlay_dir.display().to_string().as_str())
+        );
+
+        cleanup_overlay_dir(&overlay_dir).expect("cleanup");
+        assert!(!overlay_dir.exists());
+        assert!(hermes_home.path().join("sessions").is_dir());
+        assert_eq!(
+            fs::read_to_string(hermes_home.path().join("config.yaml")).expect("read config"),
+            original
+        );
+    }
+
+    #[test]
+    fn preview_launch_does_not_write_overlay_dir() {
+        let temp = TempDir::new().expect("temp");
+        let provider = gemini_provider();
+
+        let prepared = preview_launch_with(
+            &AppType::Gemini,
+            &provider,
+            &provider.settings_config,
+            None,
+            temp.path(),
+            || Ok(PathBuf::from("/usr/bin/gemini")),
+        )
+        .expect("preview launch");
+
+        assert_eq!(
+            prepared.files,
+            vec![prepared.overlay_dir.join("settings.json")]
+        );
+        assert!(
+            fs::read_dir(temp.path())
+                .expect("read temp dir")
+                .next()
+                .is_none(),
+            "dry-run preview should leave no temp files"
+        );
+    }
+
+    #[test]
+    fn overlay_dir_is_removed_when_finalize_step_fails() {
+        let temp = TempDir::new().expect("temp");
+        let provider = gemini_provider();
+        let overlay_dir = temp.path().join("overlay");
+        let plan = plan_gemini(&provider, &provider.settings_config, None, &overlay_dir)
+            .expect("plan gemini");
+
+        let err = write_overlay_dir_with(&overlay_dir, &plan, |_| {
+            Err(AppError::Message("simulated finalize failure".to_string()))
+        })
+        .expect_err("finalize failure should bubble up");
+
+        assert!(err.to_string().contains("simulated finalize failure"));
+        assert!(!overlay_dir.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn handoff_exports_overlay_env_and_cleans_up() {
+        let temp = TempDir::new().expect("temp");
+        let overlay_dir = temp.path().join("overlay");
+        fs::create_dir(&overlay_dir).expect("create overlay");
+        let prepared = PreparedOverlayLaunch {
+            app: AppType::OpenCode,
+            executable: PathBuf::from("/bin/sh"),
+            overlay_dir: overlay_dir.clone(),
+            env: vec![("OPENCODE_CONFIG".to_string(), "/tmp/x.json".to_string())],
+            files: Vec::new(),
+        };
+
+        let output = build_handoff_command(
+            &prepared,
+            &[
+                OsString::from("-c"),
+                OsString::from("printf '%s' \"$OPENCODE_CONFIG\""),
+            ],
+        )
+        .output()
+        .expect("run handoff");
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "/tmp/x.json");
+        assert!(!overlay_dir.exists());
+    }
+}
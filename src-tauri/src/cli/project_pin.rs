@@ -0,0 +1,138 @@
+//! Directory-scoped provider pinning via `.cc-switch.toml`.
+//!
+//! A project can commit a `.cc-switch.toml` that names the provider (and
+//! optionally the model) each app should use inside that tree:
+//!
+//! ```toml
+//! [claude]
+//! provider = "acme-relay"
+//! model = "claude-sonnet-4-5"
+//!
+//! [codex]
+//! provider = "acme-openai"
+//! ```
+//!
+//! The file is found by walking up from the working directory; the nearest
+//! one wins. Pins never touch the global current provider: `cc-switch start`
+//! and the shell hook read them per invocation.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+pub(crate) const PIN_FILE_NAME: &str = ".cc-switch.toml";
+
+/// Pin for one app. `provider` is a selector (exact id first, then name).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub(crate) struct AppPin {
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// A parsed `.cc-switch.toml` and where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProjectPin {
+    pub path: PathBuf,
+    apps: BTreeMap<String, AppPin>,
+}
+
+impl ProjectPin {
+    pub(crate) fn parse(path: PathBuf, text: &str) -> Result<Self, AppError> {
+        let apps: BTreeMap<String, AppPin> = toml::from_str(text).map_err(|e| {
+            AppError::localized(
+                "cli.project_pin.parse_failed",
+                format!("解析 {} 失败: {e}", path.display()),
+                format!("Failed to parse {}: {e}", path.display()),
+            )
+        })?;
+        Ok(Self { path, apps })
+    }
+
+    /// The pin for `app`, when it names a provider.
+    pub(crate) fn for_app(&self, app: &AppType) -> Option<&AppPin> {
+        self.apps.get(app.as_str()).filter(|pin| {
+            pin.provider
+                .as_deref()
+                .is_some_and(|p| !p.trim().is_empty())
+        })
+    }
+
+    /// Apps with a provider pin, in `AppType::all()` order.
+    pub(crate) fn pinned_apps(&self) -> Vec<AppType> {
+        AppType::all()
+            .filter(|app| self.for_app(app).is_some())
+            .collect()
+    }
+}
+
+/// Find the nearest `.cc-switch.toml` at or above `start`.
+pub(crate) fn discover_from(start: &Path) -> Result<Option<ProjectPin>, AppError> {
+    for dir in start.ancestors() {
+        let candidate = dir.join(PIN_FILE_NAME);
+        if !candidate.is_file() {
+            continue;
+        }
+        let text = std::fs::read_to_string(&candidate).map_err(|e| AppError::io(&candidate, e))?;
+        return ProjectPin::parse(candidate, &text).map(Some);
+    }
+    Ok(None)
+}
+
+pub(crate) fn discover() -> Result<Option<ProjectPin>, AppError> {
+    let cwd = std::env::current_dir().map_err(|e| AppError::io(Path::new("."), e))?;
+    discover_from(&cwd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_pin_file_wins() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let nested = root.path().join("service").join("src");
+        std::fs::create_dir_all(&nested).expect("create dirs");
+        std::fs::write(
+            root.path().join(PIN_FILE_NAME),
+            "[claude]\nprovider = \"outer\"\n",
+        )
+        .expect("write outer");
+        std::fs::write(
+            root.path().join("service").join(PIN_FILE_NAME),
+            "[claude]\nprovider = \"inner\"\nmodel = \"claude-opus-4-1\"\n\n[codex]\nprovider = \"team\"\n",
+        )
+        .expect("write inner");
+
+        let pin = discover_from(&nested).expect("discover").expect("pin");
+        assert_eq!(pin.path, root.path().join("service").join(PIN_FILE_NAME));
+        let claude = pin.for_app(&AppType::Claude).expect("claude pin");
+        assert_eq!(claude.provider.as_deref(), Some("inner"));
+        assert_eq!(claude.model.as_deref(), Some("claude-opus-4-1"));
+        assert_eq!(pin.pinned_apps(), vec![AppType::Claude, AppType::Codex]);
+    }
+
+    #[test]
+    fn missing_or_blank_provider_is_not_a_pin() {
+        let pin = ProjectPin::parse(
+            PathBuf::from(PIN_FILE_NAME),
+            "[claude]\nmodel = \"only-model\"\n[gemini]\nprovider = \" \"\n[future-app]\nprovider = \"x\"\n",
+        )
+        .expect("parse");
+        assert!(pin.for_app(&AppType::Claude).is_none());
+        assert!(pin.for_app(&AppType::Gemini).is_none());
+        assert!(pin.pinned_apps().is_empty());
+    }
+
+    #[test]
+    fn invalid_toml_reports_the_file() {
+        let err = ProjectPin::parse(PathBuf::from("/repo/.cc-switch.toml"), "[claude")
+            .expect_err("invalid toml");
+        assert!(err.to_string().contains("/repo/.cc-switch.toml"));
+    }
+}
@@ -0,0 +1,336 @@
+//! Environment variables that point a CLI at a provider, and rendering them
+//! as shell statements.
+//!
+//! Only the variables each tool honours from its environment are produced:
+//! Claude and Gemini read their whole `env` block, Codex picks up the API key
+//! and base URL of the built-in OpenAI provider. Additive apps keep every
+//! provider in their own config, so they have nothing to export.
+//!
+//! While proxy takeover is on, [`route_through_proxy`] swaps the endpoint and
+//! credentials for the local proxy route, matching what takeover writes into
+//! the live config.
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::provider::Provider;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HookShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Output formats of `cc-switch env export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Bash,
+    Zsh,
+    Fish,
+    Json,
+    Dotenv,
+}
+
+const CLAUDE_TOKEN_KEYS: [&str; 4] = [
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_API_KEY",
+    "OPENROUTER_API_KEY",
+    "OPENAI_API_KEY",
+];
+
+/// Variables selecting `provider` for `app`, with an optional model override.
+pub(crate) fn provider_env_vars(
+    app: &AppType,
+    provider: &Provider,
+    model: Option<&str>,
+) -> Vec<(String, String)> {
+    let settings = &provider.settings_config;
+    let mut vars = Vec::new();
+    match app {
+        AppType::Claude | AppType::Gemini => {
+            if let Some(env) = settings.get("env").and_then(Value::as_object) {
+                for (key, value) in env.iter().filter(|(key, _)| is_valid_env_name(key)) {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        Value::Number(value) => value.to_string(),
+                        Value::Bool(value) => value.to_string(),
+                        _ => continue,
+                    };
+                    vars.push((key.clone(), value));
+                }
+            }
+            if let Some(model) = model.filter(|model| !model.trim().is_empty()) {
+                let key = if matches!(app, AppType::Claude) {
+                    "ANTHROPIC_MODEL"
+                } else {
+                    "GEMINI_MODEL"
+                };
+                vars.retain(|(existing, _)| existing != key);
+                vars.push((key.to_string(), model.trim().to_string()));
+            }
+        }
+        AppType::Codex => {
+            let config = settings.get("config").and_then(Value::as_str);
+            if let Some(key) =
+                crate::codex_config::extract_codex_api_key(settings.get("auth"), config)
+            {
+                vars.push(("OPENAI_API_KEY".to_string(), key));
+            }
+            if let Some(base_url) = config.and_then(crate::codex_config::extract_codex_base_url) {
+                vars.push(("OPENAI_BASE_URL".to_string(), base_url));
+            }
+        }
+        AppType::OpenCode | AppType::Hermes | AppType::OpenClaw => {}
+    }
+    vars
+}
+
+/// Points `vars` at the takeover route: the base URL becomes the proxy and
+/// every credential becomes the placeholder the proxy swaps for the real key.
+pub(crate) fn route_through_proxy(
+    app: &AppType,
+    vars: &mut Vec<(String, String)>,
+    proxy_origin: &str,
+    proxy_codex_base_url: &str,
+) {
+    let placeholder = crate::services::proxy::PROXY_TOKEN_PLACEHOLDER;
+    let (base_key, base_url, token_keys, default_token_key): (_, _, &[&str], _) = match app {
+        AppType::Claude => (
+            "ANTHROPIC_BASE_URL",
+            proxy_origin,
+            &CLAUDE_TOKEN_KEYS,
+            "ANTHROPIC_AUTH_TOKEN",
+        ),
+        AppType::Codex => (
+            "OPENAI_BASE_URL",
+            proxy_codex_base_url,
+            &["OPENAI_API_KEY"],
+            "OPENAI_API_KEY",
+        ),
+        AppType::Gemini => (
+            "GOOGLE_GEMINI_BASE_URL",
+            proxy_origin,
+            &["GEMINI_API_KEY"],
+            "GEMINI_API_KEY",
+        ),
+        AppType::OpenCode | AppType::Hermes | AppType::OpenClaw => return,
+    };
+
+    let mut replaced_any = false;
+    for (key, value) in vars.iter_mut() {
+        if token_keys.contains(&key.as_str()) {
+            *value = placeholder.to_string();
+            replaced_any = true;
+        }
+    }
+    if !replaced_any {
+        vars.push((default_token_key.to_string(), placeholder.to_string()));
+    }
+    vars.retain(|(key, _)| key != base_key);
+    vars.push((base_key.to_string(), base_url.to_string()));
+}
+
+/// Renders `vars` in `format`; shell formats can be `eval`ed or sourced.
+pub(crate) fn render_vars(format: ExportFormat, vars: &[(String, String)]) -> String {
+    let shell = match format {
+        ExportFormat::Bash => HookShell::Bash,
+        ExportFormat::Zsh => HookShell::Zsh,
+        ExportFormat::Fish => HookShell::Fish,
+        ExportFormat::Json => {
+            let object: serde_json::Map<String, Value> = vars
+                .iter()
+                .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+                .collect();
+            return format!(
+                "{}\n",
+                serde_json::to_string_pretty(&Value::Object(object)).unwrap_or_default()
+            );
+        }
+        ExportFormat::Dotenv => {
+            return vars
+                .iter()
+                .map(|(name, value)| format!("{name}={}\n", dotenv_quote(value)))
+                .collect();
+        }
+    };
+    vars.iter()
+        .map(|(name, value)| render_export(shell, name, value) + "\n")
+        .collect()
+}
+
+/// Names are interpolated into `eval`ed shell code, so only plain
+/// identifiers are ever rendered.
+pub(crate) fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+pub(crate) fn render_export(shell: HookShell, name: &str, value: &str) -> String {
+    match shell {
+        HookShell::Bash | HookShell::Zsh => format!("export {name}={};", posix_quote(value)),
+        HookShell::Fish => format!("set -gx {name} {};", fish_quote(value)),
+    }
+}
+
+pub(crate) fn render_unset(shell: HookShell, name: &str) -> String {
+    match shell {
+        HookShell::Bash | HookShell::Zsh => format!("unset {name};"),
+        HookShell::Fish => format!("set -e {name};"),
+    }
+}
+
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn dotenv_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn claude_exports_env_block_and_model_override() {
+        let provider = Provider::with_id(
+            "acme".to_string(),
+            "Acme".to_string(),
+            json!({ "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example",
+                "ANTHROPIC_AUTH_TOKEN": "sk-acme",
+                "ANTHROPIC_MODEL": "claude-old",
+                "API_TIMEOUT_MS": 600000
+            }}),
+            None,
+        );
+
+        let vars = provider_env_vars(&AppType::Claude, &provider, Some("claude-sonnet-4-5"));
+        assert!(vars.contains(&(
+            "ANTHROPIC_BASE_URL".to_string(),
+            "https://relay.example".to_string()
+        )));
+        assert!(vars.contains(&("API_TIMEOUT_MS".to_string(), "600000".to_string())));
+        assert_eq!(
+            vars.iter()
+                .filter(|(key, _)| key == "ANTHROPIC_MODEL")
+                .map(|(_, value)| value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["claude-sonnet-4-5"]
+        );
+    }
+
+    #[test]
+    fn shell_statements_quote_values() {
+        assert_eq!(
+            render_export(HookShell::Bash, "KEY", "it's"),
+            "export KEY='it'\\''s';"
+        );
+        assert_eq!(
+            render_export(HookShell::Fish, "KEY", "a\\b'c"),
+            "set -gx KEY 'a\\\\b\\'c';"
+        );
+        assert_eq!(render_unset(HookShell::Fish, "KEY"), "set -e KEY;");
+    }
+
+    #[test]
+    fn env_keys_that_are_not_identifiers_are_dropped() {
+        let provider = Provider::with_id(
+            "odd".to_string(),
+            "Odd".to_string(),
+            json!({ "env": { "GEMINI_API_KEY": "k", "X; rm -rf ~": "v", "1BAD": "v" } }),
+            None,
+        );
+
+        assert_eq!(
+            provider_env_vars(&AppType::Gemini, &provider, None),
+            vec![("GEMINI_API_KEY".to_string(), "k".to_string())]
+        );
+    }
+
+    #[test]
+    fn proxy_route_replaces_endpoint_and_credentials() {
+        let mut vars = vec![
+            (
+                "ANTHROPIC_BASE_URL".to_string(),
+                "https://relay.example".to_string(),
+            ),
+            ("ANTHROPIC_API_KEY".to_string(), "sk-acme".to_string()),
+            (
+                "ANTHROPIC_MODEL".to_string(),
+                "claude-sonnet-4-5".to_string(),
+            ),
+        ];
+        route_through_proxy(
+            &AppType::Claude,
+            &mut vars,
+            "http://127.0.0.1:15721",
+            "http://127.0.0.1:15721/v1",
+        );
+        assert_eq!(
+            vars,
+            vec![
+                ("ANTHROPIC_API_KEY".to_string(), "PROXY_MANAGED".to_string()),
+                (
+                    "ANTHROPIC_MODEL".to_string(),
+                    "claude-sonnet-4-5".to_string()
+                ),
+                (
+                    "ANTHROPIC_BASE_URL".to_string(),
+                    "http://127.0.0.1:15721".to_string()
+                ),
+            ]
+        );
+
+        let mut vars = Vec::new();
+        route_through_proxy(
+            &AppType::Codex,
+            &mut vars,
+            "http://127.0.0.1:15721",
+            "http://127.0.0.1:15721/v1",
+        );
+        assert_eq!(
+            vars,
+            vec![
+                ("OPENAI_API_KEY".to_string(), "PROXY_MANAGED".to_string()),
+                (
+                    "OPENAI_BASE_URL".to_string(),
+                    "http://127.0.0.1:15721/v1".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_formats_render_every_variable() {
+        let vars = vec![
+            ("KEY".to_string(), "a \"b\"".to_string()),
+            ("URL".to_string(), "https://x".to_string()),
+        ];
+        assert_eq!(
+            render_vars(ExportFormat::Dotenv, &vars),
+            "KEY=\"a \\\"b\\\"\"\nURL=\"https://x\"\n"
+        );
+        assert_eq!(
+            render_vars(ExportFormat::Bash, &vars),
+            "export KEY='a \"b\"';\nexport URL='https://x';\n"
+        );
+        let json: Value = serde_json::from_str(&render_vars(ExportFormat::Json, &vars)).unwrap();
+        assert_eq!(json["KEY"], "a \"b\"");
+        assert_eq!(json["URL"], "https://x");
+    }
+}
@@ -24,6 +24,7 @@ mod content_config;
 mod content_entities;
 mod content_pricing;
 mod content_skills;
+mod content_universal;
 mod content_usage;
 mod editor_handlers;
 mod editor_state;
@@ -55,7 +56,7 @@ pub use types::{
     ConfirmOverlay, FilterScope, FilterState, Focus, LoadingKind, ManagedAuthLoginState, Overlay,
     PricingState, SessionProjectPickerState, SessionsPane, SessionsState, SkillsDiscoverSource,
     TextInputState, TextSubmit, TextViewAction, TextViewState, Toast, ToastAction,
-    ToastActionScope, ToastKind, UsageMetric, UsagePane, UsageState,
+    ToastActionScope, ToastKind, UniversalProvidersState, UsageMetric, UsagePane, UsageState,
 };
 #[cfg(test)]
 pub(crate) use types::{McpKeyValueEditorField, McpKeyValueEntryEditorState};
@@ -100,7 +101,10 @@ const PROVIDER_NOTES_MAX_CHARS: usize = 120;
 
 #[cfg(unix)]
 pub(crate) fn supports_temporary_provider_launch(app_type: &AppType) -> bool {
-    matches!(app_type, AppType::Claude | AppType::Codex)
+    matches!(
+        app_type,
+        AppType::Claude | AppType::Codex | AppType::Gemini | AppType::OpenCode | AppType::Hermes
+    )
 }
 
 #[cfg(not(unix))]
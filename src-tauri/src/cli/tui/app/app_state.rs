@@ -93,6 +93,7 @@ pub enum Action {
     ProviderSwitch {
         id: String,
     },
+    ProviderSwitchHistory,
     ProviderRemoveFromConfig {
         id: String,
     },
@@ -101,6 +102,10 @@ pub enum Action {
         model_id: String,
     },
     ProviderImportLiveConfig,
+    ProviderOpenForeignImport,
+    ProviderImportForeign {
+        candidates: Vec<crate::services::ForeignCandidate>,
+    },
     ProviderDelete {
         id: String,
     },
@@ -145,6 +150,12 @@ pub enum Action {
     PricingDelete {
         model_id: String,
     },
+    UniversalProviderApply {
+        id: String,
+    },
+    UniversalProviderDelete {
+        id: String,
+    },
 
     ManagedAuthRefresh {
         auth_provider: String,
@@ -738,6 +749,7 @@ pub struct App {
 
     pub usage: UsageState,
     pub pricing: PricingState,
+    pub universal: UniversalProvidersState,
     pub sessions: SessionsState,
     pub provider_idx: usize,
     pub mcp_idx: usize,
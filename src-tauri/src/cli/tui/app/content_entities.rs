@@ -341,6 +341,9 @@ impl App {
                 }
                 Action::ProviderQuotaRefresh { id: row.id.clone() }
             }
+            Intent::Universal => self.push_route_and_switch(Route::UniversalProviders),
+            Intent::SwitchHistory => Action::ProviderSwitchHistory,
+            Intent::ImportForeign => Action::ProviderOpenForeignImport,
         }
     }
 
@@ -1193,6 +1196,25 @@ mod tests {
         ));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn overlay_apps_o_key_requests_temporary_launch() {
+        for app_type in [AppType::Gemini, AppType::OpenCode, AppType::Hermes] {
+            let mut app = App::new(Some(app_type));
+            app.route = Route::Providers;
+            app.focus = Focus::Content;
+
+            let mut data = UiData::default();
+            data.providers.rows.push(provider_row("p1"));
+
+            let action = app.on_key(key(KeyCode::Char('o')), &data);
+            assert!(matches!(
+                action,
+                Action::ProviderLaunchTemporary { id } if id == "p1"
+            ));
+        }
+    }
+
     #[test]
     fn codex_provider_o_key_requests_temporary_launch() {
         let mut app = App::new(Some(AppType::Codex));
@@ -1238,8 +1260,8 @@ mod tests {
     }
 
     #[test]
-    fn non_claude_provider_o_key_is_noop() {
-        let mut app = App::new(Some(AppType::Gemini));
+    fn openclaw_provider_o_key_is_noop() {
+        let mut app = App::new(Some(AppType::OpenClaw));
         app.route = Route::Providers;
         app.focus = Focus::Content;
 
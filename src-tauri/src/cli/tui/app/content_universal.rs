@@ -0,0 +1,121 @@
+use super::*;
+
+impl App {
+    pub(crate) fn on_universal_providers_key(&mut self, key: KeyEvent, data: &UiData) -> Action {
+        let len = visible_universal_rows(&self.filter, data).len();
+        match key.code {
+            KeyCode::Up => {
+                self.universal.selected_idx = self.universal.selected_idx.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Down => {
+                if len > 0 {
+                    self.universal.selected_idx = (self.universal.selected_idx + 1).min(len - 1);
+                }
+                Action::None
+            }
+            KeyCode::Char('a') => {
+                self.open_universal_add_editor();
+                Action::None
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                self.open_universal_edit_editor(data);
+                Action::None
+            }
+            KeyCode::Char(' ') | KeyCode::Char('s') => {
+                let Some(row) = self.selected_universal_row(data) else {
+                    return Action::None;
+                };
+                if row.provider.apps.enabled_apps().is_empty() {
+                    self.push_toast(
+                        crate::t!(
+                            "This universal provider has no enabled apps.",
+                            "该统一供应商未启用任何应用。"
+                        ),
+                        ToastKind::Info,
+                    );
+                    return Action::None;
+                }
+                Action::UniversalProviderApply {
+                    id: row.provider.id.clone(),
+                }
+            }
+            KeyCode::Char('d') => {
+                self.open_universal_delete_confirm(data);
+                Action::None
+            }
+            KeyCode::Char('r') => Action::ReloadData,
+            _ => Action::None,
+        }
+    }
+
+    fn selected_universal_row<'a>(
+        &self,
+        data: &'a UiData,
+    ) -> Option<&'a data::UniversalProviderRow> {
+        visible_universal_rows(&self.filter, data)
+            .get(self.universal.selected_idx)
+            .copied()
+    }
+
+    fn open_universal_add_editor(&mut self) {
+        let template = crate::provider::UniversalProvider::new(
+            String::new(),
+            String::new(),
+            "https://".to_string(),
+            String::new(),
+        );
+        let initial = serde_json::to_string_pretty(&template).unwrap_or_else(|_| "{}".to_string());
+        self.open_editor(
+            crate::t!("Add Universal Provider", "新增统一供应商"),
+            EditorKind::Json,
+            initial,
+            EditorSubmit::UniversalProviderAdd,
+        );
+    }
+
+    fn open_universal_edit_editor(&mut self, data: &UiData) {
+        let Some(row) = self.selected_universal_row(data) else {
+            return;
+        };
+        let initial =
+            serde_json::to_string_pretty(&row.provider).unwrap_or_else(|_| "{}".to_string());
+        self.open_editor(
+            universal_edit_title(&row.provider.id),
+            EditorKind::Json,
+            initial,
+            EditorSubmit::UniversalProviderEdit {
+                id: row.provider.id.clone(),
+            },
+        );
+    }
+
+    fn open_universal_delete_confirm(&mut self, data: &UiData) {
+        let Some(row) = self.selected_universal_row(data) else {
+            return;
+        };
+        self.overlay = Overlay::Confirm(ConfirmOverlay {
+            title: crate::t!("Delete Universal Provider", "删除统一供应商").to_string(),
+            message: universal_delete_message(&row.provider.id),
+            action: ConfirmAction::UniversalProviderDelete {
+                id: row.provider.id.clone(),
+            },
+        });
+    }
+}
+
+fn universal_edit_title(id: &str) -> String {
+    if crate::cli::i18n::is_chinese() {
+        format!("编辑统一供应商: {id}")
+    } else {
+        format!("Edit Universal Provider: {id}")
+    }
+}
+
+fn universal_delete_message(id: &str) -> String {
+    if crate::cli::i18n::is_chinese() {
+        format!("确定删除统一供应商 '{id}'？各应用中由它生成的供应商也会一并删除。")
+    } else {
+        format!("Delete universal provider '{id}'? The providers it generated in each app are removed too.")
+    }
+}
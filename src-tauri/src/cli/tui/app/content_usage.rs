@@ -24,7 +24,9 @@ impl UsagePane {
     pub(crate) fn next(self) -> Self {
         match self {
             Self::Models => Self::Providers,
-            Self::Providers => Self::Recent,
+            Self::Providers => Self::Projects,
+            Self::Projects => Self::Balances,
+            Self::Balances => Self::Recent,
             Self::Recent => Self::Models,
         }
     }
@@ -33,7 +35,9 @@ impl UsagePane {
         match self {
             Self::Models => Self::Recent,
             Self::Providers => Self::Models,
-            Self::Recent => Self::Providers,
+            Self::Projects => Self::Providers,
+            Self::Balances => Self::Projects,
+            Self::Recent => Self::Balances,
         }
     }
 }
@@ -258,7 +262,10 @@ impl App {
                     .saturating_mul(crate::cli::tui::data::USAGE_LOG_PAGE_SIZE);
                 self.usage.log_pager.gate.select(page_start);
             }
-            UsagePane::Models | UsagePane::Providers => {
+            UsagePane::Models
+            | UsagePane::Providers
+            | UsagePane::Projects
+            | UsagePane::Balances => {
                 self.usage.selected_idx = 0;
             }
         }
@@ -271,7 +278,10 @@ impl App {
                 let len = data.usage.recent_logs_for(self.usage.range).len();
                 self.usage.logs_idx = move_index(self.usage.logs_idx, len, delta);
             }
-            UsagePane::Models | UsagePane::Providers => {
+            UsagePane::Models
+            | UsagePane::Providers
+            | UsagePane::Projects
+            | UsagePane::Balances => {
                 let len = usage_active_pane_len(&self.usage.pane, self.usage.range, data);
                 self.usage.selected_idx = move_index(self.usage.selected_idx, len, delta);
             }
@@ -411,6 +421,8 @@ pub(crate) fn usage_active_pane_len(
     match pane {
         UsagePane::Providers => data.usage.top_providers_for(range).len(),
         UsagePane::Models => data.usage.top_models_for(range).len(),
+        UsagePane::Projects => data.usage.top_projects_for(range).len(),
+        UsagePane::Balances => data.usage.balances.len(),
         UsagePane::Recent => data.usage.recent_logs_for(range).len(),
     }
 }
@@ -38,6 +38,10 @@ pub enum EditorSubmit {
     PricingEdit {
         model_id: String,
     },
+    UniversalProviderAdd,
+    UniversalProviderEdit {
+        id: String,
+    },
     McpAdd,
     McpEdit {
         id: String,
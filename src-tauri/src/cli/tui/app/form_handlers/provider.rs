@@ -8,6 +8,27 @@ enum ProviderValidationTarget {
     UsageScript,
 }
 
+/// Warnings and errors from `provider lint` for the provider being saved.
+fn provider_lint_message(
+    app_type: &crate::app_config::AppType,
+    provider_json: &serde_json::Value,
+) -> Option<String> {
+    let provider: crate::provider::Provider = serde_json::from_value(provider_json.clone()).ok()?;
+    let lines = ProviderService::lint_provider(app_type, &provider)
+        .into_iter()
+        .filter(|finding| finding.severity >= crate::services::LintSeverity::Warning)
+        .map(|finding| format!("• {}", finding.message))
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}\n\n{}",
+        lines.join("\n"),
+        texts::tui_provider_lint_save_anyway_prompt()
+    ))
+}
+
 impl App {
     pub(super) fn handle_provider_template_key(
         &mut self,
@@ -87,6 +108,15 @@ impl App {
     }
 
     pub(super) fn build_provider_form_save_action(&mut self, data: &UiData) -> Action {
+        self.build_provider_form_save(data, true)
+    }
+
+    /// Save after the user has seen the lint findings.
+    pub(crate) fn build_provider_form_save_action_unchecked(&mut self, data: &UiData) -> Action {
+        self.build_provider_form_save(data, false)
+    }
+
+    fn build_provider_form_save(&mut self, data: &UiData, lint: bool) -> Action {
         let validation = {
             let Some(FormState::ProviderAdd(provider)) = self.form.as_mut() else {
                 return Action::None;
@@ -219,6 +249,16 @@ impl App {
         };
 
         let provider_json = provider.to_provider_json_value();
+        if lint {
+            if let Some(message) = provider_lint_message(&provider.app_type, &provider_json) {
+                self.overlay = Overlay::Confirm(ConfirmOverlay {
+                    title: texts::tui_provider_lint_title().to_string(),
+                    message,
+                    action: ConfirmAction::ProviderLintSaveAnyway,
+                });
+                return Action::None;
+            }
+        }
         let content =
             serde_json::to_string_pretty(&provider_json).unwrap_or_else(|_| "{}".to_string());
 
@@ -686,6 +686,7 @@ pub(crate) fn route_has_content_list(route: &Route) -> bool {
     matches!(
         route,
         Route::Providers
+            | Route::UniversalProviders
             | Route::Usage
             | Route::UsageLogs
             | Route::UsageLogDetail { .. }
@@ -1574,6 +1575,25 @@ pub(crate) fn visible_pricing_rows<'a>(
         .collect()
 }
 
+pub(crate) fn visible_universal_rows<'a>(
+    filter: &FilterState,
+    data: &'a UiData,
+) -> Vec<&'a super::data::UniversalProviderRow> {
+    let query = filter.query_lower();
+    data.universal
+        .rows
+        .iter()
+        .filter(|row| match &query {
+            None => true,
+            Some(q) => {
+                filter_text_matches(&row.provider.id, q)
+                    || filter_text_matches(&row.provider.name, q)
+                    || filter_text_matches(&row.provider.base_url, q)
+            }
+        })
+        .collect()
+}
+
 pub(crate) fn visible_prompts<'a>(
     filter: &FilterState,
     data: &'a UiData,
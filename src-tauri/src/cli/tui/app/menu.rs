@@ -96,6 +96,7 @@ impl App {
             local_env_generation: 0,
             usage: UsageState::default(),
             pricing: PricingState::default(),
+            universal: UniversalProvidersState::default(),
             sessions: SessionsState::default(),
             provider_idx: 0,
             mcp_idx: 0,
@@ -179,7 +180,7 @@ impl App {
     pub(crate) fn nav_item_for_route(app_type: &AppType, route: &Route) -> NavItem {
         match route {
             Route::Main => NavItem::Main,
-            Route::Providers => NavItem::Providers,
+            Route::Providers | Route::UniversalProviders => NavItem::Providers,
             Route::Usage | Route::UsageLogs | Route::UsageLogDetail { .. } | Route::Pricing => {
                 NavItem::Usage
             }
@@ -1101,6 +1102,7 @@ impl App {
     pub(crate) fn on_content_key(&mut self, key: KeyEvent, data: &UiData) -> Action {
         match self.route.clone() {
             Route::Providers => self.on_providers_key(key, data),
+            Route::UniversalProviders => self.on_universal_providers_key(key, data),
             Route::Usage => self.on_usage_key(key, data),
             Route::UsageLogs => self.on_usage_logs_key(key, data),
             Route::UsageLogDetail { rowid } => self.on_usage_log_detail_key(key, rowid),
@@ -1266,6 +1268,13 @@ impl App {
             self.pricing.selected_idx = self.pricing.selected_idx.min(pricing_len - 1);
         }
 
+        let universal_len = visible_universal_rows(&self.filter, data).len();
+        if universal_len == 0 {
+            self.universal.selected_idx = 0;
+        } else {
+            self.universal.selected_idx = self.universal.selected_idx.min(universal_len - 1);
+        }
+
         let skills_len = visible_skills_installed(&self.filter, data).len();
         if skills_len == 0 {
             self.skills_idx = 0;
@@ -100,6 +100,9 @@ impl App {
                     ConfirmAction::PricingDelete { model_id } => Action::PricingDelete {
                         model_id: model_id.clone(),
                     },
+                    ConfirmAction::UniversalProviderDelete { id } => {
+                        Action::UniversalProviderDelete { id: id.clone() }
+                    }
                     ConfirmAction::SessionDelete {
                         key,
                         provider_id,
@@ -169,7 +172,14 @@ impl App {
                             filename: filename.clone(),
                         }
                     }
-                    ConfirmAction::FormSaveBeforeClose => self.handle_form_save_shortcut(data),
+                    ConfirmAction::FormSaveBeforeClose => {
+                        // Saving may open the lint confirm, so close this one first.
+                        self.close_overlay();
+                        return Some(self.handle_form_save_shortcut(data));
+                    }
+                    ConfirmAction::ProviderLintSaveAnyway => {
+                        self.build_provider_form_save_action_unchecked(data)
+                    }
                     ConfirmAction::EditorDiscard => Action::EditorDiscard,
                     ConfirmAction::EditorSaveBeforeClose => {
                         if let Some(editor) = self.editor.as_ref() {
@@ -142,6 +142,9 @@ impl App {
         if let Some(action) = self.handle_skills_import_picker_key(key) {
             return Some(action);
         }
+        if let Some(action) = self.handle_provider_import_picker_key(key) {
+            return Some(action);
+        }
         if let Some(action) = self.handle_failover_queue_manager_key(key, data) {
             return Some(action);
         }
@@ -1589,6 +1592,57 @@ impl App {
         })
     }
 
+    fn handle_provider_import_picker_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let Overlay::ProviderImportPicker {
+            candidates,
+            selected_idx,
+            selected,
+        } = &mut self.overlay
+        else {
+            return None;
+        };
+
+        Some(match key.code {
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                Action::None
+            }
+            KeyCode::Up => {
+                *selected_idx = selected_idx.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Down => {
+                if !candidates.is_empty() {
+                    *selected_idx = (*selected_idx + 1).min(candidates.len() - 1);
+                }
+                Action::None
+            }
+            KeyCode::Char(' ') => {
+                if *selected_idx < candidates.len() && !selected.remove(selected_idx) {
+                    selected.insert(*selected_idx);
+                }
+                Action::None
+            }
+            KeyCode::Char('r') => Action::ProviderOpenForeignImport,
+            KeyCode::Char('i') | KeyCode::Enter => {
+                if selected.is_empty() {
+                    self.push_toast(texts::tui_toast_no_foreign_selected(), ToastKind::Info);
+                    return Some(Action::None);
+                }
+
+                let candidates = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| selected.contains(index))
+                    .map(|(_, candidate)| candidate.clone())
+                    .collect();
+                self.overlay = Overlay::None;
+                Action::ProviderImportForeign { candidates }
+            }
+            _ => Action::None,
+        })
+    }
+
     fn handle_failover_queue_manager_key(
         &mut self,
         key: KeyEvent,
@@ -36,6 +36,9 @@ impl App {
         if let Some(action) = self.handle_backup_picker_key(key, data) {
             return Some(action);
         }
+        if let Some(action) = self.handle_switch_history_key(key, data) {
+            return Some(action);
+        }
         if let Some(action) = self.handle_text_view_overlay_key(key, data) {
             return Some(action);
         }
@@ -121,6 +124,54 @@ impl App {
         })
     }
 
+    fn handle_switch_history_key(&mut self, key: KeyEvent, data: &UiData) -> Option<Action> {
+        let Overlay::SwitchHistoryPicker { selected, entries } = &mut self.overlay else {
+            return None;
+        };
+
+        Some(match key.code {
+            KeyCode::Esc => {
+                self.overlay = Overlay::None;
+                Action::None
+            }
+            KeyCode::Up => {
+                *selected = selected.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Down => {
+                if !entries.is_empty() {
+                    *selected = (*selected + 1).min(entries.len() - 1);
+                }
+                Action::None
+            }
+            KeyCode::Enter => {
+                let Some(entry) = entries.get(*selected) else {
+                    return Some(Action::None);
+                };
+                // Restore the provider that was active before this switch.
+                let Some(id) = entry.from_id.clone() else {
+                    self.push_toast(
+                        texts::tui_toast_switch_history_no_previous(),
+                        ToastKind::Info,
+                    );
+                    return Some(Action::None);
+                };
+                self.overlay = Overlay::None;
+                if data
+                    .providers
+                    .rows
+                    .iter()
+                    .any(|row| row.id == id && row.is_current)
+                {
+                    self.push_toast(texts::tui_toast_provider_already_in_use(), ToastKind::Info);
+                    return Some(Action::None);
+                }
+                Action::ProviderSwitch { id }
+            }
+            _ => Action::None,
+        })
+    }
+
     fn handle_text_view_overlay_key(&mut self, key: KeyEvent, data: &UiData) -> Option<Action> {
         if !matches!(self.overlay, Overlay::TextView(_)) {
             return None;
@@ -12889,6 +12889,149 @@ mod tests {
         assert!(matches!(app.overlay, Overlay::None));
     }
 
+    #[test]
+    fn provider_form_save_with_lint_warnings_asks_before_submitting() {
+        let mut app = App::new(Some(AppType::Claude));
+        app.route = Route::Providers;
+        app.focus = Focus::Content;
+
+        let mut data = UiData::default();
+        data.providers.rows.push(super::super::data::ProviderRow {
+            id: "p1".to_string(),
+            provider: crate::provider::Provider::with_id(
+                "p1".to_string(),
+                "Provider One".to_string(),
+                json!({"env":{"ANTHROPIC_BASE_URL":"https://example.com/v1"}}),
+                None,
+            ),
+            api_url: Some("https://example.com/v1".to_string()),
+            is_current: false,
+            is_in_config: true,
+            is_saved: true,
+            is_default_model: false,
+            primary_model_id: None,
+            default_model_id: None,
+        });
+
+        app.on_key(key(KeyCode::Char('e')), &data);
+        if let Some(super::super::form::FormState::ProviderAdd(form)) = app.form.as_mut() {
+            form.name.set("Provider Renamed");
+        } else {
+            panic!("expected ProviderAdd form in edit mode");
+        }
+
+        app.on_key(key(KeyCode::Esc), &data);
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(action, Action::None));
+        assert!(matches!(
+            &app.overlay,
+            Overlay::Confirm(ConfirmOverlay {
+                message,
+                action: ConfirmAction::ProviderLintSaveAnyway,
+                ..
+            }) if message.contains("/v1/v1/messages")
+        ));
+
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(
+            action,
+            Action::EditorSubmit {
+                submit: EditorSubmit::ProviderEdit { id },
+                ..
+            } if id == "p1"
+        ));
+        assert!(matches!(app.overlay, Overlay::None));
+    }
+
+    #[test]
+    fn switch_history_enter_switches_back_to_previous_provider() {
+        use crate::local_store::switch_history::ProviderSwitchRecord;
+
+        let mut app = App::new(Some(AppType::Claude));
+        app.route = Route::Providers;
+        app.focus = Focus::Content;
+        let data = UiData::default();
+
+        let action = app.on_key(key(KeyCode::Char('b')), &data);
+        assert!(matches!(action, Action::ProviderSwitchHistory));
+
+        let record = |from: Option<&str>, to: &str| ProviderSwitchRecord {
+            app_type: "claude".to_string(),
+            from_id: from.map(str::to_string),
+            to_id: to.to_string(),
+            source: "failover".to_string(),
+            switched_at: 1,
+        };
+        app.overlay = Overlay::SwitchHistoryPicker {
+            selected: 0,
+            entries: vec![record(Some("a"), "b"), record(None, "a")],
+        };
+
+        app.on_key(key(KeyCode::Down), &data);
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(action, Action::None));
+        assert!(matches!(app.overlay, Overlay::SwitchHistoryPicker { .. }));
+
+        app.on_key(key(KeyCode::Up), &data);
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(action, Action::ProviderSwitch { id } if id == "a"));
+        assert!(matches!(app.overlay, Overlay::None));
+    }
+
+    #[test]
+    fn provider_import_picker_imports_selected_candidates() {
+        use crate::services::{ForeignCandidate, ForeignSource};
+
+        let mut app = App::new(Some(AppType::Claude));
+        app.route = Route::Providers;
+        app.focus = Focus::Content;
+        let data = UiData::default();
+
+        let action = app.on_key(key(KeyCode::Char('I')), &data);
+        assert!(matches!(action, Action::ProviderOpenForeignImport));
+
+        let candidate = |name: &str, duplicate_of: Option<&str>| ForeignCandidate {
+            source: ForeignSource::Ccr,
+            origin: "config.json".to_string(),
+            app: AppType::Claude,
+            base_url: format!("https://{name}.example.com"),
+            api_format: "openai_chat".to_string(),
+            model: None,
+            has_key: true,
+            notes: Vec::new(),
+            duplicate_of: duplicate_of.map(str::to_string),
+            provider: crate::provider::Provider::with_id(
+                String::new(),
+                name.to_string(),
+                serde_json::json!({}),
+                None,
+            ),
+        };
+        app.overlay = Overlay::ProviderImportPicker {
+            candidates: vec![
+                candidate("relay", None),
+                candidate("backup", Some("backup")),
+            ],
+            selected_idx: 0,
+            selected: std::collections::HashSet::from([0]),
+        };
+
+        app.on_key(key(KeyCode::Char(' ')), &data);
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(action, Action::None));
+        assert!(matches!(app.overlay, Overlay::ProviderImportPicker { .. }));
+
+        app.on_key(key(KeyCode::Down), &data);
+        app.on_key(key(KeyCode::Char(' ')), &data);
+        let action = app.on_key(key(KeyCode::Enter), &data);
+        assert!(matches!(
+            action,
+            Action::ProviderImportForeign { candidates }
+                if candidates.len() == 1 && candidates[0].provider.name == "backup"
+        ));
+        assert!(matches!(app.overlay, Overlay::None));
+    }
+
     #[test]
     fn provider_form_esc_clean_closes_without_confirm() {
         let mut app = App::new(Some(AppType::Claude));
@@ -18756,6 +18899,12 @@ mod tests {
         assert!(matches!(app.usage.pane, UsagePane::Models));
 
         app.on_key(key(KeyCode::Tab), &data);
+        app.on_key(key(KeyCode::Tab), &data);
+        assert!(matches!(app.usage.pane, UsagePane::Projects));
+
+        app.on_key(key(KeyCode::Tab), &data);
+        assert!(matches!(app.usage.pane, UsagePane::Balances));
+
         app.on_key(key(KeyCode::Tab), &data);
         assert!(matches!(app.usage.pane, UsagePane::Recent));
 
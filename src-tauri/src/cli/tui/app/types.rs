@@ -62,6 +62,8 @@ pub enum SessionsPane {
 pub enum UsagePane {
     Models,
     Providers,
+    Projects,
+    Balances,
     Recent,
 }
 
@@ -938,6 +940,11 @@ pub struct PricingState {
     pub selected_idx: usize,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct UniversalProvidersState {
+    pub selected_idx: usize,
+}
+
 const SESSION_PAGE_CACHE_PAGES: usize = 2;
 const TRANSCRIPT_PAGE_CACHE_PAGES: usize = 2;
 
@@ -4334,6 +4341,9 @@ pub enum ConfirmAction {
     PricingDelete {
         model_id: String,
     },
+    UniversalProviderDelete {
+        id: String,
+    },
     SessionDelete {
         key: String,
         provider_id: String,
@@ -4384,6 +4394,7 @@ pub enum ConfirmAction {
         filename: String,
     },
     FormSaveBeforeClose,
+    ProviderLintSaveAnyway,
     #[allow(dead_code)]
     EditorDiscard,
     EditorSaveBeforeClose,
@@ -4617,6 +4628,10 @@ pub enum Overlay {
     BackupPicker {
         selected: usize,
     },
+    SwitchHistoryPicker {
+        selected: usize,
+        entries: Vec<crate::local_store::switch_history::ProviderSwitchRecord>,
+    },
     TextView(TextViewState),
     #[allow(dead_code)]
     CommonSnippetPicker {
@@ -4720,6 +4735,11 @@ pub enum Overlay {
         selected_idx: usize,
         selected: HashSet<String>,
     },
+    ProviderImportPicker {
+        candidates: Vec<crate::services::ForeignCandidate>,
+        selected_idx: usize,
+        selected: HashSet<usize>,
+    },
     #[allow(dead_code)]
     SkillsSyncMethodPicker {
         selected: usize,
@@ -4868,6 +4888,7 @@ impl Overlay {
         matches!(
             self,
             Overlay::BackupPicker { .. }
+                | Overlay::SwitchHistoryPicker { .. }
                 | Overlay::TextView(_)
                 | Overlay::CommonSnippetPicker { .. }
                 | Overlay::ProviderTestMenu { .. }
@@ -4888,6 +4909,7 @@ impl Overlay {
                 | Overlay::VisibleAppsPicker { .. }
                 | Overlay::SkillsAppsPicker { .. }
                 | Overlay::SkillsImportPicker { .. }
+                | Overlay::ProviderImportPicker { .. }
                 | Overlay::SkillsSyncMethodPicker { .. }
                 | Overlay::McpKeyValuePicker { .. }
                 | Overlay::McpTypePicker { .. }
@@ -4913,6 +4935,7 @@ impl Overlay {
             | Overlay::Confirm(_)
             | Overlay::CodexHistoryConfirm(_)
             | Overlay::BackupPicker { .. }
+            | Overlay::SwitchHistoryPicker { .. }
             | Overlay::TextView(_)
             | Overlay::CommonSnippetPicker { .. }
             | Overlay::ProviderTestMenu { .. }
@@ -4930,6 +4953,7 @@ impl Overlay {
             | Overlay::VisibleAppsPicker { .. }
             | Overlay::SkillsAppsPicker { .. }
             | Overlay::SkillsImportPicker { .. }
+            | Overlay::ProviderImportPicker { .. }
             | Overlay::SkillsSyncMethodPicker { .. }
             | Overlay::McpKeyValuePicker { .. }
             | Overlay::McpTypePicker { .. }
@@ -10,8 +10,6 @@ use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde_json::Value;
 
 use crate::app_config::{AppType, CommonConfigSnippets, McpServer};
-#[cfg(test)]
-pub(crate) use crate::cli::provider_quota::QuotaTargetKind;
 pub(crate) use crate::cli::provider_quota::{ProviderUsageQuota, QuotaTarget};
 use crate::commands::workspace::{self, DailyMemoryFileInfo, ALLOWED_FILES};
 use crate::database::lock_conn;
@@ -24,6 +22,10 @@ use crate::prompt::Prompt;
 use crate::prompt_files::prompt_file_path;
 use crate::provider::Provider;
 use crate::services::config::BackupInfo;
+pub(crate) use crate::services::project_usage::ProjectUsageRow as UsageProjectStatsRow;
+#[cfg(test)]
+pub(crate) use crate::services::provider_quota::QuotaTargetKind;
+use crate::services::quota_history::QuotaTrend;
 use crate::services::{ConfigService, McpService, PromptService, ProviderService, SkillService};
 use crate::store::AppState;
 
@@ -41,6 +43,7 @@ const EMPTY_USAGE_SUMMARY: UsageSummarySnapshot = UsageSummarySnapshot {
 };
 const EMPTY_USAGE_TREND: [UsageTrendBucket; 0] = [];
 const EMPTY_USAGE_PROVIDER_ROWS: [UsageProviderStatsRow; 0] = [];
+const EMPTY_USAGE_PROJECT_ROWS: [UsageProjectStatsRow; 0] = [];
 const EMPTY_USAGE_MODEL_ROWS: [UsageModelStatsRow; 0] = [];
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -219,6 +222,8 @@ pub struct ProvidersSnapshot {
     pub current_id: String,
     pub rows: Vec<ProviderRow>,
     pub live_ids: HashSet<String>,
+    /// Referenced models each provider no longer lists, per the model watch.
+    pub missing_models: HashMap<String, Vec<String>>,
     /// True only for the transient projection shown while a cold-switched app's
     /// real data is still loading. Lets the renderer show a "loading" state
     /// instead of the "no providers / import config" empty CTA, so a freshly
@@ -333,6 +338,20 @@ pub struct ProviderHealthSnapshot {
     pub consecutive_failures: u32,
 }
 
+/// Usable and total keys of a provider's API key pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyPoolSnapshot {
+    pub usable: usize,
+    pub total: usize,
+}
+
+/// Uptime and recent latencies from health checks of a provider.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProbeHistorySnapshot {
+    pub uptime_pct: f64,
+    pub latencies: Vec<(i64, f64)>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProxySnapshot {
     pub enabled: bool,
@@ -362,6 +381,8 @@ pub struct ProxySnapshot {
     pub last_error: Option<String>,
     pub current_app_target: Option<ProxyTargetSnapshot>,
     pub provider_health: Arc<HashMap<String, ProviderHealthSnapshot>>,
+    pub api_key_pools: Arc<HashMap<String, KeyPoolSnapshot>>,
+    pub probe_history: Arc<HashMap<String, ProbeHistorySnapshot>>,
 }
 
 /// Minimal TUI projection affected by enabling or disabling a managed proxy
@@ -856,11 +877,17 @@ pub struct UsageSnapshot {
     pub top_models_today: Vec<UsageModelStatsRow>,
     pub top_models_7d: Vec<UsageModelStatsRow>,
     pub top_models_30d: Vec<UsageModelStatsRow>,
+    /// Per-project rollups from the local attribution store (all apps' rows
+    /// filtered to the current app); empty when the store is unavailable.
+    pub top_projects_today: Vec<UsageProjectStatsRow>,
+    pub top_projects_7d: Vec<UsageProjectStatsRow>,
+    pub top_projects_30d: Vec<UsageProjectStatsRow>,
     pub custom_range: Option<UsageCustomRange>,
     pub summary_custom: UsageSummarySnapshot,
     pub trends_custom: Vec<UsageTrendBucket>,
     pub top_providers_custom: Vec<UsageProviderStatsRow>,
     pub top_models_custom: Vec<UsageModelStatsRow>,
+    pub top_projects_custom: Vec<UsageProjectStatsRow>,
     pub recent_logs: Vec<UsageLogRow>,
     pub logs_total: u64,
     pub recent_logs_custom: Vec<UsageLogRow>,
@@ -870,10 +897,19 @@ pub struct UsageSnapshot {
     /// Newest `session_log_sync.last_synced_at` (unix seconds), if any file
     /// has ever been imported.
     pub last_synced_at: Option<i64>,
+    /// Remaining-balance trends over the last 30 days from the local quota
+    /// history, independent of the selected range.
+    pub balances: Vec<UsageBalanceRow>,
     /// Cache key for render-time projections of this snapshot.
     pub generation: UsageSnapshotGeneration,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageBalanceRow {
+    pub provider_name: String,
+    pub trend: QuotaTrend,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ModelPricingRow {
     pub model_id: String,
@@ -888,6 +924,18 @@ pub struct ModelPricingRow {
     pub last_used_at: Option<i64>,
 }
 
+/// One universal provider plus the apps whose current provider is derived from it.
+#[derive(Debug, Clone)]
+pub struct UniversalProviderRow {
+    pub provider: crate::provider::UniversalProvider,
+    pub current_apps: Vec<AppType>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UniversalProvidersSnapshot {
+    pub rows: Vec<UniversalProviderRow>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ModelPricingSnapshot {
     pub rows: Vec<ModelPricingRow>,
@@ -972,6 +1020,7 @@ impl UsageSnapshot {
         self.trends_custom = empty_usage_trend(UsageRangePreset::Custom(range));
         self.top_providers_custom.clear();
         self.top_models_custom.clear();
+        self.top_projects_custom.clear();
         self.recent_logs_custom.clear();
         self.logs_total_custom = 0;
     }
@@ -986,6 +1035,7 @@ impl UsageSnapshot {
                 self.trends_custom = loaded.trends_custom;
                 self.top_providers_custom = loaded.top_providers_custom;
                 self.top_models_custom = loaded.top_models_custom;
+                self.top_projects_custom = loaded.top_projects_custom;
                 self.recent_logs_custom = loaded.recent_logs_custom;
                 self.logs_total_custom = loaded.logs_total_custom;
             }
@@ -997,6 +1047,7 @@ impl UsageSnapshot {
                 let trends_custom = self.trends_custom.clone();
                 let top_providers_custom = self.top_providers_custom.clone();
                 let top_models_custom = self.top_models_custom.clone();
+                let top_projects_custom = self.top_projects_custom.clone();
                 let recent_logs_custom = self.recent_logs_custom.clone();
                 let logs_total_custom = self.logs_total_custom;
 
@@ -1006,6 +1057,7 @@ impl UsageSnapshot {
                 self.trends_custom = trends_custom;
                 self.top_providers_custom = top_providers_custom;
                 self.top_models_custom = top_models_custom;
+                self.top_projects_custom = top_projects_custom;
                 self.recent_logs_custom = recent_logs_custom;
                 self.logs_total_custom = logs_total_custom;
             }
@@ -1117,6 +1169,18 @@ impl UsageSnapshot {
         }
     }
 
+    pub fn top_projects_for(&self, range: UsageRangePreset) -> &[UsageProjectStatsRow] {
+        match range {
+            UsageRangePreset::Today => &self.top_projects_today,
+            UsageRangePreset::SevenDays => &self.top_projects_7d,
+            UsageRangePreset::ThirtyDays => &self.top_projects_30d,
+            UsageRangePreset::Custom(custom_range) if self.custom_range == Some(custom_range) => {
+                &self.top_projects_custom
+            }
+            UsageRangePreset::Custom(_) => &EMPTY_USAGE_PROJECT_ROWS,
+        }
+    }
+
     pub fn recent_logs_for(&self, range: UsageRangePreset) -> &[UsageLogRow] {
         match range {
             UsageRangePreset::Custom(custom_range) if self.custom_range == Some(custom_range) => {
@@ -1153,7 +1217,8 @@ impl UsageSnapshot {
                     || bucket.error_count > 0
             })
             || !self.top_providers_for(range).is_empty()
-            || !self.top_models_for(range).is_empty();
+            || !self.top_models_for(range).is_empty()
+            || !self.top_projects_for(range).is_empty();
         if has_stats {
             return true;
         }
@@ -1177,6 +1242,7 @@ pub struct UiData {
     pub proxy: ProxySnapshot,
     pub usage: UsageSnapshot,
     pub pricing: ModelPricingSnapshot,
+    pub universal: UniversalProvidersSnapshot,
     pub(crate) quota: QuotaSnapshot,
     pub(crate) reload_token: UiDataReloadToken,
 }
@@ -1267,6 +1333,7 @@ impl UiData {
             ProviderLoadMode::SnapshotOnly => load_skills_snapshot_from_state(state)?,
         };
         let proxy = load_proxy_snapshot_from_state(state, app_type)?;
+        let universal = load_universal_providers(state)?;
 
         Ok(Self {
             providers,
@@ -1277,6 +1344,7 @@ impl UiData {
             proxy,
             usage: UsageSnapshot::default(),
             pricing: ModelPricingSnapshot::default(),
+            universal,
             quota: QuotaSnapshot::default(),
             reload_token: next_reload_token(),
         })
@@ -1288,6 +1356,8 @@ impl UiData {
         proxy.default_cost_multiplier = None;
         proxy.current_app_target = None;
         proxy.provider_health = Arc::default();
+        proxy.api_key_pools = Arc::default();
+        proxy.probe_history = Arc::default();
 
         Self {
             providers: ProvidersSnapshot {
@@ -1301,6 +1371,7 @@ impl UiData {
             proxy,
             usage: UsageSnapshot::default(),
             pricing: ModelPricingSnapshot::default(),
+            universal: self.universal.clone(),
             quota: QuotaSnapshot::default(),
             reload_token: next_reload_token(),
         }
@@ -1606,10 +1677,19 @@ fn load_providers_with_mode(
         _ => HashSet::new(),
     };
 
+    let missing_models = crate::services::model_watch::missing_models_by_provider(
+        app_type,
+        rows.iter().map(|row| &row.provider),
+    )
+    .into_iter()
+    .map(|(id, missing)| (id, missing.into_iter().map(|entry| entry.model).collect()))
+    .collect();
+
     Ok(ProvidersSnapshot {
         current_id,
         rows,
         live_ids,
+        missing_models,
         loading: false,
     })
 }
@@ -1801,6 +1881,28 @@ fn load_mcp(state: &AppState) -> Result<McpSnapshot, AppError> {
     Ok(McpSnapshot { rows })
 }
 
+fn load_universal_providers(state: &AppState) -> Result<UniversalProvidersSnapshot, AppError> {
+    let providers = crate::services::UniversalProviderService::list(state)?;
+    let mut rows = Vec::with_capacity(providers.len());
+    for provider in providers {
+        let mut current_apps = Vec::new();
+        for app in provider.apps.enabled_apps() {
+            if app.is_additive_mode() {
+                continue;
+            }
+            let current = crate::settings::get_effective_current_provider(&state.db, &app)?;
+            if current.as_deref() == Some(provider.derived_provider_id(&app).as_str()) {
+                current_apps.push(app);
+            }
+        }
+        rows.push(UniversalProviderRow {
+            provider,
+            current_apps,
+        });
+    }
+    Ok(UniversalProvidersSnapshot { rows })
+}
+
 fn load_prompts(state: &AppState, app_type: &AppType) -> Result<PromptsSnapshot, AppError> {
     let prompts = PromptService::get_prompts(state, app_type.clone())?;
     let mut rows = prompts
@@ -2089,6 +2191,11 @@ fn load_usage_fixed_snapshot(
     let logs_total = load_usage_logs_total(&conn, app_key, None)?;
     let daily_models = load_usage_daily_models(&conn, app_key, thirty_start, now)?;
     let last_synced_at = load_session_last_synced_at(&conn);
+    drop(conn);
+    let top_projects_today = load_usage_top_projects(app_key, today_start, now);
+    let top_projects_7d = load_usage_top_projects(app_key, seven_start, now);
+    let top_projects_30d = load_usage_top_projects(app_key, thirty_start, now);
+    let balances = load_usage_balances(state, app_key);
 
     Ok(UsageSnapshot {
         summary_today,
@@ -2103,10 +2210,14 @@ fn load_usage_fixed_snapshot(
         top_models_today,
         top_models_7d,
         top_models_30d,
+        top_projects_today,
+        top_projects_7d,
+        top_projects_30d,
         recent_logs,
         logs_total,
         daily_models,
         last_synced_at,
+        balances,
         ..UsageSnapshot::default()
     })
 }
@@ -2133,6 +2244,9 @@ fn load_usage_custom_snapshot(
     let log_range = Some((custom_range.start, custom_range.end));
     let recent_logs = load_usage_recent_logs(&conn, app_key, log_range, 100)?;
     let logs_total = load_usage_logs_total(&conn, app_key, log_range)?;
+    drop(conn);
+    let top_projects_custom =
+        load_usage_top_projects(app_key, custom_range.start, custom_range.end);
 
     Ok(UsageSnapshot {
         custom_range: Some(custom_range),
@@ -2140,6 +2254,7 @@ fn load_usage_custom_snapshot(
         trends_custom,
         top_providers_custom,
         top_models_custom,
+        top_projects_custom,
         recent_logs_custom: recent_logs,
         logs_total_custom: logs_total,
         ..UsageSnapshot::default()
@@ -2938,6 +3053,66 @@ pub fn build_usage_daily_chart_series(
     }
 }
 
+/// Project rollups live in the local sidecar store, not the main DB. The page
+/// must keep working without it, so any failure degrades to an empty table.
+fn load_usage_top_projects(app_key: &str, start: i64, end: i64) -> Vec<UsageProjectStatsRow> {
+    let store = match crate::local_store::LocalStore::open() {
+        Ok(store) => store,
+        Err(error) => {
+            log::debug!("usage project stats unavailable: {error}");
+            return Vec::new();
+        }
+    };
+    let filter = crate::services::project_usage::project_usage_filter(
+        Some(app_key),
+        Some(local_date_label(start)),
+        Some(local_date_label(end)),
+    );
+    store
+        .project_usage_rows(&filter, Some(8))
+        .unwrap_or_else(|error| {
+            log::debug!("usage project stats query failed: {error}");
+            Vec::new()
+        })
+}
+
+/// Balance history is another sidecar view: unavailable means "no rows".
+fn load_usage_balances(state: &AppState, app_key: &str) -> Vec<UsageBalanceRow> {
+    const WINDOW_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+    let since = Local::now().timestamp_millis() - WINDOW_MS;
+    let samples = match crate::local_store::LocalStore::open()
+        .and_then(|store| store.quota_samples_since(app_key, None, since))
+    {
+        Ok(samples) => samples,
+        Err(error) => {
+            log::debug!("usage balance history unavailable: {error}");
+            return Vec::new();
+        }
+    };
+    let providers = state.db.get_all_providers(app_key).unwrap_or_default();
+    crate::services::quota_history::quota_trends(&samples)
+        .into_iter()
+        .map(|trend| UsageBalanceRow {
+            provider_name: providers
+                .get(&trend.provider_id)
+                .map(|provider| provider.name.clone())
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| trend.provider_id.clone()),
+            trend,
+        })
+        .collect()
+}
+
+fn local_date_label(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|datetime| datetime.date_naive())
+        .unwrap_or_else(|| Local::now().date_naive())
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
 fn load_usage_top_providers(
     conn: &rusqlite::Connection,
     app_key: &str,
@@ -3543,6 +3718,8 @@ pub(crate) async fn load_proxy_snapshot_from_state_async(
             })
             .collect(),
     );
+    let api_key_pools = Arc::new(load_api_key_pools(state, app_type)?);
+    let probe_history = Arc::new(load_probe_history(state, app_type)?);
     let active_worker_apps = runtime_status
         .active_workers
         .iter()
@@ -3603,9 +3780,82 @@ pub(crate) async fn load_proxy_snapshot_from_state_async(
             .map(str::to_string),
         current_app_target,
         provider_health,
+        api_key_pools,
+        probe_history,
     })
 }
 
+/// Health checks of the last day, for the uptime and latency badge.
+fn load_probe_history(
+    state: &AppState,
+    app_type: &AppType,
+) -> Result<HashMap<String, ProbeHistorySnapshot>, AppError> {
+    let since = chrono::Utc::now().timestamp() - 24 * 60 * 60;
+    let store = crate::local_store::LocalStore::open().ok();
+    let entries = crate::services::health_monitor::load_checks(
+        &state.db,
+        store.as_ref(),
+        Some(app_type.as_str()),
+        None,
+        since,
+    )?;
+    Ok(crate::services::health_monitor::summarize(&entries)
+        .into_iter()
+        .map(|summary| {
+            (
+                summary.provider_id,
+                ProbeHistorySnapshot {
+                    uptime_pct: summary.uptime_pct,
+                    latencies: summary.latencies,
+                },
+            )
+        })
+        .collect())
+}
+
+fn load_api_key_pools(
+    state: &AppState,
+    app_type: &AppType,
+) -> Result<HashMap<String, KeyPoolSnapshot>, AppError> {
+    let pooled: Vec<Provider> = {
+        let config = state.config.read()?;
+        config
+            .get_manager(app_type)
+            .map(|manager| {
+                manager
+                    .providers
+                    .values()
+                    .filter(|provider| crate::key_pool::pool(provider).is_some())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    if pooled.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let store = crate::local_store::LocalStore::open()?;
+    let now = chrono::Utc::now().timestamp();
+    let mut pools = HashMap::new();
+    for provider in pooled {
+        let statuses = crate::key_pool::statuses(&store, app_type, &provider)?;
+        if statuses.is_empty() {
+            continue;
+        }
+        pools.insert(
+            provider.id.clone(),
+            KeyPoolSnapshot {
+                usable: statuses
+                    .iter()
+                    .filter(|status| status.is_usable_at(now))
+                    .count(),
+                total: statuses.len(),
+            },
+        );
+    }
+    Ok(pools)
+}
+
 pub(crate) async fn load_provider_runtime_snapshot_from_state_async(
     state: &AppState,
     app_type: &AppType,
@@ -4,7 +4,7 @@ use crate::claude_model_config::{
 };
 use crate::cli::i18n::texts;
 use crate::provider::{ClaudeApiKeyField, CodexChatReasoningConfig};
-use crate::provider_preset_models::CODEX_DEFAULT_MODEL;
+use crate::provider_preset_models::codex_default_model;
 use crate::services::ProviderService;
 use serde_json::{json, Value};
 use std::collections::HashSet;
@@ -242,7 +242,7 @@ impl ProviderAddFormState {
         }
 
         let fallback_model = if self.codex_model.is_blank() {
-            CODEX_DEFAULT_MODEL
+            codex_default_model()
         } else {
             self.codex_model.value.trim()
         };
@@ -295,9 +295,9 @@ impl ProviderAddFormState {
         let model = if !model_catalog.is_empty() {
             model_catalog[0]["model"]
                 .as_str()
-                .unwrap_or(CODEX_DEFAULT_MODEL)
+                .unwrap_or(codex_default_model())
         } else if self.codex_model.is_blank() {
-            CODEX_DEFAULT_MODEL
+            codex_default_model()
         } else {
             self.codex_model.value.trim()
         };
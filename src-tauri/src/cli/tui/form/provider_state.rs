@@ -2,7 +2,7 @@ use crate::app_config::AppType;
 use crate::claude_model_config::split_claude_one_m_marker;
 use crate::cli::i18n::texts;
 use crate::provider::{ClaudeApiKeyField, CodexChatReasoningConfig, Provider};
-use crate::provider_preset_models::{CODEX_DEFAULT_MODEL, GEMINI_DEFAULT_MODEL};
+use crate::provider_preset_models::{codex_default_model, gemini_default_model};
 use crate::services::ProviderService;
 use serde_json::{json, Value};
 
@@ -126,7 +126,7 @@ impl ProviderAddFormState {
         };
 
         let codex_defaults = if is_codex {
-            ("", CODEX_DEFAULT_MODEL, CodexWireApi::Responses, true)
+            ("", codex_default_model(), CodexWireApi::Responses, true)
         } else {
             ("", "", CodexWireApi::Responses, true)
         };
@@ -217,7 +217,11 @@ impl ProviderAddFormState {
             gemini_auth_type: GeminiAuthType::ApiKey,
             gemini_api_key: TextInput::new(""),
             gemini_base_url: TextInput::new("https://generativelanguage.googleapis.com"),
-            gemini_model: TextInput::new(if is_gemini { GEMINI_DEFAULT_MODEL } else { "" }),
+            gemini_model: TextInput::new(if is_gemini {
+                gemini_default_model()
+            } else {
+                ""
+            }),
             openclaw_user_agent: false,
             openclaw_models: Vec::new(),
             usage_query_enabled: false,
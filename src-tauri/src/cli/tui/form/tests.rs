@@ -544,35 +544,33 @@ fn provider_add_form_template_change_clears_hidden_full_url_state() {
 
 #[test]
 fn provider_add_form_aicodemirror_preset_keeps_affiliate_register_url_in_metadata() {
-    let claude_presets = super::provider_templates::provider_sponsor_presets(&AppType::Claude);
-    let aicodemirror = claude_presets
-        .iter()
-        .find(|preset| preset.id() == "aicodemirror")
-        .expect("expected AICodeMirror sponsor preset for Claude");
+    let aicodemirror = &crate::preset_catalog::shared_catalog()
+        .find(&AppType::Claude, "aicodemirror")
+        .expect("expected AICodeMirror sponsor preset for Claude")
+        .preset;
 
     assert_eq!(
-        aicodemirror.register_url(),
-        "https://www.aicodemirror.ai/register?invitecode=77V9EA"
+        aicodemirror.register_url.as_deref(),
+        Some("https://www.aicodemirror.ai/register?invitecode=77V9EA")
     );
     assert!(
-        super::provider_templates::provider_sponsor_presets(&AppType::Hermes)
-            .iter()
-            .any(|preset| preset.id() == "aicodemirror"),
+        crate::preset_catalog::shared_catalog()
+            .find(&AppType::Hermes, "aicodemirror")
+            .is_some(),
         "AICodeMirror should be available for Hermes when the upstream preset is configured"
     );
 }
 
 #[test]
 fn provider_add_form_claudeapi_preset_keeps_affiliate_register_url_in_metadata() {
-    let claude_presets = super::provider_templates::provider_sponsor_presets(&AppType::Claude);
-    let claudeapi = claude_presets
-        .iter()
-        .find(|preset| preset.id() == "claudeapi")
-        .expect("expected ClaudeAPI sponsor preset for Claude");
+    let claudeapi = &crate::preset_catalog::shared_catalog()
+        .find(&AppType::Claude, "claudeapi")
+        .expect("expected ClaudeAPI sponsor preset for Claude")
+        .preset;
 
     assert_eq!(
-        claudeapi.register_url(),
-        "https://console.apito.ai/agent/register/Bsi9NDlWGpkPoAii"
+        claudeapi.register_url.as_deref(),
+        Some("https://console.apito.ai/agent/register/Bsi9NDlWGpkPoAii")
     );
 }
 
@@ -594,38 +592,41 @@ fn provider_add_form_google_oauth_template_marks_official_metadata() {
 
 #[test]
 fn provider_add_form_dds_preset_keeps_affiliate_register_url_in_metadata() {
-    let claude_presets = super::provider_templates::provider_sponsor_presets(&AppType::Claude);
-    let dds = claude_presets
-        .iter()
-        .find(|preset| preset.id() == "dds")
-        .expect("expected DDS sponsor preset for Claude");
+    let dds = &crate::preset_catalog::shared_catalog()
+        .find(&AppType::Claude, "dds")
+        .expect("expected DDS sponsor preset for Claude")
+        .preset;
 
-    assert_eq!(dds.register_url(), "https://ddshub.short.gy/ccscli");
+    assert_eq!(
+        dds.register_url.as_deref(),
+        Some("https://ddshub.short.gy/ccscli")
+    );
 }
 
 #[test]
 fn provider_add_form_cubence_preset_keeps_affiliate_register_url_in_metadata() {
-    let claude_presets = super::provider_templates::provider_sponsor_presets(&AppType::Claude);
-    let cubence = claude_presets
-        .iter()
-        .find(|preset| preset.id() == "cubence")
-        .expect("expected Cubence sponsor preset for Claude");
+    let cubence = &crate::preset_catalog::shared_catalog()
+        .find(&AppType::Claude, "cubence")
+        .expect("expected Cubence sponsor preset for Claude")
+        .preset;
 
     assert_eq!(
-        cubence.register_url(),
-        "https://cubence.com/signup?code=SC3M1CAH&source=ccscli"
+        cubence.register_url.as_deref(),
+        Some("https://cubence.com/signup?code=SC3M1CAH&source=ccscli")
     );
 }
 
 #[test]
 fn provider_add_form_runapi_preset_keeps_affiliate_register_url_in_metadata() {
-    let claude_presets = super::provider_templates::provider_sponsor_presets(&AppType::Claude);
-    let runapi = claude_presets
-        .iter()
-        .find(|preset| preset.id() == "runapi")
-        .expect("expected RunAPI sponsor preset for Claude");
+    let runapi = &crate::preset_catalog::shared_catalog()
+        .find(&AppType::Claude, "runapi")
+        .expect("expected RunAPI sponsor preset for Claude")
+        .preset;
 
-    assert_eq!(runapi.register_url(), "https://runapi.co/register?aff=kTlB");
+    assert_eq!(
+        runapi.register_url.as_deref(),
+        Some("https://runapi.co/register?aff=kTlB")
+    );
 }
 
 #[test]
@@ -2632,7 +2633,7 @@ fn provider_add_form_aicodemirror_template_gemini_sets_partner_meta_and_base_url
     );
     assert_eq!(
         provider["settingsConfig"]["env"]["GEMINI_MODEL"],
-        crate::provider_preset_models::GEMINI_DEFAULT_MODEL
+        crate::provider_preset_models::gemini_default_model()
     );
     assert!(form.gemini_api_key.value.is_empty());
     assert!(provider["settingsConfig"]["env"]
@@ -4043,7 +4044,7 @@ fn provider_add_form_gemini_builds_env_settings() {
     );
     assert_eq!(
         provider["settingsConfig"]["env"]["GEMINI_MODEL"],
-        crate::provider_preset_models::GEMINI_DEFAULT_MODEL
+        crate::provider_preset_models::gemini_default_model()
     );
 }
 
@@ -5720,10 +5721,6 @@ fn provider_add_form_opencode_exposes_supported_sponsor_presets() {
 
 #[test]
 fn provider_add_form_openclaw_uses_dedicated_template_defs() {
-    let openclaw_defs =
-        super::provider_templates::provider_builtin_template_defs(&AppType::OpenClaw);
-    let opencode_defs =
-        super::provider_templates::provider_builtin_template_defs(&AppType::OpenCode);
     let openclaw_labels = ProviderAddFormState::new(AppType::OpenClaw).template_labels();
 
     assert_eq!(
@@ -5739,10 +5736,6 @@ fn provider_add_form_openclaw_uses_dedicated_template_defs() {
             "* PackyCode"
         ]
     );
-    assert!(
-        !std::ptr::eq(openclaw_defs, opencode_defs),
-        "OpenClaw should keep its own template mapping instead of aliasing OpenCode"
-    );
 }
 
 #[test]
@@ -5910,7 +5903,7 @@ fn provider_add_form_aicodemirror_template_opencode_matches_serializer_and_loade
         .keys()
         .map(String::as_str)
         .collect::<Vec<_>>();
-    assert_eq!(model_ids, ["claude-sonnet-5", "claude-opus-5"]);
+    assert_eq!(model_ids, ["claude-opus-5", "claude-sonnet-5"]);
     assert_eq!(
         provider["settingsConfig"]["models"]["claude-sonnet-5"]["name"],
         "Claude Sonnet 5"
@@ -109,6 +109,9 @@ pub(crate) mod providers {
         LaunchTemp,
         Failover,
         SetDefault,
+        Universal,
+        SwitchHistory,
+        ImportForeign,
     }
 
     pub(crate) const BINDINGS: &[Binding<Intent>] = &[
@@ -189,6 +192,27 @@ pub(crate) mod providers {
             label: set_default_label,
             shown: set_default_shown,
         },
+        Binding {
+            display: "u",
+            keys: &[KeyCode::Char('u')],
+            intent: Intent::Universal,
+            label: |_, _| texts::tui_key_universal(),
+            shown: super::help_only,
+        },
+        Binding {
+            display: "b",
+            keys: &[KeyCode::Char('b')],
+            intent: Intent::SwitchHistory,
+            label: |_, _| texts::tui_key_switch_history(),
+            shown: super::help_only,
+        },
+        Binding {
+            display: "I",
+            keys: &[KeyCode::Char('I')],
+            intent: Intent::ImportForeign,
+            label: |_, _| texts::tui_key_import_foreign(),
+            shown: super::help_only,
+        },
     ];
 
     pub(crate) fn intent_for(key: KeyCode) -> Option<Intent> {
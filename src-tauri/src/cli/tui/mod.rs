@@ -43,11 +43,9 @@ use runtime_skills::{
 pub(crate) use runtime_systems::build_stream_check_result_lines;
 #[cfg(test)]
 use runtime_systems::{
-    apply_webdav_jianguoyun_quick_setup, build_model_fetch_candidate_urls, drain_latest_webdav_req,
-    model_fetch_strategy_for_field, parse_model_ids_from_response, update_webdav_last_error_with,
-    UpdateMsg, WebDavReqKind,
+    apply_webdav_jianguoyun_quick_setup, drain_latest_webdav_req, model_fetch_strategy_for_field,
+    update_webdav_last_error_with, UpdateMsg, WebDavReqKind,
 };
-pub(crate) use runtime_systems::{fetch_provider_models_for_tui, ModelFetchStrategy};
 use runtime_systems::{
     handle_codex_history_msg, handle_local_env_msg, handle_managed_auth_msg,
     handle_model_fetch_msg, handle_proxy_msg, handle_quota_msg, handle_session_msg,
@@ -2210,6 +2208,8 @@ fn cache_invalidation_for_action(action: &Action) -> CacheInvalidation {
         | Action::SessionResume { .. }
         | Action::SessionDelete { .. }
         | Action::ProviderSpeedtest { .. }
+        | Action::ProviderSwitchHistory
+        | Action::ProviderOpenForeignImport
         | Action::ProviderLaunchTemporary { .. }
         | Action::ProviderStreamCheck { .. }
         | Action::ProviderQuotaRefresh { .. }
@@ -2274,6 +2274,7 @@ fn cache_invalidation_for_action(action: &Action) -> CacheInvalidation {
         | Action::ProviderRemoveFromConfig { .. }
         | Action::ProviderSetDefaultModel { .. }
         | Action::ProviderImportLiveConfig
+        | Action::ProviderImportForeign { .. }
         | Action::ProviderDelete { .. }
         | Action::ProviderSetFailoverQueue { .. }
         | Action::ProviderMoveFailoverQueue { .. }
@@ -2297,6 +2298,8 @@ fn cache_invalidation_for_action(action: &Action) -> CacheInvalidation {
         | Action::SkillsRepoToggleEnabled { .. }
         | Action::SkillsImportFromApps { .. }
         | Action::PricingDelete { .. }
+        | Action::UniversalProviderApply { .. }
+        | Action::UniversalProviderDelete { .. }
         | Action::McpToggle { .. }
         | Action::McpSetApps { .. }
         | Action::McpDelete { .. }
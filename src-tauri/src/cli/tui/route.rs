@@ -4,6 +4,7 @@ use crate::app_config::AppType;
 pub enum Route {
     Main,
     Providers,
+    UniversalProviders,
     Usage,
     UsageLogs,
     UsageLogDetail { rowid: i64 },
@@ -139,6 +139,7 @@ mod tests {
                         current_id: current_id.to_string(),
                         rows,
                         live_ids: Default::default(),
+                        missing_models: Default::default(),
                         loading: false,
                     },
                     ..UiData::default()
@@ -280,6 +280,10 @@ pub(super) fn submit(
         EditorSubmit::ProviderAdd => submit_provider_add(ctx, content),
         EditorSubmit::ProviderEdit { id } => submit_provider_edit(ctx, id, content),
         EditorSubmit::PricingEdit { model_id } => submit_pricing_edit(ctx, model_id, content),
+        EditorSubmit::UniversalProviderAdd => super::universal::submit_add(ctx, content),
+        EditorSubmit::UniversalProviderEdit { id } => {
+            super::universal::submit_edit(ctx, id, content)
+        }
         EditorSubmit::McpAdd => submit_mcp_add(ctx, content),
         EditorSubmit::McpEdit { id } => submit_mcp_edit(ctx, id, content),
         EditorSubmit::ConfigCommonSnippet { app_type, source } => {
@@ -18,11 +18,13 @@ mod config;
 mod editor;
 mod helpers;
 mod mcp;
+mod overlay_temp_launch;
 mod pricing;
 mod prompts;
 mod providers;
 mod settings;
 mod skills;
+mod universal;
 mod updates;
 
 pub(crate) use helpers::{app_display_name, queue_managed_proxy_action};
@@ -37,6 +39,7 @@ fn normalize_route_for_app(app_type: &AppType, route: &super::route::Route) -> s
         AppType::OpenClaw => match route {
             super::route::Route::Main
             | super::route::Route::Providers
+            | super::route::Route::UniversalProviders
             | super::route::Route::Usage
             | super::route::Route::UsageLogs
             | super::route::Route::UsageLogDetail { .. }
@@ -56,6 +59,7 @@ fn normalize_route_for_app(app_type: &AppType, route: &super::route::Route) -> s
         AppType::Hermes => match route {
             super::route::Route::Main
             | super::route::Route::Providers
+            | super::route::Route::UniversalProviders
             | super::route::Route::Usage
             | super::route::Route::UsageLogs
             | super::route::Route::UsageLogDetail { .. }
@@ -878,18 +882,26 @@ pub(crate) fn handle_action(
         }
         Action::EditorSubmit { submit, content } => editor::submit(&mut ctx, submit, content),
         Action::ProviderSwitch { id } => providers::switch(&mut ctx, id),
+        Action::ProviderSwitchHistory => providers::open_switch_history(&mut ctx),
         Action::ProviderRemoveFromConfig { id } => providers::remove_from_config(&mut ctx, id),
         Action::ProviderSetDefaultModel {
             provider_id,
             model_id,
         } => providers::set_default_model(&mut ctx, provider_id, model_id),
         Action::ProviderImportLiveConfig => providers::import_live_config(&mut ctx),
+        Action::ProviderOpenForeignImport => providers::open_foreign_import(&mut ctx),
+        Action::ProviderImportForeign { candidates } => {
+            providers::import_foreign(&mut ctx, candidates)
+        }
         Action::ProviderDelete { id } => providers::delete(&mut ctx, id),
         Action::ProviderSpeedtest { url } => providers::speedtest(&mut ctx, url),
         Action::ProviderLaunchTemporary { id } => match ctx.app.app_type {
             AppType::Claude => claude_temp_launch::launch(&mut ctx, id),
             AppType::Codex => codex_temp_launch::launch(&mut ctx, id),
-            _ => Ok(()),
+            AppType::Gemini | AppType::OpenCode | AppType::Hermes => {
+                overlay_temp_launch::launch(&mut ctx, id)
+            }
+            AppType::OpenClaw => Ok(()),
         },
         Action::ProviderStreamCheck { id } => providers::stream_check(&mut ctx, id),
         Action::ProviderSetFailoverQueue { id, enabled } => {
@@ -921,6 +933,8 @@ pub(crate) fn handle_action(
         ),
         Action::UsageCustomRange { .. } => Ok(()),
         Action::PricingDelete { model_id } => pricing::delete(&mut ctx, model_id),
+        Action::UniversalProviderApply { id } => universal::apply(&mut ctx, id),
+        Action::UniversalProviderDelete { id } => universal::delete(&mut ctx, id),
         Action::McpToggle { id, enabled } => mcp::toggle(&mut ctx, id, enabled),
         Action::McpSetApps { id, apps } => mcp::set_apps(&mut ctx, id, apps),
         Action::McpDelete { id } => mcp::delete(&mut ctx, id),
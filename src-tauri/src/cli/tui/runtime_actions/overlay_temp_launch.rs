@@ -0,0 +1,238 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+use crate::cli::i18n::texts;
+use crate::cli::overlay_temp_launch::{
+    ensure_temp_launch_supported, exec_prepared_overlay, launch_settings, prepare_launch_with,
+    resolve_binary, supports_app, PreparedOverlayLaunch,
+};
+use crate::cli::tui::data::load_state;
+use crate::error::AppError;
+use crate::services::ProviderService;
+
+use super::super::app::ToastKind;
+use super::super::terminal::TuiTerminal;
+use super::RuntimeActionContext;
+
+pub(super) fn launch(ctx: &mut RuntimeActionContext<'_>, id: String) -> Result<(), AppError> {
+    launch_with(
+        ctx,
+        id,
+        &std::env::temp_dir(),
+        prepare_overlay_launch,
+        handoff_to_app,
+    )
+}
+
+fn prepare_overlay_launch(
+    app_type: &crate::app_config::AppType,
+    id: &str,
+    temp_dir: &Path,
+) -> Result<PreparedOverlayLaunch, AppError> {
+    let state = load_state()?;
+    let provider = ProviderService::get_provider(&state, app_type.clone(), id)?;
+    let settings = launch_settings(&state, app_type, &provider)?;
+    prepare_launch_with(app_type, &provider, &settings, None, temp_dir, || {
+        resolve_binary(app_type)
+    })
+}
+
+fn launch_with<Prepare, Handoff>(
+    ctx: &mut RuntimeActionContext<'_>,
+    id: String,
+    temp_dir: &Path,
+    prepare: Prepare,
+    handoff: Handoff,
+) -> Result<(), AppError>
+where
+    Prepare:
+        FnOnce(&crate::app_config::AppType, &str, &Path) -> Result<PreparedOverlayLaunch, AppError>,
+    Handoff: FnOnce(&mut TuiTerminal, &PreparedOverlayLaunch) -> Result<(), AppError>,
+{
+    if !supports_app(&ctx.app.app_type) {
+        return Ok(());
+    }
+
+    if let Err(err) = try_launch_with(ctx, &id, temp_dir, prepare, handoff) {
+        ctx.app.push_toast(
+            texts::tui_temp_launch_failed(&err.to_string()),
+            ToastKind::Error,
+        );
+    }
+    Ok(())
+}
+
+fn handoff_to_app(
+    terminal: &mut TuiTerminal,
+    prepared: &PreparedOverlayLaunch,
+) -> Result<(), AppError> {
+    let native_args = Vec::<OsString>::new();
+    terminal.with_terminal_restored_for_handoff(|| exec_prepared_overlay(prepared, &native_args))
+}
+
+fn try_launch_with<Prepare, Handoff>(
+    ctx: &mut RuntimeActionContext<'_>,
+    id: &str,
+    temp_dir: &Path,
+    prepare: Prepare,
+    handoff: Handoff,
+) -> Result<(), AppError>
+where
+    Prepare:
+        FnOnce(&crate::app_config::AppType, &str, &Path) -> Result<PreparedOverlayLaunch, AppError>,
+    Handoff: FnOnce(&mut TuiTerminal, &PreparedOverlayLaunch) -> Result<(), AppError>,
+{
+    let app_type = ctx.app.app_type.clone();
+    ensure_temp_launch_supported(&app_type)?;
+
+    let prepared = prepare(&app_type, id, temp_dir)?;
+    let handoff_result = handoff(ctx.terminal, &prepared);
+    let cleanup_result = prepared.cleanup_overlay_dir();
+
+    match (handoff_result, cleanup_result) {
+        (Ok(()), Ok(())) => Ok(()),
+        (Ok(()), Err(cleanup_err)) => Err(cleanup_err),
+        (Err(err), Ok(())) => Err(err),
+        (Err(err), Err(cleanup_err)) => Err(AppError::localized(
+            "temp_launch.cleanup_failed",
+            format!("启动失败: {err}；同时清理临时覆盖目录失败: {cleanup_err}"),
+            format!(
+                "Failed to launch: {err}; also failed to remove the temporary overlay directory: {cleanup_err}"
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::AppType;
+    use crate::cli::tui::app::{App, ToastKind};
+    use crate::cli::tui::data::UiData;
+    use crate::cli::tui::runtime_systems::RequestTracker;
+    use crate::provider::Provider;
+    use serde_json::json;
+    use std::cell::Cell;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    struct LaunchFixture {
+        terminal: TuiTerminal,
+        app: App,
+        data: UiData,
+        proxy_loading: RequestTracker,
+        webdav_loading: RequestTracker,
+        update_check: RequestTracker,
+    }
+
+    impl LaunchFixture {
+        fn new(app_type: AppType) -> Self {
+            Self {
+                terminal: TuiTerminal::new_for_test().expect("create test terminal"),
+                app: App::new(Some(app_type)),
+                data: UiData::default(),
+                proxy_loading: RequestTracker::default(),
+                webdav_loading: RequestTracker::default(),
+                update_check: RequestTracker::default(),
+            }
+        }
+
+        fn ctx(&mut self) -> RuntimeActionContext<'_> {
+            RuntimeActionContext {
+                terminal: &mut self.terminal,
+                app: &mut self.app,
+                data: &mut self.data,
+                speedtest_req_tx: None,
+                stream_check_req_tx: None,
+                skills_req_tx: None,
+                proxy_req_tx: None,
+                proxy_loading: &mut self.proxy_loading,
+                local_env_req_tx: None,
+                session_req_tx: None,
+                webdav_req_tx: None,
+                webdav_loading: &mut self.webdav_loading,
+                update_req_tx: None,
+                update_check: &mut self.update_check,
+                model_fetch_req_tx: None,
+                managed_auth_req_tx: None,
+            }
+        }
+    }
+
+    fn opencode_provider() -> Provider {
+        Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({
+                "npm": "@ai-sdk/openai-compatible",
+                "options": { "baseURL": "https://relay.example/v1" },
+                "models": { "qwen3-coder": {} }
+            }),
+            None,
+        )
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launch_failure_surfaces_a_toast_and_removes_the_overlay() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let mut fixture = LaunchFixture::new(AppType::OpenCode);
+        let captured_dir = std::cell::RefCell::new(None::<PathBuf>);
+
+        launch_with(
+            &mut fixture.ctx(),
+            "relay".to_string(),
+            temp_dir.path(),
+            |app_type, _, temp_dir| {
+                let provider = opencode_provider();
+                prepare_launch_with(
+                    app_type,
+                    &provider,
+                    &provider.settings_config,
+                    None,
+                    temp_dir,
+                    || Ok(PathBuf::from("/usr/bin/opencode")),
+                )
+            },
+            |_, prepared| {
+                assert!(prepared.overlay_dir.join("opencode.json").exists());
+                captured_dir.replace(Some(prepared.overlay_dir.clone()));
+                Err(AppError::Message("launch exploded".to_string()))
+            },
+        )
+        .expect("launch failure should stay in the TUI");
+
+        assert!(matches!(
+            fixture.app.toast.as_ref(),
+            Some(toast)
+                if toast.kind == ToastKind::Error
+                    && toast.message == texts::tui_temp_launch_failed("launch exploded")
+        ));
+        let overlay_dir = captured_dir
+            .into_inner()
+            .expect("handoff should observe the overlay dir");
+        assert!(!overlay_dir.exists());
+    }
+
+    #[test]
+    fn apps_without_overlay_launch_are_ignored() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let mut fixture = LaunchFixture::new(AppType::OpenClaw);
+        let prepare_called = Cell::new(false);
+
+        launch_with(
+            &mut fixture.ctx(),
+            "relay".to_string(),
+            temp_dir.path(),
+            |_, _, _| {
+                prepare_called.set(true);
+                Err(AppError::Message("unreachable".to_string()))
+            },
+            |_, _| Ok(()),
+        )
+        .expect("ignored dispatch");
+
+        assert!(!prepare_called.get());
+        assert!(fixture.app.toast.is_none());
+    }
+}
@@ -5,7 +5,8 @@ use crate::error::AppError;
 use crate::openclaw_config::OpenClawDefaultModel;
 use crate::proxy::providers::get_claude_api_format;
 use crate::services::provider::ProviderSortUpdate;
-use crate::services::ProviderService;
+use crate::services::switch_history::{self, SwitchSource};
+use crate::services::{ForeignCandidate, ForeignSource, ProviderService};
 
 use super::super::app::{ConfirmAction, ConfirmOverlay, Overlay, ToastKind};
 use super::super::data::load_state;
@@ -104,6 +105,64 @@ pub(super) fn switch(ctx: &mut RuntimeActionContext<'_>, id: String) -> Result<(
     do_switch(ctx, state, id)
 }
 
+pub(super) fn open_switch_history(ctx: &mut RuntimeActionContext<'_>) -> Result<(), AppError> {
+    let entries = switch_history::recent(Some(&ctx.app.app_type), 20)?;
+    if entries.is_empty() {
+        ctx.app
+            .push_toast(texts::tui_toast_no_switch_history(), ToastKind::Info);
+        return Ok(());
+    }
+    ctx.app.overlay = Overlay::SwitchHistoryPicker {
+        selected: 0,
+        entries,
+    };
+    Ok(())
+}
+
+pub(super) fn open_foreign_import(ctx: &mut RuntimeActionContext<'_>) -> Result<(), AppError> {
+    let state = load_state()?;
+    let mut candidates = Vec::new();
+    for source in ForeignSource::ALL {
+        candidates.extend(
+            ProviderService::scan_foreign(&state, source, None)?
+                .into_iter()
+                .filter(|candidate| candidate.app == ctx.app.app_type),
+        );
+    }
+    if candidates.is_empty() {
+        ctx.app
+            .push_toast(texts::tui_toast_no_foreign_providers(), ToastKind::Info);
+        return Ok(());
+    }
+    let selected = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| candidate.duplicate_of.is_none())
+        .map(|(index, _)| index)
+        .collect();
+    ctx.app.overlay = Overlay::ProviderImportPicker {
+        candidates,
+        selected_idx: 0,
+        selected,
+    };
+    Ok(())
+}
+
+pub(super) fn import_foreign(
+    ctx: &mut RuntimeActionContext<'_>,
+    candidates: Vec<ForeignCandidate>,
+) -> Result<(), AppError> {
+    let state = load_state()?;
+    let imported = ProviderService::import_foreign(&state, candidates)?;
+
+    refresh_provider_data_after_write(ctx, &state)?;
+    ctx.app.push_toast(
+        texts::tui_toast_foreign_imported(imported.len()),
+        ToastKind::Success,
+    );
+    Ok(())
+}
+
 pub(super) fn import_live_config(ctx: &mut RuntimeActionContext<'_>) -> Result<(), AppError> {
     let state = load_state()?;
     let imported = ProviderService::import_live_config(&state, ctx.app.app_type.clone())? > 0;
@@ -135,7 +194,7 @@ fn do_switch(
         .iter()
         .find(|row| row.id == id)
         .map(|row| row.provider.clone());
-    ProviderService::switch(&state, ctx.app.app_type.clone(), &id)?;
+    ProviderService::switch_with_source(&state, ctx.app.app_type.clone(), &id, SwitchSource::Tui)?;
     if let Some(provider) = switched_provider.as_ref() {
         if let Err(err) =
             crate::claude_plugin::sync_claude_plugin_on_provider_switch(&ctx.app.app_type, provider)
@@ -0,0 +1,134 @@
+use crate::error::AppError;
+use crate::provider::UniversalProvider;
+use crate::services::UniversalProviderService;
+
+use super::super::app::ToastKind;
+use super::super::data::{load_state, UiData};
+use super::RuntimeActionContext;
+
+pub(super) fn apply(ctx: &mut RuntimeActionContext<'_>, id: String) -> Result<(), AppError> {
+    let state = load_state()?;
+    match UniversalProviderService::apply(&state, &id, &[]) {
+        Ok(apps) => {
+            let apps = apps
+                .iter()
+                .map(|app| app.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            ctx.app.push_toast(
+                format!(
+                    "{} {apps}",
+                    crate::t!("Universal provider applied to:", "统一供应商已应用到:")
+                ),
+                ToastKind::Success,
+            );
+        }
+        Err(err) => {
+            ctx.app.push_toast(err.to_string(), ToastKind::Error);
+            return Ok(());
+        }
+    }
+
+    reload(ctx)
+}
+
+pub(super) fn delete(ctx: &mut RuntimeActionContext<'_>, id: String) -> Result<(), AppError> {
+    let state = load_state()?;
+    if let Err(err) = UniversalProviderService::delete(&state, &id) {
+        ctx.app.push_toast(err.to_string(), ToastKind::Error);
+        return Ok(());
+    }
+    ctx.app.push_toast(
+        crate::t!("Universal provider deleted.", "统一供应商已删除。"),
+        ToastKind::Success,
+    );
+
+    reload(ctx)
+}
+
+pub(super) fn submit_add(
+    ctx: &mut RuntimeActionContext<'_>,
+    content: String,
+) -> Result<(), AppError> {
+    let Some(mut provider) = parse_provider(ctx, &content) else {
+        return Ok(());
+    };
+    let state = load_state()?;
+    if provider.id.trim().is_empty() {
+        provider.id = UniversalProviderService::generate_id(&state, &provider.name)?;
+    }
+    if state.db.get_universal_provider(&provider.id)?.is_some() {
+        ctx.app.push_toast(
+            crate::t!(
+                "A universal provider with this id already exists.",
+                "已存在相同 ID 的统一供应商。"
+            ),
+            ToastKind::Warning,
+        );
+        return Ok(());
+    }
+
+    save(ctx, &state, provider)
+}
+
+pub(super) fn submit_edit(
+    ctx: &mut RuntimeActionContext<'_>,
+    id: String,
+    content: String,
+) -> Result<(), AppError> {
+    let Some(provider) = parse_provider(ctx, &content) else {
+        return Ok(());
+    };
+    if provider.id != id {
+        ctx.app.push_toast(
+            crate::t!(
+                "Universal provider id cannot be changed from this editor.",
+                "不能在此编辑器中修改统一供应商 ID。"
+            ),
+            ToastKind::Warning,
+        );
+        return Ok(());
+    }
+    let state = load_state()?;
+    save(ctx, &state, provider)
+}
+
+fn parse_provider(ctx: &mut RuntimeActionContext<'_>, content: &str) -> Option<UniversalProvider> {
+    match serde_json::from_str(content) {
+        Ok(provider) => Some(provider),
+        Err(err) => {
+            ctx.app.push_toast(
+                crate::cli::i18n::texts::tui_toast_invalid_json(&err.to_string()),
+                ToastKind::Error,
+            );
+            None
+        }
+    }
+}
+
+fn save(
+    ctx: &mut RuntimeActionContext<'_>,
+    state: &crate::store::AppState,
+    provider: UniversalProvider,
+) -> Result<(), AppError> {
+    if let Err(err) = UniversalProviderService::upsert(state, provider) {
+        ctx.app.push_toast(err.to_string(), ToastKind::Error);
+        return Ok(());
+    }
+
+    ctx.app.editor = None;
+    ctx.app.push_toast(
+        crate::t!(
+            "Universal provider saved; derived providers synced.",
+            "统一供应商已保存，派生供应商已同步。"
+        ),
+        ToastKind::Success,
+    );
+    reload(ctx)
+}
+
+fn reload(ctx: &mut RuntimeActionContext<'_>) -> Result<(), AppError> {
+    *ctx.data = UiData::load(&ctx.app.app_type)?;
+    ctx.app.clamp_selections(ctx.data);
+    Ok(())
+}
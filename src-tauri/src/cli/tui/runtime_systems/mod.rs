@@ -11,15 +11,12 @@ pub(crate) use handlers::{
     handle_skills_msg, handle_speedtest_msg, handle_stream_check_msg, handle_update_msg,
     handle_webdav_msg, ProxyMsgEffect,
 };
+pub(crate) use types::build_stream_check_result_lines;
 #[cfg(test)]
 pub(crate) use types::{
-    build_model_fetch_candidate_urls, model_fetch_strategy_for_field,
-    parse_model_ids_from_response, ManagedAuthMsg, ManagedSessionOutcome, ProxyMsg, QuotaMsg,
+    model_fetch_strategy_for_field, ManagedAuthMsg, ManagedSessionOutcome, ProxyMsg, QuotaMsg,
     UpdateMsg,
 };
-pub(crate) use types::{
-    build_stream_check_result_lines, fetch_provider_models_for_tui, ModelFetchStrategy,
-};
 pub(crate) use types::{
     next_model_fetch_request_id, AppDataLoadKind, AppDataMsg, AppDataReq, CodexHistoryReq,
     LocalEnvReq, ManagedAuthReq, ModelFetchReq, ProxyReq, QuotaReq, RequestTracker, SessionReq,
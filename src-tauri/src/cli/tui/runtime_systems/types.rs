@@ -1,9 +1,5 @@
-use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc;
-use std::time::Duration;
-
-use serde_json::Value;
 
 use crate::app_config::AppType;
 use crate::cli::i18n::texts;
@@ -11,22 +7,11 @@ use crate::cli::tui::data::{
     ProviderRuntimeSnapshot, ProxySnapshot, QuotaSnapshotGeneration, QuotaTarget, UiDataReloadToken,
 };
 use crate::provider::Provider;
+use crate::services::provider::models::ModelFetchStrategy;
 use crate::services::{EndpointLatency, HealthStatus, StreamCheckResult, SyncDecision};
 
 use super::super::form::ProviderAddField;
 
-const KNOWN_COMPAT_SUFFIXES: &[&str] = &[
-    "/api/claudecode",
-    "/api/anthropic",
-    "/apps/anthropic",
-    "/api/coding",
-    "/claudecode",
-    "/anthropic",
-    "/step_plan",
-    "/coding",
-    "/claude",
-];
-
 pub(crate) fn next_model_fetch_request_id() -> u64 {
     static NEXT_MODEL_FETCH_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
     NEXT_MODEL_FETCH_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
@@ -826,13 +811,6 @@ pub(crate) struct ModelFetchSystem {
     pub(crate) _handle: std::thread::JoinHandle<()>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum ModelFetchStrategy {
-    Bearer,
-    Anthropic,
-    GoogleApiKey,
-}
-
 pub(crate) fn model_fetch_strategy_for_field(field: ProviderAddField) -> ModelFetchStrategy {
     match field {
         ProviderAddField::GeminiModel => ModelFetchStrategy::GoogleApiKey,
@@ -841,195 +819,6 @@ pub(crate) fn model_fetch_strategy_for_field(field: ProviderAddField) -> ModelFe
     }
 }
 
-pub(crate) fn build_model_fetch_candidate_urls(
-    base_url: &str,
-    strategy: ModelFetchStrategy,
-    is_full_url: bool,
-) -> Vec<String> {
-    let base = base_url.trim().trim_end_matches('/');
-    if base.is_empty() {
-        return Vec::new();
-    }
-
-    if is_full_url {
-        let mut urls = Vec::new();
-        if let Some(index) = base.find("/v1/") {
-            urls.push(format!("{}/v1/models", &base[..index]));
-        } else if let Some(index) = base.rfind('/') {
-            let root = &base[..index];
-            if root
-                .find("://")
-                .is_some_and(|scheme| root.len() > scheme.saturating_add(3))
-            {
-                urls.push(format!("{root}/v1/models"));
-            }
-        }
-        return urls;
-    }
-
-    if base.ends_with("/models") {
-        return vec![base.to_string()];
-    }
-
-    let append_models = format!("{base}/models");
-    let append_versioned_models = if base.ends_with("/v1") || base.ends_with("/v1beta") {
-        None
-    } else {
-        Some(format!("{base}/v1/models"))
-    };
-
-    let mut urls: Vec<String> = Vec::new();
-    match strategy {
-        ModelFetchStrategy::Anthropic => {
-            if let Some(versioned) = append_versioned_models.as_ref() {
-                urls.push(versioned.clone());
-            } else {
-                urls.push(append_models.clone());
-            }
-
-            if let Some(stripped) = strip_compat_suffix(base) {
-                let root = stripped.trim_end_matches('/');
-                if !root.is_empty() && root.contains("://") {
-                    urls.push(format!("{root}/v1/models"));
-                    urls.push(format!("{root}/models"));
-                }
-            } else if append_versioned_models.is_some() {
-                urls.push(append_models);
-            }
-        }
-        ModelFetchStrategy::Bearer | ModelFetchStrategy::GoogleApiKey => {
-            urls.push(append_models);
-            if let Some(v1) = append_versioned_models.as_ref() {
-                urls.push(v1.clone());
-            }
-        }
-    }
-
-    let mut seen = HashSet::new();
-    urls.retain(|url| seen.insert(url.clone()));
-    urls
-}
-
-fn strip_compat_suffix(base: &str) -> Option<&str> {
-    let lower = base.to_ascii_lowercase();
-    KNOWN_COMPAT_SUFFIXES.iter().find_map(|suffix| {
-        lower
-            .ends_with(suffix)
-            .then(|| &base[..base.len() - suffix.len()])
-    })
-}
-
-pub(crate) fn parse_model_ids_from_response(payload: &Value) -> Vec<String> {
-    let mut out: Vec<String> = Vec::new();
-
-    if let Some(data) = payload.get("data").and_then(|v| v.as_array()) {
-        for item in data {
-            if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
-                out.push(id.to_string());
-            }
-        }
-    }
-
-    if out.is_empty() {
-        if let Some(models) = payload.get("models").and_then(|v| v.as_array()) {
-            for item in models {
-                if let Some(name) = item.get("name").and_then(|v| v.as_str()) {
-                    out.push(name.strip_prefix("models/").unwrap_or(name).to_string());
-                }
-            }
-        }
-    }
-
-    if out.is_empty() {
-        if let Some(arr) = payload.as_array() {
-            for item in arr {
-                if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
-                    out.push(id.to_string());
-                }
-            }
-        }
-    }
-
-    let mut seen = HashSet::new();
-    out.retain(|model| seen.insert(model.clone()));
-    out
-}
-
-pub(crate) async fn fetch_provider_models_for_tui(
-    base_url: &str,
-    is_full_url: bool,
-    api_key: Option<&str>,
-    custom_user_agent: Option<&str>,
-    strategy: ModelFetchStrategy,
-) -> Result<Vec<String>, String> {
-    let candidate_urls = build_model_fetch_candidate_urls(base_url, strategy, is_full_url);
-    if candidate_urls.is_empty() {
-        return Err(if is_full_url && !base_url.trim().is_empty() {
-            "Cannot derive models endpoint from full URL".to_string()
-        } else {
-            "URL cannot be empty".to_string()
-        });
-    }
-
-    let client = crate::proxy::http_client::get();
-
-    let key = api_key.map(str::trim).filter(|k| !k.is_empty());
-    let custom_user_agent = crate::provider::parse_custom_user_agent(custom_user_agent)
-        .ok()
-        .flatten();
-    let mut last_err = String::from("unknown error");
-
-    for url in candidate_urls {
-        let mut req = client.get(&url).timeout(Duration::from_secs(5));
-        if let Some(key) = key {
-            req = match strategy {
-                ModelFetchStrategy::Bearer => req.header("Authorization", format!("Bearer {key}")),
-                ModelFetchStrategy::Anthropic => req
-                    .header("Authorization", format!("Bearer {key}"))
-                    .header("x-api-key", key)
-                    .header("anthropic-version", "2023-06-01"),
-                ModelFetchStrategy::GoogleApiKey => req.header("x-goog-api-key", key),
-            };
-        }
-        if let Some(user_agent) = &custom_user_agent {
-            req = req.header(reqwest::header::USER_AGENT, user_agent.clone());
-        }
-
-        match req.send().await {
-            Ok(resp) => {
-                let status = resp.status();
-                if !status.is_success() {
-                    last_err = format!("HTTP {status} ({url})");
-                    if status != reqwest::StatusCode::NOT_FOUND
-                        && status != reqwest::StatusCode::METHOD_NOT_ALLOWED
-                    {
-                        return Err(last_err);
-                    }
-                    continue;
-                }
-                match resp.json::<Value>().await {
-                    Ok(payload) => {
-                        let models = parse_model_ids_from_response(&payload);
-                        if models.is_empty() {
-                            last_err = format!("No model list found in response ({url})");
-                        } else {
-                            return Ok(models);
-                        }
-                    }
-                    Err(err) => {
-                        last_err = format!("Invalid JSON response ({url}): {err}");
-                    }
-                }
-            }
-            Err(err) => {
-                last_err = err.to_string();
-            }
-        }
-    }
-
-    Err(last_err)
-}
-
 #[derive(Debug, Default, Clone, Copy)]
 pub(crate) struct RequestTracker {
     pub(crate) seq: u64,
@@ -17,17 +17,19 @@ use super::super::data::{
     load_snapshot_state, load_state, load_usage_pricing_data_from_state_for_range, UiData,
     UsageRangePreset,
 };
+use crate::services::provider::models::fetch_model_ids;
+
 use super::types::{
-    fetch_provider_models_for_tui, model_fetch_strategy_for_field, AppDataLoadKind, AppDataMsg,
-    AppDataReq, AppDataSystem, CodexHistoryMsg, CodexHistoryReq, CodexHistorySystem,
-    LoadedMessagePage, LocalEnvMsg, LocalEnvReq, LocalEnvSystem, ManagedAuthMsg, ManagedAuthReq,
-    ManagedAuthSystem, ManagedSessionOutcome, ModelFetchMsg, ModelFetchReq, ModelFetchSystem,
-    ProxyMsg, ProxyReq, ProxySystem, QuotaMsg, QuotaReq, QuotaSystem, RefreshedMessagePages,
-    SessionMsg, SessionReq, SessionSystem, SessionUsageSyncMsg, SessionUsageSyncReq,
-    SessionUsageSyncSystem, SkillsMsg, SkillsReq, SkillsSystem, SpeedtestMsg, SpeedtestSystem,
-    StreamCheckMsg, StreamCheckReq, StreamCheckSystem, UpdateMsg, UpdateReq, UpdateSystem,
-    UsageLogLoadError, UsagePricingLoadError, UsagePricingMsg, UsagePricingReq, UsagePricingSystem,
-    WebDavDone, WebDavErr, WebDavMsg, WebDavReq, WebDavReqKind, WebDavSystem,
+    model_fetch_strategy_for_field, AppDataLoadKind, AppDataMsg, AppDataReq, AppDataSystem,
+    CodexHistoryMsg, CodexHistoryReq, CodexHistorySystem, LoadedMessagePage, LocalEnvMsg,
+    LocalEnvReq, LocalEnvSystem, ManagedAuthMsg, ManagedAuthReq, ManagedAuthSystem,
+    ManagedSessionOutcome, ModelFetchMsg, ModelFetchReq, ModelFetchSystem, ProxyMsg, ProxyReq,
+    ProxySystem, QuotaMsg, QuotaReq, QuotaSystem, RefreshedMessagePages, SessionMsg, SessionReq,
+    SessionSystem, SessionUsageSyncMsg, SessionUsageSyncReq, SessionUsageSyncSystem, SkillsMsg,
+    SkillsReq, SkillsSystem, SpeedtestMsg, SpeedtestSystem, StreamCheckMsg, StreamCheckReq,
+    StreamCheckSystem, UpdateMsg, UpdateReq, UpdateSystem, UsageLogLoadError,
+    UsagePricingLoadError, UsagePricingMsg, UsagePricingReq, UsagePricingSystem, WebDavDone,
+    WebDavErr, WebDavMsg, WebDavReq, WebDavReqKind, WebDavSystem,
 };
 
 static SESSION_SCAN_GENERATION: AtomicU64 = AtomicU64::new(0);
@@ -646,7 +648,7 @@ fn model_fetch_worker_loop(rx: mpsc::Receiver<ModelFetchReq>, tx: mpsc::Sender<M
         } else {
             let strategy = model_fetch_strategy_for_field(field);
             rt.block_on(async {
-                fetch_provider_models_for_tui(
+                fetch_model_ids(
                     &base_url,
                     is_full_url,
                     api_key.as_deref(),
@@ -2413,7 +2415,14 @@ fn quota_worker_loop(rx: mpsc::Receiver<QuotaReq>, tx: mpsc::Sender<QuotaMsg>) {
 
     while let Ok(req) = rx.recv() {
         let QuotaReq::Refresh { generation, target } = req;
+        let queried_at = chrono::Utc::now().timestamp_millis();
         let result = rt.block_on(crate::cli::provider_quota::query_quota(&target));
+        crate::services::quota_history::record_quota_result_best_effort(
+            &target,
+            crate::services::quota_history::QuotaSource::Tui,
+            queried_at,
+            &result,
+        );
 
         let _ = tx.send(QuotaMsg::Finished {
             generation,
@@ -14,6 +14,7 @@ use super::data::UiData;
 use super::form::ProviderAddField;
 use super::*;
 use crate::cli::i18n::texts;
+use crate::services::provider::models::ModelFetchStrategy;
 use crate::test_support::{
     lock_test_home_and_settings, set_test_home_override, TestHomeSettingsLock,
 };
@@ -5256,142 +5257,6 @@ fn model_fetch_strategy_matches_provider_field() {
     );
 }
 
-#[test]
-fn model_fetch_candidate_urls_prefers_v1_for_anthropic_base() {
-    let urls = build_model_fetch_candidate_urls(
-        "https://api.anthropic.com",
-        ModelFetchStrategy::Anthropic,
-        false,
-    );
-    assert_eq!(
-        urls,
-        vec![
-            "https://api.anthropic.com/v1/models".to_string(),
-            "https://api.anthropic.com/models".to_string()
-        ]
-    );
-}
-
-#[test]
-fn model_fetch_candidate_urls_strip_anthropic_compat_suffix() {
-    let urls = build_model_fetch_candidate_urls(
-        "https://api.deepseek.com/anthropic",
-        ModelFetchStrategy::Anthropic,
-        false,
-    );
-    assert_eq!(
-        urls,
-        vec![
-            "https://api.deepseek.com/anthropic/v1/models".to_string(),
-            "https://api.deepseek.com/v1/models".to_string(),
-            "https://api.deepseek.com/models".to_string(),
-        ]
-    );
-}
-
-#[test]
-fn model_fetch_candidate_urls_for_gemini_v1beta_keeps_models_endpoint() {
-    let urls = build_model_fetch_candidate_urls(
-        "https://generativelanguage.googleapis.com/v1beta",
-        ModelFetchStrategy::GoogleApiKey,
-        false,
-    );
-    assert_eq!(
-        urls,
-        vec!["https://generativelanguage.googleapis.com/v1beta/models".to_string()]
-    );
-}
-
-#[test]
-fn model_fetch_candidate_urls_derive_models_endpoint_from_full_url() {
-    assert_eq!(
-        build_model_fetch_candidate_urls(
-            "https://relay.example/v1/chat/completions?api-version=2026-01-01",
-            ModelFetchStrategy::Bearer,
-            true,
-        ),
-        vec!["https://relay.example/v1/models".to_string()]
-    );
-    assert_eq!(
-        build_model_fetch_candidate_urls(
-            "https://relay.example/custom/chat/completions",
-            ModelFetchStrategy::Anthropic,
-            true,
-        ),
-        vec!["https://relay.example/custom/chat/v1/models".to_string()]
-    );
-}
-
-#[tokio::test]
-async fn model_fetch_full_url_reports_when_models_endpoint_cannot_be_derived() {
-    let error = fetch_provider_models_for_tui(
-        "https://relay.example",
-        true,
-        None,
-        None,
-        ModelFetchStrategy::Bearer,
-    )
-    .await
-    .expect_err("origin-only full URL should not invent a models endpoint");
-
-    assert_eq!(error, "Cannot derive models endpoint from full URL");
-}
-
-#[tokio::test]
-async fn model_fetch_sends_trimmed_custom_user_agent() {
-    use std::sync::{Arc, Mutex};
-
-    use axum::{http::HeaderMap, routing::get, Router};
-
-    let observed_user_agent = Arc::new(Mutex::new(None::<String>));
-    let handler_user_agent = Arc::clone(&observed_user_agent);
-    let app = Router::new().route(
-        "/v1/models",
-        get(move |headers: HeaderMap| {
-            let observed_user_agent = Arc::clone(&handler_user_agent);
-            async move {
-                let user_agent = headers
-                    .get(reqwest::header::USER_AGENT)
-                    .and_then(|value| value.to_str().ok())
-                    .map(str::to_string);
-                *observed_user_agent
-                    .lock()
-                    .expect("capture model fetch user agent") = user_agent;
-                axum::Json(json!({ "data": [{ "id": "model-a" }] }))
-            }
-        }),
-    );
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
-        .await
-        .expect("bind model fetch test server");
-    let address = listener.local_addr().expect("model fetch listener address");
-    let server = tokio::spawn(async move {
-        axum::serve(listener, app)
-            .await
-            .expect("model fetch test server should run");
-    });
-
-    let models = fetch_provider_models_for_tui(
-        &format!("http://{address}"),
-        false,
-        Some("sk-test"),
-        Some("  cc-switch-model-fetch/test  "),
-        ModelFetchStrategy::Bearer,
-    )
-    .await
-    .expect("model fetch should succeed");
-    server.abort();
-
-    assert_eq!(models, vec!["model-a"]);
-    assert_eq!(
-        observed_user_agent
-            .lock()
-            .expect("read model fetch user agent")
-            .as_deref(),
-        Some("cc-switch-model-fetch/test")
-    );
-}
-
 #[test]
 #[serial(home_settings)]
 fn startup_hidden_requested_app_bootstrap_uses_visible_app_normalization_before_loading_data() {
@@ -5432,33 +5297,6 @@ fn startup_reads_persisted_common_config_notice_confirmation() {
     assert!(app.common_config_notice_confirmed);
 }
 
-#[test]
-fn parse_model_ids_supports_multiple_shapes_and_dedups_stably() {
-    let data_payload = json!({
-        "data": [
-            {"id": "gpt-4o"},
-            {"id": "gpt-4o-mini"},
-            {"id": "gpt-4o"},
-            {"id": "o3"}
-        ]
-    });
-    assert_eq!(
-        parse_model_ids_from_response(&data_payload),
-        vec!["gpt-4o", "gpt-4o-mini", "o3"]
-    );
-
-    let gemini_payload = json!({
-        "models": [
-            {"name": "models/gemini-2.0-pro"},
-            {"name": "models/gemini-2.0-flash"}
-        ]
-    });
-    assert_eq!(
-        parse_model_ids_from_response(&gemini_payload),
-        vec!["gemini-2.0-pro", "gemini-2.0-flash"]
-    );
-}
-
 #[test]
 fn home_route_queues_the_fixed_usage_aggregate() {
     let mut app = App::new(Some(AppType::Claude));
@@ -49,6 +49,7 @@ mod proxy_wave;
 mod sessions;
 mod shared;
 mod skills;
+mod universal;
 mod usage;
 
 #[cfg(test)]
@@ -72,6 +73,7 @@ use proxy_wave::*;
 use sessions::*;
 use shared::*;
 use skills::*;
+use universal::*;
 use usage::*;
 
 pub fn render(frame: &mut Frame<'_>, app: &App, data: &UiData) {
@@ -160,6 +162,9 @@ fn render_content(
     match &app.route {
         Route::Main => render_main(frame, app, data, content_area, theme),
         Route::Providers => render_providers(frame, app, data, content_area, theme),
+        Route::UniversalProviders => {
+            render_universal_providers(frame, app, data, content_area, theme)
+        }
         Route::Usage => render_usage(frame, app, data, content_area, theme),
         Route::UsageLogs => render_usage_logs(frame, app, data, content_area, theme),
         Route::UsageLogDetail { rowid } => {
@@ -51,6 +51,10 @@ pub(super) fn render_confirm_overlay(
             ("Esc", texts::tui_key_cancel()),
         ],
         ConfirmAction::ProviderApiFormatProxyNotice => &[("Enter", texts::tui_key_close())],
+        ConfirmAction::ProviderLintSaveAnyway => &[
+            ("Enter", texts::tui_key_save_anyway()),
+            ("Esc", texts::tui_key_cancel()),
+        ],
         ConfirmAction::VisibleAppsAutoDetection => &[
             ("Enter", texts::tui_key_use_auto()),
             ("Esc", texts::tui_key_keep_current()),
@@ -265,6 +269,51 @@ pub(super) fn render_backup_picker_overlay(
     frame.render_stateful_widget(list, body, &mut state);
 }
 
+pub(super) fn render_switch_history_overlay(
+    frame: &mut Frame<'_>,
+    content_area: Rect,
+    theme: &theme::Theme,
+    selected: usize,
+    entries: &[crate::local_store::switch_history::ProviderSwitchRecord],
+) {
+    let body = overlay_frame(
+        frame,
+        content_area,
+        theme,
+        texts::tui_switch_history_title(),
+        &[
+            ("Enter", texts::tui_key_switch_back()),
+            ("Esc", texts::tui_key_cancel()),
+        ],
+        OverlaySize::Percent(OVERLAY_LG.0, OVERLAY_LG.1),
+        overlay_border_style(theme, false),
+    );
+
+    let items = entries.iter().map(|entry| {
+        let when = chrono::DateTime::from_timestamp(entry.switched_at, 0)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        ListItem::new(Line::from(Span::raw(format!(
+            "{when}  {} → {}  ({})",
+            entry.from_id.as_deref().unwrap_or("-"),
+            entry.to_id,
+            entry.source
+        ))))
+    });
+
+    let list = List::new(items)
+        .highlight_style(selection_style(theme))
+        .highlight_symbol(highlight_symbol(theme));
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+    frame.render_stateful_widget(list, body, &mut state);
+}
+
 pub(super) fn render_text_view_overlay(
     frame: &mut Frame<'_>,
     content_area: Rect,
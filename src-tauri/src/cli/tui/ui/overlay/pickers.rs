@@ -2365,6 +2365,89 @@ pub(super) fn render_skills_import_picker_overlay(
     frame.render_stateful_widget(table, body_area, &mut state);
 }
 
+pub(super) fn render_provider_import_picker_overlay(
+    frame: &mut Frame<'_>,
+    content_area: Rect,
+    theme: &theme::Theme,
+    candidates: &[crate::services::ForeignCandidate],
+    selected_idx: usize,
+    selected: &std::collections::HashSet<usize>,
+) {
+    let body = overlay_frame_at(
+        frame,
+        centered_rect_fixed(OVERLAY_FIXED_LG.0, OVERLAY_FIXED_LG.1, content_area),
+        theme,
+        texts::tui_provider_import_title(),
+        &[
+            ("Space", texts::tui_key_toggle()),
+            ("Enter", texts::tui_key_import()),
+            ("r", texts::tui_key_refresh()),
+            ("Esc", texts::tui_key_close()),
+        ],
+        overlay_border_style(theme, true),
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(body);
+
+    frame.render_widget(
+        Paragraph::new(texts::tui_provider_import_description())
+            .style(Style::default().fg(theme.dim))
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from(texts::header_name()),
+        Cell::from(texts::tui_header_source()),
+        Cell::from(texts::tui_header_api_url()),
+        Cell::from(texts::tui_header_api_format()),
+        Cell::from(texts::tui_header_local()),
+    ])
+    .style(Style::default().fg(theme.dim).add_modifier(Modifier::BOLD));
+
+    let rows = candidates.iter().enumerate().map(|(index, candidate)| {
+        Row::new(vec![
+            Cell::from(if selected.contains(&index) {
+                texts::tui_marker_active()
+            } else {
+                texts::tui_marker_inactive()
+            }),
+            Cell::from(candidate.provider.name.clone()),
+            Cell::from(candidate.source.label()),
+            Cell::from(candidate.base_url.clone()),
+            Cell::from(candidate.api_format.clone()),
+            Cell::from(match &candidate.duplicate_of {
+                Some(id) => texts::tui_provider_import_same_url(id),
+                None => texts::tui_provider_import_new().to_string(),
+            }),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Percentage(22),
+            Constraint::Percentage(16),
+            Constraint::Percentage(32),
+            Constraint::Percentage(14),
+            Constraint::Percentage(16),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::NONE))
+    .row_highlight_style(selection_style(theme))
+    .highlight_symbol(highlight_symbol(theme));
+
+    let mut state = TableState::default();
+    state.select(Some(selected_idx));
+    frame.render_stateful_widget(table, chunks[1], &mut state);
+}
+
 pub(super) fn render_skills_sync_method_picker_overlay(
     frame: &mut Frame<'_>,
     data: &UiData,
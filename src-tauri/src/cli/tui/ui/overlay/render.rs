@@ -24,6 +24,15 @@ pub(crate) fn render_overlay(
         Overlay::BackupPicker { selected } => {
             super::basic::render_backup_picker_overlay(frame, data, content_area, theme, *selected)
         }
+        Overlay::SwitchHistoryPicker { selected, entries } => {
+            super::basic::render_switch_history_overlay(
+                frame,
+                content_area,
+                theme,
+                *selected,
+                entries,
+            )
+        }
         Overlay::TextView(view) => super::basic::render_text_view_overlay(
             frame,
             content_area,
@@ -254,6 +263,18 @@ pub(crate) fn render_overlay(
             *selected_idx,
             selected,
         ),
+        Overlay::ProviderImportPicker {
+            candidates,
+            selected_idx,
+            selected,
+        } => super::pickers::render_provider_import_picker_overlay(
+            frame,
+            content_area,
+            theme,
+            candidates,
+            *selected_idx,
+            selected,
+        ),
         Overlay::SkillsSyncMethodPicker { selected } => {
             super::pickers::render_skills_sync_method_picker_overlay(
                 frame,
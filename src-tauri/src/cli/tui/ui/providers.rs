@@ -102,6 +102,53 @@ fn provider_name_with_quota_line(
             provider_proxy_badge_style(badge, theme),
         ));
     }
+    if let Some(pool) = data.proxy.api_key_pools.get(&row.id) {
+        let style = if pool.usable == 0 {
+            Style::default().fg(theme.err)
+        } else if pool.usable < pool.total {
+            Style::default().fg(theme.warn)
+        } else {
+            Style::default().fg(theme.comment)
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            crate::t!(
+                format!("[keys {}/{}]", pool.usable, pool.total),
+                format!("[Key {}/{}]", pool.usable, pool.total)
+            ),
+            style,
+        ));
+    }
+    if let Some(missing) = data.providers.missing_models.get(&row.id) {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            crate::t!(
+                format!("[model gone: {}]", missing.join(", ")),
+                format!("[模型已下线: {}]", missing.join(", "))
+            ),
+            Style::default().fg(theme.warn),
+        ));
+    }
+    if let Some(probes) = data.proxy.probe_history.get(&row.id) {
+        let style = if probes.uptime_pct >= 99.0 {
+            Style::default().fg(theme.comment)
+        } else if probes.uptime_pct >= 90.0 {
+            Style::default().fg(theme.warn)
+        } else {
+            Style::default().fg(theme.err)
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(format!("{:.0}%", probes.uptime_pct), style));
+        if probes.latencies.len() > 1 {
+            spans.push(Span::styled(
+                format!(
+                    " {}",
+                    crate::cli::provider_quota::quota_sparkline(&probes.latencies, 8)
+                ),
+                Style::default().fg(theme.comment),
+            ));
+        }
+    }
     if show_quota {
         if let Some(quota) = quota_compact_line(data.quota.state_for(&row.id), theme, true) {
             spans.push(Span::styled("  (", Style::default().fg(theme.comment)));
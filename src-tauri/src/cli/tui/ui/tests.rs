@@ -1538,6 +1538,83 @@ fn tui_usage_details_tables_follow_selected_range() {
     assert!(!today.contains("Week Provider"), "{today}");
 }
 
+#[test]
+fn tui_usage_projects_pane_lists_attributed_projects() {
+    let _lang = use_test_language(Language::English);
+
+    let mut app = App::new(Some(AppType::Claude));
+    app.route = Route::UsageLogs;
+    app.focus = Focus::Content;
+    app.usage.pane = UsagePane::Projects;
+    app.usage.range = UsageRangePreset::SevenDays;
+
+    let mut data = minimal_data(&app.app_type);
+    data.usage = UsageSnapshot {
+        top_projects_7d: vec![
+            crate::cli::tui::data::UsageProjectStatsRow {
+                project_key: "posix:/work/alpha".to_string(),
+                display_path: Some("/work/alpha".to_string()),
+                request_count: 5,
+                success_count: 5,
+                input_tokens: 400,
+                output_tokens: 100,
+                total_cost_usd: 1.5,
+                ..Default::default()
+            },
+            crate::cli::tui::data::UsageProjectStatsRow {
+                request_count: 1,
+                success_count: 0,
+                ..Default::default()
+            },
+        ],
+        ..UsageSnapshot::default()
+    };
+
+    let all = all_text(&render_with_size(&app, &data, 160, 40));
+    assert!(all.contains("Project Stats"), "{all}");
+    assert!(all.contains("/work/alpha"), "{all}");
+    assert!(all.contains("(unknown project)"), "{all}");
+    assert!(all.contains("project stats · 2 rows"), "{all}");
+}
+
+#[test]
+fn tui_usage_balances_pane_shows_burn_rate_and_days_until_empty() {
+    let _lang = use_test_language(Language::English);
+
+    let mut app = App::new(Some(AppType::Claude));
+    app.route = Route::UsageLogs;
+    app.focus = Focus::Content;
+    app.usage.pane = UsagePane::Balances;
+
+    let mut data = minimal_data(&app.app_type);
+    data.usage = UsageSnapshot {
+        balances: vec![crate::cli::tui::data::UsageBalanceRow {
+            provider_name: "Relay Hub".to_string(),
+            trend: crate::services::quota_history::QuotaTrend {
+                app_type: "claude".to_string(),
+                provider_id: "relay".to_string(),
+                tier: "balance".to_string(),
+                unit: Some("USD".to_string()),
+                latest_remaining: 46.0,
+                latest_total: None,
+                latest_at: 3,
+                points: vec![(1, 50.0), (2, 48.0), (3, 46.0)],
+                burn_per_day: Some(4.0),
+                days_until_empty: Some(11.5),
+            },
+        }],
+        ..UsageSnapshot::default()
+    };
+
+    let all = all_text(&render_with_size(&app, &data, 160, 40));
+    assert!(all.contains("Balances (30d)"), "{all}");
+    assert!(all.contains("Relay Hub"), "{all}");
+    assert!(all.contains("46 USD"), "{all}");
+    assert!(all.contains("4 USD"), "{all}");
+    assert!(all.contains("11.5 days"), "{all}");
+    assert!(all.contains("balances · last 30 days · 1 rows"), "{all}");
+}
+
 #[test]
 fn tui_usage_narrow_width_renders_without_losing_primary_sections() {
     let _lang = use_test_language(Language::English);
@@ -3964,6 +4041,7 @@ pub(super) fn minimal_data(_app_type: &AppType) -> UiData {
         providers: ProvidersSnapshot {
             current_id: "p0".to_string(),
             live_ids: Default::default(),
+            missing_models: Default::default(),
             rows: vec![ProviderRow {
                 id: "p1".to_string(),
                 provider,
@@ -3984,6 +4062,7 @@ pub(super) fn minimal_data(_app_type: &AppType) -> UiData {
         proxy: ProxySnapshot::default(),
         usage: UsageSnapshot::default(),
         pricing: Default::default(),
+        universal: Default::default(),
         quota: Default::default(),
         reload_token: Default::default(),
     }
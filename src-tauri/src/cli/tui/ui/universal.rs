@@ -0,0 +1,120 @@
+use super::*;
+
+pub(super) fn render_universal_providers(
+    frame: &mut Frame<'_>,
+    app: &App,
+    data: &UiData,
+    area: Rect,
+    theme: &super::theme::Theme,
+) {
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain)
+        .border_style(pane_border_style(app, Focus::Content, theme))
+        .title(breadcrumb_title(&[
+            universal_text("Providers", "供应商"),
+            universal_text("Universal Providers", "统一供应商"),
+        ]));
+    frame.render_widget(outer.clone(), area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(outer.inner(area));
+
+    render_page_key_bar(
+        frame,
+        chunks[0],
+        theme,
+        &[
+            ("↑↓", texts::tui_key_select()),
+            ("a", texts::tui_key_add()),
+            ("Enter", texts::tui_key_edit()),
+            ("Space", texts::tui_key_switch()),
+            ("d", texts::tui_key_delete()),
+            ("/", texts::tui_filter_title()),
+            ("Esc", texts::tui_key_close()),
+        ],
+        app.focus == Focus::Content,
+    );
+
+    let area = chunks[1];
+    let rows = app::visible_universal_rows(&app.filter, data);
+    if rows.is_empty() {
+        let line_count = 2u16;
+        let y = area.y + area.height.saturating_sub(line_count) / 2;
+        let centered = Rect::new(area.x, y, area.width, line_count.min(area.height));
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::styled(
+                    universal_text("No universal providers yet", "暂无统一供应商"),
+                    Style::default().fg(theme.comment),
+                ),
+                Line::styled(
+                    universal_text(
+                        "Press a to add one relay shared by every app",
+                        "按 a 新增一个供所有应用共享的中转站",
+                    ),
+                    Style::default().fg(theme.dim),
+                ),
+            ])
+            .alignment(Alignment::Center),
+            centered,
+        );
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(universal_text("ID", "ID")),
+        Cell::from(universal_text("Name", "名称")),
+        Cell::from(universal_text("Base URL", "地址")),
+        Cell::from(universal_text("Apps", "应用")),
+    ])
+    .style(Style::default().fg(theme.dim).add_modifier(Modifier::BOLD));
+
+    let table_rows = rows.iter().map(|row| {
+        let apps = row
+            .provider
+            .apps
+            .enabled_apps()
+            .iter()
+            .map(|app_type| {
+                if row.current_apps.contains(app_type) {
+                    format!("{}✓", app_type.as_str())
+                } else {
+                    app_type.as_str().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Row::new(vec![
+            Cell::from(row.provider.id.clone()),
+            Cell::from(row.provider.name.clone()),
+            Cell::from(row.provider.base_url.clone()),
+            Cell::from(apps),
+        ])
+    });
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Percentage(18),
+            Constraint::Percentage(20),
+            Constraint::Percentage(30),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(selection_style(theme))
+    .highlight_symbol(highlight_symbol(theme));
+    let mut state = TableState::default();
+    state.select(Some(app.universal.selected_idx));
+    frame.render_stateful_widget(table, inset_left(area, CONTENT_INSET_LEFT), &mut state);
+}
+
+fn universal_text(en: &'static str, zh: &'static str) -> &'static str {
+    if i18n::is_chinese() {
+        zh
+    } else {
+        en
+    }
+}
@@ -1,7 +1,8 @@
+use crate::cli::provider_quota::{days_until_empty_label, quota_sparkline, usage_number};
 use crate::cli::tui::app::{UsageMetric, UsagePane};
 use crate::cli::tui::data::{
-    UsageLogRow, UsageLogTextField, UsageModelStatsRow, UsageProviderStatsRow,
-    UsageSummarySnapshot, UsageTrendBucket,
+    UsageBalanceRow, UsageLogRow, UsageLogTextField, UsageModelStatsRow, UsageProjectStatsRow,
+    UsageProviderStatsRow, UsageSummarySnapshot, UsageTrendBucket,
 };
 
 use super::*;
@@ -804,6 +805,8 @@ fn render_usage_detail_tabs(
             UsagePane::Providers,
             usage_text("Provider Stats", "Provider 统计"),
         ),
+        (UsagePane::Projects, usage_text("Project Stats", "项目统计")),
+        (UsagePane::Balances, usage_text("Balances", "余额趋势")),
         (UsagePane::Recent, usage_text("Request Logs", "请求日志")),
     ];
     let mut spans = Vec::new();
@@ -862,6 +865,17 @@ fn render_usage_detail_table(
             theme,
             loading,
         ),
+        UsagePane::Projects => render_usage_projects_table(
+            frame,
+            app,
+            data.usage.top_projects_for(app.usage.range),
+            inner,
+            theme,
+            loading,
+        ),
+        UsagePane::Balances => {
+            render_usage_balances_table(frame, app, &data.usage.balances, inner, theme, loading)
+        }
         UsagePane::Recent => render_usage_logs_table(frame, app, data, inner, theme),
     }
 }
@@ -922,6 +936,124 @@ fn render_usage_providers_table(
     frame.render_stateful_widget(table, area, &mut state);
 }
 
+fn render_usage_projects_table(
+    frame: &mut Frame<'_>,
+    app: &App,
+    rows: &[UsageProjectStatsRow],
+    area: Rect,
+    theme: &super::theme::Theme,
+    loading: bool,
+) {
+    if rows.is_empty() {
+        render_empty_table(frame, area, theme, loading);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(usage_text("Project", "项目")),
+        Cell::from(usage_text("Req", "请求")),
+        Cell::from(usage_text("Success", "成功")),
+        Cell::from(usage_text("Tokens", "Token")),
+        Cell::from(usage_text("Cost", "费用")),
+    ])
+    .style(Style::default().fg(theme.dim).add_modifier(Modifier::BOLD));
+    let table_rows = rows.iter().map(|row| {
+        let project = if row.is_unknown() {
+            usage_text("(unknown project)", "（未知项目）").to_string()
+        } else {
+            row.display_path
+                .clone()
+                .unwrap_or_else(|| row.project_key.clone())
+        };
+        Row::new(vec![
+            Cell::from(project),
+            Cell::from(row.request_count.to_string()),
+            Cell::from(format_success_rate(row.success_count, row.request_count)),
+            Cell::from(format_token_compact(row.total_tokens())),
+            Cell::from(format_money(row.total_cost_usd)),
+        ])
+    });
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Min(24),
+            Constraint::Length(5),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(selection_style(theme))
+    .highlight_symbol(highlight_symbol(theme));
+    let mut state = TableState::default();
+    state.select(Some(app.usage.selected_idx));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn render_usage_balances_table(
+    frame: &mut Frame<'_>,
+    app: &App,
+    rows: &[UsageBalanceRow],
+    area: Rect,
+    theme: &super::theme::Theme,
+    loading: bool,
+) {
+    if rows.is_empty() {
+        render_empty_table(frame, area, theme, loading);
+        return;
+    }
+
+    let header = Row::new(vec![
+        Cell::from(usage_text("Provider", "供应商")),
+        Cell::from(usage_text("Tier", "档位")),
+        Cell::from(usage_text("Remaining", "剩余")),
+        Cell::from(usage_text("Trend", "趋势")),
+        Cell::from(usage_text("Burn/day", "日消耗")),
+        Cell::from(usage_text("Empty in", "预计耗尽")),
+    ])
+    .style(Style::default().fg(theme.dim).add_modifier(Modifier::BOLD));
+    let table_rows = rows.iter().map(|row| {
+        let trend = &row.trend;
+        let unit = trend.unit.as_deref().unwrap_or("");
+        let with_unit = |value: f64| format!("{} {unit}", usage_number(value)).trim().to_string();
+        Row::new(vec![
+            Cell::from(row.provider_name.clone()),
+            Cell::from(if trend.tier.is_empty() {
+                "-".to_string()
+            } else {
+                trend.tier.clone()
+            }),
+            Cell::from(with_unit(trend.latest_remaining)),
+            Cell::from(quota_sparkline(&trend.points, 16)),
+            Cell::from(
+                trend
+                    .burn_per_day
+                    .map(with_unit)
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Cell::from(days_until_empty_label(trend.days_until_empty)),
+        ])
+    });
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Min(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(16),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(selection_style(theme))
+    .highlight_symbol(highlight_symbol(theme));
+    let mut state = TableState::default();
+    state.select(Some(app.usage.selected_idx));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
 fn render_usage_models_table(
     frame: &mut Frame<'_>,
     app: &App,
@@ -1332,6 +1464,8 @@ fn usage_detail_pane_title(pane: UsagePane) -> &'static str {
     match pane {
         UsagePane::Models => usage_text("Model Stats", "模型统计"),
         UsagePane::Providers => usage_text("Provider Stats", "Provider 统计"),
+        UsagePane::Projects => usage_text("Project Stats", "项目统计"),
+        UsagePane::Balances => usage_text("Balances (30d)", "余额趋势（30 天）"),
         UsagePane::Recent => usage_text("Request Logs", "请求日志"),
     }
 }
@@ -1465,6 +1599,26 @@ fn usage_detail_summary_line(app: &App, data: &UiData) -> String {
                 )
             }
         }
+        UsagePane::Projects => {
+            let count = data.usage.top_projects_for(app.usage.range).len();
+            if i18n::is_chinese() {
+                format!("{} · 项目统计 · {} 条", app.usage.range.label(), count)
+            } else {
+                format!(
+                    "{} · project stats · {} rows",
+                    app.usage.range.label(),
+                    count
+                )
+            }
+        }
+        UsagePane::Balances => {
+            let count = data.usage.balances.len();
+            if i18n::is_chinese() {
+                format!("余额趋势 · 近 30 天 · {} 条", count)
+            } else {
+                format!("balances · last 30 days · {} rows", count)
+            }
+        }
         UsagePane::Recent => {
             let logs = app
                 .usage
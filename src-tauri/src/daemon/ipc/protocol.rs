@@ -39,6 +39,23 @@ pub enum Request {
     /// Foreground asks the daemon and active workers to reload the persisted
     /// global outbound proxy. The URL itself never crosses IPC.
     ReloadOutboundProxy,
+    /// Foreground hands the daemon the vault data key (base64) to hold for
+    /// this session, optionally for `ttl_secs` only.
+    VaultUnlock {
+        key: String,
+        #[serde(default)]
+        ttl_secs: Option<u64>,
+    },
+    /// Foreground asks the daemon to forget the vault data key.
+    VaultLock,
+    /// Any local process asks which vault key the daemon holds, if any.
+    VaultStatus,
+    /// Any local process asks the daemon to seal `plaintext` with the vault
+    /// key it holds. The key itself is never sent back.
+    VaultSeal { plaintext: String },
+    /// Any local process asks the daemon to open a sealed value (the part
+    /// after `vault:`).
+    VaultOpen { payload: String },
     /// Force the daemon to stop the worker (if any) and exit.
     Shutdown,
 }
@@ -69,6 +86,14 @@ pub enum Response {
     Error {
         message: String,
     },
+    /// Id of the vault key the daemon holds, or `None` while it is locked.
+    VaultStatus {
+        key_id: Option<String>,
+    },
+    /// A sealed or opened value, or `None` while the vault is locked.
+    VaultValue {
+        value: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -188,6 +213,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn vault_requests_roundtrip() {
+        roundtrip_request(Request::VaultUnlock {
+            key: "a2V5".to_string(),
+            ttl_secs: Some(3600),
+        });
+        roundtrip_request(Request::VaultLock);
+        roundtrip_request(Request::VaultStatus);
+        roundtrip_request(Request::VaultSeal {
+            plaintext: "sk-test".to_string(),
+        });
+        roundtrip_request(Request::VaultOpen {
+            payload: "v1:abc".to_string(),
+        });
+        roundtrip_response(Response::VaultStatus { key_id: None });
+        roundtrip_response(Response::VaultValue {
+            value: Some("vault:v1:abc".to_string()),
+        });
+    }
+
     #[test]
     fn shutdown_request_roundtrips() {
         roundtrip_request(Request::Shutdown);
@@ -125,6 +125,13 @@ pub async fn run(binary_path: PathBuf, pidfile: &PidFile) -> Result<(), String>
     crate::services::global_proxy::initialize_http_client(&db);
     crate::services::session_usage::spawn_periodic_session_usage_sync(db.clone(), "daemon");
     Database::spawn_periodic_usage_maintenance(db.clone(), "daemon");
+    crate::services::usage_alerts::spawn_usage_alert_monitor(db.clone(), "daemon");
+    crate::services::quota_history::spawn_periodic_quota_polling(db.clone(), "daemon");
+    crate::services::health_monitor::spawn_health_monitor(db.clone(), "daemon");
+    crate::services::model_watch::spawn_model_watch(db.clone(), "daemon");
+    crate::services::switch_rules::spawn_switch_rules(db.clone(), "daemon");
+    crate::services::otel_export::spawn_otel_metrics_exporter(db.clone(), "daemon");
+    crate::vault::mark_agent_process();
     let supervisor = Supervisor::new(db, socket_path.clone(), binary_path);
 
     if let Err(err) = supervisor.recover_on_startup().await {
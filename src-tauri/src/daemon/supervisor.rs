@@ -428,7 +428,7 @@ impl Supervisor {
             }
         }
         if plan.should_shutdown && plan.pids.is_empty() {
-            self.shutdown_notify.notify_waiters();
+            self.exit_when_idle().await;
         }
     }
 
@@ -641,7 +641,7 @@ impl Supervisor {
         if has_spawned_worker {
             tokio::time::sleep(Duration::from_millis(100)).await;
         } else if stop_plan.should_shutdown {
-            self.shutdown_notify.notify_waiters();
+            self.exit_when_idle().await;
         }
         if let Some(message) = global_disable_error {
             return Response::Error { message };
@@ -767,7 +767,7 @@ impl Supervisor {
             // No worker to drain — signal shutdown directly so the daemon
             // doesn't stay idle after a "disable everything" with nothing
             // currently running.
-            self.shutdown_notify.notify_waiters();
+            self.exit_when_idle().await;
         }
         Response::Ok
     }
@@ -910,6 +910,74 @@ impl Supervisor {
         self.shutdown_notify.notify_waiters();
     }
 
+    fn handle_vault_unlock(&self, key: &str, ttl_secs: Option<u64>) -> Response {
+        match crate::vault::VaultKey::from_base64(key) {
+            Ok(key) => {
+                let ttl = ttl_secs.map(Duration::from_secs);
+                crate::vault::set_session_key(key, ttl);
+                log::info!("[daemon] vault unlocked for this session");
+                if let Some(ttl) = ttl {
+                    // The key only expires lazily; re-check idleness once it has
+                    // so a daemon kept up for the vault alone does not linger.
+                    let supervisor = self.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(ttl).await;
+                        if crate::vault::session_key().is_none() {
+                            log::info!("[daemon] vault session expired");
+                            supervisor.shutdown_if_idle().await;
+                        }
+                    });
+                }
+                Response::Ok
+            }
+            Err(err) => Response::Error {
+                message: err.to_string(),
+            },
+        }
+    }
+
+    fn handle_vault_open(payload: &str) -> Response {
+        let Some(key) = crate::vault::session_key() else {
+            return Response::VaultValue { value: None };
+        };
+        match key.open(payload) {
+            Ok(value) => Response::VaultValue { value: Some(value) },
+            Err(err) => Response::Error {
+                message: err.to_string(),
+            },
+        }
+    }
+
+    async fn handle_vault_lock(&self) -> Response {
+        if crate::vault::clear_session_key() {
+            log::info!("[daemon] vault locked");
+        }
+        self.shutdown_if_idle().await;
+        Response::Ok
+    }
+
+    /// Exit if no worker is left, e.g. after the vault key held the daemon up.
+    async fn shutdown_if_idle(&self) {
+        let idle = {
+            let inner = self.inner.lock().await;
+            !Self::has_remaining_workers_locked(&inner) && !inner.teardown_in_progress
+        };
+        if idle {
+            self.shutdown_notify.notify_waiters();
+        }
+    }
+
+    /// Exit once no worker is left, unless the daemon is holding an unlocked
+    /// vault key for the session.
+    async fn exit_when_idle(&self) {
+        if crate::vault::session_key().is_some() {
+            log::info!("[daemon] no remaining workers; staying up for the unlocked vault");
+            self.inner.lock().await.shutdown_requested = false;
+            return;
+        }
+        self.shutdown_notify.notify_waiters();
+    }
+
     async fn handle_shutdown(&self) -> Response {
         self.shutdown().await;
         Response::Ok
@@ -967,7 +1035,7 @@ impl Supervisor {
             log::info!("[daemon] {app_key} worker exit was expected, not restarting");
             if !has_remaining_workers && !teardown_in_progress {
                 log::info!("[daemon] no remaining workers, exiting");
-                self.shutdown_notify.notify_waiters();
+                self.exit_when_idle().await;
             }
             return;
         }
@@ -995,7 +1063,7 @@ impl Supervisor {
                         app.as_str()
                     );
                     if !has_remaining_workers && !teardown_in_progress {
-                        self.shutdown_notify.notify_waiters();
+                        self.exit_when_idle().await;
                     }
                     return;
                 }
@@ -1012,7 +1080,7 @@ impl Supervisor {
                     "[daemon] {app_key} worker pid={pid} circuit-broke after repeated crashes"
                 );
                 if !has_remaining_workers && !teardown_in_progress {
-                    self.shutdown_notify.notify_waiters();
+                    self.exit_when_idle().await;
                 }
             }
         }
@@ -1164,6 +1232,15 @@ impl Handler for Supervisor {
             }
             Request::SetGlobalEnabled { enabled } => self.handle_set_global_enabled(enabled).await,
             Request::ReloadOutboundProxy => self.handle_reload_outbound_proxy().await,
+            Request::VaultUnlock { key, ttl_secs } => self.handle_vault_unlock(&key, ttl_secs),
+            Request::VaultLock => self.handle_vault_lock().await,
+            Request::VaultStatus => Response::VaultStatus {
+                key_id: crate::vault::session_key().map(|key| key.key_id()),
+            },
+            Request::VaultSeal { plaintext } => Response::VaultValue {
+                value: crate::vault::session_key().map(|key| key.seal(&plaintext)),
+            },
+            Request::VaultOpen { payload } => Self::handle_vault_open(&payload),
             Request::Shutdown => self.handle_shutdown().await,
         }
     }
@@ -1343,6 +1420,30 @@ mod tests {
         (server, port)
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn vault_session_expiry_shuts_down_an_idle_daemon() {
+        let temp_home = tempfile::tempdir().expect("create temp home");
+        let _env = TestEnvGuard::isolated(temp_home.path());
+        let db = Arc::new(Database::memory().expect("create database"));
+        let supervisor = supervisor_for_test(db, temp_home.path());
+        let shutdown = supervisor.shutdown_signal();
+        let notified = shutdown.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        // 32 zero bytes, base64-encoded.
+        let key = format!("{}=", "A".repeat(43));
+        let response = supervisor.handle_vault_unlock(&key, Some(1));
+        assert!(matches!(response, Response::Ok));
+        assert!(crate::vault::session_key().is_some());
+
+        tokio::time::timeout(Duration::from_secs(5), notified)
+            .await
+            .expect("an idle daemon should exit once the vault session expires");
+        assert!(crate::vault::session_key().is_none());
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn ensure_worker_validation_failure_does_not_start_worker_or_write_session() {
@@ -5,6 +5,7 @@
 pub mod failover;
 pub mod mcp;
 pub mod model_pricing;
+pub mod profiles;
 pub mod prompts;
 pub mod providers;
 pub mod providers_seed;
@@ -12,6 +13,7 @@ pub mod proxy;
 pub mod settings;
 pub mod skills;
 pub mod stream_check;
+pub mod universal_providers;
 pub mod usage_rollup;
 // NOTE(cc-switch-cli): keep schema aligned with upstream, but only compile the DAOs
 // that are currently supported by the CLI build. The remaining upstream DAOs are
@@ -19,5 +21,6 @@ pub mod usage_rollup;
 // services/types land in this repo.
 
 // 所有 DAO 方法都通过 Database impl 提供，无需单独导出
-// 导出 FailoverQueueItem 供外部使用
+// 导出 FailoverQueueItem、StreamCheckLogEntry 供外部使用
 pub use failover::FailoverQueueItem;
+pub use stream_check::StreamCheckLogEntry;
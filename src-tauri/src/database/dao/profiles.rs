@@ -0,0 +1,135 @@
+//! Profile 数据访问对象
+//!
+//! 读写与上游共享的 `profiles` 表，以及按 scope 存储的
+//! `current_profile_id_<scope>` 当前 Profile 标记。
+
+use crate::database::{lock_conn, to_json_string, Database};
+use crate::error::AppError;
+use crate::profile::{current_profile_key, Profile, ProfilePayload, CURRENT_PROFILE_KEY_PREFIX};
+use rusqlite::{params, OptionalExtension};
+
+type ProfileRow = (
+    String,
+    String,
+    String,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+fn profile_from_row(row: ProfileRow) -> Result<Profile, AppError> {
+    let (id, name, payload, sort_order, created_at, updated_at) = row;
+    let payload: ProfilePayload = serde_json::from_str(&payload)
+        .map_err(|e| AppError::Database(format!("解析 Profile {id} 的 payload 失败: {e}")))?;
+    Ok(Profile {
+        id,
+        name,
+        payload,
+        sort_order,
+        created_at,
+        updated_at,
+    })
+}
+
+impl Database {
+    /// 获取所有 Profile（按 sort_order、创建时间排序）
+    pub fn get_all_profiles(&self) -> Result<Vec<Profile>, AppError> {
+        let rows = {
+            let conn = lock_conn!(self.conn);
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, name, payload, sort_order, created_at, updated_at
+                     FROM profiles
+                     ORDER BY COALESCE(sort_order, 999999), created_at ASC, id ASC",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                })
+                .map_err(|e| AppError::Database(e.to_string()))?
+                .collect::<Result<Vec<ProfileRow>, _>>()
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            rows
+        };
+
+        rows.into_iter().map(profile_from_row).collect()
+    }
+
+    /// 获取单个 Profile
+    pub fn get_profile(&self, id: &str) -> Result<Option<Profile>, AppError> {
+        let row: Option<ProfileRow> = {
+            let conn = lock_conn!(self.conn);
+            conn.query_row(
+                "SELECT id, name, payload, sort_order, created_at, updated_at
+                 FROM profiles WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?
+        };
+
+        row.map(profile_from_row).transpose()
+    }
+
+    /// 保存 Profile（添加或更新）
+    pub fn save_profile(&self, profile: &Profile) -> Result<(), AppError> {
+        let payload = to_json_string(&profile.payload)?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO profiles (id, name, payload, sort_order, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                profile.id,
+                profile.name,
+                payload,
+                profile.sort_order,
+                profile.created_at,
+                profile.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除 Profile，并清除指向它的当前 Profile 标记
+    pub fn delete_profile(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let deleted = conn
+            .execute("DELETE FROM profiles WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM settings WHERE key LIKE ?1 AND value = ?2",
+            params![format!("{CURRENT_PROFILE_KEY_PREFIX}%"), id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(deleted > 0)
+    }
+
+    /// 获取指定 scope 的当前 Profile ID
+    pub fn get_current_profile_id(&self, scope: &str) -> Result<Option<String>, AppError> {
+        self.get_setting(&current_profile_key(scope))
+    }
+
+    /// 设置指定 scope 的当前 Profile ID
+    pub fn set_current_profile_id(&self, scope: &str, id: &str) -> Result<(), AppError> {
+        self.set_setting(&current_profile_key(scope), id)
+    }
+}
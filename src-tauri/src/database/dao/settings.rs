@@ -319,6 +319,29 @@ impl Database {
             .map_err(|e| AppError::Database(format!("序列化日志配置失败: {e}")))?;
         self.set_setting("log_config", &json)
     }
+
+    // --- hooks / 权限规则 / 状态栏 ---
+
+    /// 获取 hooks、权限规则集与状态栏配置
+    pub fn get_agent_settings_resources(
+        &self,
+    ) -> Result<crate::app_config::AgentSettingsResources, AppError> {
+        match self.get_setting("agent_settings_resources")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析 hooks/权限配置失败: {e}"))),
+            None => Ok(crate::app_config::AgentSettingsResources::default()),
+        }
+    }
+
+    /// 更新 hooks、权限规则集与状态栏配置
+    pub fn set_agent_settings_resources(
+        &self,
+        resources: &crate::app_config::AgentSettingsResources,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(resources)
+            .map_err(|e| AppError::Database(format!("序列化 hooks/权限配置失败: {e}")))?;
+        self.set_setting("agent_settings_resources", &json)
+    }
 }
 
 #[cfg(test)]
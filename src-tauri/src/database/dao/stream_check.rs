@@ -4,6 +4,20 @@ use crate::database::{lock_conn, Database};
 use crate::error::AppError;
 use crate::services::stream_check::{StreamCheckConfig, StreamCheckResult};
 
+/// 一条检查记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamCheckLogEntry {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub app_type: String,
+    pub status: String,
+    pub success: bool,
+    pub message: String,
+    pub response_time_ms: Option<u64>,
+    pub http_status: Option<u16>,
+    pub tested_at: i64,
+}
+
 impl Database {
     /// 保存流式检查日志
     pub fn save_stream_check_log(
@@ -39,6 +53,47 @@ impl Database {
         Ok(conn.last_insert_rowid())
     }
 
+    /// 获取 `since`（Unix 秒）之后的检查记录，按时间升序
+    ///
+    /// `app_type` / `provider_id` 为空时不过滤。
+    pub fn list_stream_check_logs(
+        &self,
+        app_type: Option<&str>,
+        provider_id: Option<&str>,
+        since: i64,
+    ) -> Result<Vec<StreamCheckLogEntry>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, provider_name, app_type, status, success, message,
+                        response_time_ms, http_status, tested_at
+                 FROM stream_check_logs
+                 WHERE tested_at >= ?1
+                   AND (?2 IS NULL OR app_type = ?2)
+                   AND (?3 IS NULL OR provider_id = ?3)
+                 ORDER BY tested_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params![since, app_type, provider_id], |row| {
+                Ok(StreamCheckLogEntry {
+                    provider_id: row.get(0)?,
+                    provider_name: row.get(1)?,
+                    app_type: row.get(2)?,
+                    status: row.get(3)?,
+                    success: row.get(4)?,
+                    message: row.get(5)?,
+                    response_time_ms: row.get::<_, Option<i64>>(6)?.map(|t| t.max(0) as u64),
+                    http_status: row.get::<_, Option<i64>>(7)?.map(|s| s as u16),
+                    tested_at: row.get(8)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     /// 获取流式检查配置
     pub fn get_stream_check_config(&self) -> Result<StreamCheckConfig, AppError> {
         match self.get_setting("stream_check_config")? {
@@ -36,7 +36,7 @@ pub(crate) use backup::run_sqlite_backup_to_completion;
 #[cfg(any(feature = "cli", test))]
 pub(crate) use dao::model_pricing::ModelPricingUpdate;
 pub(crate) use dao::providers_seed::is_official_seed_id;
-pub use dao::FailoverQueueItem;
+pub use dao::{FailoverQueueItem, StreamCheckLogEntry};
 
 use crate::config::{
     get_app_config_dir, resolve_config_dir_without_following_user_symlinks,
@@ -222,6 +222,68 @@ pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError
         .map_err(|e| AppError::Config(format!("JSON serialization failed: {e}")))
 }
 
+/// Opens (creating if needed) a lock file that only this user can touch.
+/// `name` describes the lock in error messages.
+pub(crate) fn open_lock_file(path: &Path, name: &str) -> Result<std::fs::File, AppError> {
+    #[cfg(not(unix))]
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return Err(AppError::InvalidInput(format!(
+            "{name}不能是符号链接: {}",
+            path.display()
+        )));
+    }
+
+    #[cfg(unix)]
+    let file = {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .mode(0o600)
+            .custom_flags(libc::O_CLOEXEC | libc::O_NOFOLLOW)
+            .open(path)
+            .map_err(|error| AppError::io(path, error))?
+    };
+
+    #[cfg(not(unix))]
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|error| AppError::io(path, error))?;
+
+    let metadata = file.metadata().map_err(|error| AppError::io(path, error))?;
+    if !metadata.is_file() {
+        return Err(AppError::InvalidInput(format!(
+            "{name}不是普通文件: {}",
+            path.display()
+        )));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        use std::os::unix::fs::PermissionsExt;
+
+        if metadata.nlink() != 1 {
+            return Err(AppError::InvalidInput(format!(
+                "{name}不能是硬链接: {}",
+                path.display()
+            )));
+        }
+
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|error| AppError::io(path, error))?;
+    }
+
+    Ok(file)
+}
+
 // Create folders with 0o700 permissions.
 // Leave existing folders untouched so this helper never changes user-managed
 // permissions or follows symlinked config paths.
@@ -827,6 +889,11 @@ impl Database {
         &self.runtime_key
     }
 
+    /// 数据库文件路径；内存库返回 `None`
+    pub(crate) fn file_path(&self) -> Option<&Path> {
+        self.db_path.as_deref()
+    }
+
     pub(crate) fn spawn_periodic_usage_maintenance(
         db: Arc<Self>,
         context: &'static str,
@@ -994,4 +1061,14 @@ impl Database {
             PathBuf::from(lock_name)
         })
     }
+
+    /// Lock-file path shared by every process switching `app_type`'s provider
+    /// on this database. In-memory databases need only the process lock.
+    pub(crate) fn switch_lock_path(&self, app_type: &str) -> Option<PathBuf> {
+        self.db_path.as_ref().map(|path| {
+            let mut lock_name = path.as_os_str().to_os_string();
+            lock_name.push(format!(".switch-{app_type}.lock"));
+            PathBuf::from(lock_name)
+        })
+    }
 }
@@ -878,6 +878,93 @@ pub fn apply_switch_defaults(
     set_model_config(&merged)
 }
 
+/// Build a copy of `base` (a parsed `config.yaml`) that activates `name`.
+///
+/// Used for temporary launches under a private `HERMES_HOME`: the provider is
+/// upserted into `custom_providers:` (unless it lives in the read-only
+/// `providers:` dict) and `model:` points at it, with `model_override`
+/// taking precedence over the provider's first model. Nothing is written.
+pub fn build_overlay_config(
+    base: serde_yaml::Value,
+    name: &str,
+    provider_config: &Value,
+    model_override: Option<&str>,
+) -> Result<serde_yaml::Value, AppError> {
+    let mut config = match base {
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        _ => serde_yaml::Mapping::new(),
+    };
+    let base_value = serde_yaml::Value::Mapping(config.clone());
+
+    if !is_dict_only_provider(&base_value, name) {
+        let mut normalized = provider_config.clone();
+        sanitize_hermes_provider_keys(&mut normalized);
+        normalize_provider_models_for_write(&mut normalized);
+        let first_model_id = normalized
+            .get("models")
+            .and_then(Value::as_object)
+            .and_then(|models| models.keys().next())
+            .cloned();
+        if let Some(obj) = normalized.as_object_mut() {
+            obj.insert("name".to_string(), Value::String(name.to_string()));
+            match first_model_id {
+                Some(model_id) => {
+                    obj.insert("model".to_string(), Value::String(model_id));
+                }
+                None => {
+                    obj.remove("model");
+                }
+            }
+        }
+        let entry = json_to_yaml(&normalized)?;
+
+        let mut providers: Vec<serde_yaml::Value> = base_value
+            .get("custom_providers")
+            .and_then(|v| v.as_sequence())
+            .cloned()
+            .unwrap_or_default();
+        match providers
+            .iter_mut()
+            .find(|p| p.get("name").and_then(|n| n.as_str()) == Some(name))
+        {
+            Some(existing) => *existing = entry,
+            None => providers.push(entry),
+        }
+        config.insert(
+            serde_yaml::Value::from("custom_providers"),
+            serde_yaml::Value::Sequence(providers),
+        );
+    }
+
+    let current: HermesModelConfig = match base_value.get("model") {
+        Some(model) => serde_json::from_value(yaml_to_json(model)?)
+            .map_err(|e| AppError::Config(format!("Failed to parse Hermes model config: {e}")))?,
+        None => HermesModelConfig::default(),
+    };
+    let default_model = model_override
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .map(str::to_string)
+        .or_else(|| primary_model_id_from_value(provider_config))
+        .or_else(|| {
+            provider_config
+                .get("models")
+                .and_then(Value::as_object)
+                .and_then(|models| models.keys().next().cloned())
+        })
+        .or(current.default.clone());
+    let model = HermesModelConfig {
+        default: default_model,
+        provider: Some(name.to_string()),
+        ..current
+    };
+    let model_json =
+        serde_json::to_value(&model).map_err(|e| AppError::JsonSerialize { source: e })?;
+    config.insert(serde_yaml::Value::from("model"), json_to_yaml(&model_json)?);
+
+    Ok(serde_yaml::Value::Mapping(config))
+}
+
 // ============================================================================
 // MCP Section Access (consumed by `mcp::hermes_*` helpers)
 // ============================================================================
@@ -1245,4 +1332,37 @@ custom_providers: []\n";
             assert_eq!(model.provider.as_deref(), Some("acme"));
         });
     }
+
+    #[test]
+    fn overlay_config_activates_provider_without_dropping_other_sections() {
+        let base: serde_yaml::Value = serde_yaml::from_str(
+            "model:\n  default: old-model\n  provider: other\n  max_tokens: 4096\nagent:\n  max_turns: 50\ncustom_providers:\n  - name: acme\n    base_url: https://stale\n",
+        )
+        .unwrap();
+
+        let overlay = build_overlay_config(
+            base,
+            "acme",
+            &json!({ "baseUrl": "https://fresh", "models": [{"id": "model-a"}] }),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(overlay["agent"]["max_turns"].as_u64(), Some(50));
+        assert_eq!(overlay["model"]["provider"].as_str(), Some("acme"));
+        assert_eq!(overlay["model"]["default"].as_str(), Some("model-a"));
+        assert_eq!(overlay["model"]["max_tokens"].as_u64(), Some(4096));
+        let providers = overlay["custom_providers"].as_sequence().unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0]["base_url"].as_str(), Some("https://fresh"));
+
+        let overridden = build_overlay_config(
+            serde_yaml::Value::Null,
+            "acme",
+            &json!({ "models": [{"id": "model-a"}] }),
+            Some("model-b"),
+        )
+        .unwrap();
+        assert_eq!(overridden["model"]["default"].as_str(), Some("model-b"));
+    }
 }
@@ -0,0 +1,537 @@
+//! API key pools: several keys for one provider.
+//!
+//! The key in a provider's `settings_config` is the pool's `primary` entry and
+//! `meta.apiKeyPool` lists the extra keys and the rotation strategy. The proxy
+//! picks a key per request (round-robin or fill-first) and disables a key for
+//! a cooldown when upstream rejects it (401/403) or reports it out of quota
+//! (402, or a 429 whose body names an exhausted quota or billing problem).
+//! Any other 429 is a rate limit: the key only rests for upstream's
+//! `Retry-After`, or [`RATE_LIMIT_COOLDOWN`] without one. Live configs get the
+//! first key that is not cooling down.
+//!
+//! Key state lives in the machine-local sidecar store
+//! ([`crate::local_store`]), so the proxy worker, the CLI and the TUI all see
+//! the same picture without touching the synced database. Keys are addressed by label,
+//! never by value, and pooled values may be secret references like any other
+//! stored credential.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::local_store::api_key_state::{ApiKeyOutcome, ApiKeyState};
+use crate::local_store::LocalStore;
+use crate::provider::{ApiKeyPool, KeyRotationStrategy, Provider};
+
+/// Label of the key stored in `settings_config`.
+pub(crate) const PRIMARY_LABEL: &str = "primary";
+pub(crate) const DEFAULT_COOLDOWN: Duration = Duration::from_secs(600);
+/// Rest for a rate-limited key when upstream sends no `Retry-After`.
+pub(crate) const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// A key of the pool as stored: plain, a secret reference or sealed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PoolKey {
+    pub label: String,
+    pub stored: String,
+}
+
+/// The key chosen for one proxied request.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyLease {
+    pub label: String,
+    /// The provider with the chosen key in place of its primary key.
+    pub provider: Provider,
+    cooldown: Duration,
+}
+
+pub(crate) fn pool(provider: &Provider) -> Option<&ApiKeyPool> {
+    provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.api_key_pool.as_ref())
+        .filter(|pool| !pool.keys.is_empty())
+}
+
+/// The keys of `provider` in rotation order, primary first. Empty unless the
+/// provider has a primary key and at least one extra key.
+pub(crate) fn pool_keys(app_type: &AppType, provider: &Provider) -> Vec<PoolKey> {
+    let Some(pool) = pool(provider) else {
+        return Vec::new();
+    };
+    let Some(primary) = provider.configured_api_key(app_type) else {
+        return Vec::new();
+    };
+    std::iter::once(PoolKey {
+        label: PRIMARY_LABEL.to_string(),
+        stored: primary,
+    })
+    .chain(pool.keys.iter().map(|key| PoolKey {
+        label: key.label.clone(),
+        stored: key.key.clone(),
+    }))
+    .collect()
+}
+
+pub(crate) fn cooldown(pool: &ApiKeyPool) -> Duration {
+    pool.cooldown_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_COOLDOWN)
+}
+
+/// Index of the key to use. Keys cooling down are skipped; when every key is
+/// cooling down, the one that recovers first is used rather than failing the
+/// request outright.
+pub(crate) fn pick(
+    keys: &[PoolKey],
+    states: &[ApiKeyState],
+    strategy: KeyRotationStrategy,
+    cursor: usize,
+    now: i64,
+) -> Option<usize> {
+    if keys.is_empty() {
+        return None;
+    }
+    let disabled_until = |index: usize| {
+        states
+            .iter()
+            .find(|state| state.key_label == keys[index].label)
+            .and_then(|state| state.disabled_until)
+            .filter(|until| *until > now)
+    };
+    let start = match strategy {
+        KeyRotationStrategy::RoundRobin => cursor % keys.len(),
+        KeyRotationStrategy::FillFirst => 0,
+    };
+    (0..keys.len())
+        .map(|offset| (start + offset) % keys.len())
+        .find(|index| disabled_until(*index).is_none())
+        .or_else(|| (0..keys.len()).min_by_key(|index| disabled_until(*index)))
+}
+
+/// Copy of `provider` using `key` wherever its settings hold the primary key.
+pub(crate) fn with_key(app_type: &AppType, provider: &Provider, key: &str) -> Provider {
+    let mut keyed = provider.clone();
+    if let Some(primary) = provider.configured_api_key(app_type) {
+        replace_value(&mut keyed.settings_config, &primary, key);
+    }
+    keyed
+}
+
+/// Picks the key for the next proxied request to `provider`, or `None` when
+/// the provider has no pool.
+pub(crate) fn checkout(
+    store: &LocalStore,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Option<KeyLease> {
+    let keys = pool_keys(app_type, provider);
+    let pool = pool(provider)?;
+    let states = store
+        .list_api_key_states(app_type.as_str(), &provider.id)
+        .unwrap_or_default();
+    let cursor = next_cursor(app_type, &provider.id);
+    let index = pick(
+        &keys,
+        &states,
+        pool.strategy,
+        cursor,
+        chrono::Utc::now().timestamp(),
+    )?;
+    let key = &keys[index];
+    Some(KeyLease {
+        label: key.label.clone(),
+        provider: if key.label == PRIMARY_LABEL {
+            provider.clone()
+        } else {
+            with_key(app_type, provider, &key.stored)
+        },
+        cooldown: cooldown(pool),
+    })
+}
+
+/// How a request with a pooled key went, as far as the key is concerned.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Attempt<'a> {
+    pub status: Option<u16>,
+    /// Upstream error body; tells an exhausted quota from a rate limit.
+    pub body: Option<&'a str>,
+    /// Upstream's `Retry-After`.
+    pub retry_after: Option<Duration>,
+    pub error: Option<&'a str>,
+}
+
+/// Records how a request with `lease` went. Returns whether the key was
+/// disabled by it.
+pub(crate) fn record(
+    store: &LocalStore,
+    app_type: &AppType,
+    provider_id: &str,
+    lease: &KeyLease,
+    attempt: &Attempt<'_>,
+) -> bool {
+    let disable = disable_for(attempt, lease.cooldown);
+    let outcome = ApiKeyOutcome {
+        status: attempt.status,
+        error: attempt.error,
+        disable: disable.map(|(reason, rest)| {
+            let until = chrono::Utc::now().timestamp() + rest.as_secs() as i64;
+            (until, reason)
+        }),
+    };
+    if let Err(err) =
+        store.record_api_key_outcome(app_type.as_str(), provider_id, &lease.label, &outcome)
+    {
+        log::warn!(
+            "record API key state for {provider_id}/{}: {err}",
+            lease.label
+        );
+    }
+    if let Some((reason, rest)) = disable {
+        log::warn!(
+            "API key {} of provider {provider_id} disabled for {}s ({reason})",
+            lease.label,
+            rest.as_secs()
+        );
+    }
+    disable.is_some()
+}
+
+/// Whether any key of `provider` is usable right now.
+pub(crate) fn has_usable_key(store: &LocalStore, app_type: &AppType, provider: &Provider) -> bool {
+    let now = chrono::Utc::now().timestamp();
+    statuses(store, app_type, provider)
+        .unwrap_or_default()
+        .iter()
+        .any(|status| status.is_usable_at(now))
+}
+
+/// A pool key with the state recorded for it, if any.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyStatus {
+    pub key: PoolKey,
+    pub state: Option<ApiKeyState>,
+}
+
+impl KeyStatus {
+    pub fn is_usable_at(&self, now: i64) -> bool {
+        !self
+            .state
+            .as_ref()
+            .is_some_and(|state| state.is_disabled_at(now))
+    }
+}
+
+/// Every key of `provider`'s pool with its state, in rotation order.
+pub(crate) fn statuses(
+    store: &LocalStore,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<Vec<KeyStatus>, AppError> {
+    let keys = pool_keys(app_type, provider);
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut states = store.list_api_key_states(app_type.as_str(), &provider.id)?;
+    Ok(keys
+        .into_iter()
+        .map(|key| {
+            let state = states
+                .iter()
+                .position(|state| state.key_label == key.label)
+                .map(|index| states.swap_remove(index));
+            KeyStatus { key, state }
+        })
+        .collect())
+}
+
+/// Why and for how long `attempt` takes its key out of rotation: `auth` for
+/// rejected keys and `quota` for exhausted ones rest for the pool's
+/// `cooldown`, `rate_limit` only as long as upstream asks.
+pub(crate) fn disable_for(
+    attempt: &Attempt<'_>,
+    cooldown: Duration,
+) -> Option<(&'static str, Duration)> {
+    match attempt.status? {
+        401 | 403 => Some(("auth", cooldown)),
+        402 => Some(("quota", cooldown)),
+        429 if attempt.body.is_some_and(reports_exhausted_quota) => Some(("quota", cooldown)),
+        429 => {
+            let rest = attempt.retry_after.unwrap_or(RATE_LIMIT_COOLDOWN);
+            Some(("rate_limit", rest.max(Duration::from_secs(1)).min(cooldown)))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a 429 body says the account is out of credit rather than going
+/// too fast, e.g. OpenAI's `insufficient_quota` error type.
+fn reports_exhausted_quota(body: &str) -> bool {
+    let body = body.to_ascii_lowercase();
+    [
+        "insufficient_quota",
+        "billing",
+        "exceeded your current quota",
+    ]
+    .iter()
+    .any(|marker| body.contains(marker))
+}
+
+/// Resolved copy of `provider` for writing a live config: the first key of
+/// its pool that is not cooling down replaces the primary key.
+pub(crate) fn resolve_for_live(
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<Provider, AppError> {
+    let keys = pool_keys(app_type, provider);
+    if keys.len() < 2 {
+        return crate::secret_ref::resolve_provider(provider);
+    }
+    let states = LocalStore::open()
+        .and_then(|store| store.list_api_key_states(app_type.as_str(), &provider.id))
+        .unwrap_or_default();
+    let now = chrono::Utc::now().timestamp();
+    match pick(&keys, &states, KeyRotationStrategy::FillFirst, 0, now) {
+        Some(index) if keys[index].label != PRIMARY_LABEL => {
+            crate::secret_ref::resolve_provider(&with_key(app_type, provider, &keys[index].stored))
+        }
+        _ => crate::secret_ref::resolve_provider(provider),
+    }
+}
+
+/// [`resolve_for_live`] on a blocking thread, for requests cc-switch makes
+/// with a provider's key outside the proxy (checks, model lists, usage
+/// queries): references may read files or run commands.
+pub(crate) async fn resolve_for_live_async(
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<Provider, AppError> {
+    let (app_type, provider) = (app_type.clone(), provider.clone());
+    tokio::task::spawn_blocking(move || resolve_for_live(&app_type, &provider))
+        .await
+        .map_err(|err| AppError::Message(format!("resolve provider credentials: {err}")))?
+}
+
+/// Settings read back from a live config with everything that must not be
+/// persisted put back from `stored`: secret references and, for pooled
+/// providers, the primary key in place of whichever pool key was live.
+pub(crate) fn restore_stored(app_type: &AppType, stored: &Provider, updated: Value) -> Value {
+    let mut updated = crate::secret_ref::restore_references(&stored.settings_config, updated);
+    if pool(stored).is_some() {
+        if let Some(primary) = stored.configured_api_key(app_type) {
+            let mut paths = Vec::new();
+            collect_paths(
+                &stored.settings_config,
+                &primary,
+                &mut Vec::new(),
+                &mut paths,
+            );
+            for path in paths {
+                let mut target = Some(&mut updated);
+                for segment in &path {
+                    target = target.and_then(|value| value.get_mut(segment.as_str()));
+                }
+                if let Some(target @ Value::String(_)) = target {
+                    *target = Value::String(primary.clone());
+                }
+            }
+        }
+    }
+    updated
+}
+
+fn replace_value(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::String(raw) if raw.trim() == from => *raw = to.to_string(),
+        // Codex keeps a bearer token inside its config.toml text.
+        Value::String(raw) if from.len() >= 8 && raw.contains(from) => {
+            *raw = raw.replace(from, to);
+        }
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|child| replace_value(child, from, to)),
+        _ => {}
+    }
+}
+
+fn collect_paths(
+    value: &Value,
+    needle: &str,
+    path: &mut Vec<String>,
+    found: &mut Vec<Vec<String>>,
+) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        path.push(key.clone());
+        match child {
+            Value::String(raw) if raw.trim() == needle => found.push(path.clone()),
+            Value::Object(_) => collect_paths(child, needle, path, found),
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+fn next_cursor(app_type: &AppType, provider_id: &str) -> usize {
+    static CURSORS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+    let Ok(mut cursors) = CURSORS.get_or_init(Default::default).lock() else {
+        return 0;
+    };
+    let cursor = cursors
+        .entry(format!("{}:{provider_id}", app_type.as_str()))
+        .or_insert(0);
+    let current = *cursor;
+    *cursor = cursor.wrapping_add(1);
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn keys(labels: &[&str]) -> Vec<PoolKey> {
+        labels
+            .iter()
+            .map(|label| PoolKey {
+                label: label.to_string(),
+                stored: format!("sk-{label}"),
+            })
+            .collect()
+    }
+
+    fn disabled(label: &str, until: i64) -> ApiKeyState {
+        ApiKeyState {
+            key_label: label.to_string(),
+            disabled_until: Some(until),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_robin_skips_keys_cooling_down() {
+        let keys = keys(&["primary", "b", "c"]);
+        let states = [disabled("b", 200)];
+        let picked: Vec<_> = (0..4)
+            .map(|cursor| pick(&keys, &states, KeyRotationStrategy::RoundRobin, cursor, 100))
+            .collect();
+        assert_eq!(picked, [Some(0), Some(2), Some(2), Some(0)]);
+
+        // Once the cooldown is over the key is back in rotation.
+        assert_eq!(
+            pick(&keys, &states, KeyRotationStrategy::RoundRobin, 1, 300),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn fill_first_sticks_to_the_first_usable_key() {
+        let keys = keys(&["primary", "b", "c"]);
+        assert_eq!(
+            pick(&keys, &[], KeyRotationStrategy::FillFirst, 7, 100),
+            Some(0)
+        );
+        let states = [disabled("primary", 200), disabled("b", 150)];
+        assert_eq!(
+            pick(&keys, &states, KeyRotationStrategy::FillFirst, 7, 100),
+            Some(2)
+        );
+
+        // Nothing usable: the key that recovers first.
+        let states = [
+            disabled("primary", 200),
+            disabled("b", 150),
+            disabled("c", 400),
+        ];
+        assert_eq!(
+            pick(&keys, &states, KeyRotationStrategy::FillFirst, 0, 100),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn only_quota_429s_disable_for_the_full_cooldown() {
+        let cooldown = DEFAULT_COOLDOWN;
+        let rate_limited = |body, retry_after| Attempt {
+            status: Some(429),
+            body,
+            retry_after,
+            error: None,
+        };
+
+        let quota = r#"{"error":{"type":"insufficient_quota","message":"You exceeded your current quota"}}"#;
+        assert_eq!(
+            disable_for(&rate_limited(Some(quota), None), cooldown),
+            Some(("quota", cooldown))
+        );
+        let billing = r#"{"error":{"code":"billing_hard_limit_reached"}}"#;
+        assert_eq!(
+            disable_for(&rate_limited(Some(billing), None), cooldown),
+            Some(("quota", cooldown))
+        );
+
+        let busy = r#"{"error":{"type":"rate_limit_error","message":"slow down"}}"#;
+        assert_eq!(
+            disable_for(&rate_limited(Some(busy), None), cooldown),
+            Some(("rate_limit", RATE_LIMIT_COOLDOWN))
+        );
+        assert_eq!(
+            disable_for(
+                &rate_limited(Some(busy), Some(Duration::from_secs(5))),
+                cooldown
+            ),
+            Some(("rate_limit", Duration::from_secs(5)))
+        );
+        // A Retry-After longer than the pool's cooldown never outlasts it.
+        assert_eq!(
+            disable_for(
+                &rate_limited(None, Some(Duration::from_secs(3600))),
+                cooldown
+            ),
+            Some(("rate_limit", cooldown))
+        );
+
+        let rejected = Attempt {
+            status: Some(401),
+            ..Attempt::default()
+        };
+        assert_eq!(disable_for(&rejected, cooldown), Some(("auth", cooldown)));
+        let failed = Attempt {
+            status: Some(500),
+            ..Attempt::default()
+        };
+        assert_eq!(disable_for(&failed, cooldown), None);
+    }
+
+    #[test]
+    fn restore_stored_puts_the_primary_key_back() {
+        let mut provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({"env": {"ANTHROPIC_AUTH_TOKEN": "sk-primary", "ANTHROPIC_BASE_URL": "https://relay"}}),
+            None,
+        );
+        provider.meta = Some(crate::provider::ProviderMeta {
+            api_key_pool: Some(ApiKeyPool {
+                keys: vec![crate::provider::PooledApiKey {
+                    label: "backup".to_string(),
+                    key: "sk-backup".to_string(),
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        let live = with_key(&AppType::Claude, &provider, "sk-backup").settings_config;
+        assert_eq!(live["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-backup");
+
+        let restored = restore_stored(&AppType::Claude, &provider, live);
+        assert_eq!(restored, provider.settings_config);
+    }
+}
@@ -23,17 +23,21 @@ pub mod hermes_config;
 mod import_export;
 #[allow(dead_code)]
 mod init_status;
+mod key_pool;
+mod local_store;
 mod mcp;
 mod model_capabilities;
 mod openclaw_config;
 mod opencode_config;
+mod preset_catalog;
+mod profile;
 mod prompt;
 mod prompt_files;
 mod provider;
 mod provider_defaults;
 mod provider_preset_models;
-mod provider_preset_sponsors;
 mod proxy;
+mod secret_ref;
 mod services;
 mod session_manager;
 mod settings;
@@ -41,6 +45,7 @@ mod store;
 mod sync_policy;
 mod usage_events;
 mod usage_script;
+mod vault;
 
 #[cfg(test)]
 pub(crate) mod test_support;
@@ -0,0 +1,218 @@
+//! 用量告警历史（sidecar）。
+//!
+//! 每条告警以 `dedup_key` 唯一：守护进程与前台代理进程可能同时评估规则，
+//! 先 `INSERT OR IGNORE` 成功"认领"的一方负责投递，其余直接跳过，避免重复
+//! 推送。投递结果回写到同一行，供 `usage alerts history` 查看。
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS usage_alert_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            dedup_key TEXT NOT NULL UNIQUE,
+            kind TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            title TEXT NOT NULL,
+            message TEXT NOT NULL,
+            details TEXT NOT NULL DEFAULT '{}',
+            created_at INTEGER NOT NULL,
+            delivered INTEGER NOT NULL DEFAULT 0,
+            delivery_error TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_usage_alert_history_created
+            ON usage_alert_history(created_at);",
+    )
+    .map_err(|e| AppError::Database(format!("创建告警历史表失败: {e}")))
+}
+
+/// 一条已认领的告警记录。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertHistoryRow {
+    pub id: i64,
+    pub dedup_key: String,
+    pub kind: String,
+    pub severity: String,
+    pub title: String,
+    pub message: String,
+    pub details: serde_json::Value,
+    pub created_at: i64,
+    pub delivered: bool,
+    pub delivery_error: Option<String>,
+}
+
+/// 认领告警时写入的字段。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NewAlert<'a> {
+    pub dedup_key: &'a str,
+    pub kind: &'a str,
+    pub severity: &'a str,
+    pub title: &'a str,
+    pub message: &'a str,
+    pub details: &'a serde_json::Value,
+    pub created_at: i64,
+}
+
+impl LocalStore {
+    /// 认领一条告警；返回新行 id，`dedup_key` 已存在时返回 `None`。
+    pub(crate) fn claim_usage_alert(&self, alert: &NewAlert<'_>) -> Result<Option<i64>, AppError> {
+        let conn = self.lock()?;
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO usage_alert_history
+                    (dedup_key, kind, severity, title, message, details, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    alert.dedup_key,
+                    alert.kind,
+                    alert.severity,
+                    alert.title,
+                    alert.message,
+                    alert.details.to_string(),
+                    alert.created_at,
+                ],
+            )
+            .map_err(|e| AppError::Database(format!("写入告警历史失败: {e}")))?;
+        Ok((inserted > 0).then(|| conn.last_insert_rowid()))
+    }
+
+    /// 仅登记去重键（已被更高阈值覆盖的告警），不进入投递流程。
+    pub(crate) fn suppress_usage_alert(&self, alert: &NewAlert<'_>) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT OR IGNORE INTO usage_alert_history
+                (dedup_key, kind, severity, title, message, details, created_at,
+                 delivered, delivery_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, 'superseded')",
+            params![
+                alert.dedup_key,
+                alert.kind,
+                alert.severity,
+                alert.title,
+                alert.message,
+                alert.details.to_string(),
+                alert.created_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(format!("写入告警历史失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 回写投递结果；`error` 为空表示全部渠道成功。
+    pub(crate) fn mark_usage_alert_delivery(
+        &self,
+        id: i64,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "UPDATE usage_alert_history SET delivered = ?2, delivery_error = ?3 WHERE id = ?1",
+            params![id, error.is_none(), error],
+        )
+        .map_err(|e| AppError::Database(format!("更新告警投递状态失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 按时间倒序列出告警历史（不含被更高阈值覆盖的记录）。
+    pub fn usage_alert_history(&self, limit: usize) -> Result<Vec<AlertHistoryRow>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, dedup_key, kind, severity, title, message, details, created_at,
+                        delivered, delivery_error
+                 FROM usage_alert_history
+                 WHERE delivery_error IS NULL OR delivery_error != 'superseded'
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([limit as i64], |row| {
+                let details: String = row.get(6)?;
+                Ok(AlertHistoryRow {
+                    id: row.get(0)?,
+                    dedup_key: row.get(1)?,
+                    kind: row.get(2)?,
+                    severity: row.get(3)?,
+                    title: row.get(4)?,
+                    message: row.get(5)?,
+                    details: serde_json::from_str(&details).unwrap_or(serde_json::Value::Null),
+                    created_at: row.get(7)?,
+                    delivered: row.get(8)?,
+                    delivery_error: row.get(9)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除早于 `cutoff` 的告警历史。
+    pub(crate) fn prune_usage_alert_history(&self, cutoff: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM usage_alert_history WHERE created_at < ?1",
+            [cutoff],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NewAlert;
+    use crate::local_store::LocalStore;
+
+    fn alert<'a>(key: &'a str, details: &'a serde_json::Value, at: i64) -> NewAlert<'a> {
+        NewAlert {
+            dedup_key: key,
+            kind: "budget",
+            severity: "warning",
+            title: "Daily budget 80% used",
+            message: "spent $8.00 of $10.00",
+            details,
+            created_at: at,
+        }
+    }
+
+    #[test]
+    fn claims_are_deduplicated_and_superseded_rows_are_hidden() {
+        let store = LocalStore::in_memory().expect("open store");
+        let details = serde_json::json!({ "threshold": 80 });
+
+        let id = store
+            .claim_usage_alert(&alert("budget:daily:2026-10-18:80", &details, 10))
+            .expect("claim")
+            .expect("first claim wins");
+        assert_eq!(
+            store
+                .claim_usage_alert(&alert("budget:daily:2026-10-18:80", &details, 11))
+                .expect("claim again"),
+            None
+        );
+        store
+            .suppress_usage_alert(&alert("budget:daily:2026-10-18:50", &details, 10))
+            .expect("suppress");
+        store
+            .mark_usage_alert_delivery(id, Some("webhook returned 500"))
+            .expect("mark");
+
+        let rows = store.usage_alert_history(10).expect("history");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].dedup_key, "budget:daily:2026-10-18:80");
+        assert!(!rows[0].delivered);
+        assert_eq!(
+            rows[0].delivery_error.as_deref(),
+            Some("webhook returned 500")
+        );
+        assert_eq!(rows[0].details["threshold"], 80);
+
+        assert_eq!(store.prune_usage_alert_history(11).expect("prune"), 2);
+        assert!(store.usage_alert_history(10).expect("history").is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+//! 供应商 API Key 池状态（sidecar）。
+//!
+//! 记录 Key 池中每个 Key 的停用截止时间、最近一次结果、请求计数与累计用量。
+//! 代理进程写入，CLI/TUI 与 live 配置写入读取；属于本机运行状态，不参与同步。
+//! 供应商删除或 Key 移出 Key 池后遗留的行不影响轮换，由 `provider keys remove`
+//! 顺手清理。
+
+use rusqlite::{params, Connection};
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS provider_api_key_state (
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            key_label TEXT NOT NULL,
+            disabled_until INTEGER,
+            disabled_reason TEXT,
+            last_status INTEGER,
+            last_error TEXT,
+            success_count INTEGER NOT NULL DEFAULT 0,
+            failure_count INTEGER NOT NULL DEFAULT 0,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            total_cost_usd REAL NOT NULL DEFAULT 0,
+            last_used_at INTEGER,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (app_type, provider_id, key_label)
+        );",
+    )
+    .map_err(|e| AppError::Database(format!("创建 Key 池状态表失败: {e}")))
+}
+
+/// 单个 Key 的运行状态
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiKeyState {
+    pub key_label: String,
+    /// 停用截止时间（Unix 秒）；为空或已过期表示可用
+    pub disabled_until: Option<i64>,
+    /// 停用原因：`auth`（401/403）或 `quota`（402/429）
+    pub disabled_reason: Option<String>,
+    pub last_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub success_count: u64,
+    pub failure_count: u64,
+    /// 请求日志归到该 Key 的累计 token 与费用
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_cost_usd: f64,
+    pub last_used_at: Option<i64>,
+}
+
+impl ApiKeyState {
+    pub fn is_disabled_at(&self, now: i64) -> bool {
+        self.disabled_until.is_some_and(|until| until > now)
+    }
+}
+
+/// 一次请求的结果
+#[derive(Debug, Clone)]
+pub struct ApiKeyOutcome<'a> {
+    pub status: Option<u16>,
+    pub error: Option<&'a str>,
+    /// 需要停用时的 (截止时间, 原因)
+    pub disable: Option<(i64, &'a str)>,
+}
+
+impl LocalStore {
+    /// 获取供应商 Key 池中已有记录的 Key 状态
+    pub(crate) fn list_api_key_states(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<ApiKeyState>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT key_label, disabled_until, disabled_reason, last_status, last_error,
+                        success_count, failure_count, input_tokens, output_tokens,
+                        total_cost_usd, last_used_at
+                 FROM provider_api_key_state
+                 WHERE app_type = ?1 AND provider_id = ?2",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id], |row| {
+                Ok(ApiKeyState {
+                    key_label: row.get(0)?,
+                    disabled_until: row.get(1)?,
+                    disabled_reason: row.get(2)?,
+                    last_status: row.get::<_, Option<i64>>(3)?.map(|status| status as u16),
+                    last_error: row.get(4)?,
+                    success_count: row.get::<_, i64>(5)?.max(0) as u64,
+                    failure_count: row.get::<_, i64>(6)?.max(0) as u64,
+                    input_tokens: row.get::<_, i64>(7)?.max(0) as u64,
+                    output_tokens: row.get::<_, i64>(8)?.max(0) as u64,
+                    total_cost_usd: row.get(9)?,
+                    last_used_at: row.get(10)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 记录一次使用某个 Key 的请求结果
+    ///
+    /// 成功会清除停用状态；`outcome.disable` 存在时把 Key 停用到指定时间。
+    pub(crate) fn record_api_key_outcome(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        key_label: &str,
+        outcome: &ApiKeyOutcome<'_>,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        let now = chrono::Utc::now().timestamp();
+        let success = outcome
+            .status
+            .is_some_and(|status| (200..300).contains(&status));
+        let (disabled_until, disabled_reason) = match outcome.disable {
+            Some((until, reason)) => (Some(until), Some(reason)),
+            None => (None, None),
+        };
+
+        conn.execute(
+            "INSERT INTO provider_api_key_state
+             (app_type, provider_id, key_label, disabled_until, disabled_reason,
+              last_status, last_error, success_count, failure_count, last_used_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)
+             ON CONFLICT(app_type, provider_id, key_label) DO UPDATE SET
+                disabled_until = CASE
+                    WHEN ?4 IS NOT NULL THEN ?4
+                    WHEN ?8 = 1 THEN NULL
+                    ELSE disabled_until END,
+                disabled_reason = CASE
+                    WHEN ?4 IS NOT NULL THEN ?5
+                    WHEN ?8 = 1 THEN NULL
+                    ELSE disabled_reason END,
+                last_status = ?6,
+                last_error = ?7,
+                success_count = success_count + ?8,
+                failure_count = failure_count + ?9,
+                last_used_at = ?10,
+                updated_at = ?10",
+            params![
+                app_type,
+                provider_id,
+                key_label,
+                disabled_until,
+                disabled_reason,
+                outcome.status.map(i64::from),
+                outcome.error,
+                success as i64,
+                (!success) as i64,
+                now,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 把一条请求日志的 token 与费用累加到所用的 Key 上
+    pub(crate) fn add_api_key_usage(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        key_label: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO provider_api_key_state
+             (app_type, provider_id, key_label, input_tokens, output_tokens, total_cost_usd,
+              updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(app_type, provider_id, key_label) DO UPDATE SET
+                input_tokens = input_tokens + ?4,
+                output_tokens = output_tokens + ?5,
+                total_cost_usd = total_cost_usd + ?6,
+                updated_at = ?7",
+            params![
+                app_type,
+                provider_id,
+                key_label,
+                i64::try_from(input_tokens).unwrap_or(i64::MAX),
+                i64::try_from(output_tokens).unwrap_or(i64::MAX),
+                cost_usd,
+                chrono::Utc::now().timestamp(),
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 重新启用 Key；`key_label` 为空时重置该供应商所有 Key 的状态
+    pub(crate) fn reset_api_key_state(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        key_label: Option<&str>,
+    ) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        let changed = match key_label {
+            Some(label) => conn.execute(
+                "UPDATE provider_api_key_state
+                 SET disabled_until = NULL, disabled_reason = NULL
+                 WHERE app_type = ?1 AND provider_id = ?2 AND key_label = ?3",
+                params![app_type, provider_id, label],
+            ),
+            None => conn.execute(
+                "UPDATE provider_api_key_state
+                 SET disabled_until = NULL, disabled_reason = NULL
+                 WHERE app_type = ?1 AND provider_id = ?2",
+                params![app_type, provider_id],
+            ),
+        }
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(changed)
+    }
+
+    /// 删除已从 Key 池移除的 Key 的状态
+    pub(crate) fn delete_api_key_state(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        key_label: &str,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM provider_api_key_state
+             WHERE app_type = ?1 AND provider_id = ?2 AND key_label = ?3",
+            params![app_type, provider_id, key_label],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiKeyOutcome;
+    use crate::local_store::LocalStore;
+
+    #[test]
+    fn success_clears_disable_and_usage_accumulates() {
+        let store = LocalStore::in_memory().expect("open store");
+        store
+            .record_api_key_outcome(
+                "claude",
+                "relay",
+                "second",
+                &ApiKeyOutcome {
+                    status: Some(401),
+                    error: Some("invalid key"),
+                    disable: Some((2_000_000_000, "auth")),
+                },
+            )
+            .expect("record failure");
+        let states = store.list_api_key_states("claude", "relay").expect("list");
+        assert_eq!(states.len(), 1);
+        assert!(states[0].is_disabled_at(1_000));
+        assert_eq!(states[0].disabled_reason.as_deref(), Some("auth"));
+
+        store
+            .record_api_key_outcome(
+                "claude",
+                "relay",
+                "second",
+                &ApiKeyOutcome {
+                    status: Some(200),
+                    error: None,
+                    disable: None,
+                },
+            )
+            .expect("record success");
+        store
+            .add_api_key_usage("claude", "relay", "second", 100, 20, 0.5)
+            .expect("add usage");
+        store
+            .add_api_key_usage("claude", "relay", "second", 10, 2, 0.25)
+            .expect("add usage");
+
+        let state = store
+            .list_api_key_states("claude", "relay")
+            .expect("list")
+            .remove(0);
+        assert!(!state.is_disabled_at(1_000));
+        assert_eq!((state.success_count, state.failure_count), (1, 1));
+        assert_eq!((state.input_tokens, state.output_tokens), (110, 22));
+        assert!((state.total_cost_usd - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reset_reenables_one_or_all_keys() {
+        let store = LocalStore::in_memory().expect("open store");
+        let disabled = ApiKeyOutcome {
+            status: Some(429),
+            error: None,
+            disable: Some((2_000_000_000, "quota")),
+        };
+        for label in ["primary", "second"] {
+            store
+                .record_api_key_outcome("codex", "relay", label, &disabled)
+                .expect("record");
+        }
+
+        assert_eq!(
+            store
+                .reset_api_key_state("codex", "relay", Some("second"))
+                .expect("reset one"),
+            1
+        );
+        assert_eq!(
+            store
+                .reset_api_key_state("codex", "relay", None)
+                .expect("reset all"),
+            2
+        );
+        assert!(store
+            .list_api_key_states("codex", "relay")
+            .expect("list")
+            .iter()
+            .all(|state| state.disabled_until.is_none()));
+    }
+}
@@ -0,0 +1,159 @@
+//! 供应商能力矩阵（sidecar）。
+//!
+//! `provider probe` 对每个供应商/模型发送一组小请求（流式、工具调用、thinking、
+//! 图片输入等），把每项能力的结论写在这里；代理读取后决定是否预先整流请求
+//! （例如去掉上游不支持的图片）。同一供应商/模型/能力只保留最近一次结论。
+
+use rusqlite::{params, Connection};
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS provider_capabilities (
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            capability TEXT NOT NULL,
+            status TEXT NOT NULL,
+            detail TEXT,
+            latency_ms INTEGER,
+            probed_at INTEGER NOT NULL,
+            PRIMARY KEY (app_type, provider_id, model, capability)
+        );",
+    )
+    .map_err(|e| AppError::Database(format!("创建能力矩阵表失败: {e}")))
+}
+
+/// 单项能力的探测结论
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityRecord {
+    pub provider_id: String,
+    /// 实际发往上游的模型名（模型映射之后）
+    pub model: String,
+    pub capability: String,
+    /// `supported` / `unsupported` / `failed`
+    pub status: String,
+    pub detail: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub probed_at: i64,
+}
+
+impl LocalStore {
+    /// 写入一次探测的结论（按供应商/模型/能力覆盖旧值）
+    pub(crate) fn save_capability_records(
+        &self,
+        app_type: &str,
+        records: &[CapabilityRecord],
+    ) -> Result<(), AppError> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for record in records {
+            tx.execute(
+                "INSERT OR REPLACE INTO provider_capabilities
+                 (app_type, provider_id, model, capability, status, detail, latency_ms, probed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    app_type,
+                    record.provider_id,
+                    record.model,
+                    record.capability,
+                    record.status,
+                    record.detail,
+                    record.latency_ms.map(|ms| ms as i64),
+                    record.probed_at,
+                ],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 获取能力矩阵；`provider_id` 为空时返回该应用下所有供应商
+    pub(crate) fn list_capability_records(
+        &self,
+        app_type: &str,
+        provider_id: Option<&str>,
+    ) -> Result<Vec<CapabilityRecord>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, model, capability, status, detail, latency_ms, probed_at
+                 FROM provider_capabilities
+                 WHERE app_type = ?1 AND (?2 IS NULL OR provider_id = ?2)
+                 ORDER BY provider_id, model, capability",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id], |row| {
+                Ok(CapabilityRecord {
+                    provider_id: row.get(0)?,
+                    model: row.get(1)?,
+                    capability: row.get(2)?,
+                    status: row.get(3)?,
+                    detail: row.get(4)?,
+                    latency_ms: row.get::<_, Option<i64>>(5)?.map(|ms| ms.max(0) as u64),
+                    probed_at: row.get(6)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CapabilityRecord;
+    use crate::local_store::LocalStore;
+
+    fn record(model: &str, capability: &str, status: &str, probed_at: i64) -> CapabilityRecord {
+        CapabilityRecord {
+            provider_id: "relay".to_string(),
+            model: model.to_string(),
+            capability: capability.to_string(),
+            status: status.to_string(),
+            detail: None,
+            latency_ms: Some(120),
+            probed_at,
+        }
+    }
+
+    #[test]
+    fn latest_probe_replaces_the_previous_verdict() {
+        let store = LocalStore::in_memory().expect("open store");
+        store
+            .save_capability_records(
+                "claude",
+                &[
+                    record("glm-5.1", "image_input", "supported", 1),
+                    record("glm-5.1", "tools", "supported", 1),
+                    record("glm-5.2v", "image_input", "supported", 1),
+                ],
+            )
+            .expect("save first probe");
+        store
+            .save_capability_records(
+                "claude",
+                &[record("glm-5.1", "image_input", "unsupported", 2)],
+            )
+            .expect("save second probe");
+
+        let records = store
+            .list_capability_records("claude", Some("relay"))
+            .expect("list");
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[0],
+            record("glm-5.1", "image_input", "unsupported", 2)
+        );
+        assert!(store
+            .list_capability_records("codex", None)
+            .expect("list codex")
+            .is_empty());
+    }
+}
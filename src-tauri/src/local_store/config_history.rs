@@ -0,0 +1,239 @@
+//! 供应商 / MCP 服务器 / 提示词的编辑历史（sidecar）。
+//!
+//! 每次新增、更新、删除都追加一条带版本号的完整快照；同一条目的版本号从 1
+//! 开始递增。内容与上一版完全相同的更新不产生新版本。删除记录保存删除前的
+//! 内容，便于恢复。
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS config_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            app_type TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            rev INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            snapshot TEXT NOT NULL,
+            recorded_at INTEGER NOT NULL,
+            UNIQUE (kind, app_type, item_id, rev)
+        );",
+    )
+    .map_err(|e| AppError::Database(format!("创建编辑历史表失败: {e}")))
+}
+
+/// 一个历史版本
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigRevision {
+    pub rev: i64,
+    /// `add` / `update` / `delete`
+    pub action: String,
+    pub snapshot: Value,
+    /// 秒级时间戳
+    pub recorded_at: i64,
+}
+
+fn revision_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ConfigRevision> {
+    let raw: String = row.get(2)?;
+    Ok(ConfigRevision {
+        rev: row.get(0)?,
+        action: row.get(1)?,
+        snapshot: serde_json::from_str(&raw).unwrap_or(Value::Null),
+        recorded_at: row.get(3)?,
+    })
+}
+
+impl LocalStore {
+    /// 追加一个版本并返回其版本号；内容未变化的更新返回 `None`
+    pub(crate) fn record_config_revision(
+        &self,
+        kind: &str,
+        app_type: &str,
+        item_id: &str,
+        action: &str,
+        snapshot: &Value,
+        recorded_at: i64,
+    ) -> Result<Option<i64>, AppError> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let latest: Option<(i64, String, String)> = tx
+            .query_row(
+                "SELECT rev, action, snapshot FROM config_history
+                 WHERE kind = ?1 AND app_type = ?2 AND item_id = ?3
+                 ORDER BY rev DESC LIMIT 1",
+                params![kind, app_type, item_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let unchanged = latest.as_ref().is_some_and(|(_, last_action, raw)| {
+            action == "update"
+                && last_action != "delete"
+                && serde_json::from_str::<Value>(raw).ok().as_ref() == Some(snapshot)
+        });
+        if unchanged {
+            return Ok(None);
+        }
+
+        let rev = latest.map_or(1, |(rev, _, _)| rev + 1);
+        let encoded =
+            serde_json::to_string(snapshot).map_err(|e| AppError::Database(e.to_string()))?;
+        tx.execute(
+            "INSERT INTO config_history
+                (kind, app_type, item_id, rev, action, snapshot, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![kind, app_type, item_id, rev, action, encoded, recorded_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(Some(rev))
+    }
+
+    /// 获取某条目的全部版本（版本号升序）
+    pub(crate) fn list_config_revisions(
+        &self,
+        kind: &str,
+        app_type: &str,
+        item_id: &str,
+    ) -> Result<Vec<ConfigRevision>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT rev, action, snapshot, recorded_at FROM config_history
+                 WHERE kind = ?1 AND app_type = ?2 AND item_id = ?3
+                 ORDER BY rev",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![kind, app_type, item_id], revision_from_row)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 用 `rewrite` 改写全部快照（返回 `true` 表示有改动），返回改写的版本数
+    pub(crate) fn rewrite_config_snapshots(
+        &self,
+        mut rewrite: impl FnMut(&mut Value) -> bool,
+    ) -> Result<usize, AppError> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows: Vec<(i64, String)> = {
+            let mut stmt = tx
+                .prepare("SELECT id, snapshot FROM config_history")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| AppError::Database(e.to_string()))?
+        };
+
+        let mut rewritten = 0;
+        for (id, raw) in rows {
+            let Ok(mut snapshot) = serde_json::from_str::<Value>(&raw) else {
+                continue;
+            };
+            if !rewrite(&mut snapshot) {
+                continue;
+            }
+            let encoded =
+                serde_json::to_string(&snapshot).map_err(|e| AppError::Database(e.to_string()))?;
+            tx.execute(
+                "UPDATE config_history SET snapshot = ?1 WHERE id = ?2",
+                params![encoded, id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            rewritten += 1;
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(rewritten)
+    }
+
+    /// 只保留每个条目最近的 `keep` 个版本，返回删除行数
+    pub(crate) fn prune_config_history(
+        &self,
+        kind: &str,
+        app_type: &str,
+        item_id: &str,
+        keep: i64,
+    ) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM config_history
+             WHERE kind = ?1 AND app_type = ?2 AND item_id = ?3 AND rev <= (
+                SELECT MAX(rev) FROM config_history
+                WHERE kind = ?1 AND app_type = ?2 AND item_id = ?3
+             ) - ?4",
+            params![kind, app_type, item_id, keep],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::local_store::LocalStore;
+
+    #[test]
+    fn revisions_are_numbered_and_unchanged_updates_are_skipped() {
+        let store = LocalStore::in_memory().expect("open store");
+        let v1 = json!({"name": "Relay"});
+        let v2 = json!({"name": "Relay 2"});
+
+        let record = |action: &str, snapshot: &serde_json::Value, at: i64| {
+            store
+                .record_config_revision("provider", "claude", "relay", action, snapshot, at)
+                .expect("record")
+        };
+        assert_eq!(record("add", &v1, 10), Some(1));
+        assert_eq!(record("update", &v1, 11), None);
+        assert_eq!(record("update", &v2, 12), Some(2));
+        assert_eq!(record("delete", &v2, 13), Some(3));
+        assert_eq!(record("add", &v2, 14), Some(4));
+
+        let revisions = store
+            .list_config_revisions("provider", "claude", "relay")
+            .expect("list");
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|revision| (revision.rev, revision.action.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "add"), (2, "update"), (3, "delete"), (4, "add")]
+        );
+        assert_eq!(revisions[1].snapshot, v2);
+        assert!(store
+            .list_config_revisions("provider", "codex", "relay")
+            .expect("other app")
+            .is_empty());
+
+        assert_eq!(
+            store
+                .prune_config_history("provider", "claude", "relay", 2)
+                .expect("prune"),
+            2
+        );
+        assert_eq!(
+            store
+                .list_config_revisions("provider", "claude", "relay")
+                .expect("list")[0]
+                .rev,
+            3
+        );
+    }
+}
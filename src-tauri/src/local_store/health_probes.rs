@@ -0,0 +1,109 @@
+//! 后台健康探测历史（sidecar）。
+//!
+//! 守护进程的健康监控每次探测写一行 `health_probes`。手动检查仍写主库的
+//! `stream_check_logs`；探测记录放在这里，按保留天数清理时不会碰到用户的
+//! 手动检查，也不会随主库同步到其他机器。
+
+use rusqlite::{params, Connection};
+
+use super::LocalStore;
+use crate::database::StreamCheckLogEntry;
+use crate::error::AppError;
+use crate::services::stream_check::StreamCheckResult;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS health_probes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            provider_name TEXT NOT NULL,
+            status TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            message TEXT NOT NULL,
+            response_time_ms INTEGER,
+            http_status INTEGER,
+            tested_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_health_probes_provider
+            ON health_probes(app_type, provider_id, tested_at);",
+    )
+    .map_err(|e| AppError::Database(format!("创建健康探测表失败: {e}")))
+}
+
+impl LocalStore {
+    /// 记录一次后台探测。
+    pub(crate) fn record_health_probe(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        provider_name: &str,
+        result: &StreamCheckResult,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO health_probes
+                (app_type, provider_id, provider_name, status, success, message,
+                 response_time_ms, http_status, tested_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                app_type,
+                provider_id,
+                provider_name,
+                format!("{:?}", result.status).to_lowercase(),
+                result.success,
+                result.message,
+                result.response_time_ms.map(|t| t as i64),
+                result.http_status.map(|s| s as i64),
+                result.tested_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(format!("写入健康探测失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 按时间正序读取 `since`（秒）之后的探测，形状与主库检查记录一致。
+    pub(crate) fn list_health_probes(
+        &self,
+        app_type: Option<&str>,
+        provider_id: Option<&str>,
+        since: i64,
+    ) -> Result<Vec<StreamCheckLogEntry>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, provider_name, app_type, status, success, message,
+                        response_time_ms, http_status, tested_at
+                 FROM health_probes
+                 WHERE tested_at >= ?1
+                   AND (?2 IS NULL OR app_type = ?2)
+                   AND (?3 IS NULL OR provider_id = ?3)
+                 ORDER BY tested_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![since, app_type, provider_id], |row| {
+                Ok(StreamCheckLogEntry {
+                    provider_id: row.get(0)?,
+                    provider_name: row.get(1)?,
+                    app_type: row.get(2)?,
+                    status: row.get(3)?,
+                    success: row.get(4)?,
+                    message: row.get(5)?,
+                    response_time_ms: row.get::<_, Option<i64>>(6)?.map(|t| t.max(0) as u64),
+                    http_status: row.get::<_, Option<i64>>(7)?.map(|s| s as u16),
+                    tested_at: row.get(8)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除早于 `before`（秒）的探测。
+    pub(crate) fn prune_health_probes(&self, before: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute("DELETE FROM health_probes WHERE tested_at < ?1", [before])
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
@@ -0,0 +1,213 @@
+//! 机器本地的 sidecar 持久化库。
+//!
+//! 主库 cc-switch.db 的 schema 与上游项目同步（WebDAV/S3 亦会整库同步到其他
+//! 机器），本仓库不得自行加表/加列或提升版本号。CLI 独有、且只对本机有意义
+//! 的持久化数据（项目归因、告警历史、余额轮询历史、Key 池状态、能力矩阵、
+//! 模型目录快照、编辑历史、切换记录、自动切换规则日志、健康探测等）统一放在
+//! 配置目录下的 `cc-switch-local.db`：
+//!
+//! - 不参与任何备份/同步，也不进入 SQL 导出；
+//! - 无版本化迁移：打开时幂等建表，旧文件缺列时就地 `ALTER TABLE` 补列；
+//! - 各领域的表结构与读写方法放在同级子模块里（与 `database/dao` 的分层一致）。
+//!
+//! 与 [`crate::session_manager::scan_cache_store`] 不同，这里的数据不全是可
+//! 重建的缓存，但仍然只是主库之外的"附加视图"：任何打开/读写失败都应由调用方
+//! 降级处理，绝不能影响代理转发、切换等主流程。
+
+pub(crate) mod alerts;
+pub(crate) mod api_key_state;
+pub(crate) mod capability_matrix;
+pub(crate) mod config_history;
+pub(crate) mod health_probes;
+pub(crate) mod model_catalog;
+pub(crate) mod project_usage;
+pub(crate) mod quota_history;
+pub(crate) mod switch_history;
+pub(crate) mod switch_rules;
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use rusqlite::Connection;
+
+use crate::config::{get_app_config_dir, resolve_config_dir_without_following_user_symlinks};
+use crate::error::AppError;
+
+/// sidecar 库文件名（与主库同目录）。
+const LOCAL_STORE_DB_FILE: &str = "cc-switch-local.db";
+
+/// 进程内共享的 sidecar 连接：首次使用时打开，之后复用同一连接。
+///
+/// 代理在启动时创建一个并放进服务状态，避免每个请求都重新打开库、重复建表。
+#[derive(Clone, Default)]
+pub(crate) struct SharedLocalStore(Arc<OnceLock<Option<LocalStore>>>);
+
+impl SharedLocalStore {
+    /// 打开失败时记录一次日志并返回 `None`，调用方按"无 sidecar"降级。
+    pub(crate) fn get(&self) -> Option<&LocalStore> {
+        self.0
+            .get_or_init(|| {
+                LocalStore::open()
+                    .map_err(|err| log::warn!("open local sidecar store failed: {err}"))
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_store(store: LocalStore) -> Self {
+        let cell = OnceLock::new();
+        let _ = cell.set(Some(store));
+        Self(Arc::new(cell))
+    }
+}
+
+/// 机器本地 sidecar SQLite 存储。
+///
+/// 自持连接、自建表；文件损坏时删除即可，调用方按"无历史"降级。
+pub struct LocalStore {
+    conn: Mutex<Connection>,
+}
+
+impl LocalStore {
+    /// 打开（必要时创建）配置目录下的 sidecar 库。
+    pub fn open() -> Result<Self, AppError> {
+        Self::open_at(&Self::path()?)
+    }
+
+    /// 在指定路径打开 sidecar 库（测试与 `open()` 共用）。
+    pub fn open_at(path: &Path) -> Result<Self, AppError> {
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                crate::database::create_secure_dir_all(parent)?;
+            }
+        }
+        let flags = rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE
+            | rusqlite::OpenFlags::SQLITE_OPEN_CREATE
+            | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX
+            | rusqlite::OpenFlags::SQLITE_OPEN_NOFOLLOW;
+        let conn = Connection::open_with_flags(path, flags)
+            .map_err(|e| AppError::Database(format!("打开本地 sidecar 库失败: {e}")))?;
+        // 与主库一致：含项目路径与用量明细，unix 下收紧为 0600
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+        }
+        Self::from_connection(conn)
+    }
+
+    /// 内存 sidecar 库（仅测试用）。
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self, AppError> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| AppError::Database(format!("打开内存 sidecar 库失败: {e}")))?;
+        Self::from_connection(conn)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self, AppError> {
+        conn.busy_timeout(Duration::from_secs(5))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let _ = conn.pragma_update(None, "journal_mode", "WAL");
+        let _ = conn.pragma_update(None, "synchronous", "NORMAL");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS local_meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 local_meta 表失败: {e}")))?;
+        project_usage::create_tables(&conn)?;
+        alerts::create_tables(&conn)?;
+        api_key_state::create_tables(&conn)?;
+        capability_matrix::create_tables(&conn)?;
+        config_history::create_tables(&conn)?;
+        health_probes::create_tables(&conn)?;
+        model_catalog::create_tables(&conn)?;
+        quota_history::create_tables(&conn)?;
+        switch_history::create_tables(&conn)?;
+        switch_rules::create_tables(&conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// sidecar 库的磁盘路径（不打开连接）。
+    pub fn path() -> Result<PathBuf, AppError> {
+        let config_dir = resolve_config_dir_without_following_user_symlinks(&get_app_config_dir())?;
+        Ok(config_dir.join(LOCAL_STORE_DB_FILE))
+    }
+
+    pub(crate) fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, AppError> {
+        self.conn
+            .lock()
+            .map_err(|_| AppError::Database("本地 sidecar 库连接锁中毒".to_string()))
+    }
+
+    /// 读取一条 `local_meta` 键值（水位线等小状态）。
+    pub(crate) fn get_meta(&self, key: &str) -> Result<Option<String>, AppError> {
+        let conn = self.lock()?;
+        get_meta_on_conn(&conn, key)
+    }
+
+    /// 写入一条 `local_meta` 键值。
+    pub(crate) fn set_meta(&self, key: &str, value: &str) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        set_meta_on_conn(&conn, key, value)
+    }
+}
+
+pub(crate) fn get_meta_on_conn(conn: &Connection, key: &str) -> Result<Option<String>, AppError> {
+    use rusqlite::OptionalExtension;
+
+    conn.query_row(
+        "SELECT value FROM local_meta WHERE key = ?1",
+        [key],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| AppError::Database(e.to_string()))
+}
+
+pub(crate) fn set_meta_on_conn(conn: &Connection, key: &str, value: &str) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO local_meta (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        rusqlite::params![key, value],
+    )
+    .map_err(|e| AppError::Database(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalStore;
+
+    #[test]
+    fn meta_values_round_trip_and_overwrite() {
+        let store = LocalStore::in_memory().expect("open store");
+        assert_eq!(store.get_meta("k").expect("read"), None);
+        store.set_meta("k", "1").expect("write");
+        store.set_meta("k", "2").expect("overwrite");
+        assert_eq!(store.get_meta("k").expect("read").as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn reopening_a_file_store_is_idempotent() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("nested").join("cc-switch-local.db");
+        {
+            let store = LocalStore::open_at(&path).expect("first open");
+            store.set_meta("watermark", "42").expect("write");
+        }
+        let store = LocalStore::open_at(&path).expect("second open");
+        assert_eq!(
+            store.get_meta("watermark").expect("read").as_deref(),
+            Some("42")
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! 供应商模型目录快照（sidecar）。
+//!
+//! 守护进程定期（以及 `provider models refresh` 手动）拉取每个供应商的模型
+//! 列表：`provider_model_snapshots` 只保留每个供应商最近一次的完整列表，
+//! 与上一次相比新增/下线的模型追加到 `provider_model_changes`。首次快照只
+//! 作为基线，不产生变更记录。
+
+use std::collections::BTreeSet;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS provider_model_snapshots (
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            models TEXT NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            PRIMARY KEY (app_type, provider_id)
+        );
+        CREATE TABLE IF NOT EXISTS provider_model_changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            change TEXT NOT NULL,
+            detected_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_provider_model_changes_provider
+            ON provider_model_changes(app_type, provider_id, detected_at);",
+    )
+    .map_err(|e| AppError::Database(format!("创建模型目录表失败: {e}")))
+}
+
+/// 某个供应商最近一次拉取到的模型列表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelSnapshot {
+    pub provider_id: String,
+    /// 去重并排序后的模型 ID
+    pub models: Vec<String>,
+    /// 秒级时间戳
+    pub fetched_at: i64,
+}
+
+/// 一条模型变更记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelChange {
+    pub provider_id: String,
+    pub model: String,
+    /// `added` / `removed`
+    pub change: String,
+    pub detected_at: i64,
+}
+
+/// 相邻两次快照之间的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelCatalogDiff {
+    /// 是否为该供应商的首次快照（此时 added/removed 为空）
+    pub baseline: bool,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ModelCatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+fn normalize_models(models: &[String]) -> Vec<String> {
+    models
+        .iter()
+        .map(|model| model.trim())
+        .filter(|model| !model.is_empty())
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl LocalStore {
+    /// 保存一次拉取结果，并与上一次快照比较、记录新增/下线的模型
+    pub(crate) fn record_model_snapshot(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        models: &[String],
+        fetched_at: i64,
+    ) -> Result<ModelCatalogDiff, AppError> {
+        let models = normalize_models(models);
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let previous: Option<String> = tx
+            .query_row(
+                "SELECT models FROM provider_model_snapshots
+                 WHERE app_type = ?1 AND provider_id = ?2",
+                params![app_type, provider_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let diff = match previous {
+            None => ModelCatalogDiff {
+                baseline: true,
+                ..ModelCatalogDiff::default()
+            },
+            Some(raw) => {
+                let previous: BTreeSet<String> = serde_json::from_str::<Vec<String>>(&raw)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect();
+                let current: BTreeSet<String> = models.iter().cloned().collect();
+                ModelCatalogDiff {
+                    baseline: false,
+                    added: current.difference(&previous).cloned().collect(),
+                    removed: previous.difference(&current).cloned().collect(),
+                }
+            }
+        };
+
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO provider_model_changes
+                        (app_type, provider_id, model, change, detected_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let changes = diff
+                .added
+                .iter()
+                .map(|model| (model, "added"))
+                .chain(diff.removed.iter().map(|model| (model, "removed")));
+            for (model, change) in changes {
+                stmt.execute(params![app_type, provider_id, model, change, fetched_at])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+        }
+
+        let encoded =
+            serde_json::to_string(&models).map_err(|e| AppError::Database(e.to_string()))?;
+        tx.execute(
+            "INSERT OR REPLACE INTO provider_model_snapshots
+             (app_type, provider_id, models, fetched_at) VALUES (?1, ?2, ?3, ?4)",
+            params![app_type, provider_id, encoded, fetched_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(diff)
+    }
+
+    /// 获取某应用下各供应商的最新快照
+    pub(crate) fn list_model_snapshots(
+        &self,
+        app_type: &str,
+    ) -> Result<Vec<ModelSnapshot>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, models, fetched_at FROM provider_model_snapshots
+                 WHERE app_type = ?1 ORDER BY provider_id",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                let raw: String = row.get(1)?;
+                Ok(ModelSnapshot {
+                    provider_id: row.get(0)?,
+                    models: serde_json::from_str(&raw).unwrap_or_default(),
+                    fetched_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 获取 `since` 之后的模型变更（按时间先后）；`provider_id` 为空时返回全部供应商
+    pub(crate) fn list_model_changes(
+        &self,
+        app_type: &str,
+        provider_id: Option<&str>,
+        since: i64,
+    ) -> Result<Vec<ModelChange>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, model, change, detected_at FROM provider_model_changes
+                 WHERE app_type = ?1 AND (?2 IS NULL OR provider_id = ?2) AND detected_at >= ?3
+                 ORDER BY detected_at, id",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id, since], |row| {
+                Ok(ModelChange {
+                    provider_id: row.get(0)?,
+                    model: row.get(1)?,
+                    change: row.get(2)?,
+                    detected_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 清理早于 `before` 的变更记录，返回删除行数
+    pub(crate) fn prune_model_changes(&self, before: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM provider_model_changes WHERE detected_at < ?1",
+            params![before],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::local_store::LocalStore;
+
+    fn models(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn snapshots_record_added_and_removed_models() {
+        let store = LocalStore::in_memory().expect("open store");
+        let first = store
+            .record_model_snapshot("claude", "relay", &models(&["b", "a", "a", " "]), 10)
+            .expect("first snapshot");
+        assert!(first.baseline && first.is_empty());
+
+        let second = store
+            .record_model_snapshot("claude", "relay", &models(&["b", "c"]), 20)
+            .expect("second snapshot");
+        assert_eq!(second.added, models(&["c"]));
+        assert_eq!(second.removed, models(&["a"]));
+
+        let snapshots = store.list_model_snapshots("claude").expect("snapshots");
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].models, models(&["b", "c"]));
+        assert_eq!(snapshots[0].fetched_at, 20);
+
+        let changes = store
+            .list_model_changes("claude", Some("relay"), 0)
+            .expect("changes");
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (change.model.as_str(), change.change.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("c", "added"), ("a", "removed")]
+        );
+        assert!(store
+            .list_model_changes("codex", None, 0)
+            .expect("codex")
+            .is_empty());
+
+        assert_eq!(store.prune_model_changes(21).expect("prune"), 2);
+    }
+}
@@ -0,0 +1,638 @@
+//! 按项目归因的用量表（sidecar）。
+//!
+//! 主库 `proxy_request_logs` 只有 `session_id`，项目路径只存在于会话扫描结果
+//! （[`crate::session_manager::SessionMeta::project_dir`]）里。归因任务把两者
+//! 连接起来后写入这里：
+//!
+//! - `project_request_attribution`：已归因的 request_id，保证每条明细只累加一次；
+//! - `project_attribution_pending`：带 session_id 但会话尚未被扫描到的请求，
+//!   宽限期内每轮重试，过期后归入"未知项目"；
+//! - `project_usage_daily`：按 (日期, app, 项目, provider, 模型) 的日汇总，
+//!   主库明细被 `rollup_and_prune` 删除后仍保留项目维度；
+//! - `project_directory`：项目键到展示路径的映射。
+//!
+//! 项目键为 [`crate::session_manager::project_scope::normalize_project_path`]
+//! 的结果；空字符串表示无法归因（未知项目）。
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::LocalStore;
+use crate::error::AppError;
+
+/// 未知项目的项目键。
+pub(crate) const UNKNOWN_PROJECT_KEY: &str = "";
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS project_request_attribution (
+            request_id TEXT PRIMARY KEY,
+            app_type TEXT NOT NULL,
+            project_key TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_project_request_attribution_created
+            ON project_request_attribution(created_at);
+        CREATE TABLE IF NOT EXISTS project_attribution_pending (
+            request_id TEXT PRIMARY KEY,
+            app_type TEXT NOT NULL,
+            session_id TEXT NOT NULL,
+            first_seen_at INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS project_usage_daily (
+            date TEXT NOT NULL,
+            app_type TEXT NOT NULL,
+            project_key TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            request_count INTEGER NOT NULL DEFAULT 0,
+            success_count INTEGER NOT NULL DEFAULT 0,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_creation_tokens INTEGER NOT NULL DEFAULT 0,
+            total_cost_usd REAL NOT NULL DEFAULT 0,
+            PRIMARY KEY (date, app_type, project_key, provider_id, model)
+        );
+        CREATE INDEX IF NOT EXISTS idx_project_usage_daily_project
+            ON project_usage_daily(project_key, date);
+        CREATE TABLE IF NOT EXISTS project_directory (
+            project_key TEXT PRIMARY KEY,
+            display_path TEXT NOT NULL,
+            last_seen_at INTEGER NOT NULL DEFAULT 0
+        );",
+    )
+    .map_err(|e| AppError::Database(format!("创建项目用量表失败: {e}")))
+}
+
+/// 一条待归因的用量明细（来自主库 `proxy_request_logs`）。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AttributedUsage {
+    pub request_id: String,
+    pub app_type: String,
+    pub provider_id: String,
+    pub model: String,
+    /// 本地日历日 `YYYY-MM-DD`。
+    pub date: String,
+    pub created_at: i64,
+    pub success: bool,
+    /// 已按 fresh-input 语义归一化。
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub total_cost_usd: f64,
+}
+
+/// 待重试的未解析请求。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingAttribution {
+    pub request_id: String,
+    pub app_type: String,
+    pub session_id: String,
+    pub first_seen_at: i64,
+}
+
+/// 项目维度的汇总行（`usage projects` 与 Usage 页共用）。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectUsageRow {
+    /// 项目键；空字符串为未知项目。
+    pub project_key: String,
+    /// 最近一次见到的原始路径写法；未知项目为 None。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_path: Option<String>,
+    pub request_count: u64,
+    pub success_count: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub total_cost_usd: f64,
+}
+
+impl ProjectUsageRow {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            .saturating_add(self.output_tokens)
+            .saturating_add(self.cache_read_tokens)
+            .saturating_add(self.cache_creation_tokens)
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.project_key == UNKNOWN_PROJECT_KEY
+    }
+}
+
+/// 单个项目内按 provider/模型拆分的汇总行。
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBreakdownRow {
+    pub app_type: String,
+    pub provider_id: String,
+    pub model: String,
+    pub request_count: u64,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+}
+
+/// 日期区间（含两端，`YYYY-MM-DD`）与可选的 app 过滤。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ProjectUsageFilter {
+    pub app_type: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+impl ProjectUsageFilter {
+    fn where_clause(&self, alias: &str) -> (String, Vec<String>) {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        if let Some(app_type) = &self.app_type {
+            values.push(app_type.clone());
+            clauses.push(format!("{alias}.app_type = ?{}", values.len()));
+        }
+        if let Some(start) = &self.start_date {
+            values.push(start.clone());
+            clauses.push(format!("{alias}.date >= ?{}", values.len()));
+        }
+        if let Some(end) = &self.end_date {
+            values.push(end.clone());
+            clauses.push(format!("{alias}.date <= ?{}", values.len()));
+        }
+        if clauses.is_empty() {
+            ("1 = 1".to_string(), values)
+        } else {
+            (clauses.join(" AND "), values)
+        }
+    }
+}
+
+impl LocalStore {
+    /// 在一个事务里记录一批归因结果：已归因过的 request_id 被忽略，其余累加到
+    /// 日汇总并从 pending 表移除。返回实际新归因的条数。
+    pub(crate) fn record_project_attributions(
+        &self,
+        entries: &[(AttributedUsage, String)],
+    ) -> Result<usize, AppError> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut recorded = 0usize;
+        {
+            let mut claim = tx
+                .prepare_cached(
+                    "INSERT OR IGNORE INTO project_request_attribution
+                        (request_id, app_type, project_key, created_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let mut accumulate = tx
+                .prepare_cached(
+                    "INSERT INTO project_usage_daily (
+                        date, app_type, project_key, provider_id, model,
+                        request_count, success_count, input_tokens, output_tokens,
+                        cache_read_tokens, cache_creation_tokens, total_cost_usd
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?7, ?8, ?9, ?10, ?11)
+                    ON CONFLICT(date, app_type, project_key, provider_id, model) DO UPDATE SET
+                        request_count = request_count + 1,
+                        success_count = success_count + excluded.success_count,
+                        input_tokens = input_tokens + excluded.input_tokens,
+                        output_tokens = output_tokens + excluded.output_tokens,
+                        cache_read_tokens = cache_read_tokens + excluded.cache_read_tokens,
+                        cache_creation_tokens = cache_creation_tokens + excluded.cache_creation_tokens,
+                        total_cost_usd = total_cost_usd + excluded.total_cost_usd",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let mut clear_pending = tx
+                .prepare_cached("DELETE FROM project_attribution_pending WHERE request_id = ?1")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+            for (usage, project_key) in entries {
+                let claimed = claim
+                    .execute(params![
+                        usage.request_id,
+                        usage.app_type,
+                        project_key,
+                        usage.created_at
+                    ])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+                clear_pending
+                    .execute([&usage.request_id])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+                if claimed == 0 {
+                    continue;
+                }
+                accumulate
+                    .execute(params![
+                        usage.date,
+                        usage.app_type,
+                        project_key,
+                        usage.provider_id,
+                        usage.model,
+                        i64::from(usage.success),
+                        saturating_i64(usage.input_tokens),
+                        saturating_i64(usage.output_tokens),
+                        saturating_i64(usage.cache_read_tokens),
+                        saturating_i64(usage.cache_creation_tokens),
+                        usage.total_cost_usd.max(0.0),
+                    ])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+                recorded += 1;
+            }
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(recorded)
+    }
+
+    /// 记录项目键的展示路径（最近一次见到的写法胜出）。
+    pub(crate) fn upsert_project_directories(
+        &self,
+        entries: &[(String, String, i64)],
+    ) -> Result<(), AppError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO project_directory (project_key, display_path, last_seen_at)
+                     VALUES (?1, ?2, ?3)
+                     ON CONFLICT(project_key) DO UPDATE SET
+                        display_path = CASE WHEN excluded.last_seen_at >= last_seen_at
+                            THEN excluded.display_path ELSE display_path END,
+                        last_seen_at = MAX(last_seen_at, excluded.last_seen_at)",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            for (key, display, seen_at) in entries {
+                stmt.execute(params![key, display, seen_at])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 把暂时无法解析的请求放入 pending 表（已存在则保留首次时间）。
+    pub(crate) fn defer_project_attributions(
+        &self,
+        pending: &[PendingAttribution],
+    ) -> Result<(), AppError> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT OR IGNORE INTO project_attribution_pending
+                        (request_id, app_type, session_id, first_seen_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            for item in pending {
+                stmt.execute(params![
+                    item.request_id,
+                    item.app_type,
+                    item.session_id,
+                    item.first_seen_at
+                ])
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 读取最多 `limit` 条 pending 请求（最早的优先）。
+    pub(crate) fn load_pending_project_attributions(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<PendingAttribution>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT request_id, app_type, session_id, first_seen_at
+                 FROM project_attribution_pending
+                 ORDER BY first_seen_at ASC
+                 LIMIT ?1",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([limit as i64], |row| {
+                Ok(PendingAttribution {
+                    request_id: row.get(0)?,
+                    app_type: row.get(1)?,
+                    session_id: row.get(2)?,
+                    first_seen_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 丢弃 pending 请求（主库明细已不存在时）。
+    pub(crate) fn drop_pending_project_attributions(
+        &self,
+        request_ids: &[String],
+    ) -> Result<(), AppError> {
+        if request_ids.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let mut stmt = tx
+                .prepare_cached("DELETE FROM project_attribution_pending WHERE request_id = ?1")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            for request_id in request_ids {
+                stmt.execute([request_id])
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除早于 `cutoff` 的 request_id 认领记录。日汇总不受影响；主库明细此时
+    /// 早已被 rollup 清理，不会再被重复归因。
+    pub(crate) fn prune_project_request_claims(&self, cutoff: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM project_request_attribution WHERE created_at < ?1",
+            [cutoff],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 项目维度汇总，按费用降序。
+    pub(crate) fn project_usage_rows(
+        &self,
+        filter: &ProjectUsageFilter,
+        limit: Option<usize>,
+    ) -> Result<Vec<ProjectUsageRow>, AppError> {
+        let conn = self.lock()?;
+        let (where_clause, values) = filter.where_clause("d");
+        let limit_clause = limit
+            .map(|limit| format!("LIMIT {limit}"))
+            .unwrap_or_default();
+        let sql = format!(
+            "SELECT d.project_key, dir.display_path,
+                    SUM(d.request_count), SUM(d.success_count),
+                    SUM(d.input_tokens), SUM(d.output_tokens),
+                    SUM(d.cache_read_tokens), SUM(d.cache_creation_tokens),
+                    SUM(d.total_cost_usd)
+             FROM project_usage_daily d
+             LEFT JOIN project_directory dir ON dir.project_key = d.project_key
+             WHERE {where_clause}
+             GROUP BY d.project_key
+             ORDER BY SUM(d.total_cost_usd) DESC, SUM(d.request_count) DESC, d.project_key ASC
+             {limit_clause}"
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                Ok(ProjectUsageRow {
+                    project_key: row.get(0)?,
+                    display_path: row.get(1)?,
+                    request_count: non_negative(row.get(2)?),
+                    success_count: non_negative(row.get(3)?),
+                    input_tokens: non_negative(row.get(4)?),
+                    output_tokens: non_negative(row.get(5)?),
+                    cache_read_tokens: non_negative(row.get(6)?),
+                    cache_creation_tokens: non_negative(row.get(7)?),
+                    total_cost_usd: row.get::<_, f64>(8)?.max(0.0),
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 单个项目内按 app/provider/模型拆分，按费用降序。
+    pub(crate) fn project_usage_breakdown(
+        &self,
+        project_key: &str,
+        filter: &ProjectUsageFilter,
+    ) -> Result<Vec<ProjectBreakdownRow>, AppError> {
+        let conn = self.lock()?;
+        let (where_clause, mut values) = filter.where_clause("d");
+        values.push(project_key.to_string());
+        let key_param = values.len();
+        let sql = format!(
+            "SELECT d.app_type, d.provider_id, d.model,
+                    SUM(d.request_count),
+                    SUM(d.input_tokens + d.output_tokens + d.cache_read_tokens + d.cache_creation_tokens),
+                    SUM(d.total_cost_usd)
+             FROM project_usage_daily d
+             WHERE {where_clause} AND d.project_key = ?{key_param}
+             GROUP BY d.app_type, d.provider_id, d.model
+             ORDER BY SUM(d.total_cost_usd) DESC, SUM(d.request_count) DESC"
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                Ok(ProjectBreakdownRow {
+                    app_type: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    model: row.get(2)?,
+                    request_count: non_negative(row.get(3)?),
+                    total_tokens: non_negative(row.get(4)?),
+                    total_cost_usd: row.get::<_, f64>(5)?.max(0.0),
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 项目键对应的展示路径。
+    pub(crate) fn project_display_path(
+        &self,
+        project_key: &str,
+    ) -> Result<Option<String>, AppError> {
+        let conn = self.lock()?;
+        conn.query_row(
+            "SELECT display_path FROM project_directory WHERE project_key = ?1",
+            [project_key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+fn saturating_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn non_negative(value: i64) -> u64 {
+    value.max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributedUsage, PendingAttribution, ProjectUsageFilter, UNKNOWN_PROJECT_KEY};
+    use crate::local_store::LocalStore;
+
+    fn usage(request_id: &str, date: &str, cost: f64) -> AttributedUsage {
+        AttributedUsage {
+            request_id: request_id.to_string(),
+            app_type: "claude".to_string(),
+            provider_id: "relay".to_string(),
+            model: "claude-sonnet-4".to_string(),
+            date: date.to_string(),
+            created_at: 1_700_000_000,
+            success: true,
+            input_tokens: 10,
+            output_tokens: 20,
+            cache_read_tokens: 5,
+            cache_creation_tokens: 0,
+            total_cost_usd: cost,
+        }
+    }
+
+    #[test]
+    fn attribution_is_counted_once_per_request() {
+        let store = LocalStore::in_memory().expect("store");
+        let entries = vec![
+            (usage("r1", "2026-10-01", 0.5), "posix:/repo/a".to_string()),
+            (usage("r2", "2026-10-02", 0.25), "posix:/repo/a".to_string()),
+        ];
+        assert_eq!(store.record_project_attributions(&entries).unwrap(), 2);
+        assert_eq!(store.record_project_attributions(&entries).unwrap(), 0);
+
+        let rows = store
+            .project_usage_rows(&ProjectUsageFilter::default(), None)
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].request_count, 2);
+        assert!((rows[0].total_cost_usd - 0.75).abs() < 1e-9);
+        assert_eq!(rows[0].total_tokens(), 70);
+    }
+
+    #[test]
+    fn rows_respect_date_and_app_filters_and_sort_by_cost() {
+        let store = LocalStore::in_memory().expect("store");
+        let mut codex = usage("r3", "2026-10-03", 9.0);
+        codex.app_type = "codex".to_string();
+        store
+            .record_project_attributions(&[
+                (usage("r1", "2026-09-30", 1.0), "posix:/repo/a".to_string()),
+                (usage("r2", "2026-10-01", 2.0), "posix:/repo/b".to_string()),
+                (codex, "posix:/repo/a".to_string()),
+                (
+                    usage("r4", "2026-10-01", 0.1),
+                    UNKNOWN_PROJECT_KEY.to_string(),
+                ),
+            ])
+            .unwrap();
+        store
+            .upsert_project_directories(&[
+                ("posix:/repo/a".to_string(), "/repo/a".to_string(), 10),
+                ("posix:/repo/b".to_string(), "/repo/b".to_string(), 10),
+            ])
+            .unwrap();
+
+        let october_claude = ProjectUsageFilter {
+            app_type: Some("claude".to_string()),
+            start_date: Some("2026-10-01".to_string()),
+            end_date: Some("2026-10-31".to_string()),
+        };
+        let rows = store.project_usage_rows(&october_claude, None).unwrap();
+        let keys = rows
+            .iter()
+            .map(|row| row.project_key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["posix:/repo/b", UNKNOWN_PROJECT_KEY]);
+        assert_eq!(rows[0].display_path.as_deref(), Some("/repo/b"));
+        assert!(rows[1].is_unknown());
+
+        let all = store
+            .project_usage_rows(&ProjectUsageFilter::default(), Some(1))
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].project_key, "posix:/repo/a");
+
+        let breakdown = store
+            .project_usage_breakdown("posix:/repo/a", &ProjectUsageFilter::default())
+            .unwrap();
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0].app_type, "codex");
+    }
+
+    #[test]
+    fn pending_rows_keep_first_seen_and_clear_on_attribution() {
+        let store = LocalStore::in_memory().expect("store");
+        let pending = PendingAttribution {
+            request_id: "r1".to_string(),
+            app_type: "claude".to_string(),
+            session_id: "s1".to_string(),
+            first_seen_at: 100,
+        };
+        store
+            .defer_project_attributions(&[pending.clone()])
+            .unwrap();
+        store
+            .defer_project_attributions(&[PendingAttribution {
+                first_seen_at: 500,
+                ..pending.clone()
+            }])
+            .unwrap();
+        assert_eq!(
+            store.load_pending_project_attributions(10).unwrap(),
+            vec![pending]
+        );
+
+        store
+            .record_project_attributions(&[(
+                usage("r1", "2026-10-01", 1.0),
+                "posix:/repo/a".to_string(),
+            )])
+            .unwrap();
+        assert!(store
+            .load_pending_project_attributions(10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn directory_keeps_most_recent_spelling() {
+        let store = LocalStore::in_memory().expect("store");
+        store
+            .upsert_project_directories(&[(
+                "windows:c:/repo".to_string(),
+                "C:\\Repo".to_string(),
+                20,
+            )])
+            .unwrap();
+        store
+            .upsert_project_directories(&[(
+                "windows:c:/repo".to_string(),
+                "c:/repo".to_string(),
+                10,
+            )])
+            .unwrap();
+        assert_eq!(
+            store
+                .project_display_path("windows:c:/repo")
+                .unwrap()
+                .as_deref(),
+            Some("C:\\Repo")
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! 余额/额度查询历史（sidecar）。
+//!
+//! 每次查询（守护进程定时轮询、`provider quota`、TUI 刷新）都会写入
+//! `quota_samples`：成功时每个套餐档位一行，失败时写一行 `tier = ''`、
+//! `success = 0` 的记录。失败行同样参与"上次查询时间"的判断，避免对持续
+//! 报错的供应商高频重试。
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS quota_samples (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            provider_id TEXT NOT NULL,
+            source TEXT NOT NULL,
+            queried_at INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            tier TEXT NOT NULL DEFAULT '',
+            remaining REAL,
+            total REAL,
+            used REAL,
+            unit TEXT,
+            error TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_quota_samples_provider
+            ON quota_samples(app_type, provider_id, queried_at);",
+    )
+    .map_err(|e| AppError::Database(format!("创建额度历史表失败: {e}")))
+}
+
+/// 一条额度采样。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaSample {
+    pub app_type: String,
+    pub provider_id: String,
+    /// 查询来源：`daemon` / `cli` / `tui`。
+    pub source: String,
+    /// 毫秒时间戳（与 `provider quota --json` 的 `queriedAt` 一致）。
+    pub queried_at: i64,
+    pub success: bool,
+    pub tier: String,
+    pub remaining: Option<f64>,
+    pub total: Option<f64>,
+    pub used: Option<f64>,
+    pub unit: Option<String>,
+    pub error: Option<String>,
+}
+
+impl LocalStore {
+    /// 写入一次查询产生的全部采样行（同一事务）。
+    pub(crate) fn record_quota_samples(&self, samples: &[QuotaSample]) -> Result<(), AppError> {
+        let mut conn = self.lock()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO quota_samples
+                        (app_type, provider_id, source, queried_at, success, tier,
+                         remaining, total, used, unit, error)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            for sample in samples {
+                stmt.execute(params![
+                    sample.app_type,
+                    sample.provider_id,
+                    sample.source,
+                    sample.queried_at,
+                    sample.success,
+                    sample.tier,
+                    sample.remaining,
+                    sample.total,
+                    sample.used,
+                    sample.unit,
+                    sample.error,
+                ])
+                .map_err(|e| AppError::Database(format!("写入额度历史失败: {e}")))?;
+            }
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 某供应商最近一次查询时间（毫秒，含失败记录）。
+    pub(crate) fn last_quota_query_at(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Option<i64>, AppError> {
+        let conn = self.lock()?;
+        conn.query_row(
+            "SELECT MAX(queried_at) FROM quota_samples WHERE app_type = ?1 AND provider_id = ?2",
+            params![app_type, provider_id],
+            |row| row.get::<_, Option<i64>>(0),
+        )
+        .optional()
+        .map(Option::flatten)
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 按时间正序读取 `since`（毫秒）之后的采样；`provider` 为空时返回该
+    /// 应用下全部供应商。
+    pub fn quota_samples_since(
+        &self,
+        app_type: &str,
+        provider_id: Option<&str>,
+        since: i64,
+    ) -> Result<Vec<QuotaSample>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_type, provider_id, source, queried_at, success, tier,
+                        remaining, total, used, unit, error
+                 FROM quota_samples
+                 WHERE app_type = ?1
+                   AND (?2 IS NULL OR provider_id = ?2)
+                   AND queried_at >= ?3
+                 ORDER BY queried_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id, since], |row| {
+                Ok(QuotaSample {
+                    app_type: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    source: row.get(2)?,
+                    queried_at: row.get(3)?,
+                    success: row.get(4)?,
+                    tier: row.get(5)?,
+                    remaining: row.get(6)?,
+                    total: row.get(7)?,
+                    used: row.get(8)?,
+                    unit: row.get(9)?,
+                    error: row.get(10)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除早于 `cutoff`（毫秒）的采样。
+    pub(crate) fn prune_quota_samples(&self, cutoff: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute("DELETE FROM quota_samples WHERE queried_at < ?1", [cutoff])
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuotaSample;
+    use crate::local_store::LocalStore;
+
+    fn sample(provider: &str, at: i64, success: bool, remaining: Option<f64>) -> QuotaSample {
+        QuotaSample {
+            app_type: "claude".to_string(),
+            provider_id: provider.to_string(),
+            source: "daemon".to_string(),
+            queried_at: at,
+            success,
+            tier: if success {
+                "balance".to_string()
+            } else {
+                String::new()
+            },
+            remaining,
+            total: None,
+            used: None,
+            unit: Some("USD".to_string()),
+            error: (!success).then(|| "timeout".to_string()),
+        }
+    }
+
+    #[test]
+    fn samples_round_trip_and_failed_queries_count_as_last_query() {
+        let store = LocalStore::in_memory().expect("open store");
+        store
+            .record_quota_samples(&[sample("relay", 1_000, true, Some(10.0))])
+            .expect("record");
+        store
+            .record_quota_samples(&[sample("relay", 2_000, false, None)])
+            .expect("record failure");
+        store
+            .record_quota_samples(&[sample("other", 3_000, true, Some(5.0))])
+            .expect("record other");
+
+        assert_eq!(
+            store.last_quota_query_at("claude", "relay").expect("last"),
+            Some(2_000)
+        );
+        assert_eq!(
+            store.last_quota_query_at("codex", "relay").expect("last"),
+            None
+        );
+
+        let relay = store
+            .quota_samples_since("claude", Some("relay"), 0)
+            .expect("samples");
+        assert_eq!(relay.len(), 2);
+        assert_eq!(relay[0].remaining, Some(10.0));
+        assert_eq!(relay[1].error.as_deref(), Some("timeout"));
+        assert_eq!(
+            store
+                .quota_samples_since("claude", None, 1_500)
+                .expect("all")
+                .len(),
+            2
+        );
+
+        assert_eq!(store.prune_quota_samples(2_500).expect("prune"), 2);
+    }
+}
@@ -0,0 +1,155 @@
+//! 供应商切换记录（sidecar）。
+//!
+//! 每次当前供应商发生变化（CLI / TUI / 故障转移等）都追加一行，供
+//! `cc-switch use -` 回到上一个供应商以及切换历史列表使用。只保留最近
+//! 若干条记录。
+
+use rusqlite::{params, Connection};
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS provider_switches (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            app_type TEXT NOT NULL,
+            from_id TEXT,
+            to_id TEXT NOT NULL,
+            source TEXT NOT NULL,
+            switched_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_provider_switches_app
+            ON provider_switches (app_type, id);",
+    )
+    .map_err(|e| AppError::Database(format!("创建切换记录表失败: {e}")))
+}
+
+/// 一次供应商切换
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSwitchRecord {
+    pub app_type: String,
+    /// 切换前的供应商；首次选择时为空
+    pub from_id: Option<String>,
+    pub to_id: String,
+    /// `cli` / `tui` / `failover` / `schedule` / `other`
+    pub source: String,
+    /// 秒级时间戳
+    pub switched_at: i64,
+}
+
+impl LocalStore {
+    /// 追加一条切换记录，并只保留最近 `keep` 条
+    pub(crate) fn record_provider_switch(
+        &self,
+        record: &ProviderSwitchRecord,
+        keep: i64,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO provider_switches (app_type, from_id, to_id, source, switched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                record.app_type,
+                record.from_id,
+                record.to_id,
+                record.source,
+                record.switched_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM provider_switches
+             WHERE id <= (SELECT MAX(id) FROM provider_switches) - ?1",
+            params![keep],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 最近的切换记录（新的在前）；`app_type` 为空时返回所有应用
+    pub(crate) fn list_provider_switches(
+        &self,
+        app_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ProviderSwitchRecord>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_type, from_id, to_id, source, switched_at FROM provider_switches
+                 WHERE ?1 IS NULL OR app_type = ?1
+                 ORDER BY id DESC LIMIT ?2",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, limit as i64], |row| {
+                Ok(ProviderSwitchRecord {
+                    app_type: row.get(0)?,
+                    from_id: row.get(1)?,
+                    to_id: row.get(2)?,
+                    source: row.get(3)?,
+                    switched_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProviderSwitchRecord;
+    use crate::local_store::LocalStore;
+
+    fn record(app: &str, from: Option<&str>, to: &str, at: i64) -> ProviderSwitchRecord {
+        ProviderSwitchRecord {
+            app_type: app.to_string(),
+            from_id: from.map(str::to_string),
+            to_id: to.to_string(),
+            source: "cli".to_string(),
+            switched_at: at,
+        }
+    }
+
+    #[test]
+    fn switches_are_listed_newest_first_and_pruned() {
+        let store = LocalStore::in_memory().expect("open store");
+        store
+            .record_provider_switch(&record("claude", None, "a", 1), 3)
+            .expect("record");
+        store
+            .record_provider_switch(&record("codex", Some("x"), "y", 2), 3)
+            .expect("record");
+        store
+            .record_provider_switch(&record("claude", Some("a"), "b", 3), 3)
+            .expect("record");
+
+        let claude = store
+            .list_provider_switches(Some("claude"), 10)
+            .expect("list");
+        assert_eq!(
+            claude
+                .iter()
+                .map(|switch| switch.to_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+        assert_eq!(claude[0].from_id.as_deref(), Some("a"));
+        assert_eq!(
+            store.list_provider_switches(None, 10).expect("all").len(),
+            3
+        );
+
+        store
+            .record_provider_switch(&record("claude", Some("b"), "a", 4), 3)
+            .expect("record");
+        let all = store.list_provider_switches(None, 10).expect("all");
+        assert_eq!(all.len(), 3);
+        assert_eq!(
+            all.last().map(|switch| switch.app_type.as_str()),
+            Some("codex")
+        );
+    }
+}
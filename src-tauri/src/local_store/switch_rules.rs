@@ -0,0 +1,145 @@
+//! 自动切换规则的触发日志（sidecar）。
+//!
+//! 守护进程每次因规则触发而切换供应商（无论成功与否）都记录一行，供
+//! `provider rules log` 查看；失败时保存错误信息。
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use super::LocalStore;
+use crate::error::AppError;
+
+pub(super) fn create_tables(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS switch_rule_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            rule TEXT NOT NULL,
+            app_type TEXT NOT NULL,
+            from_id TEXT,
+            to_id TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            error TEXT,
+            fired_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_switch_rule_log_fired
+            ON switch_rule_log (fired_at);",
+    )
+    .map_err(|e| AppError::Database(format!("创建切换规则日志表失败: {e}")))
+}
+
+/// 一次规则触发
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchRuleFiring {
+    pub rule: String,
+    pub app_type: String,
+    pub from_id: Option<String>,
+    pub to_id: String,
+    /// 条件成立的原因，如 `22:00-08:00`
+    pub reason: String,
+    /// 切换失败时的错误信息
+    pub error: Option<String>,
+    /// 秒级时间戳
+    pub fired_at: i64,
+}
+
+impl LocalStore {
+    /// 记录一次规则触发
+    pub(crate) fn record_switch_rule_firing(
+        &self,
+        firing: &SwitchRuleFiring,
+    ) -> Result<(), AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "INSERT INTO switch_rule_log
+                (rule, app_type, from_id, to_id, reason, error, fired_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                firing.rule,
+                firing.app_type,
+                firing.from_id,
+                firing.to_id,
+                firing.reason,
+                firing.error,
+                firing.fired_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 最近的规则触发记录（新的在前）
+    pub(crate) fn list_switch_rule_firings(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<SwitchRuleFiring>, AppError> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT rule, app_type, from_id, to_id, reason, error, fired_at
+                 FROM switch_rule_log ORDER BY id DESC LIMIT ?1",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok(SwitchRuleFiring {
+                    rule: row.get(0)?,
+                    app_type: row.get(1)?,
+                    from_id: row.get(2)?,
+                    to_id: row.get(3)?,
+                    reason: row.get(4)?,
+                    error: row.get(5)?,
+                    fired_at: row.get(6)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 删除早于 `cutoff` 的触发记录，返回删除行数
+    pub(crate) fn prune_switch_rule_log(&self, cutoff: i64) -> Result<usize, AppError> {
+        let conn = self.lock()?;
+        conn.execute(
+            "DELETE FROM switch_rule_log WHERE fired_at < ?1",
+            params![cutoff],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SwitchRuleFiring;
+    use crate::local_store::LocalStore;
+
+    #[test]
+    fn firings_are_listed_newest_first_and_pruned_by_age() {
+        let store = LocalStore::in_memory().expect("open store");
+        for (rule, at, error) in [("night", 10, None), ("budget", 20, Some("boom"))] {
+            store
+                .record_switch_rule_firing(&SwitchRuleFiring {
+                    rule: rule.to_string(),
+                    app_type: "claude".to_string(),
+                    from_id: Some("official".to_string()),
+                    to_id: "relay".to_string(),
+                    reason: "22:00-08:00".to_string(),
+                    error: error.map(str::to_string),
+                    fired_at: at,
+                })
+                .expect("record");
+        }
+
+        let firings = store.list_switch_rule_firings(10).expect("list");
+        assert_eq!(firings.len(), 2);
+        assert_eq!(firings[0].rule, "budget");
+        assert_eq!(firings[0].error.as_deref(), Some("boom"));
+
+        assert_eq!(store.prune_switch_rule_log(15).expect("prune"), 1);
+        assert_eq!(
+            store.list_switch_rule_firings(10).expect("list")[0].rule,
+            "budget"
+        );
+    }
+}
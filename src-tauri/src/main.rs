@@ -58,7 +58,16 @@ fn run(cli: Cli) -> Result<(), AppError> {
             cc_switch_lib::cli::commands::provider::ProviderCommand::Switch { id },
             cli.app,
         ),
+        Some(Commands::Universal(cmd)) => {
+            cc_switch_lib::cli::commands::universal::execute(cmd, cli.app)
+        }
+        Some(Commands::Profile(cmd)) => cc_switch_lib::cli::commands::profile::execute(cmd),
+        Some(Commands::Bundle(cmd)) => cc_switch_lib::cli::commands::bundle::execute(cmd, cli.app),
         Some(Commands::Mcp(cmd)) => cc_switch_lib::cli::commands::mcp::execute(cmd, cli.app),
+        Some(Commands::Hooks(cmd)) => cc_switch_lib::cli::commands::hooks::execute(cmd, cli.app),
+        Some(Commands::Permissions(cmd)) => {
+            cc_switch_lib::cli::commands::permissions::execute(cmd, cli.app)
+        }
         Some(Commands::Prompts(cmd)) => {
             cc_switch_lib::cli::commands::prompts::execute(cmd, cli.app)
         }
@@ -69,6 +78,7 @@ fn run(cli: Cli) -> Result<(), AppError> {
         Some(Commands::Failover(cmd)) => {
             cc_switch_lib::cli::commands::failover::execute(cmd, cli.app)
         }
+        Some(Commands::Usage(cmd)) => cc_switch_lib::cli::commands::usage::execute(cmd, cli.app),
         Some(Commands::Sessions(cmd)) => {
             cc_switch_lib::cli::commands::sessions::execute(cmd, cli.app)
         }
@@ -77,7 +87,10 @@ fn run(cli: Cli) -> Result<(), AppError> {
         Some(Commands::Start(cmd)) => cc_switch_lib::cli::commands::start::execute(cmd),
         #[cfg(unix)]
         Some(Commands::Daemon(cmd)) => cc_switch_lib::cli::commands::daemon::execute(cmd),
+        Some(Commands::Hook(cmd)) => cc_switch_lib::cli::commands::hook::execute(cmd),
         Some(Commands::Env(cmd)) => cc_switch_lib::cli::commands::env::execute(cmd, cli.app),
+        Some(Commands::Run(cmd)) => cc_switch_lib::cli::commands::run::execute(cmd, cli.app),
+        Some(Commands::Vault(cmd)) => cc_switch_lib::cli::commands::vault::execute(cmd),
         Some(Commands::Deeplink(cmd)) => {
             cc_switch_lib::cli::commands::deeplink::execute(cmd, cli.app)
         }
@@ -95,6 +108,7 @@ fn command_requires_startup_state(command: &Option<Commands>) -> bool {
 
     match command {
         Some(Commands::Completions(_))
+        | Some(Commands::Hook(_))
         | Some(Commands::Auth(_))
         | Some(Commands::Update(_))
         | Some(Commands::Internal(_))
@@ -129,7 +143,7 @@ fn command_uses_deferred_codex_migration(command: &Option<Commands>) -> bool {
 fn database_access_required(command: &Option<Commands>) -> bool {
     !matches!(
         command,
-        Some(Commands::Completions(_)) | Some(Commands::Update(_))
+        Some(Commands::Completions(_)) | Some(Commands::Hook(_)) | Some(Commands::Update(_))
     )
 }
 
@@ -35,14 +35,19 @@ pub(crate) fn resolve_image_input_capability(
 
 /// Resolve a model's image-input capability from the provider settings shapes
 /// accepted by the proxy (`modelCatalog.models`, `modelCatalog`, or `models`).
+///
+/// `probed_support` is the verdict of `provider probe` for this provider and
+/// model. An explicit declaration still wins over it, so users can correct a
+/// probe that misread an upstream.
 pub(crate) fn image_input_capability_from_settings(
     settings: &Value,
     model: &str,
+    probed_support: Option<bool>,
     use_confirmed_registry: bool,
 ) -> ImageInputCapability {
     resolve_image_input_capability(
         model,
-        declared_model_image_support(settings, model),
+        declared_model_image_support(settings, model).or(probed_support),
         use_confirmed_registry,
     )
 }
@@ -265,12 +270,32 @@ mod tests {
         });
 
         assert_eq!(
-            image_input_capability_from_settings(&settings, "vision", true),
+            image_input_capability_from_settings(&settings, "vision", None, true),
+            ImageInputCapability::Supported
+        );
+        assert_eq!(
+            image_input_capability_from_settings(&settings, "text", None, true),
+            ImageInputCapability::Unsupported
+        );
+    }
+
+    #[test]
+    fn probe_verdict_fills_in_for_undeclared_models() {
+        let settings = json!({
+            "modelCatalog": { "models": [{ "model": "vision", "supportsImage": true }] }
+        });
+
+        assert_eq!(
+            image_input_capability_from_settings(&settings, "vision", Some(false), true),
             ImageInputCapability::Supported
         );
         assert_eq!(
-            image_input_capability_from_settings(&settings, "text", true),
+            image_input_capability_from_settings(&settings, "gpt-5.4", Some(false), true),
             ImageInputCapability::Unsupported
         );
+        assert_eq!(
+            image_input_capability_from_settings(&settings, "deepseek-v4-pro", Some(true), true),
+            ImageInputCapability::Supported
+        );
     }
 }
@@ -0,0 +1,669 @@
+//! Data-driven provider preset catalog.
+//!
+//! A catalog is a versioned JSON or TOML document with model role defaults and
+//! provider presets (per-app settings skeletons, provider meta and an optional
+//! Usage Query template). Catalogs are layered, later ones overriding presets
+//! by id and model roles by name:
+//!
+//! 1. the built-in catalog embedded in the binary (`presets/builtin.json`);
+//! 2. the last verified download from the configured catalog URL
+//!    (`~/.cc-switch/preset-catalog.remote.json`);
+//! 3. user and team catalogs in `~/.cc-switch/presets.d/` (`*.json`,
+//!    `*.toml`, applied in file name order).
+//!
+//! String values and object keys in settings skeletons may reference model
+//! roles as `${model:<role>}`, so a new model release only needs a catalog
+//! update. The same roles back the model defaults in
+//! [`crate::provider_preset_models`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::config::get_app_config_dir;
+use crate::error::AppError;
+use crate::provider::{Provider, ProviderMeta, UsageScript};
+
+/// Highest catalog format version this build understands.
+pub(crate) const CATALOG_FORMAT_VERSION: u32 = 1;
+pub(crate) const PRESETS_DIR: &str = "presets.d";
+pub(crate) const REMOTE_CATALOG_FILE: &str = "preset-catalog.remote.json";
+
+const BUILTIN_CATALOG: &str = include_str!("../presets/builtin.json");
+const MODEL_PLACEHOLDER_PREFIX: &str = "${model:";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetCatalog {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Model role defaults, e.g. `claude.sonnet` → `claude-sonnet-5`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, String>,
+    #[serde(default)]
+    pub presets: Vec<CatalogPreset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogPreset {
+    pub id: String,
+    pub name: String,
+    /// Label in template pickers; defaults to the name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    /// Sign-up link of partner presets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub register_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promo_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    /// Model role overrides that only apply to this preset.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, String>,
+    /// Usage Query (`meta.usage_script`) shared by every app of the preset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_query: Option<Value>,
+    /// Per-app templates keyed by app id (`claude`, `codex`, ...).
+    #[serde(default)]
+    pub apps: BTreeMap<String, PresetAppTemplate>,
+}
+
+impl CatalogPreset {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetAppTemplate {
+    /// `settings_config` skeleton for the app.
+    pub settings: Value,
+    /// Provider meta in its stored (camelCase) shape.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+    /// Overrides the preset-level Usage Query for this app.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_query: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "path")]
+pub enum CatalogSource {
+    Builtin,
+    Remote,
+    File(PathBuf),
+}
+
+impl CatalogSource {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Builtin => "built-in".to_string(),
+            Self::Remote => "remote".to_string(),
+            Self::File(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadedPreset {
+    pub preset: CatalogPreset,
+    pub source: CatalogSource,
+}
+
+/// The merged view of every catalog layer.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedCatalog {
+    pub models: BTreeMap<String, String>,
+    pub presets: Vec<LoadedPreset>,
+    /// Catalog files that were skipped, with the reason.
+    pub warnings: Vec<String>,
+}
+
+impl LoadedCatalog {
+    fn merge(&mut self, catalog: PresetCatalog, source: CatalogSource) {
+        self.models.extend(catalog.models);
+        for preset in catalog.presets {
+            let loaded = LoadedPreset {
+                preset,
+                source: source.clone(),
+            };
+            match self
+                .presets
+                .iter_mut()
+                .find(|existing| existing.preset.id == loaded.preset.id)
+            {
+                Some(existing) => *existing = loaded,
+                None => self.presets.push(loaded),
+            }
+        }
+    }
+
+    pub fn presets_for_app(&self, app_type: &AppType) -> impl Iterator<Item = &LoadedPreset> {
+        let app = app_type.as_str();
+        self.presets
+            .iter()
+            .filter(move |loaded| loaded.preset.apps.contains_key(app))
+    }
+
+    pub fn find(&self, app_type: &AppType, id: &str) -> Option<&LoadedPreset> {
+        self.presets_for_app(app_type)
+            .find(|loaded| loaded.preset.id.eq_ignore_ascii_case(id.trim()))
+    }
+
+    /// Build a provider seed from a preset. The provider id is the preset id;
+    /// callers pick a unique id before saving.
+    pub fn build_provider(&self, app_type: &AppType, id: &str) -> Result<Provider, AppError> {
+        let loaded = self.find(app_type, id).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Preset '{}' not found for {}. Available presets: {}",
+                id,
+                app_type.as_str(),
+                self.preset_ids(app_type)
+            ))
+        })?;
+        let preset = &loaded.preset;
+        let template = &preset.apps[app_type.as_str()];
+
+        let mut models = self.models.clone();
+        models.extend(preset.models.clone());
+        let mut settings_config = template.settings.clone();
+        substitute_models(&mut settings_config, &models)
+            .map_err(|role| unknown_model_role_error(&preset.id, &role))?;
+
+        let mut meta = match &template.meta {
+            Some(meta) => Some(
+                serde_json::from_value::<ProviderMeta>(meta.clone()).map_err(|e| {
+                    AppError::InvalidInput(format!("Preset '{}' has invalid meta: {e}", preset.id))
+                })?,
+            ),
+            None => None,
+        };
+        if let Some(usage_query) = template
+            .usage_query
+            .as_ref()
+            .or(preset.usage_query.as_ref())
+        {
+            meta.get_or_insert_with(ProviderMeta::default).usage_script =
+                Some(usage_script_from_template(&preset.id, usage_query)?);
+        }
+
+        Ok(Provider {
+            id: preset.id.clone(),
+            name: preset.name.clone(),
+            settings_config,
+            website_url: preset.website_url.clone(),
+            category: preset.category.clone(),
+            created_at: None,
+            sort_index: None,
+            notes: None,
+            icon: preset.icon.clone(),
+            icon_color: preset.icon_color.clone(),
+            meta,
+            in_failover_queue: false,
+        })
+    }
+
+    pub fn preset_ids(&self, app_type: &AppType) -> String {
+        let ids = self
+            .presets_for_app(app_type)
+            .map(|loaded| loaded.preset.id.as_str())
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            "(none)".to_string()
+        } else {
+            ids.join(", ")
+        }
+    }
+}
+
+pub fn presets_dir() -> PathBuf {
+    get_app_config_dir().join(PRESETS_DIR)
+}
+
+pub fn remote_catalog_path() -> PathBuf {
+    get_app_config_dir().join(REMOTE_CATALOG_FILE)
+}
+
+/// The embedded catalog alone.
+pub fn builtin_catalog() -> PresetCatalog {
+    parse_catalog(BUILTIN_CATALOG, CatalogFormat::Json).expect("built-in preset catalog is valid")
+}
+
+/// The merged catalog, loaded once per process. Template pickers and model
+/// defaults read this; commands that change catalog files use
+/// [`load_catalog`].
+pub fn shared_catalog() -> &'static LoadedCatalog {
+    static CATALOG: OnceLock<LoadedCatalog> = OnceLock::new();
+    CATALOG.get_or_init(load_catalog)
+}
+
+/// The model of a role in the merged catalog, falling back to the built-in
+/// catalog, which defines every role the code asks for.
+pub fn model_role(role: &str) -> &'static str {
+    if let Some(model) = shared_catalog().models.get(role) {
+        return model;
+    }
+    static BUILTIN: OnceLock<PresetCatalog> = OnceLock::new();
+    BUILTIN
+        .get_or_init(builtin_catalog)
+        .models
+        .get(role)
+        .unwrap_or_else(|| panic!("built-in preset catalog has no model role '{role}'"))
+}
+
+/// Load and merge every catalog layer. Broken user files are skipped and
+/// reported in [`LoadedCatalog::warnings`] so one bad file cannot hide the
+/// built-in presets.
+pub fn load_catalog() -> LoadedCatalog {
+    load_catalog_from(&remote_catalog_path(), &presets_dir())
+}
+
+fn load_catalog_from(remote_path: &Path, dir: &Path) -> LoadedCatalog {
+    let mut loaded = LoadedCatalog::default();
+    loaded.merge(builtin_catalog(), CatalogSource::Builtin);
+
+    if remote_path.exists() {
+        match read_catalog_file(remote_path) {
+            Ok(catalog) => loaded.merge(catalog, CatalogSource::Remote),
+            Err(error) => loaded
+                .warnings
+                .push(format!("{}: {}", remote_path.display(), error)),
+        }
+    }
+
+    for path in catalog_files(dir) {
+        match read_catalog_file(&path) {
+            Ok(catalog) => loaded.merge(catalog, CatalogSource::File(path)),
+            Err(error) => loaded
+                .warnings
+                .push(format!("{}: {}", path.display(), error)),
+        }
+    }
+    loaded
+}
+
+fn catalog_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && CatalogFormat::from_path(path).is_some())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Json,
+    Toml,
+}
+
+impl CatalogFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+fn read_catalog_file(path: &Path) -> Result<PresetCatalog, AppError> {
+    let format = CatalogFormat::from_path(path).unwrap_or(CatalogFormat::Json);
+    let text = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    parse_catalog(&text, format)
+}
+
+/// Parse and validate a catalog document.
+pub fn parse_catalog(text: &str, format: CatalogFormat) -> Result<PresetCatalog, AppError> {
+    let catalog: PresetCatalog = match format {
+        CatalogFormat::Json => serde_json::from_str(text)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid preset catalog: {e}")))?,
+        CatalogFormat::Toml => toml::from_str(text)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid preset catalog: {e}")))?,
+    };
+    validate_catalog(&catalog)?;
+    Ok(catalog)
+}
+
+fn validate_catalog(catalog: &PresetCatalog) -> Result<(), AppError> {
+    if catalog.version == 0 || catalog.version > CATALOG_FORMAT_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Unsupported preset catalog version {} (this build reads up to {})",
+            catalog.version, CATALOG_FORMAT_VERSION
+        )));
+    }
+    for preset in &catalog.presets {
+        if preset.id.trim().is_empty() || preset.name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "Every preset needs a non-empty id and name".to_string(),
+            ));
+        }
+        if preset.apps.is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "Preset '{}' does not define any app",
+                preset.id
+            )));
+        }
+        for app in preset.apps.keys() {
+            app.parse::<AppType>().map_err(|_| {
+                AppError::InvalidInput(format!(
+                    "Preset '{}' targets unknown app '{}'",
+                    preset.id, app
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace `${model:<role>}` references in every string value and object key
+/// (OpenCode keys its models by id); returns the first unknown role.
+fn substitute_models(value: &mut Value, models: &BTreeMap<String, String>) -> Result<(), String> {
+    match value {
+        Value::String(text) => {
+            if text.contains(MODEL_PLACEHOLDER_PREFIX) {
+                *text = substitute_model_refs(text, models)?;
+            }
+            Ok(())
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .try_for_each(|item| substitute_models(item, models)),
+        Value::Object(map) => {
+            if map.keys().any(|key| key.contains(MODEL_PLACEHOLDER_PREFIX)) {
+                let mut substituted = serde_json::Map::with_capacity(map.len());
+                for (key, item) in std::mem::take(map) {
+                    let key = if key.contains(MODEL_PLACEHOLDER_PREFIX) {
+                        substitute_model_refs(&key, models)?
+                    } else {
+                        key
+                    };
+                    substituted.insert(key, item);
+                }
+                *map = substituted;
+            }
+            map.values_mut()
+                .try_for_each(|item| substitute_models(item, models))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn substitute_model_refs(text: &str, models: &BTreeMap<String, String>) -> Result<String, String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(MODEL_PLACEHOLDER_PREFIX) {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + MODEL_PLACEHOLDER_PREFIX.len()..];
+        let Some(end) = after.find('}') else {
+            output.push_str(&rest[start..]);
+            return Ok(output);
+        };
+        let role = after[..end].trim();
+        let model = models.get(role).ok_or_else(|| role.to_string())?;
+        output.push_str(model);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn unknown_model_role_error(preset_id: &str, role: &str) -> AppError {
+    AppError::InvalidInput(format!(
+        "Preset '{preset_id}' references unknown model role '{role}'"
+    ))
+}
+
+/// Complete a catalog Usage Query entry into a stored [`UsageScript`].
+fn usage_script_from_template(preset_id: &str, template: &Value) -> Result<UsageScript, AppError> {
+    let mut script = template.as_object().cloned().ok_or_else(|| {
+        AppError::InvalidInput(format!("Preset '{preset_id}' usageQuery must be an object"))
+    })?;
+    script.entry("enabled").or_insert(Value::Bool(true));
+    script
+        .entry("language")
+        .or_insert_with(|| Value::String("javascript".to_string()));
+    script
+        .entry("code")
+        .or_insert_with(|| Value::String(String::new()));
+    serde_json::from_value(Value::Object(script)).map_err(|e| {
+        AppError::InvalidInput(format!("Preset '{preset_id}' has invalid usageQuery: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    const TEAM_CATALOG: &str = r#"
+version = 1
+name = "team"
+
+[models]
+"claude.sonnet" = "claude-sonnet-5-1"
+
+[[presets]]
+id = "team-relay"
+name = "Team Relay"
+websiteUrl = "https://relay.example.com"
+
+[presets.usageQuery]
+templateType = "balance"
+
+[presets.apps.claude.settings.env]
+ANTHROPIC_BASE_URL = "https://relay.example.com"
+ANTHROPIC_AUTH_TOKEN = ""
+ANTHROPIC_MODEL = "${model:claude.sonnet}"
+ANTHROPIC_DEFAULT_HAIKU_MODEL = "${model:claude.haiku}"
+
+[[presets]]
+id = "deepseek"
+name = "DeepSeek (team)"
+
+[presets.apps.codex.settings]
+config = 'model = "${model:deepseek.pro}"'
+"#;
+
+    #[test]
+    fn every_builtin_preset_builds_with_its_model_roles() {
+        let mut catalog = LoadedCatalog::default();
+        catalog.merge(builtin_catalog(), CatalogSource::Builtin);
+        for loaded in &catalog.presets {
+            for app in loaded.preset.apps.keys() {
+                let app_type = app.parse::<AppType>().expect("known app");
+                let provider = catalog
+                    .build_provider(&app_type, &loaded.preset.id)
+                    .unwrap_or_else(|e| panic!("{} for {app}: {e}", loaded.preset.id));
+                let settings = provider.settings_config.to_string();
+                assert!(!settings.contains(MODEL_PLACEHOLDER_PREFIX), "{settings}");
+            }
+        }
+
+        let packycode = catalog
+            .build_provider(&AppType::OpenCode, "packycode")
+            .expect("packycode");
+        assert_eq!(
+            packycode.settings_config["models"]
+                .as_object()
+                .expect("models")
+                .keys()
+                .next()
+                .map(String::as_str),
+            Some(catalog.models["claude.opus"].as_str())
+        );
+        let deepseek = catalog.find(&AppType::Codex, "deepseek").expect("deepseek");
+        assert_eq!(deepseek.preset.icon_color.as_deref(), Some("#1E88E5"));
+    }
+
+    fn builtin_sponsor_ids(app_type: &AppType) -> Vec<String> {
+        let mut catalog = LoadedCatalog::default();
+        catalog.merge(builtin_catalog(), CatalogSource::Builtin);
+        catalog
+            .presets_for_app(app_type)
+            .filter(|loaded| loaded.preset.register_url.is_some())
+            .map(|loaded| loaded.preset.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn additive_apps_share_one_sponsor_support_matrix() {
+        let expected = [
+            "aicodemirror",
+            "cubence",
+            "openmodel",
+            "runapi",
+            "qiniu",
+            "fenno",
+            "packycode",
+        ];
+        for app_type in [AppType::OpenCode, AppType::Hermes, AppType::OpenClaw] {
+            assert_eq!(builtin_sponsor_ids(&app_type), expected, "{app_type:?}");
+        }
+    }
+
+    #[test]
+    fn aicodemirror_is_the_first_sponsor_for_every_supported_app() {
+        for app_type in [
+            AppType::Claude,
+            AppType::Codex,
+            AppType::Gemini,
+            AppType::OpenCode,
+            AppType::Hermes,
+            AppType::OpenClaw,
+        ] {
+            assert_eq!(
+                builtin_sponsor_ids(&app_type).first().map(String::as_str),
+                Some("aicodemirror"),
+                "AICodeMirror should be the first sponsor preset for {app_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn packycode_keeps_cli_promotion_url_but_uses_upstream_api_hosts() {
+        let mut catalog = LoadedCatalog::default();
+        catalog.merge(builtin_catalog(), CatalogSource::Builtin);
+        let packycode = &catalog
+            .find(&AppType::Claude, "packycode")
+            .expect("packycode")
+            .preset;
+        assert_eq!(
+            packycode.website_url.as_deref(),
+            Some("https://www.packyapi.com")
+        );
+        assert_eq!(
+            packycode.register_url.as_deref(),
+            Some("https://www.packyapi.com/register?aff=cc-switch-cli")
+        );
+        let claude = catalog
+            .build_provider(&AppType::Claude, "packycode")
+            .expect("claude");
+        assert_eq!(
+            claude.settings_config["env"]["ANTHROPIC_BASE_URL"],
+            "https://www.packyapi.ai"
+        );
+        let opencode = catalog
+            .build_provider(&AppType::OpenCode, "packycode")
+            .expect("opencode");
+        assert_eq!(
+            opencode.settings_config["options"]["baseURL"],
+            "https://www.packyapi.ai/v1"
+        );
+    }
+
+    #[test]
+    fn user_catalogs_override_presets_and_model_roles() {
+        let dir = tempdir().expect("tempdir");
+        let presets_dir = dir.path().join(PRESETS_DIR);
+        fs::create_dir_all(&presets_dir).expect("create presets.d");
+        fs::write(presets_dir.join("10-team.toml"), TEAM_CATALOG).expect("write team catalog");
+        fs::write(presets_dir.join("20-broken.json"), "{").expect("write broken catalog");
+        fs::write(presets_dir.join("notes.txt"), "ignored").expect("write notes");
+
+        let catalog = load_catalog_from(&dir.path().join(REMOTE_CATALOG_FILE), &presets_dir);
+        assert_eq!(catalog.warnings.len(), 1);
+        assert!(catalog.warnings[0].contains("20-broken.json"));
+
+        let relay = catalog
+            .build_provider(&AppType::Claude, "team-relay")
+            .expect("build team preset");
+        assert_eq!(
+            relay.settings_config["env"],
+            json!({
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "",
+                "ANTHROPIC_MODEL": "claude-sonnet-5-1",
+                "ANTHROPIC_DEFAULT_HAIKU_MODEL": model_role("claude.haiku"),
+            })
+        );
+        let usage = relay
+            .meta
+            .and_then(|meta| meta.usage_script)
+            .expect("usage query template");
+        assert_eq!(usage.template_type.as_deref(), Some("balance"));
+        assert!(usage.enabled);
+
+        let deepseek = catalog.find(&AppType::Codex, "deepseek").expect("deepseek");
+        assert_eq!(deepseek.source.label(), "10-team.toml");
+        assert_eq!(
+            catalog
+                .build_provider(&AppType::Codex, "deepseek")
+                .expect("build overridden preset")
+                .settings_config["config"],
+            "model = \"deepseek-v4-pro\""
+        );
+        assert!(catalog.find(&AppType::Claude, "deepseek").is_none());
+    }
+
+    #[test]
+    fn rejects_newer_versions_and_unknown_roles() {
+        let newer = json!({ "version": CATALOG_FORMAT_VERSION + 1, "presets": [] }).to_string();
+        assert!(parse_catalog(&newer, CatalogFormat::Json).is_err());
+
+        let mut catalog = LoadedCatalog::default();
+        catalog.merge(
+            parse_catalog(
+                &json!({
+                    "version": 1,
+                    "presets": [{
+                        "id": "typo",
+                        "name": "Typo",
+                        "apps": { "gemini": { "settings": { "model": "${model:gemini.pro}" } } }
+                    }]
+                })
+                .to_string(),
+                CatalogFormat::Json,
+            )
+            .expect("parse"),
+            CatalogSource::Builtin,
+        );
+        let error = catalog
+            .build_provider(&AppType::Gemini, "typo")
+            .expect_err("unknown role");
+        assert!(error.to_string().contains("gemini.pro"));
+    }
+}
@@ -0,0 +1,59 @@
+//! Profile（配置方案）数据结构
+//!
+//! 一个 Profile 记录某一时刻各应用的当前供应商、MCP 服务器启用矩阵、
+//! 已启用的提示词以及 Skills 启用矩阵，供 `cc-switch profile apply`
+//! 一次性切换整套多应用配置。
+//!
+//! 数据存放在与上游共享的 `profiles` 表中，payload 为 JSON。上游写入的
+//! 未知字段通过 `extra` 原样保留，保证 WebDAV 同步往返不丢数据。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::app_config::{McpApps, SkillApps};
+
+/// 当前激活 Profile 的 settings key 前缀，完整 key 为 `current_profile_id_<scope>`。
+pub const CURRENT_PROFILE_KEY_PREFIX: &str = "current_profile_id_";
+
+/// `profiles.payload` 列的内容
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilePayload {
+    /// 应用 -> 当前供应商 ID
+    #[serde(default)]
+    pub providers: BTreeMap<String, String>,
+    /// MCP 服务器 ID -> 启用的应用；缺省表示该 Profile 不管理 MCP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<BTreeMap<String, McpApps>>,
+    /// 应用 -> 已启用的提示词 ID（`null` 表示不启用任何提示词）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<BTreeMap<String, Option<String>>>,
+    /// Skill ID -> 启用的应用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<BTreeMap<String, SkillApps>>,
+    /// 上游或未来版本写入、CLI 不解释的字段
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `profiles` 表中的一行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub payload: ProfilePayload,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+}
+
+/// 生成 `current_profile_id_<scope>` settings key
+pub fn current_profile_key(scope: &str) -> String {
+    format!("{CURRENT_PROFILE_KEY_PREFIX}{scope}")
+}
@@ -486,6 +486,48 @@ impl LocalProxyRequestOverrides {
     }
 }
 
+/// Key 池轮换策略
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyRotationStrategy {
+    /// 每个请求依次使用下一个可用 Key
+    #[default]
+    RoundRobin,
+    /// 一直使用第一个可用 Key，停用后才切到下一个
+    FillFirst,
+}
+
+impl KeyRotationStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RoundRobin => "round_robin",
+            Self::FillFirst => "fill_first",
+        }
+    }
+}
+
+/// 池中的一个 API Key；`key` 与 settings_config 中的凭据一样可以是密钥引用或加密值
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PooledApiKey {
+    pub label: String,
+    pub key: String,
+}
+
+/// 供应商的 API Key 池
+///
+/// settings_config 中的 Key 始终是池中的第一个（标签为 `primary`），
+/// 这里只保存额外的 Key。
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ApiKeyPool {
+    #[serde(default)]
+    pub strategy: KeyRotationStrategy,
+    /// 认证失败或额度耗尽后的停用时长（秒），缺省为 600
+    #[serde(rename = "cooldownSecs", skip_serializing_if = "Option::is_none")]
+    pub cooldown_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<PooledApiKey>,
+}
+
 /// 供应商元数据
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProviderMeta {
@@ -600,6 +642,9 @@ pub struct ProviderMeta {
     /// 用于多账号支持，关联到特定的 GitHub 账号
     #[serde(rename = "githubAccountId", skip_serializing_if = "Option::is_none")]
     pub github_account_id: Option<String>,
+    /// 额外的 API Key 池：代理按策略轮换，认证失败或额度耗尽的 Key 会暂时停用
+    #[serde(rename = "apiKeyPool", skip_serializing_if = "Option::is_none")]
+    pub api_key_pool: Option<ApiKeyPool>,
 }
 
 /// Parse the provider-level User-Agent used by proxy-related requests.
@@ -746,6 +791,372 @@ pub struct OpenClawModelCost {
     pub extra: HashMap<String, Value>,
 }
 
+/// 统一供应商派生出的应用供应商 ID 前缀
+pub const UNIVERSAL_PROVIDER_ID_PREFIX: &str = "universal-";
+
+/// 统一供应商的应用启用状态
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct UniversalProviderApps {
+    #[serde(default)]
+    pub claude: bool,
+    #[serde(default)]
+    pub codex: bool,
+    #[serde(default)]
+    pub gemini: bool,
+    #[serde(default)]
+    pub opencode: bool,
+    #[serde(default)]
+    pub hermes: bool,
+    #[serde(default)]
+    pub openclaw: bool,
+}
+
+impl UniversalProviderApps {
+    /// 全部应用启用
+    pub fn all() -> Self {
+        Self {
+            claude: true,
+            codex: true,
+            gemini: true,
+            opencode: true,
+            hermes: true,
+            openclaw: true,
+        }
+    }
+
+    pub fn is_enabled_for(&self, app_type: &AppType) -> bool {
+        match app_type {
+            AppType::Claude => self.claude,
+            AppType::Codex => self.codex,
+            AppType::Gemini => self.gemini,
+            AppType::OpenCode => self.opencode,
+            AppType::Hermes => self.hermes,
+            AppType::OpenClaw => self.openclaw,
+        }
+    }
+
+    pub fn set_enabled_for(&mut self, app_type: &AppType, enabled: bool) {
+        match app_type {
+            AppType::Claude => self.claude = enabled,
+            AppType::Codex => self.codex = enabled,
+            AppType::Gemini => self.gemini = enabled,
+            AppType::OpenCode => self.opencode = enabled,
+            AppType::Hermes => self.hermes = enabled,
+            AppType::OpenClaw => self.openclaw = enabled,
+        }
+    }
+
+    /// 按固定顺序返回已启用的应用
+    pub fn enabled_apps(&self) -> Vec<AppType> {
+        AppType::all()
+            .filter(|app| self.is_enabled_for(app))
+            .collect()
+    }
+}
+
+/// 统一供应商的按协议端点；未设置时由 `baseUrl` 推导
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct UniversalProviderEndpoints {
+    /// Anthropic Messages 协议（Claude、Hermes/OpenClaw 的 anthropic 模式）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anthropic: Option<String>,
+    /// OpenAI 兼容协议（Codex、OpenCode、Hermes、OpenClaw）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openai: Option<String>,
+    /// Gemini 协议
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gemini: Option<String>,
+}
+
+impl UniversalProviderEndpoints {
+    pub fn is_empty(&self) -> bool {
+        self.anthropic.is_none() && self.openai.is_none() && self.gemini.is_none()
+    }
+}
+
+/// Claude 模型配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeModelConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub haiku_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sonnet_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opus_model: Option<String>,
+}
+
+/// Codex 模型配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexModelConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<String>,
+}
+
+/// 单一模型配置（Gemini / OpenCode / Hermes / OpenClaw）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct UniversalModelConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// 统一供应商的各应用默认模型
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct UniversalProviderModels {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude: Option<ClaudeModelConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codex: Option<CodexModelConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gemini: Option<UniversalModelConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opencode: Option<UniversalModelConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hermes: Option<UniversalModelConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openclaw: Option<UniversalModelConfig>,
+}
+
+/// 统一供应商：一个中转账号（一个 API Key）同时供给多个应用
+///
+/// 存储在 settings 表的 `universal_providers` 键下；各应用中的派生供应商
+/// （ID 为 `universal-<app>-<id>`）由它生成，编辑统一供应商时整体覆盖。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniversalProvider {
+    pub id: String,
+    pub name: String,
+    /// 供应商类型（如 "newapi"、"custom"）
+    #[serde(default = "default_universal_provider_type")]
+    pub provider_type: String,
+    #[serde(default)]
+    pub apps: UniversalProviderApps,
+    pub base_url: String,
+    pub api_key: String,
+    #[serde(default, skip_serializing_if = "UniversalProviderEndpoints::is_empty")]
+    pub endpoints: UniversalProviderEndpoints,
+    #[serde(default)]
+    pub models: UniversalProviderModels,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ProviderMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<usize>,
+}
+
+fn default_universal_provider_type() -> String {
+    "custom".to_string()
+}
+
+impl UniversalProvider {
+    pub fn new(id: String, name: String, base_url: String, api_key: String) -> Self {
+        Self {
+            id,
+            name,
+            provider_type: default_universal_provider_type(),
+            apps: UniversalProviderApps::all(),
+            base_url,
+            api_key,
+            endpoints: UniversalProviderEndpoints::default(),
+            models: UniversalProviderModels::default(),
+            website_url: None,
+            notes: None,
+            icon: None,
+            icon_color: None,
+            meta: None,
+            created_at: None,
+            sort_index: None,
+        }
+    }
+
+    /// 指定应用中派生供应商的 ID
+    pub fn derived_provider_id(&self, app_type: &AppType) -> String {
+        format!(
+            "{UNIVERSAL_PROVIDER_ID_PREFIX}{}-{}",
+            app_type.as_str(),
+            self.id
+        )
+    }
+
+    /// Anthropic 协议端点
+    pub fn anthropic_base_url(&self) -> String {
+        trim_base_url(
+            self.endpoints
+                .anthropic
+                .as_deref()
+                .unwrap_or(&self.base_url),
+        )
+    }
+
+    /// OpenAI 兼容端点；由 `baseUrl` 推导时补齐 `/v1`
+    pub fn openai_base_url(&self) -> String {
+        if let Some(url) = self.endpoints.openai.as_deref() {
+            return trim_base_url(url);
+        }
+        let base = trim_base_url(&self.base_url);
+        if base.ends_with("/v1") {
+            base
+        } else {
+            format!("{base}/v1")
+        }
+    }
+
+    /// Gemini 协议端点
+    pub fn gemini_base_url(&self) -> String {
+        trim_base_url(self.endpoints.gemini.as_deref().unwrap_or(&self.base_url))
+    }
+
+    /// OpenAI 兼容应用（OpenCode/Hermes/OpenClaw）的模型：优先本应用配置，
+    /// 其次沿用 Codex 模型
+    fn openai_compatible_model(&self, own: Option<&UniversalModelConfig>) -> String {
+        own.and_then(|config| non_empty(config.model.as_deref()))
+            .or_else(|| {
+                self.models
+                    .codex
+                    .as_ref()
+                    .and_then(|config| non_empty(config.model.as_deref()))
+            })
+            .unwrap_or(crate::provider_preset_models::codex_default_model())
+            .to_string()
+    }
+
+    /// 生成指定应用的派生供应商；该应用未启用时返回 `None`
+    pub fn to_app_provider(&self, app_type: &AppType) -> Option<Provider> {
+        if !self.apps.is_enabled_for(app_type) {
+            return None;
+        }
+        let api_key = self.api_key.trim();
+        let settings_config = match app_type {
+            AppType::Claude => {
+                let mut env = serde_json::Map::new();
+                env.insert(
+                    "ANTHROPIC_BASE_URL".to_string(),
+                    Value::String(self.anthropic_base_url()),
+                );
+                env.insert(
+                    CLAUDE_AUTH_TOKEN_ENV_KEY.to_string(),
+                    Value::String(api_key.to_string()),
+                );
+                if let Some(models) = self.models.claude.as_ref() {
+                    for (key, value) in [
+                        ("ANTHROPIC_MODEL", &models.model),
+                        ("ANTHROPIC_DEFAULT_HAIKU_MODEL", &models.haiku_model),
+                        ("ANTHROPIC_DEFAULT_SONNET_MODEL", &models.sonnet_model),
+                        ("ANTHROPIC_DEFAULT_OPUS_MODEL", &models.opus_model),
+                    ] {
+                        if let Some(value) = non_empty(value.as_deref()) {
+                            env.insert(key.to_string(), Value::String(value.to_string()));
+                        }
+                    }
+                }
+                serde_json::json!({ "env": env })
+            }
+            AppType::Codex => {
+                let codex = self.models.codex.clone().unwrap_or_default();
+                let model = non_empty(codex.model.as_deref())
+                    .unwrap_or(crate::provider_preset_models::codex_default_model());
+                let mut config = crate::codex_config::build_codex_third_party_config_toml(
+                    &self.name,
+                    &self.openai_base_url(),
+                    model,
+                    "responses",
+                );
+                if let Some(effort) = non_empty(codex.reasoning_effort.as_deref()) {
+                    if let Ok(mut doc) = config.parse::<toml_edit::DocumentMut>() {
+                        doc["model_reasoning_effort"] = toml_edit::value(effort);
+                        config = doc.to_string();
+                    }
+                }
+                serde_json::json!({
+                    "auth": { "OPENAI_API_KEY": api_key },
+                    "config": config.trim(),
+                })
+            }
+            AppType::Gemini => {
+                let model = self
+                    .models
+                    .gemini
+                    .as_ref()
+                    .and_then(|config| non_empty(config.model.as_deref()))
+                    .unwrap_or(crate::provider_preset_models::gemini_default_model());
+                serde_json::json!({
+                    "env": {
+                        "GOOGLE_GEMINI_BASE_URL": self.gemini_base_url(),
+                        "GEMINI_API_KEY": api_key,
+                        "GEMINI_MODEL": model,
+                    }
+                })
+            }
+            AppType::OpenCode => {
+                let model = self.openai_compatible_model(self.models.opencode.as_ref());
+                serde_json::json!({
+                    "npm": crate::opencode_config::OPENCODE_DEFAULT_NPM,
+                    "name": self.name,
+                    "options": {
+                        "baseURL": self.openai_base_url(),
+                        "apiKey": api_key,
+                    },
+                    "models": { model.clone(): { "name": model } },
+                })
+            }
+            AppType::Hermes => {
+                let model = self.openai_compatible_model(self.models.hermes.as_ref());
+                serde_json::json!({
+                    "api_mode": crate::hermes_config::HERMES_DEFAULT_API_MODE,
+                    "base_url": self.openai_base_url(),
+                    "api_key": api_key,
+                    "models": [{ "id": model, "name": model }],
+                })
+            }
+            AppType::OpenClaw => {
+                let model = self.openai_compatible_model(self.models.openclaw.as_ref());
+                serde_json::json!({
+                    "baseUrl": self.openai_base_url(),
+                    "apiKey": api_key,
+                    "api": crate::openclaw_config::OPENCLAW_DEFAULT_API_PROTOCOL,
+                    "models": [{ "id": model, "name": model }],
+                })
+            }
+        };
+
+        let mut provider = Provider::with_id(
+            self.derived_provider_id(app_type),
+            self.name.clone(),
+            settings_config,
+            self.website_url.clone(),
+        );
+        provider.category = Some("aggregator".to_string());
+        provider.notes = self.notes.clone();
+        provider.icon = self.icon.clone();
+        provider.icon_color = self.icon_color.clone();
+        provider.meta = self.meta.clone();
+        provider.created_at = self.created_at;
+        Some(provider)
+    }
+}
+
+fn trim_base_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -0,0 +1,909 @@
+//! Capability probe suite for Claude providers.
+//!
+//! `provider probe` sends one small real request per capability through the
+//! same forwarder the proxy uses, so model mapping, API-format transforms, key
+//! pools and the response conversion apply exactly as they do for real
+//! traffic. Rectifiers are off while probing so the upstream sees every
+//! feature as sent. Verdicts are stored per provider and upstream model in the
+//! sidecar capability matrix, which [`super::capability_rectifier`] reads.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderMap, HeaderValue};
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::capability_matrix::CapabilityRecord;
+use crate::local_store::LocalStore;
+use crate::provider::Provider;
+
+use super::error::ProxyError;
+use super::forwarder::{ForwardOptions, RequestForwarder, StreamingResponse};
+use super::handlers::prepare_buffered_claude_response;
+use super::model_mapper::{apply_model_mapping, strip_one_m_suffix_for_upstream};
+use super::provider_router::ProviderRouter;
+use super::types::RectifierConfig;
+
+pub(crate) const DEFAULT_PROBE_MODEL: &str = "claude-sonnet-4-5";
+const PROBE_ENDPOINT: &str = "/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const MAX_DETAIL_CHARS: usize = 160;
+const STREAM_SNIFF_BYTES: usize = 16 * 1024;
+/// Comfortably above the 1024/2048-token minimum cacheable prefix.
+const CACHE_FILLER_SENTENCES: usize = 300;
+const RED_PIXEL_PNG: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8/5+hHgAHggJ/PchI7wAAAABJRU5ErkJggg==";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Capability {
+    Streaming,
+    Tools,
+    ParallelTools,
+    Thinking,
+    ImageInput,
+    PromptCaching,
+    JsonOutput,
+    LongContext,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 8] = [
+        Capability::Streaming,
+        Capability::Tools,
+        Capability::ParallelTools,
+        Capability::Thinking,
+        Capability::ImageInput,
+        Capability::PromptCaching,
+        Capability::JsonOutput,
+        Capability::LongContext,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Capability::Streaming => "streaming",
+            Capability::Tools => "tools",
+            Capability::ParallelTools => "parallel_tools",
+            Capability::Thinking => "thinking",
+            Capability::ImageInput => "image_input",
+            Capability::PromptCaching => "prompt_caching",
+            Capability::JsonOutput => "json_output",
+            Capability::LongContext => "long_context",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Capability::Streaming => "Streaming",
+            Capability::Tools => "Tool calling",
+            Capability::ParallelTools => "Parallel tool calls",
+            Capability::Thinking => "Extended thinking",
+            Capability::ImageInput => "Image input",
+            Capability::PromptCaching => "Prompt caching",
+            Capability::JsonOutput => "JSON output",
+            Capability::LongContext => "Long context",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|capability| capability.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProbeStatus {
+    Supported,
+    Unsupported,
+    /// The probe could not reach a verdict (auth, rate limit, server or
+    /// network error).
+    Failed,
+    Skipped,
+}
+
+impl ProbeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProbeStatus::Supported => "supported",
+            ProbeStatus::Unsupported => "unsupported",
+            ProbeStatus::Failed => "failed",
+            ProbeStatus::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CapabilityOutcome {
+    pub capability: Capability,
+    pub status: ProbeStatus,
+    pub detail: Option<String>,
+    pub latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProbeOptions {
+    /// Model named in the probe requests; the provider's model mapping applies.
+    pub model: Option<String>,
+    /// Only run these probes; empty runs all of them.
+    pub only: Vec<Capability>,
+    /// Prompt size for the long-context probe; skipped when unset.
+    pub long_context_tokens: Option<u32>,
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProbeReport {
+    pub provider_id: String,
+    pub request_model: String,
+    pub upstream_model: String,
+    pub outcomes: Vec<CapabilityOutcome>,
+}
+
+impl ProbeReport {
+    /// Rows for the capability matrix; skipped probes say nothing about the
+    /// upstream and are left out.
+    pub fn records(&self, probed_at: i64) -> Vec<CapabilityRecord> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.status != ProbeStatus::Skipped)
+            .map(|outcome| CapabilityRecord {
+                provider_id: self.provider_id.clone(),
+                model: self.upstream_model.clone(),
+                capability: outcome.capability.as_str().to_string(),
+                status: outcome.status.as_str().to_string(),
+                detail: outcome.detail.clone(),
+                latency_ms: outcome.latency_ms,
+                probed_at,
+            })
+            .collect()
+    }
+}
+
+/// The model name a request for `request_model` reaches the upstream with.
+pub(crate) fn upstream_model(provider: &Provider, request_model: &str) -> String {
+    let (body, _, _) = apply_model_mapping(json!({ "model": request_model }), provider);
+    let model = body
+        .get("model")
+        .and_then(Value::as_str)
+        .unwrap_or(request_model);
+    strip_one_m_suffix_for_upstream(model).to_string()
+}
+
+/// Run the probe suite against a Claude provider.
+pub(crate) async fn run_probe_suite(
+    db: Arc<Database>,
+    provider: &Provider,
+    options: &ProbeOptions,
+) -> Result<ProbeReport, AppError> {
+    let request_model = options
+        .model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROBE_MODEL.to_string());
+    let forwarder = RequestForwarder::new(Arc::new(ProviderRouter::new(db)))
+        .map_err(|error| AppError::Message(error.to_string()))?;
+    let prober = Prober {
+        forwarder,
+        provider: provider.clone(),
+        model: request_model.clone(),
+        timeout: options.timeout,
+    };
+
+    let mut outcomes = Vec::new();
+    for capability in Capability::ALL {
+        if !options.only.is_empty() && !options.only.contains(&capability) {
+            continue;
+        }
+        outcomes.push(prober.run(capability, options.long_context_tokens).await);
+    }
+
+    Ok(ProbeReport {
+        provider_id: provider.id.clone(),
+        upstream_model: upstream_model(provider, &request_model),
+        request_model,
+        outcomes,
+    })
+}
+
+/// Store a report in the capability matrix.
+pub(crate) fn save_report(app_type: &AppType, report: &ProbeReport) -> Result<(), AppError> {
+    let records = report.records(chrono::Utc::now().timestamp());
+    LocalStore::open()?.save_capability_records(app_type.as_str(), &records)?;
+    super::capability_rectifier::invalidate_probe_cache();
+    Ok(())
+}
+
+struct ProbeReply {
+    status: u16,
+    body: Value,
+    latency_ms: u64,
+}
+
+impl ProbeReply {
+    fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    fn blocks<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Value> + 'a {
+        self.body
+            .get("content")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(move |block| block.get("type").and_then(Value::as_str) == Some(kind))
+    }
+
+    fn text(&self) -> String {
+        self.blocks("text")
+            .filter_map(|block| block.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    fn usage(&self, field: &str) -> u64 {
+        self.body
+            .pointer(&format!("/usage/{field}"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    }
+
+    fn error_summary(&self) -> String {
+        let message = self
+            .body
+            .pointer("/error/message")
+            .or_else(|| self.body.get("message"))
+            .or_else(|| self.body.get("error"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| match &self.body {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            });
+        truncate(&message)
+    }
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= MAX_DETAIL_CHARS {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX_DETAIL_CHARS).collect();
+    format!("{cut}…")
+}
+
+fn parse_body(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+/// Parse model output as a JSON object, tolerating a Markdown code fence.
+fn parse_json_object(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .map(|rest| rest.trim_end().trim_end_matches("```"))
+        .unwrap_or(trimmed);
+    serde_json::from_str::<Value>(unfenced.trim())
+        .ok()
+        .filter(Value::is_object)
+}
+
+fn outcome(
+    capability: Capability,
+    status: ProbeStatus,
+    detail: impl Into<Option<String>>,
+    latency_ms: Option<u64>,
+) -> CapabilityOutcome {
+    CapabilityOutcome {
+        capability,
+        status,
+        detail: detail.into(),
+        latency_ms,
+    }
+}
+
+/// A non-2xx answer: request-shape errors mean the feature is not accepted,
+/// anything else (auth, quota, server) leaves the question open.
+fn rejected(capability: Capability, reply: &ProbeReply) -> CapabilityOutcome {
+    let status = if matches!(reply.status, 400 | 404 | 413 | 415 | 422) {
+        ProbeStatus::Unsupported
+    } else {
+        ProbeStatus::Failed
+    };
+    outcome(
+        capability,
+        status,
+        format!("HTTP {}: {}", reply.status, reply.error_summary()),
+        Some(reply.latency_ms),
+    )
+}
+
+fn weather_tool() -> Value {
+    json!({
+        "name": "get_weather",
+        "description": "Get the current weather for a city.",
+        "input_schema": {
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"]
+        }
+    })
+}
+
+struct Prober {
+    forwarder: RequestForwarder,
+    provider: Provider,
+    model: String,
+    timeout: Duration,
+}
+
+impl Prober {
+    fn request(&self, max_tokens: u32, content: Value) -> Value {
+        json!({
+            "model": self.model,
+            "max_tokens": max_tokens,
+            "messages": [{ "role": "user", "content": content }]
+        })
+    }
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+        headers
+    }
+
+    fn options(&self) -> ForwardOptions {
+        ForwardOptions {
+            max_retries: 0,
+            request_timeout: Some(self.timeout),
+            bypass_circuit_breaker: true,
+        }
+    }
+
+    fn rectifiers_off() -> RectifierConfig {
+        RectifierConfig {
+            enabled: false,
+            ..RectifierConfig::default()
+        }
+    }
+
+    async fn send(&self, body: Value) -> Result<ProbeReply, String> {
+        let tool_schema_hints =
+            super::providers::transform_gemini::extract_anthropic_tool_schema_hints(&body);
+        let started_at = Instant::now();
+        let forwarded = self
+            .forwarder
+            .forward_buffered_response_detailed(
+                &AppType::Claude,
+                PROBE_ENDPOINT,
+                body,
+                &Self::headers(),
+                vec![self.provider.clone()],
+                self.options(),
+                Self::rectifiers_off(),
+            )
+            .await;
+        let latency_ms = started_at.elapsed().as_millis() as u64;
+
+        match forwarded {
+            Ok(forwarded) => {
+                let status = forwarded.response.status.as_u16();
+                let prepared = prepare_buffered_claude_response(
+                    &forwarded.provider,
+                    forwarded.response,
+                    None,
+                    None,
+                    Some(&tool_schema_hints),
+                )
+                .map_err(|error| error.to_string())?;
+                let bytes = axum::body::to_bytes(prepared.response.into_body(), usize::MAX)
+                    .await
+                    .map_err(|error| error.to_string())?;
+                Ok(ProbeReply {
+                    status,
+                    body: parse_body(&bytes),
+                    latency_ms,
+                })
+            }
+            Err(failure) => match failure.error {
+                ProxyError::UpstreamError { status, body } => Ok(ProbeReply {
+                    status,
+                    body: parse_body(body.unwrap_or_default().as_bytes()),
+                    latency_ms,
+                }),
+                error => Err(error.to_string()),
+            },
+        }
+    }
+
+    async fn run(
+        &self,
+        capability: Capability,
+        long_context_tokens: Option<u32>,
+    ) -> CapabilityOutcome {
+        let result = match capability {
+            Capability::Streaming => self.probe_streaming().await,
+            Capability::Tools => self.probe_tools().await,
+            Capability::ParallelTools => self.probe_parallel_tools().await,
+            Capability::Thinking => self.probe_thinking().await,
+            Capability::ImageInput => self.probe_image_input().await,
+            Capability::PromptCaching => self.probe_prompt_caching().await,
+            Capability::JsonOutput => self.probe_json_output().await,
+            Capability::LongContext => match long_context_tokens {
+                Some(tokens) => self.probe_long_context(tokens).await,
+                None => Ok(outcome(
+                    capability,
+                    ProbeStatus::Skipped,
+                    "pass --long-context <tokens> to run".to_string(),
+                    None,
+                )),
+            },
+        };
+        result.unwrap_or_else(|error| {
+            outcome(capability, ProbeStatus::Failed, truncate(&error), None)
+        })
+    }
+
+    async fn probe_streaming(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::Streaming;
+        let mut body = self.request(16, json!("Say hi."));
+        body["stream"] = json!(true);
+        let started_at = Instant::now();
+        let forwarded = self
+            .forwarder
+            .forward_response_detailed(
+                &AppType::Claude,
+                PROBE_ENDPOINT,
+                body,
+                &Self::headers(),
+                vec![self.provider.clone()],
+                self.options(),
+                Self::rectifiers_off(),
+            )
+            .await;
+
+        let response = match forwarded {
+            Ok(forwarded) => forwarded.response,
+            Err(failure) => {
+                return match failure.error {
+                    ProxyError::UpstreamError { status, body } => Ok(rejected(
+                        capability,
+                        &ProbeReply {
+                            status,
+                            body: parse_body(body.unwrap_or_default().as_bytes()),
+                            latency_ms: started_at.elapsed().as_millis() as u64,
+                        },
+                    )),
+                    error => Err(error.to_string()),
+                };
+            }
+        };
+
+        let live = match response {
+            StreamingResponse::Live(live) => live,
+            StreamingResponse::Buffered(buffered) => {
+                let reply = ProbeReply {
+                    status: buffered.status.as_u16(),
+                    body: parse_body(&buffered.body),
+                    latency_ms: started_at.elapsed().as_millis() as u64,
+                };
+                if !reply.is_success() {
+                    return Ok(rejected(capability, &reply));
+                }
+                return Ok(outcome(
+                    capability,
+                    ProbeStatus::Unsupported,
+                    "upstream answered without SSE events".to_string(),
+                    Some(reply.latency_ms),
+                ));
+            }
+        };
+
+        let status = live.status().as_u16();
+        let mut stream = live.bytes_stream();
+        let mut seen = Vec::new();
+        let mut first_event_ms = None;
+        while seen.len() < STREAM_SNIFF_BYTES {
+            let next = tokio::time::timeout(self.timeout, stream.next())
+                .await
+                .map_err(|_| "timed out waiting for stream data".to_string())?;
+            let Some(chunk) = next else {
+                break;
+            };
+            seen.extend_from_slice(&chunk.map_err(|error| error.to_string())?);
+            if (200..300).contains(&status) && String::from_utf8_lossy(&seen).contains("data:") {
+                first_event_ms = Some(started_at.elapsed().as_millis() as u64);
+                break;
+            }
+        }
+
+        let latency_ms = started_at.elapsed().as_millis() as u64;
+        if !(200..300).contains(&status) {
+            return Ok(rejected(
+                capability,
+                &ProbeReply {
+                    status,
+                    body: parse_body(&seen),
+                    latency_ms,
+                },
+            ));
+        }
+        Ok(match first_event_ms {
+            Some(ms) => outcome(
+                capability,
+                ProbeStatus::Supported,
+                format!("first event after {ms}ms"),
+                Some(ms),
+            ),
+            None => outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                "upstream answered without SSE events".to_string(),
+                Some(latency_ms),
+            ),
+        })
+    }
+
+    async fn probe_tools(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::Tools;
+        let mut body = self.request(128, json!("What is the weather in Paris?"));
+        body["tools"] = json!([weather_tool()]);
+        body["tool_choice"] = json!({ "type": "tool", "name": "get_weather" });
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+
+        let called = reply.blocks("tool_use").any(|block| {
+            block.get("name").and_then(Value::as_str) == Some("get_weather")
+                && block.get("input").is_some_and(Value::is_object)
+        });
+        Ok(if called {
+            outcome(
+                capability,
+                ProbeStatus::Supported,
+                None,
+                Some(reply.latency_ms),
+            )
+        } else {
+            outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                "forced tool choice returned no tool_use block".to_string(),
+                Some(reply.latency_ms),
+            )
+        })
+    }
+
+    async fn probe_parallel_tools(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::ParallelTools;
+        let mut body = self.request(
+            256,
+            json!("Get the current weather in Paris and in Tokyo. Call get_weather once for each city, both in this reply."),
+        );
+        body["tools"] = json!([weather_tool()]);
+        body["tool_choice"] = json!({ "type": "any" });
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+
+        let calls = reply.blocks("tool_use").count();
+        Ok(if calls >= 2 {
+            outcome(
+                capability,
+                ProbeStatus::Supported,
+                format!("{calls} tool calls in one turn"),
+                Some(reply.latency_ms),
+            )
+        } else {
+            outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                format!("{calls} tool call(s) for two independent lookups"),
+                Some(reply.latency_ms),
+            )
+        })
+    }
+
+    async fn probe_thinking(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::Thinking;
+        let mut body = self.request(2048, json!("What is 17 * 23?"));
+        body["thinking"] = json!({ "type": "enabled", "budget_tokens": 1024 });
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+
+        let thought = reply.blocks("thinking").next().is_some()
+            || reply.blocks("redacted_thinking").next().is_some();
+        Ok(if thought {
+            outcome(
+                capability,
+                ProbeStatus::Supported,
+                None,
+                Some(reply.latency_ms),
+            )
+        } else {
+            outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                "accepted but returned no thinking blocks".to_string(),
+                Some(reply.latency_ms),
+            )
+        })
+    }
+
+    async fn probe_image_input(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::ImageInput;
+        let body = self.request(
+            32,
+            json!([
+                {
+                    "type": "image",
+                    "source": { "type": "base64", "media_type": "image/png", "data": RED_PIXEL_PNG }
+                },
+                { "type": "text", "text": "What color is this image? Answer in one word." }
+            ]),
+        );
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+        Ok(outcome(
+            capability,
+            ProbeStatus::Supported,
+            truncate(&reply.text()),
+            Some(reply.latency_ms),
+        ))
+    }
+
+    async fn probe_prompt_caching(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::PromptCaching;
+        let filler =
+            "Reference note: the quick brown fox jumps over the lazy dog near the river bank. "
+                .repeat(CACHE_FILLER_SENTENCES);
+        let mut body = self.request(16, json!("Reply with OK."));
+        body["system"] = json!([{
+            "type": "text",
+            "text": filler,
+            "cache_control": { "type": "ephemeral" }
+        }]);
+
+        // The first request writes the cache, the second should read it.
+        let mut cached_tokens = 0;
+        let mut latency_ms = None;
+        for _ in 0..2 {
+            let reply = self.send(body.clone()).await?;
+            if !reply.is_success() {
+                return Ok(rejected(capability, &reply));
+            }
+            cached_tokens = cached_tokens
+                .max(reply.usage("cache_creation_input_tokens"))
+                .max(reply.usage("cache_read_input_tokens"));
+            latency_ms = Some(reply.latency_ms);
+        }
+
+        Ok(if cached_tokens > 0 {
+            outcome(
+                capability,
+                ProbeStatus::Supported,
+                format!("{cached_tokens} cached input tokens"),
+                latency_ms,
+            )
+        } else {
+            outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                "no cache tokens reported".to_string(),
+                latency_ms,
+            )
+        })
+    }
+
+    async fn probe_json_output(&self) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::JsonOutput;
+        let body = self.request(
+            64,
+            json!("Return only a JSON object with the keys \"city\" and \"country\" for Paris. No prose."),
+        );
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+
+        let text = reply.text();
+        Ok(match parse_json_object(&text) {
+            Some(_) => outcome(
+                capability,
+                ProbeStatus::Supported,
+                None,
+                Some(reply.latency_ms),
+            ),
+            None => outcome(
+                capability,
+                ProbeStatus::Unsupported,
+                format!("not a JSON object: {}", truncate(&text)),
+                Some(reply.latency_ms),
+            ),
+        })
+    }
+
+    async fn probe_long_context(&self, tokens: u32) -> Result<CapabilityOutcome, String> {
+        let capability = Capability::LongContext;
+        // About one token per word; five words per repetition.
+        let filler = "alpha bravo charlie delta echo ".repeat((tokens / 5).max(1) as usize);
+        let body = self.request(
+            16,
+            json!(format!(
+                "{filler}\n\nIgnore the words above and reply with OK."
+            )),
+        );
+        let reply = self.send(body).await?;
+        if !reply.is_success() {
+            return Ok(rejected(capability, &reply));
+        }
+
+        let input_tokens = reply.usage("input_tokens");
+        Ok(outcome(
+            capability,
+            ProbeStatus::Supported,
+            if input_tokens > 0 {
+                format!("accepted {input_tokens} input tokens")
+            } else {
+                format!("accepted ~{tokens} input tokens")
+            },
+            Some(reply.latency_ms),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Json as AxumJson, routing::post, Router};
+
+    /// Anthropic-compatible upstream that takes tools and caching but rejects
+    /// thinking and images.
+    async fn fake_upstream(AxumJson(body): AxumJson<Value>) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        let reject = |message: &str| {
+            (
+                axum::http::StatusCode::BAD_REQUEST,
+                AxumJson(json!({
+                    "type": "error",
+                    "error": { "type": "invalid_request_error", "message": message }
+                })),
+            )
+                .into_response()
+        };
+        if body.get("thinking").is_some() {
+            return reject("thinking is not supported by this model");
+        }
+        if body.to_string().contains("\"type\":\"image\"") {
+            return reject("image input is not supported by this model");
+        }
+        if body.get("stream") == Some(&json!(true)) {
+            return (
+                [("content-type", "text/event-stream")],
+                "event: message_start\ndata: {\"type\":\"message_start\"}\n\n",
+            )
+                .into_response();
+        }
+
+        let tool_use = |city: &str| json!({ "type": "tool_use", "id": format!("call_{city}"), "name": "get_weather", "input": { "city": city } });
+        let content = match body.pointer("/tool_choice/type").and_then(Value::as_str) {
+            Some("any") => json!([tool_use("Paris"), tool_use("Tokyo")]),
+            Some(_) => json!([tool_use("Paris")]),
+            None => {
+                json!([{ "type": "text", "text": "```json\n{\"city\":\"Paris\",\"country\":\"France\"}\n```" }])
+            }
+        };
+        let cache_tokens = if body.get("system").is_some() {
+            2100
+        } else {
+            0
+        };
+        AxumJson(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": body["model"],
+            "content": content,
+            "stop_reason": "end_turn",
+            "usage": {
+                "input_tokens": 12,
+                "output_tokens": 8,
+                "cache_creation_input_tokens": cache_tokens
+            }
+        }))
+        .into_response()
+    }
+
+    #[tokio::test]
+    async fn suite_reports_the_upstream_capability_matrix() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind upstream listener");
+        let address = listener.local_addr().expect("upstream address");
+        let server = tokio::spawn(async move {
+            let app = Router::new().route("/v1/messages", post(fake_upstream));
+            let _ = axum::serve(listener, app).await;
+        });
+
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": format!("http://{address}"),
+                    "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                    "ANTHROPIC_MODEL": "relay-model"
+                }
+            }),
+            None,
+        );
+        let db = Arc::new(Database::memory().expect("memory db"));
+        let report = run_probe_suite(
+            db,
+            &provider,
+            &ProbeOptions {
+                model: None,
+                only: Vec::new(),
+                long_context_tokens: None,
+                timeout: Duration::from_secs(5),
+            },
+        )
+        .await
+        .expect("run probes");
+
+        let statuses: Vec<_> = report
+            .outcomes
+            .iter()
+            .map(|outcome| (outcome.capability, outcome.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (Capability::Streaming, ProbeStatus::Supported),
+                (Capability::Tools, ProbeStatus::Supported),
+                (Capability::ParallelTools, ProbeStatus::Supported),
+                (Capability::Thinking, ProbeStatus::Unsupported),
+                (Capability::ImageInput, ProbeStatus::Unsupported),
+                (Capability::PromptCaching, ProbeStatus::Supported),
+                (Capability::JsonOutput, ProbeStatus::Supported),
+                (Capability::LongContext, ProbeStatus::Skipped),
+            ]
+        );
+        assert_eq!(report.upstream_model, "relay-model");
+        let records = report.records(1);
+        assert_eq!(records.len(), 7);
+        assert!(records
+            .iter()
+            .all(|record| record.model == "relay-model" && record.provider_id == "relay"));
+
+        server.abort();
+    }
+
+    #[test]
+    fn json_output_tolerates_code_fences_only() {
+        assert!(parse_json_object("```json\n{\"a\": 1}\n```").is_some());
+        assert!(parse_json_object(" {\"a\": 1} ").is_some());
+        assert!(parse_json_object("Sure! {\"a\": 1}").is_none());
+        assert!(parse_json_object("[1, 2]").is_none());
+    }
+}
@@ -0,0 +1,311 @@
+//! Pre-emptive request rectification from probed provider capabilities.
+//!
+//! `provider probe` records which features an upstream model accepts. For
+//! Claude requests the proxy removes what the target model is known not to
+//! take before sending, instead of waiting for a 400:
+//!
+//! - image blocks, when the model's image-input capability resolves to
+//!   unsupported (settings declaration, then probe, then the text-only
+//!   registry in [`crate::model_capabilities`]);
+//! - the `thinking` parameter and thinking history, when the probe found the
+//!   model rejects or ignores extended thinking.
+//!
+//! Probe results live in the sidecar store; they are cached here so the
+//! request path does not open it for every request.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::local_store::capability_matrix::CapabilityRecord;
+use crate::local_store::LocalStore;
+use crate::model_capabilities::{image_input_capability_from_settings, ImageInputCapability};
+use crate::provider::Provider;
+
+use super::tool_media::{strip_media_from_tool_value, ToolMediaScope};
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
+pub(crate) const IMAGE_OMITTED_MARKER: &str =
+    "[cc-switch: image omitted, the upstream model does not accept image input]";
+
+/// Probe verdicts for one provider model; `None` when never probed or the
+/// probe was inconclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ProbedCapabilities {
+    pub image_input: Option<bool>,
+    pub thinking: Option<bool>,
+}
+
+type ModelKey = (String, String);
+
+struct CachedMatrix {
+    loaded_at: Instant,
+    models: HashMap<ModelKey, ProbedCapabilities>,
+}
+
+fn cache() -> &'static Mutex<HashMap<String, CachedMatrix>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedMatrix>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Fold stored probe records into per-model verdicts.
+pub(crate) fn probed_capabilities_by_model(
+    records: &[CapabilityRecord],
+) -> HashMap<ModelKey, ProbedCapabilities> {
+    let mut models: HashMap<ModelKey, ProbedCapabilities> = HashMap::new();
+    for record in records {
+        let verdict = match record.status.as_str() {
+            "supported" => Some(true),
+            "unsupported" => Some(false),
+            _ => None,
+        };
+        let entry = models
+            .entry((record.provider_id.clone(), record.model.clone()))
+            .or_default();
+        match record.capability.as_str() {
+            "image_input" => entry.image_input = verdict,
+            "thinking" => entry.thinking = verdict,
+            _ => {}
+        }
+    }
+    models
+}
+
+fn load_matrix(app_type: &str) -> HashMap<ModelKey, ProbedCapabilities> {
+    // Never create the sidecar file just to find out nothing was probed.
+    if !LocalStore::path().is_ok_and(|path| path.exists()) {
+        return HashMap::new();
+    }
+    match LocalStore::open().and_then(|store| store.list_capability_records(app_type, None)) {
+        Ok(records) => probed_capabilities_by_model(&records),
+        Err(error) => {
+            log::debug!("[{app_type}] read capability matrix failed: {error}");
+            HashMap::new()
+        }
+    }
+}
+
+/// Cached probe verdicts for a provider's upstream model.
+pub(crate) fn probed_capabilities(
+    app_type: &str,
+    provider_id: &str,
+    model: &str,
+) -> ProbedCapabilities {
+    let Ok(mut cache) = cache().lock() else {
+        return ProbedCapabilities::default();
+    };
+    let stale = cache
+        .get(app_type)
+        .is_none_or(|matrix| matrix.loaded_at.elapsed() >= CACHE_TTL);
+    if stale {
+        cache.insert(
+            app_type.to_string(),
+            CachedMatrix {
+                loaded_at: Instant::now(),
+                models: load_matrix(app_type),
+            },
+        );
+    }
+    cache
+        .get(app_type)
+        .and_then(|matrix| {
+            matrix
+                .models
+                .get(&(provider_id.to_string(), model.to_string()))
+        })
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Drop cached verdicts after a probe so the proxy sees them right away.
+pub(crate) fn invalidate_probe_cache() {
+    if let Ok(mut cache) = cache().lock() {
+        cache.clear();
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn seed_probe_cache(
+    app_type: &str,
+    provider_id: &str,
+    model: &str,
+    capabilities: ProbedCapabilities,
+) {
+    let mut cache = cache().lock().expect("capability cache lock");
+    let matrix = cache
+        .entry(app_type.to_string())
+        .or_insert_with(|| CachedMatrix {
+            loaded_at: Instant::now(),
+            models: HashMap::new(),
+        });
+    matrix.loaded_at = Instant::now();
+    matrix
+        .models
+        .insert((provider_id.to_string(), model.to_string()), capabilities);
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CapabilityRectifyResult {
+    pub images_removed: usize,
+    pub thinking_removed: bool,
+}
+
+impl CapabilityRectifyResult {
+    pub fn applied(&self) -> bool {
+        self.images_removed > 0 || self.thinking_removed
+    }
+}
+
+/// Rectify an Anthropic Messages body for the provider's upstream `model`.
+pub(crate) fn rectify_anthropic_request(
+    body: &mut Value,
+    provider: &Provider,
+    model: &str,
+    capabilities: ProbedCapabilities,
+) -> CapabilityRectifyResult {
+    let mut result = CapabilityRectifyResult::default();
+
+    if image_input_capability_from_settings(
+        &provider.settings_config,
+        model,
+        capabilities.image_input,
+        true,
+    ) == ImageInputCapability::Unsupported
+    {
+        result.images_removed = strip_images(body);
+    }
+
+    if capabilities.thinking == Some(false) && body.get("thinking").is_some() {
+        if let Some(object) = body.as_object_mut() {
+            object.remove("thinking");
+        }
+        *body = super::copilot_optimizer::strip_thinking_blocks(std::mem::take(body));
+        result.thinking_removed = true;
+    }
+
+    result
+}
+
+fn strip_images(body: &mut Value) -> usize {
+    let Some(messages) = body.get_mut("messages").and_then(Value::as_array_mut) else {
+        return 0;
+    };
+    let replacement = json!({ "type": "text", "text": IMAGE_OMITTED_MARKER });
+    let mut removed = Vec::new();
+    messages
+        .iter_mut()
+        .filter_map(|message| message.get_mut("content"))
+        .filter(|content| content.is_array())
+        .map(|content| {
+            strip_media_from_tool_value(
+                content,
+                &mut removed,
+                ToolMediaScope::ImagesOnly,
+                &replacement,
+                IMAGE_OMITTED_MARKER,
+            )
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_block() -> Value {
+        json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo=" }
+        })
+    }
+
+    fn request() -> Value {
+        json!({
+            "model": "relay-model",
+            "thinking": { "type": "enabled", "budget_tokens": 1024 },
+            "messages": [
+                { "role": "user", "content": [image_block(), { "type": "text", "text": "hi" }] },
+                { "role": "assistant", "content": [
+                    { "type": "thinking", "thinking": "hmm", "signature": "sig" },
+                    { "type": "tool_use", "id": "t1", "name": "shot", "input": {} }
+                ] },
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "t1", "content": [image_block()] }
+                ] }
+            ]
+        })
+    }
+
+    fn provider() -> Provider {
+        Provider::with_id("relay".into(), "Relay".into(), json!({}), None)
+    }
+
+    #[test]
+    fn unsupported_verdicts_strip_images_and_thinking() {
+        let mut body = request();
+        let result = rectify_anthropic_request(
+            &mut body,
+            &provider(),
+            "relay-model",
+            ProbedCapabilities {
+                image_input: Some(false),
+                thinking: Some(false),
+            },
+        );
+
+        assert_eq!(result.images_removed, 2);
+        assert!(result.thinking_removed);
+        assert!(body.get("thinking").is_none());
+        assert_eq!(
+            body["messages"][0]["content"][0]["text"],
+            IMAGE_OMITTED_MARKER
+        );
+        assert_eq!(
+            body["messages"][2]["content"][0]["content"][0]["text"],
+            IMAGE_OMITTED_MARKER
+        );
+        assert!(!body["messages"][1]["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|block| block["type"] == "thinking"));
+    }
+
+    #[test]
+    fn unknown_or_supported_models_pass_through() {
+        for capabilities in [
+            ProbedCapabilities::default(),
+            ProbedCapabilities {
+                image_input: Some(true),
+                thinking: Some(true),
+            },
+        ] {
+            let mut body = request();
+            let result =
+                rectify_anthropic_request(&mut body, &provider(), "relay-model", capabilities);
+            assert!(!result.applied());
+            assert_eq!(body, request());
+        }
+    }
+
+    #[test]
+    fn declaration_overrides_a_negative_probe() {
+        let mut provider = provider();
+        provider.settings_config = json!({
+            "models": [{ "id": "relay-model", "supportsImage": true }]
+        });
+        let mut body = request();
+        let result = rectify_anthropic_request(
+            &mut body,
+            &provider,
+            "relay-model",
+            ProbedCapabilities {
+                image_input: Some(false),
+                thinking: None,
+            },
+        );
+        assert_eq!(result, CapabilityRectifyResult::default());
+    }
+}
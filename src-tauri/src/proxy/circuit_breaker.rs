@@ -216,6 +216,12 @@ impl CircuitBreaker {
         self.transition_to_closed().await;
     }
 
+    /// Opens the breaker without waiting for real traffic to fail, e.g. when
+    /// background probes find the provider unreachable.
+    pub async fn trip(&self) {
+        self.transition_to_open().await;
+    }
+
     fn allow_half_open_probe(&self) -> AllowResult {
         let max_half_open_requests = 1u32;
         let current = self.half_open_requests.fetch_add(1, Ordering::SeqCst);
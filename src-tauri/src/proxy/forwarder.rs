@@ -3,11 +3,20 @@ use bytes::Bytes;
 use futures::{stream::BoxStream, StreamExt};
 use serde_json::Value;
 use std::{
-    sync::Arc,
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
     time::{Duration, Instant},
 };
 
-use crate::{app_config::AppType, provider::Provider};
+use crate::{
+    app_config::AppType,
+    key_pool::{self, KeyLease},
+    local_store::{LocalStore, SharedLocalStore},
+    provider::Provider,
+};
 
 use super::{
     error::ProxyError,
@@ -33,6 +42,10 @@ pub struct RequestForwarder {
     session_client_provided: bool,
     codex_chat_history: Option<Arc<CodexChatHistoryStore>>,
     gemini_shadow: Option<Arc<GeminiShadowStore>>,
+    attempt_counter: Option<Arc<AtomicU32>>,
+    key_label_slot: Option<Arc<Mutex<Option<String>>>>,
+    /// Sidecar store with the key pool state, opened on first use.
+    key_store: SharedLocalStore,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -132,6 +145,21 @@ impl StreamingResponse {
             Self::Buffered(response) => response.status,
         }
     }
+
+    pub fn headers(&self) -> &reqwest::header::HeaderMap {
+        match self {
+            Self::Live(response) => response.headers(),
+            Self::Buffered(response) => &response.headers,
+        }
+    }
+
+    /// The body, when it was read before handing the response on.
+    pub fn buffered_body(&self) -> Option<&Bytes> {
+        match self {
+            Self::Live(_) => None,
+            Self::Buffered(response) => Some(&response.body),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -171,6 +199,9 @@ impl RequestForwarder {
             session_client_provided: false,
             codex_chat_history: None,
             gemini_shadow: None,
+            attempt_counter: None,
+            key_label_slot: None,
+            key_store: SharedLocalStore::default(),
         })
     }
 
@@ -209,6 +240,72 @@ impl RequestForwarder {
         self
     }
 
+    /// Share a counter of providers tried so request logs and spans can
+    /// report failover attempts.
+    pub fn with_attempt_counter(mut self, counter: Arc<AtomicU32>) -> Self {
+        self.attempt_counter = Some(counter);
+        self
+    }
+
+    fn record_provider_attempt(&self) {
+        if let Some(counter) = &self.attempt_counter {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Share the label of the pooled API key each attempt used, for request
+    /// logs.
+    pub fn with_key_label_slot(mut self, slot: Arc<Mutex<Option<String>>>) -> Self {
+        self.key_label_slot = Some(slot);
+        self
+    }
+
+    /// Share the proxy's sidecar store for key pool state instead of opening
+    /// one per request.
+    pub(crate) fn with_local_store(mut self, store: SharedLocalStore) -> Self {
+        self.key_store = store;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_key_store(self, store: LocalStore) -> Self {
+        self.with_local_store(SharedLocalStore::with_store(store))
+    }
+
+    pub(crate) fn key_store(&self) -> Option<&LocalStore> {
+        self.key_store.get()
+    }
+
+    fn checkout_key(&self, app_type: &AppType, provider: &Provider) -> Option<KeyLease> {
+        // Only pooled providers need the sidecar store.
+        let lease = key_pool::pool(provider)
+            .and_then(|_| self.key_store())
+            .and_then(|store| key_pool::checkout(store, app_type, provider));
+        if let Some(slot) = &self.key_label_slot {
+            if let Ok(mut label) = slot.lock() {
+                *label = lease.as_ref().map(|lease| lease.label.clone());
+            }
+        }
+        lease
+    }
+
+    /// Records how the attempt went for the pooled key it used. Returns true
+    /// when the key was just disabled and another key of the same provider
+    /// can take over the request.
+    fn settle_key(
+        &self,
+        app_type: &AppType,
+        provider: &Provider,
+        lease: Option<&KeyLease>,
+        attempt: key_pool::Attempt<'_>,
+    ) -> bool {
+        let (Some(lease), Some(store)) = (lease, self.key_store()) else {
+            return false;
+        };
+        key_pool::record(store, app_type, &provider.id, lease, &attempt)
+            && key_pool::has_usable_key(store, app_type, provider)
+    }
+
     #[cfg(test)]
     #[expect(
         clippy::too_many_arguments,
@@ -263,8 +360,12 @@ impl RequestForwarder {
         let mut pending_upstream_response = None;
         let max_attempts = (options.max_retries as usize).saturating_add(1);
 
-        for provider in providers {
-            if attempted_providers >= max_attempts {
+        let mut queue = VecDeque::from(providers);
+        let mut key_retry = false;
+        while let Some(provider) = queue.pop_front() {
+            // Retrying the same provider with another pooled key is not a
+            // failover attempt.
+            if attempted_providers >= max_attempts && !key_retry {
                 break;
             }
 
@@ -284,15 +385,22 @@ impl RequestForwarder {
             }
 
             attempted_provider = true;
-            attempted_providers += 1;
+            if !std::mem::take(&mut key_retry) {
+                attempted_providers += 1;
+                self.record_provider_attempt();
+            }
             pending_upstream_response = None;
+            let key_lease = self.checkout_key(app_type, &provider);
+            let request_provider = key_lease
+                .as_ref()
+                .map_or(&provider, |lease| &lease.provider);
             let provider_needs_transform = matches!(app_type, AppType::Claude)
                 && get_adapter(app_type).needs_transform(&provider);
 
             match self
                 .send_streaming_request(
                     app_type,
-                    &provider,
+                    request_provider,
                     endpoint,
                     &body,
                     headers,
@@ -306,6 +414,29 @@ impl RequestForwarder {
             {
                 Ok(outcome) => {
                     let response = outcome.response;
+                    if self.settle_key(
+                        app_type,
+                        &provider,
+                        key_lease.as_ref(),
+                        key_attempt(
+                            response.status(),
+                            response.headers(),
+                            response.buffered_body(),
+                        ),
+                    ) {
+                        if !bypass_circuit_breaker {
+                            self.router
+                                .release_permit_neutral(
+                                    &provider.id,
+                                    app_type.as_str(),
+                                    permit.used_half_open_permit,
+                                )
+                                .await;
+                        }
+                        key_retry = true;
+                        queue.push_front(provider);
+                        continue;
+                    }
                     if response.status().is_success() {
                         if !bypass_circuit_breaker {
                             let _ = self
@@ -409,6 +540,37 @@ impl RequestForwarder {
                 }
                 Err(StreamingRequestError::BeforeResponse(error))
                 | Err(StreamingRequestError::AfterResponse(error)) => {
+                    let (status, body) = match &error {
+                        ProxyError::UpstreamError { status, body } => {
+                            (Some(*status), body.as_deref())
+                        }
+                        _ => (None, None),
+                    };
+                    let error_message = error.to_string();
+                    if self.settle_key(
+                        app_type,
+                        &provider,
+                        key_lease.as_ref(),
+                        key_pool::Attempt {
+                            status,
+                            body,
+                            retry_after: None,
+                            error: Some(&error_message),
+                        },
+                    ) {
+                        if !bypass_circuit_breaker {
+                            self.router
+                                .release_permit_neutral(
+                                    &provider.id,
+                                    app_type.as_str(),
+                                    permit.used_half_open_permit,
+                                )
+                                .await;
+                        }
+                        key_retry = true;
+                        queue.push_front(provider);
+                        continue;
+                    }
                     match classify_attempt_error(&error, app_type, &provider) {
                         AttemptDecision::ProviderFailure => {
                             if !bypass_circuit_breaker {
@@ -508,8 +670,12 @@ impl RequestForwarder {
         let mut pending_upstream_response = None;
         let max_attempts = (options.max_retries as usize).saturating_add(1);
 
-        for provider in providers {
-            if attempted_providers >= max_attempts {
+        let mut queue = VecDeque::from(providers);
+        let mut key_retry = false;
+        while let Some(provider) = queue.pop_front() {
+            // Retrying the same provider with another pooled key is not a
+            // failover attempt.
+            if attempted_providers >= max_attempts && !key_retry {
                 break;
             }
 
@@ -529,15 +695,22 @@ impl RequestForwarder {
             }
 
             attempted_provider = true;
-            attempted_providers += 1;
+            if !std::mem::take(&mut key_retry) {
+                attempted_providers += 1;
+                self.record_provider_attempt();
+            }
             pending_upstream_response = None;
+            let key_lease = self.checkout_key(app_type, &provider);
+            let request_provider = key_lease
+                .as_ref()
+                .map_or(&provider, |lease| &lease.provider);
             let provider_needs_transform = matches!(app_type, AppType::Claude)
                 && get_adapter(app_type).needs_transform(&provider);
 
             match self
                 .send_buffered_request(
                     app_type,
-                    &provider,
+                    request_provider,
                     endpoint,
                     &body,
                     headers,
@@ -551,6 +724,25 @@ impl RequestForwarder {
             {
                 Ok(outcome) => {
                     let response = outcome.response;
+                    if self.settle_key(
+                        app_type,
+                        &provider,
+                        key_lease.as_ref(),
+                        key_attempt(response.status, &response.headers, Some(&response.body)),
+                    ) {
+                        if !bypass_circuit_breaker {
+                            self.router
+                                .release_permit_neutral(
+                                    &provider.id,
+                                    app_type.as_str(),
+                                    permit.used_half_open_permit,
+                                )
+                                .await;
+                        }
+                        key_retry = true;
+                        queue.push_front(provider);
+                        continue;
+                    }
                     if response.status.is_success() {
                         if !bypass_circuit_breaker {
                             let _ = self
@@ -654,6 +846,37 @@ impl RequestForwarder {
                 }
                 Err(BufferedRequestError::BeforeResponse(error))
                 | Err(BufferedRequestError::AfterResponse(error)) => {
+                    let (status, body) = match &error {
+                        ProxyError::UpstreamError { status, body } => {
+                            (Some(*status), body.as_deref())
+                        }
+                        _ => (None, None),
+                    };
+                    let error_message = error.to_string();
+                    if self.settle_key(
+                        app_type,
+                        &provider,
+                        key_lease.as_ref(),
+                        key_pool::Attempt {
+                            status,
+                            body,
+                            retry_after: None,
+                            error: Some(&error_message),
+                        },
+                    ) {
+                        if !bypass_circuit_breaker {
+                            self.router
+                                .release_permit_neutral(
+                                    &provider.id,
+                                    app_type.as_str(),
+                                    permit.used_half_open_permit,
+                                )
+                                .await;
+                        }
+                        key_retry = true;
+                        queue.push_front(provider);
+                        continue;
+                    }
                     match classify_attempt_error(&error, app_type, &provider) {
                         AttemptDecision::ProviderFailure => {
                             if !bypass_circuit_breaker {
@@ -731,6 +954,7 @@ impl RequestForwarder {
                     &request_body,
                     headers,
                     options,
+                    rectifier_config.enabled,
                 )
                 .await
                 .map_err(StreamingRequestError::BeforeResponse)?;
@@ -928,6 +1152,7 @@ impl RequestForwarder {
                     &request_body,
                     headers,
                     options,
+                    rectifier_config.enabled,
                 )
                 .await
                 .map_err(BufferedRequestError::BeforeResponse)?;
@@ -1169,6 +1394,28 @@ fn maybe_rectify_claude_buffered_request(
     None
 }
 
+/// What the key pool needs from an upstream response: the status, the
+/// `Retry-After` and, for failures, the body.
+fn key_attempt<'a>(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    body: Option<&'a Bytes>,
+) -> key_pool::Attempt<'a> {
+    let retry_after = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    key_pool::Attempt {
+        status: Some(status.as_u16()),
+        body: body
+            .filter(|_| !status.is_success())
+            .and_then(|body| std::str::from_utf8(body).ok()),
+        retry_after,
+        error: None,
+    }
+}
+
 fn should_buffer_streaming_error_response(app_type: &AppType, status: reqwest::StatusCode) -> bool {
     *app_type == AppType::Claude && !status.is_success()
 }
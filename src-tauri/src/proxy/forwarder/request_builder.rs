@@ -9,7 +9,7 @@ use crate::{
 
 use super::super::{
     body_filter::filter_private_params_with_whitelist,
-    copilot_optimizer,
+    capability_rectifier, copilot_optimizer,
     error::ProxyError,
     http_client,
     json_canonical::canonicalize_value,
@@ -20,7 +20,7 @@ use super::super::{
         normalize_anthropic_tool_thinking_history_for_provider,
         resolve_codex_chat_reasoning_config, should_convert_codex_responses_to_anthropic,
         should_convert_codex_responses_to_chat, transform_codex_anthropic, transform_codex_chat,
-        AuthStrategy, ProviderAdapter,
+        AuthInfo, AuthStrategy, ProviderAdapter,
     },
     session,
 };
@@ -114,7 +114,7 @@ impl RequestForwarder {
     ) -> Result<reqwest::RequestBuilder, ProxyError> {
         let client = self.client_for_provider(app_type, provider);
         self.prepare_request_with_client(
-            app_type, provider, &client, endpoint, body, headers, options,
+            app_type, provider, &client, endpoint, body, headers, options, true,
         )
         .await
     }
@@ -132,6 +132,7 @@ impl RequestForwarder {
         body: &Value,
         headers: &HeaderMap,
         options: ForwardOptions,
+        rectify_capabilities: bool,
     ) -> Result<reqwest::RequestBuilder, ProxyError> {
         let adapter = get_adapter(app_type);
         let is_claude_request = matches!(app_type, AppType::Claude);
@@ -170,6 +171,10 @@ impl RequestForwarder {
             mapped_body = strip_one_m_suffix_for_upstream_from_body(mapped_body);
         }
 
+        if is_claude_request && rectify_capabilities {
+            rectify_for_probed_capabilities(app_type, provider, &mut mapped_body);
+        }
+
         let copilot_optimization = if is_copilot && self.copilot_optimizer_config.enabled {
             let classification = copilot_optimizer::classify_request(
                 &mapped_body,
@@ -470,6 +475,34 @@ impl RequestForwarder {
     }
 }
 
+/// Drop request features the upstream model is known not to accept, per
+/// `provider probe` results and declared model capabilities.
+fn rectify_for_probed_capabilities(app_type: &AppType, provider: &Provider, body: &mut Value) {
+    let Some(model) = body
+        .get("model")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return;
+    };
+    let capabilities =
+        capability_rectifier::probed_capabilities(app_type.as_str(), &provider.id, &model);
+    let result =
+        capability_rectifier::rectify_anthropic_request(body, provider, &model, capabilities);
+    if result.applied() {
+        log::info!(
+            "[Claude] rectified request for {} ({model}): removed {} image(s){}",
+            provider.id,
+            result.images_removed,
+            if result.thinking_removed {
+                " and thinking"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
 fn prepare_upstream_request_body(request_body: Value) -> Value {
     canonicalize_value(filter_private_params_with_whitelist(request_body, &[]))
 }
@@ -610,6 +643,7 @@ async fn build_request(
     }
 
     if let Some(auth) = adapter.extract_auth(provider) {
+        let auth = resolve_secret_auth(auth).await?;
         let mut effective_auth = auth.clone();
         if auth.strategy == AuthStrategy::GitHubCopilot {
             let account_id = provider
@@ -1206,6 +1240,21 @@ fn build_codex_oauth_session_headers(
     headers
 }
 
+/// Keys stored as secret references (`env:`, `file:`, `cmd:`) are resolved
+/// per request; `secret_ref` caches command output between requests.
+async fn resolve_secret_auth(mut auth: AuthInfo) -> Result<AuthInfo, ProxyError> {
+    if !crate::secret_ref::is_reference(&auth.api_key) {
+        return Ok(auth);
+    }
+    let reference = auth.api_key.clone();
+    auth.api_key =
+        tokio::task::spawn_blocking(move || crate::secret_ref::resolve_value(&reference))
+            .await
+            .map_err(|err| ProxyError::AuthError(err.to_string()))?
+            .map_err(|err| ProxyError::AuthError(err.to_string()))?;
+    Ok(auth)
+}
+
 #[cfg(test)]
 mod tests {
     use super::prepare_upstream_request_body;
@@ -11,7 +11,9 @@ use super::{
 };
 use crate::{
     app_config::AppType,
-    provider::{LocalProxyRequestOverrides, ProviderMeta},
+    key_pool,
+    local_store::LocalStore,
+    provider::{ApiKeyPool, LocalProxyRequestOverrides, PooledApiKey, Provider, ProviderMeta},
     proxy::{
         error::ProxyError,
         forwarder::{ForwardOptions, RequestForwarder},
@@ -1782,3 +1784,170 @@ async fn claude_streaming_openai_chat_budget_rectifier_retries_same_provider() {
 
     server.abort();
 }
+
+#[tokio::test]
+async fn pooled_key_auth_failure_retries_with_next_key() {
+    let (base_url, hits, _bodies, server) = spawn_scripted_upstream(vec![
+        (
+            StatusCode::UNAUTHORIZED,
+            json!({"error": {"message": "invalid x-api-key"}}),
+        ),
+        (StatusCode::OK, json!({"ok": true})),
+    ])
+    .await;
+    let mut provider = Provider::with_id(
+        "p1".to_string(),
+        "Provider p1".to_string(),
+        json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": base_url,
+                "ANTHROPIC_AUTH_TOKEN": "key-p1"
+            }
+        }),
+        None,
+    );
+    provider.meta = Some(ProviderMeta {
+        api_key_pool: Some(ApiKeyPool {
+            keys: vec![PooledApiKey {
+                label: "backup".to_string(),
+                key: "key-backup".to_string(),
+            }],
+            ..ApiKeyPool::default()
+        }),
+        ..ProviderMeta::default()
+    });
+    let (db, router) = test_router().await;
+    let forwarder = RequestForwarder::new(router.clone())
+        .expect("create forwarder")
+        .with_key_store(LocalStore::in_memory().expect("key state store"));
+
+    db.save_provider("claude", &provider)
+        .expect("save provider for health tracking");
+
+    let result = forwarder
+        .forward_buffered_response(
+            &AppType::Claude,
+            "/v1/messages",
+            claude_request_body(),
+            &HeaderMap::new(),
+            vec![provider.clone()],
+            ForwardOptions {
+                max_retries: 0,
+                request_timeout: Some(Duration::from_secs(2)),
+                bypass_circuit_breaker: false,
+            },
+            RectifierConfig::default(),
+        )
+        .await
+        .expect("second pooled key should succeed");
+
+    assert_eq!(result.provider.id, provider.id);
+    assert_eq!(hits.count.load(Ordering::SeqCst), 2);
+    let headers = hits.headers.lock().await;
+    let keys: Vec<_> = headers
+        .iter()
+        .map(|headers| {
+            headers
+                .get("x-api-key")
+                .or_else(|| headers.get("authorization"))
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim_start_matches("Bearer ").to_string())
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        vec![Some("key-p1".to_string()), Some("key-backup".to_string())]
+    );
+
+    let store = forwarder.key_store().expect("key state store");
+    let states = store
+        .list_api_key_states("claude", "p1")
+        .expect("key states");
+    let primary = states
+        .iter()
+        .find(|state| state.key_label == "primary")
+        .expect("primary key state");
+    assert_eq!(primary.disabled_reason.as_deref(), Some("auth"));
+    assert_eq!(primary.last_status, Some(401));
+    let backup = states
+        .iter()
+        .find(|state| state.key_label == "backup")
+        .expect("backup key state");
+    assert_eq!(backup.success_count, 1);
+    assert!(backup.disabled_until.is_none());
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn pooled_key_rate_limit_only_rests_briefly() {
+    let (base_url, hits, _bodies, server) = spawn_scripted_upstream(vec![
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({"error": {"type": "rate_limit_error", "message": "slow down"}}),
+        ),
+        (StatusCode::OK, json!({"ok": true})),
+    ])
+    .await;
+    let mut provider = Provider::with_id(
+        "p1".to_string(),
+        "Provider p1".to_string(),
+        json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": base_url,
+                "ANTHROPIC_AUTH_TOKEN": "key-p1"
+            }
+        }),
+        None,
+    );
+    provider.meta = Some(ProviderMeta {
+        api_key_pool: Some(ApiKeyPool {
+            keys: vec![PooledApiKey {
+                label: "backup".to_string(),
+                key: "key-backup".to_string(),
+            }],
+            ..ApiKeyPool::default()
+        }),
+        ..ProviderMeta::default()
+    });
+    let (db, router) = test_router().await;
+    let forwarder = RequestForwarder::new(router.clone())
+        .expect("create forwarder")
+        .with_key_store(LocalStore::in_memory().expect("key state store"));
+
+    db.save_provider("claude", &provider)
+        .expect("save provider for health tracking");
+
+    forwarder
+        .forward_buffered_response(
+            &AppType::Claude,
+            "/v1/messages",
+            claude_request_body(),
+            &HeaderMap::new(),
+            vec![provider.clone()],
+            ForwardOptions {
+                max_retries: 0,
+                request_timeout: Some(Duration::from_secs(2)),
+                bypass_circuit_breaker: false,
+            },
+            RectifierConfig::default(),
+        )
+        .await
+        .expect("second pooled key should succeed");
+    assert_eq!(hits.count.load(Ordering::SeqCst), 2);
+
+    let store = forwarder.key_store().expect("key state store");
+    let states = store
+        .list_api_key_states("claude", "p1")
+        .expect("key states");
+    let primary = states
+        .iter()
+        .find(|state| state.key_label == "primary")
+        .expect("primary key state");
+    assert_eq!(primary.disabled_reason.as_deref(), Some("rate_limit"));
+    assert_eq!(primary.last_status, Some(429));
+    let rest = primary.disabled_until.expect("primary rests") - chrono::Utc::now().timestamp();
+    assert!(rest <= key_pool::RATE_LIMIT_COOLDOWN.as_secs() as i64);
+
+    server.abort();
+}
@@ -1,6 +1,7 @@
 use axum::http::HeaderMap;
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crate::app_config::AppType;
@@ -28,6 +29,11 @@ pub struct HandlerContext {
     pub session_id: String,
     pub session_client_provided: bool,
     pub current_provider_id_at_start: String,
+    /// Providers tried so far, shared with the forwarder.
+    pub provider_attempts: Arc<AtomicU32>,
+    /// Label of the pooled API key the last attempt used, shared with the
+    /// forwarder.
+    pub api_key_label: Arc<Mutex<Option<String>>>,
 }
 
 impl HandlerContext {
@@ -83,6 +89,8 @@ impl HandlerContext {
             session_id: session_result.session_id,
             session_client_provided: session_result.client_provided,
             current_provider_id_at_start,
+            provider_attempts: Arc::new(AtomicU32::new(0)),
+            api_key_label: Arc::default(),
         })
     }
 
@@ -217,6 +225,7 @@ mod tests {
             provider_router: Arc::new(ProviderRouter::new(db)),
             codex_chat_history: Arc::new(Default::default()),
             gemini_shadow: Arc::new(GeminiShadowStore::default()),
+            local_store: Default::default(),
         }
     }
 
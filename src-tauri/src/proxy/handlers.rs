@@ -15,7 +15,10 @@ use super::{
     forwarder::{ForwardOptions, RequestForwarder},
     handler_context::HandlerContext,
     metrics::estimate_tokens_from_value,
-    providers::{ClaudeAdapter, ProviderAdapter},
+    providers::{
+        gemini_shadow::GeminiShadowStore, transform_gemini::AnthropicToolSchemaHints,
+        ClaudeAdapter, ProviderAdapter,
+    },
     response::{
         build_anthropic_stream_response, build_buffered_codex_anthropic_response_with_context,
         build_buffered_codex_chat_response, build_buffered_codex_chat_response_with_context,
@@ -171,6 +174,9 @@ async fn handle_claude_request(
             .with_optimizer_config(context.optimizer_config.clone())
             .with_copilot_optimizer_config(context.copilot_optimizer_config.clone())
             .with_session(context.session_id.clone(), context.session_client_provided)
+            .with_attempt_counter(context.provider_attempts.clone())
+            .with_key_label_slot(context.api_key_label.clone())
+            .with_local_store(context.state.local_store.clone())
             .with_gemini_shadow(context.state.gemini_shadow.clone()),
         Err(error) => {
             context.state.record_request_error(&error).await;
@@ -360,30 +366,13 @@ async fn handle_claude_request(
         provider: provider.clone(),
         current_provider_id_at_start: context.current_provider_id_at_start.clone(),
     });
-    let api_format = super::providers::get_claude_api_format(provider);
-    let response_result = if adapter.needs_transform(provider) {
-        build_buffered_claude_transform_response(
-            status,
-            &response.headers,
-            response.body,
-            provider.is_codex_oauth() && api_format == "openai_responses",
-            |body| {
-                if api_format == "gemini_native" {
-                    super::providers::transform_gemini_response_for_provider(
-                        body,
-                        provider,
-                        Some(&context.session_id),
-                        Some(context.state.gemini_shadow.as_ref()),
-                        tool_schema_hints.as_ref(),
-                    )
-                } else {
-                    adapter.transform_response(body)
-                }
-            },
-        )
-    } else {
-        build_buffered_passthrough_response(status, &response.headers, response.body)
-    };
+    let response_result = prepare_buffered_claude_response(
+        provider,
+        response,
+        Some(&context.session_id),
+        Some(context.state.gemini_shadow.as_ref()),
+        tool_schema_hints.as_ref(),
+    );
 
     ResponseHandler::finish_buffered(
         &context.state,
@@ -395,6 +384,46 @@ async fn handle_claude_request(
     .await
 }
 
+/// Turn a buffered upstream reply into the Anthropic Messages response the
+/// client sees, converting from the provider's API format when needed.
+pub(crate) fn prepare_buffered_claude_response(
+    provider: &Provider,
+    response: super::forwarder::BufferedResponse,
+    session_id: Option<&str>,
+    gemini_shadow: Option<&GeminiShadowStore>,
+    tool_schema_hints: Option<&AnthropicToolSchemaHints>,
+) -> Result<PreparedResponse, ProxyError> {
+    let adapter = ClaudeAdapter::new();
+    if !adapter.needs_transform(provider) {
+        return build_buffered_passthrough_response(
+            response.status,
+            &response.headers,
+            response.body,
+        );
+    }
+
+    let api_format = super::providers::get_claude_api_format(provider);
+    build_buffered_claude_transform_response(
+        response.status,
+        &response.headers,
+        response.body,
+        provider.is_codex_oauth() && api_format == "openai_responses",
+        |body| {
+            if api_format == "gemini_native" {
+                super::providers::transform_gemini_response_for_provider(
+                    body,
+                    provider,
+                    session_id,
+                    gemini_shadow,
+                    tool_schema_hints,
+                )
+            } else {
+                adapter.transform_response(body)
+            }
+        },
+    )
+}
+
 fn build_buffered_claude_transform_response<F>(
     status: reqwest::StatusCode,
     headers: &reqwest::header::HeaderMap,
@@ -514,6 +543,9 @@ async fn handle_passthrough_request(
             .with_optimizer_config(context.optimizer_config.clone())
             .with_copilot_optimizer_config(context.copilot_optimizer_config.clone())
             .with_session(context.session_id.clone(), context.session_client_provided)
+            .with_attempt_counter(context.provider_attempts.clone())
+            .with_key_label_slot(context.api_key_label.clone())
+            .with_local_store(context.state.local_store.clone())
             .with_codex_chat_history(context.state.codex_chat_history.clone()),
         Err(error) => {
             context.state.record_request_error(&error).await;
@@ -1345,6 +1377,7 @@ mod tests {
             provider_router: Arc::new(ProviderRouter::new(db)),
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             gemini_shadow: Arc::new(GeminiShadowStore::default()),
+            local_store: Default::default(),
         }
     }
 
@@ -1,5 +1,7 @@
 pub mod body_filter;
 pub mod cache_injector;
+pub(crate) mod capability_probe;
+pub(crate) mod capability_rectifier;
 pub mod circuit_breaker;
 pub mod copilot_optimizer;
 pub mod error;
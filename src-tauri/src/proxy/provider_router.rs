@@ -1,19 +1,38 @@
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
-use crate::{app_config::AppType, database::Database, provider::Provider};
+use crate::{
+    app_config::AppType, database::Database, local_store::SharedLocalStore, provider::Provider,
+};
 
 mod upstream_endpoint;
 
 use super::{
-    circuit_breaker::{AllowResult, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats},
+    circuit_breaker::{
+        AllowResult, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState,
+    },
     error::ProxyError,
 };
 
+/// How often the probe results recorded by the daemon's health monitor are
+/// read back, per app.
+const PROBE_SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
 pub struct ProviderRouter {
     db: Arc<Database>,
     circuit_breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
+    /// When the probe results of each app were last read.
+    probe_synced_at: Mutex<HashMap<String, Instant>>,
+    /// Newest probe applied to each breaker, by circuit key.
+    applied_probes: Mutex<HashMap<String, i64>>,
+    /// Where the health monitor records its probes.
+    probe_store: SharedLocalStore,
 }
 
 impl ProviderRouter {
@@ -21,6 +40,9 @@ impl ProviderRouter {
         Self {
             db,
             circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            probe_synced_at: Mutex::new(HashMap::new()),
+            applied_probes: Mutex::new(HashMap::new()),
+            probe_store: SharedLocalStore::default(),
         }
     }
 
@@ -37,6 +59,7 @@ impl ProviderRouter {
             .unwrap_or(false);
 
         if auto_failover_enabled {
+            self.apply_probe_results(app_type).await;
             let all_providers = self
                 .db
                 .get_all_providers(app_type)
@@ -82,6 +105,69 @@ impl ProviderRouter {
         Ok(result)
     }
 
+    /// Opens the breakers of providers whose recent health probes keep
+    /// failing and closes the ones a probe found reachable again.
+    async fn apply_probe_results(&self, app_type: &str) {
+        {
+            let mut synced_at = self.probe_synced_at.lock().await;
+            if synced_at
+                .get(app_type)
+                .is_some_and(|at| at.elapsed() < PROBE_SYNC_INTERVAL)
+            {
+                return;
+            }
+            synced_at.insert(app_type.to_string(), Instant::now());
+        }
+
+        let settings = crate::settings::load_health_monitor_settings_from_disk();
+        if !settings.enabled || settings.failure_threshold == 0 {
+            return;
+        }
+        // Results older than a few probe intervals no longer describe the
+        // provider.
+        let since = chrono::Utc::now().timestamp() - settings.interval_secs as i64 * 3;
+        let entries = match crate::services::health_monitor::load_checks(
+            &self.db,
+            self.probe_store.get(),
+            Some(app_type),
+            None,
+            since,
+        ) {
+            Ok(entries) => entries,
+            Err(error) => {
+                log::debug!("[{app_type}] read health probes failed: {error}");
+                return;
+            }
+        };
+
+        let mut applied = self.applied_probes.lock().await;
+        for (provider_id, verdict) in crate::services::health_monitor::probe_verdicts(&entries) {
+            let circuit_key = format!("{app_type}:{provider_id}");
+            if applied
+                .get(&circuit_key)
+                .is_some_and(|tested_at| *tested_at >= verdict.tested_at)
+            {
+                continue;
+            }
+            applied.insert(circuit_key.clone(), verdict.tested_at);
+
+            let breaker = self.get_or_create_circuit_breaker(&circuit_key).await;
+            let state = breaker.get_state().await;
+            if verdict.consecutive_failures >= settings.failure_threshold {
+                if state != CircuitState::Open {
+                    log::info!(
+                        "[{app_type}] opening circuit for {provider_id}: {} failed health probe(s)",
+                        verdict.consecutive_failures
+                    );
+                }
+                breaker.trip().await;
+            } else if verdict.healthy && state != CircuitState::Closed {
+                log::info!("[{app_type}] closing circuit for {provider_id}: health probe passed");
+                breaker.reset().await;
+            }
+        }
+    }
+
     pub async fn allow_provider_request(&self, provider_id: &str, app_type: &str) -> AllowResult {
         let breaker = self
             .get_or_create_circuit_breaker(&format!("{app_type}:{provider_id}"))
@@ -369,3 +369,66 @@ async fn test_record_result_uses_app_failure_threshold_for_health_updates() {
     assert_eq!(second_health.consecutive_failures, 2);
     assert_eq!(second_health.last_error.as_deref(), Some("fail-2"));
 }
+
+#[tokio::test]
+#[serial(home_settings)]
+async fn test_health_probes_open_and_close_queued_provider_circuits() {
+    use crate::services::stream_check::{HealthStatus, StreamCheckResult};
+
+    let _home = TempHome::new();
+    let db = Arc::new(Database::memory().unwrap());
+
+    for id in ["a", "b"] {
+        let provider = Provider::with_id(id.to_string(), id.to_uppercase(), json!({}), None);
+        db.save_provider("claude", &provider).unwrap();
+        db.add_to_failover_queue("claude", id).unwrap();
+    }
+    let mut config = db.get_proxy_config_for_app("claude").await.unwrap();
+    config.enabled = true;
+    config.auto_failover_enabled = true;
+    db.update_proxy_config_for_app(config).await.unwrap();
+    crate::settings::set_health_monitor_settings(crate::settings::HealthMonitorSettings {
+        enabled: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let probe = |success: bool, tested_at: i64| StreamCheckResult {
+        status: if success {
+            HealthStatus::Operational
+        } else {
+            HealthStatus::Failed
+        },
+        success,
+        message: String::new(),
+        response_time_ms: success.then_some(120),
+        http_status: None,
+        model_used: String::new(),
+        tested_at,
+        retry_count: 0,
+        error_category: None,
+    };
+    let now = chrono::Utc::now().timestamp();
+    db.save_stream_check_log("a", "A", "claude", &probe(false, now - 20))
+        .unwrap();
+    db.save_stream_check_log("a", "A", "claude", &probe(false, now - 10))
+        .unwrap();
+    db.save_stream_check_log("b", "B", "claude", &probe(true, now - 10))
+        .unwrap();
+
+    let router = ProviderRouter::new(db.clone());
+    let providers = router.select_providers("claude").await.unwrap();
+    assert_eq!(
+        providers.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+        vec!["b"]
+    );
+
+    db.save_stream_check_log("a", "A", "claude", &probe(true, now))
+        .unwrap();
+    router.probe_synced_at.lock().await.clear();
+    let providers = router.select_providers("claude").await.unwrap();
+    assert_eq!(
+        providers.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+}
@@ -55,6 +55,7 @@ fn test_state_with_db(db: Arc<Database>) -> ProxyServerState {
         provider_router: Arc::new(ProviderRouter::new(db)),
         codex_chat_history: Arc::new(Default::default()),
         gemini_shadow: Arc::new(GeminiShadowStore::default()),
+        local_store: Default::default(),
     }
 }
 
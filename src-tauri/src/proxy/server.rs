@@ -12,7 +12,8 @@ use tokio::{
 use tower_http::cors::{Any, CorsLayer};
 
 use crate::{
-    app_config::AppType, database::Database, provider::Provider, services::proxy::ProxyService,
+    app_config::AppType, database::Database, local_store::SharedLocalStore, provider::Provider,
+    services::proxy::ProxyService,
 };
 
 use super::{
@@ -37,6 +38,8 @@ pub struct ProxyServerState {
     pub provider_router: Arc<ProviderRouter>,
     pub codex_chat_history: Arc<CodexChatHistoryStore>,
     pub gemini_shadow: Arc<GeminiShadowStore>,
+    /// Sidecar store for key pool state and per-key usage, shared by requests.
+    pub(crate) local_store: SharedLocalStore,
 }
 
 impl ProxyServerState {
@@ -125,6 +128,13 @@ impl ProxyServerState {
             .set_current_provider(app_type.as_str(), &provider.id)
             .ok();
         crate::settings::set_current_provider(app_type, Some(&provider.id)).ok();
+        crate::services::switch_history::record_best_effort(
+            &self.db,
+            app_type,
+            Some(current_provider_id_at_start).filter(|id| !id.is_empty()),
+            &provider.id,
+            crate::services::switch_history::SwitchSource::Failover,
+        );
 
         if takeover_enabled {
             ProxyService::new(self.db.clone())
@@ -210,6 +220,7 @@ impl ProxyServer {
                 provider_router,
                 codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
                 gemini_shadow: Arc::new(GeminiShadowStore::default()),
+                local_store: SharedLocalStore::default(),
             },
             shutdown_tx: Arc::new(RwLock::new(None)),
             server_handle: Arc::new(RwLock::new(None)),
@@ -504,6 +515,7 @@ mod tests {
             provider_router: Arc::new(ProviderRouter::new(db)),
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             gemini_shadow: Arc::new(GeminiShadowStore::default()),
+            local_store: Default::default(),
         }
     }
 
@@ -2,22 +2,44 @@
 //!
 //! 确保同一应用同时只有一个供应商切换操作在执行，
 //! 防止并发切换导致当前供应商与 Live 备份不一致。
+//! 进程内用互斥锁；落盘数据库另在数据库旁加文件锁，
+//! 使守护进程、TUI 与一次性 CLI 命令之间的切换同样串行。
 
 use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
+use crate::error::AppError;
+
 #[derive(Clone, Default)]
 pub struct SwitchLockManager {
     locks: Arc<RwLock<HashMap<String, Arc<Mutex<()>>>>>,
 }
 
+/// 持有期间同一应用的其他切换（本进程或其他进程）都会等待。
+pub struct SwitchGuard {
+    file: Option<File>,
+    _process: OwnedMutexGuard<()>,
+}
+
+impl Drop for SwitchGuard {
+    fn drop(&mut self) {
+        if let Some(file) = &self.file {
+            let _ = file.unlock();
+        }
+    }
+}
+
 impl SwitchLockManager {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub async fn lock_for_app(&self, app_type: &str) -> OwnedMutexGuard<()> {
+    /// 先取进程内锁，再取 `lock_path` 的文件锁（内存数据库传 `None`）。
+    /// 文件锁不可用时记录警告并仅依赖进程内锁，不阻断切换。
+    pub async fn lock_for_app(&self, app_type: &str, lock_path: Option<&Path>) -> SwitchGuard {
         let lock = {
             let locks = self.locks.read().await;
             if let Some(lock) = locks.get(app_type) {
@@ -31,7 +53,73 @@ impl SwitchLockManager {
                     .clone()
             }
         };
+        let process = lock.lock_owned().await;
+
+        let file = match lock_path {
+            Some(path) => match lock_file(path).await {
+                Ok(file) => Some(file),
+                Err(err) => {
+                    log::warn!("切换文件锁不可用，仅使用进程内锁: {err}");
+                    None
+                }
+            },
+            None => None,
+        };
+        SwitchGuard {
+            file,
+            _process: process,
+        }
+    }
+}
+
+/// 文件锁可能要等其他进程的切换结束，阻塞等待放到阻塞线程池，不占用运行时线程。
+/// 同步切换经 `futures::executor::block_on` 调用时没有 Tokio 运行时，
+/// 此时本就在调用方的阻塞线程上，直接等待。
+async fn lock_file(path: &Path) -> Result<File, AppError> {
+    let path = path.to_path_buf();
+    let lock = move || {
+        let file = crate::database::open_lock_file(&path, "供应商切换锁")?;
+        file.lock().map_err(|err| AppError::io(&path, err))?;
+        Ok(file)
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => handle
+            .spawn_blocking(lock)
+            .await
+            .map_err(|err| AppError::Message(format!("等待供应商切换锁失败: {err}")))?,
+        Err(_) => lock(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_lock_is_held_until_the_guard_drops() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cc-switch.db.switch-claude.lock");
+
+        // 两个管理器模拟两个进程：互不共享进程内锁，只共享锁文件。
+        let guard = SwitchLockManager::new()
+            .lock_for_app("claude", Some(&path))
+            .await;
+        let other = File::open(&path).expect("open lock file");
+        assert!(other.try_lock().is_err());
+
+        drop(guard);
+        assert!(other.try_lock().is_ok());
+    }
+
+    #[test]
+    fn file_lock_works_without_a_tokio_runtime() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cc-switch.db.switch-codex.lock");
 
-        lock.lock_owned().await
+        // 同步切换路径用 futures 执行器驱动，没有 Tokio 运行时。
+        let guard = futures::executor::block_on(
+            SwitchLockManager::new().lock_for_app("codex", Some(&path)),
+        );
+        assert!(guard.file.is_some());
     }
 }
@@ -1,9 +1,12 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use rust_decimal::prelude::ToPrimitive;
+
 use crate::{
     app_config::AppType,
     provider::Provider,
     proxy::{error::ProxyError, handler_context::HandlerContext, server::ProxyServerState},
+    services::otel_export::{self, RequestSpan},
     services::sql_helpers::{INPUT_TOKEN_SEMANTICS_FRESH, INPUT_TOKEN_SEMANTICS_TOTAL},
 };
 
@@ -48,6 +51,8 @@ pub struct RequestLogContext {
     pub started_at: std::time::Instant,
     pub is_streaming: bool,
     pub policy: UsageLogPolicy,
+    pub provider_attempts: u32,
+    pub api_key_label: Option<String>,
 }
 
 impl RequestLogContext {
@@ -65,6 +70,14 @@ impl RequestLogContext {
             started_at: context.start_time,
             is_streaming,
             policy,
+            provider_attempts: context
+                .provider_attempts
+                .load(std::sync::atomic::Ordering::Relaxed),
+            api_key_label: context
+                .api_key_label
+                .lock()
+                .ok()
+                .and_then(|label| label.clone()),
         }
     }
 
@@ -79,7 +92,7 @@ pub async fn log_buffered_response(
     status_code: u16,
     body: &[u8],
 ) {
-    if !logging_enabled(state).await {
+    if !logging_enabled(state).await && !otel_export::traces_enabled() {
         return;
     }
 
@@ -121,7 +134,7 @@ pub async fn log_stream_response(
     status_code: u16,
     collector: &StreamLogCollector,
 ) {
-    if !logging_enabled(state).await {
+    if !logging_enabled(state).await && !otel_export::traces_enabled() {
         return;
     }
 
@@ -162,7 +175,7 @@ pub async fn log_error_request(
     context: &RequestLogContext,
     error: &ProxyError,
 ) {
-    if !logging_enabled(state).await {
+    if !logging_enabled(state).await && !otel_export::traces_enabled() {
         return;
     }
 
@@ -211,6 +224,54 @@ async fn insert_request_log(
         .unwrap_or(0);
     let input_token_semantics = input_token_semantics_for_app(&context.app_type);
 
+    if otel_export::traces_enabled() {
+        let (start_unix_nanos, end_unix_nanos) = RequestSpan::timestamps(context.started_at);
+        otel_export::record_request_span(RequestSpan {
+            app_type: context.app_type.as_str().to_string(),
+            provider_id: context.provider.id.clone(),
+            provider_name: context.provider.name.clone(),
+            request_model: context.request_model.clone(),
+            response_model: model.to_string(),
+            status_code,
+            is_streaming: context.is_streaming,
+            session_id: context.session_id.clone(),
+            input_tokens: u64::from(usage.input_tokens),
+            output_tokens: u64::from(usage.output_tokens),
+            cache_read_tokens: u64::from(usage.cache_read_tokens),
+            cache_creation_tokens: u64::from(usage.cache_creation_tokens),
+            cost_usd: cost.as_ref().and_then(|value| value.total_cost.to_f64()),
+            first_token_ms,
+            provider_attempts: context.provider_attempts.max(1),
+            start_unix_nanos,
+            end_unix_nanos,
+            error_message: error_message.clone(),
+        });
+    }
+    if let Some(label) = &context.api_key_label {
+        // Per-key totals live with the key pool state in the sidecar store.
+        let recorded = state.local_store.get().map(|store| {
+            store.add_api_key_usage(
+                context.app_type.as_str(),
+                &context.provider.id,
+                label,
+                u64::from(usage.input_tokens),
+                u64::from(usage.output_tokens),
+                cost.as_ref()
+                    .and_then(|value| value.total_cost.to_f64())
+                    .unwrap_or(0.0),
+            )
+        });
+        if let Some(Err(error)) = recorded {
+            log::debug!(
+                "record API key usage for {}/{label} failed: {error}",
+                context.provider.id
+            );
+        }
+    }
+    if !logging_enabled(state).await {
+        return;
+    }
+
     let conn = match state.db.conn.lock() {
         Ok(conn) => conn,
         Err(error) => {
@@ -219,7 +280,7 @@ async fn insert_request_log(
         }
     };
 
-    match conn.execute(
+    let recorded = match conn.execute(
         "INSERT OR REPLACE INTO proxy_request_logs (
             request_id, provider_id, app_type, model, request_model, pricing_model,
             input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
@@ -271,11 +332,17 @@ async fn insert_request_log(
                 Ok(_) => {}
                 Err(error) => log::warn!("deduplicate proxy/session usage logs failed: {error}"),
             }
+            true
         }
-        Ok(_) => {}
+        Ok(inserted) => inserted > 0,
         Err(error) => {
             log::warn!("record proxy request log failed: {error}");
+            false
         }
+    };
+    drop(conn);
+    if recorded {
+        crate::usage_events::notify_log_recorded();
     }
 }
 
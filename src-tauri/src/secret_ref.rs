@@ -0,0 +1,471 @@
+//! Secret references in provider settings.
+//!
+//! A credential field (one whose name contains `key`, `token`, `secret`,
+//! `password` or `authorization`) may hold a reference instead of the secret
+//! itself:
+//!
+//! - `env:NAME` reads an environment variable of the cc-switch process;
+//! - `file:/path/to/key` reads a file (`~/` expands to the home directory);
+//! - `cmd:pass show relay/key` runs a command through the shell and uses its
+//!   standard output, e.g. `op read`, `pass` or `secret-tool lookup`;
+//! - `vault:v1:...` is a value sealed by the built-in vault (see
+//!   [`crate::vault`]).
+//!
+//! References are stored as-is, so the database, SQL exports, backups and
+//! sync artifacts never carry the secret. They are resolved only when a live
+//! config is written, when the proxy authenticates upstream and for processes
+//! cc-switch launches; refreshing a provider from its live config puts the
+//! references back before anything is saved.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::error::AppError;
+use crate::provider::Provider;
+
+/// How long a command is given to print the secret.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(15);
+/// Command output is reused for this long, so password managers are not
+/// prompted on every request.
+const COMMAND_CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SecretRef<'a> {
+    Env(&'a str),
+    File(&'a str),
+    Cmd(&'a str),
+    Vault(&'a str),
+}
+
+impl<'a> SecretRef<'a> {
+    pub(crate) fn parse(value: &'a str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(name) = value.strip_prefix("env:") {
+            Some(Self::Env(name.trim()))
+        } else if let Some(path) = value.strip_prefix("file:") {
+            Some(Self::File(path.trim()))
+        } else if let Some(command) = value.strip_prefix("cmd:") {
+            Some(Self::Cmd(command.trim()))
+        } else {
+            value
+                .strip_prefix(crate::vault::SEALED_PREFIX)
+                .map(Self::Vault)
+        }
+    }
+
+    pub(crate) fn resolve(self) -> Result<String, AppError> {
+        match self {
+            Self::Env(name) => resolve_env(name),
+            Self::File(path) => resolve_file(path),
+            Self::Cmd(command) => resolve_cmd(command),
+            Self::Vault(payload) => crate::vault::resolve(payload),
+        }
+    }
+}
+
+/// Whether a settings field with this name holds a credential. Matches on the
+/// end of the name so limits such as `MAX_THINKING_TOKENS` and names of other
+/// fields such as `env_key` are left alone.
+pub(crate) fn is_secret_field(name: &str) -> bool {
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    name == "key"
+        || [
+            "apikey",
+            "accesskey",
+            "secretkey",
+            "privatekey",
+            "token",
+            "secret",
+            "password",
+            "passwd",
+            "authorization",
+        ]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+pub(crate) fn is_reference(value: &str) -> bool {
+    SecretRef::parse(value).is_some()
+}
+
+/// The secret a stored value stands for; plain values are returned unchanged.
+pub(crate) fn resolve_value(value: &str) -> Result<String, AppError> {
+    match SecretRef::parse(value) {
+        Some(reference) => reference.resolve(),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Copy of `settings` with every referenced credential resolved.
+pub(crate) fn resolve_settings(settings: &Value) -> Result<Value, AppError> {
+    let mut resolved = settings.clone();
+    let mut path = Vec::new();
+    resolve_in_place(&mut resolved, &mut path)?;
+    Ok(resolved)
+}
+
+/// Copy of `provider` with its settings resolved, for writing live configs
+/// and launching tools.
+pub(crate) fn resolve_provider(provider: &Provider) -> Result<Provider, AppError> {
+    let mut resolved = provider.clone();
+    if has_references(&provider.settings_config) {
+        resolved.settings_config = resolve_settings(&provider.settings_config)?;
+    }
+    Ok(resolved)
+}
+
+/// Puts the references of `stored` back into `updated` (typically settings
+/// read back from a live config), so resolved secrets are never persisted.
+pub(crate) fn restore_references(stored: &Value, mut updated: Value) -> Value {
+    for (path, reference) in references(stored) {
+        let mut target = Some(&mut updated);
+        for segment in &path {
+            target = target.and_then(|value| value.get_mut(segment.as_str()));
+        }
+        if let Some(target @ Value::String(_)) = target {
+            *target = Value::String(reference);
+        }
+    }
+    updated
+}
+
+/// `(path, reference)` for every credential field of `settings` holding a
+/// reference.
+pub(crate) fn references(settings: &Value) -> Vec<(Vec<String>, String)> {
+    let mut found = Vec::new();
+    collect_references(settings, &mut Vec::new(), &mut found);
+    found
+}
+
+pub(crate) fn has_references(settings: &Value) -> bool {
+    !references(settings).is_empty()
+}
+
+/// Whether any credential field of `settings` holds a plain, non-empty secret.
+pub(crate) fn has_plain_secrets(settings: &Value) -> bool {
+    let Value::Object(map) = settings else {
+        return false;
+    };
+    map.iter().any(|(key, child)| match child {
+        Value::String(raw) => is_secret_field(key) && !raw.trim().is_empty() && !is_reference(raw),
+        Value::Object(_) => has_plain_secrets(child),
+        _ => false,
+    })
+}
+
+/// [`has_plain_secrets`] for a provider's settings and the extra keys of its
+/// key pool.
+pub(crate) fn provider_has_plain_secrets(provider: &Provider) -> bool {
+    has_plain_secrets(&provider.settings_config)
+        || provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.api_key_pool.as_ref())
+            .is_some_and(|pool| {
+                pool.keys
+                    .iter()
+                    .any(|pooled| !pooled.key.trim().is_empty() && !is_reference(&pooled.key))
+            })
+}
+
+fn resolve_in_place(value: &mut Value, path: &mut Vec<String>) -> Result<(), AppError> {
+    let Value::Object(map) = value else {
+        return Ok(());
+    };
+    for (key, child) in map.iter_mut() {
+        path.push(key.clone());
+        match child {
+            Value::String(raw) if is_secret_field(key) => {
+                if let Some(reference) = SecretRef::parse(raw) {
+                    *raw = reference.resolve().map_err(|err| {
+                        AppError::localized(
+                            "secret_ref.field_failed",
+                            format!("无法解析 {} 的密钥引用: {err}", path.join(".")),
+                            format!(
+                                "Failed to resolve the secret reference in {}: {err}",
+                                path.join(".")
+                            ),
+                        )
+                    })?;
+                }
+            }
+            Value::Object(_) => resolve_in_place(child, path)?,
+            _ => {}
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+fn collect_references(
+    value: &Value,
+    path: &mut Vec<String>,
+    found: &mut Vec<(Vec<String>, String)>,
+) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        path.push(key.clone());
+        match child {
+            Value::String(raw) if is_secret_field(key) && is_reference(raw) => {
+                found.push((path.clone(), raw.clone()));
+            }
+            Value::Object(_) => collect_references(child, path, found),
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+fn resolve_env(name: &str) -> Result<String, AppError> {
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => Ok(value.trim().to_string()),
+        _ => Err(AppError::localized(
+            "secret_ref.env_missing",
+            format!("环境变量 {name} 未设置或为空 (env:{name})"),
+            format!("Environment variable {name} is not set or empty (env:{name})"),
+        )),
+    }
+}
+
+fn resolve_file(path: &str) -> Result<String, AppError> {
+    let expanded = match path.strip_prefix("~/") {
+        Some(rest) => crate::config::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| path.into()),
+        None => path.into(),
+    };
+    let contents = std::fs::read_to_string(&expanded).map_err(|err| {
+        AppError::localized(
+            "secret_ref.file_unreadable",
+            format!("无法读取密钥文件 {}: {err}", expanded.display()),
+            format!("Cannot read secret file {}: {err}", expanded.display()),
+        )
+    })?;
+    non_empty(contents.trim(), || format!("file:{path}"))
+}
+
+fn resolve_cmd(command: &str) -> Result<String, AppError> {
+    if let Ok(cache) = command_cache().lock() {
+        if let Some((fetched_at, value)) = cache.get(command) {
+            if fetched_at.elapsed() < COMMAND_CACHE_TTL {
+                return Ok(value.clone());
+            }
+        }
+    }
+
+    let value = run_command(command)?;
+    if let Ok(mut cache) = command_cache().lock() {
+        cache.insert(command.to_string(), (Instant::now(), value.clone()));
+    }
+    Ok(value)
+}
+
+fn run_command(command: &str) -> Result<String, AppError> {
+    let failed = |detail: String| {
+        AppError::localized(
+            "secret_ref.cmd_failed",
+            format!("密钥命令 `{command}` 失败: {detail}"),
+            format!("Secret command `{command}` failed: {detail}"),
+        )
+    };
+
+    let mut child = shell_command(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| failed(err.to_string()))?;
+
+    // Drain both pipes on threads so a chatty command cannot block on a
+    // full pipe while we wait for it.
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stdout.read_to_string(&mut buf);
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf);
+        buf
+    });
+
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    let status = loop {
+        match child.try_wait().map_err(|err| failed(err.to_string()))? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(failed(format!(
+                    "timed out after {}s",
+                    COMMAND_TIMEOUT.as_secs()
+                )));
+            }
+            None => std::thread::sleep(Duration::from_millis(20)),
+        }
+    };
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    if !status.success() {
+        let detail = stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| status.to_string());
+        return Err(failed(detail));
+    }
+    non_empty(stdout.trim(), || format!("cmd:{command}"))
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("/bin/sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+fn non_empty(value: &str, reference: impl FnOnce() -> String) -> Result<String, AppError> {
+    if value.is_empty() {
+        let reference = reference();
+        return Err(AppError::localized(
+            "secret_ref.empty",
+            format!("密钥引用 {reference} 的结果为空"),
+            format!("Secret reference {reference} resolved to an empty value"),
+        ));
+    }
+    Ok(value.to_string())
+}
+
+fn command_cache() -> &'static Mutex<HashMap<String, (Instant, String)>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, (Instant, String)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_credential_fields_are_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("key");
+        std::fs::write(&key_file, "sk-from-file\n").unwrap();
+
+        let settings = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": format!("file:{}", key_file.display()),
+                "ANTHROPIC_MODEL": "env:NOT_A_SECRET",
+                "ANTHROPIC_BASE_URL": "https://relay.example"
+            }
+        });
+
+        let resolved = resolve_settings(&settings).unwrap();
+        assert_eq!(resolved["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-from-file");
+        assert_eq!(resolved["env"]["ANTHROPIC_MODEL"], "env:NOT_A_SECRET");
+        assert_eq!(
+            references(&settings),
+            vec![(
+                vec!["env".to_string(), "ANTHROPIC_AUTH_TOKEN".to_string()],
+                format!("file:{}", key_file.display())
+            )]
+        );
+    }
+
+    #[test]
+    fn secret_fields_match_credential_suffixes_only() {
+        for name in [
+            "ANTHROPIC_AUTH_TOKEN",
+            "ANTHROPIC_API_KEY",
+            "OPENAI_API_KEY",
+            "apiKey",
+            "x-api-key",
+            "Authorization",
+            "refresh_token",
+            "client_secret",
+            "AWS_SECRET_ACCESS_KEY",
+            "DB_PASSWORD",
+            "key",
+        ] {
+            assert!(is_secret_field(name), "{name} should be a secret field");
+        }
+        for name in [
+            "CLAUDE_CODE_MAX_OUTPUT_TOKENS",
+            "MAX_THINKING_TOKENS",
+            "MAX_MCP_OUTPUT_TOKENS",
+            "env_key",
+            "apiKeyField",
+            "ANTHROPIC_BASE_URL",
+        ] {
+            assert!(
+                !is_secret_field(name),
+                "{name} should not be a secret field"
+            );
+        }
+    }
+
+    #[test]
+    fn restoring_references_drops_resolved_secrets() {
+        let stored = json!({ "auth": { "OPENAI_API_KEY": "cmd:pass show relay" } });
+        let live = json!({
+            "auth": { "OPENAI_API_KEY": "sk-live" },
+            "config": "model = \"gpt-5\""
+        });
+
+        let restored = restore_references(&stored, live);
+        assert_eq!(restored["auth"]["OPENAI_API_KEY"], "cmd:pass show relay");
+        assert_eq!(restored["config"], "model = \"gpt-5\"");
+    }
+
+    #[test]
+    fn errors_name_the_field_and_reference() {
+        let settings = json!({ "env": { "GEMINI_API_KEY": "env:CC_SWITCH_TEST_UNSET_SECRET" } });
+
+        let err = resolve_settings(&settings).unwrap_err().to_string();
+        assert!(err.contains("env.GEMINI_API_KEY"), "{err}");
+        assert!(err.contains("CC_SWITCH_TEST_UNSET_SECRET"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commands_are_resolved_cached_and_report_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("count");
+        let command = format!("echo x >> '{}'; echo sk-cmd", counter.display());
+
+        assert_eq!(resolve_value(&format!("cmd:{command}")).unwrap(), "sk-cmd");
+        assert_eq!(resolve_value(&format!("cmd:{command}")).unwrap(), "sk-cmd");
+        assert_eq!(
+            std::fs::read_to_string(&counter).unwrap().lines().count(),
+            1
+        );
+
+        let err = resolve_value("cmd:echo locked >&2; exit 3")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("locked"), "{err}");
+        assert_eq!(resolve_value("sk-plain").unwrap(), "sk-plain");
+    }
+}
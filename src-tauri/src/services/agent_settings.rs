@@ -0,0 +1,932 @@
+//! Claude Code hooks、权限规则集与状态栏
+//!
+//! 与 MCP 相同的 SSOT 模型：条目统一存放在主库 settings 表（`agent_settings_resources`），
+//! 每个条目带一份 [`McpApps`] 决定同步到哪些应用。同步时把启用的条目追加进各应用的
+//! `settings.json`，并在本机 sidecar 中记录“上次写入了什么”；下次同步先按记录移除、再重新写入，
+//! 因此用户手写的 hooks / 规则原样保留。
+//!
+//! - Claude：`hooks`、`permissions.allow/deny/ask`、`statusLine`
+//! - Gemini：仅 `hooks`，事件名按 [`gemini_event`] 映射，无对应事件的条目跳过
+//! - Codex 没有等价配置，不支持
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::app_config::{
+    AgentSettingsResources, AppType, HookEntry, McpApps, PermissionRuleSet, StatusLineConfig,
+};
+use crate::config::{get_claude_settings_path, read_json_file, write_json_file};
+use crate::error::AppError;
+use crate::gemini_config::get_gemini_settings_path;
+use crate::local_store::LocalStore;
+use crate::store::AppState;
+
+const PROJECTION_META_PREFIX: &str = "agent_settings_projection_";
+
+/// Claude Code 支持的 hook 事件
+pub const CLAUDE_HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Claude 事件名与 Gemini CLI 事件名的对应关系
+const GEMINI_EVENTS: [(&str, &str); 8] = [
+    ("PreToolUse", "BeforeTool"),
+    ("PostToolUse", "AfterTool"),
+    ("UserPromptSubmit", "BeforeAgent"),
+    ("Stop", "AfterAgent"),
+    ("PreCompact", "PreCompress"),
+    ("SessionStart", "SessionStart"),
+    ("SessionEnd", "SessionEnd"),
+    ("Notification", "Notification"),
+];
+
+/// 可同步 hooks 的应用
+pub const HOOK_APPS: [AppType; 2] = [AppType::Claude, AppType::Gemini];
+
+pub fn supports_hooks(app: &AppType) -> bool {
+    matches!(app, AppType::Claude | AppType::Gemini)
+}
+
+/// 权限规则与状态栏目前只有 Claude 有对应配置
+pub fn supports_permissions(app: &AppType) -> bool {
+    matches!(app, AppType::Claude)
+}
+
+fn gemini_event(event: &str) -> Option<&'static str> {
+    GEMINI_EVENTS
+        .iter()
+        .find(|(claude, _)| *claude == event)
+        .map(|(_, gemini)| *gemini)
+}
+
+/// 目标应用中的事件名
+fn app_event(app: &AppType, event: &str) -> Option<String> {
+    match app {
+        AppType::Claude => Some(event.to_string()),
+        AppType::Gemini => gemini_event(event).map(str::to_string),
+        _ => None,
+    }
+}
+
+/// live 配置中的事件名还原为 Claude 事件名
+fn claude_event(app: &AppType, event: &str) -> Option<String> {
+    match app {
+        AppType::Claude => CLAUDE_HOOK_EVENTS
+            .contains(&event)
+            .then(|| event.to_string()),
+        AppType::Gemini => GEMINI_EVENTS
+            .iter()
+            .find(|(_, gemini)| *gemini == event)
+            .map(|(claude, _)| claude.to_string()),
+        _ => None,
+    }
+}
+
+fn settings_path(app: &AppType) -> Option<std::path::PathBuf> {
+    match app {
+        AppType::Claude => Some(get_claude_settings_path()),
+        AppType::Gemini => Some(get_gemini_settings_path()),
+        _ => None,
+    }
+}
+
+/// 写入某个应用 settings.json 的内容；也是 sidecar 中“上次写入”记录的格式
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Projection {
+    /// (应用内事件名, matcher 组)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hooks: Vec<(String, Value)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ask: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status_line: Option<Value>,
+}
+
+impl Projection {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn rules_mut(&mut self) -> [(&'static str, &mut Vec<String>); 3] {
+        [
+            ("allow", &mut self.allow),
+            ("deny", &mut self.deny),
+            ("ask", &mut self.ask),
+        ]
+    }
+
+    fn rules(&self) -> [(&'static str, &Vec<String>); 3] {
+        [
+            ("allow", &self.allow),
+            ("deny", &self.deny),
+            ("ask", &self.ask),
+        ]
+    }
+}
+
+fn hook_group(entry: &HookEntry) -> Value {
+    let mut group = Map::new();
+    if let Some(matcher) = &entry.matcher {
+        group.insert("matcher".to_string(), Value::String(matcher.clone()));
+    }
+    group.insert("hooks".to_string(), Value::Array(entry.hooks.clone()));
+    Value::Object(group)
+}
+
+fn status_line_value(config: &StatusLineConfig) -> Value {
+    let mut value = json!({ "type": "command", "command": config.command });
+    if let Some(padding) = config.padding {
+        value["padding"] = json!(padding);
+    }
+    value
+}
+
+/// 计算某个应用应当写入的内容
+fn render(resources: &AgentSettingsResources, app: &AppType) -> Projection {
+    let mut projection = Projection::default();
+    if supports_hooks(app) {
+        for entry in resources
+            .hooks
+            .iter()
+            .filter(|entry| entry.apps.is_enabled_for(app))
+        {
+            if let Some(event) = app_event(app, &entry.event) {
+                projection.hooks.push((event, hook_group(entry)));
+            }
+        }
+    }
+    if supports_permissions(app) {
+        for set in resources
+            .permissions
+            .iter()
+            .filter(|set| set.apps.is_enabled_for(app))
+        {
+            projection.allow.extend(set.allow.iter().cloned());
+            projection.deny.extend(set.deny.iter().cloned());
+            projection.ask.extend(set.ask.iter().cloned());
+        }
+        projection.status_line = resources.status_line.as_ref().map(status_line_value);
+    }
+    projection
+}
+
+/// 从 live settings 中移除记录过的条目；被清空的容器一并删除
+fn strip_recorded(settings: &mut Value, record: &Projection) {
+    let Some(root) = settings.as_object_mut() else {
+        return;
+    };
+
+    if !record.hooks.is_empty() {
+        if let Some(hooks) = root.get_mut("hooks").and_then(Value::as_object_mut) {
+            for (event, group) in &record.hooks {
+                let Some(groups) = hooks.get_mut(event).and_then(Value::as_array_mut) else {
+                    continue;
+                };
+                if let Some(index) = groups.iter().position(|item| item == group) {
+                    groups.remove(index);
+                    if groups.is_empty() {
+                        hooks.remove(event);
+                    }
+                }
+            }
+            if hooks.is_empty() {
+                root.remove("hooks");
+            }
+        }
+    }
+
+    if record.rules().iter().any(|(_, rules)| !rules.is_empty()) {
+        if let Some(permissions) = root.get_mut("permissions").and_then(Value::as_object_mut) {
+            for (key, rules) in record.rules() {
+                let Some(list) = permissions.get_mut(key).and_then(Value::as_array_mut) else {
+                    continue;
+                };
+                let before = list.len();
+                for rule in rules {
+                    if let Some(index) = list.iter().position(|item| item.as_str() == Some(rule)) {
+                        list.remove(index);
+                    }
+                }
+                if before > 0 && list.is_empty() {
+                    permissions.remove(key);
+                }
+            }
+            if permissions.is_empty() {
+                root.remove("permissions");
+            }
+        }
+    }
+
+    if let Some(status_line) = &record.status_line {
+        if root.get("statusLine") == Some(status_line) {
+            root.remove("statusLine");
+        }
+    }
+}
+
+/// 追加条目并返回实际写入的部分（live 中已有相同条目时跳过，不计入记录）
+fn apply(settings: &mut Value, projection: &Projection) -> Projection {
+    let mut written = Projection::default();
+    if !settings.is_object() {
+        *settings = json!({});
+    }
+    let root = settings.as_object_mut().expect("settings is an object");
+
+    for (event, group) in &projection.hooks {
+        let hooks = root.entry("hooks").or_insert_with(|| json!({}));
+        let Some(groups) = hooks.as_object_mut().map(|hooks| {
+            hooks
+                .entry(event.clone())
+                .or_insert_with(|| Value::Array(Vec::new()))
+        }) else {
+            log::warn!("settings.json 中的 hooks 不是对象，跳过写入");
+            break;
+        };
+        let Some(groups) = groups.as_array_mut() else {
+            log::warn!("settings.json 中的 hooks.{event} 不是数组，跳过写入");
+            continue;
+        };
+        if !groups.contains(group) {
+            groups.push(group.clone());
+            written.hooks.push((event.clone(), group.clone()));
+        }
+    }
+
+    let rules = projection.rules();
+    for ((key, rules), (_, written_rules)) in rules.into_iter().zip(written.rules_mut()) {
+        for rule in rules {
+            let permissions = root.entry("permissions").or_insert_with(|| json!({}));
+            let Some(list) = permissions.as_object_mut().map(|permissions| {
+                permissions
+                    .entry(key)
+                    .or_insert_with(|| Value::Array(Vec::new()))
+            }) else {
+                log::warn!("settings.json 中的 permissions 不是对象，跳过写入");
+                break;
+            };
+            let Some(list) = list.as_array_mut() else {
+                log::warn!("settings.json 中的 permissions.{key} 不是数组，跳过写入");
+                break;
+            };
+            if !list.iter().any(|item| item.as_str() == Some(rule.as_str())) {
+                list.push(Value::String(rule.clone()));
+                written_rules.push(rule.clone());
+            }
+        }
+    }
+
+    if let Some(status_line) = &projection.status_line {
+        root.insert("statusLine".to_string(), status_line.clone());
+        written.status_line = Some(status_line.clone());
+    }
+
+    written
+}
+
+fn meta_key(app: &AppType) -> String {
+    format!("{PROJECTION_META_PREFIX}{}", app.as_str())
+}
+
+fn load_record(store: &LocalStore, app: &AppType) -> Result<Projection, AppError> {
+    match store.get_meta(&meta_key(app))? {
+        Some(json) => serde_json::from_str(&json).map_err(|e| {
+            AppError::Config(format!("解析 {} hooks 写入记录失败: {e}", app.as_str()))
+        }),
+        None => Ok(Projection::default()),
+    }
+}
+
+fn save_record(store: &LocalStore, app: &AppType, record: &Projection) -> Result<(), AppError> {
+    let json =
+        serde_json::to_string(record).map_err(|source| AppError::JsonSerialize { source })?;
+    store.set_meta(&meta_key(app), &json)
+}
+
+/// 从 live settings 中去掉 cc-switch 写入的条目，用于回填供应商快照
+pub(crate) fn strip_projected(app: &AppType, settings: &mut Value) {
+    if !supports_hooks(app) {
+        return;
+    }
+    let record = LocalStore::path()
+        .map(|path| path.exists())
+        .and_then(|exists| {
+            if exists {
+                load_record(&LocalStore::open()?, app)
+            } else {
+                Ok(Projection::default())
+            }
+        });
+    match record {
+        Ok(record) => strip_recorded(settings, &record),
+        Err(err) => log::warn!("读取 {} hooks 写入记录失败: {err}", app.as_str()),
+    }
+}
+
+/// `Pre-commit Lint` -> `pre-commit-lint`，冲突时追加 `-2`、`-3` ...
+pub fn generate_id(taken: &[&str], name: &str, fallback: &str) -> String {
+    let mut base = String::new();
+    for ch in name.to_lowercase().chars() {
+        if ch.is_ascii_lowercase() || ch.is_ascii_digit() {
+            base.push(ch);
+        } else if !base.is_empty() && !base.ends_with('-') {
+            base.push('-');
+        }
+    }
+    let base = match base.trim_end_matches('-') {
+        "" => fallback.to_string(),
+        slug => slug.to_string(),
+    };
+    if !taken.contains(&base.as_str()) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|id| !taken.contains(&id.as_str()))
+        .expect("unbounded suffix search")
+}
+
+fn single_app(app: &AppType) -> McpApps {
+    let mut apps = McpApps::default();
+    apps.set_enabled_for(app, true);
+    apps
+}
+
+fn validate_apps(
+    apps: &McpApps,
+    supported: fn(&AppType) -> bool,
+    feature: &str,
+) -> Result<(), AppError> {
+    if let Some(app) = apps.enabled_apps().into_iter().find(|app| !supported(app)) {
+        let names = [AppType::Claude, AppType::Gemini]
+            .into_iter()
+            .filter(supported)
+            .map(|app| app.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(AppError::InvalidInput(format!(
+            "{} has no {feature} setting; supported apps: {names}",
+            app.as_str()
+        )));
+    }
+    Ok(())
+}
+
+fn validate_hook(entry: &HookEntry) -> Result<(), AppError> {
+    if entry.name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Hook name cannot be empty".to_string(),
+        ));
+    }
+    if !CLAUDE_HOOK_EVENTS.contains(&entry.event.as_str()) {
+        return Err(AppError::InvalidInput(format!(
+            "Unknown hook event '{}'; expected one of: {}",
+            entry.event,
+            CLAUDE_HOOK_EVENTS.join(", ")
+        )));
+    }
+    if entry.hooks.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A hook needs at least one command".to_string(),
+        ));
+    }
+    if entry
+        .hooks
+        .iter()
+        .any(|hook| hook.get("type").and_then(Value::as_str).is_none())
+    {
+        return Err(AppError::InvalidInput(
+            "Every hook needs a \"type\" field".to_string(),
+        ));
+    }
+    validate_apps(&entry.apps, supports_hooks, "hooks")
+}
+
+fn validate_permission_set(set: &PermissionRuleSet) -> Result<(), AppError> {
+    if set.name.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Rule set name cannot be empty".to_string(),
+        ));
+    }
+    if set.allow.is_empty() && set.deny.is_empty() && set.ask.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A rule set needs at least one allow, deny or ask rule".to_string(),
+        ));
+    }
+    validate_apps(&set.apps, supports_permissions, "permissions")
+}
+
+/// 从 live 配置导入的数量
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentSettingsImport {
+    pub hooks: usize,
+    pub permission_rules: usize,
+    pub status_line: bool,
+}
+
+pub struct AgentSettingsService;
+
+impl AgentSettingsService {
+    pub fn get(state: &AppState) -> Result<AgentSettingsResources, AppError> {
+        state.db.get_agent_settings_resources()
+    }
+
+    /// 修改后保存并同步到 live
+    fn update<T>(
+        state: &AppState,
+        change: impl FnOnce(&mut AgentSettingsResources) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let mut resources = Self::get(state)?;
+        let result = change(&mut resources)?;
+        state.db.set_agent_settings_resources(&resources)?;
+        Self::sync(state)?;
+        Ok(result)
+    }
+
+    /// 新增或按 id 替换 hook
+    pub fn upsert_hook(state: &AppState, entry: HookEntry) -> Result<(), AppError> {
+        validate_hook(&entry)?;
+        Self::update(state, |resources| {
+            match resources.hooks.iter_mut().find(|item| item.id == entry.id) {
+                Some(existing) => *existing = entry,
+                None => resources.hooks.push(entry),
+            }
+            Ok(())
+        })
+    }
+
+    pub fn delete_hook(state: &AppState, id: &str) -> Result<bool, AppError> {
+        Self::update(state, |resources| {
+            let before = resources.hooks.len();
+            resources.hooks.retain(|entry| entry.id != id);
+            Ok(resources.hooks.len() != before)
+        })
+    }
+
+    /// 切换 hook 在指定应用的启用状态，返回 hook 是否存在
+    pub fn toggle_hook_app(
+        state: &AppState,
+        id: &str,
+        app: &AppType,
+        enabled: bool,
+    ) -> Result<bool, AppError> {
+        if enabled {
+            validate_apps(&single_app(app), supports_hooks, "hooks")?;
+        }
+        Self::update(state, |resources| {
+            Ok(
+                match resources.hooks.iter_mut().find(|item| item.id == id) {
+                    Some(entry) => {
+                        entry.apps.set_enabled_for(app, enabled);
+                        true
+                    }
+                    None => false,
+                },
+            )
+        })
+    }
+
+    /// 新增或按 id 替换权限规则集
+    pub fn upsert_permission_set(state: &AppState, set: PermissionRuleSet) -> Result<(), AppError> {
+        validate_permission_set(&set)?;
+        Self::update(state, |resources| {
+            match resources
+                .permissions
+                .iter_mut()
+                .find(|item| item.id == set.id)
+            {
+                Some(existing) => *existing = set,
+                None => resources.permissions.push(set),
+            }
+            Ok(())
+        })
+    }
+
+    pub fn delete_permission_set(state: &AppState, id: &str) -> Result<bool, AppError> {
+        Self::update(state, |resources| {
+            let before = resources.permissions.len();
+            resources.permissions.retain(|set| set.id != id);
+            Ok(resources.permissions.len() != before)
+        })
+    }
+
+    /// 切换规则集在指定应用的启用状态，返回规则集是否存在
+    pub fn toggle_permission_app(
+        state: &AppState,
+        id: &str,
+        app: &AppType,
+        enabled: bool,
+    ) -> Result<bool, AppError> {
+        if enabled {
+            validate_apps(&single_app(app), supports_permissions, "permissions")?;
+        }
+        Self::update(state, |resources| {
+            Ok(
+                match resources.permissions.iter_mut().find(|set| set.id == id) {
+                    Some(set) => {
+                        set.apps.set_enabled_for(app, enabled);
+                        true
+                    }
+                    None => false,
+                },
+            )
+        })
+    }
+
+    /// 设置或清除 Claude 状态栏
+    pub fn set_status_line(
+        state: &AppState,
+        status_line: Option<StatusLineConfig>,
+    ) -> Result<(), AppError> {
+        if status_line
+            .as_ref()
+            .is_some_and(|config| config.command.trim().is_empty())
+        {
+            return Err(AppError::InvalidInput(
+                "Status line command cannot be empty".to_string(),
+            ));
+        }
+        Self::update(state, |resources| {
+            resources.status_line = status_line;
+            Ok(())
+        })
+    }
+
+    /// 把所有启用的条目同步到 Claude / Gemini 的 settings.json
+    pub fn sync(state: &AppState) -> Result<(), AppError> {
+        let resources = Self::get(state)?;
+        let mut failures = Vec::new();
+        for app in HOOK_APPS {
+            if let Err(err) = Self::sync_app(&resources, &app) {
+                log::warn!("同步 hooks/权限到 {app:?} 失败: {err}");
+                failures.push(format!("{}: {err}", app.as_str()));
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Message(format!(
+                "Failed to sync hooks/permissions: {}",
+                failures.join("; ")
+            )))
+        }
+    }
+
+    /// 供应商切换后重新写入（该应用的 settings.json 可能已被整体覆盖）
+    pub fn sync_app_best_effort(state: &AppState, app: &AppType) {
+        if !supports_hooks(app) {
+            return;
+        }
+        if let Err(err) = Self::get(state).and_then(|resources| Self::sync_app(&resources, app)) {
+            log::warn!("同步 hooks/权限到 {app:?} 失败: {err}");
+        }
+    }
+
+    fn sync_app(resources: &AgentSettingsResources, app: &AppType) -> Result<(), AppError> {
+        if !crate::sync_policy::should_sync_live(app) {
+            return Ok(());
+        }
+        let Some(path) = settings_path(app) else {
+            return Ok(());
+        };
+        let projection = render(resources, app);
+        let store_exists = LocalStore::path()?.exists();
+        if projection.is_empty() && !store_exists {
+            return Ok(());
+        }
+        let store = LocalStore::open()?;
+        let record = load_record(&store, app)?;
+        if projection.is_empty() && record.is_empty() {
+            return Ok(());
+        }
+
+        let original = if path.exists() {
+            read_json_file::<Value>(&path)?
+        } else {
+            json!({})
+        };
+        if !original.is_object() {
+            return Err(AppError::Config(format!(
+                "{} 不是 JSON 对象",
+                path.display()
+            )));
+        }
+        let mut settings = original.clone();
+        strip_recorded(&mut settings, &record);
+        let written = apply(&mut settings, &projection);
+
+        if settings != original {
+            write_json_file(&path, &settings)?;
+        }
+        if written != record {
+            save_record(&store, app, &written)?;
+        }
+        Ok(())
+    }
+
+    /// 把 live 配置中用户手写的 hooks / 权限规则 / 状态栏导入为统一条目
+    ///
+    /// 导入的条目记入写入记录，随后的同步会用统一条目替换它们，之后即可按应用启停。
+    pub fn import_from_live(state: &AppState) -> Result<AgentSettingsImport, AppError> {
+        let mut resources = Self::get(state)?;
+        let mut summary = AgentSettingsImport::default();
+        let store = LocalStore::open()?;
+
+        for app in HOOK_APPS {
+            if !crate::sync_policy::should_sync_live(&app) {
+                continue;
+            }
+            let Some(path) = settings_path(&app).filter(|path| path.exists()) else {
+                continue;
+            };
+            let mut live = read_json_file::<Value>(&path)?;
+            let mut record = load_record(&store, &app)?;
+            strip_recorded(&mut live, &record);
+
+            let adopted = Self::adopt_live(&mut resources, &app, &live, &mut summary);
+            if adopted.is_empty() {
+                continue;
+            }
+            record.hooks.extend(adopted.hooks.iter().cloned());
+            for ((_, rules), (_, adopted_rules)) in
+                record.rules_mut().into_iter().zip(adopted.rules())
+            {
+                rules.extend(adopted_rules.iter().cloned());
+            }
+            if adopted.status_line.is_some() {
+                record.status_line = adopted.status_line;
+            }
+            save_record(&store, &app, &record)?;
+        }
+
+        state.db.set_agent_settings_resources(&resources)?;
+        Self::sync(state)?;
+        Ok(summary)
+    }
+
+    /// 把 live 中（已去掉 cc-switch 写入部分）的条目并入 resources，返回被接管的原始条目
+    fn adopt_live(
+        resources: &mut AgentSettingsResources,
+        app: &AppType,
+        live: &Value,
+        summary: &mut AgentSettingsImport,
+    ) -> Projection {
+        let mut adopted = Projection::default();
+
+        if let Some(hooks) = live.get("hooks").and_then(Value::as_object) {
+            for (event, groups) in hooks {
+                let Some(claude_event) = claude_event(app, event) else {
+                    continue;
+                };
+                for group in groups.as_array().into_iter().flatten() {
+                    let Some(commands) = group.get("hooks").and_then(Value::as_array) else {
+                        continue;
+                    };
+                    let matcher = group
+                        .get("matcher")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                    match resources.hooks.iter_mut().find(|entry| {
+                        entry.event == claude_event
+                            && entry.matcher == matcher
+                            && entry.hooks == *commands
+                    }) {
+                        Some(entry) => entry.apps.set_enabled_for(app, true),
+                        None => {
+                            let name = match matcher.as_deref() {
+                                Some(matcher) if !matcher.is_empty() => {
+                                    format!("{claude_event} {matcher}")
+                                }
+                                _ => claude_event.clone(),
+                            };
+                            let taken: Vec<&str> = resources
+                                .hooks
+                                .iter()
+                                .map(|entry| entry.id.as_str())
+                                .collect();
+                            let id = generate_id(&taken, &name, "hook");
+                            resources.hooks.push(HookEntry {
+                                id,
+                                name,
+                                event: claude_event.clone(),
+                                matcher,
+                                hooks: commands.clone(),
+                                apps: single_app(app),
+                                description: None,
+                            });
+                        }
+                    }
+                    adopted.hooks.push((event.clone(), group.clone()));
+                    summary.hooks += 1;
+                }
+            }
+        }
+
+        if !supports_permissions(app) {
+            return adopted;
+        }
+
+        if let Some(permissions) = live.get("permissions").and_then(Value::as_object) {
+            for (key, rules) in adopted.rules_mut() {
+                rules.extend(
+                    permissions
+                        .get(key)
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string),
+                );
+            }
+            let count = adopted.allow.len() + adopted.deny.len() + adopted.ask.len();
+            if count > 0 {
+                let taken: Vec<&str> = resources
+                    .permissions
+                    .iter()
+                    .map(|set| set.id.as_str())
+                    .collect();
+                let name = format!("{} settings", app.as_str());
+                resources.permissions.push(PermissionRuleSet {
+                    id: generate_id(&taken, &name, "rules"),
+                    name,
+                    allow: adopted.allow.clone(),
+                    deny: adopted.deny.clone(),
+                    ask: adopted.ask.clone(),
+                    apps: single_app(app),
+                    description: None,
+                });
+                summary.permission_rules += count;
+            }
+        }
+
+        if resources.status_line.is_none() {
+            if let Some(status_line) = live.get("statusLine") {
+                if let Some(command) = status_line.get("command").and_then(Value::as_str) {
+                    resources.status_line = Some(StatusLineConfig {
+                        command: command.to_string(),
+                        padding: status_line
+                            .get("padding")
+                            .and_then(Value::as_u64)
+                            .and_then(|padding| u32::try_from(padding).ok()),
+                    });
+                    adopted.status_line = Some(status_line.clone());
+                    summary.status_line = true;
+                }
+            }
+        }
+
+        adopted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(id: &str, event: &str, matcher: Option<&str>, command: &str) -> HookEntry {
+        HookEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            event: event.to_string(),
+            matcher: matcher.map(str::to_string),
+            hooks: vec![json!({ "type": "command", "command": command })],
+            apps: McpApps {
+                claude: true,
+                gemini: true,
+                ..McpApps::default()
+            },
+            description: None,
+        }
+    }
+
+    fn resources() -> AgentSettingsResources {
+        AgentSettingsResources {
+            hooks: vec![
+                hook("fmt", "PostToolUse", Some("Edit"), "cargo fmt"),
+                hook("subagent", "SubagentStop", None, "notify-send done"),
+            ],
+            permissions: vec![PermissionRuleSet {
+                id: "git".to_string(),
+                name: "git".to_string(),
+                allow: vec!["Bash(git status)".to_string()],
+                deny: vec!["Bash(git push --force:*)".to_string()],
+                ask: Vec::new(),
+                apps: McpApps {
+                    claude: true,
+                    ..McpApps::default()
+                },
+                description: None,
+            }],
+            status_line: Some(StatusLineConfig {
+                command: "~/.claude/statusline.sh".to_string(),
+                padding: Some(0),
+            }),
+        }
+    }
+
+    #[test]
+    fn projection_keeps_hand_written_entries_and_strips_cleanly() {
+        let original = json!({
+            "env": { "ANTHROPIC_MODEL": "opus" },
+            "hooks": {
+                "PostToolUse": [{ "matcher": "Write", "hooks": [{ "type": "command", "command": "mine" }] }]
+            },
+            "permissions": { "allow": ["Read"] }
+        });
+        let mut live = original.clone();
+        let written = apply(&mut live, &render(&resources(), &AppType::Claude));
+
+        assert_eq!(live["hooks"]["PostToolUse"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            live["hooks"]["SubagentStop"][0]["hooks"][0]["command"],
+            "notify-send done"
+        );
+        assert_eq!(
+            live["permissions"]["allow"],
+            json!(["Read", "Bash(git status)"])
+        );
+        assert_eq!(
+            live["permissions"]["deny"],
+            json!(["Bash(git push --force:*)"])
+        );
+        assert_eq!(live["statusLine"]["command"], "~/.claude/statusline.sh");
+
+        strip_recorded(&mut live, &written);
+        assert_eq!(live, original);
+    }
+
+    #[test]
+    fn existing_identical_entries_are_not_recorded() {
+        let mut live = json!({ "permissions": { "allow": ["Bash(git status)"] } });
+        let written = apply(&mut live, &render(&resources(), &AppType::Claude));
+        assert!(written.allow.is_empty());
+
+        strip_recorded(&mut live, &written);
+        assert_eq!(live["permissions"]["allow"], json!(["Bash(git status)"]));
+    }
+
+    #[test]
+    fn gemini_gets_mapped_hook_events_only() {
+        let projection = render(&resources(), &AppType::Gemini);
+        let events: Vec<&str> = projection
+            .hooks
+            .iter()
+            .map(|(event, _)| event.as_str())
+            .collect();
+        assert_eq!(events, vec!["AfterTool"]);
+        assert!(projection.allow.is_empty() && projection.status_line.is_none());
+        assert_eq!(
+            claude_event(&AppType::Gemini, "AfterTool").as_deref(),
+            Some("PostToolUse")
+        );
+    }
+
+    #[test]
+    fn adopt_live_imports_and_merges_existing_entries() {
+        let mut resources = AgentSettingsResources {
+            hooks: vec![hook("fmt", "PostToolUse", Some("Edit"), "cargo fmt")],
+            ..AgentSettingsResources::default()
+        };
+        resources.hooks[0].apps = McpApps {
+            claude: true,
+            ..McpApps::default()
+        };
+        let live = json!({
+            "hooks": {
+                "AfterTool": [{ "matcher": "Edit", "hooks": [{ "type": "command", "command": "cargo fmt" }] }],
+                "BeforeModel": [{ "hooks": [{ "type": "command", "command": "skip" }] }]
+            }
+        });
+        let mut summary = AgentSettingsImport::default();
+        let adopted =
+            AgentSettingsService::adopt_live(&mut resources, &AppType::Gemini, &live, &mut summary);
+
+        assert_eq!(summary.hooks, 1);
+        assert_eq!(adopted.hooks.len(), 1);
+        assert_eq!(resources.hooks.len(), 1);
+        assert!(resources.hooks[0].apps.gemini);
+    }
+
+    #[test]
+    fn unsupported_apps_are_rejected() {
+        let mut entry = hook("fmt", "PostToolUse", None, "cargo fmt");
+        entry.apps.codex = true;
+        assert!(validate_hook(&entry).is_err());
+
+        entry.apps.codex = false;
+        entry.event = "AfterTool".to_string();
+        assert!(validate_hook(&entry).is_err());
+    }
+}
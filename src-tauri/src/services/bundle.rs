@@ -0,0 +1,1286 @@
+//! Portable bundles: a selection of providers, MCP servers, prompts and skill
+//! references in one JSON or YAML file that can be handed to a teammate.
+//!
+//! Unlike `config export`, a bundle never carries a plain credential. Every
+//! credential field (the ones [`crate::secret_ref`] recognises) is, depending
+//! on [`SecretMode`], emptied, replaced by an `env:` reference the importer
+//! provides through their environment, or sealed with a key wrapped by a
+//! shared passphrase. The encryption reuses the vault format: the bundle
+//! header is a [`VaultMeta`] and values are `vault:v1:` payloads, opened again
+//! on import before the regular services store (and possibly re-seal) them.
+//! References that hold no secret (`env:`, `file:`, `cmd:`) are kept as-is on
+//! export. On import only `env:` references are kept: a `file:` or `cmd:`
+//! reference from someone else's bundle would read a local file or run a
+//! command when the credential is resolved, so those are emptied (and listed
+//! by `bundle inspect`) unless the importer passes `--trust-references`.
+//!
+//! Import goes through the regular services (`ProviderService::add`,
+//! `McpService::upsert_server`, `PromptService::upsert_prompt`,
+//! `SkillService::install`) so live configs and edit history behave exactly
+//! like a manual add.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::{AppType, McpServer, SkillApps};
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::services::{McpService, PromptService, ProviderService, SkillService};
+use crate::store::AppState;
+use crate::vault::{self, VaultKey, VaultMeta};
+
+/// Value of the `format` field identifying a bundle file.
+pub(crate) const BUNDLE_FORMAT: &str = "cc-switch-bundle";
+pub(crate) const BUNDLE_VERSION: u32 = 1;
+/// Environment variable holding the bundle passphrase for non-interactive use.
+pub(crate) const PASSPHRASE_ENV: &str = "CC_SWITCH_BUNDLE_PASSPHRASE";
+
+/// What happens to credentials on export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum SecretMode {
+    /// Empty every credential; the importer fills them in.
+    Strip,
+    /// Replace credentials with `env:CC_SWITCH_...` references.
+    #[default]
+    Placeholder,
+    /// Encrypt credentials with a shared passphrase.
+    Encrypt,
+}
+
+impl SecretMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretMode::Strip => "strip",
+            SecretMode::Placeholder => "placeholder",
+            SecretMode::Encrypt => "encrypt",
+        }
+    }
+}
+
+/// What import does with an item whose id already exists locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ConflictPolicy {
+    /// Keep the local item.
+    #[default]
+    Skip,
+    /// Import under a new id (`<id>-2`, `<id>-3`, ...).
+    Rename,
+    /// Replace the local item.
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BundleFileFormat {
+    Json,
+    Yaml,
+}
+
+impl BundleFileFormat {
+    /// YAML for `.yaml` / `.yml` files, JSON otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("yaml" | "yml") => BundleFileFormat::Yaml,
+            _ => BundleFileFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Bundle {
+    pub format: String,
+    pub version: u32,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub secrets: SecretMode,
+    /// Passphrase-wrapped key of an encrypted bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<VaultMeta>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<BundleProvider>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<McpServer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<BundlePrompt>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<BundleSkill>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleProvider {
+    pub app: AppType,
+    pub provider: Provider,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundlePrompt {
+    pub app: AppType,
+    pub prompt: Prompt,
+}
+
+/// A skill by reference: the repository it is installed from, not its files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleSkill {
+    /// Install key, `owner/repo:directory`.
+    pub id: String,
+    pub name: String,
+    pub directory: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    pub apps: SkillApps,
+}
+
+/// Items to export; `all` takes everything.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BundleSelection {
+    pub all: bool,
+    pub providers: Vec<(AppType, String)>,
+    pub mcp_servers: Vec<String>,
+    pub prompts: Vec<(AppType, String)>,
+    pub skills: Vec<String>,
+}
+
+impl BundleSelection {
+    pub fn is_empty(&self) -> bool {
+        !self.all
+            && self.providers.is_empty()
+            && self.mcp_servers.is_empty()
+            && self.prompts.is_empty()
+            && self.skills.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum BundleItemKind {
+    Provider,
+    Mcp,
+    Prompt,
+    Skill,
+}
+
+impl BundleItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BundleItemKind::Provider => "provider",
+            BundleItemKind::Mcp => "mcp",
+            BundleItemKind::Prompt => "prompt",
+            BundleItemKind::Skill => "skill",
+        }
+    }
+}
+
+/// One item of a bundle, as shown by `bundle inspect`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleEntry {
+    pub kind: BundleItemKind,
+    pub app: Option<String>,
+    pub id: String,
+    pub name: String,
+    /// Credential fields carried by the item (empty, reference or sealed).
+    pub secret_fields: usize,
+    /// Whether an item with this id already exists locally.
+    pub exists: bool,
+}
+
+/// A credential reference carried by a bundle, as shown by `bundle inspect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleReference {
+    /// `app/id` or `mcp/id`.
+    pub item: String,
+    pub field: String,
+    pub reference: String,
+    /// Whether import keeps it without `--trust-references`: only the
+    /// placeholders export writes for this item and field are kept.
+    pub kept: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status", content = "detail")]
+pub(crate) enum ImportOutcome {
+    Added,
+    Renamed(String),
+    Overwritten,
+    Skipped,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportedItem {
+    pub kind: BundleItemKind,
+    pub app: Option<String>,
+    pub id: String,
+    pub outcome: ImportOutcome,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportReport {
+    pub items: Vec<ImportedItem>,
+    /// `env:` references the imported items expect to be set.
+    pub env_vars: Vec<String>,
+    /// Items imported with at least one empty credential.
+    pub missing_credentials: Vec<String>,
+    /// Foreign references emptied on import, as `item field`.
+    pub dropped_references: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Builds a bundle from the local setup. `passphrase` is required for
+/// [`SecretMode::Encrypt`]; sealed local credentials need the vault unlocked
+/// in that mode.
+pub(crate) fn export(
+    state: &AppState,
+    selection: &BundleSelection,
+    mode: SecretMode,
+    passphrase: Option<&str>,
+    description: Option<String>,
+) -> Result<Bundle, AppError> {
+    if selection.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Nothing selected: pass --all or at least one --provider/--mcp/--prompt/--skill"
+                .to_string(),
+        ));
+    }
+
+    let (encryption, key) = match mode {
+        SecretMode::Encrypt => {
+            let passphrase = passphrase.ok_or_else(|| {
+                AppError::InvalidInput("An encrypted bundle needs a passphrase".to_string())
+            })?;
+            let (meta, key) = VaultMeta::create(passphrase)?;
+            (Some(meta), Some(key))
+        }
+        _ => (None, None),
+    };
+    let protect = |prefix: &str, field: &str, value: &str| {
+        protect_secret(mode, key.as_ref(), prefix, field, value)
+    };
+
+    let mut providers = Vec::new();
+    for app in AppType::all() {
+        let wanted: Vec<&str> = selection
+            .providers
+            .iter()
+            .filter(|(selected_app, _)| *selected_app == app)
+            .map(|(_, id)| id.as_str())
+            .collect();
+        if !selection.all && wanted.is_empty() {
+            continue;
+        }
+        let stored = ProviderService::list(state, app.clone())?;
+        for id in &wanted {
+            if !stored.contains_key(*id) {
+                return Err(AppError::InvalidInput(format!(
+                    "Provider '{id}' not found for {}",
+                    app.as_str()
+                )));
+            }
+        }
+        for (id, provider) in stored {
+            if !selection.all && !wanted.contains(&id.as_str()) {
+                continue;
+            }
+            let mut provider = provider;
+            provider.in_failover_queue = false;
+            let prefix = format!("{}_{}", app.as_str(), provider.id);
+            map_secret_fields(&mut provider.settings_config, &mut |field, value| {
+                protect(&prefix, field, value)
+            })?;
+            if let Some(pool) = provider
+                .meta
+                .as_mut()
+                .and_then(|meta| meta.api_key_pool.as_mut())
+            {
+                for (index, pooled) in pool.keys.iter_mut().enumerate() {
+                    if let Some(value) = protect(&prefix, &pool_field(index), &pooled.key)? {
+                        pooled.key = value;
+                    }
+                }
+            }
+            providers.push(BundleProvider {
+                app: app.clone(),
+                provider,
+            });
+        }
+    }
+
+    let mut all_servers: Vec<McpServer> =
+        McpService::get_all_servers(state)?.into_values().collect();
+    all_servers.sort_by(|a, b| a.id.cmp(&b.id));
+    for id in &selection.mcp_servers {
+        if !all_servers.iter().any(|server| &server.id == id) {
+            return Err(AppError::InvalidInput(format!(
+                "MCP server '{id}' not found"
+            )));
+        }
+    }
+    let mut mcp_servers = Vec::new();
+    for mut server in all_servers {
+        if !selection.all && !selection.mcp_servers.contains(&server.id) {
+            continue;
+        }
+        let prefix = format!("mcp_{}", server.id);
+        map_secret_fields(&mut server.server, &mut |field, value| {
+            protect(&prefix, field, value)
+        })?;
+        mcp_servers.push(server);
+    }
+
+    let mut prompts = Vec::new();
+    for app in AppType::all() {
+        let stored = PromptService::get_prompts(state, app.clone())?;
+        for (selected_app, id) in &selection.prompts {
+            if *selected_app == app && !stored.contains_key(id) {
+                return Err(AppError::InvalidInput(format!(
+                    "Prompt '{id}' not found for {}",
+                    app.as_str()
+                )));
+            }
+        }
+        for (id, mut prompt) in stored {
+            let selected = selection
+                .prompts
+                .iter()
+                .any(|(selected_app, wanted)| *selected_app == app && *wanted == id);
+            if !selection.all && !selected {
+                continue;
+            }
+            prompt.enabled = false;
+            prompts.push(BundlePrompt {
+                app: app.clone(),
+                prompt,
+            });
+        }
+    }
+
+    let mut skills = Vec::new();
+    if selection.all || !selection.skills.is_empty() {
+        let installed = SkillService::list_installed()?;
+        for wanted in &selection.skills {
+            let found = installed
+                .iter()
+                .find(|skill| &skill.id == wanted || &skill.directory == wanted)
+                .ok_or_else(|| AppError::InvalidInput(format!("Skill '{wanted}' not installed")))?;
+            if found.repo_owner.is_none() || found.repo_name.is_none() {
+                return Err(AppError::InvalidInput(format!(
+                    "Skill '{wanted}' was not installed from a repository and cannot be referenced"
+                )));
+            }
+        }
+        for skill in installed {
+            let selected = selection
+                .skills
+                .iter()
+                .any(|wanted| *wanted == skill.id || *wanted == skill.directory);
+            if !selection.all && !selected {
+                continue;
+            }
+            // Local skills have no source to reinstall from; `--all` skips them.
+            let (Some(owner), Some(repo)) = (&skill.repo_owner, &skill.repo_name) else {
+                continue;
+            };
+            skills.push(BundleSkill {
+                id: format!("{owner}/{repo}:{}", skill.directory),
+                name: skill.name,
+                directory: skill.directory,
+                branch: skill.repo_branch,
+                apps: skill.apps,
+            });
+        }
+    }
+
+    Ok(Bundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description,
+        secrets: mode,
+        encryption,
+        providers,
+        mcp_servers,
+        prompts,
+        skills,
+    })
+}
+
+/// Export form of one credential, or `None` to keep it.
+fn protect_secret(
+    mode: SecretMode,
+    key: Option<&VaultKey>,
+    prefix: &str,
+    field: &str,
+    value: &str,
+) -> Result<Option<String>, AppError> {
+    if value.trim().is_empty() {
+        return Ok(None);
+    }
+    let sealed_locally = vault::is_sealed(value);
+    if crate::secret_ref::is_reference(value) && !sealed_locally {
+        return Ok(None);
+    }
+    match mode {
+        SecretMode::Strip => Ok(Some(String::new())),
+        SecretMode::Placeholder => Ok(Some(format!("env:{}", placeholder_var(prefix, field)))),
+        SecretMode::Encrypt => {
+            let key = key.expect("encrypted export always has a key");
+            let plain = if sealed_locally {
+                crate::secret_ref::resolve_value(value)?
+            } else {
+                value.to_string()
+            };
+            Ok(Some(key.seal(&plain)))
+        }
+    }
+}
+
+/// `CC_SWITCH_<PREFIX>_<FIELD>`, upper-cased with every other character
+/// turned into `_`.
+fn placeholder_var(prefix: &str, field: &str) -> String {
+    let raw = format!("CC_SWITCH_{prefix}_{field}");
+    let mut name = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+        let ch = if ch.is_ascii_alphanumeric() {
+            ch.to_ascii_uppercase()
+        } else {
+            '_'
+        };
+        if !(ch == '_' && name.ends_with('_')) {
+            name.push(ch);
+        }
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+/// Calls `f` with the name and value of every credential field of `value`
+/// and replaces the value when `f` returns `Some`.
+fn map_secret_fields<F>(value: &mut Value, f: &mut F) -> Result<(), AppError>
+where
+    F: FnMut(&str, &str) -> Result<Option<String>, AppError>,
+{
+    let Value::Object(map) = value else {
+        return Ok(());
+    };
+    for (name, child) in map.iter_mut() {
+        match child {
+            Value::String(raw) if crate::secret_ref::is_secret_field(name) => {
+                if let Some(replacement) = f(name, raw)? {
+                    *raw = replacement;
+                }
+            }
+            Value::Object(_) => map_secret_fields(child, f)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Files
+// ---------------------------------------------------------------------------
+
+pub(crate) fn render(bundle: &Bundle, format: BundleFileFormat) -> Result<String, AppError> {
+    match format {
+        BundleFileFormat::Json => serde_json::to_string_pretty(bundle)
+            .map_err(|source| AppError::JsonSerialize { source }),
+        BundleFileFormat::Yaml => serde_yaml::to_string(bundle)
+            .map_err(|e| AppError::Message(format!("Failed to write bundle as YAML: {e}"))),
+    }
+}
+
+/// Parses a JSON or YAML bundle and checks its header.
+pub(crate) fn parse(text: &str) -> Result<Bundle, AppError> {
+    let bundle: Bundle = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid bundle: {e}")))?
+    } else {
+        serde_yaml::from_str(text)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid bundle: {e}")))?
+    };
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "Not a cc-switch bundle (format '{}')",
+            bundle.format
+        )));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::InvalidInput(format!(
+            "Bundle version {} is newer than this cc-switch supports ({BUNDLE_VERSION}); upgrade cc-switch",
+            bundle.version
+        )));
+    }
+    if bundle.secrets == SecretMode::Encrypt && bundle.encryption.is_none() {
+        return Err(AppError::InvalidInput(
+            "Encrypted bundle has no encryption header".to_string(),
+        ));
+    }
+    Ok(bundle)
+}
+
+// ---------------------------------------------------------------------------
+// Inspect
+// ---------------------------------------------------------------------------
+
+/// Items of `bundle`, marked when they already exist in `state`.
+pub(crate) fn entries(state: &AppState, bundle: &Bundle) -> Result<Vec<BundleEntry>, AppError> {
+    let mut entries = Vec::new();
+    for item in &bundle.providers {
+        let mut settings = item.provider.settings_config.clone();
+        entries.push(BundleEntry {
+            kind: BundleItemKind::Provider,
+            app: Some(item.app.as_str().to_string()),
+            id: item.provider.id.clone(),
+            name: item.provider.name.clone(),
+            secret_fields: count_secret_fields(&mut settings),
+            exists: provider_exists(state, &item.app, &item.provider.id)?,
+        });
+    }
+    let servers = McpService::get_all_servers(state)?;
+    for server in &bundle.mcp_servers {
+        let mut config = server.server.clone();
+        entries.push(BundleEntry {
+            kind: BundleItemKind::Mcp,
+            app: None,
+            id: server.id.clone(),
+            name: server.name.clone(),
+            secret_fields: count_secret_fields(&mut config),
+            exists: servers.contains_key(&server.id),
+        });
+    }
+    for item in &bundle.prompts {
+        entries.push(BundleEntry {
+            kind: BundleItemKind::Prompt,
+            app: Some(item.app.as_str().to_string()),
+            id: item.prompt.id.clone(),
+            name: item.prompt.name.clone(),
+            secret_fields: 0,
+            exists: PromptService::get_prompts(state, item.app.clone())?
+                .contains_key(&item.prompt.id),
+        });
+    }
+    if !bundle.skills.is_empty() {
+        let installed = SkillService::list_installed()?;
+        for skill in &bundle.skills {
+            entries.push(BundleEntry {
+                kind: BundleItemKind::Skill,
+                app: None,
+                id: skill.id.clone(),
+                name: skill.name.clone(),
+                secret_fields: 0,
+                exists: installed
+                    .iter()
+                    .any(|local| local.directory == skill.directory),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// `env:` variables referenced by the credentials of `bundle`, sorted.
+pub(crate) fn required_env_vars(bundle: &Bundle) -> Vec<String> {
+    let mut vars = BTreeSet::new();
+    let mut collect = |value: &str| {
+        if let Some(name) = value.trim().strip_prefix("env:") {
+            vars.insert(name.trim().to_string());
+        }
+    };
+    for item in &bundle.providers {
+        let mut settings = item.provider.settings_config.clone();
+        let _ = map_secret_fields(&mut settings, &mut |_, value| {
+            collect(value);
+            Ok(None)
+        });
+        if let Some(pool) = item
+            .provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.api_key_pool.as_ref())
+        {
+            for pooled in &pool.keys {
+                collect(&pooled.key);
+            }
+        }
+    }
+    for server in &bundle.mcp_servers {
+        let mut config = server.server.clone();
+        let _ = map_secret_fields(&mut config, &mut |_, value| {
+            collect(value);
+            Ok(None)
+        });
+    }
+    vars.into_iter().collect()
+}
+
+/// Every credential reference of `bundle`. Values sealed with the bundle
+/// passphrase are only checked on import, once they are opened.
+pub(crate) fn references(bundle: &Bundle) -> Vec<BundleReference> {
+    let mut found = Vec::new();
+    let mut push = |item: &str, prefix: &str, field: &str, value: &str| {
+        if crate::secret_ref::is_reference(value) && !vault::is_sealed(value) {
+            found.push(BundleReference {
+                item: item.to_string(),
+                field: field.to_string(),
+                reference: value.trim().to_string(),
+                kept: !is_foreign_reference(prefix, field, value),
+            });
+        }
+    };
+    for item in &bundle.providers {
+        let owner = format!("{}/{}", item.app.as_str(), item.provider.id);
+        let prefix = format!("{}_{}", item.app.as_str(), item.provider.id);
+        let mut settings = item.provider.settings_config.clone();
+        let _ = map_secret_fields(&mut settings, &mut |field, value| {
+            push(&owner, &prefix, field, value);
+            Ok(None)
+        });
+        if let Some(pool) = item
+            .provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.api_key_pool.as_ref())
+        {
+            for (index, pooled) in pool.keys.iter().enumerate() {
+                push(&owner, &prefix, &pool_field(index), &pooled.key);
+            }
+        }
+    }
+    for server in &bundle.mcp_servers {
+        let owner = format!("mcp/{}", server.id);
+        let prefix = format!("mcp_{}", server.id);
+        let mut config = server.server.clone();
+        let _ = map_secret_fields(&mut config, &mut |field, value| {
+            push(&owner, &prefix, field, value);
+            Ok(None)
+        });
+    }
+    found
+}
+
+/// Whether `value` is a reference the exporter did not write for this field.
+/// Resolving it would read an environment variable, a file, a command's
+/// output or the local vault chosen by whoever wrote the bundle, and send the
+/// result to the bundle's base URL.
+fn is_foreign_reference(prefix: &str, field: &str, value: &str) -> bool {
+    crate::secret_ref::is_reference(value)
+        && value.trim() != format!("env:{}", placeholder_var(prefix, field))
+}
+
+fn pool_field(index: usize) -> String {
+    format!("pool_{}", index + 1)
+}
+
+fn count_secret_fields(value: &mut Value) -> usize {
+    let mut count = 0;
+    let _ = map_secret_fields(value, &mut |_, _| {
+        count += 1;
+        Ok(None)
+    });
+    count
+}
+
+fn provider_exists(state: &AppState, app: &AppType, id: &str) -> Result<bool, AppError> {
+    Ok(ProviderService::list(state, app.clone())?.contains_key(id))
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// The bundle key of an encrypted bundle.
+pub(crate) fn unlock(bundle: &Bundle, passphrase: &str) -> Result<VaultKey, AppError> {
+    let meta = bundle
+        .encryption
+        .as_ref()
+        .ok_or_else(|| AppError::InvalidInput("This bundle is not encrypted".to_string()))?;
+    meta.unlock(passphrase)
+        .map_err(|_| AppError::InvalidInput("Wrong bundle passphrase".to_string()))
+}
+
+/// Imports every item of `bundle`. Failures of single items are reported, not
+/// returned, so one bad provider does not stop the rest. `key` is required
+/// for encrypted bundles (see [`unlock`]). References other than the
+/// placeholders export writes are emptied unless `trust_references` is set.
+pub(crate) async fn import(
+    state: &AppState,
+    bundle: Bundle,
+    policy: ConflictPolicy,
+    key: Option<&VaultKey>,
+    trust_references: bool,
+) -> Result<ImportReport, AppError> {
+    if bundle.secrets == SecretMode::Encrypt && key.is_none() {
+        return Err(AppError::InvalidInput(
+            "This bundle is encrypted: a passphrase is required".to_string(),
+        ));
+    }
+    let mut report = ImportReport {
+        env_vars: required_env_vars(&bundle),
+        ..Default::default()
+    };
+
+    for item in bundle.providers {
+        let app = item.app.clone();
+        let id = item.provider.id.clone();
+        let outcome = import_provider(state, item, policy, key, trust_references, &mut report)
+            .unwrap_or_else(|err| ImportOutcome::Failed(err.to_string()));
+        report.items.push(ImportedItem {
+            kind: BundleItemKind::Provider,
+            app: Some(app.as_str().to_string()),
+            id,
+            outcome,
+        });
+    }
+
+    for server in bundle.mcp_servers {
+        let id = server.id.clone();
+        let outcome = import_mcp(state, server, policy, key, trust_references, &mut report)
+            .unwrap_or_else(|err| ImportOutcome::Failed(err.to_string()));
+        report.items.push(ImportedItem {
+            kind: BundleItemKind::Mcp,
+            app: None,
+            id,
+            outcome,
+        });
+    }
+
+    for item in bundle.prompts {
+        let app = item.app.clone();
+        let id = item.prompt.id.clone();
+        let outcome = import_prompt(state, item, policy)
+            .unwrap_or_else(|err| ImportOutcome::Failed(err.to_string()));
+        report.items.push(ImportedItem {
+            kind: BundleItemKind::Prompt,
+            app: Some(app.as_str().to_string()),
+            id,
+            outcome,
+        });
+    }
+
+    for skill in bundle.skills {
+        let id = skill.id.clone();
+        let outcome = import_skill(skill, policy)
+            .await
+            .unwrap_or_else(|err| ImportOutcome::Failed(err.to_string()));
+        report.items.push(ImportedItem {
+            kind: BundleItemKind::Skill,
+            app: None,
+            id,
+            outcome,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Opens bundle-sealed credentials and notes whether any stays empty.
+fn restore_secrets(
+    value: &mut Value,
+    key: Option<&VaultKey>,
+    empty: &mut bool,
+) -> Result<(), AppError> {
+    map_secret_fields(value, &mut |_, raw| {
+        if raw.trim().is_empty() {
+            *empty = true;
+            return Ok(None);
+        }
+        open_secret(key, raw)
+    })
+}
+
+/// Empties the foreign references of `value` (see [`is_foreign_reference`]),
+/// noting each one in `dropped` and marking the item as missing a credential.
+fn drop_foreign_references(
+    value: &mut Value,
+    prefix: &str,
+    owner: &str,
+    dropped: &mut Vec<String>,
+    empty: &mut bool,
+) -> Result<(), AppError> {
+    map_secret_fields(value, &mut |field, raw| {
+        if !is_foreign_reference(prefix, field, raw) {
+            return Ok(None);
+        }
+        dropped.push(format!("{owner} {field}"));
+        *empty = true;
+        Ok(Some(String::new()))
+    })
+}
+
+fn open_secret(key: Option<&VaultKey>, raw: &str) -> Result<Option<String>, AppError> {
+    match (key, raw.trim().strip_prefix(vault::SEALED_PREFIX)) {
+        (Some(key), Some(payload)) => key.open(payload).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// First `<id>-N` (N ≥ 2) not taken according to `taken`.
+fn free_id(id: &str, taken: impl Fn(&str) -> bool) -> (String, usize) {
+    let mut n = 2;
+    loop {
+        let candidate = format!("{id}-{n}");
+        if !taken(&candidate) {
+            return (candidate, n);
+        }
+        n += 1;
+    }
+}
+
+fn import_provider(
+    state: &AppState,
+    item: BundleProvider,
+    policy: ConflictPolicy,
+    key: Option<&VaultKey>,
+    trust_references: bool,
+    report: &mut ImportReport,
+) -> Result<ImportOutcome, AppError> {
+    let BundleProvider { app, mut provider } = item;
+    let stored = ProviderService::list(state, app.clone())?;
+    let owner = format!("{}/{}", app.as_str(), provider.id);
+    let prefix = format!("{}_{}", app.as_str(), provider.id);
+
+    let mut empty = false;
+    restore_secrets(&mut provider.settings_config, key, &mut empty)?;
+    if !trust_references {
+        drop_foreign_references(
+            &mut provider.settings_config,
+            &prefix,
+            &owner,
+            &mut report.dropped_references,
+            &mut empty,
+        )?;
+    }
+    if let Some(pool) = provider
+        .meta
+        .as_mut()
+        .and_then(|meta| meta.api_key_pool.as_mut())
+    {
+        for pooled in &mut pool.keys {
+            if let Some(value) = open_secret(key, &pooled.key)? {
+                pooled.key = value;
+            }
+        }
+        if !trust_references {
+            let before = pool.keys.len();
+            let mut index = 0;
+            pool.keys.retain(|pooled| {
+                let field = pool_field(index);
+                index += 1;
+                !is_foreign_reference(&prefix, &field, &pooled.key)
+            });
+            if pool.keys.len() != before {
+                report
+                    .dropped_references
+                    .push(format!("{owner} apiKeyPool"));
+            }
+        }
+    }
+    provider.in_failover_queue = false;
+
+    let outcome = match (stored.get(&provider.id), policy) {
+        (None, _) => {
+            ProviderService::add(state, app.clone(), provider.clone())?;
+            ImportOutcome::Added
+        }
+        (Some(_), ConflictPolicy::Skip) => return Ok(ImportOutcome::Skipped),
+        (Some(existing), ConflictPolicy::Overwrite) => {
+            provider.in_failover_queue = existing.in_failover_queue;
+            ProviderService::update(state, app.clone(), provider.clone())?;
+            ImportOutcome::Overwritten
+        }
+        (Some(_), ConflictPolicy::Rename) => {
+            let (new_id, n) = free_id(&provider.id, |candidate| stored.contains_key(candidate));
+            provider.id = new_id.clone();
+            provider.name = format!("{} ({n})", provider.name);
+            ProviderService::add(state, app.clone(), provider.clone())?;
+            ImportOutcome::Renamed(new_id)
+        }
+    };
+    if empty {
+        report
+            .missing_credentials
+            .push(format!("{}/{}", app.as_str(), provider.id));
+    }
+    Ok(outcome)
+}
+
+fn import_mcp(
+    state: &AppState,
+    mut server: McpServer,
+    policy: ConflictPolicy,
+    key: Option<&VaultKey>,
+    trust_references: bool,
+    report: &mut ImportReport,
+) -> Result<ImportOutcome, AppError> {
+    let servers = McpService::get_all_servers(state)?;
+    let mut empty = false;
+    restore_secrets(&mut server.server, key, &mut empty)?;
+    if !trust_references {
+        drop_foreign_references(
+            &mut server.server,
+            &format!("mcp_{}", server.id),
+            &format!("mcp/{}", server.id),
+            &mut report.dropped_references,
+            &mut empty,
+        )?;
+    }
+
+    let outcome = match (servers.contains_key(&server.id), policy) {
+        (false, _) => ImportOutcome::Added,
+        (true, ConflictPolicy::Skip) => return Ok(ImportOutcome::Skipped),
+        (true, ConflictPolicy::Overwrite) => ImportOutcome::Overwritten,
+        (true, ConflictPolicy::Rename) => {
+            let (new_id, n) = free_id(&server.id, |candidate| servers.contains_key(candidate));
+            server.id = new_id.clone();
+            server.name = format!("{} ({n})", server.name);
+            ImportOutcome::Renamed(new_id)
+        }
+    };
+    let id = server.id.clone();
+    McpService::upsert_server(state, server)?;
+    if empty {
+        report.missing_credentials.push(format!("mcp/{id}"));
+    }
+    Ok(outcome)
+}
+
+fn import_prompt(
+    state: &AppState,
+    item: BundlePrompt,
+    policy: ConflictPolicy,
+) -> Result<ImportOutcome, AppError> {
+    let BundlePrompt { app, mut prompt } = item;
+    let stored = PromptService::get_prompts(state, app.clone())?;
+    // Imported prompts never replace the active one.
+    prompt.enabled = false;
+
+    let outcome = match (stored.get(&prompt.id), policy) {
+        (None, _) => ImportOutcome::Added,
+        (Some(_), ConflictPolicy::Skip) => return Ok(ImportOutcome::Skipped),
+        (Some(existing), ConflictPolicy::Overwrite) => {
+            prompt.enabled = existing.enabled;
+            ImportOutcome::Overwritten
+        }
+        (Some(_), ConflictPolicy::Rename) => {
+            let (new_id, n) = free_id(&prompt.id, |candidate| stored.contains_key(candidate));
+            prompt.id = new_id.clone();
+            prompt.name = format!("{} ({n})", prompt.name);
+            ImportOutcome::Renamed(new_id)
+        }
+    };
+    PromptService::validate_prompt_id(&prompt.id)?;
+    let id = prompt.id.clone();
+    PromptService::upsert_prompt(state, app, &id, prompt)?;
+    Ok(outcome)
+}
+
+/// Installs a referenced skill for its apps. A skill already installed under
+/// the same directory is kept (`skip`, `rename`) or gets the bundle's app
+/// matrix (`overwrite`); its files are never replaced.
+async fn import_skill(
+    skill: BundleSkill,
+    policy: ConflictPolicy,
+) -> Result<ImportOutcome, AppError> {
+    let installed = SkillService::list_installed()?;
+    if installed
+        .iter()
+        .any(|local| local.directory == skill.directory)
+    {
+        if policy != ConflictPolicy::Overwrite {
+            return Ok(ImportOutcome::Skipped);
+        }
+        SkillService::set_apps(&skill.directory, skill.apps)?;
+        return Ok(ImportOutcome::Overwritten);
+    }
+
+    let apps: Vec<AppType> = SkillService::supported_skill_apps()
+        .filter(|app| skill.apps.is_enabled_for(app))
+        .collect();
+    let first = apps.first().cloned().unwrap_or(AppType::Claude);
+    let service = SkillService::new()?;
+    service.install(&skill.id, &first).await?;
+    SkillService::set_apps(&skill.directory, skill.apps)?;
+    Ok(ImportOutcome::Added)
+}
+
+/// Parses a `[app:]id` selector; ids without an app belong to `default_app`.
+pub(crate) fn parse_app_selector(
+    selector: &str,
+    default_app: &AppType,
+) -> Result<(AppType, String), AppError> {
+    match selector.split_once(':') {
+        Some((app, id)) if AppType::from_str(app).is_ok() && !id.trim().is_empty() => {
+            Ok((AppType::from_str(app)?, id.trim().to_string()))
+        }
+        _ if selector.trim().is_empty() => {
+            Err(AppError::InvalidInput("Empty selector".to_string()))
+        }
+        _ => Ok((default_app.clone(), selector.trim().to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestEnvGuard;
+    use serde_json::json;
+    use serial_test::serial;
+
+    fn sample_bundle(settings: Value, mode: SecretMode) -> Bundle {
+        Bundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            description: None,
+            secrets: mode,
+            encryption: None,
+            providers: vec![BundleProvider {
+                app: AppType::Claude,
+                provider: Provider::with_id(
+                    "relay".to_string(),
+                    "Relay".to_string(),
+                    settings,
+                    None,
+                ),
+            }],
+            mcp_servers: Vec::new(),
+            prompts: Vec::new(),
+            skills: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn placeholders_become_env_references_and_keep_other_references() {
+        let mut settings = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-plain",
+                "ANTHROPIC_API_KEY": "env:TEAM_KEY",
+                "ANTHROPIC_BASE_URL": "https://relay.example"
+            }
+        });
+        map_secret_fields(&mut settings, &mut |field, value| {
+            protect_secret(SecretMode::Placeholder, None, "claude_relay", field, value)
+        })
+        .unwrap();
+
+        assert_eq!(
+            settings["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "env:CC_SWITCH_CLAUDE_RELAY_ANTHROPIC_AUTH_TOKEN"
+        );
+        assert_eq!(settings["env"]["ANTHROPIC_API_KEY"], "env:TEAM_KEY");
+        assert_eq!(
+            settings["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example"
+        );
+
+        let bundle = sample_bundle(settings, SecretMode::Placeholder);
+        assert_eq!(
+            required_env_vars(&bundle),
+            vec![
+                "CC_SWITCH_CLAUDE_RELAY_ANTHROPIC_AUTH_TOKEN".to_string(),
+                "TEAM_KEY".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn encrypted_secrets_round_trip_with_the_passphrase_only() {
+        let (meta, key) = VaultMeta::create("team secret").unwrap();
+        let mut settings = json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-plain" } });
+        map_secret_fields(&mut settings, &mut |field, value| {
+            protect_secret(
+                SecretMode::Encrypt,
+                Some(&key),
+                "claude_relay",
+                field,
+                value,
+            )
+        })
+        .unwrap();
+        let sealed = settings["env"]["ANTHROPIC_AUTH_TOKEN"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(vault::is_sealed(&sealed));
+        assert!(!sealed.contains("sk-plain"));
+
+        let mut bundle = sample_bundle(settings, SecretMode::Encrypt);
+        bundle.encryption = Some(meta);
+        let yaml = render(&bundle, BundleFileFormat::Yaml).unwrap();
+        let parsed = parse(&yaml).unwrap();
+
+        assert!(unlock(&parsed, "wrong").is_err());
+        let key = unlock(&parsed, "team secret").unwrap();
+        let mut restored = parsed.providers[0].provider.settings_config.clone();
+        let mut empty = false;
+        restore_secrets(&mut restored, Some(&key), &mut empty).unwrap();
+        assert_eq!(restored["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-plain");
+        assert!(!empty);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn export_then_import_renames_conflicting_providers() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+        ProviderService::add(
+            &state,
+            AppType::Claude,
+            Provider::with_id(
+                "relay".to_string(),
+                "Relay".to_string(),
+                json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-relay" } }),
+                None,
+            ),
+        )
+        .expect("add relay");
+
+        let selection = BundleSelection {
+            providers: vec![(AppType::Claude, "relay".to_string())],
+            ..Default::default()
+        };
+        let exported = export(&state, &selection, SecretMode::Strip, None, None).expect("export");
+        let text = render(&exported, BundleFileFormat::Json).expect("render");
+        assert!(!text.contains("sk-relay"));
+
+        let report = import(
+            &state,
+            parse(&text).unwrap(),
+            ConflictPolicy::Rename,
+            None,
+            false,
+        )
+        .await
+        .expect("import");
+        assert_eq!(
+            report.items[0].outcome,
+            ImportOutcome::Renamed("relay-2".to_string())
+        );
+        assert_eq!(
+            report.missing_credentials,
+            vec!["claude/relay-2".to_string()]
+        );
+        let providers = ProviderService::list(&state, AppType::Claude).unwrap();
+        assert_eq!(providers["relay-2"].name, "Relay (2)");
+        assert_eq!(
+            providers["relay"].settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-relay"
+        );
+
+        let report = import(
+            &state,
+            parse(&text).unwrap(),
+            ConflictPolicy::Skip,
+            None,
+            false,
+        )
+        .await
+        .expect("import");
+        assert_eq!(report.items[0].outcome, ImportOutcome::Skipped);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn foreign_references_are_dropped_unless_trusted() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+        let key_file = home.path().join("key.txt");
+        std::fs::write(&key_file, "sk-from-file").expect("write key");
+        let file_ref = format!("file:{}", key_file.display());
+        let bundle = sample_bundle(
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "cmd:printf sk-from-cmd",
+                    "ANTHROPIC_API_KEY": file_ref,
+                    "AWS_SECRET_ACCESS_KEY": "env:CC_SWITCH_TEST_FOREIGN_SECRET",
+                    "OPENROUTER_API_KEY": "env:CC_SWITCH_CLAUDE_RELAY_OPENROUTER_API_KEY",
+                    "ANTHROPIC_BASE_URL": "https://relay.example"
+                }
+            }),
+            SecretMode::Placeholder,
+        );
+        // The placeholder is kept and resolved when the provider goes live.
+        std::env::set_var(
+            "CC_SWITCH_CLAUDE_RELAY_OPENROUTER_API_KEY",
+            "sk-placeholder",
+        );
+        std::env::set_var("CC_SWITCH_TEST_FOREIGN_SECRET", "foreign-secret");
+        let kept: Vec<(String, bool)> = references(&bundle)
+            .into_iter()
+            .map(|reference| (reference.field, reference.kept))
+            .collect();
+        assert_eq!(kept.len(), 4);
+        assert!(kept.contains(&("AWS_SECRET_ACCESS_KEY".to_string(), false)));
+        assert!(kept.contains(&("OPENROUTER_API_KEY".to_string(), true)));
+
+        let report = import(&state, bundle.clone(), ConflictPolicy::Skip, None, false)
+            .await
+            .expect("import");
+        assert_eq!(report.dropped_references.len(), 3);
+        assert_eq!(report.missing_credentials, vec!["claude/relay".to_string()]);
+        let env = &ProviderService::list(&state, AppType::Claude).unwrap()["relay"].settings_config
+            ["env"];
+        assert_eq!(env["ANTHROPIC_AUTH_TOKEN"], "");
+        assert_eq!(env["ANTHROPIC_API_KEY"], "");
+        assert_eq!(env["AWS_SECRET_ACCESS_KEY"], "");
+        assert_eq!(
+            env["OPENROUTER_API_KEY"],
+            "env:CC_SWITCH_CLAUDE_RELAY_OPENROUTER_API_KEY"
+        );
+        assert_eq!(env["ANTHROPIC_BASE_URL"], "https://relay.example");
+
+        let report = import(&state, bundle, ConflictPolicy::Overwrite, None, true)
+            .await
+            .expect("import");
+        assert!(report.dropped_references.is_empty());
+        let env = &ProviderService::list(&state, AppType::Claude).unwrap()["relay"].settings_config
+            ["env"];
+        assert_eq!(env["ANTHROPIC_API_KEY"], file_ref.as_str());
+        std::env::remove_var("CC_SWITCH_CLAUDE_RELAY_OPENROUTER_API_KEY");
+        std::env::remove_var("CC_SWITCH_TEST_FOREIGN_SECRET");
+    }
+
+    #[test]
+    fn parse_rejects_foreign_files_and_selectors_default_to_the_app() {
+        assert!(parse(r#"{"format":"other","version":1}"#).is_err());
+        let json = render(
+            &sample_bundle(json!({}), SecretMode::Strip),
+            BundleFileFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(parse(&json).unwrap().providers.len(), 1);
+
+        assert_eq!(
+            parse_app_selector("codex:relay", &AppType::Claude).unwrap(),
+            (AppType::Codex, "relay".to_string())
+        );
+        assert_eq!(
+            parse_app_selector("relay", &AppType::Gemini).unwrap(),
+            (AppType::Gemini, "relay".to_string())
+        );
+        assert_eq!(free_id("relay", |id| id == "relay-2").0, "relay-3");
+    }
+}
@@ -0,0 +1,467 @@
+//! Prompt-cache efficiency analytics.
+//!
+//! Every usage row carries `cache_read_tokens` / `cache_creation_tokens`, so
+//! the report is a pure read over `proxy_request_logs` plus
+//! `usage_daily_rollups`. Savings are priced at list price from
+//! `model_pricing`: a cache read saves `input - cache_read` per token compared
+//! with sending the same prefix uncached, while a cache write costs
+//! `cache_creation - input` extra. Provider cost multipliers are ignored so
+//! relays remain comparable with each other.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rusqlite::Connection;
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::proxy::usage::calculator::ModelPricing;
+use crate::services::sql_helpers::fresh_input_sql;
+use crate::services::usage_stats::{
+    effective_usage_log_filter, find_model_pricing, provider_name_coalesce,
+};
+
+/// Below this many requests a provider's hit ratio is too noisy to warn about.
+const WARN_MIN_REQUESTS: u64 = 20;
+/// Caching only kicks in above ~1-4k prompt tokens depending on the vendor;
+/// short prompts legitimately never hit.
+const WARN_MIN_AVG_PROMPT_TOKENS: u64 = 4096;
+/// Hit ratios under this are treated as "caching is not working".
+const WARN_MAX_HIT_RATIO: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheGroupBy {
+    Provider,
+    Model,
+    Day,
+}
+
+/// Inclusive local-date filter (`YYYY-MM-DD`).
+#[derive(Debug, Clone, Default)]
+pub struct CacheAnalyticsFilter {
+    pub app_type: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub request_count: u64,
+    /// Input tokens billed at the normal (uncached) rate.
+    pub fresh_input_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// `cache_read / (fresh + cache_read + cache_creation)`.
+    pub hit_ratio: f64,
+    /// What the cache reads would have cost as plain input, minus what they cost.
+    pub saved_usd: f64,
+    /// Premium paid for cache writes over plain input.
+    pub write_overhead_usd: f64,
+    /// `saved_usd - write_overhead_usd`.
+    pub net_saved_usd: f64,
+    /// Requests whose model has no pricing entry (excluded from dollar figures).
+    pub unpriced_requests: u64,
+}
+
+impl CacheStats {
+    fn absorb(&mut self, other: &CacheStats) {
+        self.request_count += other.request_count;
+        self.fresh_input_tokens += other.fresh_input_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_creation_tokens += other.cache_creation_tokens;
+        self.saved_usd += other.saved_usd;
+        self.write_overhead_usd += other.write_overhead_usd;
+        self.unpriced_requests += other.unpriced_requests;
+    }
+
+    fn finish(&mut self) {
+        let prompt = self.prompt_tokens();
+        self.hit_ratio = if prompt > 0 {
+            self.cache_read_tokens as f64 / prompt as f64
+        } else {
+            0.0
+        };
+        self.net_saved_usd = self.saved_usd - self.write_overhead_usd;
+    }
+
+    pub fn prompt_tokens(&self) -> u64 {
+        self.fresh_input_tokens + self.cache_read_tokens + self.cache_creation_tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStatsRow {
+    /// Provider id, model name or date depending on the grouping.
+    pub key: String,
+    /// Human label (provider name for provider grouping).
+    pub label: String,
+    pub app_type: Option<String>,
+    #[serde(flatten)]
+    pub stats: CacheStats,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheWarningKind {
+    /// Cache writes are billed but never read back.
+    WritesWithoutReads,
+    /// Long prompts, yet almost nothing is served from cache.
+    NoReads,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheWarning {
+    pub kind: CacheWarningKind,
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub request_count: u64,
+    pub hit_ratio: f64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheAnalyticsReport {
+    pub group_by: CacheGroupBy,
+    pub totals: CacheStats,
+    pub rows: Vec<CacheStatsRow>,
+    pub warnings: Vec<CacheWarning>,
+}
+
+/// One aggregated (day, app, provider, model) bucket straight from SQL.
+#[derive(Debug, Clone)]
+struct CacheBucket {
+    date: String,
+    app_type: String,
+    provider_id: String,
+    provider_name: String,
+    model: String,
+    pricing_model: String,
+    request_count: u64,
+    fresh_input_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+}
+
+impl Database {
+    /// Prompt-cache efficiency grouped by provider, model or day.
+    pub fn get_cache_analytics(
+        &self,
+        filter: &CacheAnalyticsFilter,
+        group_by: CacheGroupBy,
+    ) -> Result<CacheAnalyticsReport, AppError> {
+        let conn = lock_conn!(self.conn);
+        let buckets = load_cache_buckets(&conn, filter)?;
+
+        let mut pricing_cache: HashMap<String, Option<ModelPricing>> = HashMap::new();
+        let mut grouped: BTreeMap<(String, Option<String>), (String, CacheStats)> = BTreeMap::new();
+        let mut per_provider: BTreeMap<(String, String), (String, CacheStats)> = BTreeMap::new();
+        let mut totals = CacheStats::default();
+
+        for bucket in &buckets {
+            let pricing = pricing_cache
+                .entry(bucket.pricing_model.clone())
+                .or_insert_with(|| find_model_pricing(&conn, &bucket.pricing_model));
+            let stats = bucket_stats(bucket, pricing.as_ref());
+
+            let (key, app, label) = match group_by {
+                CacheGroupBy::Provider => (
+                    bucket.provider_id.clone(),
+                    Some(bucket.app_type.clone()),
+                    bucket.provider_name.clone(),
+                ),
+                CacheGroupBy::Model => (bucket.model.clone(), None, bucket.model.clone()),
+                CacheGroupBy::Day => (bucket.date.clone(), None, bucket.date.clone()),
+            };
+            grouped
+                .entry((key, app))
+                .or_insert_with(|| (label, CacheStats::default()))
+                .1
+                .absorb(&stats);
+            per_provider
+                .entry((bucket.app_type.clone(), bucket.provider_id.clone()))
+                .or_insert_with(|| (bucket.provider_name.clone(), CacheStats::default()))
+                .1
+                .absorb(&stats);
+            totals.absorb(&stats);
+        }
+        totals.finish();
+
+        let mut rows: Vec<CacheStatsRow> = grouped
+            .into_iter()
+            .map(|((key, app_type), (label, mut stats))| {
+                stats.finish();
+                CacheStatsRow {
+                    key,
+                    label,
+                    app_type,
+                    stats,
+                }
+            })
+            .collect();
+        match group_by {
+            CacheGroupBy::Day => rows.reverse(),
+            CacheGroupBy::Provider | CacheGroupBy::Model => rows.sort_by(|a, b| {
+                b.stats
+                    .prompt_tokens()
+                    .cmp(&a.stats.prompt_tokens())
+                    .then_with(|| a.key.cmp(&b.key))
+            }),
+        }
+
+        let warnings = per_provider
+            .into_iter()
+            .filter_map(|((app_type, provider_id), (name, mut stats))| {
+                stats.finish();
+                cache_warning(&app_type, &provider_id, &name, &stats)
+            })
+            .collect();
+
+        Ok(CacheAnalyticsReport {
+            group_by,
+            totals,
+            rows,
+            warnings,
+        })
+    }
+}
+
+fn load_cache_buckets(
+    conn: &Connection,
+    filter: &CacheAnalyticsFilter,
+) -> Result<Vec<CacheBucket>, AppError> {
+    let detail_date = "date(l.created_at, 'unixepoch', 'localtime')";
+    let mut detail_conditions = vec![effective_usage_log_filter("l")];
+    let mut detail_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    let mut rollup_conditions = Vec::new();
+    let mut rollup_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    if let Some(app) = &filter.app_type {
+        detail_conditions.push("l.app_type = ?".to_string());
+        detail_params.push(Box::new(app.clone()));
+        rollup_conditions.push("r.app_type = ?".to_string());
+        rollup_params.push(Box::new(app.clone()));
+    }
+    if let Some(start) = &filter.start {
+        detail_conditions.push(format!("{detail_date} >= ?"));
+        detail_params.push(Box::new(start.clone()));
+        rollup_conditions.push("r.date >= ?".to_string());
+        rollup_params.push(Box::new(start.clone()));
+    }
+    if let Some(end) = &filter.end {
+        detail_conditions.push(format!("{detail_date} <= ?"));
+        detail_params.push(Box::new(end.clone()));
+        rollup_conditions.push("r.date <= ?".to_string());
+        rollup_params.push(Box::new(end.clone()));
+    }
+    let detail_where = format!("WHERE {}", detail_conditions.join(" AND "));
+    let rollup_where = if rollup_conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", rollup_conditions.join(" AND "))
+    };
+
+    let detail_pname = provider_name_coalesce("l", "p");
+    let rollup_pname = provider_name_coalesce("r", "p2");
+    let fresh_input_detail = fresh_input_sql("l");
+    let fresh_input_rollup = fresh_input_sql("r");
+    let sql = format!(
+        "SELECT date, app_type, provider_id, MAX(provider_name), model, pricing_model,
+                SUM(request_count), SUM(fresh_input), SUM(cache_read), SUM(cache_creation)
+         FROM (
+            SELECT {detail_date} AS date, l.app_type, l.provider_id,
+                {detail_pname} AS provider_name,
+                l.model,
+                COALESCE(NULLIF(l.pricing_model, ''), l.model) AS pricing_model,
+                COUNT(*) AS request_count,
+                COALESCE(SUM({fresh_input_detail}), 0) AS fresh_input,
+                COALESCE(SUM(l.cache_read_tokens), 0) AS cache_read,
+                COALESCE(SUM(l.cache_creation_tokens), 0) AS cache_creation
+            FROM proxy_request_logs l
+            LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+            {detail_where}
+            GROUP BY 1, 2, 3, 5, 6
+            UNION ALL
+            SELECT r.date, r.app_type, r.provider_id,
+                {rollup_pname},
+                r.model,
+                COALESCE(NULLIF(r.pricing_model, ''), r.model),
+                COALESCE(SUM(r.request_count), 0),
+                COALESCE(SUM({fresh_input_rollup}), 0),
+                COALESCE(SUM(r.cache_read_tokens), 0),
+                COALESCE(SUM(r.cache_creation_tokens), 0)
+            FROM usage_daily_rollups r
+            LEFT JOIN providers p2 ON r.provider_id = p2.id AND r.app_type = p2.app_type
+            {rollup_where}
+            GROUP BY 1, 2, 3, 5, 6
+         )
+         GROUP BY date, app_type, provider_id, model, pricing_model
+         ORDER BY date ASC"
+    );
+
+    let mut params = detail_params;
+    params.extend(rollup_params);
+    let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let rows = stmt
+        .query_map(param_refs.as_slice(), |row| {
+            Ok(CacheBucket {
+                date: row.get(0)?,
+                app_type: row.get(1)?,
+                provider_id: row.get(2)?,
+                provider_name: row.get(3)?,
+                model: row.get(4)?,
+                pricing_model: row.get(5)?,
+                request_count: row.get::<_, i64>(6)?.max(0) as u64,
+                fresh_input_tokens: row.get::<_, i64>(7)?.max(0) as u64,
+                cache_read_tokens: row.get::<_, i64>(8)?.max(0) as u64,
+                cache_creation_tokens: row.get::<_, i64>(9)?.max(0) as u64,
+            })
+        })
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Database(e.to_string()))
+}
+
+fn bucket_stats(bucket: &CacheBucket, pricing: Option<&ModelPricing>) -> CacheStats {
+    let mut stats = CacheStats {
+        request_count: bucket.request_count,
+        fresh_input_tokens: bucket.fresh_input_tokens,
+        cache_read_tokens: bucket.cache_read_tokens,
+        cache_creation_tokens: bucket.cache_creation_tokens,
+        ..CacheStats::default()
+    };
+    let Some(pricing) = pricing else {
+        stats.unpriced_requests = bucket.request_count;
+        return stats;
+    };
+    let per_token = |value: rust_decimal::Decimal| value.to_f64().unwrap_or(0.0) / 1_000_000.0;
+    let input = per_token(pricing.input_cost_per_million);
+    let read = per_token(pricing.cache_read_cost_per_million);
+    let write = per_token(pricing.cache_creation_cost_per_million);
+    stats.saved_usd = bucket.cache_read_tokens as f64 * (input - read).max(0.0);
+    stats.write_overhead_usd = bucket.cache_creation_tokens as f64 * (write - input).max(0.0);
+    stats
+}
+
+fn cache_warning(
+    app_type: &str,
+    provider_id: &str,
+    provider_name: &str,
+    stats: &CacheStats,
+) -> Option<CacheWarning> {
+    if stats.request_count < WARN_MIN_REQUESTS || stats.hit_ratio >= WARN_MAX_HIT_RATIO {
+        return None;
+    }
+    let avg_prompt = stats.prompt_tokens() / stats.request_count;
+    let (kind, message) = if stats.cache_creation_tokens > 0 && stats.cache_read_tokens == 0 {
+        (
+            CacheWarningKind::WritesWithoutReads,
+            format!(
+                "{provider_name}: {} cache-write tokens billed but no cache reads in {} requests; the relay may be dropping cache_control or routing across upstream accounts",
+                stats.cache_creation_tokens, stats.request_count
+            ),
+        )
+    } else if avg_prompt >= WARN_MIN_AVG_PROMPT_TOKENS {
+        (
+            CacheWarningKind::NoReads,
+            format!(
+                "{provider_name}: {:.1}% cache hit ratio over {} requests averaging {avg_prompt} prompt tokens; prompt caching looks broken on this provider",
+                stats.hit_ratio * 100.0,
+                stats.request_count
+            ),
+        )
+    } else {
+        return None;
+    };
+    Some(CacheWarning {
+        kind,
+        app_type: app_type.to_string(),
+        provider_id: provider_id.to_string(),
+        provider_name: provider_name.to_string(),
+        request_count: stats.request_count,
+        hit_ratio: stats.hit_ratio,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::params;
+
+    use super::{CacheAnalyticsFilter, CacheGroupBy, CacheWarningKind};
+    use crate::database::Database;
+
+    fn insert_log(
+        db: &Database,
+        request_id: &str,
+        provider: &str,
+        input: i64,
+        cache_read: i64,
+        cache_creation: i64,
+    ) {
+        let conn = db.conn.lock().expect("lock db");
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model,
+                input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                input_token_semantics, total_cost_usd, latency_ms, status_code, created_at
+            ) VALUES (?1, ?2, 'claude', 'cache-test-model', ?3, 10, ?4, ?5, 2, '0', 10, 200,
+                      strftime('%s', 'now'))",
+            params![request_id, provider, input, cache_read, cache_creation],
+        )
+        .expect("insert log");
+    }
+
+    #[test]
+    fn computes_savings_and_flags_providers_without_cache_reads() {
+        let db = Database::memory().expect("db");
+        {
+            let conn = db.conn.lock().expect("lock db");
+            conn.execute(
+                "INSERT INTO model_pricing VALUES
+                    ('cache-test-model', 'Cache Test', '3', '15', '0.3', '3.75')",
+                [],
+            )
+            .expect("insert pricing");
+        }
+        for i in 0..20 {
+            insert_log(&db, &format!("good-{i}"), "good", 1_000, 9_000, 0);
+            insert_log(&db, &format!("bad-{i}"), "bad", 1_000, 0, 9_000);
+        }
+
+        let report = db
+            .get_cache_analytics(&CacheAnalyticsFilter::default(), CacheGroupBy::Provider)
+            .expect("report");
+
+        let good = report.rows.iter().find(|row| row.key == "good").unwrap();
+        assert!((good.stats.hit_ratio - 0.9).abs() < 1e-9);
+        // 180k reads * (3 - 0.3) / 1M
+        assert!((good.stats.saved_usd - 0.486).abs() < 1e-9);
+        let bad = report.rows.iter().find(|row| row.key == "bad").unwrap();
+        assert_eq!(bad.stats.hit_ratio, 0.0);
+        // 180k writes * (3.75 - 3) / 1M
+        assert!((bad.stats.write_overhead_usd - 0.135).abs() < 1e-9);
+        assert!((report.totals.net_saved_usd - (0.486 - 0.135)).abs() < 1e-9);
+
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].provider_id, "bad");
+        assert_eq!(
+            report.warnings[0].kind,
+            CacheWarningKind::WritesWithoutReads
+        );
+
+        let by_day = db
+            .get_cache_analytics(&CacheAnalyticsFilter::default(), CacheGroupBy::Day)
+            .expect("by day");
+        assert_eq!(by_day.rows.len(), 1);
+        assert_eq!(by_day.rows[0].stats.request_count, 40);
+    }
+}
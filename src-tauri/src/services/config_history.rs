@@ -0,0 +1,561 @@
+//! Edit history for providers, MCP servers and prompts.
+//!
+//! Every add, update and delete appends a full snapshot to the sidecar store
+//! (see [`crate::local_store::config_history`]). Only file-backed databases
+//! record history, so in-memory test states never touch the sidecar. History
+//! is an extra: recording failures are logged and never fail the edit.
+//!
+//! Snapshots never hold a plain credential: values already sealed by the vault
+//! are kept, plain ones are recorded masked, and [`restore_masked`] takes the
+//! current value back when a revision is restored.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::config_history::ConfigRevision;
+use crate::local_store::LocalStore;
+
+/// Revisions kept per item; older ones are pruned on write.
+const MAX_REVISIONS_PER_ITEM: i64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    Provider,
+    Mcp,
+    Prompt,
+}
+
+impl HistoryKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Provider => "provider",
+            Self::Mcp => "mcp",
+            Self::Prompt => "prompt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    Add,
+    Update,
+    Delete,
+}
+
+impl HistoryAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// MCP servers are shared by every app, so they are recorded without one.
+fn app_key(app_type: Option<&AppType>) -> &'static str {
+    app_type.map_or("", AppType::as_str)
+}
+
+/// Append a snapshot of `item`; failures are only logged.
+pub(crate) fn record_best_effort<T: Serialize>(
+    db: &Database,
+    kind: HistoryKind,
+    app_type: Option<&AppType>,
+    item_id: &str,
+    action: HistoryAction,
+    item: &T,
+) {
+    if db.file_path().is_none() {
+        return;
+    }
+    let result = serde_json::to_value(item)
+        .map_err(|e| AppError::Message(e.to_string()))
+        .and_then(|snapshot| {
+            let snapshot = mask_with(&snapshot, redact_secret);
+            let store = LocalStore::open()?;
+            let (kind, app) = (kind.as_str(), app_key(app_type));
+            let recorded = store.record_config_revision(
+                kind,
+                app,
+                item_id,
+                action.as_str(),
+                &snapshot,
+                chrono::Utc::now().timestamp(),
+            )?;
+            if recorded.is_some() {
+                store.prune_config_history(kind, app, item_id, MAX_REVISIONS_PER_ITEM)?;
+            }
+            Ok(())
+        });
+    if let Err(error) = result {
+        log::debug!(
+            "[HISTORY] record {} {item_id} failed: {error}",
+            kind.as_str()
+        );
+    }
+}
+
+/// Recorded revisions of an item, oldest first.
+pub fn list_revisions(
+    kind: HistoryKind,
+    app_type: Option<&AppType>,
+    item_id: &str,
+) -> Result<Vec<ConfigRevision>, AppError> {
+    if !LocalStore::path()?.exists() {
+        return Ok(Vec::new());
+    }
+    LocalStore::open()?.list_config_revisions(kind.as_str(), app_key(app_type), item_id)
+}
+
+/// A single revision; errors if it was never recorded or has been pruned.
+pub fn find_revision(
+    kind: HistoryKind,
+    app_type: Option<&AppType>,
+    item_id: &str,
+    rev: i64,
+) -> Result<ConfigRevision, AppError> {
+    list_revisions(kind, app_type, item_id)?
+        .into_iter()
+        .find(|revision| revision.rev == rev)
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!("No revision {rev} recorded for '{item_id}'"))
+        })
+}
+
+fn is_masked_field(name: &str) -> bool {
+    // `apiKeyField` names the variable the key lives in, it is not a key.
+    crate::secret_ref::is_secret_field(name) && !name.to_ascii_lowercase().ends_with("field")
+}
+
+/// Display form of a stored credential.
+fn mask_secret(value: &str) -> String {
+    if crate::vault::is_sealed(value) {
+        return format!("{}…", crate::vault::SEALED_PREFIX);
+    }
+    if value.is_empty() || crate::secret_ref::is_reference(value) || is_masked(value) {
+        return value.to_string();
+    }
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 12 {
+        return "****".to_string();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}…{tail}")
+}
+
+/// Recorded form of a credential: references (sealed values included) are
+/// kept so a revert can restore them, plain values are masked.
+fn redact_secret(value: &str) -> String {
+    if crate::secret_ref::is_reference(value) {
+        return value.to_string();
+    }
+    mask_secret(value)
+}
+
+/// Whether `value` is the output of [`mask_secret`].
+fn is_masked(value: &str) -> bool {
+    value == "****" || value.contains('…')
+}
+
+/// Mask `name = "value"` lines of embedded TOML/env text.
+fn mask_text(text: &str, mask: fn(&str) -> String) -> String {
+    text.lines()
+        .map(|line| {
+            let Some((name, value)) = line.split_once('=') else {
+                return line.to_string();
+            };
+            let key = name.trim().trim_matches('"');
+            let quoted = value.trim();
+            if !is_masked_field(key) || quoted.len() < 2 || !quoted.starts_with('"') {
+                return line.to_string();
+            }
+            let inner = quoted.trim_matches('"');
+            format!("{name}= \"{}\"", mask(inner))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn mask_with(value: &Value, mask: fn(&str) -> String) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(name, child)| {
+                    let masked = match child {
+                        Value::String(raw) if is_masked_field(name) => Value::String(mask(raw)),
+                        _ => mask_with(child, mask),
+                    };
+                    (name.clone(), masked)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| mask_with(item, mask)).collect())
+        }
+        Value::String(raw) if raw.contains('\n') => Value::String(mask_text(raw, mask)),
+        other => other.clone(),
+    }
+}
+
+/// Copy of `value` with credentials replaced by a masked form.
+pub fn mask_secrets(value: &Value) -> Value {
+    mask_with(value, mask_secret)
+}
+
+/// Puts the credentials of `current` back where `snapshot` only recorded a
+/// masked value; credentials `current` no longer has are left empty.
+pub(crate) fn restore_masked(snapshot: &mut Value, current: Option<&Value>) {
+    match snapshot {
+        Value::Object(map) => {
+            for (name, child) in map.iter_mut() {
+                let current = current.and_then(|current| current.get(name));
+                match child {
+                    Value::String(raw) if is_masked_field(name) && is_masked(raw) => {
+                        *raw = current
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string();
+                    }
+                    _ => restore_masked(child, current),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                restore_masked(child, current.and_then(|current| current.get(index)));
+            }
+        }
+        Value::String(raw) if raw.contains('\n') => {
+            *raw = restore_masked_text(raw, current.and_then(Value::as_str));
+        }
+        _ => {}
+    }
+}
+
+fn restore_masked_text(text: &str, current: Option<&str>) -> String {
+    let field = |line: &str| {
+        line.split_once('=').map(|(name, value)| {
+            (
+                name.trim().trim_matches('"').to_string(),
+                value.trim().to_string(),
+            )
+        })
+    };
+    text.lines()
+        .map(|line| {
+            let Some((key, quoted)) = field(line) else {
+                return line.to_string();
+            };
+            if !is_masked_field(&key) || !is_masked(quoted.trim_matches('"')) {
+                return line.to_string();
+            }
+            current
+                .into_iter()
+                .flat_map(str::lines)
+                .find(|candidate| field(candidate).is_some_and(|(name, _)| name == key))
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{key} = \"\""))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Seals the plain credentials left in revisions recorded before snapshots
+/// were masked; returns how many revisions changed.
+pub(crate) fn seal_recorded(key: &crate::vault::Unlocked) -> Result<usize, AppError> {
+    if !LocalStore::path()?.exists() {
+        return Ok(0);
+    }
+    LocalStore::open()?.rewrite_config_snapshots(|snapshot| seal_snapshot(key, snapshot) > 0)
+}
+
+fn seal_snapshot(key: &crate::vault::Unlocked, value: &mut Value) -> usize {
+    match value {
+        Value::Object(map) => map
+            .iter_mut()
+            .map(|(name, child)| match child {
+                Value::String(raw) if is_masked_field(name) && !is_masked(raw) => {
+                    match crate::vault::seal_plain(key, raw) {
+                        Some(sealed) => {
+                            *raw = sealed;
+                            1
+                        }
+                        None => 0,
+                    }
+                }
+                _ => seal_snapshot(key, child),
+            })
+            .sum(),
+        Value::Array(items) => items.iter_mut().map(|item| seal_snapshot(key, item)).sum(),
+        // Credentials inside TOML text cannot be resolved once sealed.
+        Value::String(raw) if raw.contains('\n') => {
+            let masked = mask_text(raw, redact_secret);
+            if masked == *raw {
+                return 0;
+            }
+            *raw = masked;
+            1
+        }
+        _ => 0,
+    }
+}
+
+/// One changed leaf between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+fn flatten(value: &Value, path: String, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (name, child) in map {
+                let child_path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}.{name}")
+                };
+                flatten(child, child_path, leaves);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                flatten(child, format!("{path}[{index}]"), leaves);
+            }
+        }
+        _ => {
+            leaves.insert(path, value.clone());
+        }
+    }
+}
+
+fn render_leaf(value: &Value) -> String {
+    match value {
+        Value::String(raw) => raw.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Line changes of a multi-line string (longest common subsequence).
+fn diff_lines(path: &str, before: &str, after: &str, changes: &mut Vec<FieldChange>) {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(FieldChange {
+                path: format!("{path}:{}", i + 1),
+                before: Some(old[i].to_string()),
+                after: None,
+            });
+            i += 1;
+        } else {
+            changes.push(FieldChange {
+                path: format!("{path}:{}", j + 1),
+                before: None,
+                after: Some(new[j].to_string()),
+            });
+            j += 1;
+        }
+    }
+}
+
+/// Leaf-level differences from `before` to `after`, secrets masked.
+pub fn diff_snapshots(before: Option<&Value>, after: Option<&Value>) -> Vec<FieldChange> {
+    let mut old = BTreeMap::new();
+    let mut new = BTreeMap::new();
+    if let Some(before) = before {
+        flatten(&mask_secrets(before), String::new(), &mut old);
+    }
+    if let Some(after) = after {
+        flatten(&mask_secrets(after), String::new(), &mut new);
+    }
+
+    let mut paths: Vec<&String> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut changes = Vec::new();
+    for path in paths {
+        match (old.get(path), new.get(path)) {
+            (Some(a), Some(b)) if a == b => {}
+            (Some(Value::String(a)), Some(Value::String(b)))
+                if a.contains('\n') || b.contains('\n') =>
+            {
+                diff_lines(path, a, b, &mut changes);
+            }
+            (a, b) => changes.push(FieldChange {
+                path: path.clone(),
+                before: a.map(render_leaf),
+                after: b.map(render_leaf),
+            }),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_mask_credentials() {
+        let before = json!({
+            "name": "Relay",
+            "settingsConfig": {
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://relay.example.com/v1",
+                    "ANTHROPIC_AUTH_TOKEN": "sk-ant-0123456789abcdef"
+                },
+                "config": "model = \"a\"\nexperimental_bearer_token = \"sk-0123456789abcdef\"\n"
+            },
+            "meta": {"apiKeyField": "ANTHROPIC_AUTH_TOKEN"}
+        });
+        let after = json!({
+            "name": "Relay",
+            "settingsConfig": {
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                    "ANTHROPIC_AUTH_TOKEN": "sk-ant-fedcba9876543210"
+                },
+                "config": "model = \"b\"\nexperimental_bearer_token = \"sk-0123456789abcdef\"\n"
+            },
+            "meta": {"apiKeyField": "ANTHROPIC_AUTH_TOKEN"}
+        });
+
+        let changes = diff_snapshots(Some(&before), Some(&after));
+        let rendered = serde_json::to_string(&changes).expect("serialize");
+        assert!(!rendered.contains("0123456789abcdef"));
+        assert!(!rendered.contains("9876543210"));
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.path.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "settingsConfig.config:1",
+                "settingsConfig.config:1",
+                "settingsConfig.env.ANTHROPIC_AUTH_TOKEN",
+                "settingsConfig.env.ANTHROPIC_BASE_URL",
+            ]
+        );
+        assert_eq!(changes[0].before.as_deref(), Some("model = \"a\""));
+        assert_eq!(changes[1].after.as_deref(), Some("model = \"b\""));
+        assert_eq!(changes[2].before.as_deref(), Some("sk-a…cdef"));
+
+        let masked = mask_secrets(&before);
+        assert_eq!(masked["meta"]["apiKeyField"], "ANTHROPIC_AUTH_TOKEN");
+
+        let removed = diff_snapshots(Some(&before), None);
+        assert!(removed.iter().all(|change| change.after.is_none()));
+    }
+
+    #[test]
+    fn recorded_snapshots_mask_plain_credentials_and_revert_takes_the_current_ones() {
+        let recorded = mask_with(
+            &json!({
+                "settingsConfig": {
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "sk-ant-0123456789abcdef",
+                        "ANTHROPIC_API_KEY": "vault:payload",
+                        "ANTHROPIC_BASE_URL": "https://relay.example.com"
+                    },
+                    "config": "model = \"a\"\nexperimental_bearer_token = \"sk-0123456789abcdef\""
+                },
+                "meta": {"apiKeyPool": {"keys": [{"key": "sk-pool-0123456789"}]}}
+            }),
+            redact_secret,
+        );
+        let rendered = recorded.to_string();
+        assert!(!rendered.contains("0123456789"));
+        assert_eq!(
+            recorded["settingsConfig"]["env"]["ANTHROPIC_API_KEY"],
+            "vault:payload"
+        );
+        assert_eq!(mask_secret("vault:payload"), "vault:…");
+        assert_eq!(
+            mask_secrets(&recorded)["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-a…cdef"
+        );
+
+        let current = json!({
+            "settingsConfig": {
+                "env": {"ANTHROPIC_AUTH_TOKEN": "sk-current"},
+                "config": "experimental_bearer_token = \"sk-current\"\nmodel = \"b\""
+            },
+            "meta": {"apiKeyPool": {"keys": [{"key": "vault:pooled"}]}}
+        });
+        let mut restored = recorded.clone();
+        restore_masked(&mut restored, Some(&current));
+        assert_eq!(
+            restored["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-current"
+        );
+        assert_eq!(
+            restored["settingsConfig"]["config"],
+            "model = \"a\"\nexperimental_bearer_token = \"sk-current\""
+        );
+        assert_eq!(
+            restored["meta"]["apiKeyPool"]["keys"][0]["key"],
+            "vault:pooled"
+        );
+
+        let mut orphan = recorded;
+        restore_masked(&mut orphan, None);
+        assert_eq!(orphan["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"], "");
+    }
+
+    #[test]
+    fn legacy_plain_revisions_are_sealed() {
+        let key = crate::vault::Unlocked::Local(
+            crate::vault::VaultKey::from_base64(&format!("{}=", "A".repeat(43))).expect("key"),
+        );
+        let mut snapshot = json!({
+            "settingsConfig": {
+                "env": {"ANTHROPIC_AUTH_TOKEN": "sk-legacy", "ANTHROPIC_API_KEY": "****"},
+                "config": "experimental_bearer_token = \"sk-0123456789abcdef\"\nmodel = \"a\""
+            },
+            "meta": {"apiKeyPool": {"keys": [{"key": "sk-pooled"}]}}
+        });
+        assert_eq!(seal_snapshot(&key, &mut snapshot), 3);
+        let token = snapshot["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"]
+            .as_str()
+            .unwrap();
+        assert!(crate::vault::is_sealed(token));
+        assert_eq!(
+            key.open(token.strip_prefix(crate::vault::SEALED_PREFIX).unwrap())
+                .unwrap(),
+            "sk-legacy"
+        );
+        assert_eq!(
+            snapshot["settingsConfig"]["env"]["ANTHROPIC_API_KEY"],
+            "****"
+        );
+        assert!(!snapshot.to_string().contains("0123456789"));
+        assert_eq!(seal_snapshot(&key, &mut snapshot), 0);
+    }
+}
@@ -100,7 +100,7 @@ fn check_system_env(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
 
 /// Check shell configuration files for environment variable exports (Unix only)
 #[cfg(not(target_os = "windows"))]
-fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
+pub(crate) fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
     let mut conflicts = Vec::new();
 
     let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
@@ -116,41 +116,47 @@ fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
 
     for file_path in config_files {
         if let Ok(content) = fs::read_to_string(&file_path) {
-            // Parse lines for export statements
-            for (line_num, line) in content.lines().enumerate() {
-                let trimmed = line.trim();
-
-                // Match patterns like: export VAR=value or VAR=value
-                if trimmed.starts_with("export ")
-                    || (!trimmed.starts_with('#') && trimmed.contains('='))
-                {
-                    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
-
-                    if let Some(eq_pos) = export_line.find('=') {
-                        let var_name = export_line[..eq_pos].trim();
-                        let var_value = export_line[eq_pos + 1..].trim();
-
-                        // Check if variable name contains any keyword
-                        if keywords.iter().any(|k| var_name.to_uppercase().contains(k)) {
-                            conflicts.push(EnvConflict {
-                                var_name: var_name.to_string(),
-                                var_value: var_value
-                                    .trim_matches('"')
-                                    .trim_matches('\'')
-                                    .to_string(),
-                                source_type: "file".to_string(),
-                                source_path: format!("{}:{}", file_path, line_num + 1),
-                            });
-                        }
-                    }
-                }
-            }
+            conflicts.extend(scan_shell_exports(&file_path, &content, keywords));
         }
     }
 
     Ok(conflicts)
 }
 
+/// Parse `export VAR=value` / `VAR=value` lines of one shell file whose variable
+/// name contains any of the keywords
+pub(crate) fn scan_shell_exports(
+    file_path: &str,
+    content: &str,
+    keywords: &[&str],
+) -> Vec<EnvConflict> {
+    let mut conflicts = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        // Match patterns like: export VAR=value or VAR=value
+        if trimmed.starts_with("export ") || (!trimmed.starts_with('#') && trimmed.contains('=')) {
+            let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+
+            if let Some(eq_pos) = export_line.find('=') {
+                let var_name = export_line[..eq_pos].trim();
+                let var_value = export_line[eq_pos + 1..].trim();
+
+                // Check if variable name contains any keyword
+                if keywords.iter().any(|k| var_name.to_uppercase().contains(k)) {
+                    conflicts.push(EnvConflict {
+                        var_name: var_name.to_string(),
+                        var_value: var_value.trim_matches('"').trim_matches('\'').to_string(),
+                        source_type: "file".to_string(),
+                        source_path: format!("{}:{}", file_path, line_num + 1),
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
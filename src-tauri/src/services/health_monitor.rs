@@ -0,0 +1,394 @@
+//! Background provider health monitoring.
+//!
+//! When enabled (it is off by default, since every probe is a real upstream
+//! request), the daemon probes the providers of the failover queues (or every
+//! provider of the failover-capable apps with `allProviders`) at the
+//! configured interval, using the same reachability check, per-provider test
+//! config and resolved credentials as `provider stream-check`. Probe results
+//! go to the machine-local sidecar store and only they are pruned after
+//! `retainDays`; manual checks stay in `stream_check_logs`. [`load_checks`]
+//! reads both, [`summarize`] turns them into uptime and latency figures for
+//! `provider health` and the TUI, and the proxy reads them back through
+//! [`probe_verdicts`] to open or close circuit breakers ahead of real traffic.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::database::{Database, StreamCheckLogEntry};
+use crate::error::AppError;
+use crate::local_store::{LocalStore, SharedLocalStore};
+use crate::provider::Provider;
+use crate::services::stream_check::StreamCheckService;
+use crate::settings::HealthMonitorSettings;
+
+const TICK_SECS: u64 = 30;
+/// Prune roughly once an hour.
+const PRUNE_EVERY_TICKS: u64 = 120;
+const PROBE_CONCURRENCY: usize = 4;
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// Providers the monitor should probe, per the settings.
+pub(crate) fn probe_targets(
+    db: &Database,
+    settings: &HealthMonitorSettings,
+) -> Vec<(AppType, Provider)> {
+    let mut targets = Vec::new();
+    for app_type in AppType::all().filter(AppType::supports_failover) {
+        let providers = match db.get_all_providers(app_type.as_str()) {
+            Ok(providers) => providers,
+            Err(error) => {
+                log::debug!(
+                    "[HEALTH-MONITOR] list {} providers failed: {error}",
+                    app_type.as_str()
+                );
+                continue;
+            }
+        };
+        if settings.all_providers {
+            targets.extend(
+                providers
+                    .into_values()
+                    .map(|provider| (app_type.clone(), provider)),
+            );
+            continue;
+        }
+        let queue = db.get_failover_queue(app_type.as_str()).unwrap_or_default();
+        targets.extend(queue.into_iter().filter_map(|item| {
+            providers
+                .get(&item.provider_id)
+                .cloned()
+                .map(|provider| (app_type.clone(), provider))
+        }));
+    }
+    targets
+}
+
+/// Manual checks and monitor probes since `since`, oldest first.
+pub(crate) fn load_checks(
+    db: &Database,
+    store: Option<&LocalStore>,
+    app_type: Option<&str>,
+    provider_id: Option<&str>,
+    since: i64,
+) -> Result<Vec<StreamCheckLogEntry>, AppError> {
+    let mut entries = db.list_stream_check_logs(app_type, provider_id, since)?;
+    if let Some(store) = store {
+        match store.list_health_probes(app_type, provider_id, since) {
+            Ok(probes) => entries.extend(probes),
+            Err(error) => log::debug!("[HEALTH-MONITOR] read probes failed: {error}"),
+        }
+        entries.sort_by_key(|entry| entry.tested_at);
+    }
+    Ok(entries)
+}
+
+/// Probe every target whose last check is older than the interval. Returns
+/// the number of providers probed.
+pub(crate) async fn probe_due_providers(
+    db: &Database,
+    store: &LocalStore,
+    settings: &HealthMonitorSettings,
+) -> usize {
+    let now = chrono::Utc::now().timestamp();
+    let interval = settings.interval_secs as i64;
+    let recent = load_checks(db, Some(store), None, None, now - interval).unwrap_or_default();
+    let due: Vec<_> = probe_targets(db, settings)
+        .into_iter()
+        .filter(|(app_type, provider)| {
+            !recent.iter().any(|entry| {
+                entry.app_type == app_type.as_str() && entry.provider_id == provider.id
+            })
+        })
+        .collect();
+    if due.is_empty() {
+        return 0;
+    }
+
+    let config = db.get_stream_check_config().unwrap_or_default();
+    let config = &config;
+    futures::stream::iter(due.iter())
+        .for_each_concurrent(PROBE_CONCURRENCY, |(app_type, provider)| async move {
+            match StreamCheckService::check_with_retry(app_type, provider, config).await {
+                Ok(result) => {
+                    if let Err(error) = store.record_health_probe(
+                        app_type.as_str(),
+                        &provider.id,
+                        &provider.name,
+                        &result,
+                    ) {
+                        log::debug!(
+                            "[HEALTH-MONITOR] record {}/{} failed: {error}",
+                            app_type.as_str(),
+                            provider.id
+                        );
+                    }
+                }
+                Err(error) => log::debug!(
+                    "[HEALTH-MONITOR] probe {}/{} failed: {error}",
+                    app_type.as_str(),
+                    provider.id
+                ),
+            }
+        })
+        .await;
+    due.len()
+}
+
+fn prune_probes(store: &LocalStore, settings: &HealthMonitorSettings) -> Result<usize, AppError> {
+    let cutoff = chrono::Utc::now().timestamp() - settings.retain_days as i64 * DAY_SECS;
+    store.prune_health_probes(cutoff)
+}
+
+/// Daemon task: look for due providers every 30 seconds.
+pub(crate) fn spawn_health_monitor(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(TICK_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut ticks: u64 = 0;
+        let store = SharedLocalStore::default();
+        loop {
+            interval.tick().await;
+            let settings = crate::settings::load_health_monitor_settings_from_disk();
+            if !settings.enabled {
+                continue;
+            }
+            let Some(store) = store.get() else {
+                continue;
+            };
+            if ticks.is_multiple_of(PRUNE_EVERY_TICKS) {
+                match prune_probes(store, &settings) {
+                    Ok(pruned) if pruned > 0 => {
+                        log::debug!("[HEALTH-MONITOR] pruned {pruned} probe(s) ({context})");
+                    }
+                    Ok(_) => {}
+                    Err(error) => log::debug!("[HEALTH-MONITOR] prune failed ({context}): {error}"),
+                }
+            }
+            ticks = ticks.wrapping_add(1);
+            let probed = probe_due_providers(&db, store, &settings).await;
+            if probed > 0 {
+                log::debug!("[HEALTH-MONITOR] probed {probed} provider(s) ({context})");
+            }
+        }
+    })
+}
+
+/// Uptime and latency of one provider over a window of checks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthSummary {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub probes: usize,
+    pub successes: usize,
+    pub uptime_pct: f64,
+    pub avg_latency_ms: Option<u64>,
+    pub p95_latency_ms: Option<u64>,
+    pub last_status: String,
+    pub last_message: String,
+    pub last_tested_at: i64,
+    /// Latency of each successful check, oldest first.
+    pub latencies: Vec<(i64, f64)>,
+}
+
+/// Group checks (oldest first) by provider.
+pub fn summarize(entries: &[StreamCheckLogEntry]) -> Vec<ProviderHealthSummary> {
+    let mut groups: Vec<((&str, &str), Vec<&StreamCheckLogEntry>)> = Vec::new();
+    for entry in entries {
+        let key = (entry.app_type.as_str(), entry.provider_id.as_str());
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, rows)) => rows.push(entry),
+            None => groups.push((key, vec![entry])),
+        }
+    }
+
+    let mut summaries: Vec<_> = groups
+        .into_iter()
+        .filter_map(|(_, rows)| {
+            let last = *rows.last()?;
+            let successes = rows.iter().filter(|row| row.success).count();
+            let latencies: Vec<(i64, f64)> = rows
+                .iter()
+                .filter(|row| row.success)
+                .filter_map(|row| Some((row.tested_at, row.response_time_ms? as f64)))
+                .collect();
+            let mut sorted: Vec<u64> = latencies.iter().map(|(_, ms)| *ms as u64).collect();
+            sorted.sort_unstable();
+            Some(ProviderHealthSummary {
+                app_type: last.app_type.clone(),
+                provider_id: last.provider_id.clone(),
+                provider_name: last.provider_name.clone(),
+                probes: rows.len(),
+                successes,
+                uptime_pct: successes as f64 * 100.0 / rows.len() as f64,
+                avg_latency_ms: (!sorted.is_empty())
+                    .then(|| sorted.iter().sum::<u64>() / sorted.len() as u64),
+                p95_latency_ms: percentile(&sorted, 0.95),
+                last_status: last.status.clone(),
+                last_message: last.message.clone(),
+                last_tested_at: last.tested_at,
+                latencies,
+            })
+        })
+        .collect();
+    summaries.sort_by(|a, b| {
+        (a.app_type.as_str(), a.provider_name.as_str())
+            .cmp(&(b.app_type.as_str(), b.provider_name.as_str()))
+    });
+    summaries
+}
+
+fn percentile(sorted: &[u64], quantile: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// What the latest checks of a provider say.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProbeVerdict {
+    pub tested_at: i64,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+}
+
+/// Latest verdict per provider ID, from checks of one app (oldest first).
+pub(crate) fn probe_verdicts(entries: &[StreamCheckLogEntry]) -> HashMap<String, ProbeVerdict> {
+    let mut verdicts: HashMap<String, ProbeVerdict> = HashMap::new();
+    for entry in entries {
+        let verdict = verdicts
+            .entry(entry.provider_id.clone())
+            .or_insert(ProbeVerdict {
+                tested_at: entry.tested_at,
+                healthy: entry.success,
+                consecutive_failures: 0,
+            });
+        verdict.tested_at = entry.tested_at;
+        verdict.healthy = entry.success;
+        verdict.consecutive_failures = if entry.success {
+            0
+        } else {
+            verdict.consecutive_failures + 1
+        };
+    }
+    verdicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(provider: &str, at: i64, success: bool, latency: Option<u64>) -> StreamCheckLogEntry {
+        StreamCheckLogEntry {
+            provider_id: provider.to_string(),
+            provider_name: provider.to_uppercase(),
+            app_type: "claude".to_string(),
+            status: if success { "operational" } else { "failed" }.to_string(),
+            success,
+            message: String::new(),
+            response_time_ms: latency,
+            http_status: None,
+            tested_at: at,
+        }
+    }
+
+    #[test]
+    fn summary_reports_uptime_and_latency_of_successful_checks() {
+        let entries = [
+            entry("a", 1, true, Some(100)),
+            entry("b", 2, false, None),
+            entry("a", 3, false, Some(8000)),
+            entry("a", 4, true, Some(300)),
+            entry("a", 5, true, Some(200)),
+        ];
+        let summaries = summarize(&entries);
+        assert_eq!(summaries.len(), 2);
+
+        let a = &summaries[0];
+        assert_eq!((a.provider_id.as_str(), a.probes, a.successes), ("a", 4, 3));
+        assert!((a.uptime_pct - 75.0).abs() < f64::EPSILON);
+        assert_eq!(a.avg_latency_ms, Some(200));
+        assert_eq!(a.p95_latency_ms, Some(300));
+        assert_eq!(a.latencies, vec![(1, 100.0), (4, 300.0), (5, 200.0)]);
+        assert_eq!(a.last_tested_at, 5);
+
+        let b = &summaries[1];
+        assert_eq!(b.uptime_pct, 0.0);
+        assert_eq!(b.avg_latency_ms, None);
+    }
+
+    fn check_result(tested_at: i64) -> crate::services::stream_check::StreamCheckResult {
+        crate::services::stream_check::StreamCheckResult {
+            status: crate::services::stream_check::HealthStatus::Operational,
+            success: true,
+            message: "Reachable".to_string(),
+            response_time_ms: Some(100),
+            http_status: Some(200),
+            model_used: String::new(),
+            tested_at,
+            retry_count: 0,
+            error_category: None,
+        }
+    }
+
+    #[test]
+    fn pruning_probes_keeps_manual_checks() {
+        let db = Database::memory().expect("memory database");
+        let store = LocalStore::in_memory().expect("sidecar store");
+        let now = chrono::Utc::now().timestamp();
+        db.save_stream_check_log("a", "A", "claude", &check_result(10))
+            .expect("manual check");
+        for tested_at in [20, now] {
+            store
+                .record_health_probe("claude", "a", "A", &check_result(tested_at))
+                .expect("probe");
+        }
+
+        let settings = HealthMonitorSettings {
+            retain_days: 1,
+            ..Default::default()
+        };
+        assert_eq!(prune_probes(&store, &settings).expect("prune"), 1);
+
+        let tested: Vec<i64> = load_checks(&db, Some(&store), Some("claude"), None, 0)
+            .expect("load checks")
+            .iter()
+            .map(|entry| entry.tested_at)
+            .collect();
+        assert_eq!(tested, vec![10, now]);
+        assert!(!HealthMonitorSettings::default().enabled);
+    }
+
+    #[test]
+    fn verdict_counts_failures_since_the_last_success() {
+        let entries = [
+            entry("a", 1, false, None),
+            entry("a", 2, true, Some(10)),
+            entry("a", 3, false, None),
+            entry("a", 4, false, None),
+            entry("b", 5, true, Some(10)),
+        ];
+        let verdicts = probe_verdicts(&entries);
+        assert_eq!(
+            verdicts["a"],
+            ProbeVerdict {
+                tested_at: 4,
+                healthy: false,
+                consecutive_failures: 2,
+            }
+        );
+        assert!(verdicts["b"].healthy);
+        assert_eq!(verdicts["b"].consecutive_failures, 0);
+    }
+}
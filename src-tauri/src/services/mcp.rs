@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use crate::app_config::{AppType, McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 use crate::mcp;
+use crate::services::config_history::{self, HistoryAction, HistoryKind};
 use crate::store::AppState;
 
 /// MCP 相关业务逻辑（v3.7.0 统一结构）
@@ -39,11 +40,16 @@ impl McpService {
 
     /// 添加或更新 MCP 服务器
     pub fn upsert_server(state: &AppState, server: McpServer) -> Result<(), AppError> {
-        let (server_id, apps_to_remove) = {
+        let mut server = server;
+        // 加密库已解锁时，env/headers 中的凭据以密文保存
+        crate::vault::seal_on_save(&state.db, &mut server.server);
+
+        let (server_id, apps_to_remove, existed) = {
             let mut cfg = state.config.write()?;
 
             let servers = cfg.mcp.servers.get_or_insert_with(HashMap::new);
             let server_id = server.id.clone();
+            let existed = servers.contains_key(&server_id);
 
             let apps_to_remove = servers
                 .get(&server_id)
@@ -60,10 +66,22 @@ impl McpService {
             // 插入或更新
             servers.insert(server_id.clone(), server.clone());
 
-            (server_id, apps_to_remove)
+            (server_id, apps_to_remove, existed)
         };
 
         state.save()?;
+        config_history::record_best_effort(
+            &state.db,
+            HistoryKind::Mcp,
+            None,
+            &server_id,
+            if existed {
+                HistoryAction::Update
+            } else {
+                HistoryAction::Add
+            },
+            &server,
+        );
 
         // 如果是更新：对“由启用变为禁用”的应用，清理对应 live 配置
         for app in apps_to_remove {
@@ -90,6 +108,14 @@ impl McpService {
 
         if let Some(server) = server {
             state.save()?;
+            config_history::record_best_effort(
+                &state.db,
+                HistoryKind::Mcp,
+                None,
+                id,
+                HistoryAction::Delete,
+                &server,
+            );
 
             // 从所有应用的 live 配置中移除
             Self::remove_server_from_all_apps(state, id, &server)?;
@@ -200,6 +226,11 @@ impl McpService {
         server: &McpServer,
         app: &AppType,
     ) -> Result<(), AppError> {
+        // live 配置写入解析后的凭据，数据库中保留引用/密文
+        let server = &McpServer {
+            server: crate::secret_ref::resolve_settings(&server.server)?,
+            ..server.clone()
+        };
         match app {
             AppType::Claude => {
                 mcp::sync_single_server_to_claude(cfg, &server.id, &server.server)?;
@@ -1,10 +1,14 @@
+pub mod agent_settings;
 pub mod auth;
 pub mod balance;
+pub(crate) mod bundle;
+pub mod cache_analytics;
 pub mod codex_history;
 pub mod codex_oauth;
 pub mod codex_oauth_models;
 pub mod coding_plan;
 pub mod config;
+pub mod config_history;
 pub mod copilot_auth;
 #[cfg(feature = "cli")]
 pub mod env_checker;
@@ -12,13 +16,20 @@ pub mod env_checker;
 #[cfg(feature = "cli")]
 pub mod env_manager;
 pub mod global_proxy;
+pub(crate) mod health_monitor;
 #[cfg(feature = "cli")]
 pub mod local_env_check;
 pub mod mcp;
 pub mod model_fetch;
+pub(crate) mod model_watch;
+pub mod otel_export;
+pub mod profile;
+pub mod project_usage;
 pub mod prompt;
 pub mod provider;
+pub mod provider_quota;
 pub mod proxy;
+pub mod quota_history;
 pub(crate) mod s3;
 pub mod s3_sync;
 pub(crate) mod session_cost;
@@ -33,13 +44,20 @@ pub mod sql_helpers;
 pub(crate) mod state_coordination;
 pub mod stream_check;
 pub mod subscription;
+pub mod switch_history;
+pub mod switch_rules;
 pub(crate) mod sync_protocol;
+pub mod universal_provider;
+pub mod usage_alerts;
 pub mod usage_stats;
+pub(crate) mod vault;
 #[cfg(feature = "cli")]
 pub mod visible_apps;
 pub mod webdav;
 pub mod webdav_sync;
 
+#[cfg(feature = "cli")]
+pub use agent_settings::AgentSettingsService;
 pub use auth::{AuthService, ManagedAuthAccount, ManagedAuthDeviceCodeResponse, ManagedAuthStatus};
 pub use codex_oauth::CodexOAuthService;
 pub use config::ConfigService;
@@ -47,14 +65,20 @@ pub use copilot_auth::CopilotAuthService;
 pub use global_proxy::GlobalOutboundProxyConfig;
 pub use mcp::McpService;
 pub use model_fetch::FetchedModel;
+#[cfg(feature = "cli")]
+pub use profile::{ProfileChange, ProfileService};
 pub use prompt::PromptService;
 pub use provider::{reapply_current_codex_official_live, ProviderService};
+#[cfg(feature = "cli")]
+pub use provider::{ForeignCandidate, ForeignSource, LintFinding, LintSeverity};
 pub use proxy::ProxyService;
 pub use s3_sync::{S3RemoteInfo, S3SyncService, S3SyncSummary};
 pub use skill::{ImportSkillSelection, SkillService};
 pub use speedtest::{EndpointLatency, SpeedtestService};
 pub use stream_check::{HealthStatus, StreamCheckConfig, StreamCheckResult, StreamCheckService};
 pub use subscription::{CredentialStatus, ExtraUsage, QuotaTier, SubscriptionQuota};
+#[cfg(feature = "cli")]
+pub use universal_provider::{UniversalProviderService, UniversalSyncReport};
 #[allow(unused_imports)]
 pub use usage_stats::{
     DailyStats, LogFilters, ModelStats, PaginatedLogs, ProviderLimitStatus, ProviderStats,
@@ -0,0 +1,513 @@
+//! Model catalog watching.
+//!
+//! The daemon refetches every provider's model list at the configured
+//! interval (the same request as `provider fetch-models`) and records the
+//! snapshot in the local sidecar store, which keeps the additions and
+//! removals between two fetches. Role mappings of a provider
+//! (`ANTHROPIC_DEFAULT_*_MODEL`, the Codex `model`, OpenCode `models`) are
+//! checked against the latest snapshot so `provider list` and the TUI can
+//! flag models the upstream no longer serves, and [`remap_model`] rewrites
+//! them in one go.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::model_catalog::{ModelCatalogDiff, ModelSnapshot};
+use crate::local_store::LocalStore;
+use crate::provider::Provider;
+use crate::settings::ModelWatchSettings;
+
+const TICK_SECS: u64 = 60;
+/// Prune roughly once a day.
+const PRUNE_EVERY_TICKS: u64 = 24 * 60;
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// A model a provider's settings point at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelReference {
+    /// Where the model is set, e.g. `ANTHROPIC_DEFAULT_OPUS_MODEL` or `model`.
+    pub field: String,
+    pub model: String,
+}
+
+/// A referenced model missing from the provider's latest model list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingModel {
+    pub field: String,
+    pub model: String,
+    /// Closest model the provider still lists, if any.
+    pub suggestion: Option<String>,
+}
+
+fn is_claude_model_key(key: &str) -> bool {
+    matches!(
+        key,
+        "ANTHROPIC_MODEL" | "ANTHROPIC_SMALL_FAST_MODEL" | "CLAUDE_CODE_SUBAGENT_MODEL"
+    ) || (key.starts_with("ANTHROPIC_DEFAULT_") && key.ends_with("_MODEL"))
+}
+
+fn codex_config_model(settings_config: &Value) -> Option<String> {
+    let config = settings_config.get("config")?.as_str()?;
+    let doc = config.parse::<toml_edit::DocumentMut>().ok()?;
+    doc.get("model")?.as_str().map(str::to_string)
+}
+
+/// Models the provider's role mapping refers to.
+pub fn referenced_models(app_type: &AppType, settings_config: &Value) -> Vec<ModelReference> {
+    let mut references = Vec::new();
+    match app_type {
+        AppType::Claude => {
+            if let Some(env) = settings_config.get("env").and_then(Value::as_object) {
+                for (key, value) in env {
+                    if let Some(model) = value.as_str().filter(|_| is_claude_model_key(key)) {
+                        references.push(ModelReference {
+                            field: key.clone(),
+                            model: model.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        AppType::Codex => {
+            if let Some(model) = codex_config_model(settings_config) {
+                references.push(ModelReference {
+                    field: "model".to_string(),
+                    model,
+                });
+            }
+        }
+        AppType::OpenCode => {
+            if let Some(models) = settings_config.get("models").and_then(Value::as_object) {
+                references.extend(models.keys().map(|model| ModelReference {
+                    field: "models".to_string(),
+                    model: model.clone(),
+                }));
+            }
+        }
+        _ => {}
+    }
+    references.retain(|reference| !reference.model.trim().is_empty());
+    references
+}
+
+/// Split a trailing context-window marker such as `[1m]` off a model ID.
+fn split_model_suffix(model: &str) -> (&str, &str) {
+    let model = model.trim();
+    match model.rfind('[') {
+        Some(start) if model.ends_with(']') => (&model[..start], &model[start..]),
+        _ => (model, ""),
+    }
+}
+
+fn same_model(a: &str, b: &str) -> bool {
+    split_model_suffix(a)
+        .0
+        .eq_ignore_ascii_case(split_model_suffix(b).0)
+}
+
+/// The listed model sharing the longest prefix with `model`; ties go to the
+/// lexically greatest ID, which is usually the newest release.
+pub fn closest_model(model: &str, available: &[String]) -> Option<String> {
+    let (base, suffix) = split_model_suffix(model);
+    let base = base.to_ascii_lowercase();
+    let family_len = base.find(['-', '/', '.']).map_or(base.len(), |at| at + 1);
+    available
+        .iter()
+        .filter(|candidate| !same_model(candidate, model))
+        .map(|candidate| {
+            let shared = candidate
+                .to_ascii_lowercase()
+                .chars()
+                .zip(base.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            (shared, candidate)
+        })
+        .filter(|(shared, _)| *shared >= family_len.max(3))
+        .max_by(|(a, left), (b, right)| a.cmp(b).then_with(|| left.cmp(right)))
+        .map(|(_, candidate)| format!("{candidate}{suffix}"))
+}
+
+/// Referenced models the snapshot no longer lists. An empty snapshot says
+/// nothing about the upstream, so it never flags anything.
+pub fn missing_models(
+    app_type: &AppType,
+    provider: &Provider,
+    snapshot: &ModelSnapshot,
+) -> Vec<MissingModel> {
+    if snapshot.models.is_empty() {
+        return Vec::new();
+    }
+    referenced_models(app_type, &provider.settings_config)
+        .into_iter()
+        .filter(|reference| {
+            !snapshot
+                .models
+                .iter()
+                .any(|model| same_model(model, &reference.model))
+        })
+        .map(|reference| MissingModel {
+            suggestion: closest_model(&reference.model, &snapshot.models),
+            field: reference.field,
+            model: reference.model,
+        })
+        .collect()
+}
+
+/// Missing models per provider ID, from the stored snapshots. Never creates
+/// the sidecar file and treats read failures as "nothing known".
+pub fn missing_models_by_provider<'a>(
+    app_type: &AppType,
+    providers: impl IntoIterator<Item = &'a Provider>,
+) -> HashMap<String, Vec<MissingModel>> {
+    if !LocalStore::path().is_ok_and(|path| path.exists()) {
+        return HashMap::new();
+    }
+    let snapshots =
+        match LocalStore::open().and_then(|store| store.list_model_snapshots(app_type.as_str())) {
+            Ok(snapshots) => snapshots,
+            Err(error) => {
+                log::debug!(
+                    "[MODEL-WATCH] read {} snapshots failed: {error}",
+                    app_type.as_str()
+                );
+                return HashMap::new();
+            }
+        };
+    providers
+        .into_iter()
+        .filter_map(|provider| {
+            let snapshot = snapshots
+                .iter()
+                .find(|snapshot| snapshot.provider_id == provider.id)?;
+            let missing = missing_models(app_type, provider, snapshot);
+            (!missing.is_empty()).then(|| (provider.id.clone(), missing))
+        })
+        .collect()
+}
+
+fn replacement(current: &str, to: &str) -> String {
+    let (_, suffix) = split_model_suffix(current);
+    if split_model_suffix(to).1.is_empty() {
+        format!("{}{suffix}", to.trim())
+    } else {
+        to.trim().to_string()
+    }
+}
+
+/// Point every role that uses `from` at `to`. Returns the fields changed.
+pub fn remap_model(
+    app_type: &AppType,
+    settings_config: &mut Value,
+    from: &str,
+    to: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut changed = Vec::new();
+    match app_type {
+        AppType::Claude => {
+            if let Some(env) = settings_config
+                .get_mut("env")
+                .and_then(Value::as_object_mut)
+            {
+                for (key, value) in env.iter_mut() {
+                    let Some(current) = value.as_str().filter(|_| is_claude_model_key(key)) else {
+                        continue;
+                    };
+                    if same_model(current, from) {
+                        *value = Value::String(replacement(current, to));
+                        changed.push(key.clone());
+                    }
+                }
+            }
+        }
+        AppType::Codex => {
+            if let Some(config) = settings_config
+                .get("config")
+                .and_then(Value::as_str)
+                .filter(|_| {
+                    codex_config_model(settings_config)
+                        .is_some_and(|model| same_model(&model, from))
+                })
+            {
+                let mut doc = config.parse::<toml_edit::DocumentMut>().map_err(|e| {
+                    AppError::InvalidInput(format!("Invalid Codex config.toml: {e}"))
+                })?;
+                doc["model"] = toml_edit::value(to.trim());
+                settings_config["config"] = Value::String(doc.to_string());
+                changed.push("model".to_string());
+            }
+        }
+        AppType::OpenCode => {
+            if let Some(models) = settings_config
+                .get_mut("models")
+                .and_then(Value::as_object_mut)
+            {
+                let to = to.trim();
+                if let Some(from_key) = models.keys().find(|key| same_model(key, from)).cloned() {
+                    let keep_existing = models.contains_key(to);
+                    let entries = std::mem::take(models);
+                    for (key, mut entry) in entries {
+                        if key != from_key {
+                            models.insert(key, entry);
+                            continue;
+                        }
+                        if keep_existing {
+                            continue;
+                        }
+                        if let Some(name) = entry.get_mut("name") {
+                            if name.as_str() == Some(from_key.as_str()) {
+                                *name = Value::String(to.to_string());
+                            }
+                        }
+                        models.insert(to.to_string(), entry);
+                    }
+                    changed.push("models".to_string());
+                }
+            }
+        }
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "Model remapping is not supported for {}",
+                app_type.as_str()
+            )))
+        }
+    }
+    Ok(changed)
+}
+
+/// Fetch the provider's model list and record it as the latest snapshot.
+pub(crate) async fn refresh_provider(
+    store: &LocalStore,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<ModelCatalogDiff, AppError> {
+    let models = super::provider::models::fetch_provider_model_ids(provider, app_type).await?;
+    if models.is_empty() {
+        // An empty list is far more likely a broken endpoint than every model
+        // being retired at once; keep the previous snapshot.
+        return Err(AppError::Message(format!(
+            "Provider '{}' returned no models",
+            provider.id
+        )));
+    }
+    store.record_model_snapshot(
+        app_type.as_str(),
+        &provider.id,
+        &models,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+fn log_diff(app_type: &AppType, provider: &Provider, diff: &ModelCatalogDiff) {
+    if !diff.added.is_empty() {
+        log::info!(
+            "[MODEL-WATCH] {}/{}: new model(s): {}",
+            app_type.as_str(),
+            provider.id,
+            diff.added.join(", ")
+        );
+    }
+    if !diff.removed.is_empty() {
+        log::warn!(
+            "[MODEL-WATCH] {}/{}: model(s) no longer listed: {}",
+            app_type.as_str(),
+            provider.id,
+            diff.removed.join(", ")
+        );
+    }
+}
+
+/// Refresh every provider whose snapshot (or last failed attempt) is older
+/// than the interval. Returns the number of providers fetched.
+pub(crate) async fn refresh_due_providers(
+    db: &Database,
+    settings: &ModelWatchSettings,
+    attempts: &mut HashMap<(String, String), i64>,
+) -> usize {
+    let store = match LocalStore::open() {
+        Ok(store) => store,
+        Err(error) => {
+            log::debug!("[MODEL-WATCH] sidecar unavailable: {error}");
+            return 0;
+        }
+    };
+    let now = chrono::Utc::now().timestamp();
+    let interval = settings.interval_secs as i64;
+    let mut fetched = 0;
+    for app_type in AppType::all() {
+        let providers = match db.get_all_providers(app_type.as_str()) {
+            Ok(providers) => providers,
+            Err(error) => {
+                log::debug!(
+                    "[MODEL-WATCH] list {} providers failed: {error}",
+                    app_type.as_str()
+                );
+                continue;
+            }
+        };
+        let snapshots = store
+            .list_model_snapshots(app_type.as_str())
+            .unwrap_or_default();
+        for provider in providers.values() {
+            let key = (app_type.as_str().to_string(), provider.id.clone());
+            let last_fetch = snapshots
+                .iter()
+                .find(|snapshot| snapshot.provider_id == provider.id)
+                .map(|snapshot| snapshot.fetched_at);
+            let last = last_fetch.max(attempts.get(&key).copied());
+            if last.is_some_and(|last| now - last < interval) {
+                continue;
+            }
+            attempts.insert(key, now);
+            fetched += 1;
+            match refresh_provider(&store, &app_type, provider).await {
+                Ok(diff) => log_diff(&app_type, provider, &diff),
+                Err(error) => log::debug!(
+                    "[MODEL-WATCH] fetch {}/{} failed: {error}",
+                    app_type.as_str(),
+                    provider.id
+                ),
+            }
+        }
+    }
+    fetched
+}
+
+fn prune_changes(settings: &ModelWatchSettings) -> Result<usize, AppError> {
+    let cutoff = chrono::Utc::now().timestamp() - settings.retain_days as i64 * DAY_SECS;
+    LocalStore::open()?.prune_model_changes(cutoff)
+}
+
+/// Daemon task: look for due providers every minute.
+pub(crate) fn spawn_model_watch(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(TICK_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut attempts = HashMap::new();
+        let mut ticks: u64 = 0;
+        loop {
+            interval.tick().await;
+            let settings = crate::settings::load_model_watch_settings_from_disk();
+            if !settings.enabled {
+                continue;
+            }
+            if ticks.is_multiple_of(PRUNE_EVERY_TICKS) {
+                if let Err(error) = prune_changes(&settings) {
+                    log::debug!("[MODEL-WATCH] prune failed ({context}): {error}");
+                }
+            }
+            ticks = ticks.wrapping_add(1);
+            let fetched = refresh_due_providers(&db, &settings, &mut attempts).await;
+            if fetched > 0 {
+                log::debug!("[MODEL-WATCH] fetched {fetched} model list(s) ({context})");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(models: &[&str]) -> ModelSnapshot {
+        ModelSnapshot {
+            provider_id: "relay".to_string(),
+            models: models.iter().map(|model| model.to_string()).collect(),
+            fetched_at: 1,
+        }
+    }
+
+    #[test]
+    fn retired_claude_roles_are_flagged_with_a_suggestion() {
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({ "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_MODEL": "glm-4.6",
+                "ANTHROPIC_DEFAULT_OPUS_MODEL": "claude-opus-4-5[1m]",
+                "ANTHROPIC_DEFAULT_HAIKU_MODEL": "claude-haiku-4-5"
+            }}),
+            None,
+        );
+        let missing = missing_models(
+            &AppType::Claude,
+            &provider,
+            &snapshot(&[
+                "claude-haiku-4-5",
+                "claude-opus-4-6",
+                "claude-opus-4-7",
+                "glm-4.7",
+                "glm-5",
+            ]),
+        );
+
+        assert_eq!(
+            missing,
+            vec![
+                MissingModel {
+                    field: "ANTHROPIC_MODEL".to_string(),
+                    model: "glm-4.6".to_string(),
+                    suggestion: Some("glm-4.7".to_string()),
+                },
+                MissingModel {
+                    field: "ANTHROPIC_DEFAULT_OPUS_MODEL".to_string(),
+                    model: "claude-opus-4-5[1m]".to_string(),
+                    suggestion: Some("claude-opus-4-7[1m]".to_string()),
+                },
+            ]
+        );
+        assert!(missing_models(&AppType::Claude, &provider, &snapshot(&[])).is_empty());
+        assert_eq!(closest_model("qwen-max", &["glm-5".to_string()]), None);
+    }
+
+    #[test]
+    fn remap_rewrites_every_role_using_the_model() {
+        let mut claude = json!({ "env": {
+            "ANTHROPIC_DEFAULT_SONNET_MODEL": "old[1m]",
+            "ANTHROPIC_DEFAULT_OPUS_MODEL": "old",
+            "ANTHROPIC_AUTH_TOKEN": "old"
+        }});
+        let changed = remap_model(&AppType::Claude, &mut claude, "old", "new").expect("remap");
+        assert_eq!(changed.len(), 2);
+        assert_eq!(claude["env"]["ANTHROPIC_DEFAULT_SONNET_MODEL"], "new[1m]");
+        assert_eq!(claude["env"]["ANTHROPIC_DEFAULT_OPUS_MODEL"], "new");
+        assert_eq!(claude["env"]["ANTHROPIC_AUTH_TOKEN"], "old");
+
+        let mut codex =
+            json!({ "config": "model = \"gpt-old\" # pinned\nmodel_provider = \"relay\"\n" });
+        assert_eq!(
+            remap_model(&AppType::Codex, &mut codex, "gpt-old", "gpt-new").expect("remap"),
+            vec!["model".to_string()]
+        );
+        assert_eq!(codex_config_model(&codex).as_deref(), Some("gpt-new"));
+        assert!(codex["config"]
+            .as_str()
+            .unwrap()
+            .contains("model_provider = \"relay\""));
+
+        let mut opencode = json!({ "models": {
+            "a": { "name": "a" },
+            "old": { "name": "old", "limit": { "context": 1 } },
+            "z": {}
+        }});
+        remap_model(&AppType::OpenCode, &mut opencode, "old", "new").expect("remap");
+        let keys: Vec<_> = opencode["models"].as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["a", "new", "z"]);
+        assert_eq!(opencode["models"]["new"]["name"], "new");
+        assert_eq!(opencode["models"]["new"]["limit"]["context"], 1);
+    }
+}
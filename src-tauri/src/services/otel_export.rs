@@ -0,0 +1,706 @@
+//! OpenTelemetry export over OTLP/HTTP (JSON encoding).
+//!
+//! Two independent pieces:
+//! - **Spans**: the proxy worker hands one [`RequestSpan`] per proxied request
+//!   to a bounded in-memory queue. A background task drains it in batches and
+//!   POSTs `/v1/traces`. When the queue is full the span is dropped and
+//!   counted, so the request path never waits on the collector.
+//! - **Metrics**: the daemon (or a foreground proxy not managed by one)
+//!   periodically pushes today's per app/provider/model usage, read from
+//!   `usage_daily_rollups` plus not-yet-rolled-up request logs, to
+//!   `/v1/metrics` as gauges.
+//!
+//! The JSON mapping follows the OTLP protobuf schema, so no OpenTelemetry SDK
+//! is needed; any collector with the OTLP/HTTP receiver accepts it.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::Local;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::sql_helpers::fresh_input_sql;
+use crate::services::usage_stats::effective_usage_log_filter;
+use crate::settings::OtelExportSettings;
+
+const SCOPE_NAME: &str = "cc-switch.proxy";
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+const METRICS_TICK: Duration = Duration::from_secs(10);
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+/// OTLP `SPAN_KIND_CLIENT`: the proxy calls an upstream on behalf of a tool.
+const SPAN_KIND_CLIENT: i64 = 3;
+const STATUS_CODE_OK: i64 = 1;
+const STATUS_CODE_ERROR: i64 = 2;
+
+static SPAN_QUEUE: OnceLock<mpsc::Sender<RequestSpan>> = OnceLock::new();
+static DROPPED_SPANS: AtomicU64 = AtomicU64::new(0);
+
+/// Everything recorded about one proxied request.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RequestSpan {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub request_model: String,
+    pub response_model: String,
+    pub status_code: u16,
+    pub is_streaming: bool,
+    pub session_id: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: Option<f64>,
+    pub first_token_ms: Option<u64>,
+    /// Providers tried for this request, including the one that answered.
+    pub provider_attempts: u32,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub error_message: Option<String>,
+}
+
+impl RequestSpan {
+    /// Start/end timestamps for a request that started at `started_at`.
+    pub(crate) fn timestamps(started_at: Instant) -> (u64, u64) {
+        let end = unix_nanos(SystemTime::now());
+        let elapsed = started_at.elapsed().as_nanos() as u64;
+        (end.saturating_sub(elapsed), end)
+    }
+}
+
+/// Cheap hot-path check: only build spans when an exporter is running in this
+/// process and traces are switched on.
+pub(crate) fn traces_enabled() -> bool {
+    SPAN_QUEUE.get().is_some() && crate::settings::get_otel_export_settings().traces_active()
+}
+
+/// Queue a span without blocking; drops it when the exporter is behind.
+pub(crate) fn record_request_span(span: RequestSpan) {
+    let Some(queue) = SPAN_QUEUE.get() else {
+        return;
+    };
+    if queue.try_send(span).is_err() {
+        DROPPED_SPANS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Start the span exporter for a proxy worker. The queue is process-wide and
+/// created once; later calls reuse it.
+pub(crate) fn spawn_otel_span_exporter(context: &'static str) -> tokio::task::JoinHandle<()> {
+    let settings = crate::settings::get_otel_export_settings();
+    let (tx, rx) = mpsc::channel(settings.queue_capacity.max(1));
+    let installed = SPAN_QUEUE.set(tx).is_ok();
+    tokio::spawn(async move {
+        if !installed {
+            log::debug!("[otel:{context}] span exporter already running");
+            return;
+        }
+        run_span_exporter(rx, context).await;
+    })
+}
+
+async fn run_span_exporter(mut rx: mpsc::Receiver<RequestSpan>, context: &'static str) {
+    let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+    flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut batch: Vec<RequestSpan> = Vec::new();
+    let mut reported_drops = 0u64;
+    loop {
+        let settings = tokio::select! {
+            span = rx.recv() => {
+                let Some(span) = span else { return };
+                batch.push(span);
+                let settings = crate::settings::get_otel_export_settings();
+                if batch.len() < settings.batch_size {
+                    continue;
+                }
+                settings
+            }
+            _ = flush.tick() => {
+                let dropped = DROPPED_SPANS.load(Ordering::Relaxed);
+                if dropped > reported_drops {
+                    log::warn!(
+                        "[otel:{context}] dropped {} span(s): export queue full",
+                        dropped - reported_drops
+                    );
+                    reported_drops = dropped;
+                }
+                if batch.is_empty() {
+                    continue;
+                }
+                crate::settings::get_otel_export_settings()
+            }
+        };
+        let spans = std::mem::take(&mut batch);
+        if !settings.traces_active() {
+            continue;
+        }
+        let client = crate::proxy::http_client::get();
+        for chunk in spans.chunks(settings.batch_size) {
+            let payload = encode_spans(&settings, chunk);
+            if let Err(err) = post_otlp(&client, &settings, "v1/traces", &payload).await {
+                log::warn!(
+                    "[otel:{context}] export of {} span(s) failed: {err}",
+                    chunk.len()
+                );
+            }
+        }
+    }
+}
+
+/// Periodically push today's usage metrics. The interval is re-read from
+/// `settings.json` each tick so CLI edits apply without a restart.
+pub(crate) fn spawn_otel_metrics_exporter(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(METRICS_TICK);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut last_push: Option<Instant> = None;
+        loop {
+            tick.tick().await;
+            let settings = crate::settings::load_otel_export_settings_from_disk();
+            if !settings.metrics_active() {
+                continue;
+            }
+            let interval = Duration::from_secs(settings.metrics_interval_secs);
+            if last_push.is_some_and(|at| at.elapsed() < interval) {
+                continue;
+            }
+            last_push = Some(Instant::now());
+
+            let query_db = db.clone();
+            let today = Local::now().format("%Y-%m-%d").to_string();
+            let rows = match tokio::task::spawn_blocking(move || {
+                load_usage_metric_rows(&query_db, &today)
+            })
+            .await
+            {
+                Ok(Ok(rows)) => rows,
+                Ok(Err(err)) => {
+                    log::warn!("[otel:{context}] load usage metrics failed: {err}");
+                    continue;
+                }
+                Err(err) => {
+                    log::warn!("[otel:{context}] usage metrics task failed: {err}");
+                    continue;
+                }
+            };
+            if rows.is_empty() {
+                continue;
+            }
+            let payload = encode_metrics(&settings, &rows, unix_nanos(SystemTime::now()));
+            let client = crate::proxy::http_client::get();
+            if let Err(err) = post_otlp(&client, &settings, "v1/metrics", &payload).await {
+                log::warn!("[otel:{context}] metrics export failed: {err}");
+            }
+        }
+    })
+}
+
+/// Push one synthetic span to verify endpoint and headers.
+pub(crate) async fn send_test_span(settings: &OtelExportSettings) -> Result<(), String> {
+    let (start_unix_nanos, end_unix_nanos) = RequestSpan::timestamps(Instant::now());
+    let span = RequestSpan {
+        app_type: "test".to_string(),
+        provider_id: "cc-switch-test".to_string(),
+        provider_name: "cc-switch test span".to_string(),
+        request_model: String::new(),
+        response_model: String::new(),
+        status_code: 200,
+        is_streaming: false,
+        session_id: String::new(),
+        input_tokens: 0,
+        output_tokens: 0,
+        cache_read_tokens: 0,
+        cache_creation_tokens: 0,
+        cost_usd: None,
+        first_token_ms: None,
+        provider_attempts: 1,
+        start_unix_nanos,
+        end_unix_nanos,
+        error_message: None,
+    };
+    let payload = encode_spans(settings, &[span]);
+    post_otlp(
+        &crate::proxy::http_client::get(),
+        settings,
+        "v1/traces",
+        &payload,
+    )
+    .await
+}
+
+/// Today's usage for one app/provider/model.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UsageMetricRow {
+    pub date: String,
+    pub app_type: String,
+    pub provider_id: String,
+    pub model: String,
+    pub request_count: u64,
+    pub success_count: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: f64,
+}
+
+pub(crate) fn load_usage_metric_rows(
+    db: &Database,
+    date: &str,
+) -> Result<Vec<UsageMetricRow>, AppError> {
+    let conn = lock_conn!(db.conn);
+    let log_filter = effective_usage_log_filter("l");
+    let fresh_input_detail = fresh_input_sql("l");
+    let fresh_input_rollup = fresh_input_sql("r");
+    let sql = format!(
+        "SELECT app_type, provider_id, model,
+                SUM(requests), SUM(successes), SUM(input), SUM(output),
+                SUM(cache_read), SUM(cache_creation), SUM(cost)
+         FROM (
+            SELECT r.app_type, r.provider_id, r.model,
+                r.request_count AS requests, r.success_count AS successes,
+                {fresh_input_rollup} AS input, r.output_tokens AS output,
+                r.cache_read_tokens AS cache_read,
+                r.cache_creation_tokens AS cache_creation,
+                CAST(r.total_cost_usd AS REAL) AS cost
+            FROM usage_daily_rollups r
+            WHERE r.date = ?1
+            UNION ALL
+            SELECT l.app_type, l.provider_id, l.model,
+                1,
+                CASE WHEN l.status_code >= 200 AND l.status_code < 300 THEN 1 ELSE 0 END,
+                {fresh_input_detail}, l.output_tokens,
+                l.cache_read_tokens, l.cache_creation_tokens,
+                CAST(l.total_cost_usd AS REAL)
+            FROM proxy_request_logs l
+            WHERE date(l.created_at, 'unixepoch', 'localtime') = ?1 AND {log_filter}
+         )
+         GROUP BY app_type, provider_id, model
+         ORDER BY app_type, provider_id, model"
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let to_u64 = |value: i64| value.max(0) as u64;
+    let rows = stmt
+        .query_map([date], |row| {
+            Ok(UsageMetricRow {
+                date: date.to_string(),
+                app_type: row.get(0)?,
+                provider_id: row.get(1)?,
+                model: row.get(2)?,
+                request_count: to_u64(row.get(3)?),
+                success_count: to_u64(row.get(4)?),
+                input_tokens: to_u64(row.get(5)?),
+                output_tokens: to_u64(row.get(6)?),
+                cache_read_tokens: to_u64(row.get(7)?),
+                cache_creation_tokens: to_u64(row.get(8)?),
+                cost_usd: row.get(9)?,
+            })
+        })
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Database(e.to_string()))
+}
+
+async fn post_otlp(
+    client: &reqwest::Client,
+    settings: &OtelExportSettings,
+    signal: &str,
+    payload: &Value,
+) -> Result<(), String> {
+    let mut request = client
+        .post(settings.signal_url(signal))
+        .timeout(EXPORT_TIMEOUT)
+        .json(payload);
+    for (name, value) in &settings.headers {
+        request = request.header(name, value);
+    }
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("collector returned HTTP {status}"))
+    }
+}
+
+fn unix_nanos(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn attr_str(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+// OTLP/JSON encodes 64-bit integers as strings.
+fn attr_int(key: &str, value: u64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+fn attr_double(key: &str, value: f64) -> Value {
+    json!({ "key": key, "value": { "doubleValue": value } })
+}
+
+fn attr_bool(key: &str, value: bool) -> Value {
+    json!({ "key": key, "value": { "boolValue": value } })
+}
+
+fn resource(settings: &OtelExportSettings) -> Value {
+    json!({
+        "attributes": [
+            attr_str("service.name", &settings.service_name),
+            attr_str("service.version", env!("CARGO_PKG_VERSION")),
+        ]
+    })
+}
+
+fn scope() -> Value {
+    json!({ "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") })
+}
+
+fn random_hex_id(bytes: usize) -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..bytes * 2].to_string()
+}
+
+pub(crate) fn encode_spans(settings: &OtelExportSettings, spans: &[RequestSpan]) -> Value {
+    let spans: Vec<Value> = spans.iter().map(encode_span).collect();
+    json!({
+        "resourceSpans": [{
+            "resource": resource(settings),
+            "scopeSpans": [{ "scope": scope(), "spans": spans }]
+        }]
+    })
+}
+
+fn encode_span(span: &RequestSpan) -> Value {
+    let mut attributes = vec![
+        attr_str("cc_switch.app", &span.app_type),
+        attr_str("cc_switch.provider.id", &span.provider_id),
+        attr_str("cc_switch.provider.name", &span.provider_name),
+        attr_str("gen_ai.request.model", &span.request_model),
+        attr_str("gen_ai.response.model", &span.response_model),
+        attr_int("http.response.status_code", u64::from(span.status_code)),
+        attr_bool("cc_switch.streaming", span.is_streaming),
+        attr_int("gen_ai.usage.input_tokens", span.input_tokens),
+        attr_int("gen_ai.usage.output_tokens", span.output_tokens),
+        attr_int(
+            "gen_ai.usage.cache_read_input_tokens",
+            span.cache_read_tokens,
+        ),
+        attr_int(
+            "gen_ai.usage.cache_creation_input_tokens",
+            span.cache_creation_tokens,
+        ),
+        attr_int(
+            "cc_switch.provider_attempts",
+            u64::from(span.provider_attempts),
+        ),
+        attr_int(
+            "cc_switch.failover_count",
+            u64::from(span.provider_attempts.saturating_sub(1)),
+        ),
+    ];
+    if !span.session_id.is_empty() {
+        attributes.push(attr_str("session.id", &span.session_id));
+    }
+    if let Some(cost) = span.cost_usd {
+        attributes.push(attr_double("cc_switch.cost_usd", cost));
+    }
+    if let Some(first_token_ms) = span.first_token_ms {
+        attributes.push(attr_int("cc_switch.first_token_ms", first_token_ms));
+    }
+    let failed = span.status_code >= 400 || span.error_message.is_some();
+    let status = if failed {
+        json!({
+            "code": STATUS_CODE_ERROR,
+            "message": span.error_message.clone().unwrap_or_default(),
+        })
+    } else {
+        json!({ "code": STATUS_CODE_OK })
+    };
+    json!({
+        "traceId": random_hex_id(16),
+        "spanId": random_hex_id(8),
+        "name": format!("proxy {}", span.app_type),
+        "kind": SPAN_KIND_CLIENT,
+        "startTimeUnixNano": span.start_unix_nanos.to_string(),
+        "endTimeUnixNano": span.end_unix_nanos.to_string(),
+        "attributes": attributes,
+        "status": status,
+    })
+}
+
+pub(crate) fn encode_metrics(
+    settings: &OtelExportSettings,
+    rows: &[UsageMetricRow],
+    now_nanos: u64,
+) -> Value {
+    let point = |row: &UsageMetricRow, extra: Option<Value>, value: Value| {
+        let mut attributes = vec![
+            attr_str("cc_switch.app", &row.app_type),
+            attr_str("cc_switch.provider.id", &row.provider_id),
+            attr_str("gen_ai.request.model", &row.model),
+            attr_str("cc_switch.date", &row.date),
+        ];
+        attributes.extend(extra);
+        let mut point = json!({ "attributes": attributes, "timeUnixNano": now_nanos.to_string() });
+        if let (Some(point), Some(value)) = (point.as_object_mut(), value.as_object()) {
+            point.extend(value.clone());
+        }
+        point
+    };
+    let int = |value: u64| json!({ "asInt": value.to_string() });
+
+    let requests: Vec<Value> = rows
+        .iter()
+        .flat_map(|row| {
+            [
+                point(
+                    row,
+                    Some(attr_str("outcome", "all")),
+                    int(row.request_count),
+                ),
+                point(
+                    row,
+                    Some(attr_str("outcome", "success")),
+                    int(row.success_count),
+                ),
+            ]
+        })
+        .collect();
+    let tokens: Vec<Value> = rows
+        .iter()
+        .flat_map(|row| {
+            [
+                ("input", row.input_tokens),
+                ("output", row.output_tokens),
+                ("cache_read", row.cache_read_tokens),
+                ("cache_creation", row.cache_creation_tokens),
+            ]
+            .into_iter()
+            .map(|(kind, value)| point(row, Some(attr_str("token.type", kind)), int(value)))
+            .collect::<Vec<_>>()
+        })
+        .collect();
+    let cost: Vec<Value> = rows
+        .iter()
+        .map(|row| point(row, None, json!({ "asDouble": row.cost_usd })))
+        .collect();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(settings),
+            "scopeMetrics": [{
+                "scope": scope(),
+                "metrics": [
+                    {
+                        "name": "cc_switch.usage.requests",
+                        "description": "Requests so far on the local calendar day",
+                        "unit": "{request}",
+                        "gauge": { "dataPoints": requests },
+                    },
+                    {
+                        "name": "cc_switch.usage.tokens",
+                        "description": "Tokens so far on the local calendar day",
+                        "unit": "{token}",
+                        "gauge": { "dataPoints": tokens },
+                    },
+                    {
+                        "name": "cc_switch.usage.cost",
+                        "description": "Spend so far on the local calendar day",
+                        "unit": "USD",
+                        "gauge": { "dataPoints": cost },
+                    },
+                ]
+            }]
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{
+        encode_metrics, encode_spans, load_usage_metric_rows, post_otlp, RequestSpan,
+        UsageMetricRow,
+    };
+    use crate::database::Database;
+    use crate::settings::OtelExportSettings;
+    use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+    use serde_json::Value;
+
+    type Captured = Arc<Mutex<Vec<(String, Option<String>, Value)>>>;
+
+    /// Minimal OTLP/HTTP collector stand-in that records every payload.
+    async fn start_collector() -> (String, Captured) {
+        let captured: Captured = Arc::default();
+        let record = |signal: &'static str| {
+            move |State(captured): State<Captured>, headers: HeaderMap, Json(body): Json<Value>| async move {
+                let auth = headers
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                captured
+                    .lock()
+                    .expect("lock")
+                    .push((signal.to_string(), auth, body));
+                Json(serde_json::json!({}))
+            }
+        };
+        let app = Router::new()
+            .route("/v1/traces", post(record("traces")))
+            .route("/v1/metrics", post(record("metrics")))
+            .with_state(captured.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind collector");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        (format!("http://{addr}"), captured)
+    }
+
+    fn span(status: u16) -> RequestSpan {
+        RequestSpan {
+            app_type: "claude".to_string(),
+            provider_id: "relay".to_string(),
+            provider_name: "Relay".to_string(),
+            request_model: "claude-sonnet-4-6".to_string(),
+            response_model: "claude-sonnet-4-6".to_string(),
+            status_code: status,
+            is_streaming: true,
+            session_id: "s-1".to_string(),
+            input_tokens: 120,
+            output_tokens: 30,
+            cache_read_tokens: 4000,
+            cache_creation_tokens: 0,
+            cost_usd: Some(0.0123),
+            first_token_ms: Some(450),
+            provider_attempts: 2,
+            start_unix_nanos: 1_000,
+            end_unix_nanos: 2_000,
+            error_message: (status >= 400).then(|| "upstream 529".to_string()),
+        }
+    }
+
+    fn attr<'a>(span: &'a Value, key: &str) -> &'a Value {
+        span["attributes"]
+            .as_array()
+            .expect("attributes")
+            .iter()
+            .find(|attr| attr["key"] == key)
+            .map(|attr| &attr["value"])
+            .unwrap_or_else(|| panic!("missing attribute {key}"))
+    }
+
+    #[tokio::test]
+    async fn spans_and_metrics_reach_the_collector_with_headers() {
+        let (endpoint, captured) = start_collector().await;
+        let mut settings = OtelExportSettings {
+            enabled: true,
+            endpoint: format!("{endpoint}/"),
+            ..OtelExportSettings::default()
+        };
+        settings
+            .headers
+            .insert("authorization".to_string(), "Bearer t".to_string());
+        settings.normalize();
+        let client = reqwest::Client::new();
+
+        let payload = encode_spans(&settings, &[span(200), span(529)]);
+        post_otlp(&client, &settings, "v1/traces", &payload)
+            .await
+            .expect("export spans");
+
+        let rows = vec![UsageMetricRow {
+            date: "2026-10-18".to_string(),
+            app_type: "codex".to_string(),
+            provider_id: "relay".to_string(),
+            model: "gpt-5".to_string(),
+            request_count: 3,
+            success_count: 2,
+            input_tokens: 10,
+            output_tokens: 5,
+            cache_read_tokens: 7,
+            cache_creation_tokens: 0,
+            cost_usd: 0.5,
+        }];
+        let payload = encode_metrics(&settings, &rows, 42);
+        post_otlp(&client, &settings, "v1/metrics", &payload)
+            .await
+            .expect("export metrics");
+
+        let captured = captured.lock().expect("lock").clone();
+        assert_eq!(captured.len(), 2);
+        let (signal, auth, traces) = &captured[0];
+        assert_eq!(signal, "traces");
+        assert_eq!(auth.as_deref(), Some("Bearer t"));
+        let spans = &traces["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans.as_array().map(Vec::len), Some(2));
+        assert_eq!(spans[0]["traceId"].as_str().map(str::len), Some(32));
+        assert_eq!(spans[0]["spanId"].as_str().map(str::len), Some(16));
+        assert_eq!(attr(&spans[0], "cc_switch.failover_count")["intValue"], "1");
+        assert_eq!(
+            attr(&spans[0], "cc_switch.first_token_ms")["intValue"],
+            "450"
+        );
+        assert_eq!(spans[0]["status"]["code"], 1);
+        assert_eq!(spans[1]["status"]["code"], 2);
+        assert_eq!(spans[1]["status"]["message"], "upstream 529");
+
+        let (signal, _, metrics) = &captured[1];
+        assert_eq!(signal, "metrics");
+        let metrics = &metrics["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics[0]["name"], "cc_switch.usage.requests");
+        assert_eq!(
+            metrics[1]["gauge"]["dataPoints"].as_array().map(Vec::len),
+            Some(4)
+        );
+        assert_eq!(metrics[2]["gauge"]["dataPoints"][0]["asDouble"], 0.5);
+    }
+
+    #[test]
+    fn metric_rows_merge_rollups_and_todays_logs() {
+        let db = Database::memory().expect("db");
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        {
+            let conn = db.conn.lock().expect("lock db");
+            conn.execute(
+                "INSERT INTO usage_daily_rollups
+                    (date, app_type, provider_id, model, request_count, success_count,
+                     input_tokens, output_tokens, input_token_semantics, total_cost_usd)
+                 VALUES (?1, 'claude', 'relay', 'm', 4, 3, 100, 10, 2, '1.5')",
+                [&today],
+            )
+            .expect("insert rollup");
+            conn.execute(
+                "INSERT INTO proxy_request_logs (
+                    request_id, provider_id, app_type, model, input_tokens, output_tokens,
+                    input_token_semantics, total_cost_usd, latency_ms, status_code, created_at
+                ) VALUES ('r1', 'relay', 'claude', 'm', 50, 5, 2, '0.5', 10, 500,
+                          strftime('%s', 'now'))",
+                [],
+            )
+            .expect("insert log");
+        }
+
+        let rows = load_usage_metric_rows(&db, &today).expect("rows");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].request_count, 5);
+        assert_eq!(rows[0].success_count, 3);
+        assert_eq!(rows[0].input_tokens, 150);
+        assert!((rows[0].cost_usd - 2.0).abs() < 1e-9);
+    }
+}
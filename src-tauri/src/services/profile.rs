@@ -0,0 +1,609 @@
+//! Profiles: named snapshots of a whole multi-app setup.
+//!
+//! A [`Profile`] records the current provider of every switchable app, the
+//! MCP server matrix, the enabled prompt per app and the skill matrix.
+//! Applying one replays only the differences through the regular services
+//! (`ProviderService::switch`, `McpService::set_apps`,
+//! `PromptService::enable_prompt`, `SkillService::set_apps`) so live configs
+//! are written exactly as a manual switch would. Every reference is checked
+//! before anything is touched, and a failure halfway through replays the
+//! pre-apply snapshot so the machine is never left half-switched.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::app_config::{AppType, McpApps, SkillApps};
+use crate::error::AppError;
+use crate::profile::{Profile, ProfilePayload};
+use crate::services::{McpService, PromptService, ProviderService, SkillService};
+use crate::store::AppState;
+
+/// Apps whose single "current provider" a profile pins. Additive apps keep
+/// every provider live at once, so there is nothing to switch.
+const PROVIDER_APPS: [AppType; 3] = [AppType::Claude, AppType::Codex, AppType::Gemini];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProfileSection {
+    Provider,
+    Mcp,
+    Prompt,
+    Skill,
+}
+
+impl ProfileSection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProfileSection::Provider => "provider",
+            ProfileSection::Mcp => "mcp",
+            ProfileSection::Prompt => "prompt",
+            ProfileSection::Skill => "skill",
+        }
+    }
+}
+
+/// One difference between two payloads. `key` is the app for providers and
+/// prompts, the server / skill id for MCP and skills; `None` means unset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileChange {
+    pub section: ProfileSection,
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+pub struct ProfileService;
+
+impl ProfileService {
+    pub fn list(state: &AppState) -> Result<Vec<Profile>, AppError> {
+        state.db.get_all_profiles()
+    }
+
+    /// Look a profile up by id, then by case-insensitive name.
+    pub fn find(state: &AppState, id_or_name: &str) -> Result<Option<Profile>, AppError> {
+        if let Some(profile) = state.db.get_profile(id_or_name)? {
+            return Ok(Some(profile));
+        }
+        let wanted = id_or_name.trim().to_lowercase();
+        Ok(Self::list(state)?
+            .into_iter()
+            .find(|profile| profile.name.trim().to_lowercase() == wanted))
+    }
+
+    pub fn get(state: &AppState, id_or_name: &str) -> Result<Profile, AppError> {
+        Self::find(state, id_or_name)?.ok_or_else(|| {
+            AppError::localized(
+                "profile.not_found",
+                format!("Profile 不存在: {id_or_name}"),
+                format!("Profile not found: {id_or_name}"),
+            )
+        })
+    }
+
+    /// Ids of the profiles marked current, keyed by scope (app).
+    pub fn current_ids(state: &AppState) -> Result<BTreeMap<String, String>, AppError> {
+        let mut current = BTreeMap::new();
+        for app in PROVIDER_APPS {
+            if let Some(id) = state.db.get_current_profile_id(app.as_str())? {
+                current.insert(app.as_str().to_string(), id);
+            }
+        }
+        Ok(current)
+    }
+
+    /// Snapshot the live setup in profile payload form.
+    pub fn capture(state: &AppState) -> Result<ProfilePayload, AppError> {
+        let mut providers = BTreeMap::new();
+        for app in PROVIDER_APPS {
+            let current = ProviderService::current(state, app.clone())?;
+            if !current.is_empty() {
+                providers.insert(app.as_str().to_string(), current);
+            }
+        }
+
+        let mcp_servers = McpService::get_all_servers(state)?
+            .into_iter()
+            .map(|(id, server)| (id, server.apps))
+            .collect();
+
+        let mut prompts = BTreeMap::new();
+        for app in AppType::all() {
+            let enabled = state
+                .db
+                .get_prompts(app.as_str())?
+                .into_values()
+                .find(|prompt| prompt.enabled)
+                .map(|prompt| prompt.id);
+            prompts.insert(app.as_str().to_string(), enabled);
+        }
+
+        let skills = SkillService::list_installed()?
+            .into_iter()
+            .map(|skill| (skill.id, skill.apps))
+            .collect();
+
+        Ok(ProfilePayload {
+            providers,
+            mcp_servers: Some(mcp_servers),
+            prompts: Some(prompts),
+            skills: Some(skills),
+            extra: Default::default(),
+        })
+    }
+
+    /// Save the live setup under `name`, overwriting a profile with the same
+    /// id or name. Returns the profile and whether it already existed.
+    pub fn save(state: &AppState, name: &str) -> Result<(Profile, bool), AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Profile name cannot be empty".to_string(),
+            ));
+        }
+        let payload = Self::capture(state)?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let (profile, existed) = match Self::find(state, name)? {
+            Some(mut profile) => {
+                // Keep fields written by other clients that the CLI does not model.
+                let extra = std::mem::take(&mut profile.payload.extra);
+                profile.payload = ProfilePayload { extra, ..payload };
+                profile.updated_at = Some(now);
+                (profile, true)
+            }
+            None => {
+                let profiles = Self::list(state)?;
+                let sort_order = profiles
+                    .iter()
+                    .filter_map(|profile| profile.sort_order)
+                    .max()
+                    .map_or(0, |max| max + 1);
+                let profile = Profile {
+                    id: Self::generate_id(&profiles, name),
+                    name: name.to_string(),
+                    payload,
+                    sort_order: Some(sort_order),
+                    created_at: Some(now),
+                    updated_at: Some(now),
+                };
+                (profile, false)
+            }
+        };
+
+        state.db.save_profile(&profile)?;
+        Ok((profile, existed))
+    }
+
+    pub fn delete(state: &AppState, id_or_name: &str) -> Result<Profile, AppError> {
+        let profile = Self::get(state, id_or_name)?;
+        state.db.delete_profile(&profile.id)?;
+        Ok(profile)
+    }
+
+    /// What applying `profile` would change on this machine right now.
+    pub fn diff_with_current(
+        state: &AppState,
+        profile: &Profile,
+    ) -> Result<Vec<ProfileChange>, AppError> {
+        Ok(Self::diff(&Self::capture(state)?, &profile.payload))
+    }
+
+    /// Differences from `from` to `to`. Entries `to` does not mention, and
+    /// scopes only other clients understand, are left alone by an apply, so
+    /// they are not reported either.
+    pub fn diff(from: &ProfilePayload, to: &ProfilePayload) -> Vec<ProfileChange> {
+        let mut changes = Vec::new();
+
+        for (app, id) in &to.providers {
+            if !PROVIDER_APPS.iter().any(|known| known.as_str() == app) {
+                continue;
+            }
+            let before = from.providers.get(app);
+            if before != Some(id) {
+                changes.push(ProfileChange {
+                    section: ProfileSection::Provider,
+                    key: app.clone(),
+                    before: before.cloned(),
+                    after: Some(id.clone()),
+                });
+            }
+        }
+
+        if let Some(servers) = &to.mcp_servers {
+            for (id, apps) in servers {
+                let before = from.mcp_servers.as_ref().and_then(|all| all.get(id));
+                if before != Some(apps) {
+                    changes.push(ProfileChange {
+                        section: ProfileSection::Mcp,
+                        key: id.clone(),
+                        before: before.map(format_mcp_apps),
+                        after: Some(format_mcp_apps(apps)),
+                    });
+                }
+            }
+        }
+
+        if let Some(prompts) = &to.prompts {
+            for (app, id) in prompts {
+                if AppType::from_str(app).is_err() {
+                    continue;
+                }
+                let before = from
+                    .prompts
+                    .as_ref()
+                    .and_then(|all| all.get(app))
+                    .cloned()
+                    .flatten();
+                if &before != id {
+                    changes.push(ProfileChange {
+                        section: ProfileSection::Prompt,
+                        key: app.clone(),
+                        before,
+                        after: id.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(skills) = &to.skills {
+            for (id, apps) in skills {
+                let before = from.skills.as_ref().and_then(|all| all.get(id));
+                if before != Some(apps) {
+                    changes.push(ProfileChange {
+                        section: ProfileSection::Skill,
+                        key: id.clone(),
+                        before: before.map(format_skill_apps),
+                        after: Some(format_skill_apps(apps)),
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Switch the whole setup to `profile`. Returns the changes made.
+    pub fn apply(state: &AppState, id_or_name: &str) -> Result<Vec<ProfileChange>, AppError> {
+        let profile = Self::get(state, id_or_name)?;
+        Self::validate_references(state, &profile.payload)?;
+
+        let before = Self::capture(state)?;
+        let changes = Self::diff(&before, &profile.payload);
+
+        if let Err(err) = Self::apply_payload(state, &before, &profile.payload) {
+            let rollback =
+                Self::capture(state).and_then(|now| Self::apply_payload(state, &now, &before));
+            return Err(match rollback {
+                Ok(()) => AppError::localized(
+                    "profile.apply.rolled_back",
+                    format!("应用 Profile {} 失败，已回滚: {err}", profile.name),
+                    format!(
+                        "Applying profile {} failed and was rolled back: {err}",
+                        profile.name
+                    ),
+                ),
+                Err(rollback_err) => {
+                    log::error!("Profile rollback failed: {rollback_err}");
+                    AppError::localized(
+                        "profile.apply.rollback_failed",
+                        format!(
+                            "应用 Profile {} 失败: {err}；回滚也失败: {rollback_err}",
+                            profile.name
+                        ),
+                        format!(
+                            "Applying profile {} failed: {err}; rollback also failed: {rollback_err}",
+                            profile.name
+                        ),
+                    )
+                }
+            });
+        }
+
+        for app in PROVIDER_APPS {
+            if profile.payload.providers.contains_key(app.as_str()) {
+                state.db.set_current_profile_id(app.as_str(), &profile.id)?;
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Fail before touching anything when the profile points at providers,
+    /// servers, prompts or skills that no longer exist.
+    fn validate_references(state: &AppState, payload: &ProfilePayload) -> Result<(), AppError> {
+        let mut missing = Vec::new();
+
+        for app in PROVIDER_APPS {
+            let Some(id) = payload.providers.get(app.as_str()) else {
+                continue;
+            };
+            if !state.db.get_all_providers(app.as_str())?.contains_key(id) {
+                missing.push(format!("provider {}/{id}", app.as_str()));
+            }
+        }
+
+        if let Some(servers) = &payload.mcp_servers {
+            let existing = McpService::get_all_servers(state)?;
+            for id in servers.keys() {
+                if !existing.contains_key(id) {
+                    missing.push(format!("mcp {id}"));
+                }
+            }
+        }
+
+        if let Some(prompts) = &payload.prompts {
+            for (app, id) in prompts {
+                let (Ok(app), Some(id)) = (AppType::from_str(app), id) else {
+                    continue;
+                };
+                if !state.db.get_prompts(app.as_str())?.contains_key(id) {
+                    missing.push(format!("prompt {}/{id}", app.as_str()));
+                }
+            }
+        }
+
+        if let Some(skills) = &payload.skills {
+            let installed = SkillService::list_installed()?;
+            for id in skills.keys() {
+                if !installed
+                    .iter()
+                    .any(|skill| &skill.id == id || &skill.directory == id)
+                {
+                    missing.push(format!("skill {id}"));
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let missing = missing.join(", ");
+        Err(AppError::localized(
+            "profile.references.missing",
+            format!("Profile 引用的项目不存在: {missing}"),
+            format!("Profile references items that no longer exist: {missing}"),
+        ))
+    }
+
+    fn apply_payload(
+        state: &AppState,
+        current: &ProfilePayload,
+        target: &ProfilePayload,
+    ) -> Result<(), AppError> {
+        for app in PROVIDER_APPS {
+            let Some(id) = target.providers.get(app.as_str()) else {
+                continue;
+            };
+            if current.providers.get(app.as_str()) != Some(id) {
+                ProviderService::switch(state, app.clone(), id)?;
+            }
+        }
+
+        if let Some(servers) = &target.mcp_servers {
+            for (id, apps) in servers {
+                let before = current.mcp_servers.as_ref().and_then(|all| all.get(id));
+                if before != Some(apps) {
+                    McpService::set_apps(state, id, apps.clone())?;
+                }
+            }
+        }
+
+        if let Some(prompts) = &target.prompts {
+            for (app_key, id) in prompts {
+                // Scopes other clients write (e.g. claude-desktop) are kept but not applied.
+                let Ok(app) = AppType::from_str(app_key) else {
+                    continue;
+                };
+                let before = current
+                    .prompts
+                    .as_ref()
+                    .and_then(|all| all.get(app_key))
+                    .cloned()
+                    .flatten();
+                match (before, id) {
+                    (before, Some(id)) if before.as_ref() != Some(id) => {
+                        PromptService::enable_prompt(state, app, id)?;
+                    }
+                    (Some(before), None) => PromptService::disable_prompt(state, app, &before)?,
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(skills) = &target.skills {
+            for (id, apps) in skills {
+                let before = current.skills.as_ref().and_then(|all| all.get(id));
+                if before != Some(apps) {
+                    SkillService::set_apps(id, apps.clone())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `Work Setup` -> `work-setup`, suffixed `-2`, `-3`, ... on collision.
+    fn generate_id(profiles: &[Profile], name: &str) -> String {
+        let mut base = String::new();
+        for ch in name.to_lowercase().chars() {
+            if ch.is_ascii_lowercase() || ch.is_ascii_digit() {
+                base.push(ch);
+            } else if !base.is_empty() && !base.ends_with('-') {
+                base.push('-');
+            }
+        }
+        let base = match base.trim_end_matches('-') {
+            "" => "profile".to_string(),
+            slug => slug.to_string(),
+        };
+
+        let taken = |id: &str| profiles.iter().any(|profile| profile.id == id);
+        if !taken(&base) {
+            return base;
+        }
+        (2..)
+            .map(|n| format!("{base}-{n}"))
+            .find(|id| !taken(id))
+            .expect("unbounded suffix search")
+    }
+}
+
+fn format_mcp_apps(apps: &McpApps) -> String {
+    format_app_list(apps.enabled_apps())
+}
+
+fn format_skill_apps(apps: &SkillApps) -> String {
+    format_app_list(
+        SkillService::supported_skill_apps()
+            .filter(|app| apps.is_enabled_for(app))
+            .collect(),
+    )
+}
+
+fn format_app_list(apps: Vec<AppType>) -> String {
+    if apps.is_empty() {
+        return "-".to_string();
+    }
+    apps.iter()
+        .map(|app| app.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use crate::test_support::TestEnvGuard;
+    use serial_test::serial;
+
+    fn claude_provider(id: &str, token: &str) -> Provider {
+        Provider::with_id(
+            id.to_string(),
+            id.to_string(),
+            serde_json::json!({ "env": { "ANTHROPIC_AUTH_TOKEN": token } }),
+            None,
+        )
+    }
+
+    #[test]
+    fn upstream_payload_fields_survive_a_round_trip() {
+        let raw =
+            r#"{"providers":{"claude-desktop":"desktop-provider"},"workspace":{"path":"/w"}}"#;
+        let payload: ProfilePayload = serde_json::from_str(raw).expect("parse");
+        assert_eq!(
+            payload.providers.get("claude-desktop").map(String::as_str),
+            Some("desktop-provider")
+        );
+        assert!(payload.mcp_servers.is_none());
+
+        let value = serde_json::to_value(&payload).expect("serialize");
+        assert_eq!(value["workspace"]["path"], "/w");
+        assert!(value.get("mcpServers").is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn apply_switches_back_and_marks_current_profile() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+
+        ProviderService::add(&state, AppType::Claude, claude_provider("work", "sk-work"))
+            .expect("add work");
+        ProviderService::add(
+            &state,
+            AppType::Claude,
+            claude_provider("personal", "sk-personal"),
+        )
+        .expect("add personal");
+        let prompt =
+            PromptService::create_prompt(&state, AppType::Claude, "Work rules", "Be terse.")
+                .expect("create prompt");
+        PromptService::enable_prompt(&state, AppType::Claude, &prompt.id).expect("enable prompt");
+
+        let (work, existed) = ProfileService::save(&state, "Work").expect("save work");
+        assert!(!existed);
+        assert_eq!(work.id, "work");
+        assert_eq!(
+            work.payload.providers.get("claude").map(String::as_str),
+            Some("work")
+        );
+
+        ProviderService::switch(&state, AppType::Claude, "personal").expect("switch");
+        PromptService::disable_prompt(&state, AppType::Claude, &prompt.id).expect("disable");
+
+        let pending = ProfileService::diff_with_current(&state, &work).expect("diff");
+        assert!(pending
+            .iter()
+            .any(|change| change.section == ProfileSection::Provider
+                && change.after.as_deref() == Some("work")));
+        assert!(pending
+            .iter()
+            .any(|change| change.section == ProfileSection::Prompt
+                && change.after.as_deref() == Some(prompt.id.as_str())));
+
+        let applied = ProfileService::apply(&state, "work").expect("apply");
+        assert_eq!(applied, pending);
+        assert_eq!(
+            ProviderService::current(&state, AppType::Claude).expect("current"),
+            "work"
+        );
+        assert!(state.db.get_prompts("claude").expect("prompts")[&prompt.id].enabled);
+        assert_eq!(
+            state.db.get_current_profile_id("claude").expect("marker"),
+            Some("work".to_string())
+        );
+        assert!(ProfileService::diff_with_current(&state, &work)
+            .expect("diff")
+            .is_empty());
+
+        ProfileService::delete(&state, "Work").expect("delete");
+        assert_eq!(
+            state.db.get_current_profile_id("claude").expect("marker"),
+            None
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn apply_refuses_profiles_with_missing_references() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+
+        ProviderService::add(&state, AppType::Claude, claude_provider("work", "sk-work"))
+            .expect("add work");
+        ProviderService::add(
+            &state,
+            AppType::Claude,
+            claude_provider("personal", "sk-personal"),
+        )
+        .expect("add personal");
+        ProfileService::save(&state, "Personal").expect("save");
+
+        let mut profile = ProfileService::get(&state, "personal").expect("get");
+        profile
+            .payload
+            .providers
+            .insert("claude".to_string(), "personal".to_string());
+        profile.payload.prompts = Some(BTreeMap::from([(
+            "claude".to_string(),
+            Some("gone".to_string()),
+        )]));
+        state.db.save_profile(&profile).expect("save edited");
+
+        let err = ProfileService::apply(&state, "personal").expect_err("missing prompt");
+        assert!(err.to_string().contains("prompt claude/gone"));
+        assert_eq!(
+            ProviderService::current(&state, AppType::Claude).expect("current"),
+            "work"
+        );
+        assert_eq!(
+            state.db.get_current_profile_id("claude").expect("marker"),
+            None
+        );
+    }
+}
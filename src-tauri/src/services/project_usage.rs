@@ -0,0 +1,573 @@
+//! Per-project cost attribution.
+//!
+//! Proxy request logs and imported session logs only carry a `session_id`.
+//! The project a session belongs to is known to the session scanners
+//! ([`SessionMeta::project_dir`]), so each attribution cycle:
+//!
+//! 1. reads `proxy_request_logs` rows past a rowid watermark (plus rows parked
+//!    as pending by earlier cycles),
+//! 2. resolves `(app_type, session_id)` to a project through the session scan
+//!    cache, normalizing paths with [`project_scope::normalize_project_path`],
+//! 3. accumulates the row into the sidecar `project_usage_daily` rollup exactly
+//!    once per `request_id`.
+//!
+//! Rows whose session is not visible yet (the CLI may still be writing the
+//! transcript) are retried for [`PENDING_GRACE_SECONDS`] before falling back
+//! to the unknown project. Everything here is best-effort: failures are logged
+//! and never block the proxy or the session usage sync.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::ControlFlow;
+
+use chrono::{Datelike, Days, Local};
+use rusqlite::params;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::local_store::project_usage::{
+    AttributedUsage, PendingAttribution, ProjectUsageFilter, UNKNOWN_PROJECT_KEY,
+};
+use crate::local_store::LocalStore;
+use crate::services::sql_helpers::fresh_input_sql;
+use crate::services::usage_stats::effective_usage_log_filter;
+use crate::session_manager::project_scope::normalize_project_path;
+use crate::session_manager::scan_cache_store::ScanCacheStore;
+use crate::session_manager::{self, SessionMeta};
+
+#[cfg(feature = "cli")]
+pub use crate::local_store::project_usage::{ProjectBreakdownRow, ProjectUsageRow};
+
+const WATERMARK_META_KEY: &str = "project_usage.rowid_watermark";
+
+/// How long an unresolved session id is retried before the row is attributed
+/// to the unknown project.
+pub(crate) const PENDING_GRACE_SECONDS: i64 = 6 * 60 * 60;
+
+/// Request-id claims are kept slightly longer than the main DB keeps raw logs
+/// (30 days), so a row can never be re-read after its claim was pruned.
+const CLAIM_RETAIN_SECONDS: i64 = 35 * 24 * 60 * 60;
+
+const BATCH_SIZE: usize = 2_000;
+/// Bounds one cycle's memory on a first run over a large history; the
+/// watermark lets the next cycle continue where this one stopped.
+const MAX_BATCHES_PER_CYCLE: usize = 25;
+const PENDING_BATCH_SIZE: usize = 5_000;
+
+/// Session apps whose scanners report a project directory.
+const SESSION_APPS: [&str; 6] = session_manager::CACHED_PROVIDERS;
+
+/// Outcome of one attribution cycle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectAttributionResult {
+    pub attributed: usize,
+    pub deferred: usize,
+}
+
+/// Resolved project of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SessionProject {
+    pub key: String,
+    pub display_path: String,
+    pub last_active_at: i64,
+}
+
+/// `(app_type, session_id) -> project` lookup built from session scans.
+#[derive(Debug, Default)]
+pub(crate) struct SessionProjectIndex {
+    sessions: HashMap<(String, String), SessionProject>,
+}
+
+impl SessionProjectIndex {
+    /// Scan the given apps through the session scan cache. Apps that fail to
+    /// scan are skipped; their rows stay pending until the next cycle.
+    pub(crate) fn scan(apps: &BTreeSet<String>) -> Self {
+        let store = match ScanCacheStore::open() {
+            Ok(store) => Some(store),
+            Err(error) => {
+                log::debug!("[PROJECT-USAGE] scan sidecar unavailable: {error}");
+                None
+            }
+        };
+        let mut index = Self::default();
+        for app in apps {
+            if !SESSION_APPS.contains(&app.as_str()) {
+                continue;
+            }
+            let mut on_session = |meta: SessionMeta| {
+                index.insert_meta(&meta);
+                ControlFlow::Continue(())
+            };
+            if let Err(error) = session_manager::stream_sessions_for_provider_cancellable(
+                store.as_ref(),
+                app,
+                false,
+                &mut on_session,
+                &|| false,
+            ) {
+                log::debug!("[PROJECT-USAGE] session scan for {app} stopped: {error:?}");
+            }
+        }
+        index
+    }
+
+    pub(crate) fn insert_meta(&mut self, meta: &SessionMeta) {
+        let Some(raw) = meta.project_dir.as_deref() else {
+            return;
+        };
+        let Some(key) = normalize_project_path(raw) else {
+            return;
+        };
+        self.sessions.insert(
+            (meta.provider_id.clone(), meta.session_id.clone()),
+            SessionProject {
+                key,
+                display_path: raw.trim().to_string(),
+                last_active_at: meta.last_active_at.or(meta.created_at).unwrap_or(0),
+            },
+        );
+    }
+
+    fn lookup(&self, app_type: &str, session_id: &str) -> Option<&SessionProject> {
+        let direct = self
+            .sessions
+            .get(&(app_type.to_string(), session_id.to_string()));
+        if direct.is_some() {
+            return direct;
+        }
+        // Proxy-side Codex session ids carry a `codex_` prefix (see
+        // `proxy::session`); the session files use the bare thread id.
+        session_id
+            .strip_prefix("codex_")
+            .filter(|_| app_type == "codex")
+            .and_then(|bare| self.sessions.get(&(app_type.to_string(), bare.to_string())))
+    }
+}
+
+/// Best-effort wrapper used by the periodic usage sync.
+pub(crate) fn run_project_attribution_best_effort(db: &Database, context: &str) {
+    let store = match LocalStore::open() {
+        Ok(store) => store,
+        Err(error) => {
+            log::warn!("Project usage attribution skipped ({context}): {error}");
+            return;
+        }
+    };
+    match refresh_project_attribution(db, &store) {
+        Ok(result) if result.attributed > 0 => log::debug!(
+            "Project usage attribution ({context}): attributed={}, pending={}",
+            result.attributed,
+            result.deferred
+        ),
+        Ok(_) => {}
+        Err(error) => log::warn!("Project usage attribution failed ({context}): {error}"),
+    }
+}
+
+/// Bring attribution up to date before an on-demand report. Uses the same
+/// cached session scan as the periodic cycle.
+pub(crate) fn refresh_project_attribution(
+    db: &Database,
+    store: &LocalStore,
+) -> Result<ProjectAttributionResult, AppError> {
+    attribute_new_usage(db, store, SessionProjectIndex::scan, now_unix())
+}
+
+/// Attribute every usage row added since the last cycle, plus rows still
+/// pending from earlier cycles. `load_index` is called at most once, with the
+/// set of apps that have session ids to resolve.
+pub(crate) fn attribute_new_usage(
+    db: &Database,
+    store: &LocalStore,
+    load_index: impl FnOnce(&BTreeSet<String>) -> SessionProjectIndex,
+    now: i64,
+) -> Result<ProjectAttributionResult, AppError> {
+    let mut watermark = store
+        .get_meta(WATERMARK_META_KEY)?
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(0);
+    let max_rowid = max_log_rowid(db)?;
+    if max_rowid < watermark {
+        // The main DB was replaced (import/restore); request-id claims still
+        // guard against double counting, so rescanning from scratch is safe.
+        watermark = 0;
+    }
+
+    let pending = store.load_pending_project_attributions(PENDING_BATCH_SIZE)?;
+    let (pending_rows, vanished) = load_rows_by_request_id(db, &pending)?;
+    store.drop_pending_project_attributions(&vanished)?;
+
+    let mut rows = pending_rows;
+    let mut next_watermark = watermark;
+    for _ in 0..MAX_BATCHES_PER_CYCLE {
+        let batch = load_rows_after(db, next_watermark, BATCH_SIZE)?;
+        let Some((last_rowid, _)) = batch.last() else {
+            break;
+        };
+        next_watermark = *last_rowid;
+        let full = batch.len() == BATCH_SIZE;
+        rows.extend(batch);
+        if !full {
+            break;
+        }
+    }
+
+    let apps = rows
+        .iter()
+        .filter(|(_, row)| !row.session_id.is_empty())
+        .map(|(_, row)| row.usage.app_type.clone())
+        .collect::<BTreeSet<_>>();
+    let index = if apps.is_empty() {
+        SessionProjectIndex::default()
+    } else {
+        load_index(&apps)
+    };
+
+    let mut attributed = Vec::new();
+    let mut deferred = Vec::new();
+    let mut directories = HashMap::<String, (String, i64)>::new();
+    for (_, row) in rows {
+        let session_id = row.session_id;
+        let usage = row.usage;
+        if session_id.is_empty() {
+            attributed.push((usage, UNKNOWN_PROJECT_KEY.to_string()));
+            continue;
+        }
+        if let Some(project) = index.lookup(&usage.app_type, &session_id) {
+            let entry = directories
+                .entry(project.key.clone())
+                .or_insert_with(|| (project.display_path.clone(), project.last_active_at));
+            if project.last_active_at > entry.1 {
+                *entry = (project.display_path.clone(), project.last_active_at);
+            }
+            attributed.push((usage, project.key.clone()));
+            continue;
+        }
+        // Grace is measured from the request itself, so a first run over old
+        // history does not park rows whose sessions were long deleted.
+        let first_seen_at = usage.created_at;
+        if now - first_seen_at >= PENDING_GRACE_SECONDS {
+            attributed.push((usage, UNKNOWN_PROJECT_KEY.to_string()));
+        } else {
+            deferred.push(PendingAttribution {
+                request_id: usage.request_id.clone(),
+                app_type: usage.app_type.clone(),
+                session_id,
+                first_seen_at,
+            });
+        }
+    }
+
+    let directories = directories
+        .into_iter()
+        .map(|(key, (display, seen))| (key, display, seen))
+        .collect::<Vec<_>>();
+    store.upsert_project_directories(&directories)?;
+    store.defer_project_attributions(&deferred)?;
+    let recorded = store.record_project_attributions(&attributed)?;
+    store.set_meta(WATERMARK_META_KEY, &next_watermark.to_string())?;
+    store.prune_project_request_claims(now - CLAIM_RETAIN_SECONDS)?;
+
+    Ok(ProjectAttributionResult {
+        attributed: recorded,
+        deferred: deferred.len(),
+    })
+}
+
+struct LogRow {
+    usage: AttributedUsage,
+    session_id: String,
+}
+
+/// `(rowid, row)` pairs read from `proxy_request_logs`.
+type LogRows = Vec<(i64, LogRow)>;
+
+fn max_log_rowid(db: &Database) -> Result<i64, AppError> {
+    let conn = lock_conn!(db.conn);
+    conn.query_row(
+        "SELECT COALESCE(MAX(rowid), 0) FROM proxy_request_logs",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(AppError::from)
+}
+
+fn log_row_select_sql(where_clause: &str) -> String {
+    let fresh_input = fresh_input_sql("l");
+    let effective_filter = effective_usage_log_filter("l");
+    format!(
+        "SELECT l.rowid, l.request_id, l.app_type, l.provider_id, l.model, l.created_at,
+                l.status_code, COALESCE(l.session_id, ''),
+                {fresh_input}, l.output_tokens, l.cache_read_tokens, l.cache_creation_tokens,
+                CAST(l.total_cost_usd AS REAL),
+                date(l.created_at, 'unixepoch', 'localtime')
+         FROM proxy_request_logs l
+         WHERE {where_clause} AND {effective_filter}"
+    )
+}
+
+fn map_log_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<(i64, LogRow)> {
+    let status_code: i64 = row.get(6)?;
+    Ok((
+        row.get(0)?,
+        LogRow {
+            usage: AttributedUsage {
+                request_id: row.get(1)?,
+                app_type: row.get(2)?,
+                provider_id: row.get(3)?,
+                model: row.get(4)?,
+                created_at: row.get(5)?,
+                success: (200..300).contains(&status_code),
+                input_tokens: row.get::<_, i64>(8)?.max(0) as u64,
+                output_tokens: row.get::<_, i64>(9)?.max(0) as u64,
+                cache_read_tokens: row.get::<_, i64>(10)?.max(0) as u64,
+                cache_creation_tokens: row.get::<_, i64>(11)?.max(0) as u64,
+                total_cost_usd: row.get::<_, Option<f64>>(12)?.unwrap_or(0.0),
+                date: row.get(13)?,
+            },
+            session_id: row.get(7)?,
+        },
+    ))
+}
+
+fn load_rows_after(db: &Database, watermark: i64, limit: usize) -> Result<LogRows, AppError> {
+    let conn = lock_conn!(db.conn);
+    let sql = format!(
+        "{} ORDER BY l.rowid ASC LIMIT ?2",
+        log_row_select_sql("l.rowid > ?1")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![watermark, limit as i64], map_log_row)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
+}
+
+/// Reload pending rows from the main DB. Returns the rows that still exist
+/// (and still pass the dedup filter) plus the request ids that vanished.
+fn load_rows_by_request_id(
+    db: &Database,
+    pending: &[PendingAttribution],
+) -> Result<(LogRows, Vec<String>), AppError> {
+    if pending.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let conn = lock_conn!(db.conn);
+    let mut stmt = conn.prepare(&log_row_select_sql("l.request_id = ?1"))?;
+    let mut found = Vec::new();
+    let mut vanished = Vec::new();
+    for item in pending {
+        let mut rows = stmt.query_map([&item.request_id], map_log_row)?;
+        match rows.next() {
+            Some(row) => found.push(row?),
+            None => vanished.push(item.request_id.clone()),
+        }
+    }
+    Ok((found, vanished))
+}
+
+fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Date window presets shared by `cc-switch usage projects` and the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectUsageRange {
+    Today,
+    /// The last N local days, today included.
+    Days(u64),
+    ThisMonth,
+    All,
+}
+
+impl ProjectUsageRange {
+    /// Inclusive local start date (`YYYY-MM-DD`); `None` means unbounded.
+    pub fn start_date(self) -> Option<String> {
+        let today = Local::now().date_naive();
+        let start = match self {
+            Self::Today => Some(today),
+            Self::Days(days) => today.checked_sub_days(Days::new(days.saturating_sub(1))),
+            Self::ThisMonth => today.with_day0(0),
+            Self::All => None,
+        };
+        start.map(|date| date.format("%Y-%m-%d").to_string())
+    }
+}
+
+/// Build a query filter. `start`/`end` are inclusive `YYYY-MM-DD` dates.
+pub(crate) fn project_usage_filter(
+    app_type: Option<&str>,
+    start: Option<String>,
+    end: Option<String>,
+) -> ProjectUsageFilter {
+    ProjectUsageFilter {
+        app_type: app_type.map(str::to_string),
+        start_date: start,
+        end_date: end,
+    }
+}
+
+/// Resolve a user-supplied project path (absolute, relative, or `.`) to the
+/// project key used by the attribution tables.
+pub fn resolve_project_key(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let path = std::path::Path::new(trimmed);
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let absolute = std::fs::canonicalize(&absolute).unwrap_or(absolute);
+    normalize_project_path(&absolute.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rusqlite::params;
+
+    use super::{attribute_new_usage, SessionProjectIndex, PENDING_GRACE_SECONDS};
+    use crate::database::Database;
+    use crate::local_store::project_usage::ProjectUsageFilter;
+    use crate::local_store::LocalStore;
+    use crate::session_manager::SessionMeta;
+
+    const NOW: i64 = 1_760_000_000;
+
+    fn insert_log(db: &Database, request_id: &str, app: &str, session: Option<&str>, cost: &str) {
+        let conn = db.conn.lock().expect("lock db");
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model,
+                input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                input_cost_usd, output_cost_usd, cache_read_cost_usd, cache_creation_cost_usd,
+                total_cost_usd, latency_ms, status_code, session_id, created_at
+            ) VALUES (?1, 'relay', ?2, 'model-x', 100, 10, 0, 0, '0', '0', '0', '0', ?3, 10, 200, ?4, ?5)",
+            params![request_id, app, cost, session, NOW - 60],
+        )
+        .expect("insert log");
+    }
+
+    fn meta(provider: &str, session: &str, dir: &str) -> SessionMeta {
+        SessionMeta {
+            provider_id: provider.to_string(),
+            session_id: session.to_string(),
+            project_dir: Some(dir.to_string()),
+            last_active_at: Some(NOW),
+            ..Default::default()
+        }
+    }
+
+    fn index(metas: &[SessionMeta]) -> SessionProjectIndex {
+        let mut index = SessionProjectIndex::default();
+        for meta in metas {
+            index.insert_meta(meta);
+        }
+        index
+    }
+
+    #[test]
+    fn attributes_rows_to_session_projects_once() {
+        let db = Database::memory().expect("db");
+        let store = LocalStore::in_memory().expect("store");
+        insert_log(&db, "r1", "claude", Some("s-claude"), "0.5");
+        insert_log(&db, "r2", "codex", Some("codex_thread-1"), "1.5");
+        insert_log(&db, "r3", "claude", None, "0.1");
+
+        let mut requested = BTreeSet::new();
+        let result = attribute_new_usage(
+            &db,
+            &store,
+            |apps| {
+                requested = apps.clone();
+                index(&[
+                    meta("claude", "s-claude", "/work/alpha/"),
+                    meta("codex", "thread-1", "/work/alpha"),
+                ])
+            },
+            NOW,
+        )
+        .expect("attribute");
+        assert_eq!(result.attributed, 3);
+        assert_eq!(result.deferred, 0);
+        assert_eq!(
+            requested.into_iter().collect::<Vec<_>>(),
+            vec!["claude".to_string(), "codex".to_string()]
+        );
+
+        let rows = store
+            .project_usage_rows(&ProjectUsageFilter::default(), None)
+            .expect("rows");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].display_path.as_deref(), Some("/work/alpha/"));
+        assert_eq!(rows[0].request_count, 2);
+        assert!((rows[0].total_cost_usd - 2.0).abs() < 1e-9);
+        assert!(rows[1].is_unknown());
+
+        // A second cycle sees no new rows and does not rescan sessions.
+        let again = attribute_new_usage(&db, &store, |_| panic!("no scan needed"), NOW)
+            .expect("second cycle");
+        assert_eq!(again.attributed, 0);
+    }
+
+    #[test]
+    fn unresolved_sessions_stay_pending_until_grace_expires() {
+        let db = Database::memory().expect("db");
+        let store = LocalStore::in_memory().expect("store");
+        insert_log(&db, "r1", "claude", Some("late-session"), "1.0");
+
+        let first = attribute_new_usage(&db, &store, |_| SessionProjectIndex::default(), NOW)
+            .expect("first");
+        assert_eq!(first.deferred, 1);
+        assert!(store
+            .project_usage_rows(&ProjectUsageFilter::default(), None)
+            .expect("rows")
+            .is_empty());
+
+        let resolved = attribute_new_usage(
+            &db,
+            &store,
+            |_| index(&[meta("claude", "late-session", "/work/beta")]),
+            NOW + 60,
+        )
+        .expect("resolved");
+        assert_eq!(resolved.attributed, 1);
+        assert_eq!(resolved.deferred, 0);
+
+        insert_log(&db, "r2", "claude", Some("never-seen"), "2.0");
+        attribute_new_usage(&db, &store, |_| SessionProjectIndex::default(), NOW).expect("park");
+        let expired = attribute_new_usage(
+            &db,
+            &store,
+            |_| SessionProjectIndex::default(),
+            NOW + PENDING_GRACE_SECONDS + 1,
+        )
+        .expect("expire");
+        assert_eq!(expired.attributed, 1);
+        let rows = store
+            .project_usage_rows(&ProjectUsageFilter::default(), None)
+            .expect("rows");
+        assert!(rows
+            .iter()
+            .any(|row| row.is_unknown() && row.request_count == 1));
+    }
+
+    #[test]
+    fn pending_rows_deleted_from_main_db_are_dropped() {
+        let db = Database::memory().expect("db");
+        let store = LocalStore::in_memory().expect("store");
+        insert_log(&db, "r1", "claude", Some("gone"), "1.0");
+        attribute_new_usage(&db, &store, |_| SessionProjectIndex::default(), NOW).expect("park");
+        {
+            let conn = db.conn.lock().expect("lock db");
+            conn.execute("DELETE FROM proxy_request_logs", [])
+                .expect("delete");
+        }
+        attribute_new_usage(&db, &store, |_| SessionProjectIndex::default(), NOW).expect("drop");
+        assert!(store
+            .load_pending_project_attributions(10)
+            .expect("pending")
+            .is_empty());
+    }
+}
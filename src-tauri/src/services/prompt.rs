@@ -5,6 +5,7 @@ use crate::config::write_text_file;
 use crate::error::AppError;
 use crate::prompt::Prompt;
 use crate::prompt_files::prompt_file_path;
+use crate::services::config_history::{self, HistoryAction, HistoryKind};
 use crate::store::AppState;
 
 fn get_unix_timestamp() -> Result<i64, AppError> {
@@ -14,6 +15,18 @@ fn get_unix_timestamp() -> Result<i64, AppError> {
         .map_err(|e| AppError::Message(format!("Failed to get system time: {e}")))
 }
 
+/// 记录提示词的历史版本（失败只记日志）
+fn record_history(state: &AppState, app: &AppType, prompt: &Prompt, action: HistoryAction) {
+    config_history::record_best_effort(
+        &state.db,
+        HistoryKind::Prompt,
+        Some(app),
+        &prompt.id,
+        action,
+        prompt,
+    );
+}
+
 pub struct PromptService;
 
 impl PromptService {
@@ -83,8 +96,19 @@ impl PromptService {
         prompt: Prompt,
     ) -> Result<(), AppError> {
         let is_enabled = prompt.enabled;
+        let existed = state.db.get_prompts(app.as_str())?.contains_key(&prompt.id);
 
         state.db.save_prompt(app.as_str(), &prompt)?;
+        record_history(
+            state,
+            &app,
+            &prompt,
+            if existed {
+                HistoryAction::Update
+            } else {
+                HistoryAction::Add
+            },
+        );
 
         if is_enabled {
             let target_path = prompt_file_path(&app)?;
@@ -104,6 +128,9 @@ impl PromptService {
         }
 
         state.db.delete_prompt(app.as_str(), id)?;
+        if let Some(prompt) = prompts.get(id) {
+            record_history(state, &app, prompt, HistoryAction::Delete);
+        }
         Ok(())
     }
 
@@ -173,6 +200,10 @@ impl PromptService {
         state.db.save_prompt(app.as_str(), &prompt)?;
         if old_prompt_id != prompt.id {
             state.db.delete_prompt(app.as_str(), &old_prompt_id)?;
+            record_history(state, &app, existing, HistoryAction::Delete);
+            record_history(state, &app, &prompt, HistoryAction::Add);
+        } else {
+            record_history(state, &app, &prompt, HistoryAction::Update);
         }
 
         Ok(prompt)
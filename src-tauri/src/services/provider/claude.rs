@@ -147,6 +147,7 @@ impl ProviderService {
 
         let mut live = read_json_file::<Value>(&settings_path)?;
         let _ = Self::normalize_claude_models_in_value(&mut live);
+        crate::services::agent_settings::strip_projected(&AppType::Claude, &mut live);
         live = common_config::strip_common_config_from_live_settings(
             &AppType::Claude,
             &current_provider,
@@ -155,7 +156,8 @@ impl ProviderService {
         );
         if let Some(manager) = config.get_manager_mut(&AppType::Claude) {
             if let Some(current) = manager.providers.get_mut(current_id) {
-                current.settings_config = live;
+                current.settings_config =
+                    crate::key_pool::restore_stored(&AppType::Claude, current, live);
             }
         }
 
@@ -191,6 +193,7 @@ impl ProviderService {
         common_config_snippet: Option<&str>,
         apply_common_config: bool,
     ) -> Result<(), AppError> {
+        let provider = &crate::key_pool::resolve_for_live(&AppType::Claude, provider)?;
         let prepared = Self::prepare_claude_live_write(
             provider,
             common_config_snippet,
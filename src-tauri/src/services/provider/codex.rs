@@ -88,6 +88,8 @@ impl ProviderService {
 
         // Remove provider-specific fields.
         let root = doc.as_table_mut();
+        // Profiles materialized by cc-switch are regenerated, never shared.
+        codex_profiles::strip_managed(root);
         root.remove("model");
         root.remove("model_provider");
         // Legacy/alt formats might use a top-level base_url.
@@ -198,6 +200,8 @@ impl ProviderService {
             .map_err(|e| AppError::Config(format!("TOML parse error: {e}")))?;
         let root = doc.as_table_mut();
         root.remove("mcp_servers");
+        // 托管的 profile 只属于 live 文件，不进入供应商快照
+        codex_profiles::strip_managed(root);
 
         if let Some(mcp_item) = root.get_mut("mcp") {
             if let Some(mcp_table) = mcp_item.as_table_like_mut() {
@@ -443,7 +447,10 @@ impl ProviderService {
             if let Some(auth) = capture_auth {
                 raw_settings.insert("auth".to_string(), auth);
             }
-            raw_settings.insert("config".to_string(), Value::String(text));
+            raw_settings.insert(
+                "config".to_string(),
+                Value::String(codex_profiles::strip_managed_text(&text)?),
+            );
             let mut settings_for_storage = Value::Object(raw_settings);
             crate::codex_config::strip_codex_mcp_servers_from_settings(&mut settings_for_storage)?;
             if is_official {
@@ -477,6 +484,11 @@ impl ProviderService {
         };
         if let Some(manager) = config.get_manager_mut(&AppType::Codex) {
             if let Some(current) = manager.providers.get_mut(current_id) {
+                snapshot_provider.settings_config = crate::key_pool::restore_stored(
+                    &AppType::Codex,
+                    current,
+                    snapshot_provider.settings_config,
+                );
                 *current = snapshot_provider;
             }
         }
@@ -494,6 +506,7 @@ impl ProviderService {
         common_config_snippet: Option<&str>,
         apply_common_config: bool,
     ) -> Result<(), AppError> {
+        let provider = &crate::key_pool::resolve_for_live(&AppType::Codex, provider)?;
         let prepared = Self::prepare_codex_live_write(
             provider,
             common_config_snippet,
@@ -626,17 +639,24 @@ impl ProviderService {
         };
 
         crate::codex_config::write_prepared_codex_model_catalog(config)?;
+        // 整体重写前带入旧文件中的托管 profile，保留用户对它们的修改
+        let previous = crate::codex_config::read_codex_config_text().unwrap_or_default();
+        let config_text = codex_profiles::carry_over_managed(&previous, &config.config_text)
+            .unwrap_or_else(|err| {
+                log::warn!("带入 Codex 托管 profile 失败: {err}");
+                config.config_text.clone()
+            });
         match auth {
             PreparedCodexAuthWrite::Preserve => {
-                crate::codex_config::write_codex_live_config_atomic(Some(&config.config_text))?
+                crate::codex_config::write_codex_live_config_atomic(Some(&config_text))?
             }
             PreparedCodexAuthWrite::Write(auth) => {
-                crate::codex_config::write_codex_live_atomic(auth, Some(&config.config_text))?
+                crate::codex_config::write_codex_live_atomic(auth, Some(&config_text))?
             }
             PreparedCodexAuthWrite::Delete => {
                 crate::codex_config::write_codex_live_atomic_optional_auth(
                     None,
-                    Some(&config.config_text),
+                    Some(&config_text),
                 )?
             }
         }
@@ -0,0 +1,723 @@
+//! 将全部 Codex 供应商写成 `config.toml` 中的命名 profile（可选功能）。
+//!
+//! 每个带 Base URL 的供应商对应 `[model_providers.cc-switch-<id>]` 与
+//! `[profiles.<id>]`，用户可以用 `codex --profile <id>` 按次选择供应商。
+//! 托管条目以 `cc-switch-` 前缀识别：切换供应商整体重写 config.toml 时从旧文件带入，
+//! 回填快照和提取通用配置时剔除。同步以上次写入的托管内容为 base 调用
+//! [`merge_toml_with_base_live`]，用户对托管条目的手动修改只要 cc-switch 未改动就会保留。
+//!
+//! 以引用（`env:` / `file:` / `cmd:` / `vault:`）保存的 Key 不会解析进 config.toml：
+//! `env:` 引用写成 `env_key`，其余引用写成占位环境变量 `CC_SWITCH_<ID>_API_KEY`
+//! 的 `env_key`。保存到 sidecar 的 base 只记录内联 Key 的摘要。
+
+use serde_json::Value;
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use super::live_merge::merge_toml_with_base_live;
+use super::ProviderService;
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::local_store::LocalStore;
+use crate::provider::Provider;
+use crate::secret_ref::SecretRef;
+use crate::store::AppState;
+
+/// 托管 model_provider 的名称前缀
+pub(crate) const MANAGED_MODEL_PROVIDER_PREFIX: &str = "cc-switch-";
+
+/// 上次写入的托管内容，作为下次合并的 base
+const BASE_META_KEY: &str = "codex_profiles_base";
+
+/// base 中内联 Key 的摘要前缀
+const TOKEN_DIGEST_PREFIX: &str = "sha256:";
+
+/// 从供应商配置根级复制到 profile 的键
+const PROFILE_KEYS: [&str; 4] = [
+    "model",
+    "model_reasoning_effort",
+    "model_reasoning_summary",
+    "model_verbosity",
+];
+
+/// 不复制到托管 model_provider 的键：鉴权由 env_key / experimental_bearer_token 重新生成
+const DROPPED_MODEL_PROVIDER_KEYS: [&str; 3] = [
+    "requires_openai_auth",
+    "experimental_bearer_token",
+    "env_key",
+];
+
+pub(crate) fn is_managed_model_provider(name: &str) -> bool {
+    name.starts_with(MANAGED_MODEL_PROVIDER_PREFIX)
+}
+
+fn is_managed_profile(profile: &Item) -> bool {
+    profile
+        .get("model_provider")
+        .and_then(Item::as_str)
+        .is_some_and(is_managed_model_provider)
+}
+
+fn parse_doc(text: &str) -> Result<DocumentMut, AppError> {
+    text.trim()
+        .parse::<DocumentMut>()
+        .map_err(|e| AppError::Config(format!("TOML parse error: {e}")))
+}
+
+/// 复制表内容但不带原文档中的位置信息，插入新文档后排在末尾
+fn detached(table: &Table) -> Table {
+    let mut copy = Table::new();
+    for (key, item) in table.iter() {
+        let item = match item.as_table() {
+            Some(child) => Item::Table(detached(child)),
+            None => item.clone(),
+        };
+        copy.insert(key, item);
+    }
+    copy
+}
+
+/// 由单个供应商生成 (profile, model_provider)；没有 Base URL 的供应商（如官方登录）跳过
+fn managed_entries(provider: &Provider) -> Option<(Table, Table)> {
+    let config = provider
+        .settings_config
+        .get("config")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let doc = match parse_doc(config) {
+        Ok(doc) => doc,
+        Err(err) => {
+            log::warn!("跳过 Codex 供应商 {} 的 profile: {err}", provider.id);
+            return None;
+        }
+    };
+
+    // 与 extract_codex_base_url 一致：有 model_provider 时只看对应的表，否则接受旧式平铺配置
+    let source = match doc.get("model_provider").and_then(Item::as_str) {
+        Some(active) => doc
+            .get("model_providers")
+            .and_then(|providers| providers.get(active))
+            .and_then(Item::as_table)
+            .map(detached)?,
+        None => {
+            let mut flat = Table::new();
+            for key in ["base_url", "wire_api"] {
+                if let Some(item) = doc.get(key) {
+                    flat.insert(key, item.clone());
+                }
+            }
+            flat
+        }
+    };
+    source
+        .get("base_url")
+        .and_then(Item::as_str)
+        .filter(|url| !url.trim().is_empty())?;
+
+    let mut model_provider = Table::new();
+    if source.get("name").is_none() {
+        model_provider.insert("name", value(provider.name.as_str()));
+    }
+    for (key, item) in source.iter() {
+        if !DROPPED_MODEL_PROVIDER_KEYS.contains(&key) {
+            model_provider.insert(key, item.clone());
+        }
+    }
+    if let Some(key) = provider.configured_api_key(&AppType::Codex) {
+        match SecretRef::parse(&key) {
+            // 环境变量引用交给 Codex 自己读取，不写入明文
+            Some(SecretRef::Env(var)) => {
+                model_provider.insert("env_key", value(var));
+            }
+            // 其余引用不在这里解析，避免明文落盘和每次同步都执行命令
+            Some(_) => {
+                model_provider.insert("env_key", value(key_env_var(&provider.id)));
+            }
+            None => {
+                model_provider.insert("experimental_bearer_token", value(key));
+            }
+        }
+    }
+
+    let mut profile = Table::new();
+    for key in PROFILE_KEYS {
+        if let Some(item) = doc.get(key).and_then(Item::as_value) {
+            let mut item = item.clone();
+            item.decor_mut().clear();
+            profile.insert(key, Item::Value(item));
+        }
+    }
+    Some((profile, model_provider))
+}
+
+/// 引用型 Key 的占位环境变量名，由用户在运行 Codex 前导出
+pub(crate) fn key_env_var(provider_id: &str) -> String {
+    let id: String = provider_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("CC_SWITCH_{id}_API_KEY")
+}
+
+/// 对每个托管 model_provider 的内联 Key 调用 `f`，返回值替换原值
+fn map_managed_tokens(doc: &mut DocumentMut, mut f: impl FnMut(&str, &str) -> Option<String>) {
+    let Some(providers) = doc
+        .get_mut("model_providers")
+        .and_then(Item::as_table_like_mut)
+    else {
+        return;
+    };
+    for (name, item) in providers.iter_mut() {
+        if !is_managed_model_provider(name.get()) {
+            continue;
+        }
+        let Some(entry) = item.as_table_like_mut() else {
+            continue;
+        };
+        let Some(token) = entry
+            .get("experimental_bearer_token")
+            .and_then(Item::as_str)
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if let Some(replacement) = f(name.get(), &token) {
+            entry.insert("experimental_bearer_token", value(replacement));
+        }
+    }
+}
+
+fn token_digest(token: &str) -> String {
+    format!(
+        "{TOKEN_DIGEST_PREFIX}{}",
+        crate::services::sync_protocol::sha256_hex(token.as_bytes())
+    )
+}
+
+/// 落盘用的 base：内联 Key 只保留摘要
+pub(crate) fn redact_base(text: &str) -> Result<String, AppError> {
+    if !text.contains("experimental_bearer_token") {
+        return Ok(text.to_string());
+    }
+    let mut doc = parse_doc(text)?;
+    map_managed_tokens(&mut doc, |_, token| Some(token_digest(token)));
+    Ok(doc.to_string())
+}
+
+/// 把 base 中的摘要换回 live 或本次写入内容里摘要相同的 Key，
+/// 使三方合并看到的 base 与上次写入的内容一致
+fn restore_base(base: &str, sources: &[&str]) -> Result<String, AppError> {
+    if !base.contains(TOKEN_DIGEST_PREFIX) {
+        return Ok(base.to_string());
+    }
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    for source in sources {
+        let Ok(mut doc) = parse_doc(source) else {
+            continue;
+        };
+        map_managed_tokens(&mut doc, |name, token| {
+            candidates.push((name.to_string(), token.to_string()));
+            None
+        });
+    }
+    let mut doc = parse_doc(base)?;
+    map_managed_tokens(&mut doc, |name, digest| {
+        candidates
+            .iter()
+            .find(|(candidate, token)| candidate == name && token_digest(token) == digest)
+            .map(|(_, token)| token.clone())
+    });
+    Ok(doc.to_string())
+}
+
+/// 生成托管部分：只包含 `profiles` 与 `model_providers` 两张表
+pub(crate) fn render_managed(providers: &[Provider], exclude: &[String]) -> String {
+    let mut profiles = Table::new();
+    profiles.set_implicit(true);
+    let mut model_providers = Table::new();
+    model_providers.set_implicit(true);
+
+    for provider in providers {
+        if exclude.iter().any(|id| id == &provider.id) {
+            continue;
+        }
+        let Some((entries, model_provider)) = managed_entries(provider) else {
+            continue;
+        };
+        let name = format!("{MANAGED_MODEL_PROVIDER_PREFIX}{}", provider.id);
+        let mut profile = Table::new();
+        profile.insert("model_provider", value(name.as_str()));
+        for (key, item) in entries.iter() {
+            profile.insert(key, item.clone());
+        }
+        profiles.insert(&provider.id, Item::Table(profile));
+        model_providers.insert(&name, Item::Table(model_provider));
+    }
+
+    let mut doc = DocumentMut::new();
+    doc.insert("profiles", Item::Table(profiles));
+    doc.insert("model_providers", Item::Table(model_providers));
+    doc.to_string()
+}
+
+/// 用户手写的同名 profile 优先：从托管内容中去掉这些供应商
+fn drop_taken_names(doc: &mut DocumentMut, taken: &[String]) {
+    for name in taken {
+        if let Some(profiles) = doc.get_mut("profiles").and_then(Item::as_table_like_mut) {
+            profiles.remove(name);
+        }
+        if let Some(providers) = doc
+            .get_mut("model_providers")
+            .and_then(Item::as_table_like_mut)
+        {
+            providers.remove(&format!("{MANAGED_MODEL_PROVIDER_PREFIX}{name}"));
+        }
+    }
+}
+
+fn max_table_position(table: &Table) -> usize {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .map(|child| child.position().unwrap_or(0).max(max_table_position(child)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// 托管条目统一排到文件末尾，空的 `profiles` / `model_providers` 表一并移除
+fn tidy_managed(doc: &mut DocumentMut) {
+    let mut next = max_table_position(doc.as_table()) + 1;
+    for key in ["profiles", "model_providers"] {
+        let Some(table) = doc.get_mut(key).and_then(Item::as_table_mut) else {
+            continue;
+        };
+        table.set_implicit(true);
+        for (name, item) in table.iter_mut() {
+            let managed = if key == "profiles" {
+                is_managed_profile(item)
+            } else {
+                is_managed_model_provider(name.get())
+            };
+            if let Some(entry) = item.as_table_mut().filter(|_| managed) {
+                entry.set_position(next);
+                entry.decor_mut().set_prefix("\n");
+                next += 1;
+            }
+        }
+        if table.is_empty() {
+            doc.remove(key);
+        }
+    }
+}
+
+/// 用户手写的 profile 名；同名的供应商不生成托管 profile
+fn hand_written_profile_names(doc: &DocumentMut) -> Vec<String> {
+    doc.get("profiles")
+        .and_then(Item::as_table_like)
+        .map(|profiles| {
+            profiles
+                .iter()
+                .filter(|(_, profile)| !is_managed_profile(profile))
+                .map(|(name, _)| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 把托管内容按 base 合并进 live 文本
+pub(crate) fn merge_managed(live: &str, base: &str, incoming: &str) -> Result<String, AppError> {
+    let taken = hand_written_profile_names(&parse_doc(live)?);
+    let mut base_doc = parse_doc(base)?;
+    let mut incoming_doc = parse_doc(incoming)?;
+    for doc in [&mut base_doc, &mut incoming_doc] {
+        drop_taken_names(doc, &taken);
+        // 空表也要写出表头，否则合并会把整张 profiles 表当作被删除
+        for key in ["profiles", "model_providers"] {
+            match doc.get_mut(key).and_then(Item::as_table_mut) {
+                Some(table) => table.set_implicit(false),
+                None => {
+                    doc.insert(key, Item::Table(Table::new()));
+                }
+            }
+        }
+    }
+
+    let merged = merge_toml_with_base_live(
+        &AppType::Codex,
+        "config.toml",
+        live,
+        &base_doc.to_string(),
+        &incoming_doc.to_string(),
+    )?;
+    let mut merged = parse_doc(&merged)?;
+    tidy_managed(&mut merged);
+    Ok(merged.to_string())
+}
+
+/// 切换供应商会整体重写 config.toml：把旧文件中的托管条目带入新文本
+pub(crate) fn carry_over_managed(previous: &str, next: &str) -> Result<String, AppError> {
+    if !previous.contains(MANAGED_MODEL_PROVIDER_PREFIX) {
+        return Ok(next.to_string());
+    }
+    let previous_doc = parse_doc(previous)?;
+    let mut next_doc = parse_doc(next)?;
+    let mut carried = false;
+    for key in ["profiles", "model_providers"] {
+        let Some(source) = previous_doc.get(key).and_then(Item::as_table) else {
+            continue;
+        };
+        for (name, item) in source.iter() {
+            let managed = if key == "profiles" {
+                is_managed_profile(item)
+            } else {
+                is_managed_model_provider(name)
+            };
+            let Some(entry) = item.as_table().filter(|_| managed) else {
+                continue;
+            };
+            let target = next_doc.entry(key).or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            });
+            let Some(target) = target.as_table_mut() else {
+                continue;
+            };
+            if !target.contains_key(name) {
+                target.insert(name, Item::Table(detached(entry)));
+                carried = true;
+            }
+        }
+    }
+    if !carried {
+        return Ok(next.to_string());
+    }
+    tidy_managed(&mut next_doc);
+    Ok(next_doc.to_string())
+}
+
+/// 移除托管条目；快照与通用配置片段不保存它们
+pub(crate) fn strip_managed(root: &mut Table) {
+    for key in ["profiles", "model_providers"] {
+        let Some(table) = root.get_mut(key).and_then(Item::as_table_like_mut) else {
+            continue;
+        };
+        let managed: Vec<String> = table
+            .iter()
+            .filter(|(name, item)| {
+                if key == "profiles" {
+                    is_managed_profile(item)
+                } else {
+                    is_managed_model_provider(name)
+                }
+            })
+            .map(|(name, _)| name.to_string())
+            .collect();
+        if managed.is_empty() {
+            continue;
+        }
+        for name in &managed {
+            table.remove(name);
+        }
+        if table.is_empty() {
+            root.remove(key);
+        }
+    }
+}
+
+/// 文本形式的 [`strip_managed`]，没有托管条目时原样返回
+pub(crate) fn strip_managed_text(text: &str) -> Result<String, AppError> {
+    if !text.contains(MANAGED_MODEL_PROVIDER_PREFIX) {
+        return Ok(text.to_string());
+    }
+    let mut doc = parse_doc(text)?;
+    strip_managed(doc.as_table_mut());
+    Ok(doc.to_string())
+}
+
+/// live 文本中的托管 profile 名
+pub(crate) fn managed_profile_names(text: &str) -> Vec<String> {
+    let Ok(doc) = parse_doc(text) else {
+        return Vec::new();
+    };
+    doc.get("profiles")
+        .and_then(Item::as_table_like)
+        .map(|profiles| {
+            profiles
+                .iter()
+                .filter(|(_, profile)| is_managed_profile(profile))
+                .map(|(name, _)| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl ProviderService {
+    /// 按设置重写 config.toml 中的托管 profile，返回当前托管的 profile 名；
+    /// 关闭后首次同步会移除之前写入的条目
+    pub fn sync_codex_profiles(state: &AppState) -> Result<Vec<String>, AppError> {
+        let settings = crate::settings::get_codex_profiles_settings();
+        let base = if LocalStore::path()?.exists() {
+            LocalStore::open()?
+                .get_meta(BASE_META_KEY)?
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+        if !settings.enabled && base.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        if !crate::sync_policy::should_sync_live(&AppType::Codex) {
+            return Ok(Vec::new());
+        }
+
+        let live = crate::codex_config::read_and_validate_codex_config_text()?;
+        let incoming = if settings.enabled {
+            let providers: Vec<Provider> =
+                Self::list(state, AppType::Codex)?.into_values().collect();
+            let mut exclude = settings.exclude.clone();
+            exclude.extend(hand_written_profile_names(&parse_doc(&live)?));
+            render_managed(&providers, &exclude)
+        } else {
+            String::new()
+        };
+
+        let base = restore_base(&base, &[&live, &incoming])?;
+        let merged = merge_managed(&live, &base, &incoming)?;
+        if merged.trim() != live.trim() {
+            crate::codex_config::write_codex_live_config_atomic(Some(&merged))?;
+        }
+        LocalStore::open()?.set_meta(BASE_META_KEY, &redact_base(&incoming)?)?;
+        Ok(managed_profile_names(&merged))
+    }
+
+    /// 供应商增删改或切换后同步托管 profile（失败只记日志）
+    pub(super) fn sync_codex_profiles_best_effort(state: &AppState, app_type: &AppType) {
+        if !matches!(app_type, AppType::Codex) {
+            return;
+        }
+        if let Err(err) = Self::sync_codex_profiles(state) {
+            log::warn!("同步 Codex profiles 失败: {err}");
+        }
+    }
+
+    /// live config.toml 中由 cc-switch 托管的 profile 名
+    pub fn list_codex_profiles() -> Result<Vec<String>, AppError> {
+        let live = crate::codex_config::read_codex_config_text()?;
+        Ok(managed_profile_names(&live))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codex_provider(id: &str, key: &str, base_url: &str) -> Provider {
+        Provider::with_id(
+            id.to_string(),
+            id.to_uppercase(),
+            json!({
+                "auth": { "OPENAI_API_KEY": key },
+                "config": format!(
+                    "model_provider = \"custom\"\nmodel = \"gpt-5\"\nmodel_reasoning_effort = \"high\"\n\n[model_providers.custom]\nname = \"{id}\"\nbase_url = \"{base_url}\"\nwire_api = \"responses\"\nrequires_openai_auth = true\n"
+                ),
+            }),
+            None,
+        )
+    }
+
+    #[test]
+    fn render_writes_profile_and_model_provider_per_provider() {
+        let official = Provider::with_id(
+            "official".to_string(),
+            "OpenAI".to_string(),
+            json!({ "auth": {}, "config": "model = \"gpt-5\"\n" }),
+            None,
+        );
+        let rendered = render_managed(
+            &[
+                codex_provider("relay", "sk-relay", "https://relay.example/v1"),
+                codex_provider("envy", "env:ENVY_KEY", "https://envy.example/v1"),
+                codex_provider("skipped", "sk-skip", "https://skip.example/v1"),
+                official,
+            ],
+            &["skipped".to_string()],
+        );
+        let doc: toml::Table = toml::from_str(&rendered).expect("valid toml");
+
+        assert_eq!(
+            doc["profiles"]["relay"]["model_provider"].as_str(),
+            Some("cc-switch-relay")
+        );
+        assert_eq!(doc["profiles"]["relay"]["model"].as_str(), Some("gpt-5"));
+        assert_eq!(
+            doc["profiles"]["relay"]["model_reasoning_effort"].as_str(),
+            Some("high")
+        );
+        let relay = &doc["model_providers"]["cc-switch-relay"];
+        assert_eq!(relay["base_url"].as_str(), Some("https://relay.example/v1"));
+        assert_eq!(
+            relay["experimental_bearer_token"].as_str(),
+            Some("sk-relay")
+        );
+        assert!(relay.get("requires_openai_auth").is_none());
+        assert_eq!(
+            doc["model_providers"]["cc-switch-envy"]["env_key"].as_str(),
+            Some("ENVY_KEY")
+        );
+        assert!(doc["profiles"].get("skipped").is_none());
+        assert!(doc["profiles"].get("official").is_none());
+    }
+
+    #[test]
+    fn merge_keeps_user_edits_and_hand_written_profiles() {
+        let first = render_managed(
+            &[codex_provider("relay", "sk-1", "https://relay.example/v1")],
+            &[],
+        );
+        let live = "model = \"o3\"\n\n[profiles.mine]\nmodel_provider = \"openai\"\n";
+        let written = merge_managed(live, "", &first).expect("first merge");
+        assert!(written.starts_with("model = \"o3\""));
+        assert_eq!(managed_profile_names(&written), vec!["relay".to_string()]);
+
+        // 用户修改托管 profile 的推理强度，cc-switch 随后更新了 Key
+        let edited = written.replace(
+            "model_reasoning_effort = \"high\"",
+            "model_reasoning_effort = \"low\"",
+        );
+        let second = render_managed(
+            &[codex_provider("relay", "sk-2", "https://relay.example/v1")],
+            &[],
+        );
+        let merged = merge_managed(&edited, &first, &second).expect("second merge");
+        let doc: toml::Table = toml::from_str(&merged).expect("valid toml");
+        assert_eq!(
+            doc["profiles"]["relay"]["model_reasoning_effort"].as_str(),
+            Some("low")
+        );
+        assert_eq!(
+            doc["model_providers"]["cc-switch-relay"]["experimental_bearer_token"].as_str(),
+            Some("sk-2")
+        );
+        assert_eq!(
+            doc["profiles"]["mine"]["model_provider"].as_str(),
+            Some("openai")
+        );
+
+        // 关闭后移除托管条目，手写 profile 保留
+        let removed = merge_managed(&merged, &second, &render_managed(&[], &[])).expect("removal");
+        let doc: toml::Table = toml::from_str(&removed).expect("valid toml");
+        assert!(doc.get("model_providers").is_none());
+        assert_eq!(doc["profiles"].as_table().map(|t| t.len()), Some(1));
+    }
+
+    #[test]
+    fn reference_keys_become_env_keys_and_base_keeps_only_digests() {
+        let rendered = render_managed(
+            &[
+                codex_provider("filed", "file:/run/keys/relay", "https://filed.example/v1"),
+                codex_provider("cmd.relay", "cmd:pass show relay", "https://cmd.example/v1"),
+            ],
+            &[],
+        );
+        let doc: toml::Table = toml::from_str(&rendered).expect("valid toml");
+        let filed = &doc["model_providers"]["cc-switch-filed"];
+        assert_eq!(filed["env_key"].as_str(), Some("CC_SWITCH_FILED_API_KEY"));
+        assert!(filed.get("experimental_bearer_token").is_none());
+        assert_eq!(
+            doc["model_providers"]["cc-switch-cmd.relay"]["env_key"].as_str(),
+            Some("CC_SWITCH_CMD_RELAY_API_KEY")
+        );
+
+        let first = render_managed(
+            &[codex_provider(
+                "relay",
+                "sk-old",
+                "https://relay.example/v1",
+            )],
+            &[],
+        );
+        let base = redact_base(&first).expect("redact");
+        assert!(!base.contains("sk-old"));
+        let live = merge_managed("", "", &first).expect("first merge");
+
+        // 用户改了推理强度，cc-switch 随后轮换了 Key：用摘要还原的 base 合并结果不变
+        let edited = live.replace(
+            "model_reasoning_effort = \"high\"",
+            "model_reasoning_effort = \"low\"",
+        );
+        let second = render_managed(
+            &[codex_provider(
+                "relay",
+                "sk-new",
+                "https://relay.example/v1",
+            )],
+            &[],
+        );
+        let restored = restore_base(&base, &[&edited, &second]).expect("restore");
+        assert_eq!(restored, first);
+        let merged = merge_managed(&edited, &restored, &second).expect("merge");
+        let doc: toml::Table = toml::from_str(&merged).expect("valid toml");
+        assert_eq!(
+            doc["model_providers"]["cc-switch-relay"]["experimental_bearer_token"].as_str(),
+            Some("sk-new")
+        );
+        assert_eq!(
+            doc["profiles"]["relay"]["model_reasoning_effort"].as_str(),
+            Some("low")
+        );
+    }
+
+    #[test]
+    fn merge_never_overwrites_hand_written_profile_with_same_name() {
+        let incoming = render_managed(
+            &[codex_provider("relay", "sk-1", "https://relay.example/v1")],
+            &[],
+        );
+        let live = "[profiles.relay]\nmodel_provider = \"openai\"\nmodel = \"o3\"\n";
+        let merged = merge_managed(live, "", &incoming).expect("merge");
+        let doc: toml::Table = toml::from_str(&merged).expect("valid toml");
+        assert_eq!(
+            doc["profiles"]["relay"]["model_provider"].as_str(),
+            Some("openai")
+        );
+        assert!(doc.get("model_providers").is_none());
+    }
+
+    #[test]
+    fn carry_over_and_strip_only_touch_managed_entries() {
+        let previous = merge_managed(
+            "model_provider = \"custom\"\n\n[model_providers.custom]\nbase_url = \"https://old.example\"\n\n[profiles.mine]\nmodel = \"o3\"\n",
+            "",
+            &render_managed(
+                &[codex_provider("relay", "sk-1", "https://relay.example/v1")],
+                &[],
+            ),
+        )
+        .expect("merge");
+        let next = "model_provider = \"custom\"\n\n[model_providers.custom]\nbase_url = \"https://new.example\"\n";
+
+        let carried = carry_over_managed(&previous, next).expect("carry over");
+        let doc: toml::Table = toml::from_str(&carried).expect("valid toml");
+        assert_eq!(
+            doc["model_providers"]["custom"]["base_url"].as_str(),
+            Some("https://new.example")
+        );
+        assert!(doc["model_providers"].get("cc-switch-relay").is_some());
+        assert!(doc["profiles"].get("relay").is_some());
+        assert!(doc["profiles"].get("mine").is_none());
+
+        let mut stripped = carried.parse::<DocumentMut>().expect("parse");
+        strip_managed(stripped.as_table_mut());
+        let stripped = stripped.to_string();
+        assert!(!stripped.contains("cc-switch-"));
+        assert!(!stripped.contains("[profiles"));
+        assert!(stripped.contains("https://new.example"));
+    }
+}
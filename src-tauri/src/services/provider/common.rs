@@ -1,5 +1,5 @@
 use super::*;
-use crate::provider_preset_models::CODEX_DEFAULT_MODEL;
+use crate::provider_preset_models::codex_default_model;
 
 pub fn migrate_legacy_codex_config(cfg_text: &str, provider: &Provider) -> Option<String> {
     let trimmed = cfg_text.trim();
@@ -32,7 +32,7 @@ pub fn migrate_legacy_codex_config(cfg_text: &str, provider: &Provider) -> Optio
     let model = table
         .get("model")
         .and_then(|v| v.as_str())
-        .unwrap_or(CODEX_DEFAULT_MODEL)
+        .unwrap_or(codex_default_model())
         .trim();
     let wire_api = table
         .get("wire_api")
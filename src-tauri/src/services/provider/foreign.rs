@@ -0,0 +1,858 @@
+//! 从其他切换工具与路由导入供应商。
+//!
+//! 支持 claude-code-router 的 `config.json`（Providers 与 Router 规则）、
+//! LiteLLM 代理 `config.yaml` 的 `model_list`、shell rc 文件中导出的
+//! `ANTHROPIC_*` / `OPENAI_*` 变量，以及 Codex `config.toml` 中手写的 `[profiles.*]`。扫描只生成候选项供预览和勾选，
+//! 选中后再由 [`ProviderService::import_foreign`] 写入。
+//! 引用环境变量的 Key（`$VAR`、`os.environ/VAR`）转换为 `env:VAR` 引用，不复制明文。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use super::{codex_profiles, current_timestamp, lint, ProviderService};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{Provider, ProviderMeta, CLAUDE_API_KEY_ENV_KEY, CLAUDE_AUTH_TOKEN_ENV_KEY};
+use crate::provider_preset_models::codex_default_model;
+use crate::services::env_checker::{self, EnvConflict};
+use crate::store::AppState;
+
+const ANTHROPIC_OFFICIAL_BASE_URL: &str = "https://api.anthropic.com";
+const OPENAI_OFFICIAL_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// 导入来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ForeignSource {
+    /// claude-code-router 的 config.json
+    Ccr,
+    /// LiteLLM 代理的 config.yaml
+    Litellm,
+    /// shell rc 文件中的 export
+    Shell,
+    /// Codex config.toml 中手写的 profile
+    #[serde(rename = "codex-profiles")]
+    CodexProfiles,
+}
+
+impl ForeignSource {
+    pub const ALL: [Self; 4] = [Self::Ccr, Self::Litellm, Self::Shell, Self::CodexProfiles];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ccr => "ccr",
+            Self::Litellm => "litellm",
+            Self::Shell => "shell",
+            Self::CodexProfiles => "codex-profiles",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ccr => "claude-code-router",
+            Self::Litellm => "LiteLLM",
+            Self::Shell => "shell rc files",
+            Self::CodexProfiles => "Codex profiles",
+        }
+    }
+
+    /// 未指定文件时依次尝试的位置；shell 来源读取固定的一组 rc 文件
+    pub fn default_paths(self) -> Vec<PathBuf> {
+        let home = crate::config::home_dir().unwrap_or_default();
+        match self {
+            Self::Ccr => vec![home.join(".claude-code-router").join("config.json")],
+            Self::Litellm => vec![
+                home.join(".litellm").join("config.yaml"),
+                home.join("litellm").join("config.yaml"),
+                PathBuf::from("litellm_config.yaml"),
+                PathBuf::from("config.yaml"),
+            ],
+            Self::Shell => Vec::new(),
+            Self::CodexProfiles => vec![crate::codex_config::get_codex_config_path()],
+        }
+    }
+}
+
+/// 一个待导入的供应商候选项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignCandidate {
+    pub source: ForeignSource,
+    /// 来源文件
+    pub origin: String,
+    pub app: AppType,
+    pub base_url: String,
+    /// Claude 为 `anthropic` / `openai_chat` / `openai_responses` / `gemini_native`
+    pub api_format: String,
+    pub model: Option<String>,
+    pub has_key: bool,
+    /// 无法完整映射的配置说明
+    pub notes: Vec<String>,
+    /// 已存在相同 Base URL 的本地供应商
+    pub duplicate_of: Option<String>,
+    pub provider: Provider,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignImported {
+    pub app: AppType,
+    pub id: String,
+    pub name: String,
+}
+
+/// 生成候选项前的中间结构
+struct Draft {
+    source: ForeignSource,
+    origin: String,
+    app: AppType,
+    name: String,
+    base_url: String,
+    api_format: String,
+    api_key: Option<String>,
+    /// Claude：Key 写入 ANTHROPIC_API_KEY 而不是 ANTHROPIC_AUTH_TOKEN
+    use_api_key_env: bool,
+    model: Option<String>,
+    haiku_model: Option<String>,
+    sonnet_model: Option<String>,
+    opus_model: Option<String>,
+    notes: Vec<String>,
+}
+
+impl Draft {
+    fn new(source: ForeignSource, origin: &str, app: AppType, name: &str, base_url: &str) -> Self {
+        Self {
+            source,
+            origin: origin.to_string(),
+            app,
+            name: name.to_string(),
+            base_url: base_url.to_string(),
+            api_format: "anthropic".to_string(),
+            api_key: None,
+            use_api_key_env: false,
+            model: None,
+            haiku_model: None,
+            sonnet_model: None,
+            opus_model: None,
+            notes: Vec::new(),
+        }
+    }
+
+    fn into_candidate(self) -> ForeignCandidate {
+        let has_key = self.api_key.as_deref().is_some_and(|key| !key.is_empty());
+        let mut meta = ProviderMeta::default();
+        let settings = match self.app {
+            AppType::Codex => {
+                // Codex 供应商以 openai_chat 标记本地 Responses ↔ Chat 路由
+                if self.api_format == "openai_chat" {
+                    meta.api_format = Some(self.api_format.clone());
+                }
+                json!({
+                    "auth": { "OPENAI_API_KEY": self.api_key.clone().unwrap_or_default() },
+                    "config": crate::codex_config::build_codex_third_party_config_toml(
+                        &self.name,
+                        &self.base_url,
+                        self.model.as_deref().unwrap_or(codex_default_model()),
+                        "responses",
+                    ),
+                })
+            }
+            _ => {
+                let mut env = Map::new();
+                env.insert("ANTHROPIC_BASE_URL".into(), json!(self.base_url));
+                let key_env = if self.use_api_key_env {
+                    meta.api_key_field = Some(CLAUDE_API_KEY_ENV_KEY.to_string());
+                    CLAUDE_API_KEY_ENV_KEY
+                } else {
+                    CLAUDE_AUTH_TOKEN_ENV_KEY
+                };
+                env.insert(
+                    key_env.into(),
+                    json!(self.api_key.clone().unwrap_or_default()),
+                );
+                for (key, value) in [
+                    ("ANTHROPIC_MODEL", &self.model),
+                    ("ANTHROPIC_DEFAULT_HAIKU_MODEL", &self.haiku_model),
+                    ("ANTHROPIC_DEFAULT_SONNET_MODEL", &self.sonnet_model),
+                    ("ANTHROPIC_DEFAULT_OPUS_MODEL", &self.opus_model),
+                ] {
+                    if let Some(value) = value {
+                        env.insert(key.into(), json!(value));
+                    }
+                }
+                if self.api_format != "anthropic" {
+                    meta.api_format = Some(self.api_format.clone());
+                }
+                json!({ "env": env })
+            }
+        };
+
+        let mut provider = Provider::with_id(String::new(), self.name, settings, None);
+        provider.notes = Some(format!(
+            "Imported from {} ({})",
+            self.source.label(),
+            self.origin
+        ));
+        provider.meta = Some(meta);
+        ForeignCandidate {
+            source: self.source,
+            origin: self.origin,
+            app: self.app,
+            base_url: self.base_url,
+            api_format: self.api_format,
+            model: self.model,
+            has_key,
+            notes: self.notes,
+            duplicate_of: None,
+            provider,
+        }
+    }
+}
+
+/// 去掉 URL 末尾的具体接口路径，并返回由该路径推断出的 API 格式
+fn strip_endpoint(url: &str) -> (String, Option<&'static str>) {
+    let trimmed = url.trim().trim_end_matches('/');
+    for (suffix, format) in [
+        ("/v1/messages", "anthropic"),
+        ("/messages", "anthropic"),
+        ("/chat/completions", "openai_chat"),
+        ("/responses", "openai_responses"),
+        ("/models", "gemini_native"),
+    ] {
+        if let Some(base) = trimmed.strip_suffix(suffix) {
+            return (base.to_string(), Some(format));
+        }
+    }
+    (trimmed.to_string(), None)
+}
+
+/// `$VAR`、`${VAR}`、`os.environ/VAR` 转为 `env:VAR` 引用
+fn normalize_key(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let var = raw
+        .strip_prefix("os.environ/")
+        .or_else(|| {
+            raw.strip_prefix("${")
+                .and_then(|rest| rest.strip_suffix('}'))
+        })
+        .or_else(|| raw.strip_prefix('$'));
+    match var {
+        Some(var) if is_env_name(var) => Some(format!("env:{var}")),
+        _ => Some(raw.to_string()),
+    }
+}
+
+fn is_env_name(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// 解析 claude-code-router 的 `config.json`
+pub fn parse_ccr_config(text: &str, origin: &str) -> Result<Vec<ForeignCandidate>, AppError> {
+    let config: Value = serde_json::from_str(text).map_err(|e| {
+        AppError::InvalidInput(format!("Invalid claude-code-router config {origin}: {e}"))
+    })?;
+    let providers = config
+        .get("Providers")
+        .or_else(|| config.get("providers"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    // Router 的值形如 "provider,model"
+    let mut routes: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    if let Some(router) = config.get("Router").and_then(Value::as_object) {
+        for (rule, value) in router {
+            let Some((provider, model)) = value.as_str().and_then(|v| v.split_once(',')) else {
+                continue;
+            };
+            routes
+                .entry(provider.trim().to_string())
+                .or_default()
+                .push((rule.clone(), model.trim().to_string()));
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for entry in providers {
+        let Some(name) = entry.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(url) = entry.get("api_base_url").and_then(Value::as_str) else {
+            continue;
+        };
+        let (base_url, url_format) = strip_endpoint(url);
+        let transformers = ccr_transformers(&entry);
+        let api_format = if transformers.iter().any(|t| t == "anthropic") {
+            "anthropic"
+        } else if transformers.iter().any(|t| t.contains("gemini")) {
+            "gemini_native"
+        } else {
+            url_format.unwrap_or("openai_chat")
+        };
+
+        let mut draft = Draft::new(ForeignSource::Ccr, origin, AppType::Claude, name, &base_url);
+        draft.api_format = api_format.to_string();
+        draft.api_key = entry
+            .get("api_key")
+            .and_then(Value::as_str)
+            .and_then(normalize_key);
+        for (rule, model) in routes.get(name).into_iter().flatten() {
+            match rule.as_str() {
+                "default" => {
+                    draft.model = Some(model.clone());
+                    draft.sonnet_model = Some(model.clone());
+                }
+                "background" => draft.haiku_model = Some(model.clone()),
+                "think" => draft.opus_model = Some(model.clone()),
+                other => draft.notes.push(format!(
+                    "Router rule '{other}' ({model}) has no cc-switch equivalent"
+                )),
+            }
+        }
+        if draft.model.is_none() {
+            draft.model = entry
+                .get("models")
+                .and_then(Value::as_array)
+                .and_then(|models| models.first())
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+        if let Some(unknown) = transformers.iter().find(|t| {
+            !matches!(
+                t.as_str(),
+                "anthropic" | "openrouter" | "deepseek" | "gemini"
+            )
+        }) {
+            draft
+                .notes
+                .push(format!("Transformer '{unknown}' is not carried over"));
+        }
+        candidates.push(draft.into_candidate());
+    }
+    Ok(candidates)
+}
+
+/// `transformer.use` 的元素可以是名称，也可以是 `[名称, 选项]`
+fn ccr_transformers(entry: &Value) -> Vec<String> {
+    entry
+        .pointer("/transformer/use")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    Value::String(name) => Some(name.as_str()),
+                    Value::Array(pair) => pair.first().and_then(Value::as_str),
+                    _ => None,
+                })
+                .map(str::to_ascii_lowercase)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 解析 LiteLLM 代理 `config.yaml` 的 `model_list`
+pub fn parse_litellm_config(text: &str, origin: &str) -> Result<Vec<ForeignCandidate>, AppError> {
+    let config: Value = serde_yaml::from_str(text)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid LiteLLM config {origin}: {e}")))?;
+    let entries = config
+        .get("model_list")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut candidates = Vec::new();
+    for entry in entries {
+        let Some(params) = entry.get("litellm_params") else {
+            continue;
+        };
+        let Some(model) = params.get("model").and_then(Value::as_str) else {
+            continue;
+        };
+        let name = entry
+            .get("model_name")
+            .and_then(Value::as_str)
+            .unwrap_or(model);
+        let (prefix, upstream_model) = model.split_once('/').unwrap_or(("openai", model));
+        let (api_format, default_base) = match prefix {
+            "anthropic" => ("anthropic", Some(ANTHROPIC_OFFICIAL_BASE_URL)),
+            "openai" | "text-completion-openai" => ("openai_chat", Some(OPENAI_OFFICIAL_BASE_URL)),
+            "gemini" => (
+                "gemini_native",
+                Some("https://generativelanguage.googleapis.com/v1beta"),
+            ),
+            "deepseek" => ("openai_chat", Some("https://api.deepseek.com")),
+            "openrouter" => ("openai_chat", Some("https://openrouter.ai/api/v1")),
+            // 需要云厂商凭据签名，无法映射为普通供应商
+            "azure" | "azure_ai" | "bedrock" | "vertex_ai" | "sagemaker" => continue,
+            _ => ("openai_chat", None),
+        };
+        let explicit_base = params.get("api_base").and_then(Value::as_str);
+        let Some(base) = explicit_base.or(default_base) else {
+            continue;
+        };
+        let (base_url, url_format) = strip_endpoint(base);
+
+        let mut draft = Draft::new(
+            ForeignSource::Litellm,
+            origin,
+            AppType::Claude,
+            name,
+            &base_url,
+        );
+        draft.api_format = url_format.unwrap_or(api_format).to_string();
+        draft.api_key = params
+            .get("api_key")
+            .and_then(Value::as_str)
+            .and_then(normalize_key);
+        if draft.api_format == "anthropic" && draft.api_key.is_some() {
+            draft.use_api_key_env = true;
+        }
+        draft.model = Some(upstream_model.to_string());
+        if params.get("rpm").is_some() || params.get("tpm").is_some() {
+            draft
+                .notes
+                .push("rpm/tpm limits are not carried over".to_string());
+        }
+        candidates.push(draft.into_candidate());
+    }
+    Ok(candidates)
+}
+
+/// 解析 Codex `config.toml` 中手写的 profile；cc-switch 托管的条目跳过
+pub fn parse_codex_profiles(text: &str, origin: &str) -> Result<Vec<ForeignCandidate>, AppError> {
+    let config: toml::Table = toml::from_str(text)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid Codex config {origin}: {e}")))?;
+    let Some(profiles) = config.get("profiles").and_then(toml::Value::as_table) else {
+        return Ok(Vec::new());
+    };
+    let model_providers = config
+        .get("model_providers")
+        .and_then(toml::Value::as_table);
+
+    let mut candidates = Vec::new();
+    for (name, profile) in profiles {
+        let Some(provider_id) = profile.get("model_provider").and_then(toml::Value::as_str) else {
+            continue;
+        };
+        if codex_profiles::is_managed_model_provider(provider_id) {
+            continue;
+        }
+        let Some(entry) = model_providers.and_then(|providers| providers.get(provider_id)) else {
+            continue;
+        };
+        let Some(url) = entry.get("base_url").and_then(toml::Value::as_str) else {
+            continue;
+        };
+        let (base_url, _) = strip_endpoint(url);
+
+        let mut draft = Draft::new(
+            ForeignSource::CodexProfiles,
+            origin,
+            AppType::Codex,
+            name,
+            &base_url,
+        );
+        draft.api_format = match entry.get("wire_api").and_then(toml::Value::as_str) {
+            Some("chat") => "openai_chat",
+            _ => "openai_responses",
+        }
+        .to_string();
+        draft.api_key = match entry.get("env_key").and_then(toml::Value::as_str) {
+            Some(var) if is_env_name(var) => Some(format!("env:{var}")),
+            _ => entry
+                .get("experimental_bearer_token")
+                .and_then(toml::Value::as_str)
+                .and_then(normalize_key),
+        };
+        draft.model = profile
+            .get("model")
+            .and_then(toml::Value::as_str)
+            .map(str::to_string);
+        let extra: Vec<&str> = profile
+            .as_table()
+            .into_iter()
+            .flat_map(|table| table.keys())
+            .map(String::as_str)
+            .filter(|key| !matches!(*key, "model" | "model_provider"))
+            .collect();
+        if !extra.is_empty() {
+            draft.notes.push(format!(
+                "Profile settings {} are not carried over",
+                extra.join(", ")
+            ));
+        }
+        candidates.push(draft.into_candidate());
+    }
+    Ok(candidates)
+}
+
+/// 按文件汇总 shell export，生成 Claude（ANTHROPIC_*）与 Codex（OPENAI_*）候选项
+pub fn candidates_from_shell_exports(exports: &[EnvConflict]) -> Vec<ForeignCandidate> {
+    // 保持文件出现顺序；同一文件中后出现的赋值覆盖先前的
+    let mut files: Vec<(String, HashMap<String, String>)> = Vec::new();
+    for export in exports {
+        let file = export
+            .source_path
+            .rsplit_once(':')
+            .map(|(file, _)| file)
+            .unwrap_or(&export.source_path)
+            .to_string();
+        let index = match files.iter().position(|(existing, _)| existing == &file) {
+            Some(index) => index,
+            None => {
+                files.push((file, HashMap::new()));
+                files.len() - 1
+            }
+        };
+        files[index]
+            .1
+            .insert(export.var_name.clone(), export.var_value.clone());
+    }
+
+    let mut candidates = Vec::new();
+    for (file, vars) in &files {
+        let var = |name: &str| vars.get(name).map(String::as_str).filter(|v| !v.is_empty());
+
+        let claude_key = var(CLAUDE_AUTH_TOKEN_ENV_KEY)
+            .map(|key| (key, false))
+            .or_else(|| var(CLAUDE_API_KEY_ENV_KEY).map(|key| (key, true)));
+        let claude_base = var("ANTHROPIC_BASE_URL");
+        if claude_base.is_some() || claude_key.is_some() {
+            let base_url = strip_endpoint(claude_base.unwrap_or(ANTHROPIC_OFFICIAL_BASE_URL)).0;
+            let mut draft = Draft::new(
+                ForeignSource::Shell,
+                file,
+                AppType::Claude,
+                &host_name(&base_url),
+                &base_url,
+            );
+            if let Some((key, use_api_key_env)) = claude_key {
+                draft.api_key = shell_key(key, &mut draft.notes);
+                draft.use_api_key_env = use_api_key_env;
+            }
+            draft.model = var("ANTHROPIC_MODEL").map(str::to_string);
+            draft.haiku_model = var("ANTHROPIC_DEFAULT_HAIKU_MODEL").map(str::to_string);
+            draft.sonnet_model = var("ANTHROPIC_DEFAULT_SONNET_MODEL").map(str::to_string);
+            draft.opus_model = var("ANTHROPIC_DEFAULT_OPUS_MODEL").map(str::to_string);
+            candidates.push(draft.into_candidate());
+        }
+
+        let openai_key = var("OPENAI_API_KEY");
+        let openai_base = var("OPENAI_BASE_URL").or_else(|| var("OPENAI_API_BASE"));
+        if openai_base.is_some() || openai_key.is_some() {
+            let (base_url, url_format) =
+                strip_endpoint(openai_base.unwrap_or(OPENAI_OFFICIAL_BASE_URL));
+            let mut draft = Draft::new(
+                ForeignSource::Shell,
+                file,
+                AppType::Codex,
+                &host_name(&base_url),
+                &base_url,
+            );
+            draft.api_format = url_format.unwrap_or("openai_responses").to_string();
+            if let Some(key) = openai_key {
+                draft.api_key = shell_key(key, &mut draft.notes);
+            }
+            draft.model = var("OPENAI_MODEL").map(str::to_string);
+            candidates.push(draft.into_candidate());
+        }
+    }
+    candidates
+}
+
+/// shell 中由命令生成的 Key 无法静态读取，导入后需手动填写
+fn shell_key(raw: &str, notes: &mut Vec<String>) -> Option<String> {
+    if raw.contains("$(") || raw.contains('`') {
+        notes.push("API key is produced by a shell command; set it after import".to_string());
+        return None;
+    }
+    normalize_key(raw)
+}
+
+fn host_name(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', ':']).next().unwrap_or(rest).to_string()
+}
+
+fn read_foreign_file(path: &Path) -> Result<String, AppError> {
+    std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))
+}
+
+impl ProviderService {
+    /// 扫描其他工具的配置，返回候选项并标记与本地供应商重复的项；
+    /// 未指定文件且默认位置都不存在时返回空列表
+    pub fn scan_foreign(
+        state: &AppState,
+        source: ForeignSource,
+        path: Option<&Path>,
+    ) -> Result<Vec<ForeignCandidate>, AppError> {
+        let mut candidates = match (source, path) {
+            (ForeignSource::Shell, Some(path)) => {
+                let origin = path.display().to_string();
+                let exports = env_checker::scan_shell_exports(
+                    &origin,
+                    &read_foreign_file(path)?,
+                    &["ANTHROPIC", "OPENAI"],
+                );
+                candidates_from_shell_exports(&exports)
+            }
+            (ForeignSource::Shell, None) => {
+                #[cfg(not(target_os = "windows"))]
+                let exports = env_checker::check_shell_configs(&["ANTHROPIC", "OPENAI"])
+                    .map_err(AppError::Message)?;
+                #[cfg(target_os = "windows")]
+                let exports = Vec::new();
+                candidates_from_shell_exports(&exports)
+            }
+            (source, path) => {
+                let path = match path {
+                    Some(path) => path.to_path_buf(),
+                    None => match source.default_paths().into_iter().find(|p| p.is_file()) {
+                        Some(path) => path,
+                        None => return Ok(Vec::new()),
+                    },
+                };
+                let text = read_foreign_file(&path)?;
+                let origin = path.display().to_string();
+                match source {
+                    ForeignSource::Ccr => parse_ccr_config(&text, &origin)?,
+                    ForeignSource::CodexProfiles => parse_codex_profiles(&text, &origin)?,
+                    _ => parse_litellm_config(&text, &origin)?,
+                }
+            }
+        };
+
+        let mut existing: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for candidate in &mut candidates {
+            let app = candidate.app.as_str().to_string();
+            if !existing.contains_key(&app) {
+                let urls = Self::list(state, candidate.app.clone())?
+                    .into_values()
+                    .filter_map(|provider| {
+                        lint::base_url(&candidate.app, &provider)
+                            .map(|url| (provider.id, url.trim_end_matches('/').to_string()))
+                    })
+                    .collect();
+                existing.insert(app.clone(), urls);
+            }
+            candidate.duplicate_of = existing[&app]
+                .iter()
+                .find(|(_, url)| url.eq_ignore_ascii_case(&candidate.base_url))
+                .map(|(id, _)| id.clone());
+        }
+        Ok(candidates)
+    }
+
+    /// 写入选中的候选项，ID 由名称生成并避开已有 ID
+    pub fn import_foreign(
+        state: &AppState,
+        candidates: Vec<ForeignCandidate>,
+    ) -> Result<Vec<ForeignImported>, AppError> {
+        let mut imported = Vec::new();
+        for candidate in candidates {
+            let existing_ids: Vec<String> = Self::list(state, candidate.app.clone())?
+                .into_keys()
+                .collect();
+            let mut provider = candidate.provider;
+            provider.id = Self::generate_provider_key(&provider.name, &existing_ids);
+            provider.created_at = Some(current_timestamp());
+            let (id, name) = (provider.id.clone(), provider.name.clone());
+            Self::add(state, candidate.app.clone(), provider)?;
+            imported.push(ForeignImported {
+                app: candidate.app,
+                id,
+                name,
+            });
+        }
+        Ok(imported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CCR_CONFIG: &str = r#"{
+      "APIKEY": "ccr-secret",
+      "Providers": [
+        {
+          "name": "deepseek",
+          "api_base_url": "https://api.deepseek.com/chat/completions",
+          "api_key": "$DEEPSEEK_API_KEY",
+          "models": ["deepseek-chat", "deepseek-reasoner"],
+          "transformer": { "use": ["deepseek"] }
+        },
+        {
+          "name": "relay",
+          "api_base_url": "https://relay.example.com/v1/messages",
+          "api_key": "sk-relay",
+          "models": ["claude-sonnet-4"],
+          "transformer": { "use": [["maxtoken", { "max_tokens": 8192 }]] }
+        }
+      ],
+      "Router": {
+        "default": "deepseek,deepseek-chat",
+        "think": "deepseek,deepseek-reasoner",
+        "longContext": "relay,claude-sonnet-4"
+      }
+    }"#;
+
+    #[test]
+    fn ccr_providers_map_router_rules_and_formats() {
+        let candidates = parse_ccr_config(CCR_CONFIG, "config.json").unwrap();
+        assert_eq!(candidates.len(), 2);
+
+        let deepseek = &candidates[0];
+        assert_eq!(deepseek.base_url, "https://api.deepseek.com");
+        assert_eq!(deepseek.api_format, "openai_chat");
+        let env = &deepseek.provider.settings_config["env"];
+        assert_eq!(env["ANTHROPIC_AUTH_TOKEN"], "env:DEEPSEEK_API_KEY");
+        assert_eq!(env["ANTHROPIC_MODEL"], "deepseek-chat");
+        assert_eq!(env["ANTHROPIC_DEFAULT_OPUS_MODEL"], "deepseek-reasoner");
+        assert_eq!(
+            deepseek
+                .provider
+                .meta
+                .as_ref()
+                .unwrap()
+                .api_format
+                .as_deref(),
+            Some("openai_chat")
+        );
+
+        let relay = &candidates[1];
+        assert_eq!(relay.base_url, "https://relay.example.com");
+        assert_eq!(relay.api_format, "anthropic");
+        assert_eq!(relay.model.as_deref(), Some("claude-sonnet-4"));
+        assert!(relay.notes.iter().any(|note| note.contains("longContext")));
+        assert!(relay.notes.iter().any(|note| note.contains("maxtoken")));
+    }
+
+    #[test]
+    fn litellm_entries_map_prefixes_and_env_keys() {
+        let config = r#"
+model_list:
+  - model_name: sonnet
+    litellm_params:
+      model: anthropic/claude-sonnet-4
+      api_key: os.environ/ANTHROPIC_API_KEY
+  - model_name: local
+    litellm_params:
+      model: ollama/qwen2.5-coder
+      api_base: http://localhost:11434
+  - model_name: azure-gpt
+    litellm_params:
+      model: azure/gpt-4o
+  - model_name: custom
+    litellm_params:
+      model: hosted_vllm/llama
+"#;
+        let candidates = parse_litellm_config(config, "config.yaml").unwrap();
+        assert_eq!(candidates.len(), 2);
+
+        let sonnet = &candidates[0];
+        assert_eq!(sonnet.base_url, ANTHROPIC_OFFICIAL_BASE_URL);
+        assert_eq!(sonnet.api_format, "anthropic");
+        let env = &sonnet.provider.settings_config["env"];
+        assert_eq!(env["ANTHROPIC_API_KEY"], "env:ANTHROPIC_API_KEY");
+        assert_eq!(env["ANTHROPIC_MODEL"], "claude-sonnet-4");
+
+        let local = &candidates[1];
+        assert_eq!(local.provider.name, "local");
+        assert_eq!(local.api_format, "openai_chat");
+        assert!(!local.has_key);
+    }
+
+    #[test]
+    fn shell_exports_group_by_file_into_claude_and_codex() {
+        let export = |name: &str, value: &str, path: &str| EnvConflict {
+            var_name: name.to_string(),
+            var_value: value.to_string(),
+            source_type: "file".to_string(),
+            source_path: path.to_string(),
+        };
+        let exports = vec![
+            export(
+                "ANTHROPIC_BASE_URL",
+                "https://relay.example.com",
+                "/h/.zshrc:3",
+            ),
+            export("ANTHROPIC_AUTH_TOKEN", "$RELAY_TOKEN", "/h/.zshrc:4"),
+            export("OPENAI_API_KEY", "$(pass show openai)", "/h/.zshrc:5"),
+            export("ANTHROPIC_API_KEY", "sk-old", "/h/.bashrc:1"),
+        ];
+        let candidates = candidates_from_shell_exports(&exports);
+        assert_eq!(candidates.len(), 3);
+
+        assert_eq!(candidates[0].app, AppType::Claude);
+        assert_eq!(candidates[0].provider.name, "relay.example.com");
+        assert_eq!(
+            candidates[0].provider.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "env:RELAY_TOKEN"
+        );
+
+        assert_eq!(candidates[1].app, AppType::Codex);
+        assert_eq!(candidates[1].base_url, OPENAI_OFFICIAL_BASE_URL);
+        assert!(!candidates[1].has_key);
+        assert_eq!(candidates[1].notes.len(), 1);
+
+        assert_eq!(candidates[2].origin, "/h/.bashrc");
+        assert_eq!(candidates[2].base_url, ANTHROPIC_OFFICIAL_BASE_URL);
+        assert_eq!(
+            candidates[2].provider.settings_config["env"]["ANTHROPIC_API_KEY"],
+            "sk-old"
+        );
+    }
+
+    #[test]
+    fn codex_profiles_skip_managed_and_map_auth() {
+        let config = r#"
+model_provider = "custom"
+
+[profiles.work]
+model_provider = "corp"
+model = "gpt-5"
+model_reasoning_effort = "high"
+
+[profiles.relay]
+model_provider = "cc-switch-relay"
+
+[profiles.official]
+model_provider = "openai"
+
+[model_providers.corp]
+name = "Corp"
+base_url = "https://llm.corp.example/v1/"
+wire_api = "chat"
+env_key = "CORP_KEY"
+
+[model_providers.cc-switch-relay]
+base_url = "https://relay.example/v1"
+"#;
+        let candidates = parse_codex_profiles(config, "config.toml").expect("parse");
+        assert_eq!(candidates.len(), 1);
+
+        let work = &candidates[0];
+        assert_eq!(work.app, AppType::Codex);
+        assert_eq!(work.provider.name, "work");
+        assert_eq!(work.base_url, "https://llm.corp.example/v1");
+        assert_eq!(work.api_format, "openai_chat");
+        assert_eq!(work.model.as_deref(), Some("gpt-5"));
+        assert_eq!(
+            work.provider.settings_config["auth"]["OPENAI_API_KEY"],
+            "env:CORP_KEY"
+        );
+        assert_eq!(work.notes.len(), 1);
+    }
+}
@@ -156,11 +156,12 @@ impl ProviderService {
         let mut live = env_to_json(&env_map);
 
         let settings_path = get_gemini_settings_path();
-        let config_value = if settings_path.exists() {
+        let mut config_value = if settings_path.exists() {
             read_json_file(&settings_path)?
         } else {
             json!({})
         };
+        crate::services::agent_settings::strip_projected(&AppType::Gemini, &mut config_value);
         if let Some(obj) = live.as_object_mut() {
             obj.insert("config".to_string(), config_value);
         }
@@ -173,7 +174,8 @@ impl ProviderService {
 
         if let Some(manager) = config.get_manager_mut(&AppType::Gemini) {
             if let Some(current) = manager.providers.get_mut(current_id) {
-                current.settings_config = live;
+                current.settings_config =
+                    crate::key_pool::restore_stored(&AppType::Gemini, current, live);
             }
         }
 
@@ -184,6 +186,7 @@ impl ProviderService {
         provider: &Provider,
         common_config_snippet: Option<&str>,
     ) -> Result<(), AppError> {
+        let provider = &crate::key_pool::resolve_for_live(&AppType::Gemini, provider)?;
         let prepared =
             Self::prepare_gemini_live_write(provider, common_config_snippet, None, true)?;
         Self::apply_gemini_live_write(&prepared)
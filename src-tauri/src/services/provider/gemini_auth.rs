@@ -61,6 +61,11 @@ impl ProviderService {
         }
     }
 
+    /// `security.auth.selectedType` 取值，供临时启动写入 settings 覆盖层
+    pub(crate) fn gemini_selected_auth_type(provider: &Provider) -> &'static str {
+        Self::gemini_security_selected_type(Self::detect_gemini_auth_type(provider))
+    }
+
     pub(super) fn ensure_gemini_app_security_flag(
         auth_type: GeminiAuthType,
     ) -> Result<(), AppError> {
@@ -0,0 +1,715 @@
+//! 供应商配置检查（lint）与自动修复。
+//!
+//! 每条规则只读供应商配置本身，输出带严重程度、建议与可选修复的结果；
+//! 标记为安全的修复（去掉重复的版本路径、把 Key 挪到声明的字段、撤销误用的
+//! 完整 URL 开关）可由 `provider lint --fix` 直接应用，其余只给出建议。
+//! 模型是否仍被上游列出依赖模型目录快照（见 [`crate::services::model_watch`]）。
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ProviderService;
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{ClaudeApiKeyField, Provider, ProviderMeta};
+use crate::services::model_watch::{self, MissingModel};
+
+/// 写错位置的 Claude Key 常见变量名
+const MISPLACED_CLAUDE_KEY_ENV_KEYS: &[&str] = &["OPENAI_API_KEY", "ANTHROPIC_KEY", "API_KEY"];
+
+/// 检查结果的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl LintSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// 可自动应用的修复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum LintFix {
+    /// 替换基础 URL
+    SetBaseUrl { url: String },
+    /// 把 env 中的 Key 从 `from` 挪到 `to`
+    MoveApiKey { from: String, to: String },
+    /// 撤销 `isFullUrl`
+    ClearFullUrl,
+    /// 把角色映射中的模型替换为上游仍提供的模型
+    RemapModel { from: String, to: String },
+    /// 把 Codex `wire_api` 改为与 `apiFormat` 一致
+    SetWireApi { wire_api: String },
+}
+
+impl LintFix {
+    /// 是否可以不经确认直接应用（不改变供应商实际使用的协议与模型）
+    pub fn is_safe(&self) -> bool {
+        matches!(
+            self,
+            Self::SetBaseUrl { .. } | Self::MoveApiKey { .. } | Self::ClearFullUrl
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::SetBaseUrl { url } => format!("set base URL to {url}"),
+            Self::MoveApiKey { from, to } => format!("move {from} to {to}"),
+            Self::ClearFullUrl => "turn off full URL mode".to_string(),
+            Self::RemapModel { from, to } => format!("remap {from} to {to}"),
+            Self::SetWireApi { wire_api } => format!("set wire_api = \"{wire_api}\""),
+        }
+    }
+}
+
+/// 一条检查结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    /// 规则 ID，例如 `base-url-version`
+    pub rule: &'static str,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// 无法自动修复时给用户的建议
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<LintFix>,
+}
+
+impl LintFinding {
+    fn new(rule: &'static str, severity: LintSeverity, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            severity,
+            message: message.into(),
+            suggestion: None,
+            fix: None,
+        }
+    }
+
+    fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    fn with_fix(mut self, fix: LintFix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// 是否带有可直接应用的安全修复
+    pub fn has_safe_fix(&self) -> bool {
+        self.fix.as_ref().is_some_and(LintFix::is_safe)
+    }
+}
+
+impl ProviderService {
+    /// 检查供应商配置，包括模型目录快照中已下线的模型
+    pub fn lint_provider(app_type: &AppType, provider: &Provider) -> Vec<LintFinding> {
+        let mut findings = lint_rules(app_type, provider);
+        let missing = model_watch::missing_models_by_provider(app_type, [provider])
+            .remove(&provider.id)
+            .unwrap_or_default();
+        findings.extend(missing_model_findings(&missing));
+        findings
+    }
+
+    /// 应用一条修复；不会保存，调用方负责持久化
+    pub fn apply_lint_fix(
+        app_type: &AppType,
+        provider: &mut Provider,
+        fix: &LintFix,
+    ) -> Result<(), AppError> {
+        match fix {
+            LintFix::SetBaseUrl { url } => set_base_url(app_type, provider, url),
+            LintFix::MoveApiKey { from, to } => {
+                let env = provider
+                    .settings_config
+                    .get_mut("env")
+                    .and_then(Value::as_object_mut)
+                    .ok_or_else(|| AppError::InvalidInput("Provider has no env".to_string()))?;
+                let value = env.remove(from).ok_or_else(|| {
+                    AppError::InvalidInput(format!("{from} is not set on this provider"))
+                })?;
+                env.insert(to.clone(), value);
+                Ok(())
+            }
+            LintFix::ClearFullUrl => {
+                if let Some(meta) = provider.meta.as_mut() {
+                    meta.is_full_url = None;
+                }
+                Ok(())
+            }
+            LintFix::RemapModel { from, to } => {
+                model_watch::remap_model(app_type, &mut provider.settings_config, from, to)
+                    .map(|_| ())
+            }
+            LintFix::SetWireApi { wire_api } => edit_codex_config(provider, |doc| {
+                match active_codex_provider_table(doc) {
+                    Some(table) => table["wire_api"] = toml_edit::value(wire_api.as_str()),
+                    None => doc["wire_api"] = toml_edit::value(wire_api.as_str()),
+                };
+            }),
+        }
+    }
+}
+
+/// 只依赖供应商配置本身的规则
+pub(crate) fn lint_rules(app_type: &AppType, provider: &Provider) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let meta = provider.meta.as_ref();
+
+    if let Some(raw) = base_url(app_type, provider) {
+        lint_base_url(app_type, meta, &raw, &mut findings);
+    }
+    lint_full_url(app_type, provider, &mut findings);
+    if matches!(app_type, AppType::Claude) {
+        lint_claude_api_key(meta, &provider.settings_config, &mut findings);
+    }
+    if matches!(app_type, AppType::Codex) {
+        lint_codex_wire_api(provider, &mut findings);
+    }
+    findings
+}
+
+/// 模型目录快照中已不存在的模型
+pub(crate) fn missing_model_findings(missing: &[MissingModel]) -> Vec<LintFinding> {
+    missing
+        .iter()
+        .map(|entry| {
+            let finding = LintFinding::new(
+                "model-not-listed",
+                LintSeverity::Warning,
+                format!(
+                    "{} = {} is no longer listed by the provider",
+                    entry.field, entry.model
+                ),
+            );
+            match &entry.suggestion {
+                Some(to) => finding.with_fix(LintFix::RemapModel {
+                    from: entry.model.clone(),
+                    to: to.clone(),
+                }),
+                None => finding.suggest("Pick a model from `cc-switch provider models refresh`"),
+            }
+        })
+        .collect()
+}
+
+fn is_full_url(meta: Option<&ProviderMeta>) -> bool {
+    meta.and_then(|meta| meta.is_full_url).unwrap_or(false)
+}
+
+fn lint_base_url(
+    app_type: &AppType,
+    meta: Option<&ProviderMeta>,
+    raw: &str,
+    findings: &mut Vec<LintFinding>,
+) {
+    let url = raw.trim();
+    if url.is_empty() {
+        return;
+    }
+    if url != raw {
+        findings.push(
+            LintFinding::new(
+                "base-url-whitespace",
+                LintSeverity::Warning,
+                "Base URL has leading or trailing whitespace",
+            )
+            .with_fix(LintFix::SetBaseUrl {
+                url: url.to_string(),
+            }),
+        );
+    }
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => {
+            findings.push(
+                LintFinding::new(
+                    "base-url-invalid",
+                    LintSeverity::Error,
+                    format!("Base URL '{url}' is not a valid http(s) URL"),
+                )
+                .suggest("Use the provider's API root, e.g. https://api.example.com"),
+            );
+            return;
+        }
+    }
+    if is_full_url(meta) {
+        return;
+    }
+
+    let trimmed = url.trim_end_matches('/');
+    let lower = trimmed.to_ascii_lowercase();
+    let strip = |suffix: &str| trimmed[..trimmed.len() - suffix.len()].to_string();
+    match app_type {
+        AppType::Claude => {
+            if lower.ends_with("/v1/messages") {
+                findings.push(
+                    LintFinding::new(
+                        "base-url-endpoint",
+                        LintSeverity::Error,
+                        "Claude Code appends /v1/messages itself; requests would go to .../v1/messages/v1/messages",
+                    )
+                    .with_fix(LintFix::SetBaseUrl {
+                        url: strip("/v1/messages"),
+                    }),
+                );
+            } else if lower.ends_with("/v1") {
+                findings.push(
+                    LintFinding::new(
+                        "base-url-version",
+                        LintSeverity::Warning,
+                        "Claude Code appends /v1/messages itself; requests would go to .../v1/v1/messages",
+                    )
+                    .with_fix(LintFix::SetBaseUrl { url: strip("/v1") }),
+                );
+            } else if lower.ends_with("/chat/completions") {
+                findings.push(
+                    LintFinding::new(
+                        "base-url-endpoint",
+                        LintSeverity::Error,
+                        "Base URL points at a Chat Completions endpoint but full URL mode is off",
+                    )
+                    .suggest("Use the API root, or set apiFormat to openai_chat and turn on full URL mode"),
+                );
+            }
+        }
+        AppType::Codex => {
+            if lower.ends_with("/responses") {
+                findings.push(
+                    LintFinding::new(
+                        "base-url-endpoint",
+                        LintSeverity::Error,
+                        "Codex appends /responses itself; requests would go to .../responses/responses",
+                    )
+                    .with_fix(LintFix::SetBaseUrl {
+                        url: strip("/responses"),
+                    }),
+                );
+            }
+        }
+        AppType::Gemini => {
+            for suffix in ["/v1beta", "/v1"] {
+                if lower.ends_with(suffix) {
+                    findings.push(
+                        LintFinding::new(
+                            "base-url-version",
+                            LintSeverity::Warning,
+                            format!("Gemini CLI appends the API version itself; drop the trailing {suffix}"),
+                        )
+                        .with_fix(LintFix::SetBaseUrl { url: strip(suffix) }),
+                    );
+                    break;
+                }
+            }
+        }
+        AppType::OpenCode => {
+            if lower.ends_with("/chat/completions") {
+                findings.push(
+                    LintFinding::new(
+                        "base-url-endpoint",
+                        LintSeverity::Error,
+                        "The AI SDK appends /chat/completions itself; use the API root",
+                    )
+                    .with_fix(LintFix::SetBaseUrl {
+                        url: strip("/chat/completions"),
+                    }),
+                );
+            }
+        }
+        AppType::Hermes | AppType::OpenClaw => {}
+    }
+}
+
+fn lint_full_url(app_type: &AppType, provider: &Provider, findings: &mut Vec<LintFinding>) {
+    if !is_full_url(provider.meta.as_ref()) {
+        return;
+    }
+    if !matches!(app_type, AppType::Claude | AppType::Codex) {
+        findings.push(
+            LintFinding::new(
+                "full-url",
+                LintSeverity::Info,
+                format!("Full URL mode has no effect for {}", app_type.as_str()),
+            )
+            .with_fix(LintFix::ClearFullUrl),
+        );
+        return;
+    }
+    if provider.is_codex_oauth() {
+        return;
+    }
+    let Some(url) = base_url(app_type, provider) else {
+        return;
+    };
+    let url = url.trim().trim_end_matches('/');
+    let has_endpoint_path = url::Url::parse(url).is_ok_and(|parsed| {
+        let path = parsed.path().trim_end_matches('/').to_ascii_lowercase();
+        !path.is_empty() && !path.ends_with("/v1") && path != "/v1"
+    });
+    if !has_endpoint_path {
+        findings.push(
+            LintFinding::new(
+                "full-url",
+                LintSeverity::Warning,
+                "Full URL mode sends requests to the base URL as is, but it has no endpoint path",
+            )
+            .with_fix(LintFix::ClearFullUrl),
+        );
+    }
+}
+
+fn lint_claude_api_key(
+    meta: Option<&ProviderMeta>,
+    settings_config: &Value,
+    findings: &mut Vec<LintFinding>,
+) {
+    if let Some(raw) = meta.and_then(|meta| meta.api_key_field.as_deref()) {
+        if ClaudeApiKeyField::from_raw(raw).is_none() {
+            findings.push(
+                LintFinding::new(
+                    "api-key-field",
+                    LintSeverity::Error,
+                    format!("apiKeyField '{raw}' is not a Claude key variable"),
+                )
+                .suggest("Use ANTHROPIC_AUTH_TOKEN or ANTHROPIC_API_KEY"),
+            );
+            return;
+        }
+    }
+    let Some(env) = settings_config.get("env").and_then(Value::as_object) else {
+        return;
+    };
+    let field = ClaudeApiKeyField::from_meta_and_settings(meta, settings_config);
+    let value_of = |key: &str| {
+        env.get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let declared = value_of(field.as_env_key());
+    let alternate = value_of(field.alternate_env_key());
+
+    match (declared, alternate) {
+        (None, Some(_)) => findings.push(
+            LintFinding::new(
+                "api-key-field",
+                LintSeverity::Warning,
+                format!(
+                    "The key is stored in {} but the provider uses {}",
+                    field.alternate_env_key(),
+                    field.as_env_key()
+                ),
+            )
+            .with_fix(LintFix::MoveApiKey {
+                from: field.alternate_env_key().to_string(),
+                to: field.as_env_key().to_string(),
+            }),
+        ),
+        (Some(declared), Some(alternate)) if declared != alternate => findings.push(
+            LintFinding::new(
+                "api-key-field",
+                LintSeverity::Warning,
+                format!(
+                    "Both {} and {} are set to different keys",
+                    field.as_env_key(),
+                    field.alternate_env_key()
+                ),
+            )
+            .suggest(format!("Remove {}", field.alternate_env_key())),
+        ),
+        (None, None) => {
+            if let Some(key) = MISPLACED_CLAUDE_KEY_ENV_KEYS
+                .iter()
+                .find(|key| value_of(key).is_some())
+            {
+                findings.push(
+                    LintFinding::new(
+                        "api-key-field",
+                        LintSeverity::Warning,
+                        format!("Claude Code does not read {key}"),
+                    )
+                    .with_fix(LintFix::MoveApiKey {
+                        from: key.to_string(),
+                        to: field.as_env_key().to_string(),
+                    }),
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 归一化后的 Codex 上游协议
+fn wire_protocol(value: &str) -> Option<&'static str> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "responses" | "openai_responses" | "openai-responses" => Some("responses"),
+        "chat"
+        | "chat_completions"
+        | "chat-completions"
+        | "openai_chat"
+        | "openai-chat"
+        | "openai_chat_completions" => Some("chat"),
+        "anthropic" | "anthropic_messages" | "anthropic-messages" | "claude" | "messages" => {
+            Some("anthropic")
+        }
+        _ => None,
+    }
+}
+
+fn lint_codex_wire_api(provider: &Provider, findings: &mut Vec<LintFinding>) {
+    let api_format = provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.api_format.as_deref())
+        .or_else(|| {
+            ["api_format", "apiFormat"]
+                .iter()
+                .find_map(|key| provider.settings_config.get(*key).and_then(Value::as_str))
+        });
+    let Some(api_format) = api_format else {
+        return;
+    };
+    let Some(wire_api) = codex_config_doc(provider).and_then(|doc| {
+        active_codex_provider(&doc)
+            .and_then(|table| table.get("wire_api"))
+            .or_else(|| doc.get("wire_api"))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    }) else {
+        return;
+    };
+    let (Some(format_protocol), Some(wire_protocol)) =
+        (wire_protocol(api_format), wire_protocol(&wire_api))
+    else {
+        return;
+    };
+    if format_protocol != wire_protocol {
+        findings.push(
+            LintFinding::new(
+                "codex-wire-api",
+                LintSeverity::Warning,
+                format!(
+                    "apiFormat '{api_format}' overrides wire_api = \"{wire_api}\" in config.toml"
+                ),
+            )
+            .with_fix(LintFix::SetWireApi {
+                wire_api: format_protocol.to_string(),
+            }),
+        );
+    }
+}
+
+fn codex_config_doc(provider: &Provider) -> Option<toml::Value> {
+    provider
+        .settings_config
+        .get("config")
+        .and_then(Value::as_str)
+        .and_then(|text| text.parse::<toml::Value>().ok())
+}
+
+fn active_codex_provider(doc: &toml::Value) -> Option<&toml::Value> {
+    let name = doc.get("model_provider")?.as_str()?;
+    doc.get("model_providers")?.get(name)
+}
+
+fn active_codex_provider_table(doc: &mut toml_edit::DocumentMut) -> Option<&mut toml_edit::Item> {
+    let name = doc.get("model_provider")?.as_str()?.to_string();
+    doc.get_mut("model_providers")?
+        .get_mut(&name)
+        .filter(|item| item.is_table_like())
+}
+
+fn edit_codex_config(
+    provider: &mut Provider,
+    edit: impl FnOnce(&mut toml_edit::DocumentMut),
+) -> Result<(), AppError> {
+    let config = provider
+        .settings_config
+        .get("config")
+        .and_then(Value::as_str)
+        .ok_or_else(|| AppError::InvalidInput("Codex provider has no config.toml".to_string()))?;
+    let mut doc = config
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| AppError::InvalidInput(format!("Invalid Codex config.toml: {e}")))?;
+    edit(&mut doc);
+    provider.settings_config["config"] = Value::String(doc.to_string());
+    Ok(())
+}
+
+pub(super) fn base_url(app_type: &AppType, provider: &Provider) -> Option<String> {
+    let settings = &provider.settings_config;
+    let value = match app_type {
+        AppType::Claude => settings.get("env")?.get("ANTHROPIC_BASE_URL")?.as_str(),
+        AppType::Gemini => settings.get("env")?.get("GOOGLE_GEMINI_BASE_URL")?.as_str(),
+        AppType::OpenCode => settings.get("options")?.get("baseURL")?.as_str(),
+        AppType::Codex => {
+            let doc = codex_config_doc(provider)?;
+            return active_codex_provider(&doc)
+                .or(Some(&doc))
+                .and_then(|table| table.get("base_url"))
+                .and_then(|value| value.as_str())
+                .map(str::to_string);
+        }
+        AppType::Hermes | AppType::OpenClaw => None,
+    };
+    value.map(str::to_string)
+}
+
+fn set_base_url(app_type: &AppType, provider: &mut Provider, url: &str) -> Result<(), AppError> {
+    let settings = &mut provider.settings_config;
+    let (section, key) = match app_type {
+        AppType::Claude => ("env", "ANTHROPIC_BASE_URL"),
+        AppType::Gemini => ("env", "GOOGLE_GEMINI_BASE_URL"),
+        AppType::OpenCode => ("options", "baseURL"),
+        AppType::Codex => {
+            return edit_codex_config(provider, |doc| {
+                match active_codex_provider_table(doc) {
+                    Some(table) => table["base_url"] = toml_edit::value(url),
+                    None => doc["base_url"] = toml_edit::value(url),
+                };
+            })
+        }
+        AppType::Hermes | AppType::OpenClaw => {
+            return Err(AppError::InvalidInput(format!(
+                "Base URL fixes are not supported for {}",
+                app_type.as_str()
+            )))
+        }
+    };
+    let section = settings
+        .get_mut(section)
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| AppError::InvalidInput(format!("Provider has no {section}")))?;
+    section.insert(key.to_string(), Value::String(url.to_string()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider(settings_config: Value, meta: Option<ProviderMeta>) -> Provider {
+        let mut provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            settings_config,
+            None,
+        );
+        provider.meta = meta;
+        provider
+    }
+
+    fn rules(findings: &[LintFinding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.rule).collect()
+    }
+
+    fn fix_all(app_type: &AppType, provider: &mut Provider) {
+        for finding in lint_rules(app_type, provider) {
+            if let Some(fix) = finding.fix.filter(LintFix::is_safe) {
+                ProviderService::apply_lint_fix(app_type, provider, &fix).expect("apply fix");
+            }
+        }
+    }
+
+    #[test]
+    fn claude_duplicate_version_and_misplaced_key_are_fixed() {
+        let meta = ProviderMeta {
+            api_key_field: Some("ANTHROPIC_API_KEY".to_string()),
+            ..ProviderMeta::default()
+        };
+        let mut relay = provider(
+            json!({"env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com/v1/",
+                "ANTHROPIC_AUTH_TOKEN": "sk-test"
+            }}),
+            Some(meta),
+        );
+        let findings = lint_rules(&AppType::Claude, &relay);
+        assert_eq!(rules(&findings), vec!["base-url-version", "api-key-field"]);
+        assert!(findings.iter().all(LintFinding::has_safe_fix));
+
+        fix_all(&AppType::Claude, &mut relay);
+        let env = &relay.settings_config["env"];
+        assert_eq!(env["ANTHROPIC_BASE_URL"], "https://relay.example.com");
+        assert_eq!(env["ANTHROPIC_API_KEY"], "sk-test");
+        assert!(env.get("ANTHROPIC_AUTH_TOKEN").is_none());
+        assert!(lint_rules(&AppType::Claude, &relay).is_empty());
+    }
+
+    #[test]
+    fn full_url_without_endpoint_path_is_cleared() {
+        let meta = ProviderMeta {
+            is_full_url: Some(true),
+            ..ProviderMeta::default()
+        };
+        let mut relay = provider(
+            json!({"env": {"ANTHROPIC_BASE_URL": "https://relay.example.com/v1", "ANTHROPIC_AUTH_TOKEN": "sk"}}),
+            Some(meta.clone()),
+        );
+        assert_eq!(
+            rules(&lint_rules(&AppType::Claude, &relay)),
+            vec!["full-url"]
+        );
+        fix_all(&AppType::Claude, &mut relay);
+        assert_eq!(relay.meta.as_ref().and_then(|meta| meta.is_full_url), None);
+
+        let full = provider(
+            json!({"env": {"ANTHROPIC_BASE_URL": "https://relay.example.com/api/chat/completions", "ANTHROPIC_AUTH_TOKEN": "sk"}}),
+            Some(meta),
+        );
+        assert!(lint_rules(&AppType::Claude, &full).is_empty());
+    }
+
+    #[test]
+    fn codex_wire_api_mismatch_is_reported_but_not_safe() {
+        let meta = ProviderMeta {
+            api_format: Some("openai_chat".to_string()),
+            ..ProviderMeta::default()
+        };
+        let mut relay = provider(
+            json!({"config": "model_provider = \"relay\"\n\n[model_providers.relay]\nbase_url = \"https://relay.example.com/v1/responses\"\nwire_api = \"responses\"\n"}),
+            Some(meta),
+        );
+        let findings = lint_rules(&AppType::Codex, &relay);
+        assert_eq!(
+            rules(&findings),
+            vec!["base-url-endpoint", "codex-wire-api"]
+        );
+        assert!(!findings[1].has_safe_fix());
+
+        fix_all(&AppType::Codex, &mut relay);
+        let config = relay.settings_config["config"].as_str().expect("config");
+        assert!(config.contains("base_url = \"https://relay.example.com/v1\""));
+        assert!(config.contains("wire_api = \"responses\""));
+
+        let fix = findings[1].fix.clone().expect("wire_api fix");
+        ProviderService::apply_lint_fix(&AppType::Codex, &mut relay, &fix).expect("apply");
+        assert!(lint_rules(&AppType::Codex, &relay).is_empty());
+    }
+
+    #[test]
+    fn invalid_base_url_is_an_error() {
+        let relay = provider(
+            json!({"env": {"GOOGLE_GEMINI_BASE_URL": "relay.example.com/v1beta"}}),
+            None,
+        );
+        let findings = lint_rules(&AppType::Gemini, &relay);
+        assert_eq!(rules(&findings), vec!["base-url-invalid"]);
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+    }
+}
@@ -4,14 +4,19 @@ mod claude;
 mod codex;
 #[cfg(test)]
 mod codex_openai_auth_tests;
+mod codex_profiles;
 mod common;
 mod common_config;
 mod endpoints;
+#[cfg(feature = "cli")]
+mod foreign;
 mod gemini;
 mod gemini_auth;
+#[cfg(feature = "cli")]
+mod lint;
 mod live;
 pub(crate) mod live_merge;
-mod models;
+pub(crate) mod models;
 #[cfg(test)]
 mod tests;
 mod usage;
@@ -28,7 +33,9 @@ use crate::config::{
 };
 use crate::error::AppError;
 use crate::provider::{Provider, ProviderMeta, UsageScript};
+use crate::services::config_history::{self, HistoryAction, HistoryKind};
 use crate::services::mcp::McpService;
+use crate::services::switch_history::{self, SwitchSource};
 use crate::store::AppState;
 
 use gemini_auth::GeminiAuthType;
@@ -37,6 +44,10 @@ use live::LiveSnapshot;
 pub use common::migrate_legacy_codex_config;
 #[cfg(test)]
 use common::strip_codex_common_config_from_full_text;
+#[cfg(feature = "cli")]
+pub use foreign::{ForeignCandidate, ForeignSource};
+#[cfg(feature = "cli")]
+pub use lint::{LintFinding, LintSeverity};
 
 /// 统一会话开关变更后，立即按新开关状态重写当前官方 Codex 供应商的
 /// live 配置，使开关即时生效（无需等下一次切换）。
@@ -888,6 +899,10 @@ impl ProviderService {
             use crate::services::mcp::McpService;
             McpService::sync_all_enabled(state)?;
         }
+        crate::services::agent_settings::AgentSettingsService::sync_app_best_effort(
+            state,
+            &prepared.action.app_type,
+        );
         if !prepared.action.takeover_active
             && prepared.action.refresh_snapshot
             && crate::sync_policy::should_sync_live(&prepared.action.app_type)
@@ -922,6 +937,7 @@ impl ProviderService {
                 }
                 let mut live_after = read_json_file::<Value>(&settings_path)?;
                 let _ = Self::normalize_claude_models_in_value(&mut live_after);
+                crate::services::agent_settings::strip_projected(app_type, &mut live_after);
 
                 let (provider, common_snippet) = {
                     let guard = state.config.read().map_err(AppError::from)?;
@@ -950,7 +966,8 @@ impl ProviderService {
                     let mut guard = state.config.write().map_err(AppError::from)?;
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
-                            target.settings_config = live_after;
+                            target.settings_config =
+                                crate::key_pool::restore_stored(app_type, target, live_after);
                         }
                     }
                 }
@@ -1086,6 +1103,11 @@ impl ProviderService {
                     }
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
+                            snapshot_provider.settings_config = crate::key_pool::restore_stored(
+                                app_type,
+                                target,
+                                snapshot_provider.settings_config,
+                            );
                             *target = snapshot_provider;
                         }
                     }
@@ -1109,11 +1131,12 @@ impl ProviderService {
                 let mut live_after = env_to_json(&env_map);
 
                 let settings_path = get_gemini_settings_path();
-                let config_value = if settings_path.exists() {
+                let mut config_value = if settings_path.exists() {
                     read_json_file(&settings_path)?
                 } else {
                     json!({})
                 };
+                crate::services::agent_settings::strip_projected(app_type, &mut config_value);
 
                 if let Some(obj) = live_after.as_object_mut() {
                     obj.insert("config".to_string(), config_value);
@@ -1147,7 +1170,8 @@ impl ProviderService {
                     let mut guard = state.config.write().map_err(AppError::from)?;
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
-                            target.settings_config = live_after;
+                            target.settings_config =
+                                crate::key_pool::restore_stored(app_type, target, live_after);
                         }
                     }
                 }
@@ -1167,7 +1191,8 @@ impl ProviderService {
                     let mut guard = state.config.write().map_err(AppError::from)?;
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
-                            target.settings_config = live_after;
+                            target.settings_config =
+                                crate::key_pool::restore_stored(app_type, target, live_after);
                         }
                     }
                 }
@@ -1187,7 +1212,8 @@ impl ProviderService {
                     let mut guard = state.config.write().map_err(AppError::from)?;
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
-                            target.settings_config = live_after;
+                            target.settings_config =
+                                crate::key_pool::restore_stored(app_type, target, live_after);
                         }
                     }
                 }
@@ -1207,7 +1233,8 @@ impl ProviderService {
                     let mut guard = state.config.write().map_err(AppError::from)?;
                     if let Some(manager) = guard.get_manager_mut(app_type) {
                         if let Some(target) = manager.providers.get_mut(provider_id) {
-                            target.settings_config = live_after;
+                            target.settings_config =
+                                crate::key_pool::restore_stored(app_type, target, live_after);
                         }
                     }
                 }
@@ -1909,11 +1936,44 @@ impl ProviderService {
 
     /// 新增供应商
     pub fn add(state: &AppState, app_type: AppType, provider: Provider) -> Result<bool, AppError> {
+        let provider_id = provider.id.clone();
+        let added = Self::add_entry(state, app_type.clone(), provider)?;
+        Self::record_history(state, &app_type, &provider_id, HistoryAction::Add);
+        Self::sync_codex_profiles_best_effort(state, &app_type);
+        Ok(added)
+    }
+
+    /// 记录供应商当前存储内容的历史版本（失败只记日志）
+    fn record_history(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+        action: HistoryAction,
+    ) {
+        if let Ok(Some(provider)) = state.db.get_provider_by_id(provider_id, app_type.as_str()) {
+            config_history::record_best_effort(
+                &state.db,
+                HistoryKind::Provider,
+                Some(app_type),
+                provider_id,
+                action,
+                &provider,
+            );
+        }
+    }
+
+    fn add_entry(
+        state: &AppState,
+        app_type: AppType,
+        provider: Provider,
+    ) -> Result<bool, AppError> {
         let mut provider = provider;
         // 归一化 Claude 模型键
         Self::normalize_provider_if_claude(&app_type, &mut provider);
         Self::inject_coding_plan_usage_script(&app_type, &mut provider);
         Self::validate_provider_settings(&app_type, &provider)?;
+        // 加密库已解锁时，新凭据以密文保存
+        crate::vault::seal_provider_on_save(&state.db, &mut provider);
 
         let app_type_clone = app_type.clone();
         let provider_clone = provider.clone();
@@ -2003,11 +2063,24 @@ impl ProviderService {
         state: &AppState,
         app_type: AppType,
         provider: Provider,
+    ) -> Result<bool, AppError> {
+        let provider_id = provider.id.clone();
+        let updated = Self::update_entry(state, app_type.clone(), provider)?;
+        Self::record_history(state, &app_type, &provider_id, HistoryAction::Update);
+        Self::sync_codex_profiles_best_effort(state, &app_type);
+        Ok(updated)
+    }
+
+    fn update_entry(
+        state: &AppState,
+        app_type: AppType,
+        provider: Provider,
     ) -> Result<bool, AppError> {
         let mut provider = provider;
         // 归一化 Claude 模型键
         Self::normalize_provider_if_claude(&app_type, &mut provider);
         Self::validate_provider_settings(&app_type, &provider)?;
+        crate::vault::seal_provider_on_save(&state.db, &mut provider);
         let provider_id = provider.id.clone();
         let app_type_clone = app_type.clone();
         let provider_clone = provider.clone();
@@ -2691,6 +2764,10 @@ impl ProviderService {
             log::warn!("sync_current_to_live: MCP 同步失败: {e}");
         }
 
+        if let Err(e) = crate::services::agent_settings::AgentSettingsService::sync(state) {
+            log::warn!("sync_current_to_live: hooks/权限同步失败: {e}");
+        }
+
         if let Err(e) = crate::services::skill::SkillService::sync_all_enabled_best_effort() {
             log::warn!("sync_current_to_live: Skills 同步失败: {e}");
         }
@@ -2779,6 +2856,42 @@ impl ProviderService {
 
     /// 切换指定应用的供应商
     pub fn switch(state: &AppState, app_type: AppType, provider_id: &str) -> Result<(), AppError> {
+        Self::switch_with_source(state, app_type, provider_id, SwitchSource::Other)
+    }
+
+    /// 切换供应商，并以 `source` 记录到切换历史
+    pub fn switch_with_source(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        source: SwitchSource,
+    ) -> Result<(), AppError> {
+        let previous = if app_type.is_additive_mode() {
+            None
+        } else {
+            crate::settings::get_effective_current_provider(&state.db, &app_type)
+                .ok()
+                .flatten()
+        };
+        Self::switch_entry(state, app_type.clone(), provider_id)?;
+        if !app_type.is_additive_mode() {
+            switch_history::record_best_effort(
+                &state.db,
+                &app_type,
+                previous.as_deref(),
+                provider_id,
+                source,
+            );
+        }
+        Self::sync_codex_profiles_best_effort(state, &app_type);
+        Ok(())
+    }
+
+    fn switch_entry(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<(), AppError> {
         if !app_type.is_additive_mode() {
             let providers = state.db.get_all_providers(app_type.as_str())?;
             providers.get(provider_id).ok_or_else(|| {
@@ -2819,6 +2932,9 @@ impl ProviderService {
             }
         }
 
+        let _switch_guard = futures::executor::block_on(
+            state.proxy_service.lock_switches_for_app(app_type.as_str()),
+        );
         let app_type_clone = app_type.clone();
         let provider_id_owned = provider_id.to_string();
         let effective_current_provider = if app_type.is_additive_mode() {
@@ -2875,6 +2991,9 @@ impl ProviderService {
         previous_common_config_snippet: Option<&str>,
         apply_common_config: bool,
     ) -> Result<PreparedLiveWrite, AppError> {
+        // 密钥引用只在写入 live 配置时解析，数据库中始终保存引用本身；
+        // 有 Key 池时写入当前可用的 Key
+        let provider = &crate::key_pool::resolve_for_live(app_type, provider)?;
         let apply_common_config = Self::resolve_live_apply_common_config(
             app_type,
             provider,
@@ -3384,6 +3503,69 @@ impl ProviderService {
     }
 
     pub fn delete(state: &AppState, app_type: AppType, provider_id: &str) -> Result<(), AppError> {
+        let previous = state
+            .db
+            .get_provider_by_id(provider_id, app_type.as_str())
+            .ok()
+            .flatten();
+        Self::delete_entry(state, app_type.clone(), provider_id)?;
+        if let Some(previous) = previous {
+            config_history::record_best_effort(
+                &state.db,
+                HistoryKind::Provider,
+                Some(&app_type),
+                provider_id,
+                HistoryAction::Delete,
+                &previous,
+            );
+        }
+        Self::sync_codex_profiles_best_effort(state, &app_type);
+        Ok(())
+    }
+
+    /// 将供应商恢复到编辑历史中的某个版本；走正常的新增/更新流程，live 配置随之重写
+    pub fn revert(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        rev: i64,
+    ) -> Result<Provider, AppError> {
+        let revision = config_history::find_revision(
+            HistoryKind::Provider,
+            Some(&app_type),
+            provider_id,
+            rev,
+        )?;
+        // 历史中未加密的凭据只记录了掩码，恢复时沿用当前保存的值
+        let current = state
+            .db
+            .get_provider_by_id(provider_id, app_type.as_str())?
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|source| AppError::JsonSerialize { source })?;
+        let mut snapshot = revision.snapshot;
+        config_history::restore_masked(&mut snapshot, current.as_ref());
+        let provider: Provider = serde_json::from_value(snapshot).map_err(|e| {
+            AppError::localized(
+                "provider.history.invalid",
+                format!("历史版本 {rev} 无法解析: {e}"),
+                format!("Revision {rev} cannot be parsed: {e}"),
+            )
+        })?;
+
+        if current.is_some() {
+            Self::update(state, app_type, provider.clone())?;
+        } else {
+            Self::add(state, app_type, provider.clone())?;
+        }
+        Ok(provider)
+    }
+
+    fn delete_entry(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<(), AppError> {
         let (local_current_provider, stored_current_provider) = if app_type.is_additive_mode() {
             (None, None)
         } else {
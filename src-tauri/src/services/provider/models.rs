@@ -3,10 +3,15 @@ use serde_json::Value;
 use std::collections::HashSet;
 use std::time::Duration;
 
+use crate::app_config::AppType;
 use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::{CodexOAuthService, StreamCheckService};
 
 use super::ProviderService;
 
+const AUTH_PROVIDER_CODEX_OAUTH: &str = "codex_oauth";
+
 const KNOWN_COMPAT_SUFFIXES: &[&str] = &[
     "/api/claudecode",
     "/api/anthropic",
@@ -186,9 +191,460 @@ fn strip_compat_suffix(base: &str) -> Option<&str> {
     })
 }
 
+/// 请求模型列表时的鉴权方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModelFetchStrategy {
+    Bearer,
+    Anthropic,
+    GoogleApiKey,
+}
+
+/// 由 base URL 推导候选的 models 接口地址（去重，按尝试顺序）。
+pub(crate) fn build_model_fetch_candidate_urls(
+    base_url: &str,
+    strategy: ModelFetchStrategy,
+    is_full_url: bool,
+) -> Vec<String> {
+    let base = base_url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return Vec::new();
+    }
+
+    if is_full_url {
+        let mut urls = Vec::new();
+        if let Some(index) = base.find("/v1/") {
+            urls.push(format!("{}/v1/models", &base[..index]));
+        } else if let Some(index) = base.rfind('/') {
+            let root = &base[..index];
+            if root
+                .find("://")
+                .is_some_and(|scheme| root.len() > scheme.saturating_add(3))
+            {
+                urls.push(format!("{root}/v1/models"));
+            }
+        }
+        return urls;
+    }
+
+    if base.ends_with("/models") {
+        return vec![base.to_string()];
+    }
+
+    let append_models = format!("{base}/models");
+    let append_versioned_models = if base.ends_with("/v1") || base.ends_with("/v1beta") {
+        None
+    } else {
+        Some(format!("{base}/v1/models"))
+    };
+
+    let mut urls: Vec<String> = Vec::new();
+    match strategy {
+        ModelFetchStrategy::Anthropic => {
+            if let Some(versioned) = append_versioned_models.as_ref() {
+                urls.push(versioned.clone());
+            } else {
+                urls.push(append_models.clone());
+            }
+
+            if let Some(stripped) = strip_compat_suffix(base) {
+                let root = stripped.trim_end_matches('/');
+                if !root.is_empty() && root.contains("://") {
+                    urls.push(format!("{root}/v1/models"));
+                    urls.push(format!("{root}/models"));
+                }
+            } else if append_versioned_models.is_some() {
+                urls.push(append_models);
+            }
+        }
+        ModelFetchStrategy::Bearer | ModelFetchStrategy::GoogleApiKey => {
+            urls.push(append_models);
+            if let Some(v1) = append_versioned_models.as_ref() {
+                urls.push(v1.clone());
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    urls.retain(|url| seen.insert(url.clone()));
+    urls
+}
+
+/// 兼容 OpenAI `data[].id`、Gemini `models[].name` 与裸数组三种响应。
+pub(crate) fn parse_model_ids_from_response(payload: &Value) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+
+    if let Some(data) = payload.get("data").and_then(|v| v.as_array()) {
+        for item in data {
+            if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
+                out.push(id.to_string());
+            }
+        }
+    }
+
+    if out.is_empty() {
+        if let Some(models) = payload.get("models").and_then(|v| v.as_array()) {
+            for item in models {
+                if let Some(name) = item.get("name").and_then(|v| v.as_str()) {
+                    out.push(name.strip_prefix("models/").unwrap_or(name).to_string());
+                }
+            }
+        }
+    }
+
+    if out.is_empty() {
+        if let Some(arr) = payload.as_array() {
+            for item in arr {
+                if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
+                    out.push(id.to_string());
+                }
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    out.retain(|model| seen.insert(model.clone()));
+    out
+}
+
+/// 依次尝试候选的 models 接口，返回第一个成功响应中的模型 id。
+pub(crate) async fn fetch_model_ids(
+    base_url: &str,
+    is_full_url: bool,
+    api_key: Option<&str>,
+    custom_user_agent: Option<&str>,
+    strategy: ModelFetchStrategy,
+) -> Result<Vec<String>, String> {
+    let candidate_urls = build_model_fetch_candidate_urls(base_url, strategy, is_full_url);
+    if candidate_urls.is_empty() {
+        return Err(if is_full_url && !base_url.trim().is_empty() {
+            "Cannot derive models endpoint from full URL".to_string()
+        } else {
+            "URL cannot be empty".to_string()
+        });
+    }
+
+    let client = crate::proxy::http_client::get();
+
+    let key = api_key.map(str::trim).filter(|k| !k.is_empty());
+    let custom_user_agent = crate::provider::parse_custom_user_agent(custom_user_agent)
+        .ok()
+        .flatten();
+    let mut last_err = String::from("unknown error");
+
+    for url in candidate_urls {
+        let mut req = client.get(&url).timeout(Duration::from_secs(5));
+        if let Some(key) = key {
+            req = match strategy {
+                ModelFetchStrategy::Bearer => req.header("Authorization", format!("Bearer {key}")),
+                ModelFetchStrategy::Anthropic => req
+                    .header("Authorization", format!("Bearer {key}"))
+                    .header("x-api-key", key)
+                    .header("anthropic-version", "2023-06-01"),
+                ModelFetchStrategy::GoogleApiKey => req.header("x-goog-api-key", key),
+            };
+        }
+        if let Some(user_agent) = &custom_user_agent {
+            req = req.header(reqwest::header::USER_AGENT, user_agent.clone());
+        }
+
+        match req.send().await {
+            Ok(resp) => {
+                let status = resp.status();
+                if !status.is_success() {
+                    last_err = format!("HTTP {status} ({url})");
+                    if status != reqwest::StatusCode::NOT_FOUND
+                        && status != reqwest::StatusCode::METHOD_NOT_ALLOWED
+                    {
+                        return Err(last_err);
+                    }
+                    continue;
+                }
+                match resp.json::<Value>().await {
+                    Ok(payload) => {
+                        let models = parse_model_ids_from_response(&payload);
+                        if models.is_empty() {
+                            last_err = format!("No model list found in response ({url})");
+                        } else {
+                            return Ok(models);
+                        }
+                    }
+                    Err(err) => {
+                        last_err = format!("Invalid JSON response ({url}): {err}");
+                    }
+                }
+            }
+            Err(err) => {
+                last_err = err.to_string();
+            }
+        }
+    }
+
+    Err(last_err)
+}
+
+/// 已保存供应商的模型列表来源：HTTP 接口，或 Codex OAuth 托管账号。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ModelFetchSource {
+    Http(ModelFetchTarget),
+    CodexOAuth { account_id: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModelFetchTarget {
+    pub(crate) base_url: String,
+    pub(crate) is_full_url: bool,
+    pub(crate) auth_value: Option<String>,
+    pub(crate) custom_user_agent: Option<String>,
+    pub(crate) strategy: ModelFetchStrategy,
+}
+
+pub(crate) async fn fetch_models_from_source(
+    source: &ModelFetchSource,
+) -> Result<Vec<String>, AppError> {
+    match &source {
+        ModelFetchSource::Http(target) => fetch_model_ids(
+            &target.base_url,
+            target.is_full_url,
+            target.auth_value.as_deref(),
+            target.custom_user_agent.as_deref(),
+            target.strategy,
+        )
+        .await
+        .map_err(AppError::Message),
+        ModelFetchSource::CodexOAuth { account_id } => {
+            CodexOAuthService::get_models(account_id.as_deref())
+                .await
+                .map(|models| models.into_iter().map(|model| model.id).collect())
+                .map_err(AppError::Message)
+        }
+    }
+}
+
+/// 拉取已保存供应商的模型列表（与 `provider fetch-models` 相同的请求），
+/// 先解析密钥引用。
+pub(crate) async fn fetch_provider_model_ids(
+    provider: &Provider,
+    app_type: &AppType,
+) -> Result<Vec<String>, AppError> {
+    let provider = crate::key_pool::resolve_for_live_async(app_type, provider).await?;
+    let source = model_fetch_source(&provider, app_type)?;
+    fetch_models_from_source(&source).await
+}
+
+fn model_fetch_target(
+    provider: &Provider,
+    app_type: &AppType,
+) -> Result<ModelFetchTarget, AppError> {
+    let base_url = StreamCheckService::extract_base_url(provider, app_type)?;
+    let base_url = base_url.trim().trim_end_matches('/').to_string();
+    if base_url.is_empty() {
+        return Err(AppError::Message(format!(
+            "No API URL configured for provider '{}'",
+            provider.id
+        )));
+    }
+    let custom_user_agent = provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.custom_user_agent.clone());
+    let is_full_url = matches!(app_type, AppType::Claude | AppType::Codex)
+        && !provider.is_codex_oauth()
+        && provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.is_full_url)
+            .unwrap_or(false);
+
+    match app_type {
+        AppType::Claude => {
+            let auth_value = StreamCheckService::extract_claude_key(provider).ok_or_else(|| {
+                AppError::Message(format!("Missing API key for provider '{}'", provider.id))
+            })?;
+            let strategy = if claude_uses_bearer_auth(provider, &base_url) {
+                ModelFetchStrategy::Bearer
+            } else {
+                ModelFetchStrategy::Anthropic
+            };
+
+            Ok(ModelFetchTarget {
+                base_url,
+                is_full_url,
+                auth_value: Some(auth_value),
+                custom_user_agent,
+                strategy,
+            })
+        }
+        AppType::Codex => {
+            Ok(ModelFetchTarget {
+                base_url,
+                is_full_url,
+                auth_value: Some(StreamCheckService::extract_codex_key(provider).ok_or_else(
+                    || AppError::Message(format!("Missing API key for provider '{}'", provider.id)),
+                )?),
+                custom_user_agent,
+                strategy: ModelFetchStrategy::Bearer,
+            })
+        }
+        AppType::Gemini => {
+            let (auth_value, strategy) = extract_gemini_model_fetch_auth(provider)?;
+            Ok(ModelFetchTarget {
+                base_url,
+                is_full_url,
+                auth_value: Some(auth_value),
+                custom_user_agent,
+                strategy,
+            })
+        }
+        AppType::OpenCode => Ok(ModelFetchTarget {
+            base_url,
+            is_full_url,
+            auth_value: Some(
+                provider
+                    .settings_config
+                    .get("options")
+                    .and_then(|options| options.get("apiKey"))
+                    .and_then(|value| value.as_str())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
+                    })?,
+            ),
+            custom_user_agent,
+            strategy: ModelFetchStrategy::Bearer,
+        }),
+        AppType::Hermes => Ok(ModelFetchTarget {
+            base_url,
+            is_full_url,
+            auth_value: Some(
+                provider
+                    .settings_config
+                    .get("apiKey")
+                    .or_else(|| provider.settings_config.get("api_key"))
+                    .and_then(|value| value.as_str())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
+                    })?,
+            ),
+            custom_user_agent,
+            strategy: ModelFetchStrategy::Bearer,
+        }),
+        AppType::OpenClaw => Ok(ModelFetchTarget {
+            base_url,
+            is_full_url,
+            auth_value: Some(
+                provider
+                    .settings_config
+                    .get("apiKey")
+                    .and_then(|value| value.as_str())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        AppError::Message(format!("Missing API key for provider '{}'", provider.id))
+                    })?,
+            ),
+            custom_user_agent,
+            strategy: ModelFetchStrategy::Bearer,
+        }),
+    }
+}
+
+pub(crate) fn model_fetch_source(
+    provider: &Provider,
+    app_type: &AppType,
+) -> Result<ModelFetchSource, AppError> {
+    if matches!(app_type, AppType::Claude) && provider.is_codex_oauth() {
+        return Ok(ModelFetchSource::CodexOAuth {
+            account_id: codex_oauth_account_id(provider),
+        });
+    }
+
+    model_fetch_target(provider, app_type).map(ModelFetchSource::Http)
+}
+
+fn codex_oauth_account_id(provider: &Provider) -> Option<String> {
+    provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.managed_account_id_for(AUTH_PROVIDER_CODEX_OAUTH))
+}
+
+fn claude_uses_bearer_auth(provider: &Provider, base_url: &str) -> bool {
+    if base_url.contains("openrouter.ai") {
+        return true;
+    }
+
+    provider
+        .settings_config
+        .get("auth_mode")
+        .and_then(|value| value.as_str())
+        .or_else(|| {
+            provider
+                .settings_config
+                .get("env")
+                .and_then(|env| env.get("AUTH_MODE"))
+                .and_then(|value| value.as_str())
+        })
+        .is_some_and(|value| value == "bearer_only")
+}
+
+fn extract_gemini_model_fetch_auth(
+    provider: &Provider,
+) -> Result<(String, ModelFetchStrategy), AppError> {
+    let env_map = crate::gemini_config::json_to_env(&provider.settings_config)?;
+
+    if let Some(token) = env_map
+        .get("GOOGLE_ACCESS_TOKEN")
+        .or_else(|| env_map.get("GEMINI_ACCESS_TOKEN"))
+        .map(String::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        return Ok((token.to_string(), ModelFetchStrategy::Bearer));
+    }
+
+    let key = env_map
+        .get("GEMINI_API_KEY")
+        .or_else(|| env_map.get("GOOGLE_API_KEY"))
+        .or_else(|| env_map.get("API_KEY"))
+        .map(String::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            AppError::Message(format!("Missing API key for provider '{}'", provider.id))
+        })?;
+
+    if key.starts_with("ya29.") {
+        return Ok((key.to_string(), ModelFetchStrategy::Bearer));
+    }
+
+    if let Some(access_token) = parse_access_token_blob(key) {
+        return Ok((access_token, ModelFetchStrategy::Bearer));
+    }
+
+    Ok((key.to_string(), ModelFetchStrategy::GoogleApiKey))
+}
+
+fn parse_access_token_blob(raw: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(raw.trim()).ok()?;
+    value
+        .get("access_token")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::build_provider_model_candidate_urls;
+    use super::*;
+    use crate::provider::{AuthBinding, AuthBindingSource, ProviderMeta};
+    use serde_json::json;
 
     #[test]
     fn model_candidates_strip_deepseek_anthropic_suffix() {
@@ -203,4 +659,395 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn model_fetch_candidate_urls_prefers_v1_for_anthropic_base() {
+        let urls = build_model_fetch_candidate_urls(
+            "https://api.anthropic.com",
+            ModelFetchStrategy::Anthropic,
+            false,
+        );
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.anthropic.com/v1/models".to_string(),
+                "https://api.anthropic.com/models".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn model_fetch_candidate_urls_strip_anthropic_compat_suffix() {
+        let urls = build_model_fetch_candidate_urls(
+            "https://api.deepseek.com/anthropic",
+            ModelFetchStrategy::Anthropic,
+            false,
+        );
+        assert_eq!(
+            urls,
+            vec![
+                "https://api.deepseek.com/anthropic/v1/models".to_string(),
+                "https://api.deepseek.com/v1/models".to_string(),
+                "https://api.deepseek.com/models".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn model_fetch_candidate_urls_for_gemini_v1beta_keeps_models_endpoint() {
+        let urls = build_model_fetch_candidate_urls(
+            "https://generativelanguage.googleapis.com/v1beta",
+            ModelFetchStrategy::GoogleApiKey,
+            false,
+        );
+        assert_eq!(
+            urls,
+            vec!["https://generativelanguage.googleapis.com/v1beta/models".to_string()]
+        );
+    }
+
+    #[test]
+    fn model_fetch_candidate_urls_derive_models_endpoint_from_full_url() {
+        assert_eq!(
+            build_model_fetch_candidate_urls(
+                "https://relay.example/v1/chat/completions?api-version=2026-01-01",
+                ModelFetchStrategy::Bearer,
+                true,
+            ),
+            vec!["https://relay.example/v1/models".to_string()]
+        );
+        assert_eq!(
+            build_model_fetch_candidate_urls(
+                "https://relay.example/custom/chat/completions",
+                ModelFetchStrategy::Anthropic,
+                true,
+            ),
+            vec!["https://relay.example/custom/chat/v1/models".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_model_ids_supports_multiple_shapes_and_dedups_stably() {
+        let data_payload = json!({
+            "data": [
+                {"id": "gpt-4o"},
+                {"id": "gpt-4o-mini"},
+                {"id": "gpt-4o"},
+                {"id": "o3"}
+            ]
+        });
+        assert_eq!(
+            parse_model_ids_from_response(&data_payload),
+            vec!["gpt-4o", "gpt-4o-mini", "o3"]
+        );
+
+        let gemini_payload = json!({
+            "models": [
+                {"name": "models/gemini-2.0-pro"},
+                {"name": "models/gemini-2.0-flash"}
+            ]
+        });
+        assert_eq!(
+            parse_model_ids_from_response(&gemini_payload),
+            vec!["gemini-2.0-pro", "gemini-2.0-flash"]
+        );
+    }
+
+    #[tokio::test]
+    async fn model_fetch_full_url_reports_when_models_endpoint_cannot_be_derived() {
+        let error = fetch_model_ids(
+            "https://relay.example",
+            true,
+            None,
+            None,
+            ModelFetchStrategy::Bearer,
+        )
+        .await
+        .expect_err("origin-only full URL should not invent a models endpoint");
+
+        assert_eq!(error, "Cannot derive models endpoint from full URL");
+    }
+
+    #[tokio::test]
+    async fn model_fetch_sends_trimmed_custom_user_agent() {
+        use std::sync::{Arc, Mutex};
+
+        use axum::{http::HeaderMap, routing::get, Router};
+
+        let observed_user_agent = Arc::new(Mutex::new(None::<String>));
+        let handler_user_agent = Arc::clone(&observed_user_agent);
+        let app = Router::new().route(
+            "/v1/models",
+            get(move |headers: HeaderMap| {
+                let observed_user_agent = Arc::clone(&handler_user_agent);
+                async move {
+                    let user_agent = headers
+                        .get(reqwest::header::USER_AGENT)
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_string);
+                    *observed_user_agent
+                        .lock()
+                        .expect("capture model fetch user agent") = user_agent;
+                    axum::Json(json!({ "data": [{ "id": "model-a" }] }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind model fetch test server");
+        let address = listener.local_addr().expect("model fetch listener address");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("model fetch test server should run");
+        });
+
+        let models = fetch_model_ids(
+            &format!("http://{address}"),
+            false,
+            Some("sk-test"),
+            Some("  cc-switch-model-fetch/test  "),
+            ModelFetchStrategy::Bearer,
+        )
+        .await
+        .expect("model fetch should succeed");
+        server.abort();
+
+        assert_eq!(models, vec!["model-a"]);
+        assert_eq!(
+            observed_user_agent
+                .lock()
+                .expect("read model fetch user agent")
+                .as_deref(),
+            Some("cc-switch-model-fetch/test")
+        );
+    }
+
+    #[test]
+    fn model_fetch_target_for_claude_uses_base_url_and_api_key() {
+        let provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://claude.example.com",
+                    "ANTHROPIC_API_KEY": "sk-claude"
+                }
+            }),
+            None,
+        );
+
+        let target = model_fetch_target(&provider, &AppType::Claude)
+            .expect("claude provider should resolve fetch target");
+
+        assert_eq!(target.base_url, "https://claude.example.com");
+        assert_eq!(target.auth_value.as_deref(), Some("sk-claude"));
+        assert_eq!(target.strategy, ModelFetchStrategy::Anthropic);
+    }
+
+    #[test]
+    fn saved_provider_model_fetch_target_keeps_custom_user_agent() {
+        let mut provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://claude.example.com",
+                    "ANTHROPIC_API_KEY": "sk-claude"
+                }
+            }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            custom_user_agent: Some("cc-switch-model-fetch/test".to_string()),
+            is_full_url: Some(true),
+            ..Default::default()
+        });
+
+        let target = model_fetch_target(&provider, &AppType::Claude)
+            .expect("saved provider should resolve fetch target");
+
+        assert_eq!(
+            target.custom_user_agent.as_deref(),
+            Some("cc-switch-model-fetch/test")
+        );
+        assert!(target.is_full_url);
+    }
+
+    #[test]
+    fn model_fetch_target_for_claude_supports_openrouter_bearer_mode() {
+        let provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://openrouter.ai/api/v1",
+                    "OPENROUTER_API_KEY": "sk-openrouter"
+                }
+            }),
+            None,
+        );
+
+        let target = model_fetch_target(&provider, &AppType::Claude)
+            .expect("openrouter provider should resolve fetch target");
+
+        assert_eq!(target.strategy, ModelFetchStrategy::Bearer);
+        assert_eq!(target.auth_value.as_deref(), Some("sk-openrouter"));
+    }
+
+    #[test]
+    fn model_fetch_source_for_claude_codex_oauth_uses_managed_auth_without_config_key() {
+        let mut provider = Provider::with_id(
+            "codex".to_string(),
+            "Codex OAuth".to_string(),
+            json!({ "env": {} }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            provider_type: Some("codex_oauth".to_string()),
+            ..Default::default()
+        });
+
+        let source = model_fetch_source(&provider, &AppType::Claude)
+            .expect("codex oauth provider should use managed auth");
+
+        assert_eq!(source, ModelFetchSource::CodexOAuth { account_id: None });
+    }
+
+    #[test]
+    fn model_fetch_source_for_claude_codex_oauth_keeps_bound_account_id() {
+        let mut provider = Provider::with_id(
+            "codex".to_string(),
+            "Codex OAuth".to_string(),
+            json!({ "env": {} }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            provider_type: Some("codex_oauth".to_string()),
+            auth_binding: Some(AuthBinding {
+                source: AuthBindingSource::ManagedAccount,
+                auth_provider: Some("codex_oauth".to_string()),
+                account_id: Some("acc-123".to_string()),
+            }),
+            ..Default::default()
+        });
+
+        let source = model_fetch_source(&provider, &AppType::Claude)
+            .expect("codex oauth provider should use managed auth");
+
+        assert_eq!(
+            source,
+            ModelFetchSource::CodexOAuth {
+                account_id: Some("acc-123".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn model_fetch_target_for_codex_supports_env_openai_key() {
+        let provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "env": {
+                    "OPENAI_API_KEY": "sk-codex-env"
+                },
+                "config": "model_provider = \"demo\"\n\n[model_providers.demo]\nbase_url = \"https://codex.example.com/v1\"\n"
+            }),
+            None,
+        );
+
+        let target = model_fetch_target(&provider, &AppType::Codex)
+            .expect("codex provider should resolve fetch target");
+
+        assert_eq!(target.base_url, "https://codex.example.com/v1");
+        assert_eq!(target.auth_value.as_deref(), Some("sk-codex-env"));
+        assert_eq!(target.strategy, ModelFetchStrategy::Bearer);
+    }
+
+    #[test]
+    fn model_fetch_target_for_gemini_supports_access_token() {
+        let provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "env": {
+                    "GOOGLE_GEMINI_BASE_URL": "https://generativelanguage.googleapis.com",
+                    "GOOGLE_ACCESS_TOKEN": "ya29.token"
+                }
+            }),
+            None,
+        );
+
+        let target = model_fetch_target(&provider, &AppType::Gemini)
+            .expect("gemini provider should resolve oauth fetch target");
+
+        assert_eq!(target.auth_value.as_deref(), Some("ya29.token"));
+        assert_eq!(target.strategy, ModelFetchStrategy::Bearer);
+    }
+
+    #[test]
+    fn model_fetch_target_rejects_empty_base_url() {
+        let provider = Provider::with_id(
+            "demo".to_string(),
+            "Demo".to_string(),
+            json!({
+                "options": {
+                    "baseURL": "",
+                    "apiKey": "sk-opencode"
+                }
+            }),
+            None,
+        );
+
+        let err = model_fetch_target(&provider, &AppType::OpenCode)
+            .expect_err("empty base url should be rejected");
+
+        assert!(
+            err.to_string().contains("options.baseURL"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_provider_model_ids_sends_resolved_key_reference() {
+        use axum::{http::HeaderMap, routing::get, Router};
+
+        // 上游把收到的 Authorization 原样作为模型 id 返回
+        let app = Router::new().fallback(get(|headers: HeaderMap| async move {
+            let auth = headers
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            axum::Json(json!({ "data": [{ "id": auth }] }))
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let address = listener.local_addr().expect("listener local addr");
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        std::env::set_var("CC_SWITCH_TEST_FETCH_MODELS_KEY", "sk-from-env");
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({
+                "auth": { "OPENAI_API_KEY": "env:CC_SWITCH_TEST_FETCH_MODELS_KEY" },
+                "config": format!(
+                    "model_provider = \"relay\"\n\n[model_providers.relay]\nbase_url = \"http://{address}/v1\"\n"
+                ),
+            }),
+            None,
+        );
+
+        let models = fetch_provider_model_ids(&provider, &AppType::Codex)
+            .await
+            .expect("fetch models");
+        assert_eq!(models, vec!["Bearer sk-from-env".to_string()]);
+
+        server.abort();
+    }
 }
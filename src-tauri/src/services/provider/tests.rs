@@ -1274,6 +1274,124 @@ fn switch_claude_writes_target_when_live_matches_current_provider() {
     );
 }
 
+#[test]
+#[serial]
+fn switch_resolves_secret_references_only_in_live_settings() {
+    let (temp_home, _env, state) = setup_claude_switch_preview_state(json!({
+        "env": {
+            "ANTHROPIC_AUTH_TOKEN": "token1",
+            "ANTHROPIC_BASE_URL": "https://claude.one"
+        }
+    }));
+    let key_file = temp_home.path().join("relay.key");
+    std::fs::write(&key_file, "sk-from-file\n").expect("write key file");
+    let reference = format!("file:{}", key_file.display());
+    ProviderService::add(
+        &state,
+        AppType::Claude,
+        Provider::with_id(
+            "p3".to_string(),
+            "Third".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": reference,
+                    "ANTHROPIC_BASE_URL": "https://claude.three"
+                }
+            }),
+            None,
+        ),
+    )
+    .expect("add provider with secret reference");
+
+    ProviderService::switch(&state, AppType::Claude, "p3").expect("switch should succeed");
+
+    let live: Value = read_json_file(&get_claude_settings_path()).expect("read live settings");
+    assert_eq!(
+        live.pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .and_then(Value::as_str),
+        Some("sk-from-file"),
+    );
+    let stored = state
+        .db
+        .get_provider_by_id("p3", AppType::Claude.as_str())
+        .expect("load provider")
+        .expect("provider exists");
+    assert_eq!(
+        stored
+            .settings_config
+            .pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .and_then(Value::as_str),
+        Some(reference.as_str()),
+    );
+
+    // Switching away backfills p3 from live; the reference must survive.
+    ProviderService::switch(&state, AppType::Claude, "p1").expect("switch back");
+    let stored = state
+        .db
+        .get_provider_by_id("p3", AppType::Claude.as_str())
+        .expect("load provider")
+        .expect("provider exists");
+    assert_eq!(
+        stored
+            .settings_config
+            .pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .and_then(Value::as_str),
+        Some(reference.as_str()),
+    );
+
+    std::fs::remove_file(&key_file).expect("remove key file");
+    let err = ProviderService::switch(&state, AppType::Claude, "p3")
+        .expect_err("missing key file should fail the live write");
+    assert!(
+        err.to_string().contains("env.ANTHROPIC_AUTH_TOKEN"),
+        "{err}"
+    );
+}
+
+#[test]
+#[serial]
+fn vault_seals_stored_keys_and_decrypts_only_for_live_writes() {
+    let (_temp_home, _env, state) = setup_claude_switch_preview_state(json!({
+        "env": {
+            "ANTHROPIC_AUTH_TOKEN": "token1",
+            "ANTHROPIC_BASE_URL": "https://claude.one"
+        }
+    }));
+
+    let (key, migration) =
+        crate::services::vault::VaultService::init(&state, "correct horse").expect("init vault");
+    assert_eq!(migration.providers, 2);
+    let stored_token = |id: &str| {
+        state
+            .db
+            .get_provider_by_id(id, AppType::Claude.as_str())
+            .expect("load provider")
+            .expect("provider exists")
+            .settings_config
+            .pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .expect("token")
+    };
+    let sealed = stored_token("p2");
+    assert!(sealed.starts_with("vault:v1:"), "{sealed}");
+
+    crate::vault::set_session_key(key, None);
+    ProviderService::switch(&state, AppType::Claude, "p2").expect("switch while unlocked");
+    let live: Value = read_json_file(&get_claude_settings_path()).expect("read live settings");
+    assert_eq!(
+        live.pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .and_then(Value::as_str),
+        Some("token2"),
+    );
+    assert_eq!(stored_token("p2"), sealed);
+
+    crate::vault::clear_session_key();
+    let err = ProviderService::switch(&state, AppType::Claude, "p1")
+        .expect_err("a locked vault should fail the live write");
+    assert!(err.to_string().contains("vault unlock"), "{err}");
+}
+
 #[test]
 #[serial]
 fn switch_overwrites_claude_settings_discarding_unstored_live_edit() {
@@ -7320,3 +7438,208 @@ fn delete_rejects_last_failover_queue_provider_while_active() {
         .expect("read queued provider")
         .is_some());
 }
+
+#[test]
+#[serial]
+fn provider_edits_are_recorded_and_revert_restores_a_revision() {
+    let temp_home = TempDir::new().expect("create temp home");
+    let _env = TestEnvGuard::isolated(temp_home.path());
+    let db = std::sync::Arc::new(Database::init().expect("initialize file database"));
+    let state = AppState {
+        db: db.clone(),
+        config: std::sync::RwLock::new(MultiAppConfig::default()),
+        proxy_service: crate::ProxyService::new(db),
+    };
+    let claude =
+        |url: &str| json!({"env": {"ANTHROPIC_BASE_URL": url, "ANTHROPIC_AUTH_TOKEN": "sk-test"}});
+
+    for id in ["p1", "p2"] {
+        ProviderService::add(
+            &state,
+            AppType::Claude,
+            Provider::with_id(
+                id.to_string(),
+                id.to_string(),
+                claude("https://one.example"),
+                None,
+            ),
+        )
+        .expect("add provider");
+    }
+    let mut edited = state
+        .db
+        .get_provider_by_id("p2", "claude")
+        .expect("read provider")
+        .expect("p2 exists");
+    edited.settings_config = claude("https://two.example");
+    ProviderService::update(&state, AppType::Claude, edited.clone()).expect("update");
+    ProviderService::update(&state, AppType::Claude, edited).expect("unchanged update");
+    ProviderService::delete(&state, AppType::Claude, "p2").expect("delete");
+
+    let history = crate::services::config_history::list_revisions(
+        crate::services::config_history::HistoryKind::Provider,
+        Some(&AppType::Claude),
+        "p2",
+    )
+    .expect("list history");
+    assert_eq!(
+        history
+            .iter()
+            .map(|revision| (revision.rev, revision.action.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(1, "add"), (2, "update"), (3, "delete")]
+    );
+    assert!(history
+        .iter()
+        .all(|revision| !revision.snapshot.to_string().contains("sk-test")));
+
+    // 已删除的供应商没有可沿用的凭据，恢复后需重新填写
+    ProviderService::revert(&state, AppType::Claude, "p2", 1).expect("revert deleted provider");
+    let restored = state
+        .db
+        .get_provider_by_id("p2", "claude")
+        .expect("read provider")
+        .expect("p2 restored");
+    assert_eq!(
+        restored.settings_config["env"]["ANTHROPIC_BASE_URL"],
+        "https://one.example"
+    );
+    assert_eq!(restored.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"], "");
+    assert!(ProviderService::revert(&state, AppType::Claude, "p2", 9).is_err());
+
+    let mut edited = state
+        .db
+        .get_provider_by_id("p1", "claude")
+        .expect("read provider")
+        .expect("p1 exists");
+    edited.settings_config = claude("https://two.example");
+    ProviderService::update(&state, AppType::Claude, edited).expect("update p1");
+    ProviderService::revert(&state, AppType::Claude, "p1", 1).expect("revert p1");
+    let reverted = state
+        .db
+        .get_provider_by_id("p1", "claude")
+        .expect("read provider")
+        .expect("p1 exists");
+    assert_eq!(
+        reverted.settings_config,
+        claude("https://one.example"),
+        "the current credential is kept"
+    );
+}
+
+#[test]
+#[serial]
+fn provider_switches_are_logged_for_switching_back() {
+    let temp_home = TempDir::new().expect("create temp home");
+    let _env = TestEnvGuard::isolated(temp_home.path());
+    let db = std::sync::Arc::new(Database::init().expect("initialize file database"));
+    let state = AppState {
+        db: db.clone(),
+        config: std::sync::RwLock::new(MultiAppConfig::default()),
+        proxy_service: crate::ProxyService::new(db),
+    };
+
+    for id in ["p1", "p2"] {
+        ProviderService::add(
+            &state,
+            AppType::Claude,
+            Provider::with_id(
+                id.to_string(),
+                id.to_string(),
+                json!({"env": {"ANTHROPIC_BASE_URL": "https://one.example", "ANTHROPIC_AUTH_TOKEN": "sk-test"}}),
+                None,
+            ),
+        )
+        .expect("add provider");
+    }
+    ProviderService::switch_with_source(
+        &state,
+        AppType::Claude,
+        "p1",
+        crate::services::switch_history::SwitchSource::Cli,
+    )
+    .expect("switch to p1");
+    ProviderService::switch_with_source(
+        &state,
+        AppType::Claude,
+        "p2",
+        crate::services::switch_history::SwitchSource::Tui,
+    )
+    .expect("switch to p2");
+    ProviderService::switch(&state, AppType::Claude, "p2").expect("no-op switch");
+
+    let switches =
+        crate::services::switch_history::recent(Some(&AppType::Claude), 10).expect("list");
+    assert_eq!(switches.len(), 1, "{switches:?}");
+    assert_eq!(switches[0].from_id.as_deref(), Some("p1"));
+    assert_eq!(switches[0].to_id, "p2");
+    assert_eq!(switches[0].source, "tui");
+    assert_eq!(
+        crate::services::switch_history::previous_provider(&AppType::Claude, Some("p2"))
+            .expect("previous")
+            .as_deref(),
+        Some("p1")
+    );
+}
+
+#[test]
+#[serial]
+fn codex_profiles_sync_never_stores_resolved_keys() {
+    let temp_home = TempDir::new().expect("create temp home");
+    let _env = TestEnvGuard::isolated(temp_home.path());
+    std::fs::create_dir_all(crate::codex_config::get_codex_config_dir()).expect("create ~/.codex");
+    std::fs::write(get_codex_config_path(), "model = \"gpt-5\"\n").expect("seed config.toml");
+    let key_file = temp_home.path().join("filed.key");
+    std::fs::write(&key_file, "sk-from-file").expect("write key file");
+
+    let relay = |id: &str, key: String| {
+        Provider::with_id(
+            id.to_string(),
+            id.to_string(),
+            json!({
+                "auth": { "OPENAI_API_KEY": key },
+                "config": format!(
+                    "model_provider = \"custom\"\nmodel = \"gpt-5\"\n\n[model_providers.custom]\nbase_url = \"https://{id}.example/v1\"\n"
+                ),
+            }),
+            None,
+        )
+    };
+    let mut config = MultiAppConfig::default();
+    config.ensure_app(&AppType::Codex);
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Codex)
+            .expect("codex manager");
+        manager.providers.insert(
+            "plain".to_string(),
+            relay("plain", "sk-plain-secret".to_string()),
+        );
+        manager.providers.insert(
+            "filed".to_string(),
+            relay("filed", format!("file:{}", key_file.display())),
+        );
+    }
+    let state = state_from_config(config);
+    crate::settings::set_codex_profiles_settings(crate::settings::CodexProfilesSettings {
+        enabled: true,
+        ..Default::default()
+    })
+    .expect("enable codex profiles");
+
+    let names = ProviderService::sync_codex_profiles(&state).expect("sync profiles");
+    assert_eq!(names.len(), 2);
+
+    let live = std::fs::read_to_string(get_codex_config_path()).expect("read config.toml");
+    assert!(!live.contains("sk-from-file"));
+    assert!(live.contains("env_key = \"CC_SWITCH_FILED_API_KEY\""));
+
+    let base = crate::local_store::LocalStore::open()
+        .expect("open sidecar store")
+        .get_meta("codex_profiles_base")
+        .expect("read base")
+        .expect("base stored");
+    assert!(base.contains("cc-switch-plain"));
+    assert!(!base.contains("sk-plain-secret"));
+    assert!(!base.contains("sk-from-file"));
+}
@@ -95,7 +95,7 @@ impl ProviderService {
         app_type: AppType,
         provider_id: &str,
     ) -> Result<UsageResult, AppError> {
-        let (script_code, timeout, api_key, base_url, access_token, user_id, template_type) = {
+        let (provider, usage_script) = {
             let providers = state.db.get_all_providers(app_type.as_str())?;
             let provider = providers.get(provider_id).ok_or_else(|| {
                 AppError::localized(
@@ -124,19 +124,18 @@ impl ProviderService {
                 ));
             }
 
-            let (api_key, base_url) =
-                Self::resolve_usage_script_credentials(provider, &app_type, usage_script)?;
-
-            (
-                usage_script.code.clone(),
-                usage_script.timeout.unwrap_or(10),
-                api_key,
-                base_url,
-                usage_script.access_token.clone(),
-                usage_script.user_id.clone(),
-                usage_script.template_type.clone(),
-            )
+            (provider.clone(), usage_script.clone())
         };
+        let (api_key, base_url) =
+            Self::resolve_usage_script_credentials_async(&provider, &app_type, &usage_script)
+                .await?;
+        let (script_code, timeout, access_token, user_id, template_type) = (
+            usage_script.code,
+            usage_script.timeout.unwrap_or(10),
+            usage_script.access_token,
+            usage_script.user_id,
+            usage_script.template_type,
+        );
 
         Self::execute_and_format_usage_result(
             &script_code,
@@ -178,7 +177,8 @@ impl ProviderService {
                 return Err("Usage script is not configured".to_string());
             };
             let (api_key, base_url) =
-                Self::resolve_usage_script_credentials(provider, &app_type, usage_script)
+                Self::resolve_usage_script_credentials_async(provider, &app_type, usage_script)
+                    .await
                     .map_err(|e| e.to_string())?;
 
             let quota = crate::services::coding_plan::get_coding_plan_quota(&base_url, &api_key)
@@ -225,7 +225,8 @@ impl ProviderService {
                 return Err("Usage script is not configured".to_string());
             };
             let (api_key, base_url) =
-                Self::resolve_usage_script_credentials(provider, &app_type, usage_script)
+                Self::resolve_usage_script_credentials_async(provider, &app_type, usage_script)
+                    .await
                     .map_err(|e| e.to_string())?;
 
             return crate::services::balance::get_balance(&base_url, &api_key)
@@ -569,6 +570,23 @@ impl ProviderService {
         ))
     }
 
+    /// [`Self::resolve_usage_script_credentials`] with secret references
+    /// resolved on a blocking thread (they may read files or run commands).
+    async fn resolve_usage_script_credentials_async(
+        provider: &Provider,
+        app_type: &AppType,
+        usage_script: &UsageScript,
+    ) -> Result<(String, String), AppError> {
+        let provider = crate::key_pool::resolve_for_live_async(app_type, provider).await?;
+        let (api_key, base_url) =
+            Self::resolve_usage_script_credentials(&provider, app_type, usage_script)?;
+        let api_key =
+            tokio::task::spawn_blocking(move || crate::secret_ref::resolve_value(&api_key))
+                .await
+                .map_err(|err| AppError::Message(format!("resolve usage query key: {err}")))??;
+        Ok((api_key, base_url))
+    }
+
     pub(super) fn resolve_usage_script_credentials(
         provider: &Provider,
         app_type: &AppType,
@@ -746,6 +764,84 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn query_usage_sends_resolved_key_reference() {
+        let state = super::super::state_from_config(MultiAppConfig::default());
+
+        // 上游把收到的 Authorization 原样作为套餐名返回
+        let app = Router::new().route(
+            "/",
+            get(|headers: axum::http::HeaderMap| async move {
+                let auth = headers
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                axum::Json(json!({ "auth": auth }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let address = listener.local_addr().expect("listener local addr");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+
+        std::env::set_var("CC_SWITCH_TEST_USAGE_KEY", "sk-from-env");
+        let mut provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({"env": {"ANTHROPIC_AUTH_TOKEN": "env:CC_SWITCH_TEST_USAGE_KEY"}}),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            usage_script: Some(UsageScript {
+                enabled: true,
+                language: "javascript".to_string(),
+                code: r#"({
+                    request: {
+                        url: "{{baseUrl}}",
+                        method: "GET",
+                        headers: { "Authorization": "Bearer {{apiKey}}" }
+                    },
+                    extractor: function(response) {
+                        return { planName: response.auth, total: 1 };
+                    }
+                })"#
+                .to_string(),
+                timeout: Some(2),
+                api_key: None,
+                base_url: Some(format!("http://{address}/")),
+                access_token: None,
+                user_id: None,
+                template_type: None,
+                auto_query_interval: None,
+                coding_plan_provider: None,
+            }),
+            ..Default::default()
+        });
+        state
+            .db
+            .save_provider(AppType::Claude.as_str(), &provider)
+            .expect("save provider");
+
+        let result = ProviderService::query_provider_usage(&state, AppType::Claude, "relay")
+            .await
+            .expect("query usage");
+        assert_eq!(
+            result
+                .data
+                .as_ref()
+                .and_then(|items| items.first())
+                .and_then(|usage| usage.plan_name.as_deref()),
+            Some("Bearer sk-from-env"),
+            "{result:?}"
+        );
+
+        server.abort();
+    }
+
     #[test]
     fn resolve_usage_script_credentials_reads_codex_provider_config() {
         let provider = Provider::with_id(
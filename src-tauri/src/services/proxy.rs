@@ -1,6 +1,7 @@
 mod codex_toml;
 
 use std::{
+    borrow::Cow,
     collections::HashMap,
     future::Future,
     sync::{Arc, Mutex as StdMutex, OnceLock, Weak},
@@ -26,7 +27,7 @@ use crate::{
     },
     provider::Provider,
     proxy::{
-        switch_lock::SwitchLockManager,
+        switch_lock::{SwitchGuard, SwitchLockManager},
         types::{ActiveTarget, GlobalProxyConfig, ProxyTakeoverStatus},
         ProxyConfig, ProxyServer, ProxyServerInfo, ProxyStatus,
     },
@@ -37,7 +38,7 @@ use crate::{
 #[cfg(test)]
 use crate::claude_model_config::CLAUDE_CONTEXT_WINDOW_ENV_KEYS;
 
-const PROXY_TOKEN_PLACEHOLDER: &str = "PROXY_MANAGED";
+pub(crate) const PROXY_TOKEN_PLACEHOLDER: &str = "PROXY_MANAGED";
 const PROXY_RUNTIME_SESSION_KEY: &str = "proxy_runtime_session";
 const PROXY_RUNTIME_KIND_ENV_KEY: &str = "CC_SWITCH_PROXY_RUNTIME_KIND";
 const PROXY_RUNTIME_SESSION_TOKEN_ENV_KEY: &str = "CC_SWITCH_PROXY_SESSION_TOKEN";
@@ -60,11 +61,14 @@ struct EnableTakeoverOptions {
 pub struct ProxyService {
     db: Arc<Database>,
     runtime: Arc<ProxyRuntimeState>,
-    switch_locks: SwitchLockManager,
 }
 
 struct ProxyRuntimeState {
     server: RwLock<Option<ProxyServer>>,
+    /// Shared by every `ProxyService` of the same database, so hot switches,
+    /// regular switches and switching rules serialize per app. Other
+    /// processes are kept out by the database's switch lock file.
+    switch_locks: SwitchLockManager,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -214,11 +218,7 @@ impl ProxyService {
 
     pub fn new(db: Arc<Database>) -> Self {
         let runtime = Self::shared_runtime_state(db.runtime_key());
-        Self {
-            db,
-            runtime,
-            switch_locks: SwitchLockManager::new(),
-        }
+        Self { db, runtime }
     }
 
     fn shared_runtime_state(runtime_key: &str) -> Arc<ProxyRuntimeState> {
@@ -232,6 +232,7 @@ impl ProxyService {
 
         let runtime = Arc::new(ProxyRuntimeState {
             server: RwLock::new(None),
+            switch_locks: SwitchLockManager::new(),
         });
         registry.insert(runtime_key.to_string(), Arc::downgrade(&runtime));
         runtime
@@ -2057,6 +2058,24 @@ impl ProxyService {
         })
     }
 
+    /// Proxy origin and Codex base URL for `app_type` while its takeover is
+    /// enabled, so processes outside the live config can use the same route.
+    pub async fn takeover_route_for_app(
+        &self,
+        app_type: &AppType,
+    ) -> Result<Option<(String, String)>, String> {
+        let enabled = self
+            .db
+            .get_proxy_config_for_app(app_type.as_str())
+            .await
+            .map_err(|error| format!("load {} proxy config failed: {error}", app_type.as_str()))?
+            .enabled;
+        if !enabled {
+            return Ok(None);
+        }
+        self.build_proxy_urls_for_app(app_type).await.map(Some)
+    }
+
     pub async fn set_takeover_for_app(&self, app_type: &str, enabled: bool) -> Result<(), String> {
         let app_type = Self::takeover_app_from_str(app_type)?;
         let _guard = crate::services::state_coordination::acquire_restore_mutation_guard().await?;
@@ -2559,12 +2578,22 @@ impl ProxyService {
         }
     }
 
+    /// Per-app switch lock, across threads and cc-switch processes; held for
+    /// the whole of a provider switch.
+    pub async fn lock_switches_for_app(&self, app_type: &str) -> SwitchGuard {
+        let lock_path = self.db.switch_lock_path(app_type);
+        self.runtime
+            .switch_locks
+            .lock_for_app(app_type, lock_path.as_deref())
+            .await
+    }
+
     pub async fn hot_switch_provider(
         &self,
         app_type: &str,
         provider_id: &str,
     ) -> Result<HotSwitchOutcome, String> {
-        let _guard = self.switch_locks.lock_for_app(app_type).await;
+        let _guard = self.lock_switches_for_app(app_type).await;
 
         let app_type_enum = Self::takeover_app_from_str(app_type)?;
         let provider = self
@@ -2681,9 +2710,21 @@ impl ProxyService {
         app_type: &str,
         provider_id: &str,
     ) -> Result<(), String> {
+        let app_type_enum = Self::takeover_app_from_str(app_type)?;
+        let previous_provider_id =
+            crate::settings::get_effective_current_provider(&self.db, &app_type_enum)
+                .ok()
+                .flatten();
         let outcome = self.hot_switch_provider(app_type, provider_id).await?;
 
         if outcome.logical_target_changed {
+            crate::services::switch_history::record_best_effort(
+                &self.db,
+                &app_type_enum,
+                previous_provider_id.as_deref(),
+                provider_id,
+                crate::services::switch_history::SwitchSource::Failover,
+            );
             log::info!("代理模式：已切换 {app_type} 的目标供应商为 {provider_id}");
         } else {
             log::debug!("代理模式：{app_type} 已对齐到目标供应商 {provider_id}");
@@ -3629,7 +3670,20 @@ impl ProxyService {
         }
     }
 
+    /// Live files get real credentials while providers and live backups keep
+    /// their secret references.
+    fn resolve_live_secrets(config: &Value) -> Result<Cow<'_, Value>, String> {
+        if !crate::secret_ref::has_references(config) {
+            return Ok(Cow::Borrowed(config));
+        }
+        crate::secret_ref::resolve_settings(config)
+            .map(Cow::Owned)
+            .map_err(|error| error.to_string())
+    }
+
     fn write_claude_live(&self, config: &Value) -> Result<(), String> {
+        let resolved = Self::resolve_live_secrets(config)?;
+        let config = resolved.as_ref();
         write_json_file(&get_claude_settings_path(), config)
             .map_err(|error| format!("write Claude settings.json failed: {error}"))
     }
@@ -3648,6 +3702,8 @@ impl ProxyService {
         config: &Value,
         provider: Option<&Provider>,
     ) -> Result<(), String> {
+        let resolved = Self::resolve_live_secrets(config)?;
+        let config = resolved.as_ref();
         let Some(provider) = provider else {
             if crate::settings::preserve_codex_official_auth_on_switch() {
                 if let (Some(auth), Some(config_text)) = (
@@ -3767,6 +3823,8 @@ impl ProxyService {
         config: &Value,
         provider: &Provider,
     ) -> Result<(), String> {
+        let resolved = Self::resolve_live_secrets(config)?;
+        let config = resolved.as_ref();
         let auth = config
             .get("auth")
             .ok_or_else(|| "Codex config missing auth field".to_string())?;
@@ -3800,6 +3858,8 @@ impl ProxyService {
     }
 
     fn write_codex_live_verbatim(&self, config: &Value) -> Result<(), String> {
+        let resolved = Self::resolve_live_secrets(config)?;
+        let config = resolved.as_ref();
         let auth = config.get("auth");
         let config_text = config.get("config").and_then(Value::as_str);
 
@@ -4299,7 +4359,7 @@ impl ProxyService {
         }
     }
 
-    fn resolve_managed_proxy_executable() -> Result<std::path::PathBuf, String> {
+    pub(crate) fn resolve_managed_proxy_executable() -> Result<std::path::PathBuf, String> {
         if let Some(path) = std::env::var_os("CARGO_BIN_EXE_cc-switch") {
             return Ok(path.into());
         }
@@ -4370,6 +4430,8 @@ impl ProxyService {
     }
 
     fn write_gemini_live(&self, config: &Value) -> Result<(), String> {
+        let resolved = Self::resolve_live_secrets(config)?;
+        let config = resolved.as_ref();
         let env =
             json_to_env(config).map_err(|error| format!("build Gemini .env failed: {error}"))?;
         write_gemini_env_atomic(&env).map_err(|error| format!("write Gemini .env failed: {error}"))
@@ -0,0 +1,419 @@
+//! Scheduled balance / quota polling and history.
+//!
+//! The daemon polls every provider that has a quota source (Usage Query
+//! script, balance / coding-plan templates, official subscriptions, Codex
+//! OAuth) at the provider's `auto_query_interval`, and every result — from the
+//! daemon, `provider quota` or the TUI — is appended to the sidecar
+//! `quota_samples` table. [`quota_trends`] turns those samples into per-tier
+//! trends with a burn rate and a "days until empty" estimate.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::LocalStore;
+use crate::services::provider_quota::{
+    query_quota, quota_target_for_provider, ProviderUsageQuota, QuotaTarget,
+};
+
+pub use crate::local_store::quota_history::QuotaSample;
+
+const POLL_TICK_SECS: u64 = 60;
+/// Prune roughly once an hour.
+const PRUNE_EVERY_TICKS: u64 = 60;
+const SAMPLE_RETAIN_MS: i64 = 180 * 24 * 60 * 60 * 1000;
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+/// Burn rates over shorter spans are mostly noise.
+const MIN_TREND_SPAN_MS: i64 = 60 * 60 * 1000;
+
+/// Where a quota result came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QuotaSource {
+    Daemon,
+    Cli,
+    Tui,
+}
+
+impl QuotaSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Daemon => "daemon",
+            Self::Cli => "cli",
+            Self::Tui => "tui",
+        }
+    }
+}
+
+/// Flatten one query result into history rows.
+pub(crate) fn samples_from_result(
+    target: &QuotaTarget,
+    source: QuotaSource,
+    queried_at: i64,
+    result: &Result<ProviderUsageQuota, String>,
+) -> Vec<QuotaSample> {
+    let base = QuotaSample {
+        app_type: target.app_type.as_str().to_string(),
+        provider_id: target.provider_id.clone(),
+        source: source.as_str().to_string(),
+        queried_at,
+        success: false,
+        tier: String::new(),
+        remaining: None,
+        total: None,
+        used: None,
+        unit: None,
+        error: None,
+    };
+    let failed = |error: Option<String>| {
+        vec![QuotaSample {
+            error,
+            ..base.clone()
+        }]
+    };
+
+    let samples: Vec<QuotaSample> = match result {
+        Err(error) => return failed(Some(error.clone())),
+        Ok(ProviderUsageQuota::Subscription(quota)) => {
+            if !quota.success {
+                return failed(quota.error.clone().or(quota.credential_message.clone()));
+            }
+            quota
+                .tiers
+                .iter()
+                .map(|tier| QuotaSample {
+                    success: true,
+                    tier: tier.name.clone(),
+                    remaining: Some(100.0 - tier.utilization),
+                    total: Some(100.0),
+                    used: Some(tier.utilization),
+                    unit: Some("%".to_string()),
+                    ..base.clone()
+                })
+                .collect()
+        }
+        Ok(ProviderUsageQuota::Script(result)) => {
+            if !result.success {
+                return failed(result.error.clone());
+            }
+            result
+                .data
+                .iter()
+                .flatten()
+                .map(|item| QuotaSample {
+                    success: true,
+                    tier: item.plan_name.clone().unwrap_or_default(),
+                    remaining: item.remaining,
+                    total: item.total,
+                    used: item.used,
+                    unit: item.unit.clone(),
+                    ..base.clone()
+                })
+                .collect()
+        }
+    };
+    if samples.is_empty() {
+        // Keep the query time even when the source reported nothing.
+        return vec![QuotaSample {
+            success: true,
+            ..base
+        }];
+    }
+    samples
+}
+
+/// Persist a query result; history is optional, so failures are only logged.
+pub(crate) fn record_quota_result_best_effort(
+    target: &QuotaTarget,
+    source: QuotaSource,
+    queried_at: i64,
+    result: &Result<ProviderUsageQuota, String>,
+) {
+    let samples = samples_from_result(target, source, queried_at, result);
+    if let Err(error) = LocalStore::open().and_then(|store| store.record_quota_samples(&samples)) {
+        log::debug!(
+            "[QUOTA-HISTORY] record {} failed: {error}",
+            target.cache_key()
+        );
+    }
+}
+
+/// Remaining-balance trend of one provider tier.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaTrend {
+    pub app_type: String,
+    pub provider_id: String,
+    pub tier: String,
+    pub unit: Option<String>,
+    pub latest_remaining: f64,
+    pub latest_total: Option<f64>,
+    pub latest_at: i64,
+    /// Remaining value of each successful sample, oldest first.
+    pub points: Vec<(i64, f64)>,
+    /// Average consumption per day since the last top-up; `None` when the
+    /// balance is flat, growing, or there is not enough history.
+    pub burn_per_day: Option<f64>,
+    pub days_until_empty: Option<f64>,
+}
+
+/// Group successful samples by `(provider, tier)` and estimate burn rates.
+pub fn quota_trends(samples: &[QuotaSample]) -> Vec<QuotaTrend> {
+    let mut groups: BTreeMap<(String, String, String), Vec<&QuotaSample>> = BTreeMap::new();
+    for sample in samples.iter().filter(|sample| sample.success) {
+        if sample.remaining.is_none() {
+            continue;
+        }
+        groups
+            .entry((
+                sample.app_type.clone(),
+                sample.provider_id.clone(),
+                sample.tier.clone(),
+            ))
+            .or_default()
+            .push(sample);
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|((app_type, provider_id, tier), rows)| {
+            let latest = *rows.last()?;
+            let latest_remaining = latest.remaining?;
+            let points: Vec<(i64, f64)> = rows
+                .iter()
+                .filter_map(|row| Some((row.queried_at, row.remaining?)))
+                .collect();
+            let burn_per_day = burn_rate_per_day(&points);
+            let days_until_empty = burn_per_day
+                .filter(|_| latest_remaining > 0.0)
+                .map(|burn| latest_remaining / burn);
+            Some(QuotaTrend {
+                app_type,
+                provider_id,
+                tier,
+                unit: latest.unit.clone(),
+                latest_remaining,
+                latest_total: latest.total,
+                latest_at: latest.queried_at,
+                points,
+                burn_per_day,
+                days_until_empty,
+            })
+        })
+        .collect()
+}
+
+/// Least-squares slope of remaining over time, restricted to the segment
+/// after the most recent top-up / reset (any increase in the remaining value).
+fn burn_rate_per_day(points: &[(i64, f64)]) -> Option<f64> {
+    let segment_start = points
+        .windows(2)
+        .rposition(|pair| pair[1].1 > pair[0].1 + f64::EPSILON)
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let segment = &points[segment_start..];
+    let (first, last) = (segment.first()?, segment.last()?);
+    if last.0 - first.0 < MIN_TREND_SPAN_MS {
+        return None;
+    }
+
+    let n = segment.len() as f64;
+    let mean_t = segment
+        .iter()
+        .map(|(t, _)| (t - first.0) as f64)
+        .sum::<f64>()
+        / n;
+    let mean_v = segment.iter().map(|(_, v)| v).sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (t, v) in segment {
+        let dt = (t - first.0) as f64 - mean_t;
+        cov += dt * (v - mean_v);
+        var += dt * dt;
+    }
+    if var <= 0.0 {
+        return None;
+    }
+    let burn = -(cov / var) * DAY_MS;
+    (burn > 0.0).then_some(burn)
+}
+
+/// Poll every provider whose interval has elapsed since its last recorded
+/// query. Returns the number of providers queried.
+pub(crate) async fn poll_due_quotas(db: &Database, context: &str) -> usize {
+    let store = match LocalStore::open() {
+        Ok(store) => store,
+        Err(error) => {
+            log::debug!("[QUOTA-HISTORY] sidecar unavailable ({context}): {error}");
+            return 0;
+        }
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut due = Vec::new();
+    for app_type in AppType::all() {
+        let providers = match db.get_all_providers(app_type.as_str()) {
+            Ok(providers) => providers,
+            Err(error) => {
+                log::debug!(
+                    "[QUOTA-HISTORY] list {} providers failed: {error}",
+                    app_type.as_str()
+                );
+                continue;
+            }
+        };
+        for (id, provider) in &providers {
+            let Some(target) = quota_target_for_provider(&app_type, id, provider) else {
+                continue;
+            };
+            let interval_ms = target.auto_query_interval_minutes as i64 * 60_000;
+            if interval_ms <= 0 {
+                continue;
+            }
+            let last = store
+                .last_quota_query_at(app_type.as_str(), id)
+                .ok()
+                .flatten();
+            if last.is_some_and(|last| now - last < interval_ms) {
+                continue;
+            }
+            due.push(target);
+        }
+    }
+
+    for target in &due {
+        let queried_at = chrono::Utc::now().timestamp_millis();
+        let result = query_quota(target).await;
+        if let Err(error) = &result {
+            log::debug!("[QUOTA-HISTORY] {} failed: {error}", target.cache_key());
+        }
+        let samples = samples_from_result(target, QuotaSource::Daemon, queried_at, &result);
+        if let Err(error) = store.record_quota_samples(&samples) {
+            log::debug!(
+                "[QUOTA-HISTORY] record {} failed: {error}",
+                target.cache_key()
+            );
+        }
+    }
+    due.len()
+}
+
+fn prune_samples(context: &str) -> Result<usize, AppError> {
+    let cutoff = chrono::Utc::now().timestamp_millis() - SAMPLE_RETAIN_MS;
+    let pruned = LocalStore::open()?.prune_quota_samples(cutoff)?;
+    if pruned > 0 {
+        log::debug!("[QUOTA-HISTORY] pruned {pruned} sample(s) ({context})");
+    }
+    Ok(pruned)
+}
+
+/// Daemon task: check for due providers once a minute.
+pub(crate) fn spawn_periodic_quota_polling(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(POLL_TICK_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut ticks: u64 = 0;
+        loop {
+            interval.tick().await;
+            if ticks.is_multiple_of(PRUNE_EVERY_TICKS) {
+                if let Err(error) = prune_samples(context) {
+                    log::debug!("[QUOTA-HISTORY] prune failed ({context}): {error}");
+                }
+            }
+            ticks = ticks.wrapping_add(1);
+            let polled = poll_due_quotas(&db, context).await;
+            if polled > 0 {
+                log::debug!("[QUOTA-HISTORY] polled {polled} provider(s) ({context})");
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{burn_rate_per_day, quota_trends, samples_from_result, QuotaSource};
+    use crate::app_config::AppType;
+    use crate::provider::{UsageData, UsageResult};
+    use crate::services::provider_quota::{ProviderUsageQuota, QuotaTarget, QuotaTargetKind};
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    fn target() -> QuotaTarget {
+        QuotaTarget {
+            app_type: AppType::Claude,
+            provider_id: "relay".to_string(),
+            provider_name: "Relay".to_string(),
+            kind: QuotaTargetKind::UsageScript,
+            auto_query_interval_minutes: 30,
+        }
+    }
+
+    fn balance(remaining: f64) -> Result<ProviderUsageQuota, String> {
+        Ok(ProviderUsageQuota::Script(UsageResult {
+            success: true,
+            data: Some(vec![UsageData {
+                plan_name: Some("balance".to_string()),
+                remaining: Some(remaining),
+                total: None,
+                used: None,
+                unit: Some("USD".to_string()),
+                extra: None,
+                is_valid: Some(true),
+                invalid_message: None,
+            }]),
+            error: None,
+        }))
+    }
+
+    #[test]
+    fn failed_queries_record_a_single_error_row() {
+        let rows = samples_from_result(&target(), QuotaSource::Daemon, 1, &Err("boom".into()));
+        assert_eq!(rows.len(), 1);
+        assert!(!rows[0].success);
+        assert_eq!(rows[0].error.as_deref(), Some("boom"));
+        assert_eq!(rows[0].source, "daemon");
+    }
+
+    #[test]
+    fn trend_estimates_days_until_empty_after_last_top_up() {
+        let mut samples = Vec::new();
+        // Drained 10 -> 4, topped up to 50, then 2 USD per 12h.
+        for (hour, remaining) in [(0, 10.0), (12, 4.0), (13, 50.0), (25, 48.0), (37, 46.0)] {
+            samples.extend(samples_from_result(
+                &target(),
+                QuotaSource::Daemon,
+                hour * HOUR_MS,
+                &balance(remaining),
+            ));
+        }
+        samples.extend(samples_from_result(
+            &target(),
+            QuotaSource::Daemon,
+            38 * HOUR_MS,
+            &Err("timeout".into()),
+        ));
+
+        let trends = quota_trends(&samples);
+        assert_eq!(trends.len(), 1);
+        let trend = &trends[0];
+        assert_eq!(trend.tier, "balance");
+        assert_eq!(trend.latest_remaining, 46.0);
+        assert_eq!(trend.points.len(), 5);
+        let burn = trend.burn_per_day.expect("burn rate");
+        assert!((burn - 4.0).abs() < 1e-6, "{burn}");
+        let days = trend.days_until_empty.expect("days until empty");
+        assert!((days - 11.5).abs() < 1e-6, "{days}");
+    }
+
+    #[test]
+    fn flat_or_short_history_has_no_burn_rate() {
+        assert_eq!(burn_rate_per_day(&[(0, 5.0), (2 * HOUR_MS, 5.0)]), None);
+        assert_eq!(burn_rate_per_day(&[(0, 5.0), (HOUR_MS / 2, 4.0)]), None);
+        assert_eq!(burn_rate_per_day(&[(0, 5.0)]), None);
+    }
+}
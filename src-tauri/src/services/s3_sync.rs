@@ -55,7 +55,7 @@ impl S3SyncService {
         run_http(async {
             let settings = load_s3_settings(false)?;
             settings.validate()?;
-            s3::test_connection(&credentials_for(&settings)).await
+            s3::test_connection(&credentials_for(&settings)?).await
         })
     }
 
@@ -83,7 +83,7 @@ impl S3SyncService {
 async fn upload() -> Result<S3SyncSummary, AppError> {
     let mut settings = load_s3_settings(true)?;
     settings.validate()?;
-    let credentials = credentials_for(&settings);
+    let credentials = credentials_for(&settings)?;
     let snapshot = build_local_snapshot()?;
 
     let database_key = s3_key(&settings, REMOTE_DB_SQL);
@@ -132,7 +132,7 @@ async fn upload() -> Result<S3SyncSummary, AppError> {
 async fn download() -> Result<S3SyncSummary, AppError> {
     let mut settings = load_s3_settings(true)?;
     settings.validate()?;
-    let credentials = credentials_for(&settings);
+    let credentials = credentials_for(&settings)?;
     let manifest_key = s3_key(&settings, REMOTE_MANIFEST);
     let (manifest_bytes, etag) = s3::get_object(&credentials, &manifest_key, MAX_MANIFEST_BYTES)
         .await?
@@ -173,7 +173,7 @@ async fn download() -> Result<S3SyncSummary, AppError> {
 async fn fetch_remote_info() -> Result<Option<S3RemoteInfo>, AppError> {
     let settings = load_s3_settings(true)?;
     settings.validate()?;
-    let credentials = credentials_for(&settings);
+    let credentials = credentials_for(&settings)?;
     let manifest_key = s3_key(&settings, REMOTE_MANIFEST);
     let Some((bytes, _)) = s3::get_object(&credentials, &manifest_key, MAX_MANIFEST_BYTES).await?
     else {
@@ -286,14 +286,16 @@ fn persist_sync_error_best_effort(error: &AppError, source: &str) {
     }
 }
 
-fn credentials_for(settings: &S3SyncSettings) -> S3Credentials {
-    S3Credentials {
+/// The secret key may be a secret reference or a vault value; it is
+/// resolved only here, right before signing requests.
+fn credentials_for(settings: &S3SyncSettings) -> Result<S3Credentials, AppError> {
+    Ok(S3Credentials {
         access_key_id: settings.access_key_id.clone(),
-        secret_access_key: settings.secret_access_key.clone(),
+        secret_access_key: crate::secret_ref::resolve_value(&settings.secret_access_key)?,
         region: settings.region.clone(),
         bucket: settings.bucket.clone(),
         endpoint: settings.endpoint.clone(),
-    }
+    })
 }
 
 fn s3_key(settings: &S3SyncSettings, artifact: &str) -> String {
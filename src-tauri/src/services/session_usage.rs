@@ -21,7 +21,7 @@ use crate::session_manager::scan_cache_store::{ScanCacheStore, SyncResumeHint};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::{self, File, OpenOptions};
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 use std::time::{Duration, SystemTime};
@@ -91,63 +91,7 @@ impl Drop for SessionSyncGuard {
 }
 
 fn open_session_sync_lock(path: &Path) -> Result<File, AppError> {
-    #[cfg(not(unix))]
-    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
-        return Err(AppError::InvalidInput(format!(
-            "会话用量同步锁不能是符号链接: {}",
-            path.display()
-        )));
-    }
-
-    #[cfg(unix)]
-    let file = {
-        use std::os::unix::fs::OpenOptionsExt;
-
-        OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .mode(0o600)
-            .custom_flags(libc::O_CLOEXEC | libc::O_NOFOLLOW)
-            .open(path)
-            .map_err(|error| AppError::io(path, error))?
-    };
-
-    #[cfg(not(unix))]
-    let file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .truncate(false)
-        .open(path)
-        .map_err(|error| AppError::io(path, error))?;
-
-    let metadata = file.metadata().map_err(|error| AppError::io(path, error))?;
-    if !metadata.is_file() {
-        return Err(AppError::InvalidInput(format!(
-            "会话用量同步锁不是普通文件: {}",
-            path.display()
-        )));
-    }
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::MetadataExt;
-        use std::os::unix::fs::PermissionsExt;
-
-        if metadata.nlink() != 1 {
-            return Err(AppError::InvalidInput(format!(
-                "会话用量同步锁不能是硬链接: {}",
-                path.display()
-            )));
-        }
-
-        file.set_permissions(std::fs::Permissions::from_mode(0o600))
-            .map_err(|error| AppError::io(path, error))?;
-    }
-
-    Ok(file)
+    crate::database::open_lock_file(path, "会话用量同步锁")
 }
 
 /// Acquire the process-wide mutex and then the database-scoped file lock. A
@@ -399,6 +343,8 @@ pub(crate) fn run_session_usage_sync_cycle(
     let sync_result = sync_all_session_usage(db)?;
     result.merge(sync_result);
     log_session_usage_sync_result(&result, context);
+    // 新导入的会话日志与代理日志一并按项目归因（写 sidecar，失败只记日志）
+    crate::services::project_usage::run_project_attribution_best_effort(db, context);
     Ok(result)
 }
 
@@ -17,6 +17,8 @@ impl StreamCheckService {
         config: &StreamCheckConfig,
     ) -> Result<StreamCheckResult, AppError> {
         let effective_config = Self::merge_provider_config(provider, config);
+        // 与写入 live 配置相同的凭证：引用无法解析的供应商直接判为失败
+        let provider = &crate::key_pool::resolve_for_live_async(app_type, provider).await?;
         let mut last_result = None;
 
         for attempt in 0..=effective_config.max_retries {
@@ -316,3 +316,50 @@ async fn stream_check_openclaw_reachability_does_not_require_auth() {
 
     upstream_handle.abort();
 }
+
+#[tokio::test]
+async fn stream_check_resolves_key_references_before_probing() {
+    let upstream_state = ReachabilityState::default();
+    let upstream_router = Router::new()
+        .route("/", get(handle_reachability_probe))
+        .with_state(upstream_state.clone());
+    let upstream_listener = bind_test_listener().await;
+    let upstream_addr = upstream_listener
+        .local_addr()
+        .expect("read upstream address");
+    let upstream_handle = tokio::spawn(async move {
+        let _ = axum::serve(upstream_listener, upstream_router).await;
+    });
+
+    let provider = |key: &str| {
+        make_provider(json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": format!("http://{}", upstream_addr),
+                "ANTHROPIC_AUTH_TOKEN": key
+            }
+        }))
+    };
+    std::env::set_var("CC_SWITCH_TEST_STREAM_CHECK_KEY", "sk-from-env");
+
+    let result = StreamCheckService::check_with_retry(
+        &AppType::Claude,
+        &provider("env:CC_SWITCH_TEST_STREAM_CHECK_KEY"),
+        &StreamCheckConfig::default(),
+    )
+    .await
+    .expect("resolvable reference should be checked");
+    assert!(result.success);
+
+    // 无法解析的引用不会被当作 Key 发出，检查直接失败
+    *upstream_state.request_method.lock().await = None;
+    StreamCheckService::check_with_retry(
+        &AppType::Claude,
+        &provider("env:CC_SWITCH_TEST_STREAM_CHECK_UNSET"),
+        &StreamCheckConfig::default(),
+    )
+    .await
+    .expect_err("unresolvable reference should fail the check");
+    assert!(upstream_state.request_method.lock().await.is_none());
+
+    upstream_handle.abort();
+}
@@ -0,0 +1,128 @@
+//! Provider switch log behind `cc-switch use -` and `provider history --switches`.
+//!
+//! Every change of an app's current provider is appended to the sidecar store
+//! (see [`crate::local_store::switch_history`]) together with what triggered
+//! it. Like the edit history, only file-backed databases record switches and
+//! recording failures never fail the switch itself.
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::switch_history::ProviderSwitchRecord;
+use crate::local_store::LocalStore;
+
+/// Switches kept across all apps; older ones are pruned on write.
+const MAX_SWITCH_RECORDS: i64 = 500;
+
+/// What triggered a switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchSource {
+    Cli,
+    Tui,
+    /// The proxy moved to another provider after a failure, or failover
+    /// was enabled and moved the app to the head of its queue.
+    Failover,
+    Schedule,
+    /// Profiles, universal providers and deep links.
+    Other,
+}
+
+impl SwitchSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cli => "cli",
+            Self::Tui => "tui",
+            Self::Failover => "failover",
+            Self::Schedule => "schedule",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Log a switch from `from` to `to`; no-op switches are skipped and
+/// failures are only logged.
+pub(crate) fn record_best_effort(
+    db: &Database,
+    app_type: &AppType,
+    from: Option<&str>,
+    to: &str,
+    source: SwitchSource,
+) {
+    if db.file_path().is_none() || from == Some(to) {
+        return;
+    }
+    let record = ProviderSwitchRecord {
+        app_type: app_type.as_str().to_string(),
+        from_id: from.map(str::to_string),
+        to_id: to.to_string(),
+        source: source.as_str().to_string(),
+        switched_at: chrono::Utc::now().timestamp(),
+    };
+    let result = LocalStore::open()
+        .and_then(|store| store.record_provider_switch(&record, MAX_SWITCH_RECORDS));
+    if let Err(error) = result {
+        log::debug!("[SWITCH] record {} {to} failed: {error}", app_type.as_str());
+    }
+}
+
+/// Most recent switches, newest first; all apps when `app_type` is `None`.
+pub fn recent(
+    app_type: Option<&AppType>,
+    limit: usize,
+) -> Result<Vec<ProviderSwitchRecord>, AppError> {
+    if !LocalStore::path()?.exists() {
+        return Ok(Vec::new());
+    }
+    LocalStore::open()?.list_provider_switches(app_type.map(AppType::as_str), limit)
+}
+
+/// The provider `cc-switch use -` returns to.
+///
+/// Normally the one the latest switch came from. If the current provider
+/// was changed without a logged switch, the latest target is used instead.
+pub fn previous_provider(
+    app_type: &AppType,
+    current: Option<&str>,
+) -> Result<Option<String>, AppError> {
+    Ok(recent(Some(app_type), 1)?
+        .into_iter()
+        .next()
+        .and_then(|latest| resolve_previous(latest, current)))
+}
+
+fn resolve_previous(latest: ProviderSwitchRecord, current: Option<&str>) -> Option<String> {
+    if current == Some(latest.to_id.as_str()) {
+        latest.from_id
+    } else {
+        Some(latest.to_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previous_provider_follows_the_latest_switch() {
+        let latest = ProviderSwitchRecord {
+            app_type: "claude".to_string(),
+            from_id: Some("a".to_string()),
+            to_id: "b".to_string(),
+            source: "cli".to_string(),
+            switched_at: 1,
+        };
+        assert_eq!(
+            resolve_previous(latest.clone(), Some("b")).as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            resolve_previous(latest.clone(), Some("c")).as_deref(),
+            Some("b")
+        );
+        let first = ProviderSwitchRecord {
+            from_id: None,
+            ..latest
+        };
+        assert_eq!(resolve_previous(first, Some("b")), None);
+    }
+}
@@ -0,0 +1,598 @@
+//! Automatic provider switching rules.
+//!
+//! Rules from [`SwitchRulesSettings`] are evaluated by the daemon once a
+//! minute:
+//!
+//! - **schedule**: a local time window, e.g. an off-peak relay at night and
+//!   the official plan during the day,
+//! - **budget**: the current provider's `limitDailyUsd` / `limitMonthlyUsd` is
+//!   exceeded ([`Database::check_provider_limits`]),
+//! - **quota**: a subscription quota window of the app
+//!   ([`get_subscription_quota`]) reached a utilization percentage.
+//!
+//! A rule fires when its condition becomes true, not on every tick, so a
+//! manual switch made while a window is open is left alone. Rules are checked
+//! in list order and at most one rule switches an app per tick. Switches go
+//! through [`ProviderService::switch_with_source`], which holds the app's
+//! switch lock: an in-process mutex plus a lock file beside the database, so a
+//! rule firing waits for a CLI or TUI switch of the same app and vice versa.
+//! Every firing is logged in the sidecar store.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime, Weekday};
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::local_store::switch_rules::SwitchRuleFiring;
+use crate::local_store::LocalStore;
+use crate::services::subscription::{get_subscription_quota, SubscriptionQuota};
+use crate::services::switch_history::SwitchSource;
+use crate::services::ProviderService;
+use crate::settings::{SwitchCondition, SwitchRule};
+use crate::store::AppState;
+
+const TICK_SECS: u64 = 60;
+/// Subscription quotas are fetched from upstream at most this often.
+const QUOTA_REFRESH_SECS: i64 = 5 * 60;
+/// Prune roughly once a day.
+const PRUNE_EVERY_TICKS: u64 = 24 * 60;
+const LOG_RETAIN_SECS: i64 = 90 * 24 * 60 * 60;
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+fn parse_day(value: &str) -> Option<Weekday> {
+    value.trim().parse::<Weekday>().ok()
+}
+
+fn supports_subscription_quota(app_type: &AppType) -> bool {
+    matches!(app_type, AppType::Claude | AppType::Codex | AppType::Gemini)
+}
+
+/// Reject rules the daemon could never evaluate.
+pub fn validate_rule(rule: &SwitchRule) -> Result<(), AppError> {
+    if rule.name.trim().is_empty() {
+        return Err(AppError::localized(
+            "switch_rule.name.empty",
+            "规则名称不能为空",
+            "Rule name cannot be empty",
+        ));
+    }
+    if rule.app.is_additive_mode() {
+        return Err(AppError::localized(
+            "switch_rule.app.additive",
+            format!("{} 没有单一的当前供应商可切换", rule.app.as_str()),
+            format!(
+                "{} has no single current provider to switch",
+                rule.app.as_str()
+            ),
+        ));
+    }
+    if rule.from.as_deref() == Some(rule.to.as_str()) {
+        return Err(AppError::localized(
+            "switch_rule.from_to.same",
+            "--from 与 --to 指向同一个供应商",
+            "--from and --to name the same provider",
+        ));
+    }
+    match &rule.condition {
+        SwitchCondition::Schedule { start, end, days } => {
+            for time in [start, end] {
+                if parse_time(time).is_none() {
+                    return Err(AppError::localized(
+                        "switch_rule.time.invalid",
+                        format!("时间 '{time}' 无效，应为 HH:MM"),
+                        format!("Invalid time '{time}', expected HH:MM"),
+                    ));
+                }
+            }
+            if parse_time(start) == parse_time(end) {
+                return Err(AppError::localized(
+                    "switch_rule.schedule.empty",
+                    "时间段的开始与结束不能相同",
+                    "Schedule start and end must differ",
+                ));
+            }
+            if let Some(day) = days.iter().find(|day| parse_day(day).is_none()) {
+                return Err(AppError::localized(
+                    "switch_rule.day.invalid",
+                    format!("星期 '{day}' 无效，应为 mon、tue、wed、thu、fri、sat 或 sun"),
+                    format!("Invalid day '{day}', expected mon, tue, wed, thu, fri, sat or sun"),
+                ));
+            }
+        }
+        SwitchCondition::QuotaAbove { .. } if !supports_subscription_quota(&rule.app) => {
+            return Err(AppError::localized(
+                "switch_rule.quota.unsupported",
+                "订阅额度仅支持 claude、codex 和 gemini",
+                "Subscription quota is only available for claude, codex and gemini",
+            ));
+        }
+        SwitchCondition::BudgetExceeded | SwitchCondition::QuotaAbove { .. } => {}
+    }
+    Ok(())
+}
+
+/// Short human-readable form of a condition.
+pub fn describe_condition(condition: &SwitchCondition) -> String {
+    match condition {
+        SwitchCondition::Schedule { start, end, days } if days.is_empty() => {
+            format!("schedule {start}-{end}")
+        }
+        SwitchCondition::Schedule { start, end, days } => {
+            format!("schedule {start}-{end} ({})", days.join(","))
+        }
+        SwitchCondition::BudgetExceeded => "budget exceeded".to_string(),
+        SwitchCondition::QuotaAbove { tier, percent } => format!("quota {tier} >= {percent}%"),
+    }
+}
+
+/// Whether `now` falls in the window. After midnight, a window that wraps
+/// belongs to the day it started on.
+pub(crate) fn schedule_active(start: &str, end: &str, days: &[String], now: NaiveDateTime) -> bool {
+    let (Some(start), Some(end)) = (parse_time(start), parse_time(end)) else {
+        return false;
+    };
+    let time = now.time();
+    let (in_window, day) = if start < end {
+        (start <= time && time < end, now.weekday())
+    } else if time >= start {
+        (true, now.weekday())
+    } else {
+        (time < end, now.weekday().pred())
+    };
+    in_window && (days.is_empty() || days.iter().filter_map(|d| parse_day(d)).any(|d| d == day))
+}
+
+/// What the conditions of one app are evaluated against.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AppSnapshot {
+    pub current: Option<String>,
+    /// The current provider is over its daily or monthly limit.
+    pub budget_exceeded: bool,
+    pub quota: Option<SubscriptionQuota>,
+}
+
+fn condition_reason(
+    condition: &SwitchCondition,
+    snapshot: &AppSnapshot,
+    now: NaiveDateTime,
+) -> Option<String> {
+    match condition {
+        SwitchCondition::Schedule { start, end, days } => {
+            schedule_active(start, end, days, now).then(|| format!("{start}-{end}"))
+        }
+        SwitchCondition::BudgetExceeded => snapshot
+            .budget_exceeded
+            .then(|| "budget exceeded".to_string()),
+        SwitchCondition::QuotaAbove { tier, percent } => snapshot
+            .quota
+            .as_ref()
+            .filter(|quota| quota.success)
+            .and_then(|quota| quota.tiers.iter().find(|candidate| candidate.name == *tier))
+            .filter(|window| window.utilization >= f64::from(*percent))
+            .map(|window| format!("{tier} at {:.0}%", window.utilization)),
+    }
+}
+
+/// The state of one rule in an evaluation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleEvaluation {
+    pub rule: String,
+    pub app: String,
+    pub enabled: bool,
+    /// Why the condition holds; `None` while it does not.
+    pub reason: Option<String>,
+    pub current: Option<String>,
+    pub to: String,
+    /// The rule switches the app now.
+    pub fires: bool,
+}
+
+/// Evaluate `rules` in order. Rules in `was_active` already held on the
+/// previous tick and do not fire again; a rule only fires while its `from`
+/// provider (if any) is current and the app is not on its target yet.
+pub(crate) fn evaluate_rules(
+    rules: &[SwitchRule],
+    snapshots: &HashMap<AppType, AppSnapshot>,
+    now: NaiveDateTime,
+    was_active: &HashSet<String>,
+) -> Vec<RuleEvaluation> {
+    let empty = AppSnapshot::default();
+    let mut switched = HashSet::new();
+    rules
+        .iter()
+        .map(|rule| {
+            let snapshot = snapshots.get(&rule.app).unwrap_or(&empty);
+            let reason = rule
+                .enabled
+                .then(|| condition_reason(&rule.condition, snapshot, now))
+                .flatten();
+            let current = snapshot.current.as_deref();
+            let fires = reason.is_some()
+                && !was_active.contains(&rule.name)
+                && rule
+                    .from
+                    .as_deref()
+                    .is_none_or(|from| current == Some(from))
+                && current != Some(rule.to.as_str())
+                && !switched.contains(&rule.app);
+            if fires {
+                switched.insert(rule.app.clone());
+            }
+            RuleEvaluation {
+                rule: rule.name.clone(),
+                app: rule.app.as_str().to_string(),
+                enabled: rule.enabled,
+                reason,
+                current: snapshot.current.clone(),
+                to: rule.to.clone(),
+                fires,
+            }
+        })
+        .collect()
+}
+
+/// Gather what the rules of each app need; quotas are cached between calls.
+async fn collect_snapshots(
+    db: &Database,
+    rules: &[SwitchRule],
+    quota_cache: &mut HashMap<AppType, (i64, SubscriptionQuota)>,
+) -> HashMap<AppType, AppSnapshot> {
+    let now = chrono::Utc::now().timestamp();
+    let mut snapshots = HashMap::new();
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        if snapshots.contains_key(&rule.app) {
+            continue;
+        }
+        let app_rules = rules
+            .iter()
+            .filter(|candidate| candidate.enabled && candidate.app == rule.app)
+            .collect::<Vec<_>>();
+        let current = crate::settings::get_effective_current_provider(db, &rule.app)
+            .ok()
+            .flatten();
+
+        let budget_exceeded = app_rules
+            .iter()
+            .any(|rule| matches!(rule.condition, SwitchCondition::BudgetExceeded))
+            && current.as_deref().is_some_and(|id| {
+                db.check_provider_limits(id, rule.app.as_str())
+                    .is_ok_and(|status| status.daily_exceeded || status.monthly_exceeded)
+            });
+
+        let needs_quota = app_rules
+            .iter()
+            .any(|rule| matches!(rule.condition, SwitchCondition::QuotaAbove { .. }));
+        let quota = if needs_quota && supports_subscription_quota(&rule.app) {
+            match quota_cache.get(&rule.app) {
+                Some((fetched_at, quota)) if now - fetched_at < QUOTA_REFRESH_SECS => {
+                    Some(quota.clone())
+                }
+                _ => match get_subscription_quota(rule.app.as_str()).await {
+                    Ok(quota) => {
+                        quota_cache.insert(rule.app.clone(), (now, quota.clone()));
+                        Some(quota)
+                    }
+                    Err(error) => {
+                        log::debug!(
+                            "[SWITCH-RULES] quota of {} unavailable: {error}",
+                            rule.app.as_str()
+                        );
+                        None
+                    }
+                },
+            }
+        } else {
+            None
+        };
+
+        snapshots.insert(
+            rule.app.clone(),
+            AppSnapshot {
+                current,
+                budget_exceeded,
+                quota,
+            },
+        );
+    }
+    snapshots
+}
+
+/// Dry run: how every configured rule evaluates right now, as if each
+/// condition had just become true.
+pub async fn preview_rules(db: &Database) -> Vec<RuleEvaluation> {
+    let rules = crate::settings::get_switch_rules_settings().rules;
+    let snapshots = collect_snapshots(db, &rules, &mut HashMap::new()).await;
+    evaluate_rules(
+        &rules,
+        &snapshots,
+        Local::now().naive_local(),
+        &HashSet::new(),
+    )
+}
+
+/// Recently fired rules, newest first.
+pub fn recent_firings(limit: usize) -> Result<Vec<SwitchRuleFiring>, AppError> {
+    if !LocalStore::path()?.exists() {
+        return Ok(Vec::new());
+    }
+    LocalStore::open()?.list_switch_rule_firings(limit)
+}
+
+async fn fire(db: Arc<Database>, rule: &SwitchRule, evaluation: &RuleEvaluation) {
+    let app_type = rule.app.clone();
+    let to = rule.to.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let state = AppState::from_shared_db(db)?;
+        ProviderService::switch_with_source(&state, app_type, &to, SwitchSource::Schedule)
+    })
+    .await
+    .map_err(|error| AppError::Message(error.to_string()))
+    .and_then(|result| result);
+
+    let reason = evaluation.reason.clone().unwrap_or_default();
+    match &result {
+        Ok(()) => log::info!(
+            "[SWITCH-RULES] rule '{}' switched {} to {} ({reason})",
+            rule.name,
+            rule.app.as_str(),
+            rule.to
+        ),
+        Err(error) => log::warn!(
+            "[SWITCH-RULES] rule '{}' failed to switch {} to {}: {error}",
+            rule.name,
+            rule.app.as_str(),
+            rule.to
+        ),
+    }
+
+    let firing = SwitchRuleFiring {
+        rule: rule.name.clone(),
+        app_type: rule.app.as_str().to_string(),
+        from_id: evaluation.current.clone(),
+        to_id: rule.to.clone(),
+        reason,
+        error: result.err().map(|error| error.to_string()),
+        fired_at: chrono::Utc::now().timestamp(),
+    };
+    if let Err(error) =
+        LocalStore::open().and_then(|store| store.record_switch_rule_firing(&firing))
+    {
+        log::debug!("[SWITCH-RULES] record firing failed: {error}");
+    }
+}
+
+/// Daemon task: evaluate the rules every minute.
+pub(crate) fn spawn_switch_rules(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(TICK_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut was_active = HashSet::new();
+        let mut quota_cache = HashMap::new();
+        let mut ticks: u64 = 0;
+        loop {
+            interval.tick().await;
+            let settings = crate::settings::load_switch_rules_settings_from_disk();
+            if !settings.enabled || settings.rules.is_empty() {
+                was_active.clear();
+                continue;
+            }
+            if ticks.is_multiple_of(PRUNE_EVERY_TICKS) {
+                let cutoff = chrono::Utc::now().timestamp() - LOG_RETAIN_SECS;
+                if let Err(error) =
+                    LocalStore::open().and_then(|store| store.prune_switch_rule_log(cutoff))
+                {
+                    log::debug!("[SWITCH-RULES] prune failed ({context}): {error}");
+                }
+            }
+            ticks = ticks.wrapping_add(1);
+
+            // Pick up current providers changed by other processes.
+            let _ = crate::settings::reload_settings();
+            let snapshots = collect_snapshots(&db, &settings.rules, &mut quota_cache).await;
+            let evaluations = evaluate_rules(
+                &settings.rules,
+                &snapshots,
+                Local::now().naive_local(),
+                &was_active,
+            );
+            was_active = evaluations
+                .iter()
+                .filter(|evaluation| evaluation.reason.is_some())
+                .map(|evaluation| evaluation.rule.clone())
+                .collect();
+            for (rule, evaluation) in settings.rules.iter().zip(&evaluations) {
+                if evaluation.fires {
+                    fire(db.clone(), rule, evaluation).await;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::subscription::{CredentialStatus, QuotaTier};
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").expect("parse time")
+    }
+
+    fn rule(name: &str, to: &str, condition: SwitchCondition) -> SwitchRule {
+        SwitchRule {
+            name: name.to_string(),
+            app: AppType::Claude,
+            to: to.to_string(),
+            from: None,
+            enabled: true,
+            condition,
+        }
+    }
+
+    fn night() -> SwitchCondition {
+        SwitchCondition::Schedule {
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+            days: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn schedule_windows_wrap_midnight_and_respect_days() {
+        // 2026-10-16 is a Friday.
+        assert!(schedule_active(
+            "22:00",
+            "08:00",
+            &[],
+            at("2026-10-16 23:30")
+        ));
+        assert!(schedule_active(
+            "22:00",
+            "08:00",
+            &[],
+            at("2026-10-17 07:59")
+        ));
+        assert!(!schedule_active(
+            "22:00",
+            "08:00",
+            &[],
+            at("2026-10-17 08:00")
+        ));
+        assert!(schedule_active(
+            "09:00",
+            "18:00",
+            &[],
+            at("2026-10-16 12:00")
+        ));
+        assert!(!schedule_active(
+            "09:00",
+            "18:00",
+            &[],
+            at("2026-10-16 18:30")
+        ));
+
+        let weekdays = ["mon", "tue", "wed", "thu", "fri"].map(str::to_string);
+        assert!(schedule_active(
+            "22:00",
+            "08:00",
+            &weekdays,
+            at("2026-10-17 02:00")
+        ));
+        assert!(!schedule_active(
+            "22:00",
+            "08:00",
+            &weekdays,
+            at("2026-10-18 02:00")
+        ));
+        assert!(!schedule_active(
+            "09:00",
+            "18:00",
+            &weekdays,
+            at("2026-10-17 12:00")
+        ));
+    }
+
+    #[test]
+    fn rules_fire_once_when_their_condition_becomes_true() {
+        let rules = vec![
+            rule("night", "relay", night()),
+            rule("night-backup", "backup", night()),
+            rule(
+                "plan-limit",
+                "relay",
+                SwitchCondition::QuotaAbove {
+                    tier: "five_hour".to_string(),
+                    percent: 90,
+                },
+            ),
+        ];
+        let mut snapshots = HashMap::new();
+        snapshots.insert(
+            AppType::Claude,
+            AppSnapshot {
+                current: Some("official".to_string()),
+                budget_exceeded: false,
+                quota: Some(SubscriptionQuota {
+                    tool: "claude".to_string(),
+                    credential_status: CredentialStatus::Valid,
+                    credential_message: None,
+                    success: true,
+                    tiers: vec![QuotaTier {
+                        name: "five_hour".to_string(),
+                        utilization: 93.0,
+                        resets_at: None,
+                    }],
+                    extra_usage: None,
+                    error: None,
+                    queried_at: None,
+                }),
+            },
+        );
+
+        let evaluations =
+            evaluate_rules(&rules, &snapshots, at("2026-10-16 23:00"), &HashSet::new());
+        assert_eq!(
+            evaluations
+                .iter()
+                .map(|evaluation| (evaluation.rule.as_str(), evaluation.fires))
+                .collect::<Vec<_>>(),
+            vec![
+                ("night", true),
+                ("night-backup", false),
+                ("plan-limit", false)
+            ]
+        );
+        assert_eq!(evaluations[2].reason.as_deref(), Some("five_hour at 93%"));
+
+        let was_active = HashSet::from(["night".to_string(), "night-backup".to_string()]);
+        let evaluations = evaluate_rules(&rules, &snapshots, at("2026-10-16 23:01"), &was_active);
+        assert!(!evaluations[0].fires);
+        assert!(evaluations[2].fires);
+
+        let daytime = evaluate_rules(&rules[..1], &snapshots, at("2026-10-17 12:00"), &was_active);
+        assert_eq!(daytime[0].reason, None);
+
+        let mut from_relay = rule("night", "backup", night());
+        from_relay.from = Some("relay".to_string());
+        let evaluations = evaluate_rules(
+            &[from_relay],
+            &snapshots,
+            at("2026-10-16 23:00"),
+            &HashSet::new(),
+        );
+        assert!(evaluations[0].reason.is_some() && !evaluations[0].fires);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(validate_rule(&rule("night", "relay", night())).is_ok());
+        let bad_time = SwitchCondition::Schedule {
+            start: "25:00".to_string(),
+            end: "08:00".to_string(),
+            days: Vec::new(),
+        };
+        assert!(validate_rule(&rule("night", "relay", bad_time)).is_err());
+        let mut opencode = rule("budget", "relay", SwitchCondition::BudgetExceeded);
+        opencode.app = AppType::OpenCode;
+        assert!(validate_rule(&opencode).is_err());
+        assert_eq!(
+            describe_condition(&SwitchCondition::QuotaAbove {
+                tier: "five_hour".to_string(),
+                percent: 90
+            }),
+            "quota five_hour >= 90%"
+        );
+    }
+}
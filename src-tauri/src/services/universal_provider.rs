@@ -0,0 +1,439 @@
+//! Universal providers: one relay account fanned out to every app.
+//!
+//! A [`UniversalProvider`] is stored under the `universal_providers` settings
+//! key. Each enabled app gets a derived `Provider` row (`universal-<app>-<id>`)
+//! generated from it; saving or deleting the universal provider rewrites,
+//! creates or removes those rows through [`ProviderService`] so live configs
+//! follow along exactly as they would for a hand-edited provider.
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::UniversalProvider;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// What a sync did to the derived provider rows, per app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniversalSyncReport {
+    pub created: Vec<AppType>,
+    pub updated: Vec<AppType>,
+    pub removed: Vec<AppType>,
+}
+
+pub struct UniversalProviderService;
+
+impl UniversalProviderService {
+    /// All universal providers, ordered by sort index, then creation time, then id.
+    pub fn list(state: &AppState) -> Result<Vec<UniversalProvider>, AppError> {
+        let mut providers: Vec<UniversalProvider> = state
+            .db
+            .get_all_universal_providers()?
+            .into_values()
+            .collect();
+        providers.sort_by(|a, b| {
+            a.sort_index
+                .unwrap_or(usize::MAX)
+                .cmp(&b.sort_index.unwrap_or(usize::MAX))
+                .then(a.created_at.cmp(&b.created_at))
+                .then(a.id.cmp(&b.id))
+        });
+        Ok(providers)
+    }
+
+    pub fn get(state: &AppState, id: &str) -> Result<UniversalProvider, AppError> {
+        state
+            .db
+            .get_universal_provider(id)?
+            .ok_or_else(|| Self::not_found(id))
+    }
+
+    /// Derive a free id from a display name (`My Relay` -> `my-relay`, `my-relay-2`, ...).
+    pub fn generate_id(state: &AppState, name: &str) -> Result<String, AppError> {
+        let mut base = String::new();
+        for ch in name.trim().to_lowercase().chars() {
+            if ch.is_ascii_lowercase() || ch.is_ascii_digit() {
+                base.push(ch);
+            } else if !base.is_empty() && !base.ends_with('-') {
+                base.push('-');
+            }
+        }
+        let base = base.trim_end_matches('-');
+        let base = if base.is_empty() { "relay" } else { base };
+
+        let existing = state.db.get_all_universal_providers()?;
+        if !existing.contains_key(base) {
+            return Ok(base.to_string());
+        }
+        let mut counter = 2;
+        loop {
+            let candidate = format!("{base}-{counter}");
+            if !existing.contains_key(&candidate) {
+                return Ok(candidate);
+            }
+            counter += 1;
+        }
+    }
+
+    pub fn validate(provider: &UniversalProvider) -> Result<(), AppError> {
+        // The id is embedded in every derived provider id, including the
+        // Hermes/OpenClaw provider keys, so it has to satisfy their rules.
+        if !ProviderService::is_valid_provider_key(&provider.id) {
+            return Err(AppError::localized(
+                "universal.id.invalid",
+                format!(
+                    "统一供应商 ID 只能包含小写字母、数字和单个连字符: {}",
+                    provider.id
+                ),
+                format!(
+                    "Universal provider id may only contain lowercase letters, digits and single dashes: {}",
+                    provider.id
+                ),
+            ));
+        }
+        if provider.name.trim().is_empty() {
+            return Err(AppError::localized(
+                "universal.name.empty",
+                "统一供应商名称不能为空",
+                "Universal provider name cannot be empty",
+            ));
+        }
+        if provider.api_key.trim().is_empty() {
+            return Err(AppError::localized(
+                "universal.api_key.empty",
+                "统一供应商 API Key 不能为空",
+                "Universal provider API key cannot be empty",
+            ));
+        }
+        let endpoints = [
+            Some(provider.base_url.as_str()),
+            provider.endpoints.anthropic.as_deref(),
+            provider.endpoints.openai.as_deref(),
+            provider.endpoints.gemini.as_deref(),
+        ];
+        for url in endpoints.into_iter().flatten() {
+            let url = url.trim();
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(AppError::localized(
+                    "universal.url.invalid",
+                    format!("无效的地址（需以 http:// 或 https:// 开头）: {url}"),
+                    format!("Invalid URL (must start with http:// or https://): {url}"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert or replace a universal provider and bring its derived rows in line.
+    pub fn upsert(
+        state: &AppState,
+        mut provider: UniversalProvider,
+    ) -> Result<UniversalSyncReport, AppError> {
+        Self::validate(&provider)?;
+        if let Some(previous) = state.db.get_universal_provider(&provider.id)? {
+            provider.created_at = provider.created_at.or(previous.created_at);
+            provider.sort_index = provider.sort_index.or(previous.sort_index);
+        }
+        if provider.created_at.is_none() {
+            provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+        }
+
+        for app in AppType::all() {
+            if !provider.apps.is_enabled_for(&app) {
+                Self::ensure_derived_not_current(state, &provider, &app)?;
+            }
+        }
+
+        // Derived rows copy the sealed key as a `vault:` reference.
+        crate::vault::seal_universal_provider_on_save(&state.db, &mut provider);
+        state.db.save_universal_provider(&provider)?;
+        Self::sync(state, &provider)
+    }
+
+    /// Delete a universal provider together with all of its derived rows.
+    pub fn delete(state: &AppState, id: &str) -> Result<UniversalSyncReport, AppError> {
+        let mut provider = Self::get(state, id)?;
+        for app in AppType::all() {
+            Self::ensure_derived_not_current(state, &provider, &app)?;
+        }
+
+        provider.apps = Default::default();
+        let report = Self::sync(state, &provider)?;
+        state.db.delete_universal_provider(id)?;
+        Ok(report)
+    }
+
+    /// Regenerate the derived rows for every enabled app, and remove the rows
+    /// of apps that are no longer enabled.
+    pub fn sync(
+        state: &AppState,
+        provider: &UniversalProvider,
+    ) -> Result<UniversalSyncReport, AppError> {
+        let mut report = UniversalSyncReport::default();
+        for app in AppType::all() {
+            let derived_id = provider.derived_provider_id(&app);
+            let existing = ProviderService::list(state, app.clone())?
+                .get(&derived_id)
+                .cloned();
+            match (provider.to_app_provider(&app), existing) {
+                (Some(mut derived), Some(existing)) => {
+                    derived.sort_index = existing.sort_index;
+                    derived.created_at = existing.created_at.or(derived.created_at);
+                    derived.in_failover_queue = existing.in_failover_queue;
+                    ProviderService::update(state, app.clone(), derived)?;
+                    report.updated.push(app);
+                }
+                (Some(derived), None) => {
+                    ProviderService::add(state, app.clone(), derived)?;
+                    report.created.push(app);
+                }
+                (None, Some(_)) => {
+                    ProviderService::delete(state, app.clone(), &derived_id)?;
+                    report.removed.push(app);
+                }
+                (None, None) => {}
+            }
+        }
+        Ok(report)
+    }
+
+    /// Re-sync and make the derived provider current in each requested app
+    /// (all enabled apps when `apps` is empty). Returns the apps switched.
+    pub fn apply(state: &AppState, id: &str, apps: &[AppType]) -> Result<Vec<AppType>, AppError> {
+        let provider = Self::get(state, id)?;
+        let targets = if apps.is_empty() {
+            provider.apps.enabled_apps()
+        } else {
+            for app in apps {
+                if !provider.apps.is_enabled_for(app) {
+                    return Err(AppError::localized(
+                        "universal.app.disabled",
+                        format!("统一供应商 {id} 未启用应用 {}", app.as_str()),
+                        format!(
+                            "Universal provider {id} is not enabled for {}",
+                            app.as_str()
+                        ),
+                    ));
+                }
+            }
+            apps.to_vec()
+        };
+
+        Self::sync(state, &provider)?;
+        for app in &targets {
+            ProviderService::switch(state, app.clone(), &provider.derived_provider_id(app))?;
+        }
+        Ok(targets)
+    }
+
+    fn ensure_derived_not_current(
+        state: &AppState,
+        provider: &UniversalProvider,
+        app: &AppType,
+    ) -> Result<(), AppError> {
+        if app.is_additive_mode() {
+            return Ok(());
+        }
+        let derived_id = provider.derived_provider_id(app);
+        if ProviderService::current(state, app.clone())? == derived_id {
+            return Err(AppError::localized(
+                "universal.derived.current",
+                format!(
+                    "{} 正在使用 {derived_id}，请先切换到其他供应商",
+                    app.as_str()
+                ),
+                format!(
+                    "{} is currently using {derived_id}; switch to another provider first",
+                    app.as_str()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn not_found(id: &str) -> AppError {
+        AppError::localized(
+            "universal.not_found",
+            format!("统一供应商不存在: {id}"),
+            format!("Universal provider not found: {id}"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{CodexModelConfig, UniversalProviderApps, UniversalProviderModels};
+    use crate::test_support::TestEnvGuard;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn relay() -> UniversalProvider {
+        let mut provider = UniversalProvider::new(
+            "relay".to_string(),
+            "Relay".to_string(),
+            "https://relay.example.com/".to_string(),
+            "sk-relay".to_string(),
+        );
+        provider.apps = UniversalProviderApps {
+            claude: true,
+            codex: true,
+            gemini: true,
+            ..Default::default()
+        };
+        provider.models = UniversalProviderModels {
+            codex: Some(CodexModelConfig {
+                model: Some("gpt-relay".to_string()),
+                reasoning_effort: Some("medium".to_string()),
+            }),
+            ..Default::default()
+        };
+        provider
+    }
+
+    #[test]
+    fn derived_providers_follow_protocol_endpoints() {
+        let mut provider = relay();
+        provider.endpoints.anthropic = Some("https://relay.example.com/anthropic".to_string());
+
+        let claude = provider.to_app_provider(&AppType::Claude).expect("claude");
+        assert_eq!(claude.id, "universal-claude-relay");
+        assert_eq!(
+            claude.settings_config["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example.com/anthropic"
+        );
+        assert_eq!(
+            claude.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-relay"
+        );
+
+        let codex = provider.to_app_provider(&AppType::Codex).expect("codex");
+        let config = codex.settings_config["config"].as_str().expect("toml");
+        assert!(config.contains("base_url = \"https://relay.example.com/v1\""));
+        assert!(config.contains("model = \"gpt-relay\""));
+        assert!(config.contains("model_reasoning_effort = \"medium\""));
+        assert_eq!(codex.settings_config["auth"]["OPENAI_API_KEY"], "sk-relay");
+
+        let gemini = provider.to_app_provider(&AppType::Gemini).expect("gemini");
+        assert_eq!(
+            gemini.settings_config["env"]["GOOGLE_GEMINI_BASE_URL"],
+            "https://relay.example.com"
+        );
+
+        assert!(provider.to_app_provider(&AppType::OpenCode).is_none());
+        provider.apps.openclaw = true;
+        let openclaw = provider
+            .to_app_provider(&AppType::OpenClaw)
+            .expect("openclaw");
+        assert_eq!(openclaw.settings_config["models"][0]["id"], "gpt-relay");
+    }
+
+    #[test]
+    #[serial]
+    fn upsert_and_delete_keep_derived_rows_in_sync() {
+        let home = TempDir::new().expect("temp home");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+        // Keep a hand-made provider current so the derived rows stay removable.
+        for (app, settings) in [
+            (
+                AppType::Claude,
+                serde_json::json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-plain" } }),
+            ),
+            (
+                AppType::Codex,
+                serde_json::json!({
+                    "auth": { "OPENAI_API_KEY": "sk-plain" },
+                    "config": crate::codex_config::build_codex_third_party_config_toml(
+                        "Plain",
+                        "https://plain.example.com/v1",
+                        "gpt-5.5",
+                        "responses",
+                    ),
+                }),
+            ),
+            (
+                AppType::Gemini,
+                serde_json::json!({ "env": { "GEMINI_API_KEY": "sk-plain" } }),
+            ),
+        ] {
+            let plain = crate::provider::Provider::with_id(
+                "plain".to_string(),
+                "Plain".to_string(),
+                settings,
+                None,
+            );
+            ProviderService::add(&state, app, plain).expect("seed plain provider");
+        }
+
+        let report = UniversalProviderService::upsert(&state, relay()).expect("add");
+        assert_eq!(
+            report.created,
+            vec![AppType::Claude, AppType::Codex, AppType::Gemini]
+        );
+
+        let mut edited = UniversalProviderService::get(&state, "relay").expect("get");
+        edited.api_key = "sk-rotated".to_string();
+        edited.apps.gemini = false;
+        let report = UniversalProviderService::upsert(&state, edited).expect("edit");
+        assert_eq!(report.updated, vec![AppType::Claude, AppType::Codex]);
+        assert_eq!(report.removed, vec![AppType::Gemini]);
+
+        let claude = ProviderService::list(&state, AppType::Claude).expect("list");
+        assert_eq!(
+            claude["universal-claude-relay"].settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-rotated"
+        );
+        assert!(!ProviderService::list(&state, AppType::Gemini)
+            .expect("list")
+            .contains_key("universal-gemini-relay"));
+
+        UniversalProviderService::delete(&state, "relay").expect("delete");
+        assert!(UniversalProviderService::list(&state)
+            .expect("list")
+            .is_empty());
+        assert!(!ProviderService::list(&state, AppType::Codex)
+            .expect("list")
+            .contains_key("universal-codex-relay"));
+    }
+
+    #[test]
+    #[serial]
+    fn universal_keys_are_sealed_by_migrate_and_on_save() {
+        let home = TempDir::new().expect("temp home");
+        let _env = TestEnvGuard::isolated(home.path());
+        let state = AppState::try_new().expect("state");
+        let mut provider = relay();
+        provider.apps = UniversalProviderApps::default();
+        UniversalProviderService::upsert(&state, provider).expect("add");
+
+        let (key, migration) =
+            crate::services::vault::VaultService::init(&state, "correct horse").expect("init");
+        assert_eq!(migration.universal_providers, 1);
+        let stored = UniversalProviderService::get(&state, "relay").expect("get");
+        assert!(
+            crate::vault::is_sealed(&stored.api_key),
+            "{}",
+            stored.api_key
+        );
+
+        crate::vault::set_session_key(key, None);
+        let mut edited = stored;
+        edited.api_key = "sk-rotated".to_string();
+        UniversalProviderService::upsert(&state, edited).expect("edit");
+        let stored = UniversalProviderService::get(&state, "relay").expect("get");
+        crate::vault::clear_session_key();
+        assert!(
+            crate::vault::is_sealed(&stored.api_key),
+            "{}",
+            stored.api_key
+        );
+        assert_eq!(
+            crate::secret_ref::resolve_value(&stored.api_key).ok(),
+            None,
+            "resolving needs the key again once the session ends"
+        );
+    }
+}
@@ -0,0 +1,718 @@
+//! Budget and anomaly alerts.
+//!
+//! Rules are evaluated against the main usage tables and configured through
+//! [`UsageAlertSettings`]:
+//!
+//! - **budget**: daily / monthly spend crossing configured percentages of a
+//!   budget (once per threshold per period; when several thresholds are
+//!   crossed at once only the highest one is delivered),
+//! - **spend spike**: the last hour's spend compared with the trailing 24h
+//!   hourly average (at most once per clock hour),
+//! - **error rate**: share of failed proxy requests per provider over the last
+//!   15 minutes (at most once per provider per clock hour).
+//!
+//! Every alert is claimed in the sidecar history table by a dedup key before
+//! delivery, so the daemon and a foreground proxy evaluating the same rules
+//! never send the same alert twice. Delivery goes to the configured webhooks
+//! and, on Linux, `notify-send`. Everything here is best-effort: failures are
+//! logged and recorded on the history row, never surfaced to the proxy.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::local_store::alerts::NewAlert;
+use crate::local_store::LocalStore;
+use crate::services::usage_stats::effective_usage_log_filter;
+use crate::settings::{AlertWebhook, AlertWebhookFormat, UsageAlertSettings};
+
+#[cfg(feature = "cli")]
+pub use crate::local_store::alerts::AlertHistoryRow;
+
+/// Fallback poll interval; covers writes made by other processes (the daemon's
+/// proxy worker, session imports) that cannot wake this process directly.
+const POLL_INTERVAL_SECS: u64 = 60;
+/// Let a burst of proxy writes settle before evaluating.
+const WAKE_DEBOUNCE_MS: u64 = 2_000;
+const ERROR_RATE_WINDOW_SECS: i64 = 15 * 60;
+const SPIKE_BASELINE_HOURS: i64 = 24;
+const HISTORY_RETAIN_SECONDS: i64 = 90 * 24 * 60 * 60;
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    Budget,
+    SpendSpike,
+    ErrorRate,
+    Test,
+}
+
+impl AlertKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Budget => "budget",
+            Self::SpendSpike => "spend_spike",
+            Self::ErrorRate => "error_rate",
+            Self::Test => "test",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// An alert produced by rule evaluation, before it is claimed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageAlert {
+    pub kind: AlertKind,
+    pub severity: AlertSeverity,
+    pub dedup_key: String,
+    pub title: String,
+    pub message: String,
+    pub details: serde_json::Value,
+    pub created_at: i64,
+    /// `false` for lower budget thresholds crossed in the same evaluation as
+    /// a higher one: they are recorded for dedup but not delivered.
+    #[serde(skip)]
+    pub deliver: bool,
+}
+
+impl UsageAlert {
+    fn as_new_alert(&self) -> NewAlert<'_> {
+        NewAlert {
+            dedup_key: &self.dedup_key,
+            kind: self.kind.as_str(),
+            severity: self.severity.as_str(),
+            title: &self.title,
+            message: &self.message,
+            details: &self.details,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Evaluate every enabled rule at `now`.
+pub(crate) fn evaluate_alerts(
+    db: &Database,
+    settings: &UsageAlertSettings,
+    now: DateTime<Local>,
+) -> Result<Vec<UsageAlert>, AppError> {
+    let mut alerts = Vec::new();
+    if let Some(budget) = settings.daily_budget_usd {
+        let start = local_midnight(now, now.date_naive());
+        let spent = spend_between(db, start, now.timestamp())?;
+        alerts.extend(budget_alerts(
+            BudgetPeriod::Daily,
+            &now.format("%Y-%m-%d").to_string(),
+            spent,
+            budget,
+            &settings.budget_thresholds,
+            now.timestamp(),
+        ));
+    }
+    if let Some(budget) = settings.monthly_budget_usd {
+        let first = now.date_naive().with_day(1).unwrap_or(now.date_naive());
+        let start = local_midnight(now, first);
+        let spent = spend_between(db, start, now.timestamp())?;
+        alerts.extend(budget_alerts(
+            BudgetPeriod::Monthly,
+            &now.format("%Y-%m").to_string(),
+            spent,
+            budget,
+            &settings.budget_thresholds,
+            now.timestamp(),
+        ));
+    }
+    if let Some(multiplier) = settings.spike_multiplier {
+        alerts.extend(spend_spike_alert(
+            db,
+            now,
+            multiplier,
+            settings.spike_min_cost_usd,
+        )?);
+    }
+    if let Some(threshold) = settings.error_rate_threshold {
+        alerts.extend(error_rate_alerts(
+            db,
+            now,
+            threshold,
+            settings.error_rate_min_requests,
+        )?);
+    }
+    Ok(alerts)
+}
+
+/// Claim freshly evaluated alerts; returns the ones this process must deliver
+/// together with their history row id.
+pub(crate) fn claim_alerts(
+    store: &LocalStore,
+    alerts: Vec<UsageAlert>,
+) -> Result<Vec<(i64, UsageAlert)>, AppError> {
+    let mut claimed = Vec::new();
+    for alert in alerts {
+        if !alert.deliver {
+            store.suppress_usage_alert(&alert.as_new_alert())?;
+            continue;
+        }
+        if let Some(id) = store.claim_usage_alert(&alert.as_new_alert())? {
+            claimed.push((id, alert));
+        }
+    }
+    Ok(claimed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BudgetPeriod {
+    Daily,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Daily => "Daily",
+            Self::Monthly => "Monthly",
+        }
+    }
+}
+
+fn budget_alerts(
+    period: BudgetPeriod,
+    period_label: &str,
+    spent: f64,
+    budget: f64,
+    thresholds: &[u8],
+    now: i64,
+) -> Vec<UsageAlert> {
+    if budget <= 0.0 {
+        return Vec::new();
+    }
+    let crossed: Vec<u8> = thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| spent >= budget * f64::from(*threshold) / 100.0)
+        .collect();
+    let Some(highest) = crossed.iter().copied().max() else {
+        return Vec::new();
+    };
+    let percent = spent / budget * 100.0;
+    crossed
+        .into_iter()
+        .map(|threshold| UsageAlert {
+            kind: AlertKind::Budget,
+            severity: if threshold >= 100 {
+                AlertSeverity::Critical
+            } else {
+                AlertSeverity::Warning
+            },
+            dedup_key: format!("budget:{}:{period_label}:{threshold}", period.as_str()),
+            title: format!(
+                "{} budget {threshold}% reached ({period_label})",
+                period.label()
+            ),
+            message: format!(
+                "Spent ${spent:.2} of ${budget:.2} ({percent:.0}%) for {period_label}."
+            ),
+            details: json!({
+                "period": period.as_str(),
+                "periodLabel": period_label,
+                "threshold": threshold,
+                "spentUsd": spent,
+                "budgetUsd": budget,
+            }),
+            created_at: now,
+            deliver: threshold == highest,
+        })
+        .collect()
+}
+
+fn spend_spike_alert(
+    db: &Database,
+    now: DateTime<Local>,
+    multiplier: f64,
+    min_cost: f64,
+) -> Result<Option<UsageAlert>, AppError> {
+    let end = now.timestamp();
+    let hour_start = end - 3600;
+    let last_hour = spend_between(db, hour_start + 1, end)?;
+    if last_hour < min_cost {
+        return Ok(None);
+    }
+    let baseline_total = spend_between(db, hour_start - SPIKE_BASELINE_HOURS * 3600, hour_start)?;
+    let baseline = baseline_total / SPIKE_BASELINE_HOURS as f64;
+    if last_hour < baseline * multiplier {
+        return Ok(None);
+    }
+    let ratio = if baseline > 0.0 {
+        format!(
+            "{:.1}x the trailing 24h average of ${baseline:.2}/h",
+            last_hour / baseline
+        )
+    } else {
+        "no spend in the previous 24h".to_string()
+    };
+    Ok(Some(UsageAlert {
+        kind: AlertKind::SpendSpike,
+        severity: AlertSeverity::Critical,
+        dedup_key: format!("spike:{}", now.format("%Y-%m-%dT%H")),
+        title: format!("Spend spike: ${last_hour:.2} in the last hour"),
+        message: format!("Spent ${last_hour:.2} in the last hour, {ratio}."),
+        details: json!({
+            "lastHourUsd": last_hour,
+            "baselineHourlyUsd": baseline,
+            "multiplier": multiplier,
+        }),
+        created_at: end,
+        deliver: true,
+    }))
+}
+
+fn error_rate_alerts(
+    db: &Database,
+    now: DateTime<Local>,
+    threshold: f64,
+    min_requests: u32,
+) -> Result<Vec<UsageAlert>, AppError> {
+    let end = now.timestamp();
+    let rows: Vec<(String, String, i64, i64)> = {
+        let conn = lock_conn!(db.conn);
+        let mut stmt = conn.prepare(
+            "SELECT app_type, provider_id, COUNT(*),
+                    SUM(CASE WHEN status_code < 200 OR status_code >= 400 THEN 1 ELSE 0 END)
+             FROM proxy_request_logs
+             WHERE COALESCE(data_source, 'proxy') = 'proxy'
+               AND created_at > ?1 AND created_at <= ?2
+             GROUP BY app_type, provider_id",
+        )?;
+        let mapped = stmt.query_map([end - ERROR_RATE_WINDOW_SECS, end], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?;
+        mapped.collect::<Result<_, _>>()?
+    };
+
+    let hour = now.format("%Y-%m-%dT%H");
+    let mut alerts = Vec::new();
+    for (app_type, provider_id, total, failed) in rows {
+        if total < i64::from(min_requests) || total == 0 {
+            continue;
+        }
+        let rate = failed as f64 / total as f64;
+        if rate < threshold {
+            continue;
+        }
+        let provider_name = db
+            .get_provider_by_id(&provider_id, &app_type)
+            .ok()
+            .flatten()
+            .map(|provider| provider.name)
+            .unwrap_or_else(|| provider_id.clone());
+        alerts.push(UsageAlert {
+            kind: AlertKind::ErrorRate,
+            severity: AlertSeverity::Warning,
+            dedup_key: format!("error_rate:{app_type}:{provider_id}:{hour}"),
+            title: format!("High error rate on {provider_name} ({app_type})"),
+            message: format!(
+                "{failed} of {total} proxied requests failed in the last 15 minutes ({:.0}%).",
+                rate * 100.0
+            ),
+            details: json!({
+                "appType": app_type,
+                "providerId": provider_id,
+                "providerName": provider_name,
+                "failed": failed,
+                "total": total,
+                "errorRate": rate,
+            }),
+            created_at: end,
+            deliver: true,
+        });
+    }
+    Ok(alerts)
+}
+
+fn local_midnight(now: DateTime<Local>, date: chrono::NaiveDate) -> i64 {
+    Local
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|start| start.timestamp())
+        .unwrap_or_else(|| now.timestamp() - 86_400)
+}
+
+/// Total cost of effective usage rows created in `[start, end]`.
+fn spend_between(db: &Database, start: i64, end: i64) -> Result<f64, AppError> {
+    let summary = db.get_usage_summary(Some(start), Some(end), None)?;
+    Ok(summary.total_cost.parse::<f64>().unwrap_or(0.0))
+}
+
+/// Highest proxy/session log rowid; lets the poll loop skip evaluation when
+/// nothing was written since the last cycle.
+fn latest_usage_rowid(db: &Database) -> Result<i64, AppError> {
+    let conn = lock_conn!(db.conn);
+    let filter = effective_usage_log_filter("l");
+    Ok(conn.query_row(
+        &format!("SELECT COALESCE(MAX(l.rowid), 0) FROM proxy_request_logs l WHERE {filter}"),
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+/// Webhook body for one alert in the given dialect.
+pub(crate) fn webhook_payload(format: AlertWebhookFormat, alert: &UsageAlert) -> serde_json::Value {
+    let text = format!("[cc-switch] {}\n{}", alert.title, alert.message);
+    match format {
+        AlertWebhookFormat::Generic => json!({
+            "source": "cc-switch",
+            "kind": alert.kind,
+            "severity": alert.severity,
+            "title": alert.title,
+            "message": alert.message,
+            "dedupKey": alert.dedup_key,
+            "createdAt": alert.created_at,
+            "details": alert.details,
+        }),
+        AlertWebhookFormat::Slack => json!({ "text": text }),
+        AlertWebhookFormat::Feishu => json!({
+            "msg_type": "text",
+            "content": { "text": text },
+        }),
+        AlertWebhookFormat::Dingtalk => json!({
+            "msgtype": "text",
+            "text": { "content": text },
+        }),
+    }
+}
+
+async fn post_webhook(webhook: &AlertWebhook, alert: &UsageAlert) -> Result<(), String> {
+    let response = crate::proxy::http_client::get()
+        .post(&webhook.url)
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .json(&webhook_payload(webhook.format, alert))
+        .send()
+        .await
+        .map_err(|error| format!("{}: {error}", webhook.url))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("{}: HTTP {}", webhook.url, response.status()))
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn desktop_notify(alert: &UsageAlert) -> Result<(), String> {
+    let urgency = match alert.severity {
+        AlertSeverity::Critical => "critical",
+        _ => "normal",
+    };
+    let status = tokio::process::Command::new("notify-send")
+        .args(["--app-name=cc-switch", "--urgency", urgency])
+        .arg(&alert.title)
+        .arg(&alert.message)
+        .status()
+        .await
+        .map_err(|error| format!("notify-send: {error}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("notify-send exited with {status}"))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn desktop_notify(_alert: &UsageAlert) -> Result<(), String> {
+    Ok(())
+}
+
+/// Send an alert to every configured channel. Returns the joined error list
+/// when at least one channel failed.
+pub async fn deliver_alert(
+    settings: &UsageAlertSettings,
+    alert: &UsageAlert,
+) -> Result<(), String> {
+    let mut errors = Vec::new();
+    for webhook in &settings.webhooks {
+        if let Err(error) = post_webhook(webhook, alert).await {
+            errors.push(error);
+        }
+    }
+    if settings.desktop_notify {
+        if let Err(error) = desktop_notify(alert).await {
+            errors.push(error);
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// A synthetic alert used by `cc-switch usage alerts test`.
+pub fn test_alert() -> UsageAlert {
+    let now = Local::now().timestamp();
+    UsageAlert {
+        kind: AlertKind::Test,
+        severity: AlertSeverity::Info,
+        dedup_key: format!("test:{now}"),
+        title: "Test alert".to_string(),
+        message: "Alert delivery from cc-switch is configured correctly.".to_string(),
+        details: json!({}),
+        created_at: now,
+        deliver: true,
+    }
+}
+
+/// Evaluate, claim and deliver once. Returns the number of alerts delivered
+/// (or attempted) by this process.
+pub async fn run_alert_cycle(db: Arc<Database>, context: &str) -> usize {
+    let settings = crate::settings::load_usage_alert_settings_from_disk();
+    if !settings.enabled {
+        return 0;
+    }
+    let eval_settings = settings.clone();
+    let claimed = tokio::task::spawn_blocking(move || -> Result<_, AppError> {
+        let store = LocalStore::open()?;
+        let alerts = evaluate_alerts(&db, &eval_settings, Local::now())?;
+        let claimed = claim_alerts(&store, alerts)?;
+        Ok((store, claimed))
+    })
+    .await;
+    let (store, claimed) = match claimed {
+        Ok(Ok(result)) => result,
+        Ok(Err(error)) => {
+            log::warn!("[USAGE-ALERTS] evaluation failed ({context}): {error}");
+            return 0;
+        }
+        Err(error) => {
+            log::warn!("[USAGE-ALERTS] evaluation task failed ({context}): {error}");
+            return 0;
+        }
+    };
+
+    let count = claimed.len();
+    for (id, alert) in claimed {
+        log::info!("[USAGE-ALERTS] {} ({context})", alert.title);
+        let result = deliver_alert(&settings, &alert).await;
+        if let Err(error) = &result {
+            log::warn!(
+                "[USAGE-ALERTS] delivery failed for {}: {error}",
+                alert.dedup_key
+            );
+        }
+        if let Err(error) = store.mark_usage_alert_delivery(id, result.err().as_deref()) {
+            log::debug!("[USAGE-ALERTS] record delivery state failed: {error}");
+        }
+    }
+    count
+}
+
+/// Background monitor: evaluates right after in-process usage writes
+/// (see [`crate::usage_events::notify_log_recorded`]) and on a fixed poll for
+/// writes from other processes.
+pub(crate) fn spawn_usage_alert_monitor(
+    db: Arc<Database>,
+    context: &'static str,
+) -> tokio::task::JoinHandle<()> {
+    let signal = crate::usage_events::log_recorded_signal();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut last_rowid: Option<i64> = None;
+        loop {
+            let polled = tokio::select! {
+                _ = signal.notified() => {
+                    tokio::time::sleep(Duration::from_millis(WAKE_DEBOUNCE_MS)).await;
+                    false
+                }
+                _ = interval.tick() => true,
+            };
+            if polled {
+                let rowid_db = db.clone();
+                let rowid = tokio::task::spawn_blocking(move || latest_usage_rowid(&rowid_db))
+                    .await
+                    .ok()
+                    .and_then(Result::ok);
+                // No new usage and unchanged rules: nothing can newly fire
+                // (windows only shrink as time passes).
+                if rowid.is_some() && rowid == last_rowid && !settings_changed() {
+                    continue;
+                }
+                last_rowid = rowid;
+                prune_history(context).await;
+            }
+            run_alert_cycle(db.clone(), context).await;
+        }
+    })
+}
+
+/// Settings edits from the CLI should be picked up without waiting for new
+/// usage; compare the on-disk rules with the last evaluated ones.
+fn settings_changed() -> bool {
+    use std::sync::Mutex;
+    static LAST: Mutex<Option<UsageAlertSettings>> = Mutex::new(None);
+    let current = crate::settings::load_usage_alert_settings_from_disk();
+    let Ok(mut last) = LAST.lock() else {
+        return true;
+    };
+    let changed = last.as_ref() != Some(&current);
+    *last = Some(current);
+    changed
+}
+
+async fn prune_history(context: &'static str) {
+    let result = tokio::task::spawn_blocking(|| -> Result<usize, AppError> {
+        let store = LocalStore::open()?;
+        store.prune_usage_alert_history(Local::now().timestamp() - HISTORY_RETAIN_SECONDS)
+    })
+    .await;
+    if let Ok(Err(error)) = result {
+        log::debug!("[USAGE-ALERTS] prune history failed ({context}): {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+    use rusqlite::params;
+
+    use super::{
+        budget_alerts, claim_alerts, evaluate_alerts, webhook_payload, AlertKind, BudgetPeriod,
+    };
+    use crate::database::Database;
+    use crate::local_store::LocalStore;
+    use crate::settings::{AlertWebhookFormat, UsageAlertSettings};
+
+    fn insert_log(
+        db: &Database,
+        request_id: &str,
+        provider: &str,
+        status: i64,
+        cost: &str,
+        at: i64,
+    ) {
+        let conn = db.conn.lock().expect("lock db");
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model,
+                input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                input_cost_usd, output_cost_usd, cache_read_cost_usd, cache_creation_cost_usd,
+                total_cost_usd, latency_ms, status_code, created_at, data_source
+            ) VALUES (?1, ?2, 'claude', 'model-x', 100, 10, 0, 0, '0', '0', '0', '0', ?3, 10, ?4, ?5, 'proxy')",
+            params![request_id, provider, cost, status, at],
+        )
+        .expect("insert log");
+    }
+
+    #[test]
+    fn budget_delivers_only_the_highest_newly_crossed_threshold() {
+        let alerts = budget_alerts(
+            BudgetPeriod::Daily,
+            "2026-10-18",
+            8.5,
+            10.0,
+            &[50, 80, 100],
+            0,
+        );
+        let keys: Vec<(&str, bool)> = alerts
+            .iter()
+            .map(|alert| (alert.dedup_key.as_str(), alert.deliver))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("budget:daily:2026-10-18:50", false),
+                ("budget:daily:2026-10-18:80", true),
+            ]
+        );
+        assert!(budget_alerts(BudgetPeriod::Monthly, "2026-10", 4.0, 10.0, &[50], 0).is_empty());
+    }
+
+    #[test]
+    fn evaluation_flags_spend_spike_and_error_rate_once() {
+        let db = Database::memory().expect("db");
+        let now = Local.with_ymd_and_hms(2026, 10, 18, 12, 30, 0).unwrap();
+        let ts = now.timestamp();
+        // Baseline: $0.10 per hour over the previous day.
+        for hour in 2..26 {
+            insert_log(
+                &db,
+                &format!("base-{hour}"),
+                "steady",
+                200,
+                "0.1",
+                ts - hour * 3600,
+            );
+        }
+        // Runaway hour: $12 on one provider, mostly failing on another.
+        insert_log(&db, "burst", "steady", 200, "12", ts - 120);
+        for i in 0..20 {
+            let status = if i < 15 { 529 } else { 200 };
+            insert_log(&db, &format!("flaky-{i}"), "flaky", status, "0", ts - 60);
+        }
+
+        let settings = UsageAlertSettings {
+            enabled: true,
+            daily_budget_usd: Some(20.0),
+            ..Default::default()
+        };
+        let alerts = evaluate_alerts(&db, &settings, now).expect("evaluate");
+        let kinds: Vec<AlertKind> = alerts.iter().map(|alert| alert.kind).collect();
+        assert!(kinds.contains(&AlertKind::SpendSpike), "{kinds:?}");
+        assert!(kinds.contains(&AlertKind::ErrorRate), "{kinds:?}");
+        assert!(kinds.contains(&AlertKind::Budget), "{kinds:?}");
+        let error_alert = alerts
+            .iter()
+            .find(|alert| alert.kind == AlertKind::ErrorRate)
+            .expect("error alert");
+        assert_eq!(error_alert.details["providerId"], "flaky");
+
+        let store = LocalStore::in_memory().expect("store");
+        let first = claim_alerts(&store, alerts.clone()).expect("claim");
+        assert_eq!(first.len(), 3);
+        assert!(claim_alerts(&store, alerts).expect("reclaim").is_empty());
+    }
+
+    #[test]
+    fn webhook_payloads_match_each_dialect() {
+        let alert = super::test_alert();
+        let slack = webhook_payload(AlertWebhookFormat::Slack, &alert);
+        assert!(slack["text"].as_str().unwrap().contains("Test alert"));
+        let feishu = webhook_payload(AlertWebhookFormat::Feishu, &alert);
+        assert_eq!(feishu["msg_type"], "text");
+        assert!(feishu["content"]["text"].is_string());
+        let dingtalk = webhook_payload(AlertWebhookFormat::Dingtalk, &alert);
+        assert_eq!(dingtalk["msgtype"], "text");
+        assert!(dingtalk["text"]["content"].is_string());
+        let generic = webhook_payload(AlertWebhookFormat::Generic, &alert);
+        assert_eq!(generic["kind"], "test");
+        assert_eq!(generic["severity"], "info");
+    }
+}
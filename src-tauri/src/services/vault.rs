@@ -0,0 +1,217 @@
+//! Vault lifecycle: creating it, unlocking it for a session, changing the
+//! passphrase and sealing credentials that are still stored in plain text.
+//!
+//! The cryptography and key lookup live in [`crate::vault`].
+
+use std::time::Duration;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+use crate::vault::{self, Unlocked, VaultKey, VaultMeta};
+
+/// How many stored credentials a migration sealed, by origin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct VaultMigration {
+    pub provider_fields: usize,
+    pub providers: usize,
+    pub mcp_fields: usize,
+    pub mcp_servers: usize,
+    pub sync_passwords: usize,
+    pub universal_providers: usize,
+    /// Edit history revisions that still held a plain credential.
+    pub history_revisions: usize,
+}
+
+impl VaultMigration {
+    pub fn total_fields(&self) -> usize {
+        self.provider_fields + self.mcp_fields + self.sync_passwords + self.universal_providers
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct VaultStatus {
+    pub meta: Option<VaultMeta>,
+    /// Whether a key matching this vault is available to this process.
+    pub unlocked: bool,
+    /// Providers, universal providers, MCP servers and sync backends still
+    /// holding a plain credential.
+    pub plain_entries: usize,
+}
+
+pub(crate) struct VaultService;
+
+impl VaultService {
+    pub fn status(state: &AppState) -> Result<VaultStatus, AppError> {
+        let meta = vault::load_meta(&state.db)?;
+        let unlocked = meta
+            .as_ref()
+            .is_some_and(|meta| vault::current_key().is_ok_and(|key| key.key_id() == meta.key_id));
+        Ok(VaultStatus {
+            meta,
+            unlocked,
+            plain_entries: Self::count_plain(state)?,
+        })
+    }
+
+    /// Creates the vault and seals every plain credential with its new key.
+    pub fn init(
+        state: &AppState,
+        passphrase: &str,
+    ) -> Result<(VaultKey, VaultMigration), AppError> {
+        if vault::load_meta(&state.db)?.is_some() {
+            return Err(AppError::localized(
+                "vault.already_initialized",
+                "加密库已存在；修改主密码请使用 `cc-switch vault rekey`",
+                "A vault already exists; use `cc-switch vault rekey` to change the passphrase",
+            ));
+        }
+        let (meta, key) = VaultMeta::create(passphrase)?;
+        vault::save_meta(&state.db, &meta)?;
+        let migration = Self::migrate(state, &Unlocked::Local(key.clone()))?;
+        Ok((key, migration))
+    }
+
+    /// The data key for `passphrase`.
+    pub fn unlock(state: &AppState, passphrase: &str) -> Result<VaultKey, AppError> {
+        Self::meta(state)?.unlock(passphrase)
+    }
+
+    /// Rewraps the data key under `new_passphrase`. Stored values are not
+    /// touched, so sessions unlocked before stay valid.
+    pub fn rekey(
+        state: &AppState,
+        old_passphrase: &str,
+        new_passphrase: &str,
+    ) -> Result<(), AppError> {
+        let meta = Self::meta(state)?;
+        let key = meta.unlock(old_passphrase)?;
+        vault::save_meta(&state.db, &meta.rekey(&key, new_passphrase)?)
+    }
+
+    /// Hands `key` to the session agent, or keeps it in this process where
+    /// there is no daemon.
+    pub fn start_session(key: VaultKey, ttl: Option<Duration>) -> Result<(), AppError> {
+        #[cfg(unix)]
+        {
+            vault::agent::store_key(
+                &key,
+                ttl,
+                crate::services::ProxyService::resolve_managed_proxy_executable,
+            )?;
+        }
+        vault::set_session_key(key, ttl);
+        Ok(())
+    }
+
+    pub fn end_session() -> Result<(), AppError> {
+        vault::clear_session_key();
+        #[cfg(unix)]
+        vault::agent::clear_key()?;
+        Ok(())
+    }
+
+    /// Seals plain credentials in provider settings, universal providers, MCP
+    /// server definitions, the WebDAV/S3 sync settings and the edit history.
+    pub fn migrate(state: &AppState, key: &Unlocked) -> Result<VaultMigration, AppError> {
+        let meta = Self::meta(state)?;
+        if key.key_id() != meta.key_id {
+            return Err(AppError::localized(
+                "vault.key_mismatch",
+                "当前会话的密钥不属于这个加密库，请重新运行 `cc-switch vault unlock`",
+                "The session key does not belong to this vault; run `cc-switch vault unlock` again",
+            ));
+        }
+
+        let mut migration = VaultMigration::default();
+        {
+            let mut config = state.config.write()?;
+            for app in AppType::all() {
+                let Some(manager) = config.get_manager_mut(&app) else {
+                    continue;
+                };
+                for provider in manager.providers.values_mut() {
+                    let sealed = vault::seal_provider(key, provider);
+                    if sealed > 0 {
+                        migration.providers += 1;
+                        migration.provider_fields += sealed;
+                    }
+                }
+            }
+            if let Some(servers) = config.mcp.servers.as_mut() {
+                for server in servers.values_mut() {
+                    let sealed = vault::seal_settings(key, &mut server.server);
+                    if sealed > 0 {
+                        migration.mcp_servers += 1;
+                        migration.mcp_fields += sealed;
+                    }
+                }
+            }
+        }
+        if migration.provider_fields + migration.mcp_fields > 0 {
+            state.save()?;
+        }
+
+        for mut provider in state.db.get_all_universal_providers()?.into_values() {
+            if let Some(sealed) = vault::seal_plain(key, &provider.api_key) {
+                provider.api_key = sealed;
+                state.db.save_universal_provider(&provider)?;
+                migration.universal_providers += 1;
+            }
+        }
+
+        if let Some(mut webdav) = crate::settings::get_webdav_sync_settings() {
+            if let Some(sealed) = vault::seal_plain(key, &webdav.password) {
+                webdav.password = sealed;
+                crate::settings::set_webdav_sync_settings(Some(webdav))?;
+                migration.sync_passwords += 1;
+            }
+        }
+        if let Some(mut s3) = crate::settings::get_s3_sync_settings() {
+            if let Some(sealed) = vault::seal_plain(key, &s3.secret_access_key) {
+                s3.secret_access_key = sealed;
+                crate::settings::set_s3_sync_settings(Some(s3))?;
+                migration.sync_passwords += 1;
+            }
+        }
+        migration.history_revisions = crate::services::config_history::seal_recorded(key)?;
+        Ok(migration)
+    }
+
+    fn count_plain(state: &AppState) -> Result<usize, AppError> {
+        let config = state.config.read()?;
+        let plain_providers = AppType::all()
+            .filter_map(|app| config.get_manager(&app))
+            .flat_map(|manager| manager.providers.values())
+            .filter(|provider| crate::secret_ref::provider_has_plain_secrets(provider))
+            .count();
+        let plain_servers = config
+            .mcp
+            .servers
+            .iter()
+            .flat_map(|servers| servers.values())
+            .filter(|server| crate::secret_ref::has_plain_secrets(&server.server))
+            .count();
+        let mut plain = plain_providers + plain_servers;
+
+        let is_plain =
+            |value: &str| !value.trim().is_empty() && !crate::secret_ref::is_reference(value);
+        plain += state
+            .db
+            .get_all_universal_providers()?
+            .values()
+            .filter(|provider| is_plain(&provider.api_key))
+            .count();
+        if crate::settings::get_webdav_sync_settings().is_some_and(|s| is_plain(&s.password)) {
+            plain += 1;
+        }
+        if crate::settings::get_s3_sync_settings().is_some_and(|s| is_plain(&s.secret_access_key)) {
+            plain += 1;
+        }
+        Ok(plain)
+    }
+
+    fn meta(state: &AppState) -> Result<VaultMeta, AppError> {
+        vault::load_meta(&state.db)?.ok_or_else(vault::not_initialized)
+    }
+}
@@ -200,7 +200,7 @@ async fn download() -> Result<WebDavSyncSummary, AppError> {
 // ---------------------------------------------------------------------------
 
 fn load_webdav_settings(require_enabled: bool) -> Result<WebDavSyncSettings, AppError> {
-    let settings = get_webdav_sync_settings().ok_or_else(|| {
+    let mut settings = get_webdav_sync_settings().ok_or_else(|| {
         localized(
             "webdav.sync.not_configured",
             "未配置 WebDAV 同步",
@@ -215,6 +215,8 @@ fn load_webdav_settings(require_enabled: bool) -> Result<WebDavSyncSettings, App
         ));
     }
     settings.validate()?;
+    // 密码可以是密钥引用或加密库密文，仅在连接时解析
+    settings.password = crate::secret_ref::resolve_value(&settings.password)?;
     Ok(settings)
 }
 
@@ -2,7 +2,7 @@ use crate::app_config::AppType;
 use crate::config::{get_app_config_dir, home_dir, write_json_file};
 use crate::error::AppError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
@@ -326,6 +326,515 @@ impl WebDavSyncSettings {
     }
 }
 
+/// Webhook payload dialect for usage alerts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertWebhookFormat {
+    /// Plain JSON object with the full alert record.
+    #[default]
+    Generic,
+    /// Slack-compatible incoming webhook (`{"text": ...}`).
+    Slack,
+    /// Feishu/Lark custom bot (`msg_type = text`).
+    Feishu,
+    /// DingTalk custom robot (`msgtype = text`).
+    Dingtalk,
+}
+
+impl AlertWebhookFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Generic => "generic",
+            Self::Slack => "slack",
+            Self::Feishu => "feishu",
+            Self::Dingtalk => "dingtalk",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertWebhook {
+    pub url: String,
+    #[serde(default)]
+    pub format: AlertWebhookFormat,
+}
+
+/// CLI-only budget and anomaly alert rules evaluated by the daemon.
+///
+/// Lives in `settings.json` (device-local) rather than the shared database;
+/// the daemon re-reads it from disk on every evaluation so CLI edits apply
+/// without a restart.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageAlertSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Daily spend budget in USD across all apps (local calendar day).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_budget_usd: Option<f64>,
+    /// Monthly spend budget in USD across all apps (local calendar month).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_budget_usd: Option<f64>,
+    /// Budget percentages that trigger an alert once per period.
+    #[serde(default = "default_alert_budget_thresholds")]
+    pub budget_thresholds: Vec<u8>,
+    /// Alert when the last hour's spend reaches this multiple of the trailing
+    /// 24h hourly average. `None` disables the rule.
+    #[serde(
+        default = "default_alert_spike_multiplier",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub spike_multiplier: Option<f64>,
+    /// Ignore spikes below this absolute hourly spend.
+    #[serde(default = "default_alert_spike_min_cost")]
+    pub spike_min_cost_usd: f64,
+    /// Alert when a provider's error share over the last 15 minutes reaches
+    /// this ratio (0-1). `None` disables the rule.
+    #[serde(
+        default = "default_alert_error_rate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub error_rate_threshold: Option<f64>,
+    /// Minimum requests in the window before the error-rate rule applies.
+    #[serde(default = "default_alert_error_min_requests")]
+    pub error_rate_min_requests: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<AlertWebhook>,
+    /// Also raise a desktop notification via `notify-send` (Linux only).
+    #[serde(default = "default_alert_desktop_notify")]
+    pub desktop_notify: bool,
+}
+
+fn default_alert_budget_thresholds() -> Vec<u8> {
+    vec![50, 80, 100]
+}
+
+fn default_alert_spike_multiplier() -> Option<f64> {
+    Some(3.0)
+}
+
+fn default_alert_spike_min_cost() -> f64 {
+    5.0
+}
+
+fn default_alert_error_rate() -> Option<f64> {
+    Some(0.5)
+}
+
+fn default_alert_error_min_requests() -> u32 {
+    20
+}
+
+fn default_alert_desktop_notify() -> bool {
+    true
+}
+
+impl Default for UsageAlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
+            budget_thresholds: default_alert_budget_thresholds(),
+            spike_multiplier: default_alert_spike_multiplier(),
+            spike_min_cost_usd: default_alert_spike_min_cost(),
+            error_rate_threshold: default_alert_error_rate(),
+            error_rate_min_requests: default_alert_error_min_requests(),
+            webhooks: Vec::new(),
+            desktop_notify: default_alert_desktop_notify(),
+        }
+    }
+}
+
+impl UsageAlertSettings {
+    pub fn normalize(&mut self) {
+        self.budget_thresholds.retain(|value| *value > 0);
+        self.budget_thresholds.sort_unstable();
+        self.budget_thresholds.dedup();
+        if self.budget_thresholds.is_empty() {
+            self.budget_thresholds = default_alert_budget_thresholds();
+        }
+        for webhook in &mut self.webhooks {
+            webhook.url = webhook.url.trim().to_string();
+        }
+        self.webhooks.retain(|webhook| !webhook.url.is_empty());
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        let positive = |value: Option<f64>| value.is_none_or(|v| v.is_finite() && v > 0.0);
+        if !positive(self.daily_budget_usd) || !positive(self.monthly_budget_usd) {
+            return Err(AppError::localized(
+                "usage_alerts.budget.invalid",
+                "预算必须是大于 0 的金额",
+                "Budgets must be positive amounts.",
+            ));
+        }
+        if self
+            .spike_multiplier
+            .is_some_and(|v| !v.is_finite() || v <= 1.0)
+        {
+            return Err(AppError::localized(
+                "usage_alerts.spike.invalid",
+                "突增倍数必须大于 1",
+                "Spike multiplier must be greater than 1.",
+            ));
+        }
+        if self
+            .error_rate_threshold
+            .is_some_and(|v| !v.is_finite() || v <= 0.0 || v > 1.0)
+        {
+            return Err(AppError::localized(
+                "usage_alerts.error_rate.invalid",
+                "错误率阈值必须在 0 到 1 之间",
+                "Error-rate threshold must be between 0 and 1.",
+            ));
+        }
+        for webhook in &self.webhooks {
+            let parsed = url::Url::parse(&webhook.url).map_err(|_| {
+                AppError::InvalidInput(format!("invalid webhook URL: {}", webhook.url))
+            })?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(AppError::InvalidInput(format!(
+                    "webhook URL must use http or https: {}",
+                    webhook.url
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CLI-only OpenTelemetry export (OTLP/HTTP with JSON encoding).
+///
+/// The proxy worker emits one span per proxied request; the daemon (or a
+/// foreground proxy not managed by it) pushes usage metrics periodically.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OtelExportSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Collector base URL, e.g. `http://localhost:4318`. `/v1/traces` and
+    /// `/v1/metrics` are appended unless the URL already ends with one.
+    #[serde(default)]
+    pub endpoint: String,
+    /// Extra request headers (auth tokens for hosted collectors).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default = "default_otel_service_name")]
+    pub service_name: String,
+    #[serde(default = "default_otel_traces")]
+    pub traces: bool,
+    /// Seconds between metric pushes; 0 disables metrics.
+    #[serde(default = "default_otel_metrics_interval")]
+    pub metrics_interval_secs: u64,
+    /// Spans per export request.
+    #[serde(default = "default_otel_batch_size")]
+    pub batch_size: usize,
+    /// Spans buffered in memory before new ones are dropped.
+    #[serde(default = "default_otel_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+fn default_otel_service_name() -> String {
+    "cc-switch".to_string()
+}
+
+fn default_otel_traces() -> bool {
+    true
+}
+
+fn default_otel_metrics_interval() -> u64 {
+    60
+}
+
+fn default_otel_batch_size() -> usize {
+    256
+}
+
+fn default_otel_queue_capacity() -> usize {
+    2048
+}
+
+impl Default for OtelExportSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            headers: BTreeMap::new(),
+            service_name: default_otel_service_name(),
+            traces: default_otel_traces(),
+            metrics_interval_secs: default_otel_metrics_interval(),
+            batch_size: default_otel_batch_size(),
+            queue_capacity: default_otel_queue_capacity(),
+        }
+    }
+}
+
+impl OtelExportSettings {
+    pub fn normalize(&mut self) {
+        self.endpoint = self.endpoint.trim().trim_end_matches('/').to_string();
+        self.service_name = self.service_name.trim().to_string();
+        if self.service_name.is_empty() {
+            self.service_name = default_otel_service_name();
+        }
+        self.batch_size = self.batch_size.clamp(1, 4096);
+        self.queue_capacity = self.queue_capacity.clamp(self.batch_size, 65_536);
+        if self.metrics_interval_secs > 0 {
+            self.metrics_interval_secs = self.metrics_interval_secs.max(10);
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !self.enabled && self.endpoint.is_empty() {
+            return Ok(());
+        }
+        let parsed = url::Url::parse(&self.endpoint).map_err(|_| {
+            AppError::InvalidInput(format!("invalid OTLP endpoint: {}", self.endpoint))
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AppError::InvalidInput(format!(
+                "OTLP endpoint must use http or https: {}",
+                self.endpoint
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether spans should be queued at all.
+    pub fn traces_active(&self) -> bool {
+        self.enabled && self.traces && !self.endpoint.is_empty()
+    }
+
+    pub fn metrics_active(&self) -> bool {
+        self.enabled && self.metrics_interval_secs > 0 && !self.endpoint.is_empty()
+    }
+
+    /// Full URL for a signal path such as `v1/traces`.
+    pub fn signal_url(&self, signal: &str) -> String {
+        let base = self
+            .endpoint
+            .strip_suffix("/v1/traces")
+            .or_else(|| self.endpoint.strip_suffix("/v1/metrics"))
+            .unwrap_or(&self.endpoint);
+        format!("{base}/{signal}")
+    }
+}
+
+/// CLI-only background health monitoring of providers by the daemon.
+///
+/// Probes reuse the connectivity check of `provider stream-check` (and each
+/// provider's test config) and are recorded in the machine-local sidecar
+/// store; the proxy uses the results to open or close circuit breakers ahead
+/// of real traffic.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthMonitorSettings {
+    /// Off by default: every probe is a real upstream request.
+    #[serde(default = "default_health_monitor_enabled")]
+    pub enabled: bool,
+    /// Seconds between two probes of the same provider.
+    #[serde(default = "default_health_probe_interval")]
+    pub interval_secs: u64,
+    /// Probe every provider of the failover-capable apps, not only the ones
+    /// in a failover queue.
+    #[serde(default)]
+    pub all_providers: bool,
+    /// Consecutive failed probes after which the proxy opens the provider's
+    /// circuit breaker; 0 leaves breakers to real traffic.
+    #[serde(default = "default_health_probe_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Days of probe history to keep.
+    #[serde(default = "default_health_probe_retain_days")]
+    pub retain_days: u32,
+}
+
+fn default_health_monitor_enabled() -> bool {
+    false
+}
+
+fn default_health_probe_interval() -> u64 {
+    300
+}
+
+fn default_health_probe_failure_threshold() -> u32 {
+    2
+}
+
+fn default_health_probe_retain_days() -> u32 {
+    14
+}
+
+impl Default for HealthMonitorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_health_monitor_enabled(),
+            interval_secs: default_health_probe_interval(),
+            all_providers: false,
+            failure_threshold: default_health_probe_failure_threshold(),
+            retain_days: default_health_probe_retain_days(),
+        }
+    }
+}
+
+impl HealthMonitorSettings {
+    pub fn normalize(&mut self) {
+        self.interval_secs = self.interval_secs.clamp(30, 24 * 60 * 60);
+        self.retain_days = self.retain_days.clamp(1, 365);
+    }
+}
+
+/// CLI-only source of the signed remote preset catalog (`provider presets refresh`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetCatalogSettings {
+    /// URL of the catalog; the signature is fetched from `<url>.minisig`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Minisign public key the catalog must be signed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+/// CLI-only background model catalog watching by the daemon.
+///
+/// Each provider's model list is refetched at the interval and compared with
+/// the previous snapshot; additions and removals are kept in the local
+/// sidecar store, and role mappings pointing at a vanished model are flagged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelWatchSettings {
+    #[serde(default = "default_model_watch_enabled")]
+    pub enabled: bool,
+    /// Seconds between two fetches of the same provider's model list.
+    #[serde(default = "default_model_watch_interval")]
+    pub interval_secs: u64,
+    /// Days of model change history to keep.
+    #[serde(default = "default_model_watch_retain_days")]
+    pub retain_days: u32,
+}
+
+fn default_model_watch_enabled() -> bool {
+    true
+}
+
+fn default_model_watch_interval() -> u64 {
+    6 * 60 * 60
+}
+
+fn default_model_watch_retain_days() -> u32 {
+    90
+}
+
+impl Default for ModelWatchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_model_watch_enabled(),
+            interval_secs: default_model_watch_interval(),
+            retain_days: default_model_watch_retain_days(),
+        }
+    }
+}
+
+impl ModelWatchSettings {
+    pub fn normalize(&mut self) {
+        self.interval_secs = self.interval_secs.clamp(15 * 60, 7 * 24 * 60 * 60);
+        self.retain_days = self.retain_days.clamp(1, 365);
+    }
+}
+
+/// CLI-only materialization of every Codex provider as a named profile.
+///
+/// Each provider gets a `[profiles.<id>]` and `[model_providers.cc-switch-<id>]`
+/// entry in `~/.codex/config.toml`, so `codex --profile <id>` can pick it per
+/// invocation. Edits made by hand to those entries survive later syncs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfilesSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Provider ids that never get a profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// CLI-only automatic provider switching evaluated by the daemon.
+///
+/// Rules are checked in list order once a minute; the first rule of an app
+/// whose condition has just become true switches that app to its target.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchRulesSettings {
+    #[serde(default = "default_switch_rules_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<SwitchRule>,
+}
+
+fn default_switch_rules_enabled() -> bool {
+    true
+}
+
+impl Default for SwitchRulesSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_switch_rules_enabled(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl SwitchRulesSettings {
+    pub fn normalize(&mut self) {
+        for rule in &mut self.rules {
+            if let SwitchCondition::QuotaAbove { percent, .. } = &mut rule.condition {
+                *percent = (*percent).clamp(1, 100);
+            }
+        }
+    }
+}
+
+/// One automatic switching rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchRule {
+    pub name: String,
+    pub app: AppType,
+    /// Provider the rule switches to.
+    pub to: String,
+    /// Only fire while this provider is the current one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default = "default_switch_rules_enabled")]
+    pub enabled: bool,
+    pub condition: SwitchCondition,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SwitchCondition {
+    /// Local time window `HH:MM`-`HH:MM`, wrapping past midnight when the end
+    /// is not after the start; `days` (`mon`..`sun`) limits it to weekdays.
+    Schedule {
+        start: String,
+        end: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        days: Vec<String>,
+    },
+    /// The current provider's daily or monthly limit is exceeded.
+    BudgetExceeded,
+    /// A subscription quota window of the app reached `percent` utilization.
+    QuotaAbove {
+        #[serde(default = "default_switch_quota_tier")]
+        tier: String,
+        percent: u8,
+    },
+}
+
+fn default_switch_quota_tier() -> String {
+    "five_hour".to_string()
+}
+
 /// S3-compatible object storage sync settings.
 ///
 /// These settings live in `settings.json`; they are intentionally not part of
@@ -584,6 +1093,27 @@ pub struct AppSettings {
     pub webdav_sync: Option<WebDavSyncSettings>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub s3_sync: Option<S3SyncSettings>,
+    /// CLI-only: budget/anomaly alert rules evaluated by the daemon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_alerts: Option<UsageAlertSettings>,
+    /// CLI-only: OTLP export of proxy spans and usage metrics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otel_export: Option<OtelExportSettings>,
+    /// CLI-only: background provider probing by the daemon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_monitor: Option<HealthMonitorSettings>,
+    /// CLI-only: background refetching of provider model lists by the daemon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_watch: Option<ModelWatchSettings>,
+    /// CLI-only: automatic provider switching rules run by the daemon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub switch_rules: Option<SwitchRulesSettings>,
+    /// CLI-only: Codex providers written as `config.toml` profiles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_profiles: Option<CodexProfilesSettings>,
+    /// CLI-only: remote preset catalog source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset_catalog: Option<PresetCatalogSettings>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub backup_retain_count: Option<u32>,
     /// 首选终端应用，用于会话恢复。
@@ -649,6 +1179,13 @@ impl Default for AppSettings {
             security: None,
             webdav_sync: None,
             s3_sync: None,
+            usage_alerts: None,
+            otel_export: None,
+            health_monitor: None,
+            model_watch: None,
+            switch_rules: None,
+            codex_profiles: None,
+            preset_catalog: None,
             backup_retain_count: None,
             preferred_terminal: None,
             preferred_editor: None,
@@ -729,6 +1266,21 @@ impl AppSettings {
         if let Some(s3) = self.s3_sync.as_mut() {
             s3.normalize();
         }
+        if let Some(alerts) = self.usage_alerts.as_mut() {
+            alerts.normalize();
+        }
+        if let Some(otel) = self.otel_export.as_mut() {
+            otel.normalize();
+        }
+        if let Some(monitor) = self.health_monitor.as_mut() {
+            monitor.normalize();
+        }
+        if let Some(watch) = self.model_watch.as_mut() {
+            watch.normalize();
+        }
+        if let Some(rules) = self.switch_rules.as_mut() {
+            rules.normalize();
+        }
         if self.s3_sync.as_ref().is_some_and(S3SyncSettings::is_empty) {
             self.s3_sync = None;
         }
@@ -1302,6 +1854,139 @@ pub fn set_s3_sync_settings(s3_sync: Option<S3SyncSettings>) -> Result<(), AppEr
     })
 }
 
+pub fn get_usage_alert_settings() -> UsageAlertSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.usage_alerts.clone())
+        .unwrap_or_default()
+}
+
+/// Read alert settings straight from `settings.json`, bypassing the in-process
+/// cache. Long-lived processes (daemon, proxy worker) use this so edits made
+/// by another CLI invocation take effect on the next evaluation.
+pub fn load_usage_alert_settings_from_disk() -> UsageAlertSettings {
+    AppSettings::load().usage_alerts.unwrap_or_default()
+}
+
+pub fn set_usage_alert_settings(mut alerts: UsageAlertSettings) -> Result<(), AppError> {
+    alerts.normalize();
+    alerts.validate()?;
+    mutate_settings(move |settings| {
+        settings.usage_alerts = Some(alerts);
+    })
+}
+
+pub fn get_otel_export_settings() -> OtelExportSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.otel_export.clone())
+        .unwrap_or_default()
+}
+
+/// Disk read for the daemon, which does not reload settings per request.
+pub fn load_otel_export_settings_from_disk() -> OtelExportSettings {
+    AppSettings::load().otel_export.unwrap_or_default()
+}
+
+pub fn set_otel_export_settings(mut otel: OtelExportSettings) -> Result<(), AppError> {
+    otel.normalize();
+    otel.validate()?;
+    mutate_settings(move |settings| {
+        settings.otel_export = Some(otel);
+    })
+}
+
+pub fn get_health_monitor_settings() -> HealthMonitorSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.health_monitor.clone())
+        .unwrap_or_default()
+}
+
+/// Disk read for the daemon and proxy worker, which do not reload settings
+/// on their own.
+pub fn load_health_monitor_settings_from_disk() -> HealthMonitorSettings {
+    AppSettings::load().health_monitor.unwrap_or_default()
+}
+
+pub fn set_health_monitor_settings(mut monitor: HealthMonitorSettings) -> Result<(), AppError> {
+    monitor.normalize();
+    mutate_settings(move |settings| {
+        settings.health_monitor = Some(monitor);
+    })
+}
+
+pub fn get_preset_catalog_settings() -> PresetCatalogSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.preset_catalog.clone())
+        .unwrap_or_default()
+}
+
+pub fn set_preset_catalog_settings(catalog: Option<PresetCatalogSettings>) -> Result<(), AppError> {
+    mutate_settings(move |settings| {
+        settings.preset_catalog = catalog;
+    })
+}
+
+pub fn get_model_watch_settings() -> ModelWatchSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.model_watch.clone())
+        .unwrap_or_default()
+}
+
+/// Disk read for the daemon, which does not reload settings on its own.
+pub fn load_model_watch_settings_from_disk() -> ModelWatchSettings {
+    AppSettings::load().model_watch.unwrap_or_default()
+}
+
+pub fn set_model_watch_settings(mut watch: ModelWatchSettings) -> Result<(), AppError> {
+    watch.normalize();
+    mutate_settings(move |settings| {
+        settings.model_watch = Some(watch);
+    })
+}
+
+pub fn get_switch_rules_settings() -> SwitchRulesSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.switch_rules.clone())
+        .unwrap_or_default()
+}
+
+/// Disk read for the daemon, which does not reload settings on its own.
+pub fn load_switch_rules_settings_from_disk() -> SwitchRulesSettings {
+    AppSettings::load().switch_rules.unwrap_or_default()
+}
+
+pub fn set_switch_rules_settings(mut rules: SwitchRulesSettings) -> Result<(), AppError> {
+    rules.normalize();
+    mutate_settings(move |settings| {
+        settings.switch_rules = Some(rules);
+    })
+}
+
+pub fn get_codex_profiles_settings() -> CodexProfilesSettings {
+    settings_store()
+        .read()
+        .ok()
+        .and_then(|s| s.codex_profiles.clone())
+        .unwrap_or_default()
+}
+
+pub fn set_codex_profiles_settings(profiles: CodexProfilesSettings) -> Result<(), AppError> {
+    mutate_settings(move |settings| {
+        settings.codex_profiles = Some(profiles);
+    })
+}
+
 pub fn update_s3_sync_status(status: WebDavSyncStatus) -> Result<(), AppError> {
     mutate_settings(move |settings| {
         if let Some(ref mut s3) = settings.s3_sync {
@@ -89,6 +89,12 @@ impl AppState {
         Self::from_parts(db, config)
     }
 
+    /// 基于已打开的数据库构建状态（守护进程内的自动切换使用）。
+    pub(crate) fn from_shared_db(db: Arc<Database>) -> Result<Self, AppError> {
+        let config = export_db_to_multi_app_config(&db)?;
+        Self::from_parts(db, config)
+    }
+
     /// 打开只读数据库快照，用于 TUI 后台热刷新等非初始化路径。
     pub fn try_open_snapshot() -> Result<Self, AppError> {
         let db = Arc::new(Database::open_readonly_current_schema()?);
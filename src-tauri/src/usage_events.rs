@@ -1,7 +1,26 @@
 //! Usage event notification shim for the CLI build.
 //!
-//! Upstream desktop emits a Tauri event after usage-log writes. This crate is
-//! CLI-only, so the backend keeps the call sites for upstream parity and makes
-//! notification a no-op.
+//! Upstream desktop emits a Tauri event after usage-log writes. The CLI has no
+//! frontend to notify; instead the call sites wake the in-process usage alert
+//! monitor (when one is running) so budget and anomaly rules are evaluated
+//! right after new spend lands rather than on the next poll.
 
-pub fn notify_log_recorded() {}
+use std::sync::OnceLock;
+
+use tokio::sync::Notify;
+
+static LOG_RECORDED: OnceLock<Notify> = OnceLock::new();
+
+pub fn notify_log_recorded() {
+    // Only processes that installed a monitor have a listener; everywhere else
+    // this stays a cheap no-op.
+    if let Some(signal) = LOG_RECORDED.get() {
+        signal.notify_one();
+    }
+}
+
+/// Signal awaited by [`crate::services::usage_alerts::spawn_usage_alert_monitor`].
+/// `notify_one` stores a permit, so bursts of writes coalesce into one wake-up.
+pub(crate) fn log_recorded_signal() -> &'static Notify {
+    LOG_RECORDED.get_or_init(Notify::new)
+}
@@ -0,0 +1,721 @@
+//! Encrypted-at-rest vault for stored credentials.
+//!
+//! Credential fields (the same ones [`crate::secret_ref`] resolves) can be
+//! sealed into `vault:v1:<base64>` values. Each value is encrypted with
+//! XChaCha20-Poly1305 under a random data key; the data key itself is stored
+//! wrapped by a key derived from the master passphrase with Argon2id, in the
+//! database `settings` table. Rekeying therefore only rewraps the data key,
+//! and database exports, backups and sync artifacts carry ciphertext only.
+//!
+//! Sealed values are decrypted through the secret reference resolver, i.e.
+//! only when a live config is written, when the proxy authenticates upstream
+//! and for processes cc-switch launches. The data key is looked up in this
+//! order:
+//!
+//! 1. the key cached in this process (`vault unlock` inside the daemon, or a
+//!    key derived earlier from the passphrase variable);
+//! 2. the session agent: the daemon holds the key after `vault unlock` until
+//!    `vault lock`, its timeout or daemon exit, and seals and opens values for
+//!    other processes without ever sending the key back (see [`agent`]);
+//! 3. `CC_SWITCH_VAULT_PASSPHRASE`, for scripts and CI.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::database::Database;
+use crate::error::AppError;
+use crate::provider::{Provider, UniversalProvider};
+
+/// Prefix of sealed values; the version follows it.
+pub(crate) const SEALED_PREFIX: &str = "vault:";
+/// Environment variable holding the master passphrase for non-interactive use.
+pub const PASSPHRASE_ENV: &str = "CC_SWITCH_VAULT_PASSPHRASE";
+
+const SEALED_V1: &str = "v1:";
+const META_KEY: &str = "secret_vault";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+const VALUE_AAD: &[u8] = b"cc-switch/vault/value/v1";
+const WRAP_AAD: &[u8] = b"cc-switch/vault/key/v1";
+
+/// OWASP baseline for Argon2id: 19 MiB, two passes, one lane.
+const DEFAULT_KDF: KdfParams = KdfParams {
+    memory_kib: 19 * 1024,
+    iterations: 2,
+    parallelism: 1,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+/// Vault header persisted under the `secret_vault` setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VaultMeta {
+    pub version: u32,
+    /// Always `argon2id`; recorded so the format can evolve.
+    pub kdf: String,
+    pub kdf_params: KdfParams,
+    pub salt: String,
+    /// `nonce || ciphertext` of the data key, base64.
+    pub wrapped_key: String,
+    /// Short fingerprint of the data key, to tell vaults apart.
+    pub key_id: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rekeyed_at: Option<String>,
+}
+
+/// The data key. Zeroed on drop.
+#[derive(Clone)]
+pub(crate) struct VaultKey(Zeroizing<[u8; KEY_LEN]>);
+
+impl std::fmt::Debug for VaultKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("VaultKey(***)")
+    }
+}
+
+impl VaultKey {
+    fn generate() -> Self {
+        let mut bytes = Zeroizing::new([0u8; KEY_LEN]);
+        OsRng.fill_bytes(bytes.as_mut());
+        Self(bytes)
+    }
+
+    fn from_slice(bytes: &[u8]) -> Result<Self, AppError> {
+        let bytes: [u8; KEY_LEN] = bytes.try_into().map_err(|_| corrupted("key length"))?;
+        Ok(Self(Zeroizing::new(bytes)))
+    }
+
+    pub(crate) fn to_base64(&self) -> Zeroizing<String> {
+        Zeroizing::new(BASE64.encode(self.0.as_ref()))
+    }
+
+    pub(crate) fn from_base64(encoded: &str) -> Result<Self, AppError> {
+        let bytes = Zeroizing::new(
+            BASE64
+                .decode(encoded.trim())
+                .map_err(|_| corrupted("key encoding"))?,
+        );
+        Self::from_slice(&bytes)
+    }
+
+    pub(crate) fn key_id(&self) -> String {
+        let digest = Sha256::digest(self.0.as_ref());
+        digest[..6]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(self.0.as_ref().into())
+    }
+
+    /// `vault:v1:<base64(nonce || ciphertext)>` for `plaintext`.
+    pub(crate) fn seal(&self, plaintext: &str) -> String {
+        let sealed = seal_bytes(&self.cipher(), plaintext.as_bytes(), VALUE_AAD);
+        format!("{SEALED_PREFIX}{SEALED_V1}{sealed}")
+    }
+
+    /// Decrypts the part of a sealed value after `vault:`.
+    pub(crate) fn open(&self, payload: &str) -> Result<String, AppError> {
+        let Some(encoded) = payload.trim().strip_prefix(SEALED_V1) else {
+            return Err(AppError::localized(
+                "vault.unsupported_version",
+                "不支持的加密值版本（请升级 cc-switch）",
+                "Unsupported vault value version (upgrade cc-switch)",
+            ));
+        };
+        let plaintext = Zeroizing::new(open_bytes(&self.cipher(), encoded, VALUE_AAD)?);
+        String::from_utf8(plaintext.to_vec()).map_err(|_| corrupted("value encoding"))
+    }
+}
+
+impl VaultMeta {
+    /// New vault header and its data key.
+    pub(crate) fn create(passphrase: &str) -> Result<(Self, VaultKey), AppError> {
+        Self::create_with(passphrase, DEFAULT_KDF)
+    }
+
+    fn create_with(passphrase: &str, params: KdfParams) -> Result<(Self, VaultKey), AppError> {
+        let key = VaultKey::generate();
+        let meta = Self {
+            version: 1,
+            kdf: "argon2id".to_string(),
+            kdf_params: params,
+            salt: String::new(),
+            wrapped_key: String::new(),
+            key_id: key.key_id(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            rekeyed_at: None,
+        }
+        .wrap(&key, passphrase)?;
+        Ok((meta, key))
+    }
+
+    /// The data key, if `passphrase` is right.
+    pub(crate) fn unlock(&self, passphrase: &str) -> Result<VaultKey, AppError> {
+        let salt = BASE64
+            .decode(&self.salt)
+            .map_err(|_| corrupted("salt encoding"))?;
+        let kek = derive_kek(passphrase, &salt, self.kdf_params)?;
+        let bytes = open_bytes(&kek.cipher(), &self.wrapped_key, WRAP_AAD).map_err(|_| {
+            AppError::localized(
+                "vault.wrong_passphrase",
+                "主密码错误",
+                "Wrong vault passphrase",
+            )
+        })?;
+        VaultKey::from_slice(&Zeroizing::new(bytes))
+    }
+
+    /// Same vault with the data key wrapped under `new_passphrase`.
+    pub(crate) fn rekey(&self, key: &VaultKey, new_passphrase: &str) -> Result<Self, AppError> {
+        let mut meta = self.clone().wrap(key, new_passphrase)?;
+        meta.rekeyed_at = Some(chrono::Utc::now().to_rfc3339());
+        Ok(meta)
+    }
+
+    fn wrap(mut self, key: &VaultKey, passphrase: &str) -> Result<Self, AppError> {
+        if passphrase.is_empty() {
+            return Err(AppError::localized(
+                "vault.empty_passphrase",
+                "主密码不能为空",
+                "The vault passphrase cannot be empty",
+            ));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let kek = derive_kek(passphrase, &salt, self.kdf_params)?;
+        self.salt = BASE64.encode(salt);
+        self.wrapped_key = seal_bytes(&kek.cipher(), key.0.as_ref(), WRAP_AAD);
+        Ok(self)
+    }
+}
+
+fn derive_kek(passphrase: &str, salt: &[u8], params: KdfParams) -> Result<VaultKey, AppError> {
+    let params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|err| corrupted(&format!("kdf parameters: {err}")))?;
+    let mut out = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, out.as_mut())
+        .map_err(|err| corrupted(&format!("kdf: {err}")))?;
+    Ok(VaultKey(out))
+}
+
+fn seal_bytes(cipher: &XChaCha20Poly1305, plaintext: &[u8], aad: &[u8]) -> String {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .expect("XChaCha20-Poly1305 encryption cannot fail for in-memory buffers");
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    BASE64.encode(out)
+}
+
+fn open_bytes(cipher: &XChaCha20Poly1305, encoded: &str, aad: &[u8]) -> Result<Vec<u8>, AppError> {
+    let raw = BASE64
+        .decode(encoded.trim())
+        .map_err(|_| corrupted("ciphertext encoding"))?;
+    if raw.len() <= NONCE_LEN {
+        return Err(corrupted("ciphertext length"));
+    }
+    let (nonce, ciphertext) = raw.split_at(NONCE_LEN);
+    cipher
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| {
+            AppError::localized(
+                "vault.decrypt_failed",
+                "无法解密：密钥不匹配或数据已损坏",
+                "Cannot decrypt: the key does not match or the data is corrupted",
+            )
+        })
+}
+
+fn corrupted(what: &str) -> AppError {
+    AppError::localized(
+        "vault.corrupted",
+        format!("加密库数据无效 ({what})"),
+        format!("Invalid vault data ({what})"),
+    )
+}
+
+fn locked() -> AppError {
+    AppError::localized(
+        "vault.locked",
+        format!("加密库已锁定：请运行 `cc-switch vault unlock` 或设置 {PASSPHRASE_ENV}"),
+        format!("The vault is locked: run `cc-switch vault unlock` or set {PASSPHRASE_ENV}"),
+    )
+}
+
+pub(crate) fn not_initialized() -> AppError {
+    AppError::localized(
+        "vault.not_initialized",
+        "尚未创建加密库：请先运行 `cc-switch vault init`",
+        "No vault yet: run `cc-switch vault init` first",
+    )
+}
+
+pub(crate) fn is_sealed(value: &str) -> bool {
+    value.trim_start().starts_with(SEALED_PREFIX)
+}
+
+pub(crate) fn load_meta(db: &Database) -> Result<Option<VaultMeta>, AppError> {
+    db.get_setting(META_KEY)?
+        .map(|raw| serde_json::from_str(&raw).map_err(|err| corrupted(&err.to_string())))
+        .transpose()
+}
+
+pub(crate) fn save_meta(db: &Database, meta: &VaultMeta) -> Result<(), AppError> {
+    let raw = serde_json::to_string(meta).map_err(|err| AppError::JsonSerialize { source: err })?;
+    db.set_setting(META_KEY, &raw)
+}
+
+// ---------------------------------------------------------------------------
+// Session key
+// ---------------------------------------------------------------------------
+
+struct SessionKey {
+    key: VaultKey,
+    expires_at: Option<Instant>,
+}
+
+static SESSION: Mutex<Option<SessionKey>> = Mutex::new(None);
+static AGENT_PROCESS: AtomicBool = AtomicBool::new(false);
+
+/// Marks this process as the session agent (the daemon), so key lookups
+/// never call back into its own socket.
+pub(crate) fn mark_agent_process() {
+    AGENT_PROCESS.store(true, Ordering::SeqCst);
+}
+
+/// Caches `key` in this process, optionally for `ttl` only.
+pub(crate) fn set_session_key(key: VaultKey, ttl: Option<Duration>) {
+    if let Ok(mut session) = SESSION.lock() {
+        *session = Some(SessionKey {
+            key,
+            expires_at: ttl.map(|ttl| Instant::now() + ttl),
+        });
+    }
+}
+
+/// Drops the cached key; returns whether one was held.
+pub(crate) fn clear_session_key() -> bool {
+    SESSION
+        .lock()
+        .map(|mut session| session.take().is_some())
+        .unwrap_or(false)
+}
+
+/// The key cached in this process, unless it has expired.
+pub(crate) fn session_key() -> Option<VaultKey> {
+    let mut session = SESSION.lock().ok()?;
+    if session
+        .as_ref()
+        .and_then(|held| held.expires_at)
+        .is_some_and(|expires_at| Instant::now() >= expires_at)
+    {
+        *session = None;
+    }
+    session.as_ref().map(|held| held.key.clone())
+}
+
+/// An unlocked vault: the data key held by this process, or the session
+/// agent, which seals and opens values with the key it keeps to itself.
+#[derive(Clone)]
+pub(crate) enum Unlocked {
+    Local(VaultKey),
+    #[cfg(unix)]
+    Agent {
+        key_id: String,
+    },
+}
+
+impl Unlocked {
+    pub(crate) fn key_id(&self) -> String {
+        match self {
+            Self::Local(key) => key.key_id(),
+            #[cfg(unix)]
+            Self::Agent { key_id } => key_id.clone(),
+        }
+    }
+
+    /// The sealed form of `plaintext`; `None` if the agent could not seal it,
+    /// e.g. because the vault was locked in the meantime.
+    pub(crate) fn seal(&self, plaintext: &str) -> Option<String> {
+        match self {
+            Self::Local(key) => Some(key.seal(plaintext)),
+            #[cfg(unix)]
+            Self::Agent { .. } => agent::seal(plaintext),
+        }
+    }
+
+    /// Decrypts the part of a sealed value after `vault:`.
+    pub(crate) fn open(&self, payload: &str) -> Result<String, AppError> {
+        match self {
+            Self::Local(key) => key.open(payload),
+            #[cfg(unix)]
+            Self::Agent { .. } => agent::open(payload),
+        }
+    }
+}
+
+/// The unlocked vault for sealing and decrypting values, or a "vault is
+/// locked" error.
+pub(crate) fn current_key() -> Result<Unlocked, AppError> {
+    if let Some(key) = session_key() {
+        return Ok(Unlocked::Local(key));
+    }
+    #[cfg(unix)]
+    if !AGENT_PROCESS.load(Ordering::SeqCst) {
+        if let Some(key_id) = agent::key_id() {
+            return Ok(Unlocked::Agent { key_id });
+        }
+    }
+    if let Some(passphrase) = passphrase_from_env() {
+        let db = Database::open_readonly_current_schema()?;
+        let meta = load_meta(&db)?.ok_or_else(not_initialized)?;
+        let key = meta.unlock(&passphrase)?;
+        set_session_key(key.clone(), None);
+        return Ok(Unlocked::Local(key));
+    }
+    Err(locked())
+}
+
+pub(crate) fn passphrase_from_env() -> Option<Zeroizing<String>> {
+    std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(Zeroizing::new)
+}
+
+/// Decrypts the payload of a `vault:` secret reference.
+pub(crate) fn resolve(payload: &str) -> Result<String, AppError> {
+    current_key()?.open(payload)
+}
+
+// ---------------------------------------------------------------------------
+// Sealing stored settings
+// ---------------------------------------------------------------------------
+
+/// Seals every plain credential field of `settings` in place and returns how
+/// many were sealed. Empty values and secret references are left alone.
+pub(crate) fn seal_settings(key: &Unlocked, settings: &mut Value) -> usize {
+    let Value::Object(map) = settings else {
+        return 0;
+    };
+    let mut sealed = 0;
+    for (name, child) in map.iter_mut() {
+        match child {
+            Value::String(raw) if crate::secret_ref::is_secret_field(name) => {
+                if let Some(value) = seal_plain(key, raw) {
+                    *raw = value;
+                    sealed += 1;
+                }
+            }
+            Value::Object(_) => sealed += seal_settings(key, child),
+            _ => {}
+        }
+    }
+    sealed
+}
+
+/// Sealed form of a single stored credential, or `None` if it is empty or
+/// already a reference.
+pub(crate) fn seal_plain(key: &Unlocked, value: &str) -> Option<String> {
+    if value.trim().is_empty() || crate::secret_ref::is_reference(value) {
+        return None;
+    }
+    key.seal(value)
+}
+
+/// Seals a provider's plain credentials: its settings and the extra keys of
+/// its key pool.
+pub(crate) fn seal_provider(key: &Unlocked, provider: &mut Provider) -> usize {
+    let mut sealed = seal_settings(key, &mut provider.settings_config);
+    if let Some(pool) = provider
+        .meta
+        .as_mut()
+        .and_then(|meta| meta.api_key_pool.as_mut())
+    {
+        for pooled in &mut pool.keys {
+            if let Some(value) = seal_plain(key, &pooled.key) {
+                pooled.key = value;
+                sealed += 1;
+            }
+        }
+    }
+    sealed
+}
+
+/// Seals new credentials on save while the vault is unlocked. Without a
+/// vault, or while it is locked, settings are stored as given and
+/// `vault migrate` seals them later.
+pub(crate) fn seal_on_save(db: &Database, settings: &mut Value) {
+    if !crate::secret_ref::has_plain_secrets(settings) {
+        return;
+    }
+    if let Some(key) = save_key(db) {
+        seal_settings(&key, settings);
+    }
+}
+
+/// [`seal_on_save`] for a provider, including its key pool.
+pub(crate) fn seal_provider_on_save(db: &Database, provider: &mut Provider) {
+    if !crate::secret_ref::provider_has_plain_secrets(provider) {
+        return;
+    }
+    if let Some(key) = save_key(db) {
+        seal_provider(&key, provider);
+    }
+}
+
+/// [`seal_on_save`] for the shared key of a universal provider.
+pub(crate) fn seal_universal_provider_on_save(db: &Database, provider: &mut UniversalProvider) {
+    if provider.api_key.trim().is_empty() || crate::secret_ref::is_reference(&provider.api_key) {
+        return;
+    }
+    if let Some(sealed) = save_key(db).and_then(|key| seal_plain(&key, &provider.api_key)) {
+        provider.api_key = sealed;
+    }
+}
+
+fn save_key(db: &Database) -> Option<Unlocked> {
+    if !matches!(load_meta(db), Ok(Some(_))) {
+        return None;
+    }
+    current_key().ok()
+}
+
+// ---------------------------------------------------------------------------
+// Daemon session agent
+// ---------------------------------------------------------------------------
+
+/// The daemon as session agent. After `vault unlock` it holds the data key
+/// and seals or opens single values for other cc-switch processes; the key
+/// itself only ever travels towards the daemon.
+///
+/// Threat model: the control socket is only reachable by the user who owns
+/// the daemon, and that user can already read the database and live configs.
+/// While the vault is unlocked any of their processes may ask for values to
+/// be opened, just as the proxy does; `vault lock` or the session timeout
+/// ends that. What the vault protects against is credentials leaking through
+/// the database, backups, sync artifacts and exports.
+#[cfg(unix)]
+pub(crate) mod agent {
+    use std::time::Duration;
+
+    use super::VaultKey;
+    use crate::daemon::ipc::client;
+    use crate::daemon::ipc::protocol::{Request, Response};
+    use crate::daemon::paths;
+    use crate::error::AppError;
+
+    /// The id of the key held by a running daemon, if any.
+    pub(crate) fn key_id() -> Option<String> {
+        match request(&Request::VaultStatus)? {
+            Response::VaultStatus { key_id } => key_id,
+            _ => None,
+        }
+    }
+
+    /// `plaintext` sealed by the daemon, or `None` if it holds no key.
+    pub(crate) fn seal(plaintext: &str) -> Option<String> {
+        let request = Request::VaultSeal {
+            plaintext: plaintext.to_string(),
+        };
+        match self::request(&request)? {
+            Response::VaultValue { value } => value,
+            _ => None,
+        }
+    }
+
+    /// A sealed value's payload opened by the daemon.
+    pub(crate) fn open(payload: &str) -> Result<String, AppError> {
+        let socket = paths::socket_path();
+        let request = Request::VaultOpen {
+            payload: payload.to_string(),
+        };
+        match client::round_trip(&socket, &request).map_err(agent_error)? {
+            Response::VaultValue { value: Some(value) } => Ok(value),
+            Response::VaultValue { value: None } => Err(super::locked()),
+            Response::Error { message } => Err(AppError::Message(message)),
+            other => Err(AppError::Message(format!(
+                "unexpected daemon response: {other:?}"
+            ))),
+        }
+    }
+
+    fn request(request: &Request) -> Option<Response> {
+        let socket = paths::socket_path();
+        if !socket.exists() {
+            return None;
+        }
+        client::round_trip(&socket, request).ok()
+    }
+
+    /// Hands `key` to the daemon (starting it if needed) for this session.
+    pub(crate) fn store_key(
+        key: &VaultKey,
+        ttl: Option<Duration>,
+        binary: impl FnOnce() -> Result<std::path::PathBuf, String>,
+    ) -> Result<(), AppError> {
+        let socket = paths::socket_path();
+        let mut stream =
+            client::connect_or_spawn(&socket, || binary().map_err(client::ClientError::NoDaemon))
+                .map_err(agent_error)?;
+        let request = Request::VaultUnlock {
+            key: key.to_base64().to_string(),
+            ttl_secs: ttl.map(|ttl| ttl.as_secs()),
+        };
+        expect_ok(client::exchange(&mut stream, &request).map_err(agent_error)?)
+    }
+
+    /// Asks a running daemon to forget the key. A missing daemon holds none.
+    pub(crate) fn clear_key() -> Result<(), AppError> {
+        let socket = paths::socket_path();
+        if !socket.exists() {
+            return Ok(());
+        }
+        match client::round_trip(&socket, &Request::VaultLock) {
+            Ok(response) => expect_ok(response),
+            Err(client::ClientError::Io(err))
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::NotFound
+                ) =>
+            {
+                Ok(())
+            }
+            Err(err) => Err(agent_error(err)),
+        }
+    }
+
+    fn expect_ok(response: Response) -> Result<(), AppError> {
+        match response {
+            Response::Ok => Ok(()),
+            Response::Error { message } => Err(AppError::Message(message)),
+            other => Err(AppError::Message(format!(
+                "unexpected daemon response: {other:?}"
+            ))),
+        }
+    }
+
+    fn agent_error(err: client::ClientError) -> AppError {
+        AppError::localized(
+            "vault.agent_unavailable",
+            format!("无法连接 cc-switch 守护进程: {err}"),
+            format!("Cannot reach the cc-switch daemon: {err}"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn sealed_values_round_trip_and_reject_other_keys() {
+        let (meta, key) = VaultMeta::create_with("correct horse", TEST_KDF).unwrap();
+        let sealed = key.seal("sk-live-secret");
+        assert!(sealed.starts_with("vault:v1:"));
+        assert!(!sealed.contains("sk-live-secret"));
+        assert_eq!(
+            key.open(sealed.strip_prefix(SEALED_PREFIX).unwrap())
+                .unwrap(),
+            "sk-live-secret"
+        );
+
+        let unlocked = meta.unlock("correct horse").unwrap();
+        assert_eq!(unlocked.key_id(), meta.key_id);
+        assert!(meta.unlock("wrong horse").is_err());
+
+        let (_, other) = VaultMeta::create_with("correct horse", TEST_KDF).unwrap();
+        assert!(other
+            .open(sealed.strip_prefix(SEALED_PREFIX).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn rekey_keeps_the_data_key() {
+        let (meta, key) = VaultMeta::create_with("old", TEST_KDF).unwrap();
+        let rekeyed = meta.rekey(&key, "new").unwrap();
+
+        assert!(rekeyed.unlock("old").is_err());
+        assert_eq!(rekeyed.unlock("new").unwrap().key_id(), meta.key_id);
+        assert!(rekeyed.rekeyed_at.is_some());
+    }
+
+    #[test]
+    fn sealing_skips_references_and_non_credentials() {
+        let (_, key) = VaultMeta::create_with("pw", TEST_KDF).unwrap();
+        let mut settings = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-plain",
+                "ANTHROPIC_API_KEY": "env:RELAY_KEY",
+                "ANTHROPIC_BASE_URL": "https://relay.example",
+                "ANTHROPIC_MODEL": ""
+            },
+            "headers": { "Authorization": "Bearer abc" }
+        });
+
+        let key = Unlocked::Local(key);
+        assert_eq!(seal_settings(&key, &mut settings), 2);
+        assert!(is_sealed(
+            settings["env"]["ANTHROPIC_AUTH_TOKEN"].as_str().unwrap()
+        ));
+        assert!(is_sealed(
+            settings["headers"]["Authorization"].as_str().unwrap()
+        ));
+        assert_eq!(settings["env"]["ANTHROPIC_API_KEY"], "env:RELAY_KEY");
+        assert_eq!(
+            settings["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example"
+        );
+        assert_eq!(seal_settings(&key, &mut settings), 0);
+    }
+}